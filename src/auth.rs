@@ -1,15 +1,39 @@
+use std::borrow::Cow;
 use std::str::FromStr;
 
+use alloy::hex::ToHexExt as _;
+use alloy::network::EthereumWallet;
 use alloy::providers::ProviderBuilder;
+use alloy::rpc::client::RpcClient;
+use alloy::signers::ledger::{HDPath, LedgerSigner};
+use alloy::signers::local::PrivateKeySigner;
+use alloy::sol_types::{Eip712Domain, SolStruct};
 use anyhow::{Context, Result};
+use async_trait::async_trait;
 use polymarket_client_sdk::auth::state::Authenticated;
-use polymarket_client_sdk::auth::{LocalSigner, Normal, Signer as _};
-use polymarket_client_sdk::clob::types::SignatureType;
-use polymarket_client_sdk::{POLYGON, clob};
+use polymarket_client_sdk::auth::{Credentials, LocalSigner, Normal, Signer, Uuid};
+use polymarket_client_sdk::clob::types::{Order, SignableOrder, SignatureType, SignedOrder};
+use polymarket_client_sdk::types::{Address, ChainId, Signature};
+use polymarket_client_sdk::types::{B256, U256};
+use polymarket_client_sdk::{POLYGON, clob, contract_config};
 
-use crate::config;
+use crate::clients;
+use crate::config::{self, SignerBackend};
+use crate::gas::{GasFiller, GasSettings};
+use crate::net;
+use crate::nonces::{RecordingWallet, TrackedNonces};
+use crate::session;
 
-pub const RPC_URL: &str = "https://polygon.drpc.org";
+const CLOB_AUTH_MESSAGE: &str = "This message attests that I control the given wallet";
+
+alloy::sol! {
+    struct ClobAuth {
+        address address;
+        string  timestamp;
+        uint256 nonce;
+        string  message;
+    }
+}
 
 fn parse_signature_type(s: &str) -> SignatureType {
     match s {
@@ -19,58 +43,346 @@ fn parse_signature_type(s: &str) -> SignatureType {
     }
 }
 
-pub fn resolve_signer(
-    private_key: Option<&str>,
-) -> Result<impl polymarket_client_sdk::auth::Signer> {
-    let (key, _) = config::resolve_key(private_key);
-    let key = key.ok_or_else(|| anyhow::anyhow!("{}", config::NO_WALLET_MSG))?;
-    LocalSigner::from_str(&key)
-        .context("Invalid private key")
-        .map(|s| s.with_chain_id(Some(POLYGON)))
+/// The wallet's signer: a software key or a Ledger device. A Ledger only
+/// signs EIP-712 typed data, never a bare hash, so orders and API key
+/// requests go through [`sign_order`] and [`request_api_key`] instead of the
+/// SDK helpers that sign precomputed hashes.
+pub enum WalletSigner {
+    Local(PrivateKeySigner),
+    Ledger(LedgerSigner),
+}
+
+#[async_trait]
+impl Signer for WalletSigner {
+    async fn sign_hash(&self, hash: &B256) -> alloy::signers::Result<Signature> {
+        match self {
+            Self::Local(s) => s.sign_hash(hash).await,
+            Self::Ledger(s) => s.sign_hash(hash).await,
+        }
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> alloy::signers::Result<Signature> {
+        match self {
+            Self::Local(s) => s.sign_message(message).await,
+            Self::Ledger(s) => s.sign_message(message).await,
+        }
+    }
+
+    async fn sign_typed_data<T: SolStruct + Send + Sync>(
+        &self,
+        payload: &T,
+        domain: &Eip712Domain,
+    ) -> alloy::signers::Result<Signature> {
+        match self {
+            Self::Local(s) => s.sign_typed_data(payload, domain).await,
+            Self::Ledger(s) => s.sign_typed_data(payload, domain).await,
+        }
+    }
+
+    fn address(&self) -> Address {
+        match self {
+            Self::Local(s) => Signer::address(s),
+            Self::Ledger(s) => Signer::address(s),
+        }
+    }
+
+    fn chain_id(&self) -> Option<ChainId> {
+        match self {
+            Self::Local(s) => Signer::chain_id(s),
+            Self::Ledger(s) => Signer::chain_id(s),
+        }
+    }
+
+    fn set_chain_id(&mut self, chain_id: Option<ChainId>) {
+        match self {
+            Self::Local(s) => Signer::set_chain_id(s, chain_id),
+            Self::Ledger(s) => Signer::set_chain_id(s, chain_id),
+        }
+    }
+}
+
+impl From<WalletSigner> for EthereumWallet {
+    fn from(signer: WalletSigner) -> Self {
+        match signer {
+            WalletSigner::Local(s) => s.into(),
+            WalletSigner::Ledger(s) => s.into(),
+        }
+    }
+}
+
+pub async fn resolve_signer(private_key: Option<&str>) -> Result<WalletSigner> {
+    let (backend, _) = config::resolve_signer_backend(private_key)?;
+    match backend {
+        SignerBackend::PrivateKey(key) => LocalSigner::from_str(&key)
+            .context("Invalid private key")
+            .map(|s| WalletSigner::Local(s.with_chain_id(Some(POLYGON)))),
+        SignerBackend::Ledger => {
+            // The HID transport panics instead of erroring when USB access is
+            // unavailable, so connect on a task to turn that into an error.
+            let connect = tokio::spawn(LedgerSigner::new(HDPath::LedgerLive(0), Some(POLYGON)));
+            let ledger = match connect.await {
+                Ok(result) => result.map_err(|e| anyhow::anyhow!("{e}")),
+                Err(_) => Err(anyhow::anyhow!("USB HID access is unavailable")),
+            };
+            ledger.map(WalletSigner::Ledger).map_err(|e| {
+                anyhow::anyhow!(
+                    "Failed to connect to Ledger: {e}. Is it unlocked with the Ethereum app open?"
+                )
+            })
+        }
+    }
 }
 
 pub async fn authenticated_clob_client(
     private_key: Option<&str>,
     signature_type_flag: Option<&str>,
 ) -> Result<clob::Client<Authenticated<Normal>>> {
-    let signer = resolve_signer(private_key)?;
+    let signer = resolve_signer(private_key).await?;
     authenticate_with_signer(&signer, signature_type_flag).await
 }
 
+/// API credentials saved in the active profile for `signer`, if any.
+fn saved_credentials(signer: Address) -> Result<Option<Credentials>> {
+    let Some(saved) = config::saved_api_credentials() else {
+        return Ok(None);
+    };
+    if !saved.address.eq_ignore_ascii_case(&signer.to_string()) {
+        return Ok(None);
+    }
+    let key = Uuid::parse_str(&saved.key).context("Invalid saved API key")?;
+    Ok(Some(Credentials::new(key, saved.secret, saved.passphrase)))
+}
+
 pub async fn authenticate_with_signer(
-    signer: &(impl polymarket_client_sdk::auth::Signer + Sync),
+    signer: &WalletSigner,
     signature_type_flag: Option<&str>,
 ) -> Result<clob::Client<Authenticated<Normal>>> {
     let sig_type = parse_signature_type(&config::resolve_signature_type(signature_type_flag));
 
-    clob::Client::default()
+    let mut builder = clients::clob()?
         .authentication_builder(signer)
-        .signature_type(sig_type)
+        .signature_type(sig_type);
+    let funder = config::resolve_funder()?;
+    if let Some(funder) = funder {
+        builder = builder.funder(funder);
+    }
+    let saved = saved_credentials(signer.address())?;
+    // Credentials saved in the profile take precedence over a session. With
+    // `--no-config` there is nowhere to keep one.
+    let session = match saved {
+        Some(_) => None,
+        None if config::no_config() => None,
+        None => session::load(signer)?.filter(|s| !s.is_expired()),
+    };
+    tracing::debug!(
+        signer = %signer.address(),
+        signature_type = ?sig_type,
+        funder = ?funder,
+        saved_credentials = saved.is_some(),
+        session = session.is_some(),
+        "authenticating with the CLOB"
+    );
+    let derived = saved.is_none() && session.is_none();
+    if let Some(credentials) = saved {
+        builder = builder.credentials(credentials);
+    } else if let Some(session) = session {
+        builder = builder.credentials(session.credentials);
+    } else if let WalletSigner::Ledger(_) = signer {
+        builder = builder.credentials(create_or_derive_api_key(signer, None).await?);
+    }
+    let client = builder
         .authenticate()
         .await
-        .context("Failed to authenticate with Polymarket CLOB")
+        .context("Failed to authenticate with Polymarket CLOB")?;
+    if derived && !config::no_config() {
+        let ttl = crate::commands::parse_duration(session::DEFAULT_TTL)?;
+        if let Err(e) = session::save(signer, client.credentials(), ttl) {
+            tracing::warn!(error = %format!("{e:#}"), "could not cache the CLOB session");
+        }
+    }
+    Ok(client)
 }
 
-pub async fn create_readonly_provider() -> Result<impl alloy::providers::Provider + Clone> {
-    ProviderBuilder::new()
-        .connect(RPC_URL)
+#[derive(Clone, Copy)]
+pub enum ApiKeyRequest {
+    Create,
+    Derive,
+}
+
+/// Creates or derives CLOB API credentials. For a Ledger, the L1 auth message
+/// is signed as typed data and the request is sent directly.
+pub async fn request_api_key(
+    signer: &WalletSigner,
+    nonce: Option<u32>,
+    request: ApiKeyRequest,
+) -> Result<Credentials> {
+    let client = clients::clob()?;
+    tracing::info!(
+        signer = %signer.address(),
+        nonce = ?nonce,
+        request = match request {
+            ApiKeyRequest::Create => "create",
+            ApiKeyRequest::Derive => "derive",
+        },
+        "requesting CLOB API credentials"
+    );
+    if let WalletSigner::Local(_) = signer {
+        return Ok(match request {
+            ApiKeyRequest::Create => net::once(client.create_api_key(signer, nonce)).await?,
+            ApiKeyRequest::Derive => net::retry(|| client.derive_api_key(signer, nonce)).await?,
+        });
+    }
+
+    let nonce = nonce.unwrap_or(0);
+    let timestamp = chrono::Utc::now().timestamp().to_string();
+    let auth = ClobAuth {
+        address: signer.address(),
+        timestamp: timestamp.clone(),
+        nonce: U256::from(nonce),
+        message: CLOB_AUTH_MESSAGE.to_string(),
+    };
+    let domain = Eip712Domain {
+        name: Some(Cow::Borrowed("ClobAuthDomain")),
+        version: Some(Cow::Borrowed("1")),
+        chain_id: Some(U256::from(POLYGON)),
+        ..Eip712Domain::default()
+    };
+    let signature = signer
+        .sign_typed_data(&auth, &domain)
         .await
-        .context("Failed to connect to Polygon RPC")
+        .context("Ledger declined to sign the API key request")?;
+
+    let (method, path) = match request {
+        ApiKeyRequest::Create => (reqwest::Method::POST, "auth/api-key"),
+        ApiKeyRequest::Derive => (reqwest::Method::GET, "auth/derive-api-key"),
+    };
+    let response = net::http_client()?
+        .request(method, client.host().join(path)?)
+        .header("POLY_ADDRESS", signer.address().encode_hex_with_prefix())
+        .header("POLY_NONCE", nonce.to_string())
+        .header("POLY_SIGNATURE", signature.to_string())
+        .header("POLY_TIMESTAMP", timestamp)
+        .send()
+        .await?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!("API key request failed ({status}): {body}");
+    }
+    Ok(response.json().await?)
+}
+
+/// Creates an API key, falling back to deriving the existing one, as the SDK
+/// does during authentication.
+pub async fn create_or_derive_api_key(
+    signer: &WalletSigner,
+    nonce: Option<u32>,
+) -> Result<Credentials> {
+    match request_api_key(signer, nonce, ApiKeyRequest::Create).await {
+        Ok(credentials) => Ok(credentials),
+        Err(_) => request_api_key(signer, nonce, ApiKeyRequest::Derive).await,
+    }
+}
+
+/// Signs an order for the CLOB. Equivalent to `clob::Client::sign`, except a
+/// Ledger gets the order as typed data so it can show what it is signing.
+pub async fn sign_order(
+    client: &clob::Client<Authenticated<Normal>>,
+    signer: &WalletSigner,
+    order: SignableOrder,
+) -> Result<SignedOrder> {
+    tracing::info!(
+        signer = %signer.address(),
+        maker = %order.order.maker,
+        token_id = %order.order.tokenId,
+        side = order.order.side,
+        maker_amount = %order.order.makerAmount,
+        taker_amount = %order.order.takerAmount,
+        order_type = %order.order_type,
+        ledger = matches!(signer, WalletSigner::Ledger(_)),
+        "signing order"
+    );
+    if let WalletSigner::Local(_) = signer {
+        return Ok(client.sign(signer, order).await?);
+    }
+    let neg_risk = net::retry(|| client.neg_risk(order.order.tokenId))
+        .await?
+        .neg_risk;
+    let signature = signer
+        .sign_typed_data(&order.order, &exchange_domain(neg_risk)?)
+        .await
+        .context("Ledger declined to sign the order")?;
+    Ok(SignedOrder::builder()
+        .order(order.order)
+        .signature(signature)
+        .order_type(order.order_type)
+        .owner(client.credentials().key())
+        .maybe_post_only(order.post_only)
+        .build())
+}
+
+fn exchange_domain(neg_risk: bool) -> Result<Eip712Domain> {
+    let exchange = contract_config(POLYGON, neg_risk)
+        .context("No exchange contract configured for Polygon")?
+        .exchange;
+    Ok(Eip712Domain {
+        name: Some("Polymarket CTF Exchange".into()),
+        version: Some("1".into()),
+        chain_id: Some(U256::from(POLYGON)),
+        verifying_contract: Some(exchange),
+        ..Eip712Domain::default()
+    })
+}
+
+/// EIP-712 hash of an order, as signed by `clob::Client::sign`. The CLOB uses
+/// it as the order ID.
+pub fn order_hash(order: &Order, neg_risk: bool) -> Result<B256> {
+    Ok(order.eip712_signing_hash(&exchange_domain(neg_risk)?))
+}
+
+pub async fn create_readonly_provider() -> Result<impl alloy::providers::Provider + Clone> {
+    Ok(ProviderBuilder::new().connect_client(net::rpc_client(&config::rpc_url())?))
 }
 
 pub async fn create_provider(
     private_key: Option<&str>,
+    gas: GasSettings,
 ) -> Result<impl alloy::providers::Provider + Clone> {
-    let (key, _) = config::resolve_key(private_key);
-    let key = key.ok_or_else(|| anyhow::anyhow!("{}", config::NO_WALLET_MSG))?;
-    let signer = LocalSigner::from_str(&key)
-        .context("Invalid private key")?
-        .with_chain_id(Some(POLYGON));
+    provider_with_signer(resolve_signer(private_key).await?, gas).await
+}
+
+/// A wallet provider for an already resolved signer.
+pub async fn provider_with_signer(
+    signer: WalletSigner,
+    gas: GasSettings,
+) -> Result<impl alloy::providers::Provider + Clone> {
+    tracing::debug!(
+        signer = %signer.address(),
+        ledger = matches!(signer, WalletSigner::Ledger(_)),
+        "transactions will be signed by this wallet"
+    );
+    Ok(wallet_provider(
+        signer,
+        net::rpc_client(&config::rpc_url())?,
+        gas,
+    ))
+}
+
+/// A provider that signs with `signer` and sends through `client`, paying
+/// gas as `gas` says and journaling what it signs.
+pub fn wallet_provider(
+    signer: WalletSigner,
+    client: RpcClient,
+    gas: GasSettings,
+) -> impl alloy::providers::Provider + Clone {
+    tracing::debug!(?gas, "gas settings for transactions");
     ProviderBuilder::new()
-        .wallet(signer)
-        .connect(RPC_URL)
-        .await
-        .context("Failed to connect to Polygon RPC with wallet")
+        .disable_recommended_fillers()
+        .filler(GasFiller(gas))
+        .with_nonce_management(TrackedNonces::new(gas.replace))
+        .fetch_chain_id()
+        .wallet(RecordingWallet(EthereumWallet::from(signer)))
+        .connect_client(client)
 }
 
 #[cfg(test)]
@@ -95,6 +407,42 @@ mod tests {
         assert_eq!(parse_signature_type("eoa"), SignatureType::Eoa);
     }
 
+    #[test]
+    fn order_hash_depends_on_exchange() {
+        let order = Order::default();
+        let regular = order_hash(&order, false).unwrap();
+        let neg_risk = order_hash(&order, true).unwrap();
+        assert_ne!(regular, neg_risk);
+        assert_eq!(regular, order_hash(&order, false).unwrap());
+    }
+
+    #[test]
+    fn clob_auth_matches_sdk_type() {
+        assert_eq!(
+            ClobAuth::eip712_encode_type(),
+            "ClobAuth(address address,string timestamp,uint256 nonce,string message)"
+        );
+    }
+
+    #[tokio::test]
+    async fn typed_data_signature_matches_hash_signature() {
+        let signer = WalletSigner::Local(
+            LocalSigner::from_str(
+                "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+            )
+            .unwrap()
+            .with_chain_id(Some(POLYGON)),
+        );
+        let order = Order::default();
+        let domain = exchange_domain(false).unwrap();
+        let typed = signer.sign_typed_data(&order, &domain).await.unwrap();
+        let hashed = signer
+            .sign_hash(&order_hash(&order, false).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(typed, hashed);
+    }
+
     #[test]
     fn parse_signature_type_unknown_defaults_to_eoa() {
         assert_eq!(parse_signature_type("unknown"), SignatureType::Eoa);
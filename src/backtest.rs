@@ -0,0 +1,422 @@
+//! Backtesting: replays a price series through a strategy and keeps score.
+//!
+//! A strategy sees one price at a time and says whether to buy or sell. The
+//! simulation is all in or all out: a buy spends the strategy's stake (or
+//! what cash is left) at the price, a sell closes the whole position. Fills
+//! take the recorded price as-is, so there is no slippage, unless the series
+//! has the book's best bid and ask (a replayed `record` file does): then buys
+//! pay the ask and sells get the bid.
+
+use std::collections::VecDeque;
+
+use anyhow::{Context, Result};
+use polymarket_client_sdk::types::Decimal;
+use toml_edit::{DocumentMut, Item};
+
+/// One observed price of the outcome being traded.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PricePoint {
+    pub timestamp: i64,
+    pub price: Decimal,
+    /// Best bid and ask at the time, when the series has the book.
+    pub bid: Option<Decimal>,
+    pub ask: Option<Decimal>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Signal {
+    Buy,
+    Sell,
+}
+
+/// A trading rule, fed the series one price at a time.
+pub trait Strategy {
+    /// What to do at `price`, given whether a position is open.
+    fn on_price(&mut self, price: Decimal, holding: bool) -> Option<Signal>;
+}
+
+/// Buys at or below one price and sells at or above another.
+pub struct Threshold {
+    pub buy_below: Decimal,
+    pub sell_above: Decimal,
+}
+
+impl Strategy for Threshold {
+    fn on_price(&mut self, price: Decimal, holding: bool) -> Option<Signal> {
+        match holding {
+            false if price <= self.buy_below => Some(Signal::Buy),
+            true if price >= self.sell_above => Some(Signal::Sell),
+            _ => None,
+        }
+    }
+}
+
+/// Buys when the price falls `band` below its moving average over the last
+/// `window` prices, and sells once it is back at the average.
+pub struct MeanReversion {
+    pub window: usize,
+    pub band: Decimal,
+    prices: VecDeque<Decimal>,
+}
+
+impl MeanReversion {
+    pub fn new(window: usize, band: Decimal) -> Self {
+        Self {
+            window,
+            band,
+            prices: VecDeque::with_capacity(window),
+        }
+    }
+}
+
+impl Strategy for MeanReversion {
+    fn on_price(&mut self, price: Decimal, holding: bool) -> Option<Signal> {
+        let signal = (self.prices.len() == self.window).then(|| {
+            let mean = self.prices.iter().sum::<Decimal>() / Decimal::from(self.window);
+            match holding {
+                false if price <= mean - self.band => Some(Signal::Buy),
+                true if price >= mean => Some(Signal::Sell),
+                _ => None,
+            }
+        });
+        if self.prices.len() == self.window {
+            self.prices.pop_front();
+        }
+        self.prices.push_back(price);
+        signal.flatten()
+    }
+}
+
+/// A strategy file: which rule to run and how much it trades.
+pub struct StrategyFile {
+    pub name: String,
+    pub strategy: Box<dyn Strategy>,
+    /// USDC spent on each entry.
+    pub stake: Decimal,
+    /// Fee charged on each fill, as a fraction of its notional.
+    pub fee_rate: Decimal,
+}
+
+/// Reads a TOML strategy file, e.g.
+///
+/// ```toml
+/// type = "mean-reversion"
+/// window = 20
+/// band = 0.05
+/// stake = 100
+/// ```
+pub fn parse_strategy(text: &str) -> Result<StrategyFile> {
+    let doc: DocumentMut = text.parse().context("Invalid strategy TOML")?;
+    let kind = doc
+        .get("type")
+        .and_then(Item::as_str)
+        .context("Strategy is missing `type`: threshold or mean-reversion")?;
+    let decimal = |key: &str| -> Result<Option<Decimal>> {
+        let Some(item) = doc.get(key) else {
+            return Ok(None);
+        };
+        let value = match (item.as_integer(), item.as_float()) {
+            (Some(i), _) => Decimal::from(i),
+            (_, Some(f)) => Decimal::try_from(f).with_context(|| format!("Invalid `{key}`"))?,
+            _ => anyhow::bail!("`{key}` must be a number"),
+        };
+        Ok(Some(value))
+    };
+    let required = |key: &str| -> Result<Decimal> {
+        decimal(key)?.with_context(|| format!("The {kind} strategy needs `{key}`"))
+    };
+
+    let strategy: Box<dyn Strategy> = match kind {
+        "threshold" => {
+            let buy_below = required("buy_below")?;
+            let sell_above = required("sell_above")?;
+            anyhow::ensure!(
+                buy_below < sell_above,
+                "`buy_below` must be under `sell_above`"
+            );
+            Box::new(Threshold {
+                buy_below,
+                sell_above,
+            })
+        }
+        "mean-reversion" => {
+            let window = doc
+                .get("window")
+                .and_then(Item::as_integer)
+                .and_then(|w| usize::try_from(w).ok())
+                .filter(|w| *w > 0)
+                .context("The mean-reversion strategy needs a positive whole `window`")?;
+            let band = required("band")?;
+            anyhow::ensure!(band >= Decimal::ZERO, "`band` must not be negative");
+            Box::new(MeanReversion::new(window, band))
+        }
+        other => anyhow::bail!("Unknown strategy type '{other}': use threshold or mean-reversion"),
+    };
+    let stake = required("stake")?;
+    anyhow::ensure!(stake > Decimal::ZERO, "`stake` must be positive");
+    let fee_rate = decimal("fee_rate")?.unwrap_or_default();
+    anyhow::ensure!(
+        (Decimal::ZERO..Decimal::ONE).contains(&fee_rate),
+        "`fee_rate` must be at least 0 and below 1"
+    );
+    Ok(StrategyFile {
+        name: doc
+            .get("name")
+            .and_then(Item::as_str)
+            .unwrap_or(kind)
+            .to_string(),
+        strategy,
+        stake,
+        fee_rate,
+    })
+}
+
+/// A simulated fill.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fill {
+    pub timestamp: i64,
+    pub signal: Signal,
+    pub price: Decimal,
+    pub shares: Decimal,
+    /// USDC paid (buy) or received (sell), fee included.
+    pub usdc: Decimal,
+    pub fee: Decimal,
+}
+
+pub struct Report {
+    pub name: String,
+    pub points: usize,
+    pub first: Option<i64>,
+    pub last: Option<i64>,
+    pub start_cash: Decimal,
+    /// Cash plus the open position at the last price.
+    pub final_equity: Decimal,
+    pub max_drawdown: Decimal,
+    /// The largest drawdown as a fraction of the peak it fell from.
+    pub max_drawdown_pct: Decimal,
+    /// Profit of each closed position, in order.
+    pub round_trips: Vec<Decimal>,
+    pub open_shares: Decimal,
+    pub fees: Decimal,
+    pub fills: Vec<Fill>,
+}
+
+impl Report {
+    pub fn pnl(&self) -> Decimal {
+        self.final_equity - self.start_cash
+    }
+
+    pub fn return_pct(&self) -> Decimal {
+        self.pnl() / self.start_cash
+    }
+
+    pub fn wins(&self) -> usize {
+        self.round_trips
+            .iter()
+            .filter(|p| **p > Decimal::ZERO)
+            .count()
+    }
+
+    pub fn win_rate(&self) -> Option<Decimal> {
+        (!self.round_trips.is_empty())
+            .then(|| Decimal::from(self.wins()) / Decimal::from(self.round_trips.len()))
+    }
+}
+
+/// Runs `file`'s strategy over `points`, oldest first, starting with `cash`.
+pub fn run(file: StrategyFile, points: &[PricePoint], cash: Decimal) -> Report {
+    let StrategyFile {
+        name,
+        mut strategy,
+        stake,
+        fee_rate,
+    } = file;
+    let start_cash = cash;
+    let mut cash = cash;
+    let mut shares = Decimal::ZERO;
+    let mut cost = Decimal::ZERO;
+    let mut fills = Vec::new();
+    let mut round_trips = Vec::new();
+    let mut fees = Decimal::ZERO;
+    let mut peak = cash;
+    let mut max_drawdown = Decimal::ZERO;
+    let mut max_drawdown_pct = Decimal::ZERO;
+
+    for point in points {
+        let price = point.price;
+        match strategy.on_price(price, !shares.is_zero()) {
+            Some(Signal::Buy) if point.ask.unwrap_or(price) > Decimal::ZERO => {
+                let price = point.ask.unwrap_or(price);
+                let spend = stake.min(cash);
+                if spend > Decimal::ZERO {
+                    let fee = spend * fee_rate;
+                    shares = (spend - fee) / price;
+                    cash -= spend;
+                    cost = spend;
+                    fees += fee;
+                    fills.push(Fill {
+                        timestamp: point.timestamp,
+                        signal: Signal::Buy,
+                        price,
+                        shares,
+                        usdc: spend,
+                        fee,
+                    });
+                }
+            }
+            Some(Signal::Sell) if !shares.is_zero() => {
+                let price = point.bid.unwrap_or(price);
+                let gross = shares * price;
+                let fee = gross * fee_rate;
+                cash += gross - fee;
+                fees += fee;
+                round_trips.push(gross - fee - cost);
+                fills.push(Fill {
+                    timestamp: point.timestamp,
+                    signal: Signal::Sell,
+                    price,
+                    shares,
+                    usdc: gross - fee,
+                    fee,
+                });
+                shares = Decimal::ZERO;
+            }
+            _ => {}
+        }
+
+        let equity = cash + shares * price;
+        peak = peak.max(equity);
+        if peak - equity > max_drawdown {
+            max_drawdown = peak - equity;
+            max_drawdown_pct = max_drawdown / peak;
+        }
+    }
+
+    let last_price = points.last().map_or(Decimal::ZERO, |p| p.price);
+    Report {
+        name,
+        points: points.len(),
+        first: points.first().map(|p| p.timestamp),
+        last: points.last().map(|p| p.timestamp),
+        start_cash,
+        final_equity: cash + shares * last_price,
+        max_drawdown,
+        max_drawdown_pct,
+        round_trips,
+        open_shares: shares,
+        fees,
+        fills,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn series(prices: &[Decimal]) -> Vec<PricePoint> {
+        prices
+            .iter()
+            .enumerate()
+            .map(|(i, p)| PricePoint {
+                timestamp: i as i64,
+                price: *p,
+                bid: None,
+                ask: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parse_strategy_reads_built_ins() {
+        let file = parse_strategy(
+            "name = \"dips\"\ntype = \"threshold\"\nbuy_below = 0.35\nsell_above = 0.5\nstake = 100\n",
+        )
+        .unwrap();
+        assert_eq!(file.name, "dips");
+        assert_eq!(file.stake, dec!(100));
+        assert_eq!(file.fee_rate, Decimal::ZERO);
+
+        let file = parse_strategy(
+            "type = \"mean-reversion\"\nwindow = 3\nband = 0.05\nstake = 50\nfee_rate = 0.01\n",
+        )
+        .unwrap();
+        assert_eq!(file.name, "mean-reversion");
+        assert_eq!(file.fee_rate, dec!(0.01));
+    }
+
+    #[test]
+    fn parse_strategy_rejects_bad_files() {
+        let err = |text: &str| parse_strategy(text).err().unwrap().to_string();
+        assert!(err("stake = 1").contains("type"));
+        assert!(err("type = \"momentum\"\nstake = 1").contains("Unknown strategy"));
+        assert!(err("type = \"threshold\"\nbuy_below = 0.3\nstake = 1").contains("sell_above"));
+        assert!(
+            err("type = \"threshold\"\nbuy_below = 0.6\nsell_above = 0.5\nstake = 1")
+                .contains("under")
+        );
+        assert!(
+            err("type = \"mean-reversion\"\nwindow = 0\nband = 0.1\nstake = 1").contains("window")
+        );
+    }
+
+    #[test]
+    fn threshold_round_trip_scores_pnl_and_drawdown() {
+        let file = parse_strategy(
+            "type = \"threshold\"\nbuy_below = 0.4\nsell_above = 0.6\nstake = 100\n",
+        )
+        .unwrap();
+        let points = series(&[dec!(0.5), dec!(0.4), dec!(0.2), dec!(0.6), dec!(0.5)]);
+        let report = run(file, &points, dec!(1000));
+
+        assert_eq!(report.fills.len(), 2);
+        assert_eq!(report.fills[0].shares, dec!(250));
+        assert_eq!(report.round_trips, [dec!(50)]);
+        assert_eq!(report.pnl(), dec!(50));
+        assert_eq!(report.win_rate(), Some(Decimal::ONE));
+        // 250 shares fell from 0.4 to 0.2.
+        assert_eq!(report.max_drawdown, dec!(50));
+        assert_eq!(report.max_drawdown_pct, dec!(0.05));
+        assert!(report.open_shares.is_zero());
+    }
+
+    #[test]
+    fn mean_reversion_waits_for_a_full_window() {
+        let mut strategy = MeanReversion::new(3, dec!(0.05));
+        let signals: Vec<_> = [dec!(0.5), dec!(0.5), dec!(0.5), dec!(0.44)]
+            .into_iter()
+            .map(|p| strategy.on_price(p, false))
+            .collect();
+        assert_eq!(signals, [None, None, None, Some(Signal::Buy)]);
+        // The window is now 0.5, 0.5, 0.44: back at its mean is a sell.
+        assert_eq!(strategy.on_price(dec!(0.48), true), Some(Signal::Sell));
+    }
+
+    #[test]
+    fn fills_cross_the_spread_when_the_book_is_known() {
+        let file = parse_strategy(
+            "type = \"threshold\"\nbuy_below = 0.4\nsell_above = 0.6\nstake = 100\n",
+        )
+        .unwrap();
+        let mut points = series(&[dec!(0.4), dec!(0.6)]);
+        points[0].ask = Some(dec!(0.5));
+        points[1].bid = Some(dec!(0.55));
+        let report = run(file, &points, dec!(1000));
+        assert_eq!(report.fills[0].price, dec!(0.5));
+        assert_eq!(report.fills[0].shares, dec!(200));
+        assert_eq!(report.fills[1].price, dec!(0.55));
+        assert_eq!(report.round_trips, [dec!(10)]);
+    }
+
+    #[test]
+    fn fees_come_out_of_both_sides() {
+        let file = parse_strategy(
+            "type = \"threshold\"\nbuy_below = 0.5\nsell_above = 0.51\nstake = 100\nfee_rate = 0.01\n",
+        )
+        .unwrap();
+        let report = run(file, &series(&[dec!(0.5), dec!(0.51)]), dec!(100));
+        assert_eq!(report.fills[0].shares, dec!(198));
+        assert_eq!(report.fees, dec!(1) + dec!(1.0098));
+        assert_eq!(report.final_equity, dec!(99.9702));
+    }
+}
@@ -0,0 +1,199 @@
+//! Disk cache for market metadata (`cache/` in the config dir), so repeated
+//! invocations don't refetch markets and order parameters that rarely
+//! change. Each entry is a JSON file stamped with when it was fetched; stale
+//! or unreadable entries are refetched, and a cache that can't be written is
+//! skipped rather than failing the command.
+
+use std::fs;
+use std::future::Future;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+
+const CACHE_DIR: &str = "cache";
+
+/// Gamma markets, keyed by ID, slug, condition ID and token ID.
+pub const MARKETS: Kind = Kind {
+    dir: "markets",
+    ttl: Duration::minutes(5),
+};
+
+/// Order parameters (tick size, minimum size, neg risk), keyed by token ID.
+pub const ORDER_PARAMS: Kind = Kind {
+    dir: "order-params",
+    ttl: Duration::hours(1),
+};
+
+/// The CLOB API's version (see `compat.rs`), under a single key.
+pub const COMPATIBILITY: Kind = Kind {
+    dir: "compatibility",
+    ttl: Duration::days(1),
+};
+
+/// One kind of cached value: where it is kept and how long it stays fresh.
+#[derive(Clone, Copy, Debug)]
+pub struct Kind {
+    dir: &'static str,
+    ttl: Duration,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry<T> {
+    fetched_at: DateTime<Utc>,
+    value: T,
+}
+
+pub(crate) fn dir() -> Result<PathBuf> {
+    Ok(config::config_dir()?.join(CACHE_DIR))
+}
+
+/// Keys come from user input (slugs), so anything that isn't safe in a file
+/// name is replaced.
+fn file_name(key: &str) -> String {
+    let key: String = key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{key}.json")
+}
+
+fn path(kind: Kind, key: &str) -> Result<PathBuf> {
+    Ok(dir()?.join(kind.dir).join(file_name(key)))
+}
+
+fn is_fresh(fetched_at: DateTime<Utc>, ttl: Duration, now: DateTime<Utc>) -> bool {
+    fetched_at <= now && now - fetched_at < ttl
+}
+
+/// The cached value for `key`, if there is one younger than the kind's TTL.
+pub fn get<T: DeserializeOwned>(kind: Kind, key: &str) -> Option<T> {
+    let data = fs::read_to_string(path(kind, key).ok()?).ok()?;
+    let entry: Entry<T> = serde_json::from_str(&data).ok()?;
+    is_fresh(entry.fetched_at, kind.ttl, Utc::now()).then_some(entry.value)
+}
+
+/// Stores `value` under each of `keys`.
+pub fn put<T: Serialize>(kind: Kind, keys: &[String], value: &T) {
+    let entry = Entry {
+        fetched_at: Utc::now(),
+        value,
+    };
+    let write = || -> Result<()> {
+        let json = serde_json::to_string(&entry)?;
+        let dir = dir()?;
+        config::ensure_dir(&dir)?;
+        config::ensure_dir(&dir.join(kind.dir))?;
+        for key in keys {
+            fs::write(path(kind, key)?, &json)?;
+        }
+        Ok(())
+    };
+    // Only ever a speed-up; the command has the value either way.
+    let _ = write();
+}
+
+/// The cached value for `key`, or the result of `fetch`, which is then
+/// cached under `key`.
+pub async fn get_or_fetch<T, Fut>(kind: Kind, key: &str, fetch: Fut) -> Result<T>
+where
+    T: Serialize + DeserializeOwned,
+    Fut: Future<Output = Result<T>>,
+{
+    if let Some(value) = get(kind, key) {
+        return Ok(value);
+    }
+    let value = fetch.await?;
+    put(kind, &[key.to_string()], &value);
+    Ok(value)
+}
+
+/// Deletes the entries past their kind's TTL, and any that can't be read.
+/// Returns how many were removed and the bytes they took.
+pub fn prune() -> Result<(usize, u64)> {
+    #[derive(Deserialize)]
+    struct Stamp {
+        fetched_at: DateTime<Utc>,
+    }
+    let dir = dir()?;
+    let now = Utc::now();
+    let (mut removed, mut bytes) = (0, 0);
+    for kind in [MARKETS, ORDER_PARAMS, COMPATIBILITY] {
+        let Ok(entries) = fs::read_dir(dir.join(kind.dir)) else {
+            continue;
+        };
+        for entry in entries {
+            let path = entry?.path();
+            let data = fs::read(&path).unwrap_or_default();
+            let fresh = serde_json::from_slice::<Stamp>(&data)
+                .is_ok_and(|s| is_fresh(s.fetched_at, kind.ttl, now));
+            if !fresh {
+                fs::remove_file(&path).context("Failed to remove cache entry")?;
+                removed += 1;
+                bytes += data.len() as u64;
+            }
+        }
+    }
+    Ok((removed, bytes))
+}
+
+/// Deletes every cached entry. Returns how many were removed.
+pub fn clear() -> Result<usize> {
+    let dir = dir()?;
+    if !dir.exists() {
+        return Ok(0);
+    }
+    let mut removed = 0;
+    for kind in fs::read_dir(&dir).context("Failed to read cache directory")? {
+        let kind = kind?.path();
+        if kind.is_dir() {
+            removed += fs::read_dir(&kind)?.count();
+        }
+    }
+    fs::remove_dir_all(&dir).context("Failed to remove cache directory")?;
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_name_is_safe_for_any_key() {
+        assert_eq!(file_name("will-trump-win"), "will-trump-win.json");
+        assert_eq!(file_name("0xABc"), "0xabc.json");
+        assert_eq!(file_name("../etc/passwd"), "___etc_passwd.json");
+    }
+
+    #[test]
+    fn entries_expire_after_ttl() {
+        let now = Utc::now();
+        let ttl = Duration::minutes(5);
+        assert!(is_fresh(now - Duration::minutes(4), ttl, now));
+        assert!(!is_fresh(now - Duration::minutes(5), ttl, now));
+        // A clock that went backwards shouldn't keep an entry forever.
+        assert!(!is_fresh(now + Duration::minutes(1), ttl, now));
+    }
+
+    #[test]
+    fn entry_round_trips() {
+        let entry = Entry {
+            fetched_at: Utc::now(),
+            value: vec!["a".to_string()],
+        };
+        let json = serde_json::to_string(&entry).unwrap();
+        let back: Entry<Vec<String>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.fetched_at, entry.fetched_at);
+        assert_eq!(back.value, entry.value);
+    }
+}
@@ -0,0 +1,22 @@
+//! SDK clients pointed at the configured endpoints (see `config::endpoints`).
+
+use anyhow::{Context, Result};
+use polymarket_client_sdk::{clob, data, gamma};
+
+use crate::config;
+
+pub fn clob() -> Result<clob::Client> {
+    let url = config::clob_url();
+    clob::Client::new(&url, clob::Config::default())
+        .with_context(|| format!("Invalid CLOB URL: {url}"))
+}
+
+pub fn gamma() -> Result<gamma::Client> {
+    let url = config::gamma_url();
+    gamma::Client::new(&url).with_context(|| format!("Invalid Gamma API URL: {url}"))
+}
+
+pub fn data() -> Result<data::Client> {
+    let url = config::data_url();
+    data::Client::new(&url).with_context(|| format!("Invalid data API URL: {url}"))
+}
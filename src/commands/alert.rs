@@ -0,0 +1,298 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use polymarket_client_sdk::clob::types::request::MidpointRequest;
+use polymarket_client_sdk::gamma;
+use polymarket_client_sdk::types::{Decimal, U256};
+
+use super::clob::parse_token_id;
+use super::markets::fetch_market;
+use crate::clients;
+use crate::config::{self, Alert, AlertDirection, Notifier, NotifyEvent};
+use crate::metrics;
+use crate::net;
+use crate::notify::{self, Notification};
+use crate::output::OutputFormat;
+use crate::output::alert::{alert_event, print_alert_added, print_alert_event, print_alerts};
+use crate::procs;
+
+#[derive(Args)]
+pub struct AlertArgs {
+    #[command(subcommand)]
+    pub command: AlertCommand,
+}
+
+#[derive(Subcommand)]
+pub enum AlertCommand {
+    /// Alert when a market's price crosses a threshold
+    Add {
+        /// Market ID (numeric), slug, condition ID (0x...), or CLOB token ID
+        #[arg(long)]
+        market: String,
+        /// Fire when the price rises to or above this (0-1)
+        #[arg(long, conflicts_with = "below", required_unless_present = "below")]
+        above: Option<Decimal>,
+        /// Fire when the price falls to or below this (0-1)
+        #[arg(long)]
+        below: Option<Decimal>,
+        /// Outcome to watch (default: the first, usually Yes)
+        #[arg(long)]
+        outcome: Option<String>,
+    },
+    /// List saved alerts
+    #[command(visible_alias = "ls")]
+    List,
+    /// Remove an alert
+    #[command(visible_alias = "rm")]
+    Remove {
+        /// Alert ID as shown by `alert list`
+        id: u32,
+    },
+    /// Check prices until interrupted and notify when an alert fires (also
+    /// sent to the profile's notifiers)
+    Run {
+        /// Seconds between price checks
+        #[arg(long, default_value = "30")]
+        interval: u64,
+        /// Also show a desktop notification
+        #[arg(long)]
+        desktop: bool,
+        /// Also POST each alert as JSON to this URL
+        #[arg(long)]
+        webhook: Option<String>,
+        /// Check once and exit (for cron)
+        #[arg(long)]
+        once: bool,
+        /// Serve Prometheus metrics at http://ADDR/metrics, e.g.
+        /// 127.0.0.1:9464
+        #[arg(long, value_name = "ADDR", conflicts_with = "once")]
+        metrics_addr: Option<SocketAddr>,
+    },
+}
+
+pub async fn execute(client: &gamma::Client, args: AlertArgs, output: OutputFormat) -> Result<()> {
+    match args.command {
+        AlertCommand::Add {
+            market,
+            above,
+            below,
+            outcome,
+        } => {
+            let (direction, price) = match (above, below) {
+                (Some(p), _) => (AlertDirection::Above, p),
+                (None, Some(p)) => (AlertDirection::Below, p),
+                (None, None) => anyhow::bail!("Pass --above or --below"),
+            };
+            anyhow::ensure!(
+                price > Decimal::ZERO && price < Decimal::ONE,
+                "Alert price must be between 0 and 1, got {price}"
+            );
+
+            let m = fetch_market(client, &market).await?;
+            let names = m.outcomes.unwrap_or_default();
+            let tokens = m.clob_token_ids.unwrap_or_default();
+            let index = match &outcome {
+                Some(o) => names
+                    .iter()
+                    .position(|n| n.eq_ignore_ascii_case(o))
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("Unknown outcome {o}; choose from {}", names.join(", "))
+                    })?,
+                None => 0,
+            };
+            let (Some(name), Some(token)) = (names.get(index), tokens.get(index)) else {
+                anyhow::bail!("Market {market} has no tradable outcomes");
+            };
+
+            let mut alerts = config::load_alerts()?;
+            let alert = Alert {
+                id: alerts.iter().map(|a| a.id).max().unwrap_or(0) + 1,
+                market_id: m.id,
+                question: m.question.unwrap_or_default(),
+                outcome: name.clone(),
+                token_id: token.to_string(),
+                direction,
+                price,
+            };
+            alerts.push(alert.clone());
+            config::save_alerts(&alerts)?;
+            print_alert_added(&alert, &output)?;
+        }
+        AlertCommand::List => print_alerts(&config::load_alerts()?, &output)?,
+        AlertCommand::Remove { id } => {
+            let mut alerts = config::load_alerts()?;
+            let before = alerts.len();
+            alerts.retain(|a| a.id != id);
+            anyhow::ensure!(alerts.len() < before, "No alert with ID {id}");
+            config::save_alerts(&alerts)?;
+            println!("Removed alert #{id}");
+        }
+        AlertCommand::Run {
+            interval,
+            desktop,
+            webhook,
+            once,
+            metrics_addr,
+        } => {
+            anyhow::ensure!(interval > 0, "--interval must be at least 1 second");
+            let notifiers = notify::with_flags(desktop, webhook)?;
+            anyhow::ensure!(
+                !config::load_alerts()?.is_empty(),
+                "No alerts. Add one with `polymarket alert add --market <id> --above <price>`."
+            );
+            if let Some(addr) = metrics_addr {
+                metrics::serve(addr).await?;
+            }
+            let _running = procs::register("alert run");
+            run(&notifiers, Duration::from_secs(interval), once, output).await?;
+        }
+    }
+    Ok(())
+}
+
+fn notification(alert: &Alert, price: Decimal) -> Notification {
+    Notification {
+        event: NotifyEvent::Alert,
+        title: alert.question.clone(),
+        body: format!(
+            "{} {price} ({} {})",
+            alert.outcome, alert.direction, alert.price
+        ),
+        payload: alert_event(alert, price),
+    }
+}
+
+/// Fires alerts as prices come in. An alert fires when its price crosses
+/// the threshold and re-arms once the price moves back, so a price sitting
+/// past it doesn't fire every poll.
+#[derive(Default)]
+pub(crate) struct AlertChecker {
+    armed: HashMap<u32, bool>,
+}
+
+impl AlertChecker {
+    /// Prints and publishes every alert that fires at `prices`.
+    pub async fn check(
+        &mut self,
+        alerts: &[Alert],
+        prices: &HashMap<String, Decimal>,
+        notifiers: &[Notifier],
+        output: &OutputFormat,
+    ) -> Result<()> {
+        for alert in alerts {
+            let Some(&price) = prices.get(&alert.token_id) else {
+                continue;
+            };
+            if should_fire(self.armed.entry(alert.id).or_insert(true), alert, price) {
+                metrics::alert_fired();
+                print_alert_event(alert, price, output)?;
+                notify::publish(notifiers, &notification(alert, price)).await;
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn run(
+    notifiers: &[Notifier],
+    interval: Duration,
+    once: bool,
+    output: OutputFormat,
+) -> Result<()> {
+    let client = clients::clob()?;
+    let mut checker = AlertChecker::default();
+
+    loop {
+        // Re-read each round so `alert add`/`remove` apply without a restart.
+        let alerts = config::load_alerts()?;
+        let tokens: Vec<&str> = alerts.iter().map(|a| a.token_id.as_str()).collect();
+        match fetch_midpoints(&client, &tokens).await {
+            Ok(prices) => {
+                checker.check(&alerts, &prices, notifiers, &output).await?;
+            }
+            Err(e) if once => return Err(e),
+            Err(e) => eprintln!("Price check failed: {e:#}"),
+        }
+        if once {
+            return Ok(());
+        }
+
+        tokio::select! {
+            () = tokio::time::sleep(interval) => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
+}
+
+/// Midpoints of `token_ids`, keyed by token ID as a decimal string.
+pub(crate) async fn fetch_midpoints(
+    client: &polymarket_client_sdk::clob::Client,
+    token_ids: &[&str],
+) -> Result<HashMap<String, Decimal>> {
+    let mut tokens: Vec<U256> = token_ids
+        .iter()
+        .map(|t| parse_token_id(t))
+        .collect::<Result<_>>()?;
+    tokens.sort_unstable();
+    tokens.dedup();
+    if tokens.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let requests: Vec<_> = tokens
+        .iter()
+        .map(|t| MidpointRequest::builder().token_id(*t).build())
+        .collect();
+    let midpoints = net::retry(|| client.midpoints(&requests)).await?.midpoints;
+    Ok(midpoints
+        .into_iter()
+        .map(|(token, mid)| (token.to_string(), mid))
+        .collect())
+}
+
+/// Whether `alert` fires at `price`, updating its armed state.
+fn should_fire(armed: &mut bool, alert: &Alert, price: Decimal) -> bool {
+    let crossed = alert.direction.crossed(price, alert.price);
+    let fire = *armed && crossed;
+    *armed = !crossed;
+    fire
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(direction: AlertDirection, price: &str) -> Alert {
+        Alert {
+            id: 1,
+            market_id: "12".into(),
+            question: "Will it rain?".into(),
+            outcome: "Yes".into(),
+            token_id: "123".into(),
+            direction,
+            price: price.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn should_fire_once_per_crossing() {
+        let a = alert(AlertDirection::Above, "0.70");
+        let mut armed = true;
+        let prices = ["0.65", "0.71", "0.72", "0.69", "0.70"];
+        let fired: Vec<bool> = prices
+            .iter()
+            .map(|p| should_fire(&mut armed, &a, p.parse().unwrap()))
+            .collect();
+        assert_eq!(fired, [false, true, false, false, true]);
+    }
+
+    #[test]
+    fn should_fire_immediately_when_already_past() {
+        let a = alert(AlertDirection::Below, "0.30");
+        let mut armed = true;
+        assert!(should_fire(&mut armed, &a, "0.25".parse().unwrap()));
+        assert!(!armed);
+    }
+}
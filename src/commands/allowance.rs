@@ -0,0 +1,39 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use super::approve;
+use crate::gas::GasFlags;
+use crate::output::OutputFormat;
+
+#[derive(Args)]
+pub struct AllowanceArgs {
+    #[command(subcommand)]
+    pub command: AllowanceCommand,
+}
+
+#[derive(Subcommand)]
+pub enum AllowanceCommand {
+    /// Show USDC allowances and CTF approvals for the exchange contracts
+    Show {
+        /// Wallet address to check (defaults to configured wallet)
+        address: Option<String>,
+    },
+    /// Approve all required contracts for trading (sends on-chain transactions)
+    Set {
+        #[command(flatten)]
+        gas: GasFlags,
+    },
+}
+
+pub async fn execute(
+    args: AllowanceArgs,
+    output: OutputFormat,
+    private_key: Option<&str>,
+) -> Result<()> {
+    match args.command {
+        AllowanceCommand::Show { address } => {
+            approve::check(address.as_deref(), private_key, output).await
+        }
+        AllowanceCommand::Set { gas } => approve::set(private_key, &gas, output).await,
+    }
+}
@@ -0,0 +1,111 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use polymarket_client_sdk::auth::{Credentials, ExposeSecret as _, Signer as _};
+use polymarket_client_sdk::types::Address;
+
+use crate::auth;
+use crate::config::{self, ApiCredentials};
+use crate::net;
+use crate::output::OutputFormat;
+use crate::output::clob::{print_api_credentials, print_api_keys, print_delete_api_key};
+use crate::session;
+
+#[derive(Args)]
+pub struct ApikeyArgs {
+    #[command(subcommand)]
+    pub command: ApikeyCommand,
+}
+
+#[derive(Subcommand)]
+pub enum ApikeyCommand {
+    /// Create a new API key for the wallet (fails if one exists for the nonce)
+    Create {
+        /// Key nonce; use different nonces to provision several keys
+        #[arg(long)]
+        nonce: Option<u32>,
+        /// Save the credentials in the active wallet profile
+        #[arg(long)]
+        save: bool,
+    },
+    /// Derive the existing API key for the wallet and nonce
+    Derive {
+        /// Key nonce the key was created with
+        #[arg(long)]
+        nonce: Option<u32>,
+        /// Save the credentials in the active wallet profile
+        #[arg(long)]
+        save: bool,
+    },
+    /// List the wallet's API keys (authenticated)
+    List,
+    /// Delete the API key used to authenticate (authenticated)
+    Delete,
+}
+
+pub async fn execute(
+    args: ApikeyArgs,
+    output: &OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    match args.command {
+        ApikeyCommand::Create { nonce, save } => {
+            let signer = auth::resolve_signer(private_key).await?;
+            let credentials =
+                auth::request_api_key(&signer, nonce, auth::ApiKeyRequest::Create).await?;
+            if save {
+                save_credentials(signer.address(), &credentials)?;
+            }
+            print_api_credentials(&credentials, save, output)?;
+        }
+
+        ApikeyCommand::Derive { nonce, save } => {
+            let signer = auth::resolve_signer(private_key).await?;
+            let credentials =
+                auth::request_api_key(&signer, nonce, auth::ApiKeyRequest::Derive).await?;
+            if save {
+                save_credentials(signer.address(), &credentials)?;
+            }
+            print_api_credentials(&credentials, save, output)?;
+        }
+
+        ApikeyCommand::List => {
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let result = net::retry(|| client.api_keys()).await?;
+            print_api_keys(&result, output)?;
+        }
+
+        ApikeyCommand::Delete => {
+            let signer = auth::resolve_signer(private_key).await?;
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+            let deleted = client.credentials().key().to_string();
+            let result = net::once(client.delete_api_key()).await?;
+            // Saved or cached credentials for the deleted key would make
+            // every later authenticated call fail.
+            if config::saved_api_credentials().is_some_and(|c| c.key.eq_ignore_ascii_case(&deleted))
+            {
+                config::save_api_credentials(None)?;
+            }
+            if session::load(&signer)?.is_some_and(|s| {
+                s.credentials
+                    .key()
+                    .to_string()
+                    .eq_ignore_ascii_case(&deleted)
+            }) {
+                session::remove()?;
+            }
+            print_delete_api_key(&result, output)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn save_credentials(address: Address, credentials: &Credentials) -> Result<()> {
+    config::save_api_credentials(Some(ApiCredentials {
+        address: address.to_string(),
+        key: credentials.key().to_string(),
+        secret: credentials.secret().expose_secret().to_string(),
+        passphrase: credentials.passphrase().expose_secret().to_string(),
+    }))
+}
@@ -9,22 +9,26 @@ use polymarket_client_sdk::types::{Address, address};
 use polymarket_client_sdk::{POLYGON, contract_config};
 
 use crate::auth;
+use crate::gas::GasFlags;
 use crate::output::OutputFormat;
 use crate::output::approve::{ApprovalStatus, print_approval_status, print_tx_result};
 
-const USDC_ADDRESS: Address = address!("0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174");
+pub(crate) const USDC_ADDRESS: Address = address!("0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174");
 
 sol! {
     #[sol(rpc)]
     interface IERC20 {
         function approve(address spender, uint256 value) external returns (bool);
         function allowance(address owner, address spender) external view returns (uint256);
+        function balanceOf(address account) external view returns (uint256);
+        function transfer(address to, uint256 value) external returns (bool);
     }
 
     #[sol(rpc)]
     interface IERC1155 {
         function setApprovalForAll(address operator, bool approved) external;
         function isApprovedForAll(address account, address operator) external view returns (bool);
+        function balanceOf(address account, uint256 id) external view returns (uint256);
     }
 }
 
@@ -42,7 +46,10 @@ pub enum ApproveCommand {
         address: Option<String>,
     },
     /// Approve all required contracts for trading (sends on-chain transactions)
-    Set,
+    Set {
+        #[command(flatten)]
+        gas: GasFlags,
+    },
 }
 
 struct ApprovalTarget {
@@ -83,11 +90,11 @@ pub async fn execute(
 ) -> Result<()> {
     match args.command {
         ApproveCommand::Check { address } => check(address.as_deref(), private_key, output).await,
-        ApproveCommand::Set => set(private_key, output).await,
+        ApproveCommand::Set { gas } => set(private_key, &gas, output).await,
     }
 }
 
-async fn check(
+pub(crate) async fn check(
     address_arg: Option<&str>,
     private_key: Option<&str>,
     output: OutputFormat,
@@ -95,10 +102,16 @@ async fn check(
     let owner: Address = if let Some(addr) = address_arg {
         super::parse_address(addr)?
     } else {
-        let signer = auth::resolve_signer(private_key)?;
+        let signer = auth::resolve_signer(private_key).await?;
         polymarket_client_sdk::auth::Signer::address(&signer)
     };
 
+    print_approval_status(&approval_statuses(owner).await?, &output)
+}
+
+/// The USDC allowance and CTF approval `owner` has given each exchange
+/// contract.
+pub(crate) async fn approval_statuses(owner: Address) -> Result<Vec<ApprovalStatus>> {
     let provider = auth::create_readonly_provider().await?;
     let config = contract_config(POLYGON, false).context("No contract config for Polygon")?;
 
@@ -131,11 +144,15 @@ async fn check(
         });
     }
 
-    print_approval_status(&statuses, &output)
+    Ok(statuses)
 }
 
-async fn set(private_key: Option<&str>, output: OutputFormat) -> Result<()> {
-    let provider = auth::create_provider(private_key).await?;
+pub(crate) async fn set(
+    private_key: Option<&str>,
+    gas: &GasFlags,
+    output: OutputFormat,
+) -> Result<()> {
+    let provider = auth::create_provider(private_key, gas.settings()?).await?;
     let config = contract_config(POLYGON, false).context("No contract config for Polygon")?;
 
     let usdc = IERC20::new(USDC_ADDRESS, provider.clone());
@@ -144,7 +161,7 @@ async fn set(private_key: Option<&str>, output: OutputFormat) -> Result<()> {
     let targets = approval_targets()?;
     let total = targets.len() * 2;
 
-    if matches!(output, OutputFormat::Table) {
+    if !matches!(output, OutputFormat::Json) {
         println!("Approving contracts...\n");
     }
 
@@ -167,7 +184,9 @@ async fn set(private_key: Option<&str>, output: OutputFormat) -> Result<()> {
             ))?;
 
         match output {
-            OutputFormat::Table => print_tx_result(step, total, &label, tx_hash),
+            OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                print_tx_result(step, total, &label, tx_hash)
+            }
             OutputFormat::Json => results.push(serde_json::json!({
                 "step": step,
                 "type": "erc20",
@@ -191,7 +210,9 @@ async fn set(private_key: Option<&str>, output: OutputFormat) -> Result<()> {
             ))?;
 
         match output {
-            OutputFormat::Table => print_tx_result(step, total, &label, tx_hash),
+            OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                print_tx_result(step, total, &label, tx_hash)
+            }
             OutputFormat::Json => results.push(serde_json::json!({
                 "step": step,
                 "type": "erc1155",
@@ -202,11 +223,11 @@ async fn set(private_key: Option<&str>, output: OutputFormat) -> Result<()> {
     }
 
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             println!("\nAll contracts approved. You're ready to trade.");
         }
         OutputFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(&results)?);
+            crate::output::print_json(&results)?;
         }
     }
 
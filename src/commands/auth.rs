@@ -0,0 +1,104 @@
+//! `auth`: the cached CLOB session (see `session.rs`). Authenticated
+//! commands start one on their own; these manage it explicitly.
+
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use polymarket_client_sdk::auth::Signer as _;
+
+use crate::auth::{self, WalletSigner};
+use crate::config;
+use crate::errors;
+use crate::output::auth::{SessionState, SessionStatus, print_session_status};
+use crate::output::{OutputFormat, print_json_line};
+use crate::session::{self, Session};
+
+#[derive(Args)]
+pub struct AuthArgs {
+    #[command(subcommand)]
+    pub command: AuthCommand,
+}
+
+#[derive(Subcommand)]
+pub enum AuthCommand {
+    /// Derive the wallet's API credentials and cache them, replacing any
+    /// session
+    Login {
+        /// How long the session lasts (e.g. 12h, 7d)
+        #[arg(long, default_value = session::DEFAULT_TTL)]
+        ttl: String,
+    },
+    /// Delete the cached session
+    Logout,
+    /// Show whether a session is cached and when it expires
+    Status,
+}
+
+pub async fn execute(
+    args: AuthArgs,
+    output: &OutputFormat,
+    private_key: Option<&str>,
+) -> Result<()> {
+    match args.command {
+        AuthCommand::Login { ttl } => {
+            let ttl = super::parse_duration(&ttl)?;
+            anyhow::ensure!(
+                ttl > chrono::Duration::zero(),
+                errors::validation("--ttl must be positive")
+            );
+            let signer = auth::resolve_signer(private_key).await?;
+            anyhow::ensure!(
+                !matches!(signer, WalletSigner::Ledger(_)),
+                errors::validation(
+                    "A Ledger can't have a cached session. Save its API key in the profile with `polymarket apikey derive --save`."
+                )
+            );
+            let credentials = auth::create_or_derive_api_key(&signer, None).await?;
+            let session =
+                session::save(&signer, &credentials, ttl)?.context("Failed to start a session")?;
+            print_session_status(&status(&signer, Some(session)), output)
+        }
+        AuthCommand::Logout => {
+            let removed = session::remove()?;
+            match output {
+                OutputFormat::Json => print_json_line(serde_json::json!({"logged_out": removed})),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    println!(
+                        "{}",
+                        if removed {
+                            "Logged out."
+                        } else {
+                            "No session to log out of."
+                        }
+                    );
+                }
+            }
+            Ok(())
+        }
+        AuthCommand::Status => {
+            let signer = auth::resolve_signer(private_key).await?;
+            let session = session::load(&signer)?;
+            print_session_status(&status(&signer, session), output)
+        }
+    }
+}
+
+fn status(signer: &WalletSigner, session: Option<Session>) -> SessionStatus {
+    let state = match (&session, signer) {
+        (_, WalletSigner::Ledger(_)) => SessionState::Unsupported,
+        (None, _) => SessionState::None,
+        (Some(s), _) if s.is_expired() => SessionState::Expired,
+        (Some(_), _) => SessionState::Active,
+    };
+    SessionStatus {
+        profile: config::active_profile(),
+        wallet: signer.address(),
+        state,
+        api_key: session.as_ref().map(|s| s.credentials.key().to_string()),
+        created: session.as_ref().map(|s| s.created),
+        expires: session.as_ref().map(|s| s.expires),
+        saved_key: config::saved_api_credentials().is_some_and(|c| {
+            c.address
+                .eq_ignore_ascii_case(&signer.address().to_string())
+        }),
+    }
+}
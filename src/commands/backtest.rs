@@ -0,0 +1,190 @@
+//! `backtest`: replay a saved price series through a strategy file.
+//!
+//! The series is a trade tape from `trades market` or a price history from
+//! `clob price-history -o json`, as CSV or JSON. Prices are of the market's
+//! first outcome: a tape's trades in the other outcome count at 1 − price.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Args;
+use polymarket_client_sdk::types::Decimal;
+use serde_json::Value;
+
+use crate::backtest::{self, PricePoint, StrategyFile};
+use crate::output::OutputFormat;
+use crate::output::backtest::print_backtest;
+
+#[derive(Args)]
+pub struct BacktestArgs {
+    /// Strategy file (TOML), e.g. `type = "threshold"`
+    #[arg(long)]
+    pub strategy: PathBuf,
+    /// Price series: a .csv or .json trade tape or price history
+    #[arg(long)]
+    pub data: PathBuf,
+    /// Starting cash, in USDC
+    #[arg(long, default_value = "1000")]
+    pub cash: Decimal,
+}
+
+pub fn execute(args: BacktestArgs, output: &OutputFormat) -> Result<()> {
+    anyhow::ensure!(args.cash > Decimal::ZERO, "--cash must be positive");
+    let strategy = load_strategy(&args.strategy)?;
+    let points = load_series(&args.data)?;
+    print_backtest(&backtest::run(strategy, &points, args.cash), output)
+}
+
+pub(crate) fn load_strategy(path: &Path) -> Result<StrategyFile> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    backtest::parse_strategy(&text)
+        .with_context(|| format!("Invalid strategy file {}", path.display()))
+}
+
+/// Reads a `.json` array of objects or a `.csv` file with a header row. Each
+/// needs `timestamp` and `price`; an `outcome_index` of 1 flips the price.
+fn load_series(path: &Path) -> Result<Vec<PricePoint>> {
+    let data = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+    let rows = match extension.as_deref() {
+        Some("json") => serde_json::from_str::<Vec<Value>>(&data)
+            .with_context(|| format!("Invalid JSON in {}", path.display()))?,
+        Some("csv") => parse_csv(&data)?,
+        _ => anyhow::bail!(
+            "Unsupported data file {}: use .json or .csv",
+            path.display()
+        ),
+    };
+    let mut points = rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| point(row).map_err(|e| anyhow::anyhow!("Row {}: {e:#}", i + 1)))
+        .collect::<Result<Vec<_>>>()?;
+    anyhow::ensure!(!points.is_empty(), "No prices in {}", path.display());
+    points.sort_by_key(|p| p.timestamp);
+    Ok(points)
+}
+
+fn point(row: &Value) -> Result<PricePoint> {
+    let field = |key: &str| -> Option<String> {
+        match &row[key] {
+            Value::String(s) if !s.is_empty() => Some(s.clone()),
+            Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        }
+    };
+    let timestamp = field("timestamp")
+        .context("missing timestamp")?
+        .parse()
+        .context("timestamp must be unix seconds")?;
+    let price: Decimal = field("price")
+        .context("missing price")?
+        .parse()
+        .context("invalid price")?;
+    let price = match field("outcome_index").as_deref() {
+        Some("1") => Decimal::ONE - price,
+        _ => price,
+    };
+    Ok(PricePoint {
+        timestamp,
+        price,
+        bid: None,
+        ask: None,
+    })
+}
+
+/// CSV rows as flat objects keyed by the header, honouring quoted fields
+/// (a tape's market titles often contain commas).
+fn parse_csv(data: &str) -> Result<Vec<Value>> {
+    let mut lines = data.lines().filter(|l| !l.trim().is_empty());
+    let header = lines.next().context("Data CSV is empty")?;
+    let columns: Vec<String> = split_csv_line(header)
+        .iter()
+        .map(|c| c.trim().to_ascii_lowercase())
+        .collect();
+    for required in ["timestamp", "price"] {
+        anyhow::ensure!(
+            columns.iter().any(|c| c == required),
+            "Data CSV is missing the {required} column"
+        );
+    }
+    Ok(lines
+        .map(|line| {
+            let object: serde_json::Map<String, Value> = columns
+                .iter()
+                .cloned()
+                .zip(split_csv_line(line).into_iter().map(Value::String))
+                .collect();
+            Value::Object(object)
+        })
+        .collect())
+}
+
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        let field = fields.last_mut().expect("starts with one field");
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            c => field.push(c),
+        }
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn split_csv_line_honours_quotes() {
+        assert_eq!(
+            split_csv_line(r#"Will it rain, or not?,"say ""hi""",0.5"#),
+            ["Will it rain", " or not?", r#"say "hi""#, "0.5"]
+        );
+        assert_eq!(
+            split_csv_line(r#""Will it rain, or not?",0.5"#),
+            ["Will it rain, or not?", "0.5"]
+        );
+    }
+
+    #[test]
+    fn tape_rows_price_the_first_outcome() {
+        let rows = parse_csv(
+            "title,outcome_index,price,timestamp\n\"Rain, today?\",0,0.40,100\n\"Rain, today?\",1,0.55,200\n",
+        )
+        .unwrap();
+        let points: Vec<_> = rows.iter().map(|r| point(r).unwrap()).collect();
+        assert_eq!(points[0].price, dec!(0.40));
+        assert_eq!(points[1].price, dec!(0.45));
+        assert_eq!(points[1].timestamp, 200);
+    }
+
+    #[test]
+    fn price_history_json_rows() {
+        let row = serde_json::json!({"timestamp": 1_700_000_000, "price": "0.61"});
+        assert_eq!(
+            point(&row).unwrap(),
+            PricePoint {
+                timestamp: 1_700_000_000,
+                price: dec!(0.61),
+                bid: None,
+                ask: None,
+            }
+        );
+        assert!(point(&serde_json::json!({"price": "0.61"})).is_err());
+    }
+}
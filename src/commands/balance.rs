@@ -0,0 +1,124 @@
+use alloy::primitives::U256;
+use alloy::primitives::utils::format_units;
+use alloy::providers::Provider;
+use anyhow::{Context, Result};
+use clap::Args;
+use polymarket_client_sdk::types::{Address, Decimal};
+
+use super::approve::{IERC20, USDC_ADDRESS};
+use super::wallet::{resolve_address, resolve_trading_address};
+use crate::auth;
+use crate::config;
+use crate::errors;
+use crate::output::OutputFormat;
+use crate::output::balance::{WalletBalance, print_balances};
+
+const USDC_DECIMALS: u8 = 6;
+pub(crate) const POL_DECIMALS: u8 = 18;
+
+#[derive(Args)]
+pub struct BalanceArgs {
+    /// Wallet address to check (defaults to the configured wallet and its
+    /// proxy or Safe wallet)
+    pub address: Option<String>,
+}
+
+pub(crate) fn to_decimal(value: U256, decimals: u8) -> Result<Decimal> {
+    format_units(value, decimals)?
+        .parse::<Decimal>()
+        .map(|d| d.normalize())
+        .context("Balance out of range")
+}
+
+/// Addresses to report when none is given: the signer, which pays gas, and
+/// the trading wallet that holds USDC when it differs.
+async fn default_addresses(
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<Vec<(&'static str, Address)>> {
+    let (signer, _) = resolve_address(private_key).await?;
+    let signer = signer.ok_or_else(|| errors::auth(config::NO_WALLET_MSG))?;
+    let trading = resolve_trading_address(private_key, signature_type).await?;
+    if trading == signer {
+        Ok(vec![("Wallet", signer)])
+    } else {
+        Ok(vec![("Signer", signer), ("Trading wallet", trading)])
+    }
+}
+
+/// USDC the trading wallet holds.
+pub(crate) async fn trading_usdc(
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<Decimal> {
+    let owner = resolve_trading_address(private_key, signature_type).await?;
+    let provider = auth::create_readonly_provider().await?;
+    let raw = IERC20::new(USDC_ADDRESS, provider)
+        .balanceOf(owner)
+        .call()
+        .await
+        .context("Failed to fetch USDC balance")?;
+    to_decimal(raw, USDC_DECIMALS)
+}
+
+pub async fn execute(
+    args: BalanceArgs,
+    output: &OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    let addresses = match args.address {
+        Some(address) => vec![("Address", super::parse_address(&address)?)],
+        None => default_addresses(private_key, signature_type).await?,
+    };
+
+    let provider = auth::create_readonly_provider().await?;
+    let usdc = IERC20::new(USDC_ADDRESS, provider.clone());
+
+    let mut balances = Vec::with_capacity(addresses.len());
+    for (label, address) in addresses {
+        let (usdc_raw, pol_raw) = tokio::try_join!(
+            async {
+                usdc.balanceOf(address)
+                    .call()
+                    .await
+                    .context("Failed to fetch USDC balance")
+            },
+            async {
+                provider
+                    .get_balance(address)
+                    .await
+                    .context("Failed to fetch POL balance")
+            },
+        )?;
+        balances.push(WalletBalance {
+            label,
+            address,
+            usdc: to_decimal(usdc_raw, USDC_DECIMALS)?,
+            pol: to_decimal(pol_raw, POL_DECIMALS)?,
+        });
+    }
+
+    print_balances(&balances, output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_decimal_scales_by_decimals() {
+        let usdc = to_decimal(U256::from(12_345_000u64), USDC_DECIMALS).unwrap();
+        assert_eq!(usdc.to_string(), "12.345");
+        let pol = to_decimal(U256::from(10u64).pow(U256::from(18)), POL_DECIMALS).unwrap();
+        assert_eq!(pol.to_string(), "1");
+    }
+
+    #[test]
+    fn to_decimal_zero() {
+        assert_eq!(
+            to_decimal(U256::ZERO, USDC_DECIMALS).unwrap(),
+            Decimal::ZERO
+        );
+    }
+}
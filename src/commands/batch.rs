@@ -0,0 +1,271 @@
+//! `clob batch`: limit orders read from a JSON or CSV file, signed one by one
+//! (a Ledger confirms each in turn) and submitted concurrently.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use futures_util::StreamExt as _;
+use polymarket_client_sdk::auth::Normal;
+use polymarket_client_sdk::auth::state::Authenticated;
+use polymarket_client_sdk::clob;
+use polymarket_client_sdk::clob::types::{OrderType, Side, SignedOrder};
+use polymarket_client_sdk::types::{B256, Decimal, U256};
+use serde::Deserialize;
+
+use super::clob::{CliOrderType, CliSide, order_params, parse_token_id};
+use crate::auth::{self, WalletSigner};
+use crate::net;
+use crate::output::clob::BatchOrderResult;
+use crate::risk::Proposed;
+
+/// One order as written in the batch file. Prices and sizes may be JSON
+/// strings or numbers.
+#[derive(Deserialize)]
+struct RawOrder {
+    token: String,
+    side: String,
+    price: Decimal,
+    size: Decimal,
+    #[serde(default)]
+    order_type: Option<String>,
+    #[serde(default)]
+    post_only: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct BatchOrder {
+    pub token: U256,
+    pub side: Side,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub order_type: OrderType,
+    pub post_only: bool,
+}
+
+impl TryFrom<RawOrder> for BatchOrder {
+    type Error = anyhow::Error;
+
+    fn try_from(raw: RawOrder) -> Result<Self> {
+        let side = CliSide::from_str(&raw.side, true)
+            .map_err(|_| anyhow::anyhow!("Invalid side: {} (expected buy or sell)", raw.side))?;
+        let order_type = match raw.order_type.as_deref().filter(|t| !t.is_empty()) {
+            Some(t) => CliOrderType::from_str(t, true)
+                .map_err(|_| anyhow::anyhow!("Invalid order type: {t}"))?,
+            None => CliOrderType::Gtc,
+        };
+        Ok(Self {
+            token: parse_token_id(&raw.token)?,
+            side: side.into(),
+            price: raw.price,
+            size: raw.size,
+            order_type: order_type.into(),
+            post_only: raw.post_only,
+        })
+    }
+}
+
+/// Reads orders from a `.json` file (an array of objects) or a `.csv` file
+/// with a header row. Columns: `token`, `side`, `price`, `size`, and
+/// optionally `order_type` (default GTC) and `post_only`.
+pub(crate) fn load_orders(path: &Path) -> Result<Vec<BatchOrder>> {
+    let data = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+    let orders = match extension.as_deref() {
+        Some("json") => parse_json(&data)?,
+        Some("csv") => parse_csv(&data)?,
+        _ => anyhow::bail!(
+            "Unsupported batch file {}: use .json or .csv",
+            path.display()
+        ),
+    };
+    anyhow::ensure!(!orders.is_empty(), "No orders in {}", path.display());
+    Ok(orders)
+}
+
+fn parse_json(data: &str) -> Result<Vec<BatchOrder>> {
+    let raw: Vec<RawOrder> = serde_json::from_str(data).context("Invalid batch JSON")?;
+    raw.into_iter()
+        .enumerate()
+        .map(|(i, o)| {
+            BatchOrder::try_from(o).map_err(|e| anyhow::anyhow!("Order {}: {e:#}", i + 1))
+        })
+        .collect()
+}
+
+fn parse_csv(data: &str) -> Result<Vec<BatchOrder>> {
+    let mut lines = data
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty());
+    let (_, header) = lines.next().context("Batch CSV is empty")?;
+    let columns: Vec<String> = header
+        .split(',')
+        .map(|c| c.trim().to_ascii_lowercase())
+        .collect();
+    for required in ["token", "side", "price", "size"] {
+        anyhow::ensure!(
+            columns.iter().any(|c| c == required),
+            "Batch CSV is missing the {required} column"
+        );
+    }
+
+    lines
+        .map(|(n, line)| {
+            parse_csv_row(&columns, line).map_err(|e| anyhow::anyhow!("Line {}: {e:#}", n + 1))
+        })
+        .collect()
+}
+
+fn parse_csv_row(columns: &[String], line: &str) -> Result<BatchOrder> {
+    let object: serde_json::Map<String, serde_json::Value> = columns
+        .iter()
+        .zip(line.split(',').map(str::trim))
+        .map(|(column, field)| {
+            let value = if column == "post_only" {
+                serde_json::Value::Bool(matches!(field, "true" | "1" | "yes"))
+            } else {
+                serde_json::Value::String(field.to_string())
+            };
+            (column.clone(), value)
+        })
+        .collect();
+    let raw: RawOrder = serde_json::from_value(object.into())?;
+    BatchOrder::try_from(raw)
+}
+
+/// The orders as `risk::check` takes them.
+pub(crate) fn proposed(orders: &[BatchOrder]) -> Vec<Proposed> {
+    orders
+        .iter()
+        .map(|o| Proposed::limit(o.token, o.side, o.price, o.size))
+        .collect()
+}
+
+/// Builds and signs every order, then posts the signed ones with at most
+/// `concurrency` requests in flight. With `dry_run`, nothing is posted.
+pub(crate) async fn submit(
+    client: &clob::Client<Authenticated<Normal>>,
+    signer: &WalletSigner,
+    orders: Vec<BatchOrder>,
+    concurrency: usize,
+    dry_run: bool,
+) -> Vec<BatchOrderResult> {
+    let mut results = Vec::with_capacity(orders.len());
+    let mut signed = Vec::new();
+    for (i, order) in orders.into_iter().enumerate() {
+        let mut result = BatchOrderResult::new(i + 1, &order);
+        match sign(client, signer, order).await {
+            Ok((hash, s)) => {
+                result.order_hash = Some(hash);
+                signed.push((i, s));
+            }
+            Err(e) => result.error = Some(format!("{e:#}")),
+        }
+        results.push(result);
+    }
+    if dry_run {
+        return results;
+    }
+
+    let mut posted = futures_util::stream::iter(signed)
+        .map(|(i, s)| async move { (i, net::once(client.post_order(s)).await) })
+        .buffer_unordered(concurrency.max(1));
+    while let Some((i, response)) = posted.next().await {
+        match response {
+            Ok(r) => results[i].set_response(&r),
+            Err(e) => results[i].error = Some(e.to_string()),
+        }
+    }
+    results
+}
+
+async fn sign(
+    client: &clob::Client<Authenticated<Normal>>,
+    signer: &WalletSigner,
+    order: BatchOrder,
+) -> Result<(B256, SignedOrder)> {
+    let params = order_params(client, order.token).await?;
+    params.check_limit_order(order.price, order.size)?;
+    let signable = client
+        .limit_order()
+        .token_id(order.token)
+        .side(order.side)
+        .price(order.price)
+        .size(order.size)
+        .order_type(order.order_type)
+        .post_only(order.post_only)
+        .build()
+        .await?;
+    let signed = auth::sign_order(client, signer, signable).await?;
+    let hash = auth::order_hash(&signed.order, params.neg_risk)?;
+    Ok((hash, signed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_json_accepts_numbers_and_strings() {
+        let orders = parse_json(
+            r#"[
+                {"token": "1", "side": "buy", "price": 0.4, "size": "100"},
+                {"token": "2", "side": "SELL", "price": "0.6", "size": 50,
+                 "order_type": "fok", "post_only": false}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(orders.len(), 2);
+        assert_eq!(orders[0].side, Side::Buy);
+        assert_eq!(orders[0].price, "0.4".parse().unwrap());
+        assert_eq!(orders[0].order_type, OrderType::GTC);
+        assert_eq!(orders[1].side, Side::Sell);
+        assert_eq!(orders[1].size, Decimal::from(50));
+        assert_eq!(orders[1].order_type, OrderType::FOK);
+    }
+
+    #[test]
+    fn parse_json_names_the_bad_order() {
+        let err = parse_json(
+            r#"[{"token": "1", "side": "buy", "price": 0.4, "size": 1},
+                {"token": "1", "side": "hold", "price": 0.4, "size": 1}]"#,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().starts_with("Order 2: Invalid side: hold"),
+            "got: {err}"
+        );
+    }
+
+    #[test]
+    fn parse_csv_reads_optional_columns() {
+        let orders = parse_csv(
+            "token,side,price,size,post_only\n\
+             1,buy,0.30,50,true\n\
+             \n\
+             1,buy,0.31,50,\n",
+        )
+        .unwrap();
+        assert_eq!(orders.len(), 2);
+        assert!(orders[0].post_only);
+        assert!(!orders[1].post_only);
+        assert_eq!(orders[1].price, "0.31".parse().unwrap());
+    }
+
+    #[test]
+    fn parse_csv_reports_line_numbers() {
+        let err = parse_csv("token,side,price,size\n1,buy,0.3,50\nabc,buy,0.3,50\n").unwrap_err();
+        assert!(err.to_string().starts_with("Line 3: "), "got: {err}");
+    }
+
+    #[test]
+    fn parse_csv_requires_columns() {
+        let err = parse_csv("token,side,size\n1,buy,50\n").unwrap_err();
+        assert!(err.to_string().contains("price"), "got: {err}");
+    }
+}
@@ -0,0 +1,242 @@
+//! `book <token-id>` shows a token's order book, as `clob book` does.
+//!
+//! `book stats`: how deep a token's order book is near the midpoint, which
+//! way it leans, and what taking a given size from it would cost.
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use polymarket_client_sdk::clob::types::Side;
+use polymarket_client_sdk::clob::types::request::OrderBookSummaryRequest;
+use polymarket_client_sdk::types::{Decimal, U256};
+
+use super::clob::{CliSide, ClobArgs, ClobCommand, parse_token_id};
+use crate::clients;
+use crate::errors;
+use crate::net;
+use crate::output::OutputFormat;
+use crate::output::book::{BookStats, Depth, FillEstimate, print_book_stats};
+use crate::paper::{self, Book, Level, Target};
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub struct BookArgs {
+    #[command(subcommand)]
+    pub command: Option<BookCommand>,
+
+    /// Token ID (numeric string)
+    #[arg(required = true)]
+    pub token_id: Option<String>,
+
+    /// Levels to show per side (default: all, or the profile's
+    /// defaults.book_depth)
+    #[arg(long)]
+    pub depth: Option<usize>,
+
+    /// Refresh every N seconds (default 5) until interrupted
+    #[arg(long, value_name = "SECS", num_args = 0..=1, default_missing_value = "5")]
+    pub watch: Option<u64>,
+}
+
+#[derive(Subcommand)]
+pub enum BookCommand {
+    /// Depth near the midpoint, imbalance, and the VWAP and slippage of an order
+    Stats {
+        /// Token ID (numeric string)
+        token_id: String,
+        /// Depth bands around the midpoint, in cents (comma-separated)
+        #[arg(long, value_delimiter = ',', default_value = "1,2,5")]
+        within: Vec<Decimal>,
+        /// Shares in a hypothetical order to price against the book
+        #[arg(long)]
+        size: Option<Decimal>,
+        /// Side of the hypothetical order
+        #[arg(long, value_enum, default_value = "buy", requires = "size")]
+        side: CliSide,
+    },
+}
+
+/// Shares and USDC in `levels`.
+fn total<'a>(levels: impl IntoIterator<Item = &'a Level>) -> (Decimal, Decimal) {
+    levels
+        .into_iter()
+        .fold((Decimal::ZERO, Decimal::ZERO), |(shares, usdc), l| {
+            (shares + l.size, usdc + l.size * l.price)
+        })
+}
+
+/// Depth within `cents` of `mid`, or in the whole book when `None`.
+fn depth(book: &Book, mid: Decimal, cents: Option<Decimal>) -> Depth {
+    let band = cents.map(|c| c / Decimal::ONE_HUNDRED);
+    let (bid_shares, bid_usdc) = total(
+        book.bids
+            .iter()
+            .filter(|l| band.is_none_or(|b| l.price >= mid - b)),
+    );
+    let (ask_shares, ask_usdc) = total(
+        book.asks
+            .iter()
+            .filter(|l| band.is_none_or(|b| l.price <= mid + b)),
+    );
+    Depth {
+        cents,
+        bid_shares,
+        bid_usdc,
+        ask_shares,
+        ask_usdc,
+    }
+}
+
+/// Walks the book for `size` shares on `side`, as a taker would.
+fn estimate_fill(book: &Book, side: Side, size: Decimal) -> FillEstimate {
+    let (fills, _) = paper::take(&book.crossing(side, None), Target::Shares(size));
+    let (filled, usdc) = total(&fills);
+    FillEstimate {
+        side,
+        size,
+        filled,
+        usdc,
+        worst: fills.last().map(|l| l.price),
+    }
+}
+
+fn stats(
+    token_id: U256,
+    book: &Book,
+    within: &[Decimal],
+    order: Option<(Side, Decimal)>,
+) -> BookStats {
+    let bid = book.bids.iter().map(|l| l.price).max();
+    let ask = book.asks.iter().map(|l| l.price).min();
+    let mut stats = BookStats {
+        token_id,
+        bid,
+        ask,
+        depth: Vec::new(),
+        fill: order.map(|(side, size)| estimate_fill(book, side, size)),
+    };
+    // Bands are measured from the midpoint, so a one-sided book only gets
+    // the whole-book totals.
+    if let Some(mid) = stats.mid() {
+        stats.depth = within.iter().map(|c| depth(book, mid, Some(*c))).collect();
+    }
+    stats.depth.push(depth(book, Decimal::ZERO, None));
+    stats
+}
+
+pub async fn execute(args: BookArgs, output: &OutputFormat) -> Result<()> {
+    match args.command {
+        None => {
+            let token_id = args
+                .token_id
+                .expect("clap requires a token ID without a subcommand");
+            let command = ClobCommand::Book {
+                token_id,
+                depth: args.depth,
+                watch: args.watch,
+            };
+            super::clob::execute(ClobArgs { command }, *output, None, None).await
+        }
+        Some(BookCommand::Stats {
+            token_id,
+            mut within,
+            size,
+            side,
+        }) => {
+            anyhow::ensure!(
+                within.iter().all(|c| *c > Decimal::ZERO),
+                errors::validation("--within bands must be positive")
+            );
+            anyhow::ensure!(
+                size.is_none_or(|s| s > Decimal::ZERO),
+                errors::validation("--size must be positive")
+            );
+            within.sort();
+            within.dedup();
+            let token_id = parse_token_id(&token_id)?;
+            let client = clients::clob()?;
+            let request = OrderBookSummaryRequest::builder()
+                .token_id(token_id)
+                .build();
+            let book = net::retry(|| client.order_book(&request)).await?;
+            let order = size.map(|size| (Side::from(side), size));
+            print_book_stats(&stats(token_id, &Book::from(&book), &within, order), output)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn level(price: Decimal, size: Decimal) -> Level {
+        Level { price, size }
+    }
+
+    fn book() -> Book {
+        Book {
+            bids: vec![
+                level(dec!(0.48), dec!(100)),
+                level(dec!(0.49), dec!(50)),
+                level(dec!(0.44), dec!(500)),
+            ],
+            asks: vec![
+                level(dec!(0.51), dec!(200)),
+                level(dec!(0.53), dec!(300)),
+                level(dec!(0.60), dec!(1000)),
+            ],
+        }
+    }
+
+    #[test]
+    fn depth_counts_levels_within_each_band() {
+        let s = stats(U256::from(1), &book(), &[dec!(1), dec!(5)], None);
+        assert_eq!(s.mid(), Some(dec!(0.50)));
+        assert_eq!(s.spread(), Some(dec!(0.02)));
+        // ±1¢ reaches 0.49 and 0.51.
+        assert_eq!(s.depth[0].bid_shares, dec!(50));
+        assert_eq!(s.depth[0].ask_shares, dec!(200));
+        assert_eq!(s.depth[0].imbalance(), Some(dec!(-0.6)));
+        // ±5¢ adds 0.48 and 0.53, still short of 0.44 and 0.60.
+        assert_eq!(s.depth[1].bid_shares, dec!(150));
+        assert_eq!(s.depth[1].bid_usdc, dec!(72.5));
+        assert_eq!(s.depth[1].ask_shares, dec!(500));
+        let whole = &s.depth[2];
+        assert_eq!(whole.cents, None);
+        assert_eq!(
+            (whole.bid_shares, whole.ask_shares),
+            (dec!(650), dec!(1500))
+        );
+        assert!(s.fill.is_none());
+    }
+
+    #[test]
+    fn fill_estimate_walks_the_book() {
+        let s = stats(U256::from(1), &book(), &[], Some((Side::Buy, dec!(400))));
+        let fill = s.fill.as_ref().unwrap();
+        assert_eq!(fill.filled, dec!(400));
+        // 200 at 0.51 and 200 at 0.53.
+        assert_eq!(fill.usdc, dec!(208));
+        assert_eq!(fill.vwap(), Some(dec!(0.52)));
+        assert_eq!(fill.worst, Some(dec!(0.53)));
+        assert_eq!(fill.slippage(dec!(0.50)), Some(dec!(0.02)));
+
+        let sell = estimate_fill(&book(), Side::Sell, dec!(1000));
+        assert_eq!(sell.filled, dec!(650));
+        assert_eq!(sell.worst, Some(dec!(0.44)));
+        assert!(sell.slippage(dec!(0.50)).unwrap() > Decimal::ZERO);
+    }
+
+    #[test]
+    fn one_sided_book_has_only_whole_book_depth() {
+        let book = Book {
+            bids: Vec::new(),
+            asks: vec![level(dec!(0.6), dec!(10))],
+        };
+        let s = stats(U256::from(1), &book, &[dec!(1)], None);
+        assert_eq!(s.mid(), None);
+        assert_eq!(s.depth.len(), 1);
+        assert_eq!(s.depth[0].imbalance(), Some(dec!(-1)));
+    }
+}
@@ -1,4 +1,5 @@
 use super::parse_address;
+use crate::net;
 use crate::output::OutputFormat;
 use crate::output::bridge::{print_deposit, print_status, print_supported_assets};
 use anyhow::Result;
@@ -43,12 +44,12 @@ pub async fn execute(
                 .address(parse_address(&address)?)
                 .build();
 
-            let response = client.deposit(&request).await?;
+            let response = net::once(client.deposit(&request)).await?;
             print_deposit(&response, &output)?;
         }
 
         BridgeCommand::SupportedAssets => {
-            let response = client.supported_assets().await?;
+            let response = net::retry(|| client.supported_assets()).await?;
             print_supported_assets(&response, &output)?;
         }
 
@@ -56,7 +57,7 @@ pub async fn execute(
             anyhow::ensure!(!address.trim().is_empty(), "Address cannot be empty");
             let request = StatusRequest::builder().address(&address).build();
 
-            let response = client.status(&request).await?;
+            let response = net::retry(|| client.status(&request)).await?;
             print_status(&response, &output)?;
         }
     }
@@ -0,0 +1,36 @@
+//! `cache`: the disk cache of market metadata and order parameters.
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use crate::cache;
+use crate::output::{OutputFormat, print_json_line};
+
+#[derive(Args)]
+pub struct CacheArgs {
+    #[command(subcommand)]
+    pub command: CacheCommand,
+}
+
+#[derive(Subcommand)]
+pub enum CacheCommand {
+    /// Delete every cached market and order parameter set
+    Clear,
+}
+
+pub fn execute(args: &CacheArgs, output: &OutputFormat) -> Result<()> {
+    match args.command {
+        CacheCommand::Clear => {
+            let removed = cache::clear()?;
+            match output {
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    println!("Cleared {removed} cached entries.");
+                }
+                OutputFormat::Json => {
+                    print_json_line(serde_json::json!({"cleared": removed}));
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,33 +1,54 @@
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 
-use anyhow::Result;
-use chrono::NaiveDate;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use clap::{Args, Subcommand};
+use polymarket_client_sdk::auth::Normal;
+use polymarket_client_sdk::auth::state::{Authenticated, State};
 use polymarket_client_sdk::clob;
 use polymarket_client_sdk::clob::types::{
-    Amount, AssetType, Interval, OrderType, Side, TimeRange,
+    Amount, AssetType, Interval, OrderType, Side, SignableOrder, TickSize, TimeRange,
     request::{
         BalanceAllowanceRequest, CancelMarketOrderRequest, DeleteNotificationsRequest,
         LastTradePriceRequest, MidpointRequest, OrderBookSummaryRequest, OrdersRequest,
         PriceHistoryRequest, PriceRequest, SpreadRequest, TradesRequest, UserRewardsEarningRequest,
     },
+    response::OpenOrderResponse,
 };
 use polymarket_client_sdk::types::{Decimal, U256};
+use serde::{Deserialize, Serialize};
 
-use super::parse_condition_id;
+use super::paper;
+use super::portfolio::fetch_orders;
+use super::stop::{StopOrder, StopSize};
+use super::wallet::resolve_trading_address;
+use super::{batch, parse_condition_id, parse_date, parse_duration};
 use crate::auth;
-use crate::output::OutputFormat;
+use crate::cache;
+use crate::clients;
+use crate::config;
+use crate::confirm;
+use crate::daemon::ipc;
+use crate::net;
 use crate::output::clob::{
-    print_account_status, print_api_keys, print_balance, print_batch_prices, print_cancel_result,
-    print_clob_market, print_clob_markets, print_create_api_key, print_current_rewards,
-    print_delete_api_key, print_earnings, print_fee_rate, print_geoblock, print_last_trade,
-    print_last_trades_prices, print_market_reward, print_midpoint, print_midpoints, print_neg_risk,
-    print_notifications, print_ok, print_order_book, print_order_books, print_order_detail,
-    print_order_scoring, print_orders, print_orders_scoring, print_post_order_result,
-    print_post_orders_result, print_price, print_price_history, print_reward_percentages,
-    print_rewards, print_server_time, print_simplified_markets, print_spread, print_spreads,
-    print_tick_size, print_trades, print_user_earnings_markets,
+    format_limit_order_preview, format_market_order_preview, print_account_status, print_api_keys,
+    print_balance, print_batch_prices, print_batch_results, print_cancel_result, print_clob_market,
+    print_clob_markets, print_create_api_key, print_current_rewards, print_delete_api_key,
+    print_earnings, print_fee_rate, print_geoblock, print_last_trade, print_last_trades_prices,
+    print_market_reward, print_midpoint, print_midpoints, print_neg_risk, print_notifications,
+    print_ok, print_order_book, print_order_books, print_order_detail, print_order_scoring,
+    print_orders, print_orders_scoring, print_placed_order, print_post_orders_result, print_price,
+    print_price_history, print_reward_percentages, print_rewards, print_server_time,
+    print_signed_order, print_simplified_markets, print_spread, print_spreads, print_tick_size,
+    print_trades, print_user_earnings_markets,
 };
+use crate::output::daemon::print_stop_added;
+use crate::output::{OutputFormat, print_json_line};
+use crate::paper::{Book, NewOrder, Target};
+use crate::procs;
+use crate::risk::{self, Proposed};
 
 #[derive(Args)]
 pub struct ClobArgs {
@@ -89,6 +110,13 @@ pub enum ClobCommand {
     Book {
         /// Token ID (numeric string)
         token_id: String,
+        /// Levels to show per side (default: all, or the profile's
+        /// defaults.book_depth)
+        #[arg(long)]
+        depth: Option<usize>,
+        /// Refresh every N seconds (default 5) until interrupted
+        #[arg(long, value_name = "SECS", num_args = 0..=1, default_missing_value = "5")]
+        watch: Option<u64>,
     },
 
     /// Get order books for multiple tokens
@@ -187,9 +215,18 @@ pub enum ClobCommand {
         /// Filter by asset/token ID
         #[arg(long)]
         asset: Option<String>,
+        /// Only show orders on this side
+        #[arg(long)]
+        side: Option<CliSide>,
+        /// Only show orders created longer ago than this (e.g. 30m, 2h, 1d)
+        #[arg(long)]
+        older_than: Option<String>,
         /// Pagination cursor
         #[arg(long)]
         cursor: Option<String>,
+        /// Show the order placed with this --client-id instead
+        #[arg(long, conflicts_with_all = ["market", "asset", "side", "older_than", "cursor"])]
+        client_id: Option<String>,
     },
 
     /// Get a single order by ID (authenticated)
@@ -207,17 +244,42 @@ pub enum ClobCommand {
         #[arg(long)]
         side: CliSide,
         /// Price (decimal, e.g. 0.50)
+        #[arg(long, required_unless_present = "odds", conflicts_with = "odds")]
+        price: Option<String>,
+        /// Decimal odds instead of a price (e.g. 2.4 for a price of ~0.417),
+        /// rounded to the market's tick size
         #[arg(long)]
-        price: String,
+        odds: Option<String>,
         /// Size (number of shares, e.g. 10)
+        #[arg(
+            long,
+            required_unless_present = "notional",
+            conflicts_with = "notional"
+        )]
+        size: Option<String>,
+        /// USDC amount instead of a size (e.g. 50); shares are rounded down
         #[arg(long)]
-        size: String,
-        /// Order type: GTC, FOK, GTD, FAK (default: GTC)
-        #[arg(long, default_value = "GTC")]
-        order_type: CliOrderType,
+        notional: Option<String>,
+        /// Order type: GTC, FOK, GTD, FAK (default: GTC, or the profile's
+        /// defaults.order_type)
+        #[arg(long, alias = "type")]
+        order_type: Option<CliOrderType>,
+        #[command(flatten)]
+        expiry: ExpiryFlags,
         /// Post-only order
         #[arg(long)]
         post_only: bool,
+        /// Refuse the order if it would trade against your own resting orders
+        #[arg(long)]
+        no_self_cross: bool,
+        /// Your own ID for the order, e.g. a UUID. It is recorded locally, and
+        /// an order with an ID already used is refused, so a retried script
+        /// can't submit it twice
+        #[arg(long)]
+        client_id: Option<String>,
+        /// Sign the order and print it without submitting
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Post multiple orders at once (authenticated)
@@ -234,9 +296,23 @@ pub enum ClobCommand {
         /// Sizes (comma-separated, one per order)
         #[arg(long)]
         sizes: String,
-        /// Order type: GTC, FOK, GTD, FAK (default: GTC)
-        #[arg(long, default_value = "GTC")]
-        order_type: CliOrderType,
+        /// Order type: GTC, FOK, GTD, FAK (default: GTC, or the profile's
+        /// defaults.order_type)
+        #[arg(long)]
+        order_type: Option<CliOrderType>,
+    },
+
+    /// Sign and submit limit orders from a JSON or CSV file (authenticated)
+    Batch {
+        /// Orders file (.json array or .csv with a header row) with columns
+        /// token, side, price, size and optional order_type, post_only
+        file: PathBuf,
+        /// Orders posted at the same time
+        #[arg(long, default_value = "5")]
+        concurrency: usize,
+        /// Sign the orders and print them without submitting
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Create a market order (authenticated)
@@ -250,9 +326,59 @@ pub enum ClobCommand {
         /// Amount (USDC for buys, shares for sells)
         #[arg(long)]
         amount: String,
-        /// Order type: FOK or FAK (default: FOK)
-        #[arg(long, default_value = "FOK")]
-        order_type: CliOrderType,
+        /// Order type: FOK or FAK (default: FOK, or the profile's
+        /// defaults.market_order_type)
+        #[arg(long, alias = "type")]
+        order_type: Option<CliOrderType>,
+        /// Refuse the order if you have resting orders on the other side of
+        /// the token, which it could trade against
+        #[arg(long)]
+        no_self_cross: bool,
+        /// Refuse the order if the book says its average price would be more
+        /// than this much worse than the best price, e.g. 1%
+        #[arg(long, value_parser = parse_slippage, conflicts_with = "limit_price")]
+        max_slippage: Option<Decimal>,
+        /// Refuse the order if the book says its average price would be worse
+        /// than this
+        #[arg(long)]
+        limit_price: Option<Decimal>,
+        /// Your own ID for the order, e.g. a UUID (see create-order)
+        #[arg(long)]
+        client_id: Option<String>,
+        /// Sign the order and print it without submitting
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Wait for a price and then send a market order: a stop-loss or
+    /// take-profit (authenticated)
+    Stop {
+        /// Token ID (numeric string)
+        #[arg(long)]
+        token: String,
+        /// Midpoint that fires the order. Below the current price it fires on
+        /// a fall, above it on a rise
+        #[arg(long)]
+        trigger: Decimal,
+        /// Side: buy or sell
+        #[arg(long)]
+        side: CliSide,
+        /// Shares to sell, USDC to buy, or `all` to sell the whole position
+        #[arg(long)]
+        size: StopSize,
+        /// Order type: FOK or FAK (default: FOK, or the profile's
+        /// defaults.market_order_type)
+        #[arg(long, alias = "type")]
+        order_type: Option<CliOrderType>,
+        /// Seconds between price checks
+        #[arg(long, default_value = "5")]
+        interval: u64,
+        /// Sign the order when triggered and print it without submitting
+        #[arg(long, conflicts_with = "daemon")]
+        dry_run: bool,
+        /// Hand the stop to the running daemon and return instead of waiting
+        #[arg(long)]
+        daemon: bool,
     },
 
     /// Cancel an order by ID (authenticated)
@@ -272,6 +398,9 @@ pub enum ClobCommand {
 
     /// Cancel orders for a specific market (authenticated)
     CancelMarket {
+        /// Market condition ID (same as --market)
+        #[arg(conflicts_with = "market")]
+        condition_id: Option<String>,
         /// Market condition ID
         #[arg(long)]
         market: Option<String>,
@@ -459,6 +588,80 @@ impl From<CliOrderType> for OrderType {
     }
 }
 
+/// The CLOB rejects a GTD order unless it expires more than this long
+/// from now, its security threshold.
+const GTD_MIN_LIFETIME: chrono::Duration = chrono::Duration::seconds(60);
+
+/// When a GTD order expires. Giving either flag makes the order GTD.
+#[derive(Args, Clone, Default)]
+pub struct ExpiryFlags {
+    /// Expire the order after this long, e.g. 2h or 30m (makes it GTD)
+    #[arg(long, conflicts_with = "expires_at")]
+    pub expires_in: Option<String>,
+    /// Expire the order at this time: a Unix timestamp or RFC 3339, e.g.
+    /// 2025-06-30T18:00:00Z (makes it GTD)
+    #[arg(long)]
+    pub expires_at: Option<String>,
+}
+
+impl ExpiryFlags {
+    /// The order type and expiration for an order of type `given` (default:
+    /// GTD with an expiry, else `default`). Only a GTD order may have an
+    /// expiry, and it must have one.
+    pub(crate) fn resolve(
+        &self,
+        given: Option<OrderType>,
+        default: impl FnOnce() -> Result<OrderType>,
+    ) -> Result<(OrderType, Option<DateTime<Utc>>)> {
+        let expiration = self.expiration(Utc::now())?;
+        let order_type = match given {
+            Some(order_type) => order_type,
+            None if expiration.is_some() => OrderType::GTD,
+            None => default()?,
+        };
+        match (&order_type, expiration) {
+            (OrderType::GTD, None) => Err(crate::errors::validation(
+                "GTD orders need --expires-in or --expires-at",
+            )),
+            (OrderType::GTD, _) | (_, None) => Ok((order_type, expiration)),
+            (_, Some(_)) => Err(crate::errors::validation(format!(
+                "Only GTD orders expire; {order_type} orders can't take --expires-in or --expires-at"
+            ))),
+        }
+    }
+
+    fn expiration(&self, now: DateTime<Utc>) -> Result<Option<DateTime<Utc>>> {
+        let at = match (&self.expires_in, &self.expires_at) {
+            (Some(duration), _) => now + parse_duration(duration)?,
+            (None, Some(at)) => parse_timestamp(at)?,
+            (None, None) => return Ok(None),
+        };
+        anyhow::ensure!(
+            at - now > GTD_MIN_LIFETIME,
+            crate::errors::validation(
+                "The order must expire more than a minute from now, the CLOB's minimum for GTD orders"
+            )
+        );
+        Ok(Some(at))
+    }
+}
+
+/// A Unix timestamp in seconds, or an RFC 3339 time.
+fn parse_timestamp(s: &str) -> Result<DateTime<Utc>> {
+    let s = s.trim();
+    let parsed = match s.parse::<i64>() {
+        Ok(secs) => DateTime::from_timestamp(secs, 0),
+        Err(_) => DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|t| t.with_timezone(&Utc)),
+    };
+    parsed.ok_or_else(|| {
+        crate::errors::validation(format!(
+            "Invalid time: {s} (expected a Unix timestamp or e.g. 2025-06-30T18:00:00Z)"
+        ))
+    })
+}
+
 #[derive(Clone, Debug, clap::ValueEnum)]
 pub enum CliAssetType {
     Collateral,
@@ -474,17 +677,75 @@ impl From<CliAssetType> for AssetType {
     }
 }
 
-fn parse_token_id(s: &str) -> Result<U256> {
-    U256::from_str(s).map_err(|_| anyhow::anyhow!("Invalid token ID: {s}"))
+pub(crate) fn parse_token_id(s: &str) -> Result<U256> {
+    U256::from_str(s).map_err(|_| crate::errors::validation(format!("Invalid token ID: {s}")))
 }
 
-fn parse_token_ids(s: &str) -> Result<Vec<U256>> {
+pub(crate) fn parse_token_ids(s: &str) -> Result<Vec<U256>> {
     s.split(',').map(|t| parse_token_id(t.trim())).collect()
 }
 
-fn parse_date(s: &str) -> Result<NaiveDate> {
-    NaiveDate::parse_from_str(s, "%Y-%m-%d")
-        .map_err(|_| anyhow::anyhow!("Invalid date: expected YYYY-MM-DD format"))
+/// What an order on a token has to fit, from its order book.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct OrderParams {
+    pub tick_size: Decimal,
+    pub min_order_size: Decimal,
+    pub neg_risk: bool,
+}
+
+impl OrderParams {
+    /// Rejects a limit order the CLOB would refuse: a price outside (0, 1)
+    /// or off the tick grid, or a size below the market's minimum.
+    pub(crate) fn check_limit_order(&self, price: Decimal, size: Decimal) -> Result<()> {
+        anyhow::ensure!(
+            price > Decimal::ZERO && price < Decimal::ONE,
+            "Price must be between 0 and 1, got {price}"
+        );
+        let tick = self.tick_size.normalize();
+        if !(price % tick).is_zero() {
+            let below = ((price / tick).floor() * tick).normalize();
+            let above = (below + tick).normalize();
+            let nearest: Vec<String> = [below, above]
+                .into_iter()
+                .filter(|p| *p > Decimal::ZERO && *p < Decimal::ONE)
+                .map(|p| p.to_string())
+                .collect();
+            anyhow::bail!(
+                "Price {price} is not a multiple of this market's tick size {tick}; try {}",
+                nearest.join(" or ")
+            );
+        }
+        anyhow::ensure!(
+            size >= self.min_order_size,
+            "Size {size} is below this market's minimum order of {} shares",
+            self.min_order_size.normalize()
+        );
+        Ok(())
+    }
+}
+
+/// The token's order parameters, from the disk cache if fetched recently.
+/// They are handed to `client` too, so building and signing an order
+/// doesn't fetch them again.
+pub(crate) async fn order_params<S: State>(
+    client: &clob::Client<S>,
+    token_id: U256,
+) -> Result<OrderParams> {
+    let params = cache::get_or_fetch(cache::ORDER_PARAMS, &token_id.to_string(), async {
+        let request = OrderBookSummaryRequest::builder()
+            .token_id(token_id)
+            .build();
+        let book = net::retry(|| client.order_book(&request)).await?;
+        Ok(OrderParams {
+            tick_size: book.tick_size.as_decimal(),
+            min_order_size: book.min_order_size,
+            neg_risk: book.neg_risk,
+        })
+    })
+    .await?;
+    client.set_tick_size(token_id, TickSize::try_from(params.tick_size)?);
+    client.set_neg_risk(token_id, params.neg_risk);
+    Ok(params)
 }
 
 pub async fn execute(
@@ -523,7 +784,9 @@ pub async fn execute(
         | ClobCommand::Order { .. }
         | ClobCommand::CreateOrder { .. }
         | ClobCommand::PostOrders { .. }
+        | ClobCommand::Batch { .. }
         | ClobCommand::MarketOrder { .. }
+        | ClobCommand::Stop { .. }
         | ClobCommand::Cancel { .. }
         | ClobCommand::CancelOrders { .. }
         | ClobCommand::CancelAll
@@ -561,23 +824,23 @@ pub async fn execute(
 async fn execute_read(command: ClobCommand, output: &OutputFormat) -> Result<()> {
     match command {
         ClobCommand::Ok => {
-            let client = clob::Client::default();
-            let result = client.ok().await?;
+            let client = clients::clob()?;
+            let result = net::retry(|| client.ok()).await?;
             print_ok(&result, output)?;
         }
 
         ClobCommand::Price { token_id, side } => {
-            let client = clob::Client::default();
+            let client = clients::clob()?;
             let request = PriceRequest::builder()
                 .token_id(parse_token_id(&token_id)?)
                 .side(Side::from(side))
                 .build();
-            let result = client.price(&request).await?;
+            let result = net::retry(|| client.price(&request)).await?;
             print_price(&result, output)?;
         }
 
         ClobCommand::BatchPrices { token_ids, side } => {
-            let client = clob::Client::default();
+            let client = clients::clob()?;
             let requests: Vec<_> = parse_token_ids(&token_ids)?
                 .into_iter()
                 .map(|id| {
@@ -587,132 +850,159 @@ async fn execute_read(command: ClobCommand, output: &OutputFormat) -> Result<()>
                         .build()
                 })
                 .collect();
-            let result = client.prices(&requests).await?;
+            let result = net::retry(|| client.prices(&requests)).await?;
             print_batch_prices(&result, output)?;
         }
 
         ClobCommand::Midpoint { token_id } => {
-            let client = clob::Client::default();
+            let client = clients::clob()?;
             let request = MidpointRequest::builder()
                 .token_id(parse_token_id(&token_id)?)
                 .build();
-            let result = client.midpoint(&request).await?;
+            let result = net::retry(|| client.midpoint(&request)).await?;
             print_midpoint(&result, output)?;
         }
 
         ClobCommand::Midpoints { token_ids } => {
-            let client = clob::Client::default();
+            let client = clients::clob()?;
             let requests: Vec<_> = parse_token_ids(&token_ids)?
                 .into_iter()
                 .map(|id| MidpointRequest::builder().token_id(id).build())
                 .collect();
-            let result = client.midpoints(&requests).await?;
+            let result = net::retry(|| client.midpoints(&requests)).await?;
             print_midpoints(&result, output)?;
         }
 
         ClobCommand::Spread { token_id, side } => {
-            let client = clob::Client::default();
+            let client = clients::clob()?;
             let request = SpreadRequest::builder()
                 .token_id(parse_token_id(&token_id)?)
                 .maybe_side(side.map(Side::from))
                 .build();
-            let result = client.spread(&request).await?;
+            let result = net::retry(|| client.spread(&request)).await?;
             print_spread(&result, output)?;
         }
 
         ClobCommand::Spreads { token_ids } => {
-            let client = clob::Client::default();
+            let client = clients::clob()?;
             let requests: Vec<_> = parse_token_ids(&token_ids)?
                 .into_iter()
                 .map(|id| SpreadRequest::builder().token_id(id).build())
                 .collect();
-            let result = client.spreads(&requests).await?;
+            let result = net::retry(|| client.spreads(&requests)).await?;
             print_spreads(&result, output)?;
         }
 
-        ClobCommand::Book { token_id } => {
-            let client = clob::Client::default();
+        ClobCommand::Book {
+            token_id,
+            depth,
+            watch,
+        } => {
+            let depth = match depth {
+                Some(depth) => Some(depth),
+                None => config::book_depth()?,
+            };
+            let client = clients::clob()?;
             let request = OrderBookSummaryRequest::builder()
                 .token_id(parse_token_id(&token_id)?)
                 .build();
-            let result = client.order_book(&request).await?;
-            print_order_book(&result, output)?;
+            let Some(secs) = watch else {
+                let result = net::retry(|| client.order_book(&request)).await?;
+                return print_order_book(&result, depth, output);
+            };
+            anyhow::ensure!(secs > 0, "--watch interval must be at least 1 second");
+
+            loop {
+                let result = net::retry(|| client.order_book(&request)).await?;
+                if !matches!(output, OutputFormat::Json) {
+                    // Clear the screen and move the cursor home before redrawing.
+                    print!("\x1B[2J\x1B[H");
+                }
+                print_order_book(&result, depth, output)?;
+                tokio::select! {
+                    () = tokio::time::sleep(std::time::Duration::from_secs(secs)) => {}
+                    _ = tokio::signal::ctrl_c() => break,
+                }
+            }
         }
 
         ClobCommand::Books { token_ids } => {
-            let client = clob::Client::default();
+            let client = clients::clob()?;
             let requests: Vec<_> = parse_token_ids(&token_ids)?
                 .into_iter()
                 .map(|id| OrderBookSummaryRequest::builder().token_id(id).build())
                 .collect();
-            let result = client.order_books(&requests).await?;
+            let result = net::retry(|| client.order_books(&requests)).await?;
             print_order_books(&result, output)?;
         }
 
         ClobCommand::LastTrade { token_id } => {
-            let client = clob::Client::default();
+            let client = clients::clob()?;
             let request = LastTradePriceRequest::builder()
                 .token_id(parse_token_id(&token_id)?)
                 .build();
-            let result = client.last_trade_price(&request).await?;
+            let result = net::retry(|| client.last_trade_price(&request)).await?;
             print_last_trade(&result, output)?;
         }
 
         ClobCommand::LastTrades { token_ids } => {
-            let client = clob::Client::default();
+            let client = clients::clob()?;
             let requests: Vec<_> = parse_token_ids(&token_ids)?
                 .into_iter()
                 .map(|id| LastTradePriceRequest::builder().token_id(id).build())
                 .collect();
-            let result = client.last_trades_prices(&requests).await?;
+            let result = net::retry(|| client.last_trades_prices(&requests)).await?;
             print_last_trades_prices(&result, output)?;
         }
 
         ClobCommand::Market { condition_id } => {
-            let client = clob::Client::default();
-            let result = client.market(&condition_id).await?;
+            let client = clients::clob()?;
+            let result = net::retry(|| client.market(&condition_id)).await?;
             print_clob_market(&result, output)?;
         }
 
         ClobCommand::Markets { cursor } => {
-            let client = clob::Client::default();
-            let result = client.markets(cursor).await?;
+            let client = clients::clob()?;
+            let result = net::retry(|| client.markets(cursor.clone())).await?;
             print_clob_markets(&result, output)?;
         }
 
         ClobCommand::SamplingMarkets { cursor } => {
-            let client = clob::Client::default();
-            let result = client.sampling_markets(cursor).await?;
+            let client = clients::clob()?;
+            let result = net::retry(|| client.sampling_markets(cursor.clone())).await?;
             print_clob_markets(&result, output)?;
         }
 
         ClobCommand::SimplifiedMarkets { cursor } => {
-            let client = clob::Client::default();
-            let result = client.simplified_markets(cursor).await?;
+            let client = clients::clob()?;
+            let result = net::retry(|| client.simplified_markets(cursor.clone())).await?;
             print_simplified_markets(&result, output)?;
         }
 
         ClobCommand::SamplingSimpMarkets { cursor } => {
-            let client = clob::Client::default();
-            let result = client.sampling_simplified_markets(cursor).await?;
+            let client = clients::clob()?;
+            let result = net::retry(|| client.sampling_simplified_markets(cursor.clone())).await?;
             print_simplified_markets(&result, output)?;
         }
 
         ClobCommand::TickSize { token_id } => {
-            let client = clob::Client::default();
-            let result = client.tick_size(parse_token_id(&token_id)?).await?;
+            let client = clients::clob()?;
+            let token_id = parse_token_id(&token_id)?;
+            let result = net::retry(|| client.tick_size(token_id)).await?;
             print_tick_size(&result, output)?;
         }
 
         ClobCommand::FeeRate { token_id } => {
-            let client = clob::Client::default();
-            let result = client.fee_rate_bps(parse_token_id(&token_id)?).await?;
+            let client = clients::clob()?;
+            let token_id = parse_token_id(&token_id)?;
+            let result = net::retry(|| client.fee_rate_bps(token_id)).await?;
             print_fee_rate(&result, output)?;
         }
 
         ClobCommand::NegRisk { token_id } => {
-            let client = clob::Client::default();
-            let result = client.neg_risk(parse_token_id(&token_id)?).await?;
+            let client = clients::clob()?;
+            let token_id = parse_token_id(&token_id)?;
+            let result = net::retry(|| client.neg_risk(token_id)).await?;
             print_neg_risk(&result, output)?;
         }
 
@@ -721,25 +1011,25 @@ async fn execute_read(command: ClobCommand, output: &OutputFormat) -> Result<()>
             interval,
             fidelity,
         } => {
-            let client = clob::Client::default();
+            let client = clients::clob()?;
             let request = PriceHistoryRequest::builder()
                 .market(parse_token_id(&token_id)?)
                 .time_range(TimeRange::from_interval(Interval::from(interval)))
                 .maybe_fidelity(fidelity)
                 .build();
-            let result = client.price_history(&request).await?;
+            let result = net::retry(|| client.price_history(&request)).await?;
             print_price_history(&result, output)?;
         }
 
         ClobCommand::Time => {
-            let client = clob::Client::default();
-            let result = client.server_time().await?;
+            let client = clients::clob()?;
+            let result = net::retry(|| client.server_time()).await?;
             print_server_time(result, output)?;
         }
 
         ClobCommand::Geoblock => {
-            let client = clob::Client::default();
-            let result = client.check_geoblock().await?;
+            let client = clients::clob()?;
+            let result = net::retry(|| client.check_geoblock()).await?;
             print_geoblock(&result, output)?;
         }
 
@@ -755,24 +1045,83 @@ async fn execute_trade(
     private_key: Option<&str>,
     signature_type: Option<&str>,
 ) -> Result<()> {
+    let paper = config::paper_mode();
+    if paper
+        && !matches!(
+            command,
+            ClobCommand::Orders { .. }
+                | ClobCommand::CreateOrder { .. }
+                | ClobCommand::MarketOrder { .. }
+                | ClobCommand::Cancel { .. }
+                | ClobCommand::CancelOrders { .. }
+                | ClobCommand::CancelAll
+        )
+    {
+        anyhow::bail!("{}", paper::UNSUPPORTED_MSG);
+    }
+
     match command {
+        ClobCommand::Orders {
+            client_id: Some(client_id),
+            ..
+        } => {
+            anyhow::ensure!(
+                !paper,
+                crate::errors::validation("--client-id isn't supported in paper mode")
+            );
+            let record = config::load_client_order(&client_id)?.ok_or_else(|| {
+                crate::errors::validation(format!("No order was placed with client ID {client_id}"))
+            })?;
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let order_id = record.order_id.to_string();
+            let result = net::retry(|| client.order(&order_id)).await?;
+            print_order_detail(&result, output)?;
+        }
+
         ClobCommand::Orders {
             market,
             asset,
+            side,
+            older_than,
             cursor,
+            client_id: None,
         } => {
+            let cutoff = older_than
+                .map(|d| parse_duration(&d).map(|d| Utc::now() - d))
+                .transpose()?;
+            let side = side.map(Side::from);
+
+            if paper {
+                anyhow::ensure!(
+                    market.is_none(),
+                    "--market isn't supported in paper mode; filter with --asset"
+                );
+                let asset = asset.map(|a| parse_token_id(&a)).transpose()?;
+                return paper::filtered_orders(
+                    |o| {
+                        asset.is_none_or(|a| o.token_id == a.to_string())
+                            && side.is_none_or(|s| o.side == s)
+                            && cutoff.is_none_or(|c| o.created_at <= c)
+                    },
+                    output,
+                )
+                .await;
+            }
             let client = auth::authenticated_clob_client(private_key, signature_type).await?;
             let request = OrdersRequest::builder()
                 .maybe_market(market.map(|m| parse_condition_id(&m)).transpose()?)
                 .maybe_asset_id(asset.map(|a| parse_token_id(&a)).transpose()?)
                 .build();
-            let result = client.orders(&request, cursor).await?;
+            let mut result = net::retry(|| client.orders(&request, cursor.clone())).await?;
+            result.data.retain(|o| {
+                side.is_none_or(|s| o.side == s) && cutoff.is_none_or(|c| o.created_at <= c)
+            });
             print_orders(&result, output)?;
         }
 
         ClobCommand::Order { order_id } => {
             let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.order(&order_id).await?;
+            let result = net::retry(|| client.order(&order_id)).await?;
             print_order_detail(&result, output)?;
         }
 
@@ -781,30 +1130,116 @@ async fn execute_trade(
             side,
             price,
             size,
+            odds,
+            notional,
             order_type,
+            expiry,
             post_only,
+            no_self_cross,
+            client_id,
+            dry_run,
         } => {
-            let signer = auth::resolve_signer(private_key)?;
-            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+            let token_id = parse_token_id(&token)?;
+            check_client_id(client_id.as_deref(), paper)?;
+            let (sdk_order_type, expiration) =
+                expiry.resolve(order_type.map(Into::into), config::limit_order_type)?;
+            anyhow::ensure!(
+                !(paper && expiration.is_some()),
+                crate::errors::validation("Paper orders don't expire; leave out the expiry")
+            );
+
+            let converted = odds.is_some() || notional.is_some();
+            let price_dec = match (price, odds) {
+                (Some(price), _) => Decimal::from_str(&price)
+                    .map_err(|_| anyhow::anyhow!("Invalid price: {price}"))?,
+                (None, Some(odds)) => {
+                    let odds = Decimal::from_str(&odds)
+                        .map_err(|_| anyhow::anyhow!("Invalid odds: {odds}"))?;
+                    let tick = order_params(&clients::clob()?, token_id).await?.tick_size;
+                    odds_to_price(odds, tick)?
+                }
+                (None, None) => anyhow::bail!("Specify --price or --odds"),
+            };
+            let size_dec = match (size, notional) {
+                (Some(size), _) => {
+                    Decimal::from_str(&size).map_err(|_| anyhow::anyhow!("Invalid size: {size}"))?
+                }
+                (None, Some(notional)) => {
+                    let notional = Decimal::from_str(&notional)
+                        .map_err(|_| anyhow::anyhow!("Invalid notional: {notional}"))?;
+                    notional_to_size(notional, price_dec)?
+                }
+                (None, None) => anyhow::bail!("Specify --size or --notional"),
+            };
+            order_params(&clients::clob()?, token_id)
+                .await?
+                .check_limit_order(price_dec, size_dec)?;
+            let sdk_side = Side::from(side);
 
-            let price_dec =
-                Decimal::from_str(&price).map_err(|_| anyhow::anyhow!("Invalid price: {price}"))?;
-            let size_dec =
-                Decimal::from_str(&size).map_err(|_| anyhow::anyhow!("Invalid size: {size}"))?;
+            // Show what --odds/--notional turned into before anything is
+            // signed, and ask before a live order is sent.
+            let ask = !paper && !dry_run && !confirm::assume_yes();
+            if converted || ask {
+                let fee_rate = fee_rate_bps(token_id).await?;
+                let preview = format_limit_order_preview(
+                    sdk_side,
+                    price_dec,
+                    size_dec,
+                    fee_rate,
+                    &sdk_order_type,
+                    expiration,
+                );
+                if !ask {
+                    confirm::preview(&preview, output);
+                } else if !confirm::confirm(&preview, "Submit this order?", output)? {
+                    return Ok(());
+                }
+            }
 
-            let order = client
+            if paper {
+                let order = NewOrder {
+                    token_id: token_id.to_string(),
+                    side: sdk_side,
+                    price: Some(price_dec),
+                    target: Target::Shares(size_dec),
+                    order_type: sdk_order_type,
+                    post_only,
+                };
+                return paper::place(order, dry_run, output).await;
+            }
+            let signer = auth::resolve_signer(private_key).await?;
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+            order_params(&client, token_id).await?;
+            risk::check(
+                &client,
+                &[Proposed::limit(token_id, sdk_side, price_dec, size_dec)],
+                resolve_trading_address(private_key, signature_type),
+            )
+            .await?;
+            if no_self_cross {
+                ensure_no_self_cross(&client, token_id, sdk_side, Some(price_dec)).await?;
+            }
+            let mut builder = client
                 .limit_order()
-                .token_id(parse_token_id(&token)?)
-                .side(Side::from(side))
+                .token_id(token_id)
+                .side(sdk_side)
                 .price(price_dec)
                 .size(size_dec)
-                .order_type(OrderType::from(order_type))
-                .post_only(post_only)
-                .build()
-                .await?;
-            let order = client.sign(&signer, order).await?;
-            let result = client.post_order(order).await?;
-            print_post_order_result(&result, output)?;
+                .order_type(sdk_order_type)
+                .post_only(post_only);
+            if let Some(expiration) = expiration {
+                builder = builder.expiration(expiration);
+            }
+            let order = builder.build().await?;
+            sign_and_submit(
+                &client,
+                &signer,
+                order,
+                client_id.as_deref(),
+                dry_run,
+                output,
+            )
+            .await?;
         }
 
         ClobCommand::PostOrders {
@@ -814,9 +1249,6 @@ async fn execute_trade(
             sizes,
             order_type,
         } => {
-            let signer = auth::resolve_signer(private_key)?;
-            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
-
             let token_ids = parse_token_ids(&tokens)?;
             let price_strs: Vec<&str> = prices.split(',').map(str::trim).collect();
             let size_strs: Vec<&str> = sizes.split(',').map(str::trim).collect();
@@ -826,11 +1258,19 @@ async fn execute_trade(
                     "tokens, prices, and sizes must have the same number of comma-separated values"
                 );
             }
+            if !confirm::ask(&format!("Submit {} orders?", token_ids.len()), output)? {
+                return Ok(());
+            }
+            let signer = auth::resolve_signer(private_key).await?;
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
 
             let sdk_side = Side::from(side);
-            let sdk_order_type = OrderType::from(order_type);
+            let sdk_order_type = match order_type {
+                Some(order_type) => order_type.into(),
+                None => config::limit_order_type()?,
+            };
 
-            let mut signed_orders = Vec::with_capacity(token_ids.len());
+            let mut parsed = Vec::with_capacity(token_ids.len());
             for ((token_id, price_str), size_str) in
                 token_ids.into_iter().zip(price_strs).zip(size_strs)
             {
@@ -839,6 +1279,25 @@ async fn execute_trade(
                 let size_dec = Decimal::from_str(size_str)
                     .map_err(|_| anyhow::anyhow!("Invalid size: {size_str}"))?;
 
+                order_params(&client, token_id)
+                    .await?
+                    .check_limit_order(price_dec, size_dec)
+                    .map_err(|e| anyhow::anyhow!("Token {token_id}: {e}"))?;
+                parsed.push((token_id, price_dec, size_dec));
+            }
+            let proposed: Vec<Proposed> = parsed
+                .iter()
+                .map(|&(token_id, price, size)| Proposed::limit(token_id, sdk_side, price, size))
+                .collect();
+            risk::check(
+                &client,
+                &proposed,
+                resolve_trading_address(private_key, signature_type),
+            )
+            .await?;
+
+            let mut signed_orders = Vec::with_capacity(parsed.len());
+            for (token_id, price_dec, size_dec) in parsed {
                 let order = client
                     .limit_order()
                     .token_id(token_id)
@@ -848,22 +1307,54 @@ async fn execute_trade(
                     .order_type(sdk_order_type.clone())
                     .build()
                     .await?;
-                signed_orders.push(client.sign(&signer, order).await?);
+                signed_orders.push(auth::sign_order(&client, &signer, order).await?);
             }
 
-            let results = client.post_orders(signed_orders).await?;
+            let results = net::once(client.post_orders(signed_orders)).await?;
             print_post_orders_result(&results, output)?;
         }
 
+        ClobCommand::Batch {
+            file,
+            concurrency,
+            dry_run,
+        } => {
+            anyhow::ensure!(concurrency > 0, "--concurrency must be at least 1");
+            // Read the whole file first so a malformed row never leaves a
+            // half-submitted batch.
+            let orders = batch::load_orders(&file)?;
+            if !dry_run
+                && !confirm::ask(
+                    &format!("Submit {} orders from {}?", orders.len(), file.display()),
+                    output,
+                )?
+            {
+                return Ok(());
+            }
+            let signer = auth::resolve_signer(private_key).await?;
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+            risk::check(
+                &client,
+                &batch::proposed(&orders),
+                resolve_trading_address(private_key, signature_type),
+            )
+            .await?;
+            let results = batch::submit(&client, &signer, orders, concurrency, dry_run).await;
+            print_batch_results(&results, dry_run, output)?;
+        }
+
         ClobCommand::MarketOrder {
             token,
             side,
             amount,
             order_type,
+            no_self_cross,
+            max_slippage,
+            limit_price,
+            client_id,
+            dry_run,
         } => {
-            let signer = auth::resolve_signer(private_key)?;
-            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
-
+            check_client_id(client_id.as_deref(), paper)?;
             let amount_dec = Decimal::from_str(&amount)
                 .map_err(|_| anyhow::anyhow!("Invalid amount: {amount}"))?;
             let sdk_side = Side::from(side);
@@ -872,47 +1363,209 @@ async fn execute_trade(
             } else {
                 Amount::usdc(amount_dec)?
             };
+            let target = if sdk_side == Side::Sell {
+                Target::Shares(amount_dec)
+            } else {
+                Target::Usdc(amount_dec)
+            };
+            let sdk_order_type = match order_type {
+                Some(order_type) => order_type.into(),
+                None => config::market_order_type()?,
+            };
 
-            let order = client
+            let bound = match (max_slippage, limit_price) {
+                (Some(pct), _) => Some(SlippageBound::Percent(pct)),
+                (None, Some(price)) => Some(SlippageBound::Price(price)),
+                (None, None) => None,
+            };
+            // The expected fill, when bounded. Its worst price becomes the
+            // order's price, so a book that moves before the order lands
+            // can't fill it past what was checked here.
+            let fill = match bound {
+                Some(bound) => {
+                    let client = clients::clob()?;
+                    let request = OrderBookSummaryRequest::builder()
+                        .token_id(parse_token_id(&token)?)
+                        .build();
+                    let book = net::retry(|| client.order_book(&request)).await?;
+                    Some(check_slippage(&Book::from(&book), sdk_side, target, bound)?)
+                }
+                None => None,
+            };
+
+            if !paper && !dry_run && !confirm::assume_yes() {
+                let fee_rate = fee_rate_bps(parse_token_id(&token)?).await?;
+                let preview = format_market_order_preview(
+                    sdk_side,
+                    amount_dec,
+                    fee_rate,
+                    &sdk_order_type,
+                    fill.map(|f| f.vwap),
+                );
+                if !confirm::confirm(&preview, "Submit this order?", output)? {
+                    return Ok(());
+                }
+            }
+
+            if paper {
+                anyhow::ensure!(amount_dec > Decimal::ZERO, "Amount must be positive");
+                let order = NewOrder {
+                    token_id: parse_token_id(&token)?.to_string(),
+                    side: sdk_side,
+                    price: fill.map(|f| f.worst),
+                    target,
+                    order_type: sdk_order_type,
+                    post_only: false,
+                };
+                return paper::place(order, dry_run, output).await;
+            }
+            let token_id = parse_token_id(&token)?;
+            let signer = auth::resolve_signer(private_key).await?;
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+            order_params(&client, token_id).await?;
+            let proposed = Proposed::market(token_id, sdk_side, fill.map(|f| f.worst), amount_dec);
+            risk::check(
+                &client,
+                &[proposed],
+                resolve_trading_address(private_key, signature_type),
+            )
+            .await?;
+            if no_self_cross {
+                ensure_no_self_cross(&client, token_id, sdk_side, None).await?;
+            }
+            let mut builder = client
                 .market_order()
-                .token_id(parse_token_id(&token)?)
+                .token_id(token_id)
                 .side(sdk_side)
                 .amount(parsed_amount)
-                .order_type(OrderType::from(order_type))
+                .order_type(sdk_order_type);
+            if let Some(fill) = fill {
+                builder = builder.price(fill.worst);
+            }
+            let order = builder.build().await?;
+            sign_and_submit(
+                &client,
+                &signer,
+                order,
+                client_id.as_deref(),
+                dry_run,
+                output,
+            )
+            .await?;
+        }
+
+        ClobCommand::Stop {
+            token,
+            trigger,
+            side,
+            size,
+            order_type,
+            interval,
+            dry_run,
+            daemon,
+        } => {
+            anyhow::ensure!(interval > 0, "--interval must be at least 1 second");
+            let stop = StopOrder {
+                token: parse_token_id(&token)?,
+                side: side.into(),
+                trigger,
+                size,
+                order_type: match order_type {
+                    Some(order_type) => order_type.into(),
+                    None => config::market_order_type()?,
+                },
+            };
+            stop.validate()?;
+            if daemon {
+                if stop.side == Side::Buy && !config::risk_limits().is_empty() {
+                    // Refuse now rather than when the daemon fires it.
+                    let client =
+                        auth::authenticated_clob_client(private_key, signature_type).await?;
+                    let owner = resolve_trading_address(private_key, signature_type);
+                    stop.check_risk(&client, owner, risk::overridden()).await?;
+                }
+                let stop = stop.arm().await?;
+                let added = ipc::request(&ipc::Request::AddStop { stop }).await?;
+                return print_stop_added(&serde_json::from_value(added)?, output);
+            }
+            let signer = auth::resolve_signer(private_key).await?;
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+            let owner = resolve_trading_address(private_key, signature_type).await?;
+            if size == StopSize::All {
+                // Fail now rather than when the stop fires.
+                stop.amount(owner).await?;
+            }
+            stop.check_risk(&client, async { Ok(owner) }, risk::overridden())
+                .await?;
+
+            let _running = procs::register("clob stop");
+            let Some(mid) = stop.wait_for_trigger(Duration::from_secs(interval)).await? else {
+                return Ok(());
+            };
+            // Exposure may have grown while it waited.
+            stop.check_risk(&client, async { Ok(owner) }, risk::overridden())
+                .await?;
+            eprintln!("Triggered at {mid}; sending market order.");
+            let order = client
+                .market_order()
+                .token_id(stop.token)
+                .side(stop.side)
+                .amount(stop.amount(owner).await?)
+                .order_type(stop.order_type.clone())
                 .build()
                 .await?;
-            let order = client.sign(&signer, order).await?;
-            let result = client.post_order(order).await?;
-            print_post_order_result(&result, output)?;
+            stop.notify_triggered(mid).await;
+            sign_and_submit(&client, &signer, order, None, dry_run, output).await?;
         }
 
+        ClobCommand::Cancel { order_id } if paper => paper::cancel(&[order_id.as_str()])?,
         ClobCommand::Cancel { order_id } => {
             let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.cancel_order(&order_id).await?;
-            print_cancel_result(&result, output)?;
+            let result = net::once(client.cancel_order(&order_id)).await?;
+            print_cancel_result(&result, &[order_id.as_str()], output)?;
         }
 
         ClobCommand::CancelOrders { order_ids } => {
-            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
             let ids: Vec<&str> = order_ids.split(',').map(str::trim).collect();
-            let result = client.cancel_orders(&ids).await?;
-            print_cancel_result(&result, output)?;
+            if paper {
+                return paper::cancel(&ids);
+            }
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let result = net::once(client.cancel_orders(&ids)).await?;
+            print_cancel_result(&result, &ids, output)?;
         }
 
+        ClobCommand::CancelAll if paper => paper::cancel(&[])?,
         ClobCommand::CancelAll => {
+            if !confirm::ask("Cancel all open orders?", output)? {
+                return Ok(());
+            }
             let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.cancel_all_orders().await?;
-            print_cancel_result(&result, output)?;
+            let result = net::once(client.cancel_all_orders()).await?;
+            print_cancel_result(&result, &[], output)?;
         }
 
-        ClobCommand::CancelMarket { market, asset } => {
+        ClobCommand::CancelMarket {
+            condition_id,
+            market,
+            asset,
+        } => {
+            let market = condition_id.or(market);
+            let question = match (&market, &asset) {
+                (Some(market), _) => format!("Cancel all open orders in market {market}?"),
+                (None, Some(asset)) => format!("Cancel all open orders for token {asset}?"),
+                (None, None) => anyhow::bail!("Specify a market condition ID or --asset"),
+            };
+            if !confirm::ask(&question, output)? {
+                return Ok(());
+            }
             let client = auth::authenticated_clob_client(private_key, signature_type).await?;
             let request = CancelMarketOrderRequest::builder()
                 .maybe_market(market.map(|m| parse_condition_id(&m)).transpose()?)
                 .maybe_asset_id(asset.map(|a| parse_token_id(&a)).transpose()?)
                 .build();
-            let result = client.cancel_market_orders(&request).await?;
-            print_cancel_result(&result, output)?;
+            let result = net::once(client.cancel_market_orders(&request)).await?;
+            print_cancel_result(&result, &[], output)?;
         }
 
         ClobCommand::Trades {
@@ -925,7 +1578,7 @@ async fn execute_trade(
                 .maybe_market(market.map(|m| parse_condition_id(&m)).transpose()?)
                 .maybe_asset_id(asset.map(|a| parse_token_id(&a)).transpose()?)
                 .build();
-            let result = client.trades(&request, cursor).await?;
+            let result = net::retry(|| client.trades(&request, cursor.clone())).await?;
             print_trades(&result, output)?;
         }
 
@@ -936,7 +1589,7 @@ async fn execute_trade(
                 .asset_type(AssetType::from(asset_type))
                 .maybe_token_id(token.map(|t| parse_token_id(&t)).transpose()?)
                 .build();
-            let result = client.balance_allowance(request).await?;
+            let result = net::retry(|| client.balance_allowance(request.clone())).await?;
             print_balance(&result, is_collateral, output)?;
         }
 
@@ -946,18 +1599,20 @@ async fn execute_trade(
                 .asset_type(AssetType::from(asset_type))
                 .maybe_token_id(token.map(|t| parse_token_id(&t)).transpose()?)
                 .build();
-            client.update_balance_allowance(request).await?;
+            net::retry(|| client.update_balance_allowance(request.clone())).await?;
             match output {
-                OutputFormat::Table => println!("Balance allowance updated."),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    println!("Balance allowance updated.")
+                }
                 OutputFormat::Json => {
-                    println!("{}", serde_json::json!({"success": true}));
+                    print_json_line(serde_json::json!({"success": true}));
                 }
             }
         }
 
         ClobCommand::Notifications => {
             let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.notifications().await?;
+            let result = net::retry(|| client.notifications()).await?;
             print_notifications(&result, output)?;
         }
 
@@ -968,11 +1623,13 @@ async fn execute_trade(
             let request = DeleteNotificationsRequest::builder()
                 .notification_ids(notification_ids)
                 .build();
-            client.delete_notifications(&request).await?;
+            net::once(client.delete_notifications(&request)).await?;
             match output {
-                OutputFormat::Table => println!("Notifications deleted."),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    println!("Notifications deleted.")
+                }
                 OutputFormat::Json => {
-                    println!("{}", serde_json::json!({"success": true}));
+                    print_json_line(serde_json::json!({"success": true}));
                 }
             }
         }
@@ -983,6 +1640,250 @@ async fn execute_trade(
     Ok(())
 }
 
+/// The token's taker fee rate, for order previews.
+async fn fee_rate_bps(token_id: U256) -> Result<u32> {
+    let client = clients::clob()?;
+    Ok(net::retry(|| client.fee_rate_bps(token_id)).await?.base_fee)
+}
+
+/// Limit price for decimal `odds` (payout per 1 USDC staked), rounded to the
+/// nearest multiple of `tick`.
+fn odds_to_price(odds: Decimal, tick: Decimal) -> Result<Decimal> {
+    anyhow::ensure!(
+        odds > Decimal::ONE,
+        "Odds must be greater than 1, got {odds}"
+    );
+    let price = ((Decimal::ONE / odds) / tick).round() * tick;
+    let price = price.round_dp(tick.scale()).normalize();
+    anyhow::ensure!(
+        price > Decimal::ZERO && price < Decimal::ONE,
+        "Odds {odds} round to a price of {price}, outside the tradable range"
+    );
+    Ok(price)
+}
+
+/// Shares a `notional` USDC buys at `price`, rounded down to the CLOB's
+/// two-decimal lot size so the order never costs more than asked.
+fn notional_to_size(notional: Decimal, price: Decimal) -> Result<Decimal> {
+    anyhow::ensure!(
+        notional > Decimal::ZERO,
+        "Notional must be positive, got {notional}"
+    );
+    let size = (notional / price).trunc_with_scale(2).normalize();
+    anyhow::ensure!(
+        size > Decimal::ZERO,
+        "Notional {notional} is too small to buy a share at {price}"
+    );
+    Ok(size)
+}
+
+/// Parses `--max-slippage`: a percentage, with or without the `%`.
+fn parse_slippage(s: &str) -> Result<Decimal, String> {
+    let pct = Decimal::from_str(s.trim().trim_end_matches('%'))
+        .map_err(|_| format!("invalid percentage '{s}', expected e.g. 1%"))?;
+    if pct < Decimal::ZERO || pct >= Decimal::ONE_HUNDRED {
+        return Err(format!("must be at least 0% and below 100%, got {s}"));
+    }
+    Ok(pct)
+}
+
+/// How far from the book a market order may fill: a percentage of the best
+/// price, or a price.
+#[derive(Clone, Copy)]
+enum SlippageBound {
+    Percent(Decimal),
+    Price(Decimal),
+}
+
+/// A market order's fill as the book stands.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ExpectedFill {
+    vwap: Decimal,
+    /// The last level the order reaches.
+    worst: Decimal,
+}
+
+/// Walks `book` for a market order for `target` on `side` and fails if its
+/// average price would be worse than `bound` allows.
+fn check_slippage(
+    book: &Book,
+    side: Side,
+    target: Target,
+    bound: SlippageBound,
+) -> Result<ExpectedFill> {
+    let levels = book.crossing(side, None);
+    let buy = side == Side::Buy;
+    let Some(best) = levels.first().map(|l| l.price) else {
+        return Err(crate::errors::validation(format!(
+            "No {} on the book to fill against",
+            if buy { "asks" } else { "bids" }
+        )));
+    };
+    let (fills, _) = crate::paper::take(&levels, target);
+    let shares: Decimal = fills.iter().map(|f| f.size).sum();
+    let usdc: Decimal = fills.iter().map(|f| f.size * f.price).sum();
+    anyhow::ensure!(
+        !shares.is_zero(),
+        crate::errors::validation("The order is too small to fill a share at the best price")
+    );
+    let fill = ExpectedFill {
+        vwap: usdc / shares,
+        worst: fills.last().map_or(best, |f| f.price),
+    };
+    let limit = match bound {
+        SlippageBound::Price(price) => price,
+        SlippageBound::Percent(pct) => {
+            let pct = pct / Decimal::ONE_HUNDRED;
+            best * if buy {
+                Decimal::ONE + pct
+            } else {
+                Decimal::ONE - pct
+            }
+        }
+    };
+    let past = if buy {
+        fill.vwap > limit
+    } else {
+        fill.vwap < limit
+    };
+    anyhow::ensure!(
+        !past,
+        crate::errors::validation(format!(
+            "Expected average price {} is worse than the limit of {} (best {best}, worst level {}); not submitting",
+            fill.vwap.round_dp(4).normalize(),
+            limit.round_dp(4).normalize(),
+            fill.worst
+        ))
+    );
+    Ok(fill)
+}
+
+/// Your resting orders that an order to `side` at `price` would trade
+/// against: those on the other side at a price it reaches. A market order
+/// (no `price`) can reach any of them.
+fn self_crosses(
+    side: Side,
+    price: Option<Decimal>,
+    resting: &[OpenOrderResponse],
+) -> Vec<&OpenOrderResponse> {
+    resting
+        .iter()
+        .filter(|o| match (side, o.side) {
+            (Side::Buy, Side::Sell) => price.is_none_or(|p| o.price <= p),
+            (Side::Sell, Side::Buy) => price.is_none_or(|p| o.price >= p),
+            _ => false,
+        })
+        .collect()
+}
+
+/// Fails if an order to `side` at `price` on `token_id` would cross one of
+/// the wallet's own resting orders. Self-trades pay fees for nothing and can
+/// look like wash trading.
+async fn ensure_no_self_cross(
+    client: &clob::Client<Authenticated<Normal>>,
+    token_id: U256,
+    side: Side,
+    price: Option<Decimal>,
+) -> Result<()> {
+    let request = OrdersRequest::builder().asset_id(token_id).build();
+    let resting = fetch_orders(client, &request).await?;
+    let crossed = self_crosses(side, price, &resting);
+    if crossed.is_empty() {
+        return Ok(());
+    }
+    let orders: Vec<String> = crossed
+        .iter()
+        .map(|o| format!("{} {} @ {}", o.id, o.side, o.price))
+        .collect();
+    Err(crate::errors::validation(format!(
+        "This order would trade against your own resting order{}: {}. Cancel {} first, or leave out --no-self-cross",
+        if orders.len() == 1 { "" } else { "s" },
+        orders.join(", "),
+        if orders.len() == 1 { "it" } else { "them" },
+    )))
+}
+
+/// Refuses a `--client-id` that already has an order, or any in paper mode.
+fn check_client_id(client_id: Option<&str>, paper: bool) -> Result<()> {
+    let Some(id) = client_id else {
+        return Ok(());
+    };
+    anyhow::ensure!(
+        !paper,
+        crate::errors::validation("--client-id isn't supported in paper mode")
+    );
+    match config::load_client_order(id)? {
+        None => Ok(()),
+        Some(existing) => Err(duplicate_client_id(&existing)),
+    }
+}
+
+fn duplicate_client_id(existing: &config::ClientOrder) -> anyhow::Error {
+    let when = existing.submitted_at.to_rfc3339();
+    let id = &existing.client_id;
+    crate::errors::validation(match &existing.status {
+        Some(status) => format!(
+            "Client ID {id} was already used for order {} ({status}) at {when}; not submitting \
+             it again. See `polymarket clob orders --client-id {id}`",
+            existing.order_id
+        ),
+        None => format!(
+            "Client ID {id} was already used at {when} for order {}, but whether the CLOB got it \
+             is unknown; not submitting it again. Check `polymarket clob order {}` and `polymarket \
+             clob trades`",
+            existing.order_id, existing.order_id
+        ),
+    })
+}
+
+/// Signs `order` and posts it, or with `dry_run` just prints the signed payload.
+async fn sign_and_submit(
+    client: &clob::Client<Authenticated<Normal>>,
+    signer: &auth::WalletSigner,
+    order: SignableOrder,
+    client_id: Option<&str>,
+    dry_run: bool,
+    output: &OutputFormat,
+) -> Result<()> {
+    let neg_risk = net::retry(|| client.neg_risk(order.order.tokenId))
+        .await?
+        .neg_risk;
+    let token_id = order.order.tokenId;
+    let side = order.order.side;
+    let signed = auth::sign_order(client, signer, order).await?;
+    let hash = auth::order_hash(&signed.order, neg_risk)?;
+
+    if dry_run {
+        return print_signed_order(&signed, hash, output);
+    }
+    let Some(client_id) = client_id else {
+        let result = net::once(client.post_order(signed)).await?;
+        return print_placed_order(hash, &result, output);
+    };
+    // Recorded before posting, so a command killed mid-request still holds
+    // the ID: whether that order arrived can't be told from here.
+    let mut record = config::ClientOrder {
+        client_id: client_id.to_string(),
+        order_id: hash,
+        token_id: token_id.to_string(),
+        side: if side == 0 { Side::Buy } else { Side::Sell },
+        submitted_at: Utc::now(),
+        status: None,
+    };
+    if !config::reserve_client_order(&record)? {
+        let existing = config::load_client_order(client_id)?.context("Client order vanished")?;
+        return Err(duplicate_client_id(&existing));
+    }
+    let result = net::once(client.post_order(signed)).await?;
+    if result.success {
+        record.status = Some(result.status.to_string());
+        config::save_client_order(&record)?;
+    } else {
+        config::remove_client_order(client_id)?;
+    }
+    print_placed_order(hash, &result, output)
+}
+
 async fn execute_rewards(
     command: ClobCommand,
     output: &OutputFormat,
@@ -992,17 +1893,16 @@ async fn execute_rewards(
     match command {
         ClobCommand::Rewards { date, cursor } => {
             let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client
-                .earnings_for_user_for_day(parse_date(&date)?, cursor)
-                .await?;
+            let date = parse_date(&date)?;
+            let result =
+                net::retry(|| client.earnings_for_user_for_day(date, cursor.clone())).await?;
             print_rewards(&result, output)?;
         }
 
         ClobCommand::Earnings { date } => {
             let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client
-                .total_earnings_for_user_for_day(parse_date(&date)?)
-                .await?;
+            let date = parse_date(&date)?;
+            let result = net::retry(|| client.total_earnings_for_user_for_day(date)).await?;
             print_earnings(&result, output)?;
         }
 
@@ -1011,21 +1911,21 @@ async fn execute_rewards(
             let request = UserRewardsEarningRequest::builder()
                 .date(parse_date(&date)?)
                 .build();
-            let result = client
-                .user_earnings_and_markets_config(&request, cursor)
-                .await?;
+            let result =
+                net::retry(|| client.user_earnings_and_markets_config(&request, cursor.clone()))
+                    .await?;
             print_user_earnings_markets(&result, output)?;
         }
 
         ClobCommand::RewardPercentages => {
             let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.reward_percentages().await?;
+            let result = net::retry(|| client.reward_percentages()).await?;
             print_reward_percentages(&result, output)?;
         }
 
         ClobCommand::CurrentRewards { cursor } => {
             let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.current_rewards(cursor).await?;
+            let result = net::retry(|| client.current_rewards(cursor.clone())).await?;
             print_current_rewards(&result, output)?;
         }
 
@@ -1034,20 +1934,21 @@ async fn execute_rewards(
             cursor,
         } => {
             let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.raw_rewards_for_market(&condition_id, cursor).await?;
+            let result =
+                net::retry(|| client.raw_rewards_for_market(&condition_id, cursor.clone())).await?;
             print_market_reward(&result, output)?;
         }
 
         ClobCommand::OrderScoring { order_id } => {
             let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.is_order_scoring(&order_id).await?;
+            let result = net::retry(|| client.is_order_scoring(&order_id)).await?;
             print_order_scoring(&result, output)?;
         }
 
         ClobCommand::OrdersScoring { order_ids } => {
             let client = auth::authenticated_clob_client(private_key, signature_type).await?;
             let ids: Vec<&str> = order_ids.split(',').map(str::trim).collect();
-            let result = client.are_orders_scoring(&ids).await?;
+            let result = net::retry(|| client.are_orders_scoring(&ids)).await?;
             print_orders_scoring(&result, output)?;
         }
 
@@ -1066,26 +1967,25 @@ async fn execute_account(
     match command {
         ClobCommand::ApiKeys => {
             let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.api_keys().await?;
+            let result = net::retry(|| client.api_keys()).await?;
             print_api_keys(&result, output)?;
         }
 
         ClobCommand::DeleteApiKey => {
             let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.delete_api_key().await?;
+            let result = net::once(client.delete_api_key()).await?;
             print_delete_api_key(&result, output)?;
         }
 
         ClobCommand::CreateApiKey => {
-            let signer = auth::resolve_signer(private_key)?;
-            let client = clob::Client::default();
-            let result = client.create_or_derive_api_key(&signer, None).await?;
+            let signer = auth::resolve_signer(private_key).await?;
+            let result = auth::create_or_derive_api_key(&signer, None).await?;
             print_create_api_key(&result, output)?;
         }
 
         ClobCommand::AccountStatus => {
             let client = auth::authenticated_clob_client(private_key, signature_type).await?;
-            let result = client.closed_only_mode().await?;
+            let result = net::retry(|| client.closed_only_mode()).await?;
             print_account_status(&result, output)?;
         }
 
@@ -1095,9 +1995,172 @@ async fn execute_account(
     Ok(())
 }
 
+/// A live GTC order to test with: `side` ("BUY" or "SELL") at `price`, for
+/// `size` shares of which `matched` have filled. Set `id` or `market` where
+/// they matter.
+#[cfg(test)]
+pub(crate) fn open_order(
+    side: &str,
+    price: Decimal,
+    size: Decimal,
+    matched: Decimal,
+) -> OpenOrderResponse {
+    serde_json::from_value(serde_json::json!({
+        "id": "0x1",
+        "status": "LIVE",
+        "owner": "00000000-0000-0000-0000-000000000000",
+        "maker_address": "0x0000000000000000000000000000000000000001",
+        "market": "0x0000000000000000000000000000000000000000000000000000000000000001",
+        "asset_id": "1",
+        "side": side,
+        "original_size": size,
+        "size_matched": matched,
+        "price": price,
+        "associate_trades": [],
+        "outcome": "Yes",
+        "created_at": 1_700_000_000,
+        "expiration": "0",
+        "order_type": "GTC",
+    }))
+    .unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn odds_to_price_rounds_to_tick() {
+        assert_eq!(odds_to_price(dec!(2.4), dec!(0.01)).unwrap(), dec!(0.42));
+        assert_eq!(odds_to_price(dec!(2.4), dec!(0.001)).unwrap(), dec!(0.417));
+        assert_eq!(odds_to_price(dec!(2), dec!(0.01)).unwrap(), dec!(0.5));
+    }
+
+    #[test]
+    fn odds_to_price_rejects_untradable_odds() {
+        assert!(odds_to_price(dec!(1), dec!(0.01)).is_err());
+        assert!(odds_to_price(dec!(0.5), dec!(0.01)).is_err());
+        assert!(odds_to_price(dec!(1000), dec!(0.01)).is_err());
+    }
+
+    #[test]
+    fn check_limit_order_accepts_prices_on_the_grid() {
+        let params = OrderParams {
+            tick_size: dec!(0.01),
+            min_order_size: dec!(5),
+            neg_risk: false,
+        };
+        assert!(params.check_limit_order(dec!(0.45), dec!(5)).is_ok());
+        assert!(params.check_limit_order(dec!(0.450), dec!(100)).is_ok());
+    }
+
+    #[test]
+    fn check_limit_order_explains_rejections() {
+        let params = OrderParams {
+            tick_size: dec!(0.01),
+            min_order_size: dec!(5),
+            neg_risk: false,
+        };
+        let err = |price, size| {
+            params
+                .check_limit_order(price, size)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            err(dec!(0.455), dec!(10)),
+            "Price 0.455 is not a multiple of this market's tick size 0.01; try 0.45 or 0.46"
+        );
+        assert_eq!(
+            err(dec!(0.005), dec!(10)),
+            "Price 0.005 is not a multiple of this market's tick size 0.01; try 0.01"
+        );
+        assert_eq!(
+            err(dec!(1), dec!(10)),
+            "Price must be between 0 and 1, got 1"
+        );
+        assert_eq!(
+            err(dec!(0), dec!(10)),
+            "Price must be between 0 and 1, got 0"
+        );
+        assert_eq!(
+            err(dec!(0.45), dec!(4.99)),
+            "Size 4.99 is below this market's minimum order of 5 shares"
+        );
+    }
+
+    #[test]
+    fn notional_to_size_rounds_down() {
+        assert_eq!(
+            notional_to_size(dec!(50), dec!(0.42)).unwrap(),
+            dec!(119.04)
+        );
+        assert_eq!(notional_to_size(dec!(10), dec!(0.5)).unwrap(), dec!(20));
+        assert!(notional_to_size(dec!(0), dec!(0.5)).is_err());
+        assert!(notional_to_size(dec!(0.001), dec!(0.5)).is_err());
+    }
+
+    #[test]
+    fn parse_slippage_takes_a_percentage() {
+        assert_eq!(parse_slippage("1%").unwrap(), dec!(1));
+        assert_eq!(parse_slippage("0.5").unwrap(), dec!(0.5));
+        assert!(parse_slippage("100%").is_err());
+        assert!(parse_slippage("-1").is_err());
+        assert!(parse_slippage("lots").is_err());
+    }
+
+    fn slippage_book() -> Book {
+        let level = |price: Decimal, size: Decimal| crate::paper::Level { price, size };
+        Book {
+            bids: vec![level(dec!(0.49), dec!(100)), level(dec!(0.45), dec!(100))],
+            asks: vec![level(dec!(0.52), dec!(100)), level(dec!(0.50), dec!(100))],
+        }
+    }
+
+    #[test]
+    fn check_slippage_bounds_the_average_fill() {
+        let book = slippage_book();
+        // $76 buys 100 at 0.50 and 50 at 0.52: an average of 0.50666…
+        let fill = check_slippage(
+            &book,
+            Side::Buy,
+            Target::Usdc(dec!(76)),
+            SlippageBound::Percent(dec!(2)),
+        )
+        .unwrap();
+        assert_eq!(fill.worst, dec!(0.52));
+        assert!(fill.vwap > dec!(0.506) && fill.vwap < dec!(0.507));
+        assert!(
+            check_slippage(
+                &book,
+                Side::Buy,
+                Target::Usdc(dec!(76)),
+                SlippageBound::Percent(dec!(1)),
+            )
+            .is_err()
+        );
+
+        // 150 shares sell at 0.49 and 0.45: an average of 0.47666…
+        let sell = |bound| check_slippage(&book, Side::Sell, Target::Shares(dec!(150)), bound);
+        assert!(sell(SlippageBound::Price(dec!(0.47))).is_ok());
+        let err = sell(SlippageBound::Price(dec!(0.48))).unwrap_err();
+        assert!(
+            err.to_string().contains("worse than the limit"),
+            "got: {err}"
+        );
+
+        let empty = Book::default();
+        assert!(
+            check_slippage(
+                &empty,
+                Side::Buy,
+                Target::Usdc(dec!(1)),
+                SlippageBound::Percent(dec!(1)),
+            )
+            .is_err()
+        );
+    }
 
     #[test]
     fn parse_token_id_valid_numeric() {
@@ -1156,23 +2219,74 @@ mod tests {
         assert!(parse_token_ids("1,abc,3").is_err());
     }
 
+    fn expiry(expires_in: Option<&str>, expires_at: Option<&str>) -> ExpiryFlags {
+        ExpiryFlags {
+            expires_in: expires_in.map(String::from),
+            expires_at: expires_at.map(String::from),
+        }
+    }
+
     #[test]
-    fn parse_date_valid() {
-        let d = parse_date("2024-06-15").unwrap();
-        assert_eq!(d.to_string(), "2024-06-15");
+    fn expiry_implies_gtd_and_gtd_needs_expiry() {
+        let gtc = || Ok(OrderType::GTC);
+        let (order_type, expiration) = expiry(Some("2h"), None).resolve(None, gtc).unwrap();
+        assert_eq!(order_type, OrderType::GTD);
+        let left = expiration.unwrap() - Utc::now();
+        assert!(left > chrono::Duration::minutes(119), "{left}");
+
+        let (order_type, expiration) = expiry(None, None).resolve(None, gtc).unwrap();
+        assert_eq!((order_type, expiration), (OrderType::GTC, None));
+
+        assert!(
+            expiry(None, None)
+                .resolve(Some(OrderType::GTD), gtc)
+                .is_err()
+        );
+        assert!(
+            expiry(Some("2h"), None)
+                .resolve(Some(OrderType::FOK), gtc)
+                .is_err()
+        );
     }
 
     #[test]
-    fn parse_date_leap_day() {
-        let d = parse_date("2024-02-29").unwrap();
-        assert_eq!(d.to_string(), "2024-02-29");
+    fn expiry_must_be_over_a_minute_away() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        assert!(expiry(Some("60s"), None).expiration(now).is_err());
+        assert!(expiry(Some("61s"), None).expiration(now).is_ok());
+        assert!(expiry(None, Some("1700000030")).expiration(now).is_err());
+        assert_eq!(
+            expiry(None, Some("2023-11-15T00:00:00Z"))
+                .expiration(now)
+                .unwrap(),
+            DateTime::from_timestamp(1_700_006_400, 0)
+        );
+        assert!(expiry(None, Some("tomorrow")).expiration(now).is_err());
+    }
+
+    fn resting(side: &str, price: Decimal) -> OpenOrderResponse {
+        let mut order = open_order(side, price, dec!(10), Decimal::ZERO);
+        order.id = format!("{side}-{price}");
+        order
     }
 
     #[test]
-    fn parse_date_invalid_format() {
-        assert!(parse_date("06/15/2024").is_err());
-        assert!(parse_date("2024-13-01").is_err());
-        assert!(parse_date("not-a-date").is_err());
-        assert!(parse_date("").is_err());
+    fn self_crosses_finds_reachable_opposite_orders() {
+        let book = [
+            resting("SELL", dec!(0.55)),
+            resting("SELL", dec!(0.60)),
+            resting("BUY", dec!(0.40)),
+        ];
+        let ids = |side, price: Option<Decimal>| -> Vec<String> {
+            self_crosses(side, price, &book)
+                .iter()
+                .map(|o| o.id.clone())
+                .collect()
+        };
+        assert_eq!(ids(Side::Buy, Some(dec!(0.55))), ["SELL-0.55"]);
+        assert!(ids(Side::Buy, Some(dec!(0.50))).is_empty());
+        assert_eq!(ids(Side::Buy, None), ["SELL-0.55", "SELL-0.60"]);
+        assert_eq!(ids(Side::Sell, Some(dec!(0.40))), ["BUY-0.40"]);
+        assert!(ids(Side::Sell, Some(dec!(0.41))).is_empty());
     }
 }
@@ -0,0 +1,234 @@
+//! `positions close`: sells the trading wallet's positions in one market, or
+//! all of them, with orders priced off each token's book.
+//!
+//! By default a position goes out at market: a limit order at the deepest bid
+//! its size reaches, of the profile's market order type (FOK unless set), so
+//! it fills now or not at all. With `--limit-offset` it is a GTC limit that
+//! far under the best bid, which takes what the book pays down to there and
+//! rests the remainder.
+
+use anyhow::Result;
+use polymarket_client_sdk::auth::Normal;
+use polymarket_client_sdk::auth::state::Authenticated;
+use polymarket_client_sdk::clob;
+use polymarket_client_sdk::clob::types::request::OrderBookSummaryRequest;
+use polymarket_client_sdk::clob::types::{OrderType, Side};
+use polymarket_client_sdk::data::types::response::Position;
+use polymarket_client_sdk::types::{Address, B256, Decimal};
+use rust_decimal::RoundingStrategy;
+
+use super::batch::{self, BatchOrder};
+use super::clob::order_params;
+use super::portfolio::fetch_positions;
+use super::wallet::resolve_trading_address;
+use crate::auth::{self, WalletSigner};
+use crate::clients;
+use crate::config;
+use crate::confirm;
+use crate::net;
+use crate::output::OutputFormat;
+use crate::output::clob::{BatchOrderResult, print_batch_results};
+use crate::output::portfolio::{PositionExit, format_close_preview};
+use crate::paper::{Book, Level, Target};
+
+/// Decimal places the CLOB takes in an order's size.
+const SIZE_DP: u32 = 2;
+/// Orders posted at the same time.
+const CONCURRENCY: usize = 5;
+
+/// How a position is sold.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExitPrice {
+    /// All of it now, at whatever the book pays.
+    Market,
+    /// A GTC limit this far under the best bid.
+    Offset(Decimal),
+}
+
+/// The price to sell `shares` at against `bids` (best first) and the fills
+/// expected right away, or `None` when nobody is bidding. `tick` is the
+/// market's tick size; an offset price is rounded down onto it.
+pub(crate) fn exit_price(
+    bids: &[Level],
+    shares: Decimal,
+    how: ExitPrice,
+    tick: Decimal,
+) -> Option<(Decimal, Vec<Level>)> {
+    let best = bids.first()?.price;
+    let price = match how {
+        ExitPrice::Market => {
+            let (fills, _) = crate::paper::take(bids, Target::Shares(shares));
+            return fills.last().map(|f| f.price).map(|p| (p, fills));
+        }
+        ExitPrice::Offset(offset) => ((best - offset) / tick).floor() * tick,
+    };
+    let price = price.max(tick).normalize();
+    let reached: Vec<Level> = bids.iter().filter(|l| l.price >= price).copied().collect();
+    let (fills, _) = crate::paper::take(&reached, Target::Shares(shares));
+    Some((price, fills))
+}
+
+/// Positions to close: those in `condition`, or every one, leaving out
+/// resolved markets (which `redeem` pays out) and dust under a lot.
+fn closable(positions: Vec<Position>, condition: Option<B256>) -> Vec<Position> {
+    positions
+        .into_iter()
+        .filter(|p| !p.redeemable && condition.is_none_or(|c| p.condition_id == c))
+        .filter(|p| lots(p.size) > Decimal::ZERO)
+        .collect()
+}
+
+fn lots(size: Decimal) -> Decimal {
+    size.round_dp_with_strategy(SIZE_DP, RoundingStrategy::ToZero)
+}
+
+/// The sell orders that would close `condition`'s positions, or every
+/// position, and the order type they go out as; none when there are no
+/// positions. Those nobody is bidding for are left out with a note on stderr.
+pub(crate) async fn plan(
+    address: Address,
+    condition: Option<B256>,
+    how: ExitPrice,
+) -> Result<(Vec<PositionExit>, OrderType)> {
+    let positions = closable(
+        fetch_positions(&clients::data()?, address).await?,
+        condition,
+    );
+    let order_type = match how {
+        ExitPrice::Market => config::market_order_type()?,
+        ExitPrice::Offset(_) => OrderType::GTC,
+    };
+    let public = clients::clob()?;
+    let mut exits = Vec::with_capacity(positions.len());
+    for position in &positions {
+        let request = OrderBookSummaryRequest::builder()
+            .token_id(position.asset)
+            .build();
+        let book = Book::from(&net::retry(|| public.order_book(&request)).await?);
+        let tick = order_params(&public, position.asset).await?.tick_size;
+        let shares = lots(position.size);
+        let Some((price, fills)) = exit_price(&book.crossing(Side::Sell, None), shares, how, tick)
+        else {
+            eprintln!(
+                "Skipping {} ({}): no bids on the book",
+                position.title, position.outcome
+            );
+            continue;
+        };
+        exits.push(PositionExit {
+            token: position.asset,
+            title: position.title.clone(),
+            outcome: position.outcome.clone(),
+            shares,
+            price,
+            filling: fills.iter().map(|f| f.size).sum(),
+            proceeds: fills.iter().map(|f| f.size * f.price).sum(),
+        });
+    }
+    anyhow::ensure!(
+        !exits.is_empty() || positions.is_empty(),
+        "Nothing to close: no position has a bid"
+    );
+    Ok((exits, order_type))
+}
+
+/// Signs and posts the sell orders for `exits`.
+pub(crate) async fn submit(
+    client: &clob::Client<Authenticated<Normal>>,
+    signer: &WalletSigner,
+    exits: &[PositionExit],
+    order_type: &OrderType,
+    dry_run: bool,
+) -> Vec<BatchOrderResult> {
+    let orders = exits
+        .iter()
+        .map(|e| BatchOrder {
+            token: e.token,
+            side: Side::Sell,
+            price: e.price,
+            size: e.shares,
+            order_type: order_type.clone(),
+            post_only: false,
+        })
+        .collect();
+    batch::submit(client, signer, orders, CONCURRENCY, dry_run).await
+}
+
+pub async fn execute(
+    condition: Option<B256>,
+    how: ExitPrice,
+    dry_run: bool,
+    output: &OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    let address = resolve_trading_address(private_key, signature_type).await?;
+    let (exits, order_type) = plan(address, condition, how).await?;
+    if exits.is_empty() {
+        if let Some(c) = condition {
+            anyhow::bail!("No open position in {c} for {address}");
+        }
+        println!("No open positions to close.");
+        return Ok(());
+    }
+
+    let preview = format_close_preview(&exits, &order_type);
+    if dry_run {
+        confirm::preview(&preview, output);
+    } else if !confirm::confirm(
+        &preview,
+        &format!("Submit {} sell orders?", exits.len()),
+        output,
+    )? {
+        return Ok(());
+    }
+
+    let signer = auth::resolve_signer(private_key).await?;
+    let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+    let results = submit(&client, &signer, &exits, &order_type, dry_run).await;
+    print_batch_results(&results, dry_run, output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn bids() -> Vec<Level> {
+        [
+            (dec!(0.55), dec!(10)),
+            (dec!(0.54), dec!(20)),
+            (dec!(0.50), dec!(100)),
+        ]
+        .into_iter()
+        .map(|(price, size)| Level { price, size })
+        .collect()
+    }
+
+    #[test]
+    fn market_exit_prices_at_the_deepest_bid_it_reaches() {
+        let (price, fills) = exit_price(&bids(), dec!(25), ExitPrice::Market, dec!(0.01)).unwrap();
+        assert_eq!(price, dec!(0.54));
+        let proceeds: Decimal = fills.iter().map(|f| f.size * f.price).sum();
+        assert_eq!(proceeds, dec!(13.6));
+    }
+
+    #[test]
+    fn offset_exit_rounds_down_onto_the_tick_grid() {
+        let (price, fills) = exit_price(
+            &bids(),
+            dec!(50),
+            ExitPrice::Offset(dec!(0.015)),
+            dec!(0.01),
+        )
+        .unwrap();
+        assert_eq!(price, dec!(0.53));
+        let filling: Decimal = fills.iter().map(|f| f.size).sum();
+        assert_eq!(filling, dec!(30));
+
+        let (price, _) =
+            exit_price(&bids(), dec!(5), ExitPrice::Offset(dec!(0.9)), dec!(0.01)).unwrap();
+        assert_eq!(price, dec!(0.01));
+        assert!(exit_price(&[], dec!(5), ExitPrice::Market, dec!(0.01)).is_none());
+    }
+}
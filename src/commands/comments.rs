@@ -1,4 +1,5 @@
 use super::parse_address;
+use crate::net;
 use crate::output::comments::{print_comment_detail, print_comments_table};
 use crate::output::{OutputFormat, print_json};
 use anyhow::Result;
@@ -115,24 +116,28 @@ pub async fn execute(
                 .maybe_ascending(if ascending { Some(true) } else { None })
                 .build();
 
-            let comments = client.comments(&request).await?;
+            let comments = net::retry(|| client.comments(&request)).await?;
 
             match output {
-                OutputFormat::Table => print_comments_table(&comments),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    print_comments_table(&comments)
+                }
                 OutputFormat::Json => print_json(&comments)?,
             }
         }
 
         CommentsCommand::Get { id } => {
             let req = CommentsByIdRequest::builder().id(id).build();
-            let comments = client.comments_by_id(&req).await?;
+            let comments = net::retry(|| client.comments_by_id(&req)).await?;
 
             let Some(comment) = comments.first() else {
                 anyhow::bail!("Comment not found");
             };
 
             match output {
-                OutputFormat::Table => print_comment_detail(comment),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    print_comment_detail(comment)
+                }
                 OutputFormat::Json => print_json(&comment)?,
             }
         }
@@ -153,10 +158,12 @@ pub async fn execute(
                 .maybe_ascending(if ascending { Some(true) } else { None })
                 .build();
 
-            let comments = client.comments_by_user_address(&request).await?;
+            let comments = net::retry(|| client.comments_by_user_address(&request)).await?;
 
             match output {
-                OutputFormat::Table => print_comments_table(&comments),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    print_comments_table(&comments)
+                }
                 OutputFormat::Json => print_json(&comments)?,
             }
         }
@@ -0,0 +1,68 @@
+use anyhow::Result;
+use clap::{Args, CommandFactory};
+use clap_complete::Shell;
+use clap_complete::engine::CompletionCandidate;
+use clap_complete::env::Shells;
+
+use crate::config;
+
+/// Environment variable the generated scripts set when asking the binary for
+/// completions (the `CompleteEnv` protocol).
+pub const COMPLETE_ENV_VAR: &str = "COMPLETE";
+const BIN_NAME: &str = "polymarket";
+
+#[derive(Args)]
+pub struct CompletionsArgs {
+    /// Shell to generate completions for
+    pub shell: Shell,
+    /// Emit a self-contained script without profile and watchlist completion
+    #[arg(long = "static")]
+    pub static_script: bool,
+}
+
+pub fn execute(args: &CompletionsArgs) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    if args.static_script {
+        let mut cmd = crate::Cli::command();
+        clap_complete::generate(args.shell, &mut cmd, BIN_NAME, &mut stdout);
+        return Ok(());
+    }
+
+    // The dynamic script calls back into `polymarket` on every <TAB>, which
+    // is what lets it offer saved profiles and watched markets.
+    let shell = args.shell.to_string();
+    let shells = Shells::builtins();
+    let completer = shells
+        .completer(&shell)
+        .ok_or_else(|| anyhow::anyhow!("Unsupported shell: {shell}"))?;
+    completer.write_registration(COMPLETE_ENV_VAR, BIN_NAME, BIN_NAME, BIN_NAME, &mut stdout)?;
+    Ok(())
+}
+
+/// Saved wallet profile names, for `--profile` and `wallet use`.
+pub fn profile_candidates() -> Vec<CompletionCandidate> {
+    config::list_profiles()
+        .unwrap_or_default()
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+/// Saved contacts, with the address as help text.
+pub fn contact_candidates() -> Vec<CompletionCandidate> {
+    config::load_contacts()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|c| CompletionCandidate::new(c.name).help(Some(c.address.into())))
+        .collect()
+}
+
+/// Watched markets by slug (or ID when a market has none), with the question
+/// as help text.
+pub fn watchlist_candidates() -> Vec<CompletionCandidate> {
+    config::load_watchlist()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|e| CompletionCandidate::new(e.slug.unwrap_or(e.id)).help(Some(e.question.into())))
+        .collect()
+}
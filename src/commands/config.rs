@@ -0,0 +1,330 @@
+//! `config`: view and change the active profile's settings without editing
+//! its JSON by hand.
+
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use clap::builder::{PossibleValue, PossibleValuesParser};
+use clap::{Args, Subcommand};
+use polymarket_client_sdk::types::Decimal;
+use serde_json::{Map, Value};
+
+use crate::config;
+use crate::output::config::{print_config, print_setting_changed};
+use crate::output::{OutputFormat, print_json_line};
+
+#[derive(Args)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub command: ConfigCommand,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Show the active profile's settings (secrets are hidden)
+    Show,
+    /// Change a setting, e.g. `config set network.timeout 10s`
+    Set {
+        /// Setting to change
+        #[arg(value_parser = setting_keys())]
+        key: String,
+        /// New value; JSON for lists such as `notifications`
+        value: String,
+    },
+    /// Reset a setting to its default
+    Unset {
+        /// Setting to reset
+        #[arg(value_parser = setting_keys())]
+        key: String,
+    },
+    /// Open the profile's config in $VISUAL or $EDITOR
+    Edit,
+}
+
+/// Settings `config set` and `unset` accept, with what each one takes.
+/// Wallet fields are left to the `wallet` and `apikey` commands.
+const SETTINGS: &[(&str, &str)] = &[
+    ("chain_id", "Chain ID, e.g. 137"),
+    ("signature_type", "eoa, proxy or gnosis-safe"),
+    ("mode", "live or paper"),
+    (
+        "output",
+        "Output format when --output isn't given: table, json, csv or plain",
+    ),
+    (
+        "odds_format",
+        "How table output shows prices: price, probability, decimal or american",
+    ),
+    (
+        "theme",
+        "Colors of table output: default, colorblind or mono",
+    ),
+    (
+        "defaults.order_type",
+        "Limit order type: GTC, FOK, GTD or FAK",
+    ),
+    (
+        "defaults.market_order_type",
+        "Market order type: FOK or FAK",
+    ),
+    ("defaults.book_depth", "Levels `clob book` shows per side"),
+    ("risk.max_order_size", "Most USDC one order may commit"),
+    (
+        "risk.max_market_notional",
+        "Most USDC held in and bid for in one market",
+    ),
+    (
+        "risk.max_total_exposure",
+        "Most USDC held in and bid for across all markets",
+    ),
+    ("endpoints.rpc_url", "Polygon RPC URL"),
+    ("endpoints.clob_url", "CLOB API URL"),
+    ("endpoints.gamma_url", "Gamma API URL"),
+    ("endpoints.data_url", "Data API URL"),
+    ("network.timeout", "How long a request may take, e.g. 10s"),
+    ("network.retries", "How many times a failed read is retried"),
+    ("network.backoff", "Wait before the first retry, e.g. 500ms"),
+    (
+        "network.rate_limit",
+        "Most API requests per second, 0 for no limit",
+    ),
+    (
+        "log.level",
+        "Log level when -v isn't given: error, warn, info, debug or trace",
+    ),
+    ("log.file", "File to write the log to instead of stderr"),
+    ("notifications", "JSON list of notifiers"),
+    (DEFAULT_PROFILE, "Profile used when --profile isn't given"),
+];
+
+/// Not part of a profile: the pointer file `wallet use` writes.
+const DEFAULT_PROFILE: &str = "default_profile";
+
+/// Fields shown as `<hidden>` by `config show`.
+const SECRETS: &[&str] = &["private_key", "key", "secret", "passphrase"];
+
+fn setting_keys() -> PossibleValuesParser {
+    PossibleValuesParser::new(
+        SETTINGS
+            .iter()
+            .map(|(key, about)| PossibleValue::new(*key).help(*about)),
+    )
+}
+
+pub fn execute(args: ConfigArgs, output: &OutputFormat) -> Result<()> {
+    match args.command {
+        ConfigCommand::Show => {
+            let mut settings = config::load_config_json()?;
+            redact(&mut settings);
+            print_config(
+                &config::active_profile(),
+                &config::default_profile(),
+                &settings,
+                output,
+            )
+        }
+        ConfigCommand::Set { key, value } => {
+            if key == DEFAULT_PROFILE {
+                anyhow::ensure!(
+                    config::profile_exists(&value),
+                    "Profile '{value}' not found. Run `polymarket wallet create --profile {value}` first."
+                );
+                config::set_default_profile(&value)?;
+                return print_setting_changed(&key, Some(&Value::String(value)), output);
+            }
+            let value = parse_value(&key, &value)?;
+            let mut settings = config::load_config_json()?;
+            set_path(&mut settings, &key, value.clone());
+            config::save_config_json(settings)
+                .map_err(|e| anyhow::anyhow!("Invalid value for {key}: {e}"))?;
+            print_setting_changed(&key, Some(&value), output)
+        }
+        ConfigCommand::Unset { key } => {
+            anyhow::ensure!(
+                key != DEFAULT_PROFILE,
+                "Choose another default profile with `config set default_profile <name>`"
+            );
+            let mut settings = config::load_config_json()?;
+            unset_path(&mut settings, &key);
+            config::save_config_json(settings)?;
+            print_setting_changed(&key, None, output)
+        }
+        ConfigCommand::Edit => {
+            config::edit_config(|path| {
+                let editor = std::env::var("VISUAL")
+                    .or_else(|_| std::env::var("EDITOR"))
+                    .unwrap_or_else(|_| "vi".into());
+                let mut words = editor.split_whitespace();
+                let program = words.next().context("$EDITOR is empty")?;
+                let status = Command::new(program)
+                    .args(words)
+                    .arg(path)
+                    .status()
+                    .with_context(|| format!("Failed to run {editor}"))?;
+                anyhow::ensure!(status.success(), "{editor} exited with {status}");
+                Ok(())
+            })?;
+            match output {
+                OutputFormat::Json => print_json_line(serde_json::json!({"saved": true})),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    println!("Saved profile '{}'.", config::active_profile());
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// `value` as JSON when it parses as a number, boolean, list or object, and
+/// as a string otherwise. Strings that must look like numbers (durations,
+/// URLs) never parse as JSON, so they come through unchanged.
+fn parse_value(key: &str, value: &str) -> Result<Value> {
+    match key {
+        "signature_type" => anyhow::ensure!(
+            ["eoa", "proxy", "gnosis-safe"].contains(&value),
+            "Invalid signature_type '{value}': use eoa, proxy or gnosis-safe"
+        ),
+        "defaults.order_type" | "defaults.market_order_type" => {
+            let order_type = value.to_uppercase();
+            anyhow::ensure!(
+                ["GTC", "FOK", "GTD", "FAK"].contains(&order_type.as_str()),
+                "Invalid {key} '{value}': use GTC, FOK, GTD or FAK"
+            );
+            return Ok(Value::String(order_type));
+        }
+        "log.level" => {
+            let level = value.to_lowercase();
+            anyhow::ensure!(
+                ["error", "warn", "info", "debug", "trace"].contains(&level.as_str()),
+                "Invalid log.level '{value}': use error, warn, info, debug or trace"
+            );
+            return Ok(Value::String(level));
+        }
+        "risk.max_order_size" | "risk.max_market_notional" | "risk.max_total_exposure" => {
+            let limit: Decimal = value
+                .parse()
+                .ok()
+                .filter(|l: &Decimal| *l > Decimal::ZERO)
+                .with_context(|| format!("Invalid {key} '{value}': use a positive USDC amount"))?;
+            return Ok(Value::String(limit.normalize().to_string()));
+        }
+        "network.timeout" | "network.backoff" => {
+            super::parse_duration(value).with_context(|| format!("Invalid {key}"))?;
+        }
+        _ => {}
+    }
+    Ok(serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.into())))
+}
+
+/// Sets the dotted `key` in `settings`, creating parent objects as needed.
+fn set_path(settings: &mut Value, key: &str, value: Value) {
+    let mut node = settings;
+    let mut parts = key.split('.').peekable();
+    while let Some(part) = parts.next() {
+        if !node.is_object() {
+            *node = Value::Object(Map::new());
+        }
+        let object = node.as_object_mut().expect("just made an object");
+        if parts.peek().is_none() {
+            object.insert(part.to_string(), value);
+            return;
+        }
+        node = object
+            .entry(part)
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+}
+
+/// Removes the dotted `key` from `settings`; its default applies again.
+fn unset_path(settings: &mut Value, key: &str) {
+    let (parent, last) = match key.rsplit_once('.') {
+        Some((parent, last)) => (
+            settings.pointer_mut(&format!("/{}", parent.replace('.', "/"))),
+            last,
+        ),
+        None => (Some(settings), key),
+    };
+    if let Some(Value::Object(object)) = parent {
+        object.remove(last);
+    }
+}
+
+/// Replaces every secret in `settings` with `<hidden>`.
+fn redact(settings: &mut Value) {
+    match settings {
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                if SECRETS.contains(&key.as_str()) && value.as_str().is_some_and(|s| !s.is_empty())
+                {
+                    *value = Value::String("<hidden>".into());
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_value_keeps_durations_and_urls_as_strings() {
+        assert_eq!(parse_value("network.timeout", "10s").unwrap(), json!("10s"));
+        assert_eq!(parse_value("network.retries", "3").unwrap(), json!(3));
+        assert_eq!(
+            parse_value("endpoints.clob_url", "http://localhost:8080").unwrap(),
+            json!("http://localhost:8080")
+        );
+        assert_eq!(
+            parse_value("notifications", r#"[{"format": "desktop"}]"#).unwrap(),
+            json!([{"format": "desktop"}])
+        );
+        assert!(parse_value("signature_type", "safe").is_err());
+        assert_eq!(
+            parse_value("defaults.order_type", "fak").unwrap(),
+            json!("FAK")
+        );
+        assert!(parse_value("defaults.market_order_type", "market").is_err());
+        assert_eq!(
+            parse_value("risk.max_order_size", "250.00").unwrap(),
+            json!("250")
+        );
+        assert!(parse_value("risk.max_total_exposure", "0").is_err());
+    }
+
+    #[test]
+    fn set_and_unset_nested_keys() {
+        let mut settings = json!({"chain_id": 137});
+        set_path(&mut settings, "network.timeout", json!("10s"));
+        set_path(&mut settings, "network.retries", json!(3));
+        assert_eq!(
+            settings,
+            json!({"chain_id": 137, "network": {"timeout": "10s", "retries": 3}})
+        );
+        unset_path(&mut settings, "network.timeout");
+        unset_path(&mut settings, "endpoints.rpc_url");
+        assert_eq!(
+            settings,
+            json!({"chain_id": 137, "network": {"retries": 3}})
+        );
+    }
+
+    #[test]
+    fn redact_hides_keys_and_api_secrets() {
+        let mut settings = json!({
+            "private_key": "0xabc",
+            "api_credentials": {"address": "0x1", "key": "k", "secret": "s", "passphrase": "p"},
+            "endpoints": {"clob_url": "http://localhost"},
+        });
+        redact(&mut settings);
+        assert_eq!(settings["private_key"], "<hidden>");
+        assert_eq!(settings["api_credentials"]["address"], "0x1");
+        assert_eq!(settings["api_credentials"]["secret"], "<hidden>");
+        assert_eq!(settings["endpoints"]["clob_url"], "http://localhost");
+    }
+}
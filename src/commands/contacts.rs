@@ -0,0 +1,107 @@
+//! `contacts`: an address book, so transfers and funders can be given by
+//! name (see `resolve_address`).
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use clap_complete::engine::ArgValueCandidates;
+
+use super::completions::contact_candidates;
+use crate::config::{self, Contact};
+use crate::output::contacts::print_contacts;
+use crate::output::{OutputFormat, print_json_line};
+
+#[derive(Args)]
+pub struct ContactsArgs {
+    #[command(subcommand)]
+    pub command: ContactsCommand,
+}
+
+#[derive(Subcommand)]
+pub enum ContactsCommand {
+    /// Save an address under a name
+    Add {
+        /// Name to use in place of the address, e.g. `savings`
+        name: String,
+        /// Address (0x...)
+        address: String,
+    },
+    /// List saved contacts
+    #[command(visible_alias = "ls")]
+    List,
+    /// Forget a contact
+    #[command(visible_alias = "rm")]
+    Remove {
+        /// Contact name
+        #[arg(add = ArgValueCandidates::new(contact_candidates))]
+        name: String,
+    },
+}
+
+pub fn execute(args: ContactsArgs, output: &OutputFormat) -> Result<()> {
+    match args.command {
+        ContactsCommand::Add { name, address } => {
+            check_name(&name)?;
+            let address = super::parse_address(&address)?;
+            let mut contacts = config::load_contacts()?;
+            anyhow::ensure!(
+                !contacts.iter().any(|c| c.name.eq_ignore_ascii_case(&name)),
+                "A contact named '{name}' already exists. Remove it first to change its address."
+            );
+            contacts.push(Contact {
+                name: name.clone(),
+                address: address.to_string(),
+            });
+            config::save_contacts(&contacts)?;
+            match output {
+                OutputFormat::Json => print_json_line(
+                    serde_json::json!({"name": name, "address": address.to_string()}),
+                ),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    println!("Saved {name}: {address}");
+                }
+            }
+        }
+        ContactsCommand::List => print_contacts(&config::load_contacts()?, output)?,
+        ContactsCommand::Remove { name } => {
+            let mut contacts = config::load_contacts()?;
+            let before = contacts.len();
+            contacts.retain(|c| !c.name.eq_ignore_ascii_case(&name));
+            anyhow::ensure!(contacts.len() < before, "No contact named '{name}'");
+            config::save_contacts(&contacts)?;
+            match output {
+                OutputFormat::Json => print_json_line(serde_json::json!({"removed": name})),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    println!("Removed {name}.");
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A name must not be mistaken for an address, and must be one shell word.
+fn check_name(name: &str) -> Result<()> {
+    anyhow::ensure!(!name.is_empty(), "Contact name must not be empty");
+    anyhow::ensure!(
+        !name.to_ascii_lowercase().starts_with("0x"),
+        "Contact name must not start with 0x"
+    );
+    anyhow::ensure!(
+        !name.chars().any(char::is_whitespace),
+        "Contact name must not contain spaces"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_name_rejects_address_like_names() {
+        assert!(check_name("savings").is_ok());
+        assert!(check_name("0xabc").is_err());
+        assert!(check_name("my savings").is_err());
+        assert!(check_name("").is_err());
+    }
+}
@@ -0,0 +1,27 @@
+//! `convert`: a price in every convention it is quoted in, from any one of
+//! them (see [`crate::odds`]).
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::odds::{self, OddsFormat};
+use crate::output::OutputFormat;
+use crate::output::convert::print_conversion;
+
+#[derive(Args)]
+pub struct ConvertArgs {
+    /// Price (0.42), probability (42%), decimal odds (2.38) or American
+    /// odds (+138)
+    #[arg(allow_negative_numbers = true)]
+    pub value: String,
+
+    /// Read the value in this convention instead of telling it from its
+    /// form, e.g. for American odds without a sign
+    #[arg(long, value_enum)]
+    pub from: Option<OddsFormat>,
+}
+
+pub fn execute(args: &ConvertArgs, output: &OutputFormat) -> Result<()> {
+    let price = odds::parse(&args.value, args.from)?;
+    print_conversion(price, output)
+}
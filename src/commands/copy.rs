@@ -0,0 +1,475 @@
+//! `copy watch`: follow another wallet's public trades and mirror them.
+//!
+//! The data API is polled for the wallet's trades; each new one is scaled
+//! by `--scale` and sent as a market order, buys capped so what this run
+//! has spent (less what it has sold back) stays under `--max-exposure`.
+//! Sells only ever unwind what this run bought, so the followed wallet
+//! selling something it held before doesn't sell your own positions. Each
+//! order is held to the profile's risk limits too (see `risk.rs`).
+//!
+//! Orders are only sent with `--live`, and only for a wallet that has been
+//! followed in a dry run first.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use polymarket_client_sdk::auth::Normal;
+use polymarket_client_sdk::auth::state::Authenticated;
+use polymarket_client_sdk::clob;
+use polymarket_client_sdk::clob::types::{Amount, OrderType, Side};
+use polymarket_client_sdk::data;
+use polymarket_client_sdk::data::types::Side as TradeSide;
+use polymarket_client_sdk::data::types::request::TradesRequest;
+use polymarket_client_sdk::data::types::response::Trade;
+use polymarket_client_sdk::types::{Address, Decimal, U256};
+
+use super::clob::{CliOrderType, order_params};
+use super::resolve_address;
+use super::wallet::resolve_trading_address;
+use crate::auth;
+use crate::clients;
+use crate::config;
+use crate::confirm;
+use crate::errors;
+use crate::net;
+use crate::output::OutputFormat;
+use crate::output::copy::{CopyEvent, print_copy_event};
+use crate::procs;
+use crate::risk::{self, Proposed};
+
+/// Trades fetched per poll; more than a wallet makes between polls.
+const POLL_LIMIT: i32 = 100;
+/// The CLOB's smallest market buy.
+const MIN_BUY_USDC: Decimal = Decimal::ONE;
+
+#[derive(Args)]
+pub struct CopyArgs {
+    #[command(subcommand)]
+    pub command: CopyCommand,
+}
+
+#[derive(Subcommand)]
+pub enum CopyCommand {
+    /// Follow a wallet's trades as they happen and mirror them (dry run
+    /// unless --live)
+    Watch {
+        /// Wallet address (0x...) or saved contact name
+        address: String,
+        /// Fraction of each trade to mirror, e.g. 0.1 for a tenth
+        #[arg(long, default_value = "0.1")]
+        scale: Decimal,
+        /// Most USDC this run keeps in mirrored positions
+        #[arg(long)]
+        max_exposure: Decimal,
+        /// Seconds between checks for new trades
+        #[arg(long, default_value = "10")]
+        interval: u64,
+        /// Order type: FOK or FAK (default: FOK, or the profile's
+        /// defaults.market_order_type)
+        #[arg(long, alias = "type")]
+        order_type: Option<CliOrderType>,
+        /// Send real orders. Needs a dry run of the same wallet first
+        #[arg(long)]
+        live: bool,
+    },
+}
+
+/// What to do about one of the followed wallet's trades.
+#[derive(Debug, PartialEq)]
+enum Plan {
+    /// Spend this much USDC.
+    Buy(Decimal),
+    /// Sell this many shares.
+    Sell(Decimal),
+    Skip(String),
+}
+
+/// What this run has mirrored, which bounds what it mirrors next.
+#[derive(Debug, Default)]
+struct Mirror {
+    scale: Decimal,
+    max_exposure: Decimal,
+    /// USDC spent on buys less USDC received from sells.
+    exposure: Decimal,
+    /// Shares bought by this run and not yet sold, per token.
+    held: HashMap<U256, Decimal>,
+}
+
+impl Mirror {
+    fn new(scale: Decimal, max_exposure: Decimal) -> Self {
+        Self {
+            scale,
+            max_exposure,
+            ..Self::default()
+        }
+    }
+
+    fn plan(&self, trade: &Trade) -> Plan {
+        match trade.side {
+            TradeSide::Buy => {
+                let wanted = trade.size * trade.price * self.scale;
+                let room = (self.max_exposure - self.exposure).max(Decimal::ZERO);
+                let usdc = wanted.min(room).round_dp(2);
+                if usdc >= MIN_BUY_USDC {
+                    Plan::Buy(usdc)
+                } else if wanted < MIN_BUY_USDC {
+                    Plan::Skip(format!("${:.2} is under the $1 minimum", wanted))
+                } else {
+                    Plan::Skip("max exposure reached".into())
+                }
+            }
+            TradeSide::Sell => {
+                let held = self.held.get(&trade.asset).copied().unwrap_or_default();
+                let shares = (trade.size * self.scale).min(held).round_dp(2);
+                if shares > Decimal::ZERO {
+                    Plan::Sell(shares)
+                } else {
+                    Plan::Skip("nothing mirrored to sell".into())
+                }
+            }
+            _ => Plan::Skip("unknown side".into()),
+        }
+    }
+
+    /// Counts a fill of `shares` for `usdc`.
+    fn record(&mut self, token: U256, side: Side, shares: Decimal, usdc: Decimal) {
+        let held = self.held.entry(token).or_default();
+        if side == Side::Buy {
+            *held += shares;
+            self.exposure += usdc;
+        } else {
+            *held = (*held - shares).max(Decimal::ZERO);
+            self.exposure = (self.exposure - usdc).max(Decimal::ZERO);
+        }
+    }
+
+    fn exposure(&self) -> Decimal {
+        self.exposure
+    }
+}
+
+/// A trade's identity: the data API has no trade ID, and one transaction
+/// can fill the same wallet several times.
+fn trade_key(t: &Trade) -> String {
+    format!(
+        "{}:{}:{}:{}:{}",
+        t.transaction_hash, t.asset, t.side, t.size, t.price
+    )
+}
+
+/// The trades in `page` not in `seen`, oldest first, adding them to it.
+fn new_trades(page: Vec<Trade>, seen: &mut HashSet<String>) -> Vec<Trade> {
+    let mut fresh: Vec<Trade> = page
+        .into_iter()
+        .filter(|t| seen.insert(trade_key(t)))
+        .collect();
+    fresh.sort_by_key(|t| t.timestamp);
+    fresh
+}
+
+pub async fn execute(
+    args: CopyArgs,
+    output: &OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    match args.command {
+        CopyCommand::Watch {
+            address,
+            scale,
+            max_exposure,
+            interval,
+            order_type,
+            live,
+        } => {
+            anyhow::ensure!(
+                scale > Decimal::ZERO,
+                errors::validation("--scale must be positive")
+            );
+            anyhow::ensure!(
+                max_exposure > Decimal::ZERO,
+                errors::validation("--max-exposure must be positive")
+            );
+            anyhow::ensure!(
+                interval > 0,
+                errors::validation("--interval must be at least 1 second")
+            );
+            let leader = resolve_address(&address)?;
+            let key = leader.to_string().to_lowercase();
+            let mut dry_runs = config::load_copy_dry_runs()?;
+            if live {
+                anyhow::ensure!(!config::paper_mode(), "{}", super::paper::UNSUPPORTED_MSG);
+                anyhow::ensure!(
+                    dry_runs.contains_key(&key),
+                    errors::validation(format!(
+                        "Run `polymarket copy watch {leader}` without --live first to see \
+                         what would be mirrored"
+                    ))
+                );
+                if !confirm::ask(
+                    &format!("Mirror {leader}'s trades with real orders, up to ${max_exposure}?"),
+                    output,
+                )? {
+                    return Ok(());
+                }
+            }
+            let order_type = match order_type {
+                Some(order_type) => order_type.into(),
+                None => config::market_order_type()?,
+            };
+            let trader = if live {
+                let signer = auth::resolve_signer(private_key).await?;
+                let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+                Some((client, signer))
+            } else {
+                None
+            };
+            let watch = Watch {
+                leader,
+                mirror: Mirror::new(scale, max_exposure),
+                order_type,
+                interval: Duration::from_secs(interval),
+                private_key: private_key.map(str::to_string),
+                signature_type: signature_type.map(str::to_string),
+            };
+            watch
+                .run(&clients::data()?, trader.as_ref(), &mut dry_runs, output)
+                .await
+        }
+    }
+}
+
+struct Watch {
+    leader: Address,
+    mirror: Mirror,
+    order_type: OrderType,
+    interval: Duration,
+    /// The global flags, for the risk check to find the trading wallet.
+    private_key: Option<String>,
+    signature_type: Option<String>,
+}
+
+impl Watch {
+    /// Polls until Ctrl-C. Trades made before the start are only marked
+    /// seen; a dry run is recorded once the first poll succeeds.
+    async fn run(
+        mut self,
+        data: &data::Client,
+        trader: Option<&(clob::Client<Authenticated<Normal>>, auth::WalletSigner)>,
+        dry_runs: &mut BTreeMap<String, chrono::DateTime<chrono::Utc>>,
+        output: &OutputFormat,
+    ) -> Result<()> {
+        let request = TradesRequest::builder()
+            .user(self.leader)
+            .limit(POLL_LIMIT)?
+            .taker_only(false)
+            .build();
+        let _running = procs::register(&format!("copy watch {}", self.leader));
+        let mut seen = HashSet::new();
+        new_trades(net::retry(|| data.trades(&request)).await?, &mut seen);
+        if trader.is_none() {
+            dry_runs.insert(self.leader.to_string().to_lowercase(), chrono::Utc::now());
+            config::save_copy_dry_runs(dry_runs)?;
+        }
+        eprintln!(
+            "Following {}{}. Ctrl-C to stop.",
+            self.leader,
+            if trader.is_some() {
+                ""
+            } else {
+                " (dry run: no orders are sent)"
+            }
+        );
+
+        loop {
+            tokio::select! {
+                () = tokio::time::sleep(self.interval) => {}
+                _ = tokio::signal::ctrl_c() => {
+                    eprintln!(
+                        "Stopped. Exposure: ${:.2} of ${:.2}.",
+                        self.mirror.exposure(),
+                        self.mirror.max_exposure
+                    );
+                    return Ok(());
+                }
+            }
+            let page = match net::retry(|| data.trades(&request)).await {
+                Ok(page) => page,
+                Err(e) => {
+                    eprintln!("Trade check failed: {e}");
+                    continue;
+                }
+            };
+            for trade in new_trades(page, &mut seen) {
+                let event = self.mirror_trade(&trade, trader).await;
+                print_copy_event(&event, output);
+            }
+        }
+    }
+
+    async fn mirror_trade(
+        &mut self,
+        trade: &Trade,
+        trader: Option<&(clob::Client<Authenticated<Normal>>, auth::WalletSigner)>,
+    ) -> CopyEvent {
+        let plan = self.mirror.plan(trade);
+        let (side, amount) = match plan {
+            Plan::Buy(usdc) => (Side::Buy, usdc),
+            Plan::Sell(shares) => (Side::Sell, shares),
+            Plan::Skip(reason) => return CopyEvent::skipped(trade, reason),
+        };
+        let Some((client, signer)) = trader else {
+            // A dry run assumes a fill at the followed wallet's price.
+            let (shares, usdc) = if side == Side::Buy {
+                (amount / trade.price, amount)
+            } else {
+                (amount, amount * trade.price)
+            };
+            self.mirror.record(trade.asset, side, shares, usdc);
+            return CopyEvent::simulated(trade, side, shares, usdc, self.mirror.exposure());
+        };
+        match self.send(client, signer, trade.asset, side, amount).await {
+            Ok((shares, usdc)) => {
+                self.mirror.record(trade.asset, side, shares, usdc);
+                CopyEvent::filled(trade, side, shares, usdc, self.mirror.exposure())
+            }
+            Err(e) => CopyEvent::failed(trade, side, format!("{e:#}")),
+        }
+    }
+
+    /// Sends a market order and returns the shares and USDC it filled.
+    async fn send(
+        &self,
+        client: &clob::Client<Authenticated<Normal>>,
+        signer: &auth::WalletSigner,
+        token: U256,
+        side: Side,
+        amount: Decimal,
+    ) -> Result<(Decimal, Decimal)> {
+        order_params(client, token).await?;
+        risk::check(
+            client,
+            &[Proposed::market(token, side, None, amount)],
+            resolve_trading_address(self.private_key.as_deref(), self.signature_type.as_deref()),
+        )
+        .await?;
+        let order = client
+            .market_order()
+            .token_id(token)
+            .side(side)
+            .amount(if side == Side::Sell {
+                Amount::shares(amount)?
+            } else {
+                Amount::usdc(amount)?
+            })
+            .order_type(self.order_type.clone())
+            .build()
+            .await?;
+        let signed = auth::sign_order(client, signer, order).await?;
+        let result = net::once(client.post_order(signed)).await?;
+        if !result.success {
+            anyhow::bail!(
+                "{}",
+                result
+                    .error_msg
+                    .filter(|e| !e.is_empty())
+                    .unwrap_or_else(|| "not filled".into())
+            );
+        }
+        Ok(if side == Side::Sell {
+            (result.making_amount, result.taking_amount)
+        } else {
+            (result.taking_amount, result.making_amount)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn trade(side: &str, size: &str, price: &str, tx: u8) -> Trade {
+        serde_json::from_value(serde_json::json!({
+            "proxyWallet": "0x0000000000000000000000000000000000000001",
+            "side": side,
+            "asset": "7",
+            "conditionId": format!("0x{}", "00".repeat(32)),
+            "size": size,
+            "price": price,
+            "timestamp": 1_700_000_000 + i64::from(tx),
+            "title": "Will it rain?",
+            "slug": "rain",
+            "icon": "",
+            "eventSlug": "rain",
+            "outcome": "Yes",
+            "outcomeIndex": 0,
+            "transactionHash": format!("0x{}", format!("{tx:02x}").repeat(32)),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn buys_are_scaled_and_capped_by_exposure() {
+        let mut mirror = Mirror::new(dec!(0.1), dec!(50));
+        // $400 at 0.5 scaled to a tenth.
+        assert_eq!(
+            mirror.plan(&trade("BUY", "800", "0.5", 1)),
+            Plan::Buy(dec!(40))
+        );
+        mirror.record(U256::from(7), Side::Buy, dec!(80), dec!(40));
+        // Only $10 of room left.
+        assert_eq!(
+            mirror.plan(&trade("BUY", "800", "0.5", 2)),
+            Plan::Buy(dec!(10))
+        );
+        mirror.record(U256::from(7), Side::Buy, dec!(20), dec!(10));
+        assert_eq!(
+            mirror.plan(&trade("BUY", "800", "0.5", 3)),
+            Plan::Skip("max exposure reached".into())
+        );
+        assert!(matches!(
+            mirror.plan(&trade("BUY", "10", "0.5", 4)),
+            Plan::Skip(_)
+        ));
+    }
+
+    #[test]
+    fn sells_only_unwind_what_was_mirrored() {
+        let mut mirror = Mirror::new(dec!(0.1), dec!(100));
+        assert_eq!(
+            mirror.plan(&trade("SELL", "500", "0.6", 1)),
+            Plan::Skip("nothing mirrored to sell".into())
+        );
+        mirror.record(U256::from(7), Side::Buy, dec!(30), dec!(15));
+        assert_eq!(
+            mirror.plan(&trade("SELL", "500", "0.6", 2)),
+            Plan::Sell(dec!(30))
+        );
+        assert_eq!(
+            mirror.plan(&trade("SELL", "100", "0.6", 3)),
+            Plan::Sell(dec!(10))
+        );
+        mirror.record(U256::from(7), Side::Sell, dec!(30), dec!(18));
+        assert_eq!(mirror.exposure(), Decimal::ZERO);
+    }
+
+    #[test]
+    fn new_trades_are_oldest_first_and_seen_once() {
+        let mut seen = HashSet::new();
+        let first = new_trades(vec![trade("BUY", "1", "0.5", 1)], &mut seen);
+        assert_eq!(first.len(), 1);
+        let next = new_trades(
+            vec![
+                trade("SELL", "1", "0.5", 3),
+                trade("BUY", "2", "0.5", 2),
+                trade("BUY", "1", "0.5", 1),
+            ],
+            &mut seen,
+        );
+        let stamps: Vec<i64> = next.iter().map(|t| t.timestamp).collect();
+        assert_eq!(stamps, vec![1_700_000_002, 1_700_000_003]);
+    }
+}
@@ -1,17 +1,34 @@
-use alloy::primitives::U256;
+#![allow(clippy::exhaustive_enums, reason = "Generated by sol! macro")]
+#![allow(clippy::exhaustive_structs, reason = "Generated by sol! macro")]
+
+use alloy::contract::RawCallBuilder;
+use alloy::primitives::{Bytes, U256};
+use alloy::sol;
+use alloy::sol_types::SolCall;
 use anyhow::{Context, Result};
 use clap::{Args, Subcommand};
+use polymarket_client_sdk::auth::Signer as _;
 use polymarket_client_sdk::ctf::types::{
     CollectionIdRequest, ConditionIdRequest, MergePositionsRequest, PositionIdRequest,
     RedeemNegRiskRequest, RedeemPositionsRequest, SplitPositionRequest,
 };
 use polymarket_client_sdk::types::{Address, B256};
-use polymarket_client_sdk::{POLYGON, ctf};
+use polymarket_client_sdk::{POLYGON, contract_config, ctf};
 use rust_decimal::Decimal;
 
-use crate::auth;
+use super::redeem::{Held, group, read_balances, redeem_call};
+use crate::auth::{self, WalletSigner};
+use crate::gas::{GasFlags, GasSettings};
 use crate::output::OutputFormat;
 use crate::output::ctf as ctf_output;
+use crate::{clients, net};
+
+sol! {
+    interface INegRiskAdapter {
+        function splitPosition(bytes32 conditionId, uint256 amount) external;
+        function mergePositions(bytes32 conditionId, uint256 amount) external;
+    }
+}
 
 const USDC_DECIMALS: Decimal = Decimal::from_parts(1_000_000, 0, 0, false, 0);
 
@@ -26,8 +43,11 @@ pub enum CtfCommand {
     /// Split collateral into outcome tokens
     Split {
         /// Condition ID (0x-prefixed 32-byte hex)
+        #[arg(required_unless_present = "condition", conflicts_with = "condition")]
+        condition_id: Option<String>,
+        /// Condition ID, as a flag instead of the positional argument
         #[arg(long)]
-        condition: String,
+        condition: Option<String>,
         /// Amount in USDC (e.g. 10 for $10)
         #[arg(long)]
         amount: String,
@@ -40,12 +60,17 @@ pub enum CtfCommand {
         /// Parent collection ID for nested positions (defaults to zero)
         #[arg(long)]
         parent_collection: Option<String>,
+        #[command(flatten)]
+        gas: GasFlags,
     },
     /// Merge outcome tokens back into collateral
     Merge {
         /// Condition ID (0x-prefixed 32-byte hex)
+        #[arg(required_unless_present = "condition", conflicts_with = "condition")]
+        condition_id: Option<String>,
+        /// Condition ID, as a flag instead of the positional argument
         #[arg(long)]
-        condition: String,
+        condition: Option<String>,
         /// Amount in USDC (e.g. 10 for $10)
         #[arg(long)]
         amount: String,
@@ -58,6 +83,8 @@ pub enum CtfCommand {
         /// Parent collection ID for nested positions (defaults to zero)
         #[arg(long)]
         parent_collection: Option<String>,
+        #[command(flatten)]
+        gas: GasFlags,
     },
     /// Redeem winning tokens after market resolution
     Redeem {
@@ -73,6 +100,8 @@ pub enum CtfCommand {
         /// Parent collection ID for nested positions (defaults to zero)
         #[arg(long)]
         parent_collection: Option<String>,
+        #[command(flatten)]
+        gas: GasFlags,
     },
     /// Redeem neg-risk positions
     RedeemNegRisk {
@@ -82,6 +111,8 @@ pub enum CtfCommand {
         /// Comma-separated amounts in USDC for each outcome (e.g. "10,5")
         #[arg(long)]
         amounts: String,
+        #[command(flatten)]
+        gas: GasFlags,
     },
     /// Calculate a condition ID from oracle, question, and outcome count
     ConditionId {
@@ -118,7 +149,7 @@ pub enum CtfCommand {
     },
 }
 
-fn usdc_to_raw(val: Decimal) -> Result<U256> {
+pub(crate) fn usdc_to_raw(val: Decimal) -> Result<U256> {
     let raw = val * USDC_DECIMALS;
     anyhow::ensure!(
         raw.fract().is_zero(),
@@ -130,7 +161,7 @@ fn usdc_to_raw(val: Decimal) -> Result<U256> {
     Ok(U256::from(raw_u64))
 }
 
-fn parse_usdc_amount(s: &str) -> Result<U256> {
+pub(crate) fn parse_usdc_amount(s: &str) -> Result<U256> {
     let val: Decimal = s.trim().parse().context(format!("Invalid amount: {s}"))?;
     anyhow::ensure!(val > Decimal::ZERO, "Amount must be positive");
     usdc_to_raw(val)
@@ -171,6 +202,15 @@ fn parse_optional_parent(parent: Option<&str>) -> Result<B256> {
     }
 }
 
+/// `split` and `merge` take the condition positionally or, as before, with
+/// `--condition`; clap guarantees exactly one.
+fn parse_condition_arg(positional: Option<String>, flag: Option<String>) -> Result<B256> {
+    let condition = positional
+        .or(flag)
+        .ok_or_else(|| anyhow::anyhow!("Specify a condition ID"))?;
+    super::parse_condition_id(&condition)
+}
+
 fn resolve_collateral(collateral: &str) -> Result<Address> {
     super::parse_address(collateral)
 }
@@ -183,25 +223,108 @@ fn default_index_sets() -> Vec<U256> {
     vec![U256::from(1), U256::from(2)]
 }
 
+/// Whether `condition_id` is a neg-risk market. Its outcome tokens are backed
+/// by the adapter's wrapped collateral, so splits, merges and redemptions go
+/// through the neg-risk adapter instead of the CTF contract.
+async fn is_neg_risk(condition_id: B256) -> Result<bool> {
+    let client = clients::clob()?;
+    let id = condition_id.to_string();
+    Ok(net::retry(|| client.market(&id))
+        .await
+        .with_context(|| format!("Failed to look up market {condition_id}"))?
+        .neg_risk)
+}
+
+/// The adapter only handles USDC, both outcomes and top-level positions;
+/// anything else is a request for the CTF contract, which can't serve a
+/// neg-risk market.
+fn ensure_adapter_defaults(
+    condition_id: B256,
+    collateral: Address,
+    custom_sets: bool,
+    parent: B256,
+) -> Result<()> {
+    let usdc = contract_config(POLYGON, true)
+        .context("No neg-risk contract config for Polygon")?
+        .collateral;
+    anyhow::ensure!(
+        collateral == usdc && !custom_sets && parent.is_zero(),
+        "{condition_id} is a neg-risk market, which goes through the neg-risk adapter: leave \
+         out --collateral, --partition/--index-sets and --parent-collection"
+    );
+    Ok(())
+}
+
+/// Sends `data` to the neg-risk adapter from `signer` and waits for it to be
+/// mined.
+async fn send_to_adapter(
+    signer: WalletSigner,
+    gas: GasSettings,
+    data: Bytes,
+    what: &str,
+) -> Result<(B256, u64)> {
+    let adapter = contract_config(POLYGON, true)
+        .and_then(|c| c.neg_risk_adapter)
+        .context("No neg-risk adapter for Polygon")?;
+    let from = signer.address();
+    let provider = auth::provider_with_signer(signer, gas).await?;
+    let pending = RawCallBuilder::new_raw(&provider, data)
+        .from(from)
+        .to(adapter)
+        .send()
+        .await
+        .with_context(|| format!("Failed to send {what} transaction"))?;
+    let tx_hash = *pending.tx_hash();
+    let receipt = pending
+        .get_receipt()
+        .await
+        .with_context(|| format!("Failed to confirm {what} transaction"))?;
+    anyhow::ensure!(
+        receipt.status(),
+        "{what} transaction {tx_hash} reverted in block {}",
+        receipt.block_number.unwrap_or_default()
+    );
+    Ok((tx_hash, receipt.block_number.unwrap_or_default()))
+}
+
 pub async fn execute(args: CtfArgs, output: OutputFormat, private_key: Option<&str>) -> Result<()> {
     match args.command {
         CtfCommand::Split {
+            condition_id,
             condition,
             amount,
             collateral,
             partition,
             parent_collection,
+            gas,
         } => {
-            let condition_id = super::parse_condition_id(&condition)?;
+            let gas = gas.settings()?;
+            let condition_id = parse_condition_arg(condition_id, condition)?;
             let usdc_amount = parse_usdc_amount(&amount)?;
             let collateral_addr = resolve_collateral(&collateral)?;
             let parent = parse_optional_parent(parent_collection.as_deref())?;
+            if is_neg_risk(condition_id).await? {
+                ensure_adapter_defaults(
+                    condition_id,
+                    collateral_addr,
+                    partition.is_some(),
+                    parent,
+                )?;
+                let call = INegRiskAdapter::splitPositionCall {
+                    conditionId: condition_id,
+                    amount: usdc_amount,
+                };
+                let signer = auth::resolve_signer(private_key).await?;
+                let (tx_hash, block) =
+                    send_to_adapter(signer, gas, call.abi_encode().into(), "split").await?;
+                return ctf_output::print_tx_result("split", tx_hash, block, &output);
+            }
             let partition = match partition {
                 Some(p) => parse_u256_csv(&p)?,
                 None => default_partition(),
             };
 
-            let provider = auth::create_provider(private_key).await?;
+            let provider = auth::create_provider(private_key, gas).await?;
             let client = ctf::Client::new(provider, POLYGON)?;
 
             let req = SplitPositionRequest::builder()
@@ -220,22 +343,41 @@ pub async fn execute(args: CtfArgs, output: OutputFormat, private_key: Option<&s
             ctf_output::print_tx_result("split", resp.transaction_hash, resp.block_number, &output)
         }
         CtfCommand::Merge {
+            condition_id,
             condition,
             amount,
             collateral,
             partition,
             parent_collection,
+            gas,
         } => {
-            let condition_id = super::parse_condition_id(&condition)?;
+            let gas = gas.settings()?;
+            let condition_id = parse_condition_arg(condition_id, condition)?;
             let usdc_amount = parse_usdc_amount(&amount)?;
             let collateral_addr = resolve_collateral(&collateral)?;
             let parent = parse_optional_parent(parent_collection.as_deref())?;
+            if is_neg_risk(condition_id).await? {
+                ensure_adapter_defaults(
+                    condition_id,
+                    collateral_addr,
+                    partition.is_some(),
+                    parent,
+                )?;
+                let call = INegRiskAdapter::mergePositionsCall {
+                    conditionId: condition_id,
+                    amount: usdc_amount,
+                };
+                let signer = auth::resolve_signer(private_key).await?;
+                let (tx_hash, block) =
+                    send_to_adapter(signer, gas, call.abi_encode().into(), "merge").await?;
+                return ctf_output::print_tx_result("merge", tx_hash, block, &output);
+            }
             let partition = match partition {
                 Some(p) => parse_u256_csv(&p)?,
                 None => default_partition(),
             };
 
-            let provider = auth::create_provider(private_key).await?;
+            let provider = auth::create_provider(private_key, gas).await?;
             let client = ctf::Client::new(provider, POLYGON)?;
 
             let req = MergePositionsRequest::builder()
@@ -258,16 +400,44 @@ pub async fn execute(args: CtfArgs, output: OutputFormat, private_key: Option<&s
             collateral,
             index_sets,
             parent_collection,
+            gas,
         } => {
+            let gas = gas.settings()?;
             let condition_id = super::parse_condition_id(&condition)?;
             let collateral_addr = resolve_collateral(&collateral)?;
             let parent = parse_optional_parent(parent_collection.as_deref())?;
+            if is_neg_risk(condition_id).await? {
+                ensure_adapter_defaults(
+                    condition_id,
+                    collateral_addr,
+                    index_sets.is_some(),
+                    parent,
+                )?;
+                // The adapter redeems explicit amounts: whatever the wallet holds.
+                let signer = auth::resolve_signer(private_key).await?;
+                let holder = signer.address();
+                let positions =
+                    super::portfolio::fetch_positions(&clients::data()?, holder).await?;
+                let mut redemption = group(
+                    positions
+                        .iter()
+                        .filter(|p| p.condition_id == condition_id)
+                        .map(Held::from),
+                )
+                .pop()
+                .with_context(|| format!("No position in {condition_id} held by {holder}"))?;
+                redemption.neg_risk = true;
+                read_balances(std::slice::from_mut(&mut redemption), holder).await?;
+                let (_, data) = redeem_call(&redemption)?;
+                let (tx_hash, block) = send_to_adapter(signer, gas, data, "redeem").await?;
+                return ctf_output::print_tx_result("redeem", tx_hash, block, &output);
+            }
             let index_sets = match index_sets {
                 Some(s) => parse_u256_csv(&s)?,
                 None => default_index_sets(),
             };
 
-            let provider = auth::create_provider(private_key).await?;
+            let provider = auth::create_provider(private_key, gas).await?;
             let client = ctf::Client::new(provider, POLYGON)?;
 
             let req = RedeemPositionsRequest::builder()
@@ -284,11 +454,16 @@ pub async fn execute(args: CtfArgs, output: OutputFormat, private_key: Option<&s
 
             ctf_output::print_tx_result("redeem", resp.transaction_hash, resp.block_number, &output)
         }
-        CtfCommand::RedeemNegRisk { condition, amounts } => {
+        CtfCommand::RedeemNegRisk {
+            condition,
+            amounts,
+            gas,
+        } => {
+            let gas = gas.settings()?;
             let condition_id = super::parse_condition_id(&condition)?;
             let amounts = parse_usdc_amounts(&amounts)?;
 
-            let provider = auth::create_provider(private_key).await?;
+            let provider = auth::create_provider(private_key, gas).await?;
             let client = ctf::Client::with_neg_risk(provider, POLYGON)?;
 
             let req = RedeemNegRiskRequest::builder()
@@ -373,6 +548,16 @@ pub async fn execute(args: CtfArgs, output: OutputFormat, private_key: Option<&s
 mod tests {
     use super::*;
 
+    #[test]
+    fn neg_risk_markets_take_only_adapter_defaults() {
+        let condition = B256::repeat_byte(1);
+        let usdc = contract_config(POLYGON, false).unwrap().collateral;
+        assert!(ensure_adapter_defaults(condition, usdc, false, B256::ZERO).is_ok());
+        assert!(ensure_adapter_defaults(condition, usdc, true, B256::ZERO).is_err());
+        assert!(ensure_adapter_defaults(condition, Address::ZERO, false, B256::ZERO).is_err());
+        assert!(ensure_adapter_defaults(condition, usdc, false, condition).is_err());
+    }
+
     #[test]
     fn parse_usdc_amount_whole_dollars() {
         let result = parse_usdc_amount("10").unwrap();
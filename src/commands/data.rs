@@ -1,4 +1,6 @@
+use super::wallet::resolve_trading_address;
 use super::{parse_address, parse_condition_id};
+use crate::net;
 use crate::output::OutputFormat;
 use crate::output::data::{
     print_activity, print_builder_leaderboard, print_builder_volume, print_closed_positions,
@@ -26,8 +28,8 @@ pub struct DataArgs {
 pub enum DataCommand {
     /// Get open positions for a wallet address
     Positions {
-        /// Wallet address (0x...)
-        address: String,
+        /// Wallet address (0x...); defaults to the configured wallet
+        address: Option<String>,
 
         /// Max results
         #[arg(long, default_value = "25")]
@@ -40,8 +42,8 @@ pub enum DataCommand {
 
     /// Get closed positions for a wallet address
     ClosedPositions {
-        /// Wallet address (0x...)
-        address: String,
+        /// Wallet address (0x...); defaults to the configured wallet
+        address: Option<String>,
 
         /// Max results
         #[arg(long, default_value = "25")]
@@ -54,20 +56,20 @@ pub enum DataCommand {
 
     /// Get total position value for a wallet address
     Value {
-        /// Wallet address (0x...)
-        address: String,
+        /// Wallet address (0x...); defaults to the configured wallet
+        address: Option<String>,
     },
 
     /// Get count of unique markets traded by a wallet
     Traded {
-        /// Wallet address (0x...)
-        address: String,
+        /// Wallet address (0x...); defaults to the configured wallet
+        address: Option<String>,
     },
 
     /// Get trade history
     Trades {
-        /// Wallet address (0x...)
-        address: String,
+        /// Wallet address (0x...); defaults to the configured wallet
+        address: Option<String>,
 
         /// Max results
         #[arg(long, default_value = "25")]
@@ -80,8 +82,8 @@ pub enum DataCommand {
 
     /// Get on-chain activity for a wallet address
     Activity {
-        /// Wallet address (0x...)
-        address: String,
+        /// Wallet address (0x...); defaults to the configured wallet
+        address: Option<String>,
 
         /// Max results
         #[arg(long, default_value = "25")]
@@ -190,7 +192,13 @@ impl From<OrderBy> for polymarket_client_sdk::data::types::LeaderboardOrderBy {
     }
 }
 
-pub async fn execute(client: &data::Client, args: DataArgs, output: OutputFormat) -> Result<()> {
+pub async fn execute(
+    client: &data::Client,
+    args: DataArgs,
+    output: OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
     match args.command {
         // User-focused queries (positions, trades, activity, value)
         DataCommand::Positions { .. }
@@ -198,7 +206,9 @@ pub async fn execute(client: &data::Client, args: DataArgs, output: OutputFormat
         | DataCommand::Value { .. }
         | DataCommand::Traded { .. }
         | DataCommand::Trades { .. }
-        | DataCommand::Activity { .. } => execute_user(client, args.command, &output).await,
+        | DataCommand::Activity { .. } => {
+            execute_user(client, args.command, &output, private_key, signature_type).await
+        }
 
         // Market-focused queries (holders, open interest, volume)
         DataCommand::Holders { .. }
@@ -218,7 +228,14 @@ async fn execute_user(
     client: &data::Client,
     command: DataCommand,
     output: &OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
 ) -> Result<()> {
+    let user = async |address: Option<String>| match address {
+        Some(a) => parse_address(&a),
+        None => resolve_trading_address(private_key, signature_type).await,
+    };
+
     match command {
         DataCommand::Positions {
             address,
@@ -226,12 +243,12 @@ async fn execute_user(
             offset,
         } => {
             let request = PositionsRequest::builder()
-                .user(parse_address(&address)?)
+                .user(user(address).await?)
                 .limit(limit)?
                 .maybe_offset(offset)?
                 .build();
 
-            let positions = client.positions(&request).await?;
+            let positions = net::retry(|| client.positions(&request)).await?;
             print_positions(&positions, output)?;
         }
 
@@ -241,30 +258,26 @@ async fn execute_user(
             offset,
         } => {
             let request = ClosedPositionsRequest::builder()
-                .user(parse_address(&address)?)
+                .user(user(address).await?)
                 .limit(limit)?
                 .maybe_offset(offset)?
                 .build();
 
-            let positions = client.closed_positions(&request).await?;
+            let positions = net::retry(|| client.closed_positions(&request)).await?;
             print_closed_positions(&positions, output)?;
         }
 
         DataCommand::Value { address } => {
-            let request = ValueRequest::builder()
-                .user(parse_address(&address)?)
-                .build();
+            let request = ValueRequest::builder().user(user(address).await?).build();
 
-            let values = client.value(&request).await?;
+            let values = net::retry(|| client.value(&request)).await?;
             print_value(&values, output)?;
         }
 
         DataCommand::Traded { address } => {
-            let request = TradedRequest::builder()
-                .user(parse_address(&address)?)
-                .build();
+            let request = TradedRequest::builder().user(user(address).await?).build();
 
-            let traded = client.traded(&request).await?;
+            let traded = net::retry(|| client.traded(&request)).await?;
             print_traded(&traded, output)?;
         }
 
@@ -274,12 +287,12 @@ async fn execute_user(
             offset,
         } => {
             let request = TradesRequest::builder()
-                .user(parse_address(&address)?)
+                .user(user(address).await?)
                 .limit(limit)?
                 .maybe_offset(offset)?
                 .build();
 
-            let trades = client.trades(&request).await?;
+            let trades = net::retry(|| client.trades(&request)).await?;
             print_trades(&trades, output)?;
         }
 
@@ -289,12 +302,12 @@ async fn execute_user(
             offset,
         } => {
             let request = ActivityRequest::builder()
-                .user(parse_address(&address)?)
+                .user(user(address).await?)
                 .limit(limit)?
                 .maybe_offset(offset)?
                 .build();
 
-            let activity = client.activity(&request).await?;
+            let activity = net::retry(|| client.activity(&request)).await?;
             print_activity(&activity, output)?;
         }
 
@@ -317,7 +330,7 @@ async fn execute_market(
                 .limit(limit)?
                 .build();
 
-            let holders = client.holders(&request).await?;
+            let holders = net::retry(|| client.holders(&request)).await?;
             print_holders(&holders, output)?;
         }
 
@@ -325,13 +338,13 @@ async fn execute_market(
             let cid = parse_condition_id(&market)?;
             let request = OpenInterestRequest::builder().markets(vec![cid]).build();
 
-            let oi = client.open_interest(&request).await?;
+            let oi = net::retry(|| client.open_interest(&request)).await?;
             print_open_interest(&oi, output)?;
         }
 
         DataCommand::Volume { id } => {
             let request = LiveVolumeRequest::builder().id(id).build();
-            let volume = client.live_volume(&request).await?;
+            let volume = net::retry(|| client.live_volume(&request)).await?;
             print_live_volume(&volume, output)?;
         }
 
@@ -360,7 +373,7 @@ async fn execute_leaderboard(
                 .maybe_offset(offset)?
                 .build();
 
-            let entries = client.leaderboard(&request).await?;
+            let entries = net::retry(|| client.leaderboard(&request)).await?;
             print_leaderboard(&entries, output)?;
         }
 
@@ -375,7 +388,7 @@ async fn execute_leaderboard(
                 .maybe_offset(offset)?
                 .build();
 
-            let entries = client.builder_leaderboard(&request).await?;
+            let entries = net::retry(|| client.builder_leaderboard(&request)).await?;
             print_builder_leaderboard(&entries, output)?;
         }
 
@@ -384,7 +397,7 @@ async fn execute_leaderboard(
                 .maybe_time_period(period.map(Into::into))
                 .build();
 
-            let entries = client.builder_volume(&request).await?;
+            let entries = net::retry(|| client.builder_volume(&request)).await?;
             print_builder_volume(&entries, output)?;
         }
 
@@ -0,0 +1,29 @@
+//! `db`: the local SQLite database in the config directory.
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use crate::db;
+use crate::output::OutputFormat;
+use crate::output::db::{print_db_stats, print_vacuumed};
+
+#[derive(Args)]
+pub struct DbArgs {
+    #[command(subcommand)]
+    pub command: DbCommand,
+}
+
+#[derive(Subcommand)]
+pub enum DbCommand {
+    /// Rows in each table, the database's size and its schema version
+    Stats,
+    /// Remove expired cache entries and compact the database
+    Vacuum,
+}
+
+pub fn execute(args: &DbArgs, output: &OutputFormat) -> Result<()> {
+    match args.command {
+        DbCommand::Stats => print_db_stats(&db::stats()?, output),
+        DbCommand::Vacuum => print_vacuumed(&db::vacuum()?, output),
+    }
+}
@@ -0,0 +1,322 @@
+//! `deposit`: funds the trading wallet with USDC from Ethereum mainnet
+//! through Polymarket's bridge. The bridge gives each wallet a deposit
+//! address; USDC sent there on Ethereum arrives in the wallet on Polygon.
+//! This looks the address up, can send the USDC from the signer's own
+//! Ethereum account, and polls the bridge until the funds arrive.
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use alloy::primitives::{Address, address};
+use alloy::signers::Signer as _;
+use anyhow::{Context, Result};
+use clap::Args;
+use polymarket_client_sdk::bridge::{
+    self,
+    types::{
+        DepositRequest, DepositTransaction, DepositTransactionStatus, StatusRequest,
+        SupportedAssetsResponse,
+    },
+};
+use rust_decimal::Decimal;
+
+use super::approve::IERC20;
+use super::balance::to_decimal;
+use super::ctf::usdc_to_raw;
+use super::wallet::resolve_trading_address;
+use crate::auth;
+use crate::confirm;
+use crate::errors;
+use crate::gas::GasFlags;
+use crate::net;
+use crate::output::OutputFormat;
+use crate::output::bridge::{
+    DepositPlan, DepositResult, print_deposit_plan, print_deposit_progress, print_deposit_result,
+};
+
+/// Native USDC on Ethereum mainnet.
+const ETHEREUM_USDC: Address = address!("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+const ETHEREUM_CHAIN_ID: u64 = 1;
+const USDC_DECIMALS: u8 = 6;
+pub const DEFAULT_ETH_RPC_URL: &str = "https://eth.drpc.org";
+
+#[derive(Args)]
+pub struct DepositArgs {
+    /// USDC to send from your Ethereum account (the signer's address on
+    /// mainnet). Leave out to only show where to send it
+    #[arg(long)]
+    pub amount: Option<Decimal>,
+    /// Wallet to credit (default: the trading wallet)
+    #[arg(long)]
+    pub to: Option<String>,
+    /// Wait for the bridge to deliver a deposit sent some other way
+    #[arg(long, conflicts_with = "amount")]
+    pub wait: bool,
+    /// Return once the USDC is sent instead of waiting for it to arrive
+    #[arg(long, requires = "amount")]
+    pub no_wait: bool,
+    /// Seconds between bridge status checks
+    #[arg(long, default_value = "15")]
+    pub interval: u64,
+    /// Give up waiting after this long (e.g. 30m, 1h)
+    #[arg(long, default_value = "30m")]
+    pub wait_timeout: String,
+    /// Ethereum mainnet RPC endpoint
+    #[arg(long, default_value = DEFAULT_ETH_RPC_URL)]
+    pub eth_rpc_url: String,
+    #[command(flatten)]
+    pub gas: GasFlags,
+}
+
+pub async fn execute(
+    args: DepositArgs,
+    output: &OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    anyhow::ensure!(args.interval > 0, "--interval must be at least 1 second");
+    let timeout = super::parse_duration(&args.wait_timeout)?
+        .to_std()
+        .context("--wait-timeout must be positive")?;
+    let wallet = match &args.to {
+        Some(to) => super::resolve_address(to)?,
+        None => resolve_trading_address(private_key, signature_type).await?,
+    };
+
+    let client = bridge::Client::default();
+    let request = DepositRequest::builder().address(wallet).build();
+    let (deposit, assets) = tokio::try_join!(
+        net::once(client.deposit(&request)),
+        net::retry(|| client.supported_assets()),
+    )?;
+    let plan = DepositPlan {
+        wallet,
+        deposit_address: deposit.address.evm,
+        token: ETHEREUM_USDC,
+        minimum: ethereum_usdc_minimum(&assets),
+    };
+
+    let Some(amount) = args.amount else {
+        // Taken before the address is shown, so a deposit that was already
+        // there isn't taken for the one sent now.
+        let earlier = if args.wait {
+            let status = status_request(&plan);
+            seen(&net::retry(|| client.status(&status)).await?.transactions)
+        } else {
+            HashSet::new()
+        };
+        print_deposit_plan(&plan, output)?;
+        if args.wait {
+            let arrived = wait_for_deposit(&client, &plan, &earlier, &args, timeout).await?;
+            let result = DepositResult {
+                wallet,
+                amount: to_decimal(arrived.from_amount_base_unit, USDC_DECIMALS)?,
+                tx_hash: None,
+                status: Some(arrived.status),
+            };
+            print_deposit_result(&result, output)?;
+        }
+        return Ok(());
+    };
+
+    anyhow::ensure!(
+        amount > Decimal::ZERO,
+        errors::validation("Amount must be positive")
+    );
+    if let Some(min) = plan.minimum {
+        anyhow::ensure!(
+            amount >= min,
+            errors::validation(format!("The bridge's minimum deposit is {min} USDC"))
+        );
+    }
+    let raw = usdc_to_raw(amount)?;
+    let gas = args.gas.settings()?;
+
+    let mut signer = auth::resolve_signer(private_key).await?;
+    signer.set_chain_id(Some(ETHEREUM_CHAIN_ID));
+    let from = signer.address();
+    let provider = auth::wallet_provider(signer, net::rpc_client(&args.eth_rpc_url)?, gas);
+    let usdc = IERC20::new(ETHEREUM_USDC, provider);
+
+    let balance = usdc
+        .balanceOf(from)
+        .call()
+        .await
+        .context("Failed to fetch USDC balance on Ethereum")?;
+    anyhow::ensure!(
+        balance >= raw,
+        errors::insufficient_balance(format!(
+            "{from} holds {} USDC on Ethereum, less than {amount}",
+            to_decimal(balance, USDC_DECIMALS)?
+        ))
+    );
+    // Deposits already known to the bridge, so only the new one is awaited.
+    let status = status_request(&plan);
+    let earlier = seen(&net::retry(|| client.status(&status)).await?.transactions);
+
+    if !confirm::confirm(
+        &format!(
+            "Deposit {amount} USDC from {from} on Ethereum to {} via bridge address {}",
+            plan.wallet, plan.deposit_address
+        ),
+        "Send it?",
+        output,
+    )? {
+        return Ok(());
+    }
+    let pending = net::once(usdc.transfer(plan.deposit_address, raw).send())
+        .await
+        .context("Failed to send USDC on Ethereum")?;
+    let tx_hash = *pending.tx_hash();
+    eprintln!("Sent {tx_hash}; waiting for it to be mined on Ethereum...");
+    let receipt = pending
+        .get_receipt()
+        .await
+        .context("Failed to confirm the Ethereum transaction")?;
+    anyhow::ensure!(
+        receipt.status(),
+        "Ethereum transaction {tx_hash} reverted in block {}",
+        receipt.block_number.unwrap_or_default()
+    );
+
+    let mut result = DepositResult {
+        wallet,
+        amount,
+        tx_hash: Some(tx_hash),
+        status: None,
+    };
+    if !args.no_wait {
+        let arrived = wait_for_deposit(&client, &plan, &earlier, &args, timeout).await?;
+        result.status = Some(arrived.status);
+    }
+    print_deposit_result(&result, output)
+}
+
+/// The bridge's minimum for USDC from Ethereum, if it lists one.
+fn ethereum_usdc_minimum(assets: &SupportedAssetsResponse) -> Option<Decimal> {
+    assets
+        .supported_assets
+        .iter()
+        .find(|a| {
+            a.chain_id == ETHEREUM_CHAIN_ID
+                && a.token
+                    .address
+                    .parse::<Address>()
+                    .is_ok_and(|t| t == ETHEREUM_USDC)
+        })
+        .map(|a| a.min_checkout_usd)
+        .filter(|min| !min.is_zero())
+}
+
+fn status_request(plan: &DepositPlan) -> StatusRequest {
+    StatusRequest::builder()
+        .address(plan.deposit_address.to_string())
+        .build()
+}
+
+/// What tells one bridge transaction from another; not all of them have a
+/// transaction hash until they are underway.
+fn deposit_key(tx: &DepositTransaction) -> String {
+    format!(
+        "{}:{}:{}",
+        tx.from_chain_id,
+        tx.from_amount_base_unit,
+        tx.created_time_ms.unwrap_or_default()
+    )
+}
+
+fn seen(transactions: &[DepositTransaction]) -> HashSet<String> {
+    transactions.iter().map(deposit_key).collect()
+}
+
+/// The newest deposit the bridge has picked up since `earlier`.
+fn new_deposit<'a>(
+    earlier: &HashSet<String>,
+    transactions: &'a [DepositTransaction],
+) -> Option<&'a DepositTransaction> {
+    transactions
+        .iter()
+        .filter(|tx| !earlier.contains(&deposit_key(tx)))
+        .max_by_key(|tx| tx.created_time_ms)
+}
+
+/// Polls the bridge until a deposit not in `earlier` completes, printing
+/// each step on stderr.
+async fn wait_for_deposit(
+    client: &bridge::Client,
+    plan: &DepositPlan,
+    earlier: &HashSet<String>,
+    args: &DepositArgs,
+    timeout: Duration,
+) -> Result<DepositTransaction> {
+    let started = Instant::now();
+    let request = status_request(plan);
+    let mut last = None;
+    eprintln!(
+        "Waiting for the bridge to deliver to {} (Ctrl-C to stop; check later with `polymarket bridge status {}`)...",
+        plan.wallet, plan.deposit_address
+    );
+    loop {
+        match net::retry(|| client.status(&request)).await {
+            Ok(status) => {
+                if let Some(tx) = new_deposit(earlier, &status.transactions) {
+                    if last.as_ref() != Some(&tx.status) {
+                        print_deposit_progress(tx);
+                        last = Some(tx.status.clone());
+                    }
+                    match tx.status {
+                        DepositTransactionStatus::Completed => return Ok(tx.clone()),
+                        DepositTransactionStatus::Failed => anyhow::bail!(
+                            "The bridge reports the deposit failed. See `polymarket bridge status {}`",
+                            plan.deposit_address
+                        ),
+                        _ => {}
+                    }
+                }
+            }
+            Err(e) => eprintln!("Status check failed: {e:#}"),
+        }
+        anyhow::ensure!(
+            started.elapsed() < timeout,
+            "No completed deposit after {}. It may still arrive; check with `polymarket bridge status {}`",
+            args.wait_timeout,
+            plan.deposit_address
+        );
+        tokio::select! {
+            () = tokio::time::sleep(Duration::from_secs(args.interval)) => {}
+            _ = tokio::signal::ctrl_c() => anyhow::bail!("Stopped waiting. Check later with `polymarket bridge status {}`", plan.deposit_address),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::U256;
+
+    use super::*;
+
+    fn tx(amount: u64, created: u64, status: DepositTransactionStatus) -> DepositTransaction {
+        DepositTransaction::builder()
+            .from_chain_id(1)
+            .from_token_address(ETHEREUM_USDC.to_string())
+            .from_amount_base_unit(U256::from(amount))
+            .to_chain_id(137)
+            .to_token_address(Address::ZERO)
+            .status(status)
+            .created_time_ms(created)
+            .build()
+    }
+
+    #[test]
+    fn new_deposit_skips_earlier_ones() {
+        let old = tx(5_000_000, 100, DepositTransactionStatus::Completed);
+        let earlier = seen(std::slice::from_ref(&old));
+        let now = [
+            old,
+            tx(25_000_000, 200, DepositTransactionStatus::Processing),
+        ];
+        let found = new_deposit(&earlier, &now).unwrap();
+        assert_eq!(found.from_amount_base_unit, U256::from(25_000_000));
+        assert!(new_deposit(&seen(&now), &now).is_none());
+    }
+}
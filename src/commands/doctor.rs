@@ -0,0 +1,443 @@
+//! `doctor`: checks the things that make other commands fail in ways that
+//! are hard to trace back: a broken profile, a key that doesn't parse, a
+//! key file others can read, an endpoint that can't be reached, a clock
+//! the CLOB disagrees with, missing approvals, and a version the API no
+//! longer works with.
+
+use std::path::Path;
+use std::time::Instant;
+
+use alloy::providers::Provider as _;
+use anyhow::Result;
+use chrono::Utc;
+use polymarket_client_sdk::POLYGON;
+use polymarket_client_sdk::types::{Address, U256};
+
+use super::approve::approval_statuses;
+use super::wallet::resolve_address;
+use crate::auth;
+use crate::clients;
+use crate::compat;
+use crate::config::{self, KeySource};
+use crate::net;
+use crate::output::OutputFormat;
+use crate::output::approve::ApprovalStatus;
+use crate::output::doctor::{Check, CheckStatus, print_doctor};
+
+/// Clock skew worth mentioning, in seconds. The CLOB's time only has
+/// whole seconds, so a second or two is just rounding.
+const SKEW_WARN_SECS: i64 = 5;
+/// Clock skew at which authenticated requests start being refused.
+const SKEW_FAIL_SECS: i64 = 30;
+
+/// The active profile's file parses, and so do the settings read from the
+/// environment.
+fn check_config() -> Check {
+    const NAME: &str = "Config";
+    if config::no_config() {
+        return Check::skipped(NAME, "Not read with --no-config");
+    }
+    let profile = config::active_profile();
+    let path = match config::config_path() {
+        Ok(path) => path,
+        Err(e) => {
+            return Check::fail(
+                NAME,
+                format!("{e:#}"),
+                "Set POLYMARKET_CONFIG_DIR to a writable directory",
+            );
+        }
+    };
+    if !path.exists() {
+        return Check::warn(
+            NAME,
+            format!("No profile '{profile}' at {}", path.display()),
+            "Run `polymarket setup`, or `polymarket wallet create` or `wallet import`",
+        );
+    }
+    let parsed = std::fs::read_to_string(&path)
+        .map_err(anyhow::Error::from)
+        .and_then(|data| Ok(serde_json::from_str::<config::Config>(&data)?));
+    if let Err(e) = parsed {
+        return Check::fail(
+            NAME,
+            format!("{}: {e:#}", path.display()),
+            "Fix the file by hand, or see what is wrong with `polymarket config show`",
+        );
+    }
+    let settings = net::Policy::current()
+        .and(config::limit_order_type())
+        .and(config::market_order_type())
+        .and(config::book_depth());
+    match settings {
+        Ok(_) => Check::ok(NAME, format!("Profile '{profile}' at {}", path.display())),
+        Err(e) => Check::fail(
+            NAME,
+            format!("{e:#}"),
+            "Correct it with `polymarket config set`, or unset the environment variable",
+        ),
+    }
+}
+
+/// The key parses into an address, without asking for a keystore's
+/// passphrase.
+async fn check_key(private_key: Option<&str>) -> (Check, Option<Address>) {
+    const NAME: &str = "Wallet key";
+    match resolve_address(private_key).await {
+        Ok((Some(address), KeySource::WatchOnly)) => (
+            Check::warn(
+                NAME,
+                format!("Watch-only {address}: can read but not trade"),
+                "Import the key with `polymarket wallet import` to trade",
+            ),
+            Some(address),
+        ),
+        Ok((Some(address), source)) => (
+            Check::ok(NAME, format!("{address} from the {}", source.label())),
+            Some(address),
+        ),
+        Ok((None, KeySource::Keystore)) => (
+            Check::fail(
+                NAME,
+                "The keystore's address is missing from the profile",
+                "Re-import the key with `polymarket wallet import --encrypt`",
+            ),
+            None,
+        ),
+        Ok((None, KeySource::Keyring)) => (
+            Check::fail(
+                NAME,
+                "The keyring key's address is missing from the profile",
+                "Re-import the key with `polymarket wallet import --storage keyring`",
+            ),
+            None,
+        ),
+        Ok((None, _)) => (
+            Check::warn(
+                NAME,
+                "No wallet configured",
+                "Run `polymarket wallet create` or `polymarket wallet import <key>`",
+            ),
+            None,
+        ),
+        Err(e) => (
+            Check::fail(
+                NAME,
+                format!("{e:#}"),
+                "Check the key is 64 hex characters (0x optional), or re-import it with `polymarket wallet import`",
+            ),
+            None,
+        ),
+    }
+}
+
+/// Files holding keys or credentials that others than the owner can read.
+#[cfg(unix)]
+fn check_permissions() -> Check {
+    use std::os::unix::fs::PermissionsExt;
+
+    const NAME: &str = "File permissions";
+    let mut paths = Vec::new();
+    if let Ok(dir) = config::config_dir() {
+        paths.push((dir, 0o700));
+    }
+    for path in [config::config_path(), config::keystore_path()]
+        .into_iter()
+        .flatten()
+    {
+        paths.push((path, 0o600));
+    }
+    let mut open = Vec::new();
+    for (path, wanted) in &paths {
+        if let Ok(meta) = std::fs::metadata(path) {
+            let mode = meta.permissions().mode() & 0o777;
+            if let Some(found) = too_open(mode) {
+                open.push((path.as_path(), found, *wanted));
+            }
+        }
+    }
+    permissions_check(NAME, &open)
+}
+
+#[cfg(not(unix))]
+fn check_permissions() -> Check {
+    Check::skipped("File permissions", "Only checked on Unix")
+}
+
+/// `mode` if it gives the group or others any access.
+#[cfg_attr(not(unix), allow(dead_code))]
+fn too_open(mode: u32) -> Option<u32> {
+    (mode & 0o077 != 0).then_some(mode)
+}
+
+#[cfg_attr(not(unix), allow(dead_code))]
+fn permissions_check(name: &'static str, open: &[(&Path, u32, u32)]) -> Check {
+    if open.is_empty() {
+        return Check::ok(name, "Config files are readable only by you");
+    }
+    let detail = open
+        .iter()
+        .map(|(path, found, _)| format!("{} is {found:o}", path.display()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let fix = open
+        .iter()
+        .map(|(path, _, wanted)| format!("chmod {wanted:o} {}", path.display()))
+        .collect::<Vec<_>>()
+        .join(" && ");
+    Check::fail(name, detail, format!("Run `{fix}`"))
+}
+
+fn unreachable(name: &'static str, url: &str, e: &anyhow::Error) -> Check {
+    Check::fail(
+        name,
+        format!("{url}: {e:#}"),
+        "Check your connection, or the endpoint with `polymarket config set endpoints.*` or --timeout",
+    )
+}
+
+/// Reaches the CLOB, returning its clock as the second check.
+async fn check_clob() -> (Check, Check) {
+    const NAME: &str = "CLOB";
+    const CLOCK: &str = "Clock";
+    let url = config::clob_url();
+    let client = match clients::clob() {
+        Ok(client) => client,
+        Err(e) => {
+            return (
+                unreachable(NAME, &url, &e),
+                Check::skipped(CLOCK, "Needs the CLOB"),
+            );
+        }
+    };
+    let started = Instant::now();
+    let before = Utc::now();
+    match net::once(client.server_time()).await {
+        Ok(server) => {
+            let elapsed = started.elapsed();
+            // Assume the server read its clock halfway through the request.
+            let local = before + elapsed / 2;
+            let skew = server - local.timestamp();
+            (
+                Check::ok(NAME, format!("{url} in {}ms", elapsed.as_millis())),
+                clock_check(CLOCK, skew),
+            )
+        }
+        Err(e) => (
+            unreachable(NAME, &url, &e),
+            Check::skipped(CLOCK, "Needs the CLOB"),
+        ),
+    }
+}
+
+/// How far the local clock is from the CLOB's, `skew` seconds behind it
+/// when positive.
+fn clock_check(name: &'static str, skew: i64) -> Check {
+    let detail = match skew {
+        0 => "In step with the CLOB".to_string(),
+        s if s > 0 => format!("{s}s behind the CLOB"),
+        s => format!("{}s ahead of the CLOB", -s),
+    };
+    let fix = "Sync the system clock, e.g. `sudo timedatectl set-ntp true` on Linux or \
+               Settings > Date & Time > Set automatically";
+    match skew.abs() {
+        s if s >= SKEW_FAIL_SECS => Check::fail(name, detail, fix),
+        s if s >= SKEW_WARN_SECS => Check::warn(name, detail, fix),
+        _ => Check::ok(name, detail),
+    }
+}
+
+async fn check_gamma() -> Check {
+    const NAME: &str = "Gamma API";
+    let url = config::gamma_url();
+    let client = match clients::gamma() {
+        Ok(client) => client,
+        Err(e) => return unreachable(NAME, &url, &e),
+    };
+    let started = Instant::now();
+    match net::once(client.status()).await {
+        Ok(_) => Check::ok(
+            NAME,
+            format!("{url} in {}ms", started.elapsed().as_millis()),
+        ),
+        Err(e) => unreachable(NAME, &url, &e),
+    }
+}
+
+async fn check_rpc() -> Check {
+    const NAME: &str = "Polygon RPC";
+    let url = config::rpc_url();
+    let started = Instant::now();
+    let chain_id = async {
+        let provider = auth::create_readonly_provider().await?;
+        net::once(provider.get_chain_id()).await
+    };
+    match chain_id.await {
+        Ok(id) if id == POLYGON => Check::ok(
+            NAME,
+            format!("{url} in {}ms", started.elapsed().as_millis()),
+        ),
+        Ok(id) => Check::fail(
+            NAME,
+            format!("{url} is on chain {id}, not Polygon ({POLYGON})"),
+            "Point endpoints.rpc_url at a Polygon mainnet RPC with `polymarket config set`",
+        ),
+        Err(e) => unreachable(NAME, &url, &e),
+    }
+}
+
+async fn check_allowances(owner: Option<Address>, rpc_ok: bool) -> Check {
+    const NAME: &str = "Allowances";
+    let Some(owner) = owner else {
+        return Check::skipped(NAME, "Needs a wallet");
+    };
+    if !rpc_ok {
+        return Check::skipped(NAME, "Needs the Polygon RPC");
+    }
+    match approval_statuses(owner).await {
+        Ok(statuses) => allowance_check(NAME, &statuses),
+        Err(e) => Check::fail(
+            NAME,
+            format!("{e:#}"),
+            "Try again, or check them with `polymarket approve check`",
+        ),
+    }
+}
+
+fn allowance_check(name: &'static str, statuses: &[ApprovalStatus]) -> Check {
+    let missing: Vec<String> = statuses
+        .iter()
+        .flat_map(|s| {
+            let usdc = (s.usdc_error.is_some() || s.usdc_allowance == U256::ZERO)
+                .then(|| format!("USDC for {}", s.contract_name));
+            let ctf = (s.ctf_error.is_some() || !s.ctf_approved)
+                .then(|| format!("CTF for {}", s.contract_name));
+            [usdc, ctf].into_iter().flatten()
+        })
+        .collect();
+    if missing.is_empty() {
+        Check::ok(name, "USDC and CTF approved for every exchange contract")
+    } else {
+        Check::fail(
+            name,
+            format!("Not approved: {}", missing.join(", ")),
+            "Run `polymarket approve set` (sends transactions, needs POL for gas)",
+        )
+    }
+}
+
+/// Whether this version still works with the API, by the version the CLOB
+/// advertises.
+async fn check_compatibility() -> Check {
+    const NAME: &str = "Compatibility";
+    match compat::fetch().await {
+        Ok(api) => match api.problem() {
+            None => Check::ok(
+                NAME,
+                format!(
+                    "v{} works with CLOB API version {}",
+                    compat::VERSION,
+                    api.version
+                ),
+            ),
+            Some(problem) => Check::fail(NAME, problem, "Run `polymarket upgrade`"),
+        },
+        Err(e) => Check::warn(
+            NAME,
+            format!("Couldn't fetch the API version: {e:#}"),
+            "Check that the CLOB URL is reachable",
+        ),
+    }
+}
+
+pub async fn execute(output: &OutputFormat, private_key: Option<&str>) -> Result<()> {
+    let mut checks = vec![check_config()];
+    let (key, owner) = check_key(private_key).await;
+    checks.push(key);
+    checks.push(check_permissions());
+    if config::offline() {
+        for name in [
+            "CLOB",
+            "Gamma API",
+            "Polygon RPC",
+            "Clock",
+            "Allowances",
+            "Compatibility",
+        ] {
+            checks.push(Check::skipped(name, "Offline"));
+        }
+        return print_doctor(&checks, output);
+    }
+    let (clob, clock) = check_clob().await;
+    checks.push(clob);
+    checks.push(check_gamma().await);
+    let rpc = check_rpc().await;
+    let rpc_ok = rpc.status == CheckStatus::Ok;
+    checks.push(rpc);
+    checks.push(clock);
+    checks.push(check_allowances(owner, rpc_ok).await);
+    checks.push(check_compatibility().await);
+    print_doctor(&checks, output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(contract: &str, usdc: u64, ctf: bool) -> ApprovalStatus {
+        ApprovalStatus {
+            contract_name: contract.into(),
+            contract_address: String::new(),
+            usdc_allowance: U256::from(usdc),
+            ctf_approved: ctf,
+            usdc_error: None,
+            ctf_error: None,
+        }
+    }
+
+    #[test]
+    fn clock_skew_warns_then_fails() {
+        assert_eq!(clock_check("Clock", 1).status, CheckStatus::Ok);
+        let ahead = clock_check("Clock", -7);
+        assert_eq!(ahead.status, CheckStatus::Warn);
+        assert_eq!(ahead.detail, "7s ahead of the CLOB");
+        assert_eq!(clock_check("Clock", 45).status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn permissions_flag_group_and_other_access() {
+        assert_eq!(too_open(0o600), None);
+        assert_eq!(too_open(0o700), None);
+        assert_eq!(too_open(0o644), Some(0o644));
+        let check = permissions_check(
+            "File permissions",
+            &[(Path::new("/cfg/default.json"), 0o644, 0o600)],
+        );
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert_eq!(
+            check.fix.as_deref(),
+            Some("Run `chmod 600 /cfg/default.json`")
+        );
+    }
+
+    #[test]
+    fn allowances_list_what_is_missing() {
+        let all = [
+            status("CTF Exchange", 1, true),
+            status("Neg Risk Exchange", 5, true),
+        ];
+        assert_eq!(allowance_check("Allowances", &all).status, CheckStatus::Ok);
+        let check = allowance_check(
+            "Allowances",
+            &[
+                status("CTF Exchange", 0, true),
+                status("Neg Risk Adapter", 1, false),
+            ],
+        );
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert_eq!(
+            check.detail,
+            "Not approved: USDC for CTF Exchange, CTF for Neg Risk Adapter"
+        );
+    }
+}
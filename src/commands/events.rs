@@ -1,12 +1,22 @@
+use std::time::Duration;
+
 use anyhow::Result;
 use clap::{Args, Subcommand};
 use polymarket_client_sdk::gamma::{
     self,
     types::request::{EventByIdRequest, EventBySlugRequest, EventTagsRequest, EventsRequest},
+    types::response::Event,
 };
 
 use super::is_numeric_id;
-use crate::output::events::{print_event_detail, print_events_table};
+use super::quote::{Outcome, quote};
+use super::scan::tradable;
+use crate::clients;
+use crate::net;
+use crate::output::events::{
+    EventOdds, print_event_detail, print_event_odds, print_event_odds_tick, print_events_csv,
+    print_events_table,
+};
 use crate::output::tags::print_tags_table;
 use crate::output::{OutputFormat, print_json};
 
@@ -49,7 +59,8 @@ pub enum EventsCommand {
         tag: Option<String>,
     },
 
-    /// Get a single event by ID or slug
+    /// Get a single event by ID or slug, with its markets and outcomes
+    #[command(visible_alias = "show")]
     Get {
         /// Event ID (numeric) or slug
         id: String,
@@ -60,6 +71,98 @@ pub enum EventsCommand {
         /// Event ID
         id: String,
     },
+
+    /// Implied and normalized probabilities of an event's outcomes, and the
+    /// overround
+    Odds {
+        /// Event ID (numeric) or slug
+        id: String,
+
+        /// Keep updating until interrupted, one line per round
+        #[arg(long)]
+        watch: bool,
+
+        /// Seconds between rounds with --watch
+        #[arg(
+            long,
+            default_value = "5",
+            requires = "watch",
+            value_parser = clap::value_parser!(u64).range(1..)
+        )]
+        interval: u64,
+    },
+}
+
+async fn fetch_event(client: &gamma::Client, id: String) -> Result<Event> {
+    if is_numeric_id(&id) {
+        let req = EventByIdRequest::builder().id(id).build();
+        net::retry(|| client.event_by_id(&req)).await
+    } else {
+        let req = EventBySlugRequest::builder().slug(id).build();
+        net::retry(|| client.event_by_slug(&req)).await
+    }
+}
+
+/// The Yes outcome of each open market in `event`, named for its market.
+fn event_outcomes(event: &Event) -> Vec<Outcome> {
+    let title = event.title.clone().unwrap_or_default();
+    event
+        .markets
+        .iter()
+        .flatten()
+        .filter(|m| tradable(m))
+        .filter_map(|m| {
+            Some(Outcome {
+                market: title.clone(),
+                name: m
+                    .group_item_title
+                    .clone()
+                    .filter(|t| !t.is_empty())
+                    .or_else(|| m.question.clone())
+                    .unwrap_or_default(),
+                token_id: *m.clob_token_ids.as_ref()?.first()?,
+            })
+        })
+        .collect()
+}
+
+async fn odds(
+    gamma: &gamma::Client,
+    id: String,
+    watch: bool,
+    interval: u64,
+    output: &OutputFormat,
+) -> Result<()> {
+    let event = fetch_event(gamma, id).await?;
+    let outcomes = event_outcomes(&event);
+    anyhow::ensure!(!outcomes.is_empty(), "Event has no open markets");
+    if event.neg_risk != Some(true) {
+        eprintln!(
+            "Note: this event's markets aren't marked mutually exclusive, so the normalized \
+             probabilities and overround may not mean much."
+        );
+    }
+    let client = clients::clob()?;
+    let title = event.title.unwrap_or_default();
+    if !watch {
+        let quotes = quote(&client, &outcomes).await?;
+        return print_event_odds(&EventOdds::new(title, quotes), output);
+    }
+    let mut first = true;
+    loop {
+        match quote(&client, &outcomes).await {
+            Ok(quotes) => {
+                let odds = EventOdds::new(title.clone(), quotes);
+                print_event_odds_tick(&odds, chrono::Local::now(), first, output);
+                first = false;
+            }
+            Err(e) => eprintln!("Update failed: {e:#}"),
+        }
+        tokio::select! {
+            () = tokio::time::sleep(Duration::from_secs(interval)) => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
 }
 
 pub async fn execute(client: &gamma::Client, args: EventsArgs, output: OutputFormat) -> Result<()> {
@@ -84,39 +187,43 @@ pub async fn execute(client: &gamma::Client, args: EventsArgs, output: OutputFor
                 .order(order.into_iter().collect::<Vec<_>>())
                 .build();
 
-            let events = client.events(&request).await?;
+            let events = net::retry(|| client.events(&request)).await?;
 
             match output {
-                OutputFormat::Table => print_events_table(&events),
+                OutputFormat::Table | OutputFormat::Plain => print_events_table(&events),
                 OutputFormat::Json => print_json(&events)?,
+                OutputFormat::Csv => print_events_csv(&events),
             }
         }
 
         EventsCommand::Get { id } => {
-            let is_numeric = is_numeric_id(&id);
-            let event = if is_numeric {
-                let req = EventByIdRequest::builder().id(id).build();
-                client.event_by_id(&req).await?
-            } else {
-                let req = EventBySlugRequest::builder().slug(id).build();
-                client.event_by_slug(&req).await?
-            };
+            let event = fetch_event(client, id).await?;
 
             match output {
-                OutputFormat::Table => print_event_detail(&event),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    print_event_detail(&event)
+                }
                 OutputFormat::Json => print_json(&event)?,
             }
         }
 
         EventsCommand::Tags { id } => {
             let req = EventTagsRequest::builder().id(id).build();
-            let tags = client.event_tags(&req).await?;
+            let tags = net::retry(|| client.event_tags(&req)).await?;
 
             match output {
-                OutputFormat::Table => print_tags_table(&tags),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    print_tags_table(&tags)
+                }
                 OutputFormat::Json => print_json(&tags)?,
             }
         }
+
+        EventsCommand::Odds {
+            id,
+            watch,
+            interval,
+        } => odds(client, id, watch, interval, &output).await?,
     }
 
     Ok(())
@@ -0,0 +1,95 @@
+//! `fees`: taker fee rates for each outcome of a market, and what an order
+//! of a given price and size would pay.
+
+use anyhow::Result;
+use clap::Args;
+use polymarket_client_sdk::types::Decimal;
+
+use super::quote::{Outcome, resolve_outcomes};
+use crate::clients;
+use crate::net;
+use crate::output::OutputFormat;
+use crate::output::fees::{FeeEstimate, FeeRate, print_fees};
+
+const BPS: u32 = 10_000;
+
+#[derive(Args)]
+pub struct FeesArgs {
+    /// Token IDs, or market slugs, IDs or condition IDs for every outcome
+    #[arg(required = true)]
+    pub markets: Vec<String>,
+
+    /// Price to estimate the fee at (with --size)
+    #[arg(long, requires = "size")]
+    pub price: Option<Decimal>,
+
+    /// Shares to estimate the fee for (with --price)
+    #[arg(long, requires = "price")]
+    pub size: Option<Decimal>,
+}
+
+/// The USDC value of the fee a taker pays to trade `size` shares at `price`.
+/// Fees scale with the cheaper side of the market, so they peak at 50¢ and
+/// vanish toward 0 and 1. Buyers pay it in shares, sellers in USDC.
+pub(crate) fn taker_fee(
+    price: Decimal,
+    size: Decimal,
+    fee_rate_bps: impl Into<Decimal>,
+) -> Decimal {
+    let rate = fee_rate_bps.into() / Decimal::from(BPS);
+    rate * price.min(Decimal::ONE - price) * size
+}
+
+pub async fn execute(args: FeesArgs, output: &OutputFormat) -> Result<()> {
+    let estimate = match (args.price, args.size) {
+        (Some(price), Some(size)) => {
+            anyhow::ensure!(
+                price > Decimal::ZERO && price < Decimal::ONE,
+                "Price must be between 0 and 1, got {price}"
+            );
+            anyhow::ensure!(size > Decimal::ZERO, "Size must be positive, got {size}");
+            FeeEstimate { price, size }
+        }
+        // With nothing to price, show the most a share can pay.
+        _ => FeeEstimate::worst_case(),
+    };
+
+    let gamma = &clients::gamma()?;
+    let outcomes: Vec<Outcome> = net::fetch_all(&args.markets, |id| resolve_outcomes(gamma, id))
+        .await?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    let client = &clients::clob()?;
+    let rates = net::fetch_all(&outcomes, |o| {
+        net::retry(move || client.fee_rate_bps(o.token_id))
+    })
+    .await?;
+    let rows: Vec<FeeRate> = outcomes
+        .into_iter()
+        .zip(rates)
+        .map(|(o, rate)| FeeRate {
+            market: o.market,
+            outcome: o.name,
+            token_id: o.token_id,
+            fee_rate_bps: rate.base_fee,
+            fee: taker_fee(estimate.price, estimate.size, rate.base_fee),
+        })
+        .collect();
+    print_fees(&rows, &estimate, output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn taker_fee_scales_with_the_cheaper_side() {
+        assert_eq!(taker_fee(dec!(0.5), dec!(100), 200), dec!(1));
+        assert_eq!(taker_fee(dec!(0.2), dec!(100), 200), dec!(0.4));
+        assert_eq!(taker_fee(dec!(0.8), dec!(100), 200), dec!(0.4));
+        assert_eq!(taker_fee(dec!(0.5), dec!(100), 0), Decimal::ZERO);
+    }
+}
@@ -0,0 +1,88 @@
+//! `gas`: what gas costs on Polygon right now, as the on-chain commands
+//! would price it with the same `--gas-multiplier`.
+
+use alloy::eips::BlockNumberOrTag;
+use alloy::primitives::U256;
+use alloy::providers::Provider;
+use anyhow::{Context, Result};
+use clap::Args;
+use polymarket_client_sdk::types::Decimal;
+
+use super::balance::{POL_DECIMALS, to_decimal};
+use crate::auth;
+use crate::gas::GasSettings;
+use crate::output::OutputFormat;
+use crate::output::gas::{GasPrices, print_gas_prices};
+
+/// About what a USDC transfer uses, for a sense of what a transaction costs.
+const TRANSFER_GAS: u64 = 65_000;
+
+#[derive(Args)]
+pub struct GasArgs {
+    /// Headroom on the base fee, as for --gas-multiplier on on-chain commands
+    #[arg(long, default_value = "1.2")]
+    pub gas_multiplier: Decimal,
+}
+
+pub fn gwei(wei: u128) -> Result<Decimal> {
+    to_decimal(U256::from(wei), 9)
+}
+
+pub async fn execute(args: GasArgs, output: &OutputFormat) -> Result<()> {
+    anyhow::ensure!(
+        args.gas_multiplier >= Decimal::ONE,
+        crate::errors::validation("--gas-multiplier must be at least 1")
+    );
+    let settings = GasSettings {
+        multiplier: args.gas_multiplier,
+        ..GasSettings::default()
+    };
+    let provider = auth::create_readonly_provider().await?;
+    let (block, estimate, gas_price) = tokio::try_join!(
+        async {
+            provider
+                .get_block_by_number(BlockNumberOrTag::Latest)
+                .await
+                .context("Failed to fetch the latest block")?
+                .context("The RPC node returned no latest block")
+        },
+        async {
+            provider
+                .estimate_eip1559_fees()
+                .await
+                .context("Failed to estimate EIP-1559 fees")
+        },
+        async {
+            provider
+                .get_gas_price()
+                .await
+                .context("Failed to fetch gas price")
+        },
+    )?;
+    let base_fee = u128::from(block.header.base_fee_per_gas.unwrap_or_default());
+    let (max_fee, priority_fee) =
+        settings.fees(estimate.max_fee_per_gas, estimate.max_priority_fee_per_gas);
+    let transfer_cost = U256::from(TRANSFER_GAS) * U256::from(base_fee + priority_fee);
+
+    print_gas_prices(
+        &GasPrices {
+            block: block.header.number,
+            base_fee: gwei(base_fee)?,
+            priority_fee: gwei(priority_fee)?,
+            max_fee: gwei(max_fee)?,
+            gas_price: gwei(gas_price)?,
+            transfer_cost: to_decimal(transfer_cost, POL_DECIMALS)?,
+        },
+        output,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gwei_scales_wei() {
+        assert_eq!(gwei(30_500_000_000).unwrap(), Decimal::new(305, 1));
+    }
+}
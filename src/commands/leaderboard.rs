@@ -0,0 +1,165 @@
+//! `leaderboard`: the public trader leaderboard, and one trader's record on
+//! it with what they hold now.
+
+use anyhow::Result;
+use clap::{Args, Subcommand, ValueEnum};
+use polymarket_client_sdk::data;
+use polymarket_client_sdk::data::types::request::{
+    PositionsRequest, TradedRequest, TraderLeaderboardRequest, ValueRequest,
+};
+use polymarket_client_sdk::data::types::response::TraderLeaderboardEntry;
+use polymarket_client_sdk::data::types::{LeaderboardOrderBy, PositionSortBy, SortDirection};
+use polymarket_client_sdk::types::{Address, Decimal};
+
+use super::data::TimePeriod;
+use super::resolve_address;
+use crate::net;
+use crate::output::OutputFormat;
+use crate::output::leaderboard::{TraderProfile, print_leaderboard, print_trader};
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct LeaderboardArgs {
+    #[command(subcommand)]
+    pub command: Option<LeaderboardCommand>,
+
+    /// What to rank traders by
+    #[arg(long, value_enum, default_value_t = RankBy::Profit)]
+    pub by: RankBy,
+
+    /// Time period
+    #[arg(long, value_enum, default_value = "day")]
+    pub period: TimePeriod,
+
+    /// Number of traders (at most 50)
+    #[arg(long, default_value = "25")]
+    pub limit: i32,
+
+    /// Pagination offset
+    #[arg(long)]
+    pub offset: Option<i32>,
+}
+
+#[derive(Subcommand)]
+pub enum LeaderboardCommand {
+    /// A trader's public profit, volume and ranks, and their largest
+    /// open positions
+    User {
+        /// Wallet address (0x...) or saved contact name
+        address: String,
+
+        /// Time period the profit, volume and ranks cover
+        #[arg(long, value_enum, default_value = "month")]
+        period: TimePeriod,
+
+        /// Number of positions to show
+        #[arg(long, default_value = "10")]
+        positions: i32,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum RankBy {
+    Profit,
+    Volume,
+}
+
+impl From<RankBy> for LeaderboardOrderBy {
+    fn from(by: RankBy) -> Self {
+        match by {
+            RankBy::Profit => Self::Pnl,
+            RankBy::Volume => Self::Vol,
+        }
+    }
+}
+
+fn period_label(period: &TimePeriod) -> &'static str {
+    match period {
+        TimePeriod::Day => "day",
+        TimePeriod::Week => "week",
+        TimePeriod::Month => "month",
+        TimePeriod::All => "all",
+    }
+}
+
+pub async fn execute(
+    client: &data::Client,
+    args: LeaderboardArgs,
+    output: &OutputFormat,
+) -> Result<()> {
+    match args.command {
+        None => {
+            let request = TraderLeaderboardRequest::builder()
+                .time_period(args.period.into())
+                .order_by(args.by.into())
+                .limit(args.limit)?
+                .maybe_offset(args.offset)?
+                .build();
+            let entries = net::retry(|| client.leaderboard(&request)).await?;
+            print_leaderboard(&entries, output)
+        }
+        Some(LeaderboardCommand::User {
+            address,
+            period,
+            positions,
+        }) => {
+            let address = resolve_address(&address)?;
+            let profile = trader_profile(client, address, period, positions).await?;
+            print_trader(&profile, output)
+        }
+    }
+}
+
+/// The trader's leaderboard entry when ranked by `by`, if they have one.
+async fn entry(
+    client: &data::Client,
+    address: Address,
+    period: &TimePeriod,
+    by: RankBy,
+) -> Result<Option<TraderLeaderboardEntry>> {
+    let request = TraderLeaderboardRequest::builder()
+        .time_period(period.clone().into())
+        .order_by(by.into())
+        .user(address)
+        .build();
+    Ok(net::retry(|| client.leaderboard(&request))
+        .await?
+        .into_iter()
+        .next())
+}
+
+async fn trader_profile(
+    client: &data::Client,
+    address: Address,
+    period: TimePeriod,
+    positions: i32,
+) -> Result<TraderProfile> {
+    let positions_request = PositionsRequest::builder()
+        .user(address)
+        .limit(positions)?
+        .sort_by(PositionSortBy::Current)
+        .sort_direction(SortDirection::Desc)
+        .build();
+    let value_request = ValueRequest::builder().user(address).build();
+    let traded_request = TradedRequest::builder().user(address).build();
+    let (by_profit, by_volume, positions, value, traded) = tokio::try_join!(
+        entry(client, address, &period, RankBy::Profit),
+        entry(client, address, &period, RankBy::Volume),
+        net::retry(|| client.positions(&positions_request)),
+        net::retry(|| client.value(&value_request)),
+        net::retry(|| client.traded(&traded_request)),
+    )?;
+    let ranked = by_profit.as_ref().or(by_volume.as_ref());
+    Ok(TraderProfile {
+        address,
+        period: period_label(&period),
+        user_name: ranked.and_then(|e| e.user_name.clone()),
+        pnl_rank: by_profit.as_ref().map(|e| e.rank),
+        volume_rank: by_volume.as_ref().map(|e| e.rank),
+        pnl: ranked.map_or(Decimal::ZERO, |e| e.pnl),
+        volume: ranked.map_or(Decimal::ZERO, |e| e.vol),
+        positions_value: value.iter().map(|v| v.value).sum(),
+        markets_traded: traded.traded,
+        positions,
+    })
+}
@@ -5,16 +5,31 @@ use polymarket_client_sdk::gamma::{
     types::{
         request::{
             MarketByIdRequest, MarketBySlugRequest, MarketTagsRequest, MarketsRequest,
-            SearchRequest,
+            SearchRequest, TagBySlugRequest,
         },
-        response::Market,
+        response::{Market, Tag},
     },
 };
 
-use super::is_numeric_id;
-use crate::output::markets::{print_market_detail, print_markets_table};
+use polymarket_client_sdk::data::types::request::{HoldersRequest, TradesRequest};
+use polymarket_client_sdk::data::types::response::MetaHolder;
+use polymarket_client_sdk::data::types::{MarketFilter, TradeFilter};
+use rust_decimal::Decimal;
+
+use std::str::FromStr;
+
+use polymarket_client_sdk::types::{B256, U256};
+
+use super::resolution::ancillary_data;
+use super::{is_numeric_id, parse_address, parse_condition_id};
+use crate::output::markets::{
+    MarketSource, OutcomeHolders, SearchHit, print_market_activity, print_market_comparison,
+    print_market_detail, print_market_holders, print_market_source, print_markets_csv,
+    print_markets_table, print_search_results, print_top_markets,
+};
 use crate::output::tags::print_tags_table;
 use crate::output::{OutputFormat, print_json};
+use crate::{cache, clients, errors, net};
 
 #[derive(Args)]
 pub struct MarketsArgs {
@@ -34,14 +49,30 @@ pub enum MarketsCommand {
         #[arg(long)]
         closed: Option<bool>,
 
+        /// Filter by tag ID or slug (e.g. "politics", "crypto")
+        #[arg(long)]
+        tag: Option<String>,
+
+        /// Minimum liquidity (USDC)
+        #[arg(long)]
+        liquidity_min: Option<Decimal>,
+
+        /// Minimum volume (USDC)
+        #[arg(long)]
+        volume_min: Option<Decimal>,
+
         /// Max results
         #[arg(long, default_value = "25")]
         limit: i32,
 
         /// Pagination offset
-        #[arg(long)]
+        #[arg(long, conflicts_with = "cursor")]
         offset: Option<i32>,
 
+        /// Pagination cursor (the "Next cursor" printed with the previous page)
+        #[arg(long)]
+        cursor: Option<String>,
+
         /// Sort field (e.g. `volume_num`, `liquidity_num`)
         #[arg(long)]
         order: Option<String>,
@@ -51,20 +82,75 @@ pub enum MarketsCommand {
         ascending: bool,
     },
 
-    /// Get a single market by ID or slug
+    /// Get a single market by ID, slug, condition ID, or token ID
+    #[command(visible_alias = "show")]
     Get {
-        /// Market ID (numeric) or slug
+        /// Market ID (numeric), slug, condition ID (0x...), or CLOB token ID
+        id: String,
+    },
+
+    /// Resolution rules, resolution source links and the UMA ancillary data
+    /// a market resolves by
+    Source {
+        /// Market ID (numeric), slug, condition ID (0x...), or CLOB token ID
+        id: String,
+    },
+
+    /// Largest holders of each of a market's outcomes
+    Holders {
+        /// Market ID (numeric), slug, condition ID (0x...), or CLOB token ID
+        id: String,
+
+        /// Holders per outcome
+        #[arg(long, default_value = "10")]
+        limit: i32,
+    },
+
+    /// Recent large trades in a market, newest first
+    Activity {
+        /// Market ID (numeric), slug, condition ID (0x...), or CLOB token ID
         id: String,
+
+        /// Smallest trade to show, in USDC
+        #[arg(long, default_value = "1000")]
+        min_usdc: Decimal,
+
+        /// Max results
+        #[arg(long, default_value = "25")]
+        limit: i32,
+    },
+
+    /// Compare markets side by side: prices, volume, liquidity, spread and
+    /// end dates
+    Compare {
+        /// Two or more markets: ID, slug, condition ID, or CLOB token ID
+        #[arg(required = true, num_args = 2..)]
+        ids: Vec<String>,
     },
 
-    /// Search markets
+    /// Search markets by title, slug or tag, tolerating typos
     Search {
         /// Search query string
         query: String,
 
-        /// Results per type
+        /// Maximum number of results
         #[arg(long, default_value = "10")]
-        limit: i32,
+        limit: usize,
+
+        /// Print JSON (same as `-o json`)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Rank open markets by 24h volume, liquidity or 24h price move
+    Top {
+        /// What to rank by
+        #[arg(long, value_enum, default_value_t = TopBy::Volume)]
+        by: TopBy,
+
+        /// Number of markets
+        #[arg(long, default_value = "20")]
+        limit: usize,
     },
 
     /// Get tags for a market
@@ -74,6 +160,17 @@ pub enum MarketsCommand {
     },
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum TopBy {
+    /// Volume traded in the last 24 hours
+    Volume,
+    /// Liquidity on the book
+    Liquidity,
+    /// Largest 24h price move, up or down, among the most traded markets
+    #[value(name = "24h-change")]
+    Change24h,
+}
+
 pub async fn execute(
     client: &gamma::Client,
     args: MarketsArgs,
@@ -83,12 +180,24 @@ pub async fn execute(
         MarketsCommand::List {
             active,
             closed,
+            tag,
+            liquidity_min,
+            volume_min,
             limit,
             offset,
+            cursor,
             order,
             ascending,
         } => {
             let resolved_closed = closed.or_else(|| active.map(|a| !a));
+            let offset = match cursor {
+                Some(c) => Some(parse_cursor(&c)?),
+                None => offset,
+            };
+            let tag_id = match tag {
+                Some(t) => Some(resolve_tag_id(client, t).await?),
+                None => None,
+            };
 
             let request = MarketsRequest::builder()
                 .limit(limit)
@@ -96,59 +205,104 @@ pub async fn execute(
                 .maybe_offset(offset)
                 .maybe_order(order)
                 .maybe_ascending(if ascending { Some(true) } else { None })
+                .maybe_tag_id(tag_id)
+                .maybe_liquidity_num_min(liquidity_min)
+                .maybe_volume_num_min(volume_min)
                 .build();
 
-            let markets = client.markets(&request).await?;
+            let markets = net::retry(|| client.markets(&request)).await?;
 
             match output {
-                OutputFormat::Table => print_markets_table(&markets),
+                OutputFormat::Table | OutputFormat::Plain => {
+                    print_markets_table(&markets);
+                    if let Some(next) = next_cursor(offset, markets.len(), limit) {
+                        println!("Next cursor: {next}");
+                    }
+                }
                 OutputFormat::Json => print_json(&markets)?,
+                OutputFormat::Csv => print_markets_csv(&markets),
             }
         }
 
         MarketsCommand::Get { id } => {
-            let is_numeric = is_numeric_id(&id);
-            let market = if is_numeric {
-                let req = MarketByIdRequest::builder().id(id).build();
-                client.market_by_id(&req).await?
-            } else {
-                let req = MarketBySlugRequest::builder().slug(id).build();
-                client.market_by_slug(&req).await?
-            };
+            let market = fetch_market(client, &id).await?;
 
             match output {
-                OutputFormat::Table => print_market_detail(&market),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    print_market_detail(&market)
+                }
                 OutputFormat::Json => print_json(&market)?,
             }
         }
 
-        MarketsCommand::Search { query, limit } => {
-            let request = SearchRequest::builder()
-                .q(query)
-                .limit_per_type(limit)
+        MarketsCommand::Source { id } => {
+            let market = fetch_market(client, &id).await?;
+            print_market_source(&market_source(market).await, &output)?;
+        }
+
+        MarketsCommand::Holders { id, limit } => {
+            let market = fetch_market(client, &id).await?;
+            let request = HoldersRequest::builder()
+                .markets(vec![market_condition_id(&market)?])
+                .limit(limit)?
                 .build();
+            let data = clients::data()?;
+            let holders = net::retry(|| data.holders(&request)).await?;
+            print_market_holders(
+                market.question.as_deref().unwrap_or_default(),
+                &outcome_holders(&market, holders),
+                &output,
+            )?;
+        }
 
-            let results = client.search(&request).await?;
+        MarketsCommand::Activity {
+            id,
+            min_usdc,
+            limit,
+        } => {
+            let filter = TradeFilter::cash(min_usdc)
+                .map_err(|_| errors::validation("--min-usdc must not be negative"))?;
+            let market = fetch_market(client, &id).await?;
+            let request = TradesRequest::builder()
+                .filter(MarketFilter::markets([market_condition_id(&market)?]))
+                .trade_filter(filter)
+                .limit(limit)?
+                .build();
+            let data = clients::data()?;
+            let trades = net::retry(|| data.trades(&request)).await?;
+            print_market_activity(
+                market.question.as_deref().unwrap_or_default(),
+                &trades,
+                &output,
+            )?;
+        }
 
-            let markets: Vec<Market> = results
-                .events
-                .unwrap_or_default()
-                .into_iter()
-                .flat_map(|e| e.markets.unwrap_or_default())
-                .collect();
+        MarketsCommand::Compare { ids } => {
+            // Fresh: the cached copy's prices and volume may be stale.
+            let markets = net::fetch_all(&ids, |id| fetch_market_fresh(client, id)).await?;
+            print_market_comparison(&markets, &output)?;
+        }
 
-            match output {
-                OutputFormat::Table => print_markets_table(&markets),
-                OutputFormat::Json => print_json(&markets)?,
-            }
+        MarketsCommand::Search { query, limit, json } => {
+            let hits = search_markets(client, &query, limit).await?;
+            let output = if json { OutputFormat::Json } else { output };
+            print_search_results(&hits, &output)?;
+        }
+
+        MarketsCommand::Top { by, limit } => {
+            anyhow::ensure!(limit > 0, "--limit must be at least 1");
+            let markets = top_markets(client, by, limit).await?;
+            print_top_markets(&markets, &output)?;
         }
 
         MarketsCommand::Tags { id } => {
             let req = MarketTagsRequest::builder().id(id).build();
-            let tags = client.market_tags(&req).await?;
+            let tags = net::retry(|| client.market_tags(&req)).await?;
 
             match output {
-                OutputFormat::Table => print_tags_table(&tags),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    print_tags_table(&tags)
+                }
                 OutputFormat::Json => print_json(&tags)?,
             }
         }
@@ -156,3 +310,673 @@ pub async fn execute(
 
     Ok(())
 }
+
+fn market_condition_id(market: &Market) -> Result<B256> {
+    market
+        .condition_id
+        .ok_or_else(|| anyhow::anyhow!("Market {} has no condition ID", market.id))
+}
+
+/// The data API's holders grouped by the market's outcomes, in the order
+/// the market lists them. Outcomes nobody holds are kept, empty.
+fn outcome_holders(market: &Market, mut holders: Vec<MetaHolder>) -> Vec<OutcomeHolders> {
+    let tokens = market.clob_token_ids.clone().unwrap_or_default();
+    let outcomes = market.outcomes.clone().unwrap_or_default();
+    let mut grouped: Vec<OutcomeHolders> = tokens
+        .iter()
+        .enumerate()
+        .map(|(i, token)| OutcomeHolders {
+            outcome: outcomes
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| format!("Outcome {i}")),
+            token_id: token.to_string(),
+            holders: holders
+                .iter()
+                .position(|mh| mh.token == *token)
+                .map(|at| holders.swap_remove(at).holders)
+                .unwrap_or_default(),
+        })
+        .collect();
+    // Tokens Gamma doesn't list, named by the outcome their holders hold.
+    grouped.extend(holders.into_iter().map(|mh| {
+        OutcomeHolders {
+            outcome: mh
+                .holders
+                .first()
+                .and_then(|h| outcomes.get(usize::try_from(h.outcome_index).ok()?))
+                .cloned()
+                .unwrap_or_else(|| mh.token.to_string()),
+            token_id: mh.token.to_string(),
+            holders: mh.holders,
+        }
+    }));
+    grouped
+}
+
+/// The `http(s)://` links in `texts`, each once, with trailing punctuation
+/// that belongs to the sentence left off.
+fn links<'a>(texts: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut links: Vec<String> = Vec::new();
+    for word in texts.into_iter().flat_map(str::split_whitespace) {
+        let Some(start) = word.find("http://").or_else(|| word.find("https://")) else {
+            continue;
+        };
+        let link = word[start..].trim_end_matches(['.', ',', ';', ':', ')', ']', '"', '\'']);
+        if !links.iter().any(|l| l == link) {
+            links.push(link.to_string());
+        }
+    }
+    links
+}
+
+/// A market's rules, links and UMA ancillary data. The event's resolution
+/// source stands in when the market has none of its own.
+async fn market_source(market: Market) -> MarketSource {
+    let resolution_source = market
+        .resolution_source
+        .clone()
+        .filter(|s| !s.is_empty())
+        .or_else(|| {
+            market
+                .events
+                .iter()
+                .flatten()
+                .find_map(|e| e.resolution_source.clone().filter(|s| !s.is_empty()))
+        });
+    let rules = market.description.clone().filter(|d| !d.trim().is_empty());
+    let resolver = market.resolved_by.clone().filter(|a| !a.is_empty());
+    let ancillary = match (&resolver, market.question_id) {
+        (Some(resolver), Some(question_id)) => match parse_address(resolver) {
+            Ok(adapter) => ancillary_data(adapter, question_id)
+                .await
+                .map_err(|e| format!("{e:#}")),
+            Err(e) => Err(format!("{e:#}")),
+        },
+        _ => Err("Gamma doesn't say which adapter resolves this market".into()),
+    };
+    MarketSource {
+        links: links(
+            resolution_source
+                .as_deref()
+                .into_iter()
+                .chain(rules.as_deref()),
+        ),
+        market_id: market.id,
+        question: market.question.unwrap_or_default(),
+        question_id: market.question_id.map(|q| q.to_string()),
+        resolver,
+        rules,
+        resolution_source,
+        ancillary_data: ancillary,
+    }
+}
+
+/// Market IDs are small integers; CLOB token IDs are 256-bit and always much
+/// longer, which is how the two are told apart.
+const MAX_MARKET_ID_DIGITS: usize = 20;
+
+#[derive(Debug, PartialEq)]
+enum MarketRef {
+    Id(String),
+    Slug(String),
+    Condition(B256),
+    Token(U256),
+}
+
+fn classify_market_ref(id: &str) -> Result<MarketRef> {
+    if id.starts_with("0x") || id.starts_with("0X") {
+        return Ok(MarketRef::Condition(parse_condition_id(id)?));
+    }
+    if is_numeric_id(id) {
+        if id.len() > MAX_MARKET_ID_DIGITS {
+            let token =
+                U256::from_str(id).map_err(|_| anyhow::anyhow!("Invalid token ID: {id}"))?;
+            return Ok(MarketRef::Token(token));
+        }
+        return Ok(MarketRef::Id(id.to_string()));
+    }
+    Ok(MarketRef::Slug(id.to_string()))
+}
+
+impl MarketRef {
+    /// The market's key in the disk cache.
+    fn cache_key(&self) -> String {
+        match self {
+            Self::Id(id) => format!("id-{id}"),
+            Self::Slug(slug) => format!("slug-{slug}"),
+            Self::Condition(condition_id) => condition_id.to_string(),
+            Self::Token(token_id) => token_id.to_string(),
+        }
+    }
+}
+
+/// Every key `market` can be looked up by, so a market fetched by slug is
+/// found again by its condition or token IDs.
+fn market_cache_keys(market: &Market) -> Vec<String> {
+    let mut refs = vec![MarketRef::Id(market.id.clone())];
+    refs.extend(market.slug.clone().map(MarketRef::Slug));
+    refs.extend(market.condition_id.map(MarketRef::Condition));
+    refs.extend(
+        market
+            .clob_token_ids
+            .iter()
+            .flatten()
+            .copied()
+            .map(MarketRef::Token),
+    );
+    refs.iter().map(MarketRef::cache_key).collect()
+}
+
+/// Fetches a market by numeric ID, slug, condition ID, or CLOB token ID,
+/// from the disk cache if it was fetched recently.
+pub(crate) async fn fetch_market(client: &gamma::Client, id: &str) -> Result<Market> {
+    let market_ref = classify_market_ref(id)?;
+    let key = market_ref.cache_key();
+    if let Some(market) = cache::get(cache::MARKETS, &key) {
+        return Ok(market);
+    }
+    let market = fetch_market_uncached(client, market_ref, id).await?;
+    let mut keys = market_cache_keys(&market);
+    if !keys.contains(&key) {
+        keys.push(key);
+    }
+    cache::put(cache::MARKETS, &keys, &market);
+    Ok(market)
+}
+
+/// Like `fetch_market`, but always asks Gamma, for fields that change after
+/// a market is cached, such as its resolution status.
+pub(crate) async fn fetch_market_fresh(client: &gamma::Client, id: &str) -> Result<Market> {
+    fetch_market_uncached(client, classify_market_ref(id)?, id).await
+}
+
+async fn fetch_market_uncached(
+    client: &gamma::Client,
+    market_ref: MarketRef,
+    id: &str,
+) -> Result<Market> {
+    let request = match market_ref {
+        MarketRef::Id(id) => {
+            let req = MarketByIdRequest::builder().id(id).build();
+            return net::retry(|| client.market_by_id(&req)).await;
+        }
+        MarketRef::Slug(slug) => {
+            let req = MarketBySlugRequest::builder().slug(slug).build();
+            return net::retry(|| client.market_by_slug(&req)).await;
+        }
+        MarketRef::Condition(condition_id) => MarketsRequest::builder()
+            .condition_ids(vec![condition_id])
+            .build(),
+        MarketRef::Token(token_id) => MarketsRequest::builder()
+            .clob_token_ids(vec![token_id])
+            .build(),
+    };
+
+    net::retry(|| client.markets(&request))
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("Market not found: {id}"))
+}
+
+/// Active markets (top by 24h volume) ranked alongside the search endpoint's
+/// results, so a misspelled query it misses still finds something.
+const SEARCH_POOL: i32 = 200;
+/// Hits scoring below this are noise from partial single-word matches.
+const MIN_SEARCH_SCORE: f64 = 0.5;
+
+/// A market with the event title and tags it is matched against.
+struct SearchCandidate {
+    market: Market,
+    event_title: Option<String>,
+    tags: Vec<String>,
+}
+
+impl SearchCandidate {
+    fn score(&self, query: &str) -> f64 {
+        let slug = self.market.slug.as_deref().unwrap_or_default();
+        let tags = self.tags.join(" ");
+        match_score(
+            query,
+            &[
+                (self.market.question.as_deref().unwrap_or_default(), 1.0),
+                (self.event_title.as_deref().unwrap_or_default(), 1.0),
+                (slug, 0.9),
+                (&tags, 0.6),
+            ],
+        )
+    }
+}
+
+fn tag_labels(tags: Option<Vec<Tag>>) -> Vec<String> {
+    tags.unwrap_or_default()
+        .into_iter()
+        .filter_map(|t| t.label)
+        .collect()
+}
+
+/// Markets whose 24h moves `markets top --by 24h-change` ranks: the most
+/// traded, since a big move on a market nobody trades is noise.
+const TOP_CHANGE_POOL: usize = 500;
+
+/// The top `limit` open markets by `by`, best first.
+async fn top_markets(client: &gamma::Client, by: TopBy, limit: usize) -> Result<Vec<Market>> {
+    // Gamma can sort by volume and liquidity, but not by the size of a move.
+    let (order, pool) = match by {
+        TopBy::Volume => ("volume24hr", limit),
+        TopBy::Liquidity => ("liquidityNum", limit),
+        TopBy::Change24h => ("volume24hr", limit.max(TOP_CHANGE_POOL)),
+    };
+    let request = MarketsRequest::builder()
+        .limit(i32::try_from(pool)?)
+        .closed(false)
+        .order(order.to_string())
+        .build();
+    let mut markets = net::retry(|| client.markets(&request)).await?;
+    rank_markets(&mut markets, by);
+    markets.truncate(limit);
+    Ok(markets)
+}
+
+/// Sorts `markets` best first by `by`. Markets without the number go last.
+fn rank_markets(markets: &mut [Market], by: TopBy) {
+    markets.sort_by_key(|m| {
+        let key = match by {
+            TopBy::Volume => m.volume_24hr,
+            TopBy::Liquidity => m.liquidity_num,
+            TopBy::Change24h => m.one_day_price_change.map(|d| d.abs()),
+        };
+        std::cmp::Reverse(key)
+    });
+}
+
+/// Markets matching `query`, best first. Candidates come from Gamma's search
+/// plus the most active markets, and are ranked locally by [`match_score`].
+async fn search_markets(
+    client: &gamma::Client,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<SearchHit>> {
+    let search = SearchRequest::builder()
+        .q(query)
+        .limit_per_type(i32::try_from(limit.max(20)).unwrap_or(i32::MAX))
+        .build();
+    let pool = MarketsRequest::builder()
+        .limit(SEARCH_POOL)
+        .closed(false)
+        .order("volume24hr".to_string())
+        .build();
+    let (results, active) = tokio::try_join!(client.search(&search), client.markets(&pool))?;
+
+    let searched = results
+        .events
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(|e| {
+            let tags = tag_labels(e.tags);
+            let title = e.title;
+            e.markets
+                .unwrap_or_default()
+                .into_iter()
+                .map(move |m| SearchCandidate {
+                    market: m,
+                    event_title: title.clone(),
+                    tags: tags.clone(),
+                })
+        });
+    let pooled = active.into_iter().map(|mut m| {
+        let event = m.events.take().and_then(|e| e.into_iter().next());
+        let tags = m
+            .tags
+            .take()
+            .or_else(|| event.as_ref().and_then(|e| e.tags.clone()));
+        SearchCandidate {
+            event_title: event.and_then(|e| e.title),
+            tags: tag_labels(tags),
+            market: m,
+        }
+    });
+
+    let mut hits: Vec<SearchHit> = Vec::new();
+    for candidate in searched.chain(pooled) {
+        if hits.iter().any(|h| h.market.id == candidate.market.id) {
+            continue;
+        }
+        let score = candidate.score(query);
+        if score >= MIN_SEARCH_SCORE {
+            hits.push(SearchHit {
+                score,
+                market: candidate.market,
+            });
+        }
+    }
+    // Equal scores go to the busier market.
+    hits.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| b.market.volume_24hr.cmp(&a.market.volume_24hr))
+    });
+    hits.truncate(limit);
+    Ok(hits)
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// How well one query word matches one word of a candidate, from 0 to 1.
+/// Longer words tolerate more typos.
+fn word_similarity(query: &str, word: &str) -> f64 {
+    if query == word {
+        return 1.0;
+    }
+    if word.starts_with(query) {
+        return 0.9;
+    }
+    let len = query.chars().count();
+    if len >= 3 && word.contains(query) {
+        return 0.7;
+    }
+    let allowed = match len {
+        0..4 => 0,
+        4..8 => 1,
+        _ => 2,
+    };
+    match edit_distance(query, word) {
+        0 => 1.0,
+        1 if allowed >= 1 => 0.65,
+        2 if allowed >= 2 => 0.5,
+        _ => 0.0,
+    }
+}
+
+/// Relevance of weighted text fields to `query`: each query word's best
+/// weighted match, averaged, plus a bonus when the whole query appears as a
+/// phrase in one field.
+fn match_score(query: &str, fields: &[(&str, f64)]) -> f64 {
+    let query_words = words(query);
+    if query_words.is_empty() {
+        return 0.0;
+    }
+    let fields: Vec<(Vec<String>, f64)> = fields.iter().map(|(t, w)| (words(t), *w)).collect();
+    let total: f64 = query_words
+        .iter()
+        .map(|q| {
+            fields
+                .iter()
+                .flat_map(|(ws, weight)| ws.iter().map(move |w| weight * word_similarity(q, w)))
+                .fold(0.0, f64::max)
+        })
+        .sum();
+    let phrase = query_words.join(" ");
+    let bonus = if fields.iter().any(|(ws, _)| ws.join(" ").contains(&phrase)) {
+        0.25
+    } else {
+        0.0
+    };
+    #[allow(clippy::cast_precision_loss)]
+    let average = total / query_words.len() as f64;
+    average + bonus
+}
+
+/// Tags can be given by slug; the markets endpoint only filters by tag ID.
+async fn resolve_tag_id(client: &gamma::Client, tag: String) -> Result<String> {
+    if is_numeric_id(&tag) {
+        return Ok(tag);
+    }
+    let req = TagBySlugRequest::builder().slug(tag).build();
+    Ok(net::retry(|| client.tag_by_slug(&req)).await?.id)
+}
+
+fn parse_cursor(cursor: &str) -> Result<i32> {
+    cursor
+        .parse::<i32>()
+        .ok()
+        .filter(|n| *n >= 0)
+        .ok_or_else(|| anyhow::anyhow!("Invalid cursor: {cursor}"))
+}
+
+/// A full page means there may be more; the cursor is the next page's offset.
+fn next_cursor(offset: Option<i32>, returned: usize, limit: i32) -> Option<String> {
+    let returned = i32::try_from(returned).ok()?;
+    (limit > 0 && returned >= limit).then(|| (offset.unwrap_or(0) + returned).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_are_found_once_without_trailing_punctuation() {
+        let rules = "Resolves per https://www.bls.gov/cpi/. If unavailable, see \
+                     (https://fred.stlouisfed.org/series/CPIAUCSL), then https://www.bls.gov/cpi/";
+        assert_eq!(
+            links(["https://www.bls.gov/cpi/", rules]),
+            vec![
+                "https://www.bls.gov/cpi/".to_string(),
+                "https://fred.stlouisfed.org/series/CPIAUCSL".to_string(),
+            ]
+        );
+        assert!(links(["No links here"]).is_empty());
+    }
+
+    #[test]
+    fn holders_are_grouped_by_outcome_in_market_order() {
+        let market: Market = serde_json::from_value(serde_json::json!({
+            "id": "12",
+            "outcomes": "[\"Yes\",\"No\"]",
+            "clobTokenIds": "[\"111\",\"222\"]",
+        }))
+        .unwrap();
+        let holder = |asset: &str, index: i32| {
+            serde_json::json!({
+                "proxyWallet": "0x0000000000000000000000000000000000000001",
+                "asset": asset,
+                "amount": 50,
+                "outcomeIndex": index,
+            })
+        };
+        let holders: Vec<MetaHolder> = serde_json::from_value(serde_json::json!([
+            {"token": "222", "holders": [holder("222", 1)]},
+            {"token": "333", "holders": [holder("333", 0)]},
+        ]))
+        .unwrap();
+
+        let grouped = outcome_holders(&market, holders);
+        let summary: Vec<_> = grouped
+            .iter()
+            .map(|o| (o.outcome.as_str(), o.token_id.as_str(), o.holders.len()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("Yes", "111", 0), ("No", "222", 1), ("Yes", "333", 1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn market_source_uses_the_event_source_without_an_adapter() {
+        let market: Market = serde_json::from_value(serde_json::json!({
+            "id": "12",
+            "question": "Will it rain?",
+            "description": "Resolves Yes if https://weather.gov reports rain.",
+            "resolutionSource": "",
+            "events": [{"id": "3", "resolutionSource": "https://weather.gov/nyc"}],
+        }))
+        .unwrap();
+        let source = market_source(market).await;
+        assert_eq!(
+            source.resolution_source.as_deref(),
+            Some("https://weather.gov/nyc")
+        );
+        assert_eq!(
+            source.links,
+            ["https://weather.gov/nyc", "https://weather.gov"]
+        );
+        assert!(source.ancillary_data.is_err());
+    }
+
+    #[test]
+    fn rank_markets_puts_the_largest_first() {
+        let mut markets: Vec<Market> = [
+            ("1", Some("100"), Some("-0.02")),
+            ("2", None, Some("0.15")),
+            ("3", Some("900"), Some("-0.30")),
+            ("4", Some("50"), None),
+        ]
+        .into_iter()
+        .map(|(id, volume, change)| {
+            serde_json::from_value(serde_json::json!({
+                "id": id,
+                "volume24hr": volume.map(|v| v.parse::<f64>().unwrap()),
+                "oneDayPriceChange": change.map(|c| c.parse::<f64>().unwrap()),
+            }))
+            .unwrap()
+        })
+        .collect();
+        let ids = |markets: &[Market]| markets.iter().map(|m| m.id.clone()).collect::<Vec<_>>();
+
+        rank_markets(&mut markets, TopBy::Volume);
+        assert_eq!(ids(&markets), ["3", "1", "4", "2"]);
+        rank_markets(&mut markets, TopBy::Change24h);
+        assert_eq!(ids(&markets), ["3", "2", "1", "4"]);
+    }
+
+    #[test]
+    fn edit_distance_counts_single_edits() {
+        assert_eq!(edit_distance("bitcoin", "bitcoin"), 0);
+        assert_eq!(edit_distance("bitcion", "bitcoin"), 2);
+        assert_eq!(edit_distance("electon", "election"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn word_similarity_prefers_exact_then_prefix() {
+        assert!((word_similarity("trump", "trump") - 1.0).abs() < f64::EPSILON);
+        assert!(word_similarity("elect", "election") > word_similarity("electon", "election"));
+        assert!(word_similarity("electon", "election") > 0.0);
+        assert!(word_similarity("fed", "fee").abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn match_score_ranks_closer_titles_higher() {
+        let exact = match_score(
+            "bitcoin 100k",
+            &[("Will Bitcoin reach $100k by December?", 1.0)],
+        );
+        let typo = match_score(
+            "bitcon 100k",
+            &[("Will Bitcoin reach $100k by December?", 1.0)],
+        );
+        let unrelated = match_score("bitcoin 100k", &[("Will it rain in London?", 1.0)]);
+        assert!(exact > typo, "{exact} <= {typo}");
+        assert!(typo >= MIN_SEARCH_SCORE, "{typo}");
+        assert!(unrelated < MIN_SEARCH_SCORE, "{unrelated}");
+    }
+
+    #[test]
+    fn match_score_uses_slug_and_tags() {
+        let score = match_score(
+            "nba finals",
+            &[
+                ("Who will win?", 1.0),
+                ("nba-finals-2026", 0.9),
+                ("Sports", 0.6),
+            ],
+        );
+        assert!(score >= MIN_SEARCH_SCORE, "{score}");
+    }
+
+    #[test]
+    fn market_cache_keys_cover_every_ref() {
+        let market: Market = serde_json::from_value(serde_json::json!({
+            "id": "12",
+            "slug": "will-it-rain",
+            "conditionId": "0x00000000000000000000000000000000000000000000000000000000000000ab",
+            "clobTokenIds": "[\"101\", \"102\"]",
+        }))
+        .unwrap();
+        assert_eq!(
+            market_cache_keys(&market),
+            [
+                "id-12",
+                "slug-will-it-rain",
+                "0x00000000000000000000000000000000000000000000000000000000000000ab",
+                "101",
+                "102",
+            ]
+        );
+    }
+
+    #[test]
+    fn classify_short_number_as_market_id() {
+        assert_eq!(
+            classify_market_ref("516710").unwrap(),
+            MarketRef::Id("516710".into())
+        );
+    }
+
+    #[test]
+    fn classify_long_number_as_token_id() {
+        let token = "48331043336612883890938759509493159234755048973583954730006854632066573";
+        assert!(matches!(
+            classify_market_ref(token).unwrap(),
+            MarketRef::Token(_)
+        ));
+    }
+
+    #[test]
+    fn classify_hex_as_condition_id() {
+        let id = "0x0000000000000000000000000000000000000000000000000000000000000001";
+        assert!(matches!(
+            classify_market_ref(id).unwrap(),
+            MarketRef::Condition(_)
+        ));
+        assert!(classify_market_ref("0x1234").is_err());
+    }
+
+    #[test]
+    fn classify_text_as_slug() {
+        assert_eq!(
+            classify_market_ref("will-trump-win").unwrap(),
+            MarketRef::Slug("will-trump-win".into())
+        );
+    }
+
+    #[test]
+    fn next_cursor_on_full_page() {
+        assert_eq!(next_cursor(None, 25, 25).as_deref(), Some("25"));
+        assert_eq!(next_cursor(Some(50), 25, 25).as_deref(), Some("75"));
+    }
+
+    #[test]
+    fn next_cursor_none_on_last_page() {
+        assert_eq!(next_cursor(Some(50), 10, 25), None);
+        assert_eq!(next_cursor(None, 0, 25), None);
+    }
+
+    #[test]
+    fn parse_cursor_rejects_garbage() {
+        assert_eq!(parse_cursor("100").unwrap(), 100);
+        assert!(parse_cursor("-1").is_err());
+        assert!(parse_cursor("abc").is_err());
+    }
+}
@@ -1,20 +1,65 @@
 use polymarket_client_sdk::types::{Address, B256};
 
+use crate::errors;
+
+pub mod alert;
+pub mod allowance;
+pub mod apikey;
 pub mod approve;
+pub mod auth;
+pub mod backtest;
+pub mod balance;
+pub mod batch;
+pub mod book;
 pub mod bridge;
+pub mod cache;
 pub mod clob;
+pub mod close;
 pub mod comments;
+pub mod completions;
+pub mod config;
+pub mod contacts;
+pub mod convert;
+pub mod copy;
 pub mod ctf;
 pub mod data;
+pub mod db;
+pub mod deposit;
+pub mod doctor;
 pub mod events;
+pub mod fees;
+pub mod gas;
+pub mod leaderboard;
 pub mod markets;
+pub mod order;
+pub mod panic;
+pub mod paper;
+pub mod portfolio;
+pub mod positions;
 pub mod profiles;
+pub mod quote;
+pub mod record;
+pub mod redeem;
+pub mod replay;
+pub mod resolution;
+pub mod rewards;
+pub mod scan;
 pub mod series;
 pub mod setup;
+pub mod size;
 pub mod sports;
+pub mod state;
+pub mod stop;
+pub mod stream;
+pub mod sync;
 pub mod tags;
+pub mod tax;
+pub mod trades;
+pub mod transfer;
+pub mod tx;
 pub mod upgrade;
 pub mod wallet;
+pub mod watch;
 
 pub fn is_numeric_id(id: &str) -> bool {
     !id.is_empty() && id.chars().all(|c| c.is_ascii_digit())
@@ -22,18 +67,100 @@ pub fn is_numeric_id(id: &str) -> bool {
 
 pub fn parse_address(s: &str) -> anyhow::Result<Address> {
     s.parse()
-        .map_err(|_| anyhow::anyhow!("Invalid address: must be a 0x-prefixed hex address"))
+        .map_err(|_| errors::validation("Invalid address: must be a 0x-prefixed hex address"))
+}
+
+/// An address, or the name of a saved contact (matched case-insensitively).
+pub fn resolve_address(s: &str) -> anyhow::Result<Address> {
+    if let Ok(address) = s.parse() {
+        return Ok(address);
+    }
+    let contact = crate::config::load_contacts()?
+        .into_iter()
+        .find(|c| c.name.eq_ignore_ascii_case(s))
+        .ok_or_else(|| {
+            errors::validation(format!(
+                "'{s}' is neither a 0x-prefixed address nor a saved contact (see `polymarket contacts list`)"
+            ))
+        })?;
+    parse_address(&contact.address)
 }
 
 pub fn parse_condition_id(s: &str) -> anyhow::Result<B256> {
     s.parse()
-        .map_err(|_| anyhow::anyhow!("Invalid condition ID: must be a 0x-prefixed 32-byte hex"))
+        .map_err(|_| errors::validation("Invalid condition ID: must be a 0x-prefixed 32-byte hex"))
+}
+
+pub fn parse_date(s: &str) -> anyhow::Result<chrono::NaiveDate> {
+    chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map_err(|_| errors::validation("Invalid date: expected YYYY-MM-DD format"))
+}
+
+/// Parses a duration like `500ms`, `90s`, `30m`, `2h`, `1d` or `1w`.
+pub fn parse_duration(s: &str) -> anyhow::Result<chrono::Duration> {
+    let err = || {
+        errors::validation(format!(
+            "Invalid duration '{s}': expected e.g. 30s, 15m, 2h, 1d, 1w"
+        ))
+    };
+    let split = s.find(|c: char| !c.is_ascii_digit()).ok_or_else(err)?;
+    let (num, unit) = s.split_at(split);
+    let n: i64 = num.parse().map_err(|_| err())?;
+    let duration = match unit {
+        "ms" => chrono::Duration::try_milliseconds(n),
+        "s" => chrono::Duration::try_seconds(n),
+        "m" => chrono::Duration::try_minutes(n),
+        "h" => chrono::Duration::try_hours(n),
+        "d" => chrono::Duration::try_days(n),
+        "w" => chrono::Duration::try_weeks(n),
+        _ => None,
+    };
+    duration.ok_or_else(err)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_date_valid() {
+        let d = parse_date("2024-06-15").unwrap();
+        assert_eq!(d.to_string(), "2024-06-15");
+    }
+
+    #[test]
+    fn parse_date_leap_day() {
+        let d = parse_date("2024-02-29").unwrap();
+        assert_eq!(d.to_string(), "2024-02-29");
+    }
+
+    #[test]
+    fn parse_date_invalid_format() {
+        assert!(parse_date("06/15/2024").is_err());
+        assert!(parse_date("2024-13-01").is_err());
+        assert!(parse_date("not-a-date").is_err());
+        assert!(parse_date("").is_err());
+    }
+
+    #[test]
+    fn parse_duration_units() {
+        assert_eq!(parse_duration("500ms").unwrap().num_milliseconds(), 500);
+        assert_eq!(parse_duration("90s").unwrap().num_seconds(), 90);
+        assert_eq!(parse_duration("30m").unwrap().num_minutes(), 30);
+        assert_eq!(parse_duration("2h").unwrap().num_hours(), 2);
+        assert_eq!(parse_duration("1d").unwrap().num_hours(), 24);
+        assert_eq!(parse_duration("1w").unwrap().num_days(), 7);
+    }
+
+    #[test]
+    fn parse_duration_rejects_garbage() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("10").is_err());
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("5y").is_err());
+        assert!(parse_duration("-5m").is_err());
+    }
+
     #[test]
     fn is_numeric_id_pure_digits() {
         assert!(is_numeric_id("12345"));
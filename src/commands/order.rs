@@ -0,0 +1,890 @@
+//! `order`: execution algorithms that work a large order over time.
+//!
+//! `order place` sends one order, as `clob create-order` with `--price` or
+//! `clob market-order` without, `order list` shows open orders as `clob
+//! orders` does and `order cancel*` are the `clob cancel*` commands, so
+//! scripts written against the `order` group don't need the `clob` names.
+//!
+//! `order amend` changes the price or size of an open order. The CLOB can't
+//! edit an order in place, so the replacement is signed first and then
+//! posted straight after the cancel, keeping the gap as short as possible;
+//! the new order joins the back of the queue at its price.
+//!
+//! `order ladder` spreads limit orders evenly over a price range, previewed
+//! and then signed and posted like a `clob batch` file.
+//!
+//! `order twap` splits a market order into slices sent at even intervals,
+//! each wait nudged by a random jitter so the slices don't form an obvious
+//! pattern. The job is saved after every slice (see `config::TwapJob`), so
+//! Ctrl-C or a crash only pauses it until `order twap resume`.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use polymarket_client_sdk::auth::Normal;
+use polymarket_client_sdk::auth::state::Authenticated;
+use polymarket_client_sdk::clob;
+use polymarket_client_sdk::clob::types::response::OpenOrderResponse;
+use polymarket_client_sdk::clob::types::{Amount, OrderStatusType, OrderType, Side};
+use polymarket_client_sdk::types::Decimal;
+use rand::Rng;
+use rust_decimal::RoundingStrategy;
+use rust_decimal::prelude::ToPrimitive;
+
+use super::batch::{self, BatchOrder};
+use super::clob::{
+    CliOrderType, CliSide, ClobArgs, ClobCommand, ExpiryFlags, order_params, parse_token_id,
+};
+use super::parse_duration;
+use super::wallet::resolve_trading_address;
+use crate::auth;
+use crate::clients;
+use crate::config::{self, TwapJob};
+use crate::confirm;
+use crate::errors;
+use crate::net;
+use crate::output::clob::print_batch_results;
+use crate::output::order::{
+    AmendResult, format_amend_preview, format_ladder, print_amend, print_twap_done,
+    print_twap_jobs, print_twap_paused, print_twap_plan, print_twap_slice,
+};
+use crate::output::{OutputFormat, print_json_line};
+use crate::procs;
+use crate::risk::{self, Proposed};
+
+#[derive(Args)]
+pub struct OrderArgs {
+    #[command(subcommand)]
+    pub command: OrderCommand,
+}
+
+#[derive(Subcommand)]
+pub enum OrderCommand {
+    /// Place one order: a limit order with --price, a market order without
+    /// (authenticated)
+    Place {
+        /// Token ID (numeric string)
+        #[arg(long)]
+        token: String,
+        /// Side: buy or sell
+        #[arg(long)]
+        side: CliSide,
+        /// Limit price (e.g. 0.50); leave out to trade at market
+        #[arg(long)]
+        price: Option<String>,
+        /// Shares for a limit order; at market, USDC to spend on a buy or
+        /// shares to sell
+        #[arg(long)]
+        size: String,
+        /// Order type: GTC, FOK, GTD, FAK (default: the profile's
+        /// defaults.order_type, or defaults.market_order_type at market)
+        #[arg(long, alias = "type")]
+        order_type: Option<CliOrderType>,
+        #[command(flatten)]
+        expiry: ExpiryFlags,
+        /// Post-only order
+        #[arg(long, requires = "price")]
+        post_only: bool,
+        /// Your own ID for the order, e.g. a UUID (see `clob create-order`)
+        #[arg(long)]
+        client_id: Option<String>,
+        /// Sign the order and print it without submitting
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Work a large market order in slices over a period, or manage the
+    /// saved jobs (authenticated)
+    Twap(TwapArgs),
+    /// Place a grid of limit orders evenly spaced over a price range
+    /// (authenticated)
+    Ladder {
+        /// Token ID (numeric string)
+        #[arg(long)]
+        token: String,
+        /// Side: buy or sell
+        #[arg(long)]
+        side: CliSide,
+        /// Price of the first level
+        #[arg(long)]
+        from: Decimal,
+        /// Price of the last level
+        #[arg(long)]
+        to: Decimal,
+        /// Number of orders, from --from to --to inclusive
+        #[arg(long)]
+        levels: u32,
+        /// Shares at each level
+        #[arg(long)]
+        size_per_level: Decimal,
+        /// Only rest on the book; reject levels that would fill on arrival
+        #[arg(long)]
+        post_only: bool,
+        /// Orders posted at the same time
+        #[arg(long, default_value = "5")]
+        concurrency: usize,
+        /// Sign the orders and print them without submitting
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Cancel an open order and place it again with a new price or size
+    /// (authenticated)
+    Amend {
+        /// ID of the open order
+        order_id: String,
+        /// New limit price (default: unchanged)
+        #[arg(long, required_unless_present = "size")]
+        price: Option<Decimal>,
+        /// New size in shares (default: what is left unfilled)
+        #[arg(long)]
+        size: Option<Decimal>,
+        /// Reject the replacement if it would fill on arrival
+        #[arg(long)]
+        post_only: bool,
+    },
+    /// List your open orders, or find the one placed with --client-id
+    /// (authenticated)
+    List {
+        /// Filter by market condition ID
+        #[arg(long)]
+        market: Option<String>,
+        /// Filter by asset/token ID
+        #[arg(long)]
+        asset: Option<String>,
+        /// Only show orders on this side
+        #[arg(long)]
+        side: Option<CliSide>,
+        /// Only show orders created longer ago than this (e.g. 30m, 2h, 1d)
+        #[arg(long)]
+        older_than: Option<String>,
+        /// Pagination cursor
+        #[arg(long)]
+        cursor: Option<String>,
+        /// Show the order placed with this --client-id instead
+        #[arg(long, conflicts_with_all = ["market", "asset", "side", "older_than", "cursor"])]
+        client_id: Option<String>,
+    },
+    /// Cancel an open order (authenticated)
+    Cancel {
+        /// Order ID to cancel
+        order_id: String,
+    },
+    /// Cancel all open orders (authenticated)
+    CancelAll,
+    /// Cancel the open orders in one market (authenticated)
+    CancelMarket {
+        /// Market condition ID (same as --market)
+        #[arg(conflicts_with = "market")]
+        condition_id: Option<String>,
+        /// Market condition ID
+        #[arg(long)]
+        market: Option<String>,
+        /// Asset/token ID
+        #[arg(long)]
+        asset: Option<String>,
+    },
+}
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub struct TwapArgs {
+    #[command(subcommand)]
+    pub command: Option<TwapCommand>,
+
+    /// Token ID (numeric string)
+    #[arg(long, required = true)]
+    token: Option<String>,
+    /// Side: buy or sell
+    #[arg(long, required = true)]
+    side: Option<CliSide>,
+    /// USDC to spend on a buy, shares to sell
+    #[arg(long, required = true)]
+    total: Option<Decimal>,
+    /// How long to spread the order over, e.g. 2h or 30m
+    #[arg(long, required = true)]
+    duration: Option<String>,
+    /// Most to send in one slice, in the units of --total
+    #[arg(long, required = true)]
+    slice: Option<Decimal>,
+    /// Vary each wait by up to this fraction of the interval (0 to 1)
+    #[arg(long, default_value = "0.2")]
+    jitter: Decimal,
+    /// Order type of each slice: FAK (fill what's there) or FOK (all or nothing)
+    #[arg(long, default_value = "FAK")]
+    order_type: CliOrderType,
+}
+
+#[derive(Subcommand)]
+pub enum TwapCommand {
+    /// TWAP jobs with their progress and average fill price
+    List,
+    /// Pick up an interrupted TWAP where it stopped (authenticated)
+    Resume {
+        /// Job ID (see `order twap list`)
+        id: u32,
+    },
+    /// Forget a TWAP job
+    Remove {
+        /// Job ID
+        id: u32,
+    },
+}
+
+pub async fn execute(
+    args: OrderArgs,
+    output: &OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    match args.command {
+        OrderCommand::Place {
+            token,
+            side,
+            price,
+            size,
+            order_type,
+            expiry,
+            post_only,
+            client_id,
+            dry_run,
+        } => {
+            anyhow::ensure!(
+                price.is_some() || (expiry.expires_in.is_none() && expiry.expires_at.is_none()),
+                errors::validation("Market orders don't expire; an expiry needs --price")
+            );
+            let command = match price {
+                Some(price) => ClobCommand::CreateOrder {
+                    token,
+                    side,
+                    price: Some(price),
+                    odds: None,
+                    size: Some(size),
+                    notional: None,
+                    order_type,
+                    expiry,
+                    post_only,
+                    no_self_cross: false,
+                    client_id,
+                    dry_run,
+                },
+                None => ClobCommand::MarketOrder {
+                    token,
+                    side,
+                    amount: size,
+                    order_type,
+                    no_self_cross: false,
+                    max_slippage: None,
+                    limit_price: None,
+                    client_id,
+                    dry_run,
+                },
+            };
+            super::clob::execute(ClobArgs { command }, *output, private_key, signature_type).await
+        }
+        OrderCommand::Twap(args) => twap(args, output, private_key, signature_type).await,
+        OrderCommand::Ladder {
+            token,
+            side,
+            from,
+            to,
+            levels,
+            size_per_level,
+            post_only,
+            concurrency,
+            dry_run,
+        } => {
+            anyhow::ensure!(!config::paper_mode(), "{}", super::paper::UNSUPPORTED_MSG);
+            anyhow::ensure!(concurrency > 0, "--concurrency must be at least 1");
+            anyhow::ensure!(
+                size_per_level > Decimal::ZERO,
+                "--size-per-level must be positive"
+            );
+            let token_id = parse_token_id(&token)?;
+            let params = order_params(&clients::clob()?, token_id).await?;
+            let prices = ladder_prices(from, to, levels, params.tick_size)?;
+            let orders: Vec<BatchOrder> = prices
+                .into_iter()
+                .map(|price| {
+                    params.check_limit_order(price, size_per_level)?;
+                    Ok(BatchOrder {
+                        token: token_id,
+                        side: side.clone().into(),
+                        price,
+                        size: size_per_level,
+                        order_type: OrderType::GTC,
+                        post_only,
+                    })
+                })
+                .collect::<Result<_>>()?;
+            let preview = format_ladder(&orders);
+            if dry_run {
+                confirm::preview(&preview, output);
+            } else if !confirm::confirm(
+                &preview,
+                &format!("Submit {} orders?", orders.len()),
+                output,
+            )? {
+                return Ok(());
+            }
+            let signer = auth::resolve_signer(private_key).await?;
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+            risk::check(
+                &client,
+                &batch::proposed(&orders),
+                resolve_trading_address(private_key, signature_type),
+            )
+            .await?;
+            let results = batch::submit(&client, &signer, orders, concurrency, dry_run).await;
+            print_batch_results(&results, dry_run, output)
+        }
+        OrderCommand::Amend {
+            order_id,
+            price,
+            size,
+            post_only,
+        } => {
+            anyhow::ensure!(!config::paper_mode(), "{}", super::paper::UNSUPPORTED_MSG);
+            let signer = auth::resolve_signer(private_key).await?;
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+            let old = net::retry(|| client.order(&order_id)).await?;
+            anyhow::ensure!(
+                old.status == OrderStatusType::Live,
+                errors::validation(format!(
+                    "Order {order_id} is {}, not live; only an open order can be amended",
+                    old.status
+                ))
+            );
+            let remaining = old.original_size - old.size_matched;
+            let (new_price, new_size) = amended(old.price, remaining, price, size)?;
+            order_params(&client, old.asset_id)
+                .await?
+                .check_limit_order(new_price, new_size)?;
+            let replacement = Proposed::limit(old.asset_id, old.side, new_price, new_size);
+            risk::check(
+                &client,
+                &[replacement.replacing(&order_id)],
+                resolve_trading_address(private_key, signature_type),
+            )
+            .await?;
+            amend(
+                &client, &signer, &old, new_price, new_size, post_only, output,
+            )
+            .await
+        }
+        OrderCommand::List {
+            market,
+            asset,
+            side,
+            older_than,
+            cursor,
+            client_id,
+        } => {
+            let command = ClobCommand::Orders {
+                market,
+                asset,
+                side,
+                older_than,
+                cursor,
+                client_id,
+            };
+            super::clob::execute(ClobArgs { command }, *output, private_key, signature_type).await
+        }
+        OrderCommand::Cancel { order_id } => {
+            let command = ClobCommand::Cancel { order_id };
+            super::clob::execute(ClobArgs { command }, *output, private_key, signature_type).await
+        }
+        OrderCommand::CancelAll => {
+            let command = ClobCommand::CancelAll;
+            super::clob::execute(ClobArgs { command }, *output, private_key, signature_type).await
+        }
+        OrderCommand::CancelMarket {
+            condition_id,
+            market,
+            asset,
+        } => {
+            let command = ClobCommand::CancelMarket {
+                condition_id,
+                market,
+                asset,
+            };
+            super::clob::execute(ClobArgs { command }, *output, private_key, signature_type).await
+        }
+    }
+}
+
+async fn twap(
+    args: TwapArgs,
+    output: &OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    match args.command {
+        None => {
+            // clap requires these whenever no subcommand is given.
+            let (Some(token), Some(side), Some(total), Some(duration), Some(slice)) =
+                (args.token, args.side, args.total, args.duration, args.slice)
+            else {
+                unreachable!("clap enforces the TWAP arguments");
+            };
+            anyhow::ensure!(!config::paper_mode(), "{}", super::paper::UNSUPPORTED_MSG);
+            let duration = parse_duration(&duration)?;
+            let job = plan(
+                parse_token_id(&token)?.to_string(),
+                side.into(),
+                total,
+                slice,
+                duration,
+                args.jitter,
+                args.order_type.into(),
+            )?;
+            let signer = auth::resolve_signer(private_key).await?;
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+            check_risk(&client, &job, private_key, signature_type).await?;
+            let mut jobs = config::load_twaps()?;
+            let job = TwapJob {
+                id: jobs.iter().map(|j| j.id).max().unwrap_or(0) + 1,
+                ..job
+            };
+            jobs.push(job.clone());
+            config::save_twaps(&jobs)?;
+            print_twap_plan(&job);
+            run(job, &client, &signer, output).await
+        }
+        Some(TwapCommand::List) => print_twap_jobs(&config::load_twaps()?, output),
+        Some(TwapCommand::Resume { id }) => {
+            anyhow::ensure!(!config::paper_mode(), "{}", super::paper::UNSUPPORTED_MSG);
+            let job = find(id)?;
+            anyhow::ensure!(!job.is_done(), "TWAP #{id} has already finished");
+            let signer = auth::resolve_signer(private_key).await?;
+            let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+            check_risk(&client, &job, private_key, signature_type).await?;
+            print_twap_plan(&job);
+            run(job, &client, &signer, output).await
+        }
+        Some(TwapCommand::Remove { id }) => {
+            let mut jobs = config::load_twaps()?;
+            let before = jobs.len();
+            jobs.retain(|j| j.id != id);
+            anyhow::ensure!(jobs.len() < before, "No TWAP job #{id}");
+            config::save_twaps(&jobs)?;
+            match output {
+                OutputFormat::Json => print_json_line(serde_json::json!({"removed": id})),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    println!("Removed TWAP #{id}.");
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Checks what is left of `job` against the risk limits as one market order.
+async fn check_risk(
+    client: &clob::Client<Authenticated<Normal>>,
+    job: &TwapJob,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    let order = Proposed::market(
+        parse_token_id(&job.token_id)?,
+        job.side,
+        None,
+        job.remaining(),
+    );
+    risk::check(
+        client,
+        &[order],
+        resolve_trading_address(private_key, signature_type),
+    )
+    .await
+}
+
+fn find(id: u32) -> Result<TwapJob> {
+    config::load_twaps()?
+        .into_iter()
+        .find(|j| j.id == id)
+        .ok_or_else(|| anyhow::anyhow!("No TWAP job #{id}"))
+}
+
+/// The price and size an amended order gets: the new ones where given,
+/// otherwise the old price and the unfilled remainder.
+fn amended(
+    old_price: Decimal,
+    remaining: Decimal,
+    price: Option<Decimal>,
+    size: Option<Decimal>,
+) -> Result<(Decimal, Decimal)> {
+    let (new_price, new_size) = (price.unwrap_or(old_price), size.unwrap_or(remaining));
+    anyhow::ensure!(
+        new_price != old_price || new_size != remaining,
+        errors::validation(format!(
+            "Nothing to change: the order already rests at {old_price} for {} shares",
+            remaining.normalize()
+        ))
+    );
+    Ok((new_price, new_size))
+}
+
+/// Replaces the live order `old` with one at `new_price` for `new_size`.
+async fn amend(
+    client: &clob::Client<Authenticated<Normal>>,
+    signer: &auth::WalletSigner,
+    old: &OpenOrderResponse,
+    new_price: Decimal,
+    new_size: Decimal,
+    post_only: bool,
+    output: &OutputFormat,
+) -> Result<()> {
+    let order_id = old.id.as_str();
+    let remaining = old.original_size - old.size_matched;
+    if !confirm::confirm(
+        &format_amend_preview(old, new_price, new_size),
+        "Replace this order?",
+        output,
+    )? {
+        return Ok(());
+    }
+    // Sign before cancelling, so a rejected signature (or a Ledger that
+    // isn't plugged in) leaves the old order alone.
+    let mut builder = client
+        .limit_order()
+        .token_id(old.asset_id)
+        .side(old.side)
+        .price(new_price)
+        .size(new_size)
+        .order_type(old.order_type.clone())
+        .post_only(post_only);
+    if old.order_type == OrderType::GTD {
+        builder = builder.expiration(old.expiration);
+    }
+    let signed = auth::sign_order(client, signer, builder.build().await?).await?;
+
+    let cancelled = net::once(client.cancel_order(order_id)).await?;
+    if !cancelled.canceled.iter().any(|id| id == order_id) {
+        let reason = cancelled
+            .not_canceled
+            .get(order_id)
+            .map_or("no reason given", String::as_str);
+        anyhow::bail!("Order {order_id} was not cancelled ({reason}); nothing was replaced");
+    }
+    let posted = net::once(client.post_order(signed))
+        .await
+        .with_context(|| {
+            format!(
+                "Cancelled {order_id}, but placing the replacement failed; \
+             nothing is resting in its place"
+            )
+        })?;
+    print_amend(
+        &AmendResult {
+            old_id: order_id.to_string(),
+            new_id: posted.order_id.clone(),
+            side: old.side,
+            old_price: old.price,
+            old_size: remaining,
+            price: new_price,
+            size: new_size,
+            status: posted.status.to_string(),
+            error: posted.error_msg.clone().filter(|e| !e.is_empty()),
+        },
+        output,
+    )
+}
+
+/// `levels` prices evenly spaced from `from` to `to`, each rounded to the
+/// nearest multiple of `tick`.
+fn ladder_prices(from: Decimal, to: Decimal, levels: u32, tick: Decimal) -> Result<Vec<Decimal>> {
+    anyhow::ensure!(levels >= 2, "--levels must be at least 2");
+    anyhow::ensure!(from != to, "--from and --to must differ");
+    let step = (to - from) / Decimal::from(levels - 1);
+    let tick = tick.normalize();
+    let prices: Vec<Decimal> = (0..levels)
+        .map(|i| {
+            let price = from + step * Decimal::from(i);
+            ((price / tick).round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+                * tick)
+                .normalize()
+        })
+        .collect();
+    anyhow::ensure!(
+        prices.windows(2).all(|w| w[0] != w[1]),
+        "Levels would be closer than the tick size {tick}; use fewer --levels or a wider range"
+    );
+    Ok(prices)
+}
+
+/// A new job for `total` in slices of at most `slice`, evenly spaced over
+/// `duration`. The ID is assigned when it is saved.
+fn plan(
+    token_id: String,
+    side: Side,
+    total: Decimal,
+    slice: Decimal,
+    duration: chrono::Duration,
+    jitter: Decimal,
+    order_type: OrderType,
+) -> Result<TwapJob> {
+    anyhow::ensure!(total > Decimal::ZERO, "--total must be positive");
+    anyhow::ensure!(slice > Decimal::ZERO, "--slice must be positive");
+    anyhow::ensure!(
+        (Decimal::ZERO..Decimal::ONE).contains(&jitter),
+        "--jitter must be at least 0 and below 1, got {jitter}"
+    );
+    anyhow::ensure!(
+        matches!(order_type, OrderType::FAK | OrderType::FOK),
+        "--order-type must be FAK or FOK for market order slices"
+    );
+    let secs = u64::try_from(duration.num_seconds())
+        .ok()
+        .filter(|s| *s > 0)
+        .context("--duration must be at least 1s")?;
+    let slices = (total / slice)
+        .round_dp_with_strategy(0, RoundingStrategy::AwayFromZero)
+        .to_u32()
+        .context("Too many slices; use a larger --slice")?;
+    Ok(TwapJob {
+        id: 0,
+        token_id,
+        side,
+        total,
+        slice,
+        slices,
+        interval_secs: secs / u64::from(slices),
+        jitter,
+        order_type,
+        sent: 0,
+        filled_shares: Decimal::ZERO,
+        filled_usdc: Decimal::ZERO,
+        started_at: chrono::Utc::now(),
+    })
+}
+
+/// The next slice's amount: a full slice, or what's left, rounded down to
+/// two decimals (cents, or hundredths of a share).
+fn next_amount(job: &TwapJob) -> Decimal {
+    job.slice
+        .min(job.remaining())
+        .round_dp_with_strategy(2, RoundingStrategy::ToZero)
+}
+
+/// `interval_secs` moved by `shift` (from -1 to 1) times the jitter.
+fn jittered(interval_secs: u64, jitter: Decimal, shift: f64) -> Duration {
+    let interval = interval_secs as f64;
+    let jitter = jitter.to_f64().unwrap_or(0.0);
+    Duration::from_secs_f64((interval * (1.0 + jitter * shift)).max(0.0))
+}
+
+fn save(job: &TwapJob) -> Result<()> {
+    let mut jobs = config::load_twaps()?;
+    match jobs.iter_mut().find(|j| j.id == job.id) {
+        Some(saved) => *saved = job.clone(),
+        None => jobs.push(job.clone()),
+    }
+    config::save_twaps(&jobs)
+}
+
+/// Sends the job's remaining slices, saving after each. Returns early,
+/// leaving the job paused, on Ctrl-C or a failed request.
+async fn run(
+    mut job: TwapJob,
+    client: &clob::Client<Authenticated<Normal>>,
+    signer: &auth::WalletSigner,
+    output: &OutputFormat,
+) -> Result<()> {
+    let token_id = parse_token_id(&job.token_id)?;
+    order_params(client, token_id).await?;
+    let _running = procs::register(&format!("order twap #{}", job.id));
+
+    while !job.is_done() {
+        let amount = next_amount(&job);
+        if amount.is_zero() {
+            break;
+        }
+        let posted = async {
+            let order = client
+                .market_order()
+                .token_id(token_id)
+                .side(job.side)
+                .amount(if job.side == Side::Sell {
+                    Amount::shares(amount)?
+                } else {
+                    Amount::usdc(amount)?
+                })
+                .order_type(job.order_type.clone())
+                .build()
+                .await?;
+            let signed = auth::sign_order(client, signer, order).await?;
+            net::once(client.post_order(signed)).await
+        }
+        .await;
+        let result = match posted {
+            Ok(result) => result,
+            Err(e) => {
+                // It may have been sent, so it's left to the user to check
+                // before resuming.
+                save(&job)?;
+                return Err(e.context(format!(
+                    "Slice {} of TWAP #{} failed. Check your orders, then run \
+                     `polymarket order twap resume {}`",
+                    job.sent + 1,
+                    job.id,
+                    job.id
+                )));
+            }
+        };
+
+        let (shares, usdc) = if job.side == Side::Sell {
+            (result.making_amount, result.taking_amount)
+        } else {
+            (result.taking_amount, result.making_amount)
+        };
+        job.sent += 1;
+        if result.success {
+            job.filled_shares += shares;
+            job.filled_usdc += usdc;
+        }
+        save(&job)?;
+        print_twap_slice(
+            &job,
+            shares,
+            usdc,
+            result.error_msg.as_deref().filter(|e| !e.is_empty()),
+            output,
+        );
+        if job.is_done() {
+            break;
+        }
+
+        let shift = rand::thread_rng().gen_range(-1.0..=1.0);
+        tokio::select! {
+            () = tokio::time::sleep(jittered(job.interval_secs, job.jitter, shift)) => {}
+            _ = tokio::signal::ctrl_c() => {
+                print_twap_paused(&job);
+                return Ok(());
+            }
+        }
+    }
+    print_twap_done(&job, output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn job(total: Decimal, slice: Decimal) -> TwapJob {
+        plan(
+            "1".into(),
+            Side::Buy,
+            total,
+            slice,
+            chrono::Duration::hours(2),
+            dec!(0.2),
+            OrderType::FAK,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn plan_spreads_slices_over_the_duration() {
+        let twap = job(dec!(5000), dec!(100));
+        assert_eq!(twap.slices, 50);
+        assert_eq!(twap.interval_secs, 144);
+
+        // A partial last slice still counts.
+        assert_eq!(job(dec!(250), dec!(100)).slices, 3);
+    }
+
+    #[test]
+    fn plan_rejects_bad_input() {
+        let plan = |jitter: Decimal, order_type| {
+            plan(
+                "1".into(),
+                Side::Buy,
+                dec!(100),
+                dec!(10),
+                chrono::Duration::hours(1),
+                jitter,
+                order_type,
+            )
+        };
+        assert!(plan(dec!(1), OrderType::FAK).is_err());
+        assert!(plan(dec!(0.5), OrderType::GTC).is_err());
+        assert!(plan(dec!(0), OrderType::FOK).is_ok());
+    }
+
+    #[test]
+    fn last_slice_takes_what_is_left() {
+        let mut job = job(dec!(250), dec!(100));
+        assert_eq!(next_amount(&job), dec!(100));
+        job.filled_usdc = dec!(199.995);
+        assert_eq!(next_amount(&job), dec!(50.00));
+        job.filled_usdc = dec!(250);
+        assert!(job.is_done());
+    }
+
+    #[test]
+    fn progress_and_average_price() {
+        let mut job = job(dec!(100), dec!(50));
+        assert_eq!(job.avg_price(), None);
+        job.sent = 1;
+        job.filled_usdc = dec!(50);
+        job.filled_shares = dec!(100);
+        assert_eq!(job.remaining(), dec!(50));
+        assert_eq!(job.avg_price(), Some(dec!(0.5)));
+        assert!(!job.is_done());
+        job.sent = 2;
+        assert!(job.is_done());
+    }
+
+    #[test]
+    fn amend_keeps_what_is_not_given() {
+        assert_eq!(
+            amended(dec!(0.4), dec!(150), Some(dec!(0.45)), None).unwrap(),
+            (dec!(0.45), dec!(150))
+        );
+        assert_eq!(
+            amended(dec!(0.4), dec!(150), None, Some(dec!(200))).unwrap(),
+            (dec!(0.4), dec!(200))
+        );
+        let err = amended(dec!(0.4), dec!(150), Some(dec!(0.40)), None).unwrap_err();
+        assert!(
+            err.to_string().starts_with("Nothing to change"),
+            "got: {err}"
+        );
+    }
+
+    #[test]
+    fn ladder_prices_span_the_range_on_the_tick_grid() {
+        let prices = ladder_prices(dec!(0.30), dec!(0.40), 6, dec!(0.01)).unwrap();
+        assert_eq!(
+            prices,
+            [
+                dec!(0.3),
+                dec!(0.32),
+                dec!(0.34),
+                dec!(0.36),
+                dec!(0.38),
+                dec!(0.4)
+            ]
+        );
+
+        // A step between ticks is rounded to the nearest one.
+        let prices = ladder_prices(dec!(0.40), dec!(0.30), 4, dec!(0.01)).unwrap();
+        assert_eq!(prices, vec![dec!(0.4), dec!(0.37), dec!(0.33), dec!(0.3)]);
+    }
+
+    #[test]
+    fn ladder_prices_reject_levels_finer_than_the_tick() {
+        assert!(ladder_prices(dec!(0.30), dec!(0.32), 5, dec!(0.01)).is_err());
+        assert!(ladder_prices(dec!(0.30), dec!(0.30), 2, dec!(0.01)).is_err());
+        assert!(ladder_prices(dec!(0.30), dec!(0.40), 1, dec!(0.01)).is_err());
+    }
+
+    #[test]
+    fn jitter_stays_within_bounds() {
+        assert_eq!(jittered(100, dec!(0.2), 0.0), Duration::from_secs(100));
+        assert_eq!(jittered(100, dec!(0.2), 1.0), Duration::from_secs(120));
+        assert_eq!(jittered(100, dec!(0.2), -1.0), Duration::from_secs(80));
+    }
+}
@@ -0,0 +1,97 @@
+//! `polymarket panic`: the emergency stop. Shuts down the local daemon and
+//! every other running command that can trade (see [`crate::procs`]) so
+//! nothing places an order behind it, cancels every open order across all
+//! markets and, with `--close-positions`, sells every position at market.
+//!
+//! It asks once up front and then does each step without further prompts.
+
+use anyhow::Result;
+use clap::Args;
+use polymarket_client_sdk::auth::Normal;
+use polymarket_client_sdk::auth::state::Authenticated;
+use polymarket_client_sdk::clob;
+
+use super::close::{self, ExitPrice};
+use super::wallet::resolve_trading_address;
+use crate::auth::{self, WalletSigner};
+use crate::confirm;
+use crate::daemon::ipc::{self, Request};
+use crate::net;
+use crate::output::OutputFormat;
+use crate::output::clob::{BatchOrderResult, print_panic};
+use crate::procs;
+
+#[derive(Args)]
+pub struct PanicArgs {
+    /// Also sell every position at market once orders are canceled
+    #[arg(long)]
+    pub close_positions: bool,
+}
+
+pub async fn execute(
+    args: PanicArgs,
+    output: &OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    let question = if args.close_positions {
+        "Stop the daemon, cancel all open orders and sell every position at market?"
+    } else {
+        "Stop the daemon and cancel all open orders?"
+    };
+    if !confirm::ask(question, output)? {
+        return Ok(());
+    }
+
+    // The daemon and the bots go first so none of them can place an order
+    // after the cancel. Failing to stop one mustn't keep the orders from
+    // going; it is listed instead. A daemon the socket didn't reach is
+    // signalled like the rest.
+    let daemon = match ipc::try_request(&Request::Shutdown).await {
+        Ok(Some(_)) => "stopped".to_string(),
+        Ok(None) => "not running".to_string(),
+        Err(e) => {
+            eprintln!("Warning: could not reach the daemon: {e:#}");
+            format!("not reached ({e:#})")
+        }
+    };
+    let processes = match procs::running() {
+        Ok(running) => procs::stop(running).await,
+        Err(e) => {
+            eprintln!("Warning: could not list running commands: {e:#}");
+            Vec::new()
+        }
+    };
+
+    let signer = auth::resolve_signer(private_key).await?;
+    let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+    let canceled = net::once(client.cancel_all_orders()).await?;
+
+    // A failed close is reported after what was canceled, not instead of it.
+    let (closed, failed) = if args.close_positions {
+        match close_all(&client, &signer, private_key, signature_type).await {
+            Ok(results) => (Some(results), None),
+            Err(e) => (None, Some(e)),
+        }
+    } else {
+        (None, None)
+    };
+    print_panic(&daemon, &processes, &canceled, closed.as_deref(), output)?;
+    if let Some(e) = failed {
+        return Err(e);
+    }
+    let left = processes.iter().filter(|(_, e)| e.is_some()).count();
+    anyhow::ensure!(left == 0, "{left} running commands could not be stopped");
+    Ok(())
+}
+
+async fn close_all(
+    client: &clob::Client<Authenticated<Normal>>,
+    signer: &WalletSigner,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<Vec<BatchOrderResult>> {
+    let address = resolve_trading_address(private_key, signature_type).await?;
+    let (exits, order_type) = close::plan(address, None, ExitPrice::Market).await?;
+    Ok(close::submit(client, signer, &exits, &order_type, false).await)
+}
@@ -0,0 +1,196 @@
+//! `paper`: the simulated account behind `--paper`, plus the paper versions
+//! of the `clob` order commands.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use polymarket_client_sdk::types::Decimal;
+
+use super::alert::fetch_midpoints;
+use crate::clients;
+use crate::output::OutputFormat;
+use crate::output::paper::{
+    PaperPosition, PaperSummary, print_paper_fills, print_paper_orders, print_paper_placed,
+    print_paper_summary, report_resting_fills,
+};
+use crate::paper::{self, Account, NewOrder, PaperOrder};
+
+pub const UNSUPPORTED_MSG: &str = "Not available in paper mode. Paper trading covers \
+    `clob create-order`, `clob market-order`, `clob orders` and the `clob cancel` commands; \
+    drop --paper (or set the profile's mode to live) to trade for real.";
+
+#[derive(Args)]
+pub struct PaperArgs {
+    #[command(subcommand)]
+    pub command: PaperCommand,
+}
+
+#[derive(Subcommand)]
+pub enum PaperCommand {
+    /// Paper balance and positions marked to the midpoint, with PnL
+    #[command(visible_alias = "positions")]
+    Status,
+    /// Open paper orders
+    Orders,
+    /// Simulated fills, oldest first
+    Fills {
+        /// Show only the most recent N
+        #[arg(long, default_value = "50")]
+        limit: usize,
+    },
+    /// Start over: drop all paper positions, orders and fills
+    Reset {
+        /// Starting USDC balance
+        #[arg(long, default_value_t = paper::default_balance())]
+        balance: Decimal,
+    },
+}
+
+pub async fn execute(args: PaperArgs, output: OutputFormat) -> Result<()> {
+    match args.command {
+        PaperCommand::Status => status(&output).await,
+        PaperCommand::Orders => orders(&output).await,
+        PaperCommand::Fills { limit } => {
+            let (account, new) = paper::load_matched().await?;
+            report_resting_fills(&new);
+            let skip = account.fills.len().saturating_sub(limit);
+            print_paper_fills(&account.fills[skip..], &output)
+        }
+        PaperCommand::Reset { balance } => {
+            anyhow::ensure!(balance > Decimal::ZERO, "--balance must be positive");
+            Account::new(balance).save()?;
+            println!("Paper account reset with {balance} USDC");
+            Ok(())
+        }
+    }
+}
+
+/// Simulates `order` against the live book. With `dry_run` the account is
+/// left untouched.
+pub(crate) async fn place(order: NewOrder, dry_run: bool, output: &OutputFormat) -> Result<()> {
+    let book = paper::fetch_book(&order.token_id).await?;
+    let (mut account, new) = paper::load_matched().await?;
+    report_resting_fills(&new);
+    let placed = account.place(order, &book)?;
+    if dry_run {
+        eprintln!("Dry run: the paper account was not changed.");
+    } else {
+        account.save()?;
+    }
+    print_paper_placed(&placed, output)
+}
+
+pub(crate) async fn orders(output: &OutputFormat) -> Result<()> {
+    filtered_orders(|_| true, output).await
+}
+
+/// Open paper orders that pass `keep`.
+pub(crate) async fn filtered_orders(
+    keep: impl Fn(&PaperOrder) -> bool,
+    output: &OutputFormat,
+) -> Result<()> {
+    let (account, new) = paper::load_matched().await?;
+    report_resting_fills(&new);
+    let orders: Vec<PaperOrder> = account.orders.into_iter().filter(|o| keep(o)).collect();
+    print_paper_orders(&orders, output)
+}
+
+/// Cancels the given paper orders, or all of them when `ids` is empty.
+pub(crate) fn cancel(ids: &[&str]) -> Result<()> {
+    let mut account = Account::load()?;
+    let canceled: Vec<String> = if ids.is_empty() {
+        account.orders.drain(..).map(|o| o.id).collect()
+    } else {
+        for id in ids {
+            account.cancel(id)?;
+        }
+        ids.iter().map(ToString::to_string).collect()
+    };
+    account.save()?;
+    if canceled.is_empty() {
+        println!("No open paper orders.");
+    }
+    for id in canceled {
+        println!("Canceled {id}");
+    }
+    Ok(())
+}
+
+pub(crate) async fn status(output: &OutputFormat) -> Result<()> {
+    let (account, new) = paper::load_matched().await?;
+    report_resting_fills(&new);
+    let tokens: Vec<&str> = account.positions.keys().map(String::as_str).collect();
+    let marks = if tokens.is_empty() {
+        HashMap::new()
+    } else {
+        fetch_midpoints(&clients::clob()?, &tokens).await?
+    };
+    print_paper_summary(&summarize(&account, &marks), output)
+}
+
+/// Marks positions to `marks` (midpoints by token); positions without one
+/// are valued at cost.
+fn summarize(account: &Account, marks: &HashMap<String, Decimal>) -> PaperSummary {
+    let positions: Vec<PaperPosition> = account
+        .positions
+        .iter()
+        .map(|(token_id, p)| {
+            let midpoint = marks.get(token_id).copied();
+            let value = p.size * midpoint.unwrap_or(p.avg_price);
+            PaperPosition {
+                token_id: token_id.clone(),
+                size: p.size,
+                avg_price: p.avg_price,
+                midpoint,
+                value,
+                pnl: value - p.size * p.avg_price,
+            }
+        })
+        .collect();
+    let equity = account.balance + positions.iter().map(|p| p.value).sum::<Decimal>();
+    PaperSummary {
+        balance: account.balance,
+        starting_balance: account.starting_balance,
+        open_orders: account.orders.len(),
+        pnl: equity - account.starting_balance,
+        equity,
+        positions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paper::Position;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn summarize_marks_to_midpoint_or_cost() {
+        let mut account = Account::new(dec!(1000));
+        account.balance = dec!(940);
+        account.positions.insert(
+            "1".into(),
+            Position {
+                size: dec!(100),
+                avg_price: dec!(0.40),
+            },
+        );
+        account.positions.insert(
+            "2".into(),
+            Position {
+                size: dec!(50),
+                avg_price: dec!(0.40),
+            },
+        );
+        let marks = HashMap::from([("1".to_string(), dec!(0.55))]);
+        let s = summarize(&account, &marks);
+        assert_eq!(s.positions[0].value, dec!(55));
+        assert_eq!(s.positions[0].pnl, dec!(15));
+        // No midpoint for token 2: valued at cost.
+        assert_eq!(s.positions[1].value, dec!(20));
+        assert_eq!(s.positions[1].pnl, Decimal::ZERO);
+        assert_eq!(s.equity, dec!(1015));
+        assert_eq!(s.pnl, dec!(15));
+    }
+}
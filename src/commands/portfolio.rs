@@ -0,0 +1,646 @@
+//! `portfolio`: the trading wallet's balance, positions, open orders and
+//! PnL, with `portfolio history` how its value moved over time.
+//!
+//! History is rebuilt from the wallet's on-chain activity: trades, splits,
+//! merges and redemptions are replayed to get its holdings at each point,
+//! which are marked at the CLOB's price history. USDC is worked back from
+//! today's balance, so deposits and withdrawals (which aren't in the
+//! activity feed) show up as jumps in value but not in PnL.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use clap::{Args, Subcommand, ValueEnum};
+use futures_util::StreamExt as _;
+use polymarket_client_sdk::auth::Normal;
+use polymarket_client_sdk::auth::state::Authenticated;
+use polymarket_client_sdk::clob::types::request::{
+    BalanceAllowanceRequest, OrdersRequest, PriceHistoryRequest,
+};
+use polymarket_client_sdk::clob::types::response::OpenOrderResponse;
+use polymarket_client_sdk::clob::types::{AssetType, Side, TimeRange};
+use polymarket_client_sdk::data::types::request::{
+    ActivityRequest, ClosedPositionsRequest, PositionsRequest,
+};
+use polymarket_client_sdk::data::types::response::{Activity, ClosedPosition, Position};
+use polymarket_client_sdk::data::types::{
+    ActivitySortBy, ActivityType, Side as TradeSide, SortDirection,
+};
+use polymarket_client_sdk::types::{Address, B256, Decimal, U256};
+use polymarket_client_sdk::{clob, data};
+
+use super::wallet::resolve_trading_address;
+use crate::auth;
+use crate::clients;
+use crate::config::{self, KeySource};
+use crate::net;
+use crate::output::OutputFormat;
+use crate::output::clob::END_CURSOR;
+use crate::output::portfolio::{
+    HistoryChart, HistoryPoint, PortfolioSummary, print_history, print_portfolio,
+};
+
+/// Largest page sizes the data API accepts for each endpoint.
+const POSITIONS_PAGE: i32 = 500;
+const CLOSED_POSITIONS_PAGE: i32 = 50;
+const ACTIVITY_PAGE: i32 = 500;
+const USDC_DECIMALS: u32 = 6;
+/// Most points a history can have; a longer --interval covers more time.
+const MAX_POINTS: i64 = 5_000;
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct PortfolioArgs {
+    #[command(subcommand)]
+    pub command: Option<PortfolioCommand>,
+}
+
+#[derive(Subcommand)]
+pub enum PortfolioCommand {
+    /// Portfolio value and PnL over time, rebuilt from trades and prices
+    History {
+        /// Time between points, e.g. 1d, 6h or 1w
+        #[arg(long, default_value = "1d")]
+        interval: String,
+        /// First day to show, YYYY-MM-DD (default: the wallet's first trade)
+        #[arg(long)]
+        from: Option<String>,
+        /// What the chart plots in table output
+        #[arg(long, value_enum, default_value_t = CliHistoryChart::Value)]
+        chart: CliHistoryChart,
+    },
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum CliHistoryChart {
+    Value,
+    Pnl,
+}
+
+impl From<CliHistoryChart> for HistoryChart {
+    fn from(c: CliHistoryChart) -> Self {
+        match c {
+            CliHistoryChart::Value => Self::Value,
+            CliHistoryChart::Pnl => Self::Pnl,
+        }
+    }
+}
+
+pub async fn execute(
+    args: PortfolioArgs,
+    output: &OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    match args.command {
+        None => summary(output, private_key, signature_type).await,
+        Some(PortfolioCommand::History {
+            interval,
+            from,
+            chart,
+        }) => {
+            let interval = super::parse_duration(&interval)?;
+            anyhow::ensure!(
+                interval >= chrono::Duration::hours(1),
+                crate::errors::validation("--interval must be at least 1h")
+            );
+            let from = from.as_deref().map(super::parse_date).transpose()?;
+            history(
+                interval,
+                from,
+                chart.into(),
+                output,
+                private_key,
+                signature_type,
+            )
+            .await
+        }
+    }
+}
+
+async fn summary(
+    output: &OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    let address = resolve_trading_address(private_key, signature_type).await?;
+    let data_client = clients::data()?;
+    if matches!(config::key_source(private_key), KeySource::WatchOnly) {
+        // Open orders need an API key, which takes a signature to get, so a
+        // watch-only wallet reads its USDC on-chain and leaves orders out.
+        let (positions, closed, usdc_balance) = tokio::try_join!(
+            fetch_positions(&data_client, address),
+            fetch_closed_positions(&data_client, address),
+            super::balance::trading_usdc(private_key, signature_type),
+        )?;
+        eprintln!("Watch-only wallet: open orders aren't included.");
+        let summary = summarize(address, usdc_balance, &positions, &closed, &[]);
+        return print_portfolio(&summary, output);
+    }
+    let clob_client = auth::authenticated_clob_client(private_key, signature_type).await?;
+
+    let balance_request = BalanceAllowanceRequest::builder()
+        .asset_type(AssetType::Collateral)
+        .build();
+    let (positions, closed, balance, orders) = tokio::try_join!(
+        fetch_positions(&data_client, address),
+        fetch_closed_positions(&data_client, address),
+        net::retry(|| clob_client.balance_allowance(balance_request.clone())),
+        fetch_open_orders(&clob_client),
+    )?;
+
+    let usdc_balance = balance.balance / Decimal::from(10u64.pow(USDC_DECIMALS));
+    let summary = summarize(address, usdc_balance, &positions, &closed, &orders);
+    print_portfolio(&summary, output)
+}
+
+pub(crate) async fn fetch_open_orders(
+    client: &clob::Client<Authenticated<Normal>>,
+) -> Result<Vec<OpenOrderResponse>> {
+    fetch_orders(client, &OrdersRequest::builder().build()).await
+}
+
+/// Every page of open orders matching `request`.
+pub(crate) async fn fetch_orders(
+    client: &clob::Client<Authenticated<Normal>>,
+    request: &OrdersRequest,
+) -> Result<Vec<OpenOrderResponse>> {
+    let mut orders = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = net::retry(|| client.orders(request, cursor.clone())).await?;
+        orders.extend(page.data);
+        if page.next_cursor == END_CURSOR || page.next_cursor.is_empty() {
+            return Ok(orders);
+        }
+        cursor = Some(page.next_cursor);
+    }
+}
+
+pub(crate) async fn fetch_positions(client: &data::Client, user: Address) -> Result<Vec<Position>> {
+    let mut all = Vec::new();
+    loop {
+        let request = PositionsRequest::builder()
+            .user(user)
+            .limit(POSITIONS_PAGE)?
+            .offset(i32::try_from(all.len())?)?
+            .build();
+        let page = net::retry(|| client.positions(&request)).await?;
+        let done = page.len() < POSITIONS_PAGE as usize;
+        all.extend(page);
+        if done {
+            return Ok(all);
+        }
+    }
+}
+
+async fn fetch_closed_positions(
+    client: &data::Client,
+    user: Address,
+) -> Result<Vec<ClosedPosition>> {
+    let mut all = Vec::new();
+    loop {
+        let request = ClosedPositionsRequest::builder()
+            .user(user)
+            .limit(CLOSED_POSITIONS_PAGE)?
+            .offset(i32::try_from(all.len())?)?
+            .build();
+        let page = net::retry(|| client.closed_positions(&request)).await?;
+        let done = page.len() < CLOSED_POSITIONS_PAGE as usize;
+        all.extend(page);
+        if done {
+            return Ok(all);
+        }
+    }
+}
+
+async fn history(
+    interval: chrono::Duration,
+    from: Option<chrono::NaiveDate>,
+    chart: HistoryChart,
+    output: &OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    let address = resolve_trading_address(private_key, signature_type).await?;
+    let data_client = clients::data()?;
+    let (activity, balance) = tokio::try_join!(
+        fetch_activity(&data_client, address),
+        super::balance::trading_usdc(private_key, signature_type),
+    )?;
+    let flows: Vec<Flow> = activity.iter().filter_map(Flow::from_activity).collect();
+    let Some(first) = flows.first() else {
+        anyhow::bail!("{address} has no trading activity yet");
+    };
+
+    let step = interval.num_seconds();
+    let now = Utc::now().timestamp();
+    let start = match from {
+        Some(date) => date.and_time(chrono::NaiveTime::MIN).and_utc().timestamp(),
+        None => first.time - first.time.rem_euclid(step.min(86_400)),
+    };
+    anyhow::ensure!(
+        start < now,
+        crate::errors::validation("--from must be in the past")
+    );
+    anyhow::ensure!(
+        (now - start) / step < MAX_POINTS,
+        crate::errors::validation(format!(
+            "That is more than {MAX_POINTS} points; use a later --from or a longer --interval"
+        ))
+    );
+    let mut times: Vec<i64> = (start..now).step_by(step as usize).collect();
+    times.push(now);
+
+    let snapshots = replay(&flows, &times);
+    let held: HashSet<U256> = snapshots
+        .iter()
+        .flat_map(|s| s.holdings.iter().map(|h| h.asset))
+        .collect();
+    let prices = fetch_prices(held, start, now, step).await;
+    let total_flow = flows.iter().map(Flow::cash).sum();
+    let points = mark(&snapshots, &prices, balance, total_flow);
+    print_history(&points, chart, output)
+}
+
+pub(crate) async fn fetch_activity(client: &data::Client, user: Address) -> Result<Vec<Activity>> {
+    // Oldest first, paged by time: offsets stop at 10,000.
+    let mut all: Vec<Activity> = Vec::new();
+    let mut seen = HashSet::new();
+    let mut start = 0;
+    loop {
+        let request = ActivityRequest::builder()
+            .user(user)
+            .limit(ACTIVITY_PAGE)?
+            .start(start)
+            .sort_by(ActivitySortBy::Timestamp)
+            .sort_direction(SortDirection::Asc)
+            .build();
+        let page = net::retry(|| client.activity(&request)).await?;
+        let full = page.len() == ACTIVITY_PAGE as usize;
+        let last = page.last().map(|a| a.timestamp);
+        let before = all.len();
+        for a in page {
+            let key = (
+                a.transaction_hash,
+                a.asset,
+                a.activity_type.to_string(),
+                a.size,
+            );
+            if seen.insert(key) {
+                all.push(a);
+            }
+        }
+        // A page that added nothing new is all one second: stop there
+        // rather than ask for it again.
+        match last {
+            Some(last) if full && all.len() > before => {
+                start = u64::try_from(last).context("Invalid activity timestamp")?;
+            }
+            _ => return Ok(all),
+        }
+    }
+}
+
+/// Price history of each asset between `start` and `end`, a point per
+/// `step` seconds. An asset whose history can't be fetched is left out and
+/// marked at its last trade price instead.
+async fn fetch_prices(
+    assets: HashSet<U256>,
+    start: i64,
+    end: i64,
+    step: i64,
+) -> HashMap<U256, Vec<(i64, Decimal)>> {
+    let Ok(client) = clients::clob() else {
+        return HashMap::new();
+    };
+    let fidelity = u32::try_from(step / 60).unwrap_or(u32::MAX);
+    futures_util::stream::iter(assets)
+        .map(|asset| {
+            let client = &client;
+            async move {
+                let request = PriceHistoryRequest::builder()
+                    .market(asset)
+                    .time_range(TimeRange::from_range(start, end))
+                    .fidelity(fidelity)
+                    .build();
+                match net::retry(|| client.price_history(&request)).await {
+                    Ok(h) => Some((asset, h.history.into_iter().map(|p| (p.t, p.p)).collect())),
+                    Err(e) => {
+                        tracing::debug!(%asset, "no price history: {e:#}");
+                        None
+                    }
+                }
+            }
+        })
+        .buffer_unordered(net::MAX_CONCURRENT)
+        .filter_map(std::future::ready)
+        .collect()
+        .await
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FlowKind {
+    Buy,
+    Sell,
+    Split,
+    Merge,
+    Redeem,
+    /// Rewards, rebates, yield and conversions: USDC only.
+    Cash,
+}
+
+/// One activity as it moves shares and USDC.
+#[derive(Clone, Debug)]
+struct Flow {
+    time: i64,
+    kind: FlowKind,
+    asset: Option<U256>,
+    condition: Option<B256>,
+    size: Decimal,
+    usdc: Decimal,
+    price: Option<Decimal>,
+}
+
+impl Flow {
+    fn from_activity(a: &Activity) -> Option<Self> {
+        let kind = match (&a.activity_type, &a.side) {
+            (ActivityType::Trade, Some(TradeSide::Buy)) => FlowKind::Buy,
+            (ActivityType::Trade, Some(TradeSide::Sell)) => FlowKind::Sell,
+            (ActivityType::Trade, _) => return None,
+            (ActivityType::Split, _) => FlowKind::Split,
+            (ActivityType::Merge, _) => FlowKind::Merge,
+            (ActivityType::Redeem, _) => FlowKind::Redeem,
+            _ => FlowKind::Cash,
+        };
+        Some(Self {
+            time: a.timestamp,
+            kind,
+            asset: a.asset,
+            condition: a.condition_id,
+            size: a.size,
+            usdc: a.usdc_size,
+            price: a.price,
+        })
+    }
+
+    /// USDC into the wallet (negative when it paid).
+    fn cash(&self) -> Decimal {
+        match self.kind {
+            FlowKind::Buy | FlowKind::Split => -self.usdc,
+            FlowKind::Sell | FlowKind::Merge | FlowKind::Redeem | FlowKind::Cash => self.usdc,
+        }
+    }
+}
+
+struct Holding {
+    asset: U256,
+    shares: Decimal,
+    /// The last trade price seen, for when there's no price history.
+    last_price: Decimal,
+}
+
+/// The wallet at one point: its shares, and the USDC its activity has
+/// brought in so far.
+struct Snapshot {
+    time: i64,
+    cash_flow: Decimal,
+    holdings: Vec<Holding>,
+}
+
+/// Replays `flows` (oldest first) and takes a snapshot at each of `times`.
+/// Splits and merges add or remove shares of every outcome of the market
+/// the wallet has traded; the activity feed doesn't name them.
+fn replay(flows: &[Flow], times: &[i64]) -> Vec<Snapshot> {
+    let mut outcomes: HashMap<B256, BTreeSet<U256>> = HashMap::new();
+    for f in flows {
+        if let (Some(condition), Some(asset)) = (f.condition, f.asset) {
+            outcomes.entry(condition).or_default().insert(asset);
+        }
+    }
+    let outcomes_of = |f: &Flow| -> Vec<U256> {
+        f.condition
+            .and_then(|c| outcomes.get(&c))
+            .map(|o| o.iter().copied().collect())
+            .unwrap_or_default()
+    };
+
+    let mut shares: HashMap<U256, Decimal> = HashMap::new();
+    let mut last_price: HashMap<U256, Decimal> = HashMap::new();
+    let mut cash_flow = Decimal::ZERO;
+    let mut next = flows.iter().peekable();
+    times
+        .iter()
+        .map(|&time| {
+            while let Some(f) = next.next_if(|f| f.time <= time) {
+                cash_flow += f.cash();
+                match f.kind {
+                    FlowKind::Buy | FlowKind::Sell => {
+                        let Some(asset) = f.asset else { continue };
+                        let held = shares.entry(asset).or_default();
+                        if f.kind == FlowKind::Buy {
+                            *held += f.size;
+                        } else {
+                            *held -= f.size;
+                        }
+                        if let Some(price) = f.price {
+                            last_price.insert(asset, price);
+                        }
+                    }
+                    FlowKind::Split => {
+                        for asset in outcomes_of(f) {
+                            *shares.entry(asset).or_default() += f.size;
+                        }
+                    }
+                    FlowKind::Merge => {
+                        for asset in outcomes_of(f) {
+                            *shares.entry(asset).or_default() -= f.size;
+                        }
+                    }
+                    FlowKind::Redeem => {
+                        for asset in outcomes_of(f) {
+                            shares.remove(&asset);
+                        }
+                    }
+                    FlowKind::Cash => {}
+                }
+            }
+            let mut holdings: Vec<Holding> = shares
+                .iter()
+                .filter(|(_, s)| **s > Decimal::ZERO)
+                .map(|(&asset, &shares)| Holding {
+                    asset,
+                    shares,
+                    last_price: last_price.get(&asset).copied().unwrap_or_default(),
+                })
+                .collect();
+            holdings.sort_by_key(|h| h.asset);
+            Snapshot {
+                time,
+                cash_flow,
+                holdings,
+            }
+        })
+        .collect()
+}
+
+/// The last price at or before `time`.
+fn price_at(history: &[(i64, Decimal)], time: i64) -> Option<Decimal> {
+    let i = history.partition_point(|(t, _)| *t <= time);
+    i.checked_sub(1).map(|i| history[i].1)
+}
+
+/// Values each snapshot. `balance` is today's USDC and `total_flow` what
+/// all the activity brought in, so the USDC held at a snapshot is today's
+/// less what came in after it.
+fn mark(
+    snapshots: &[Snapshot],
+    prices: &HashMap<U256, Vec<(i64, Decimal)>>,
+    balance: Decimal,
+    total_flow: Decimal,
+) -> Vec<HistoryPoint> {
+    snapshots
+        .iter()
+        .map(|s| {
+            let positions_value = s
+                .holdings
+                .iter()
+                .map(|h| {
+                    let price = prices
+                        .get(&h.asset)
+                        .and_then(|p| price_at(p, s.time))
+                        .unwrap_or(h.last_price);
+                    h.shares * price
+                })
+                .sum::<Decimal>();
+            let cash = balance - (total_flow - s.cash_flow);
+            HistoryPoint {
+                time: DateTime::<Utc>::from_timestamp(s.time, 0).unwrap_or_default(),
+                positions_value,
+                cash,
+                total_value: cash + positions_value,
+                pnl: s.cash_flow + positions_value,
+            }
+        })
+        .collect()
+}
+
+/// USDC committed to resting buys, and the USDC resting sells would raise
+/// at their limit prices.
+fn order_exposure(orders: &[OpenOrderResponse]) -> (Decimal, Decimal) {
+    orders
+        .iter()
+        .fold((Decimal::ZERO, Decimal::ZERO), |(buy, sell), o| {
+            let notional = (o.original_size - o.size_matched) * o.price;
+            match o.side {
+                Side::Buy => (buy + notional, sell),
+                _ => (buy, sell + notional),
+            }
+        })
+}
+
+fn summarize(
+    address: Address,
+    usdc_balance: Decimal,
+    positions: &[Position],
+    closed: &[ClosedPosition],
+    orders: &[OpenOrderResponse],
+) -> PortfolioSummary {
+    let positions_value = positions.iter().map(|p| p.current_value).sum::<Decimal>();
+    let unrealized_pnl = positions.iter().map(|p| p.cash_pnl).sum();
+    // Open positions carry realized PnL from partial exits too.
+    let realized_pnl = closed.iter().map(|p| p.realized_pnl).sum::<Decimal>()
+        + positions.iter().map(|p| p.realized_pnl).sum::<Decimal>();
+    let (buy_order_exposure, sell_order_exposure) = order_exposure(orders);
+
+    PortfolioSummary {
+        address,
+        usdc_balance,
+        positions: positions.len(),
+        positions_value,
+        unrealized_pnl,
+        realized_pnl,
+        open_orders: orders.len(),
+        buy_order_exposure,
+        sell_order_exposure,
+        total_value: usdc_balance + positions_value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::clob::open_order;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn order_exposure_uses_remaining_size() {
+        let orders = [
+            open_order("BUY", dec!(0.5), dec!(100), dec!(40)),
+            open_order("BUY", dec!(0.2), dec!(10), dec!(0)),
+            open_order("SELL", dec!(0.9), dec!(10), dec!(5)),
+        ];
+        let (buy, sell) = order_exposure(&orders);
+        assert_eq!(buy, dec!(32));
+        assert_eq!(sell, dec!(4.5));
+    }
+
+    fn flow(time: i64, kind: FlowKind, asset: u64, size: &str, usdc: &str) -> Flow {
+        Flow {
+            time,
+            kind,
+            asset: (asset > 0).then(|| U256::from(asset)),
+            condition: Some(B256::with_last_byte(1)),
+            size: size.parse().unwrap(),
+            usdc: usdc.parse().unwrap(),
+            price: (size != "0")
+                .then(|| usdc.parse::<Decimal>().unwrap() / size.parse::<Decimal>().unwrap()),
+        }
+    }
+
+    #[test]
+    fn replay_tracks_shares_and_cash() {
+        let flows = [
+            flow(10, FlowKind::Buy, 1, "100", "40"),
+            flow(20, FlowKind::Buy, 2, "50", "30"),
+            flow(30, FlowKind::Sell, 1, "40", "20"),
+            flow(40, FlowKind::Redeem, 0, "60", "60"),
+        ];
+        let snapshots = replay(&flows, &[5, 25, 35, 45]);
+        assert!(snapshots[0].holdings.is_empty());
+        assert_eq!(snapshots[1].cash_flow, Decimal::from(-70));
+        assert_eq!(snapshots[1].holdings.len(), 2);
+        let after_sell = &snapshots[2];
+        assert_eq!(after_sell.holdings[0].shares, Decimal::from(60));
+        assert_eq!(after_sell.holdings[0].last_price, Decimal::new(5, 1));
+        // Redeeming clears every outcome of the market.
+        assert!(snapshots[3].holdings.is_empty());
+        assert_eq!(snapshots[3].cash_flow, Decimal::from(10));
+    }
+
+    #[test]
+    fn mark_uses_price_history_then_last_trade() {
+        let flows = [flow(10, FlowKind::Buy, 1, "100", "40")];
+        let snapshots = replay(&flows, &[15, 25]);
+        let prices = HashMap::from([(
+            U256::from(1),
+            vec![(20, Decimal::new(6, 1)), (30, Decimal::new(7, 1))],
+        )]);
+        // Today's balance is 60 after paying 40, so it was 100 before.
+        let points = mark(&snapshots, &prices, Decimal::from(60), Decimal::from(-40));
+        assert_eq!(points[0].positions_value, Decimal::from(40));
+        assert_eq!(points[0].cash, Decimal::from(60));
+        assert_eq!(points[1].positions_value, Decimal::from(60));
+        assert_eq!(points[1].total_value, Decimal::from(120));
+        assert_eq!(points[1].pnl, Decimal::from(20));
+    }
+
+    #[test]
+    fn summarize_empty_portfolio() {
+        let summary = summarize(Address::ZERO, Decimal::from(10), &[], &[], &[]);
+        assert_eq!(summary.total_value, Decimal::from(10));
+        assert_eq!(summary.positions, 0);
+        assert_eq!(summary.realized_pnl, Decimal::ZERO);
+    }
+}
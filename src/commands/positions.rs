@@ -0,0 +1,110 @@
+//! `positions`: a wallet's open positions, as `data positions` shows them,
+//! and with `positions close` a way out of them (see `close.rs`).
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use polymarket_client_sdk::types::Decimal;
+
+use super::close::ExitPrice;
+use super::data::{DataArgs, DataCommand};
+use crate::clients;
+use crate::config;
+use crate::errors;
+use crate::output::OutputFormat;
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct PositionsArgs {
+    #[command(subcommand)]
+    pub command: Option<PositionsCommand>,
+
+    /// Wallet address (0x...); defaults to the configured wallet
+    pub address: Option<String>,
+
+    /// Max results
+    #[arg(long, default_value = "25")]
+    pub limit: i32,
+
+    /// Pagination offset
+    #[arg(long)]
+    pub offset: Option<i32>,
+}
+
+#[derive(Subcommand)]
+pub enum PositionsCommand {
+    /// Sell positions in a market, or all of them, after a summary of the proceeds
+    Close {
+        /// Condition ID of the market to sell out of
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        condition_id: Option<String>,
+        /// Sell every open position
+        #[arg(long)]
+        all: bool,
+        /// Rest a GTC limit this far under the best bid instead, e.g. 0.01
+        #[arg(long, conflicts_with = "market")]
+        limit_offset: Option<Decimal>,
+        /// Sell at whatever the book pays, all or nothing (the default)
+        #[arg(long)]
+        market: bool,
+        /// Sign the orders and print them without submitting
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+pub async fn execute(
+    args: PositionsArgs,
+    output: OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    match args.command {
+        None => {
+            let command = DataCommand::Positions {
+                address: args.address,
+                limit: args.limit,
+                offset: args.offset,
+            };
+            super::data::execute(
+                &clients::data()?,
+                DataArgs { command },
+                output,
+                private_key,
+                signature_type,
+            )
+            .await
+        }
+        Some(PositionsCommand::Close {
+            condition_id,
+            all: _,
+            limit_offset,
+            market: _,
+            dry_run,
+        }) => {
+            anyhow::ensure!(!config::paper_mode(), "{}", super::paper::UNSUPPORTED_MSG);
+            let condition = condition_id
+                .as_deref()
+                .map(super::parse_condition_id)
+                .transpose()?;
+            let how = match limit_offset {
+                Some(offset) => {
+                    anyhow::ensure!(
+                        offset >= Decimal::ZERO && offset < Decimal::ONE,
+                        errors::validation("--limit-offset must be at least 0 and under 1")
+                    );
+                    ExitPrice::Offset(offset)
+                }
+                None => ExitPrice::Market,
+            };
+            super::close::execute(
+                condition,
+                how,
+                dry_run,
+                &output,
+                private_key,
+                signature_type,
+            )
+            .await
+        }
+    }
+}
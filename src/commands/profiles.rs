@@ -1,4 +1,5 @@
 use super::parse_address;
+use crate::net;
 use crate::output::profiles::print_profile_detail;
 use crate::output::{OutputFormat, print_json};
 use anyhow::Result;
@@ -29,10 +30,12 @@ pub async fn execute(
         ProfilesCommand::Get { address } => {
             let addr = parse_address(&address)?;
             let req = PublicProfileRequest::builder().address(addr).build();
-            let profile = client.public_profile(&req).await?;
+            let profile = net::retry(|| client.public_profile(&req)).await?;
 
             match output {
-                OutputFormat::Table => print_profile_detail(&profile),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    print_profile_detail(&profile)
+                }
                 OutputFormat::Json => print_json(&profile)?,
             }
         }
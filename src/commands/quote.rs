@@ -0,0 +1,168 @@
+//! `quote`: best bid, best ask, midpoint, spread and last trade for each
+//! outcome of one or more markets, once or as a polling ticker.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::Args;
+use polymarket_client_sdk::clob::types::request::OrderBookSummaryRequest;
+use polymarket_client_sdk::clob::types::response::OrderBookSummaryResponse;
+use polymarket_client_sdk::types::U256;
+
+use super::clob::parse_token_id;
+use super::markets::fetch_market;
+use crate::clients;
+use crate::net;
+use crate::output::OutputFormat;
+use crate::output::quote::{Quote, print_quote_tick, print_quotes};
+
+#[derive(Args)]
+pub struct QuoteArgs {
+    /// Token IDs, or market slugs, IDs or condition IDs to quote every outcome
+    #[arg(required = true)]
+    pub markets: Vec<String>,
+
+    /// Keep quoting until interrupted, one line per outcome each round
+    #[arg(long)]
+    pub watch: bool,
+
+    /// Seconds between rounds with --watch
+    #[arg(
+        long,
+        default_value = "5",
+        requires = "watch",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub interval: u64,
+}
+
+/// One outcome of a market.
+pub(crate) struct Outcome {
+    pub market: String,
+    pub name: String,
+    pub token_id: U256,
+}
+
+pub async fn execute(args: QuoteArgs, output: &OutputFormat) -> Result<()> {
+    let gamma = &clients::gamma()?;
+    let outcomes: Vec<Outcome> = net::fetch_all(&args.markets, |id| resolve_outcomes(gamma, id))
+        .await?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    let client = clients::clob()?;
+    if !args.watch {
+        return print_quotes(&quote(&client, &outcomes).await?, output);
+    }
+    let mut first = true;
+    loop {
+        match quote(&client, &outcomes).await {
+            Ok(quotes) => {
+                print_quote_tick(&quotes, chrono::Local::now(), first, output);
+                first = false;
+            }
+            Err(e) => eprintln!("Quote failed: {e:#}"),
+        }
+        tokio::select! {
+            () = tokio::time::sleep(Duration::from_secs(args.interval)) => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
+}
+
+/// The outcomes `id` names: just the one for a token ID, every outcome of
+/// the market otherwise.
+pub(crate) async fn resolve_outcomes(
+    client: &polymarket_client_sdk::gamma::Client,
+    id: &str,
+) -> Result<Vec<Outcome>> {
+    let market = fetch_market(client, id).await?;
+    let question = market.question.unwrap_or_default();
+    let names = market.outcomes.unwrap_or_default();
+    let tokens = market.clob_token_ids.unwrap_or_default();
+    anyhow::ensure!(!tokens.is_empty(), "Market has no tradable outcomes: {id}");
+    let only = parse_token_id(id).ok().filter(|t| tokens.contains(t));
+    Ok(tokens
+        .into_iter()
+        .enumerate()
+        .filter(|(_, t)| only.is_none_or(|o| o == *t))
+        .map(|(i, token_id)| Outcome {
+            market: question.clone(),
+            name: names
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| format!("Outcome {}", i + 1)),
+            token_id,
+        })
+        .collect())
+}
+
+pub(crate) async fn quote(
+    client: &polymarket_client_sdk::clob::Client,
+    outcomes: &[Outcome],
+) -> Result<Vec<Quote>> {
+    let requests: Vec<_> = outcomes
+        .iter()
+        .map(|o| {
+            OrderBookSummaryRequest::builder()
+                .token_id(o.token_id)
+                .build()
+        })
+        .collect();
+    let books = net::retry(|| client.order_books(&requests)).await?;
+    Ok(outcomes
+        .iter()
+        .map(|o| {
+            let book = books.iter().find(|b| b.asset_id == o.token_id);
+            to_quote(o, book)
+        })
+        .collect())
+}
+
+fn to_quote(outcome: &Outcome, book: Option<&OrderBookSummaryResponse>) -> Quote {
+    Quote {
+        market: outcome.market.clone(),
+        outcome: outcome.name.clone(),
+        token_id: outcome.token_id,
+        bid: book.and_then(|b| b.bids.iter().map(|l| l.price).max()),
+        ask: book.and_then(|b| b.asks.iter().map(|l| l.price).min()),
+        last: book.and_then(|b| b.last_trade_price),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polymarket_client_sdk::types::Decimal;
+    use serde_json::json;
+    use std::str::FromStr;
+
+    #[test]
+    fn to_quote_takes_the_best_levels_whatever_their_order() {
+        let book: OrderBookSummaryResponse = serde_json::from_value(json!({
+            "market": "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "asset_id": "7",
+            "timestamp": "1700000000000",
+            "bids": [{"price": "0.40", "size": "10"}, {"price": "0.44", "size": "5"}],
+            "asks": [{"price": "0.49", "size": "10"}, {"price": "0.47", "size": "5"}],
+            "min_order_size": "5",
+            "neg_risk": false,
+            "tick_size": "0.01",
+            "last_trade_price": "0.45",
+        }))
+        .unwrap();
+        let outcome = Outcome {
+            market: "Will it rain?".into(),
+            name: "Yes".into(),
+            token_id: U256::from(7),
+        };
+        let q = to_quote(&outcome, Some(&book));
+        assert_eq!(q.bid, Some(Decimal::from_str("0.44").unwrap()));
+        assert_eq!(q.ask, Some(Decimal::from_str("0.47").unwrap()));
+        assert_eq!(q.last, Some(Decimal::from_str("0.45").unwrap()));
+
+        let q = to_quote(&outcome, None);
+        assert_eq!((q.bid, q.ask, q.last), (None, None, None));
+    }
+}
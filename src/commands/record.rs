@@ -0,0 +1,471 @@
+//! `record`: tape order book snapshots and trades to a file, for research
+//! and backtesting.
+//!
+//! The books are fetched together every `--interval`; trades come from the
+//! market WebSocket as they happen. Each line is a JSON object with a `type`
+//! of `book` or `trade` and the time it was `recorded_at`. An output ending
+//! in `.gz` is gzip-compressed. A rotated file is renamed with the time it
+//! was started, and recording goes on in a fresh one. With `--db` the same
+//! records go to the `recordings` table of the local database instead (see
+//! [`crate::db`]).
+
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use clap::Args;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use polymarket_client_sdk::clob::types::request::OrderBookSummaryRequest;
+use rusqlite::{Connection, params};
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tokio::time::MissedTickBehavior;
+
+use super::clob::parse_token_ids;
+use super::{parse_duration, stream};
+use crate::clients;
+use crate::db;
+use crate::errors;
+use crate::net;
+use crate::output::OutputFormat;
+use crate::output::clob::order_book_to_json;
+use crate::output::record::{RecordSummary, print_record};
+
+#[derive(Args)]
+pub struct RecordArgs {
+    /// Token IDs to record (comma-separated numeric strings)
+    #[arg(long)]
+    pub tokens: String,
+    /// How often to snapshot the order books, e.g. 500ms, 1s, 1m
+    #[arg(long, default_value = "1s")]
+    pub interval: String,
+    /// File to append to; gzip-compressed when it ends in .gz
+    #[arg(long, required_unless_present = "db")]
+    pub out: Option<PathBuf>,
+    /// Record into the local database instead of a file
+    #[arg(long, conflicts_with_all = ["out", "rotate_size", "rotate_every"])]
+    pub db: bool,
+    /// Start a new file once this one reaches a size, e.g. 500KB, 100MB, 1GB
+    #[arg(long, value_name = "SIZE")]
+    pub rotate_size: Option<String>,
+    /// Start a new file this often, e.g. 1h or 1d
+    #[arg(long, value_name = "DURATION")]
+    pub rotate_every: Option<String>,
+}
+
+/// Parses a size in bytes: a number with an optional B, KB, MB or GB
+/// (powers of 1024).
+fn parse_size(s: &str) -> Result<u64> {
+    let err = || {
+        errors::validation(format!(
+            "Invalid size '{s}': expected e.g. 500KB, 100MB, 1GB"
+        ))
+    };
+    let upper = s.trim().to_ascii_uppercase();
+    let split = upper
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(upper.len());
+    let (num, unit) = upper.split_at(split);
+    let n: u64 = num.parse().map_err(|_| err())?;
+    let scale: u64 = match unit.trim() {
+        "" | "B" => 1,
+        "KB" => 1 << 10,
+        "MB" => 1 << 20,
+        "GB" => 1 << 30,
+        _ => return Err(err()),
+    };
+    n.checked_mul(scale).filter(|&b| b > 0).ok_or_else(err)
+}
+
+/// Where a file started at `started` goes when it's rotated: the time is
+/// added to the name before its extensions, so `snapshots.jsonl.gz` becomes
+/// `snapshots-20240101T120000Z.jsonl.gz`.
+fn rotated_path(path: &Path, started: DateTime<Utc>) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let (stem, extensions) = match name.find('.') {
+        Some(dot) if dot > 0 => name.split_at(dot),
+        _ => (name.as_str(), ""),
+    };
+    let stamp = started.format("%Y%m%dT%H%M%SZ");
+    let mut rotated = path.with_file_name(format!("{stem}-{stamp}{extensions}"));
+    // Files rotated within the same second get a counter.
+    let mut n = 1;
+    while rotated.exists() {
+        rotated = path.with_file_name(format!("{stem}-{stamp}-{n}{extensions}"));
+        n += 1;
+    }
+    rotated
+}
+
+enum Sink {
+    Plain(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+    /// Records are kept until the next flush and inserted together.
+    Db {
+        conn: Connection,
+        pending: Vec<Value>,
+    },
+}
+
+impl Sink {
+    fn open(path: &Path) -> Result<Self> {
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        let writer = BufWriter::new(file);
+        // Appending to a gzip file adds a member; gzip readers go on to the
+        // next one, so the file still reads as one stream.
+        Ok(if path.extension().is_some_and(|e| e == "gz") {
+            Sink::Gzip(GzEncoder::new(writer, Compression::default()))
+        } else {
+            Sink::Plain(writer)
+        })
+    }
+
+    fn write(&mut self, record: Value) -> Result<()> {
+        match self {
+            Sink::Plain(w) => writeln!(w, "{record}")?,
+            Sink::Gzip(w) => writeln!(w, "{record}")?,
+            Sink::Db { pending, .. } => pending.push(record),
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        match self {
+            Sink::Plain(w) => w.flush()?,
+            Sink::Gzip(w) => w.flush()?,
+            Sink::Db { conn, pending } => {
+                let tx = conn.transaction()?;
+                {
+                    let mut insert = tx.prepare(
+                        "INSERT INTO recordings (recorded_at, type, token_id, record)
+                         VALUES (?1, ?2, ?3, ?4)",
+                    )?;
+                    for record in pending.iter() {
+                        let field = |key: &str| record[key].as_str().unwrap_or_default();
+                        insert.execute(params![
+                            field("recorded_at"),
+                            field("type"),
+                            field("asset_id"),
+                            record.to_string(),
+                        ])?;
+                    }
+                }
+                tx.commit()?;
+                pending.clear();
+            }
+        }
+        Ok(())
+    }
+
+    /// Bytes on disk, once flushed. The database isn't rotated, so it
+    /// doesn't count.
+    fn len(&self) -> std::io::Result<u64> {
+        let file = match self {
+            Sink::Plain(w) => w.get_ref(),
+            Sink::Gzip(w) => w.get_ref().get_ref(),
+            Sink::Db { .. } => return Ok(0),
+        };
+        Ok(file.metadata()?.len())
+    }
+
+    fn finish(mut self) -> Result<()> {
+        self.flush()?;
+        if let Sink::Gzip(w) = self {
+            w.finish()?.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Appends records to the output file, rotating it by size or age.
+struct Recorder {
+    path: PathBuf,
+    sink: Option<Sink>,
+    started: DateTime<Utc>,
+    rotate_size: Option<u64>,
+    rotate_every: Option<chrono::Duration>,
+    summary: RecordSummary,
+}
+
+impl Recorder {
+    fn open(
+        path: PathBuf,
+        rotate_size: Option<u64>,
+        rotate_every: Option<chrono::Duration>,
+    ) -> Result<Self> {
+        Ok(Self {
+            sink: Some(Sink::open(&path)?),
+            path,
+            started: Utc::now(),
+            rotate_size,
+            rotate_every,
+            summary: RecordSummary::default(),
+        })
+    }
+
+    /// Records into the database `conn` is open on, at `path`.
+    fn open_db(conn: Connection, path: PathBuf) -> Self {
+        Self {
+            sink: Some(Sink::Db {
+                conn,
+                pending: Vec::new(),
+            }),
+            path,
+            started: Utc::now(),
+            rotate_size: None,
+            rotate_every: None,
+            summary: RecordSummary::default(),
+        }
+    }
+
+    /// Writes `record` as one line, with its `type` and the time now.
+    fn write(&mut self, kind: &str, mut record: Value) -> Result<()> {
+        if let Some(fields) = record.as_object_mut() {
+            fields.insert("type".into(), kind.into());
+            fields.insert(
+                "recorded_at".into(),
+                Utc::now()
+                    .to_rfc3339_opts(SecondsFormat::Millis, true)
+                    .into(),
+            );
+        }
+        let sink = self.sink.as_mut().expect("recorder is open");
+        sink.write(record)
+            .with_context(|| format!("Failed to write {}", self.path.display()))?;
+        match kind {
+            "book" => self.summary.books += 1,
+            _ => self.summary.trades += 1,
+        }
+        Ok(())
+    }
+
+    /// Flushes what's been written, then starts a new file if this one is
+    /// due for rotation.
+    fn flush(&mut self) -> Result<()> {
+        let sink = self.sink.as_mut().expect("recorder is open");
+        sink.flush()
+            .with_context(|| format!("Failed to write {}", self.path.display()))?;
+        let too_big = match self.rotate_size {
+            Some(limit) => sink.len()? >= limit,
+            None => false,
+        };
+        let too_old = self
+            .rotate_every
+            .is_some_and(|every| Utc::now() - self.started >= every);
+        if too_big || too_old {
+            self.rotate()?;
+        }
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> Result<()> {
+        self.close()?;
+        let rotated = rotated_path(&self.path, self.started);
+        fs::rename(&self.path, &rotated)
+            .with_context(|| format!("Failed to rotate {}", self.path.display()))?;
+        tracing::debug!(file = %rotated.display(), "rotated");
+        self.summary.files.push(rotated);
+        self.sink = Some(Sink::open(&self.path)?);
+        self.started = Utc::now();
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<()> {
+        if let Some(sink) = self.sink.take() {
+            sink.finish()
+                .with_context(|| format!("Failed to write {}", self.path.display()))?;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<RecordSummary> {
+        self.close()?;
+        self.summary.files.push(self.path);
+        Ok(self.summary)
+    }
+}
+
+pub async fn execute(args: RecordArgs, output: &OutputFormat) -> Result<()> {
+    let tokens = parse_token_ids(&args.tokens)?;
+    let interval = parse_duration(&args.interval)?
+        .to_std()
+        .ok()
+        .filter(|d| !d.is_zero())
+        .ok_or_else(|| errors::validation("--interval must be positive"))?;
+    let rotate_size = args.rotate_size.as_deref().map(parse_size).transpose()?;
+    let rotate_every = args
+        .rotate_every
+        .as_deref()
+        .map(parse_duration)
+        .transpose()?;
+    anyhow::ensure!(
+        rotate_every.is_none_or(|d| d > chrono::Duration::zero()),
+        errors::validation("--rotate-every must be positive")
+    );
+
+    let client = clients::clob()?;
+    let requests: Vec<_> = tokens
+        .iter()
+        .map(|&id| OrderBookSummaryRequest::builder().token_id(id).build())
+        .collect();
+    let mut recorder = match args.out {
+        Some(out) => Recorder::open(out, rotate_size, rotate_every)?,
+        None => Recorder::open_db(db::open()?, db::path()?),
+    };
+
+    let (sender, mut trades) = mpsc::unbounded_channel();
+    let token_ids = args.tokens;
+    let tape = tokio::spawn(async move {
+        stream::follow_trades(&token_ids, move |trade| {
+            let _ = sender.send(trade);
+        })
+        .await
+    });
+
+    eprintln!(
+        "Recording {} token(s) to {}; press Ctrl-C to stop",
+        tokens.len(),
+        recorder.path.display()
+    );
+    let mut tick = tokio::time::interval(interval);
+    tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    // Made once, so Ctrl-C during a snapshot isn't missed.
+    let stop = tokio::signal::ctrl_c();
+    tokio::pin!(stop);
+    loop {
+        tokio::select! {
+            _ = tick.tick() => {
+                match net::retry(|| client.order_books(&requests)).await {
+                    Ok(books) => {
+                        for book in &books {
+                            recorder.write("book", order_book_to_json(book, None))?;
+                        }
+                    }
+                    // One missed snapshot shouldn't end a long recording.
+                    Err(e) => eprintln!("Snapshot failed: {e:#}"),
+                }
+                recorder.flush()?;
+            }
+            Some(trade) = trades.recv() => recorder.write("trade", trade)?,
+            _ = &mut stop => break,
+        }
+    }
+    tape.abort();
+    while let Ok(trade) = trades.try_recv() {
+        recorder.write("trade", trade)?;
+    }
+    print_record(&recorder.finish()?, output)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use chrono::TimeZone;
+    use flate2::read::MultiGzDecoder;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parse_size_units() {
+        assert_eq!(parse_size("512").unwrap(), 512);
+        assert_eq!(parse_size("500KB").unwrap(), 500 * 1024);
+        assert_eq!(parse_size("100mb").unwrap(), 100 << 20);
+        assert_eq!(parse_size("1 GB").unwrap(), 1 << 30);
+        assert!(parse_size("0MB").is_err());
+        assert!(parse_size("MB").is_err());
+        assert!(parse_size("5TB").is_err());
+    }
+
+    #[test]
+    fn rotated_path_stamps_before_the_extensions() {
+        let dir = std::env::temp_dir().join(format!("polymarket-rotate-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let started = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        let gz = rotated_path(&dir.join("snapshots.jsonl.gz"), started);
+        assert_eq!(gz, dir.join("snapshots-20240101T120000Z.jsonl.gz"));
+        assert_eq!(
+            rotated_path(&dir.join("tape"), started),
+            dir.join("tape-20240101T120000Z")
+        );
+        fs::write(&gz, "").unwrap();
+        assert_eq!(
+            rotated_path(&dir.join("snapshots.jsonl.gz"), started),
+            dir.join("snapshots-20240101T120000Z-1.jsonl.gz")
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn recorder_appends_gzip_and_rotates_by_size() {
+        let dir = std::env::temp_dir().join(format!("polymarket-record-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("tape.jsonl.gz");
+        let mut recorder = Recorder::open(path.clone(), Some(1), None).unwrap();
+        recorder.write("trade", json!({"price": "0.5"})).unwrap();
+        recorder.flush().unwrap();
+        recorder.write("book", json!({"bids": []})).unwrap();
+        let summary = recorder.finish().unwrap();
+        assert_eq!((summary.books, summary.trades), (1, 1));
+        assert_eq!(summary.files.len(), 2);
+        assert_eq!(summary.files[1], path);
+
+        let read = |path: &Path| {
+            let mut text = String::new();
+            MultiGzDecoder::new(File::open(path).unwrap())
+                .read_to_string(&mut text)
+                .unwrap();
+            text.lines()
+                .map(|l| serde_json::from_str::<Value>(l).unwrap())
+                .collect::<Vec<_>>()
+        };
+        let rotated = read(&summary.files[0]);
+        assert_eq!(rotated.len(), 1);
+        assert_eq!(rotated[0]["type"], "trade");
+        assert_eq!(rotated[0]["price"], "0.5");
+        assert!(rotated[0]["recorded_at"].is_string());
+        assert_eq!(read(&path)[0]["type"], "book");
+
+        // Appending to an existing file keeps what's there.
+        let mut recorder = Recorder::open(path.clone(), None, None).unwrap();
+        recorder.write("trade", json!({"price": "0.6"})).unwrap();
+        recorder.finish().unwrap();
+        assert_eq!(read(&path).len(), 2);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn recorder_inserts_into_the_database_at_each_flush() {
+        let conn = db::open_in_memory();
+        let mut recorder = Recorder::open_db(conn, PathBuf::from("polymarket.db"));
+        recorder
+            .write("trade", json!({"asset_id": "7", "price": "0.5"}))
+            .unwrap();
+        let count = |recorder: &Recorder| -> u32 {
+            let Some(Sink::Db { conn, .. }) = &recorder.sink else {
+                unreachable!()
+            };
+            conn.query_row(
+                "SELECT COUNT(*) FROM recordings WHERE token_id = '7'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+        assert_eq!(count(&recorder), 0);
+        recorder.flush().unwrap();
+        assert_eq!(count(&recorder), 1);
+        assert_eq!(recorder.finish().unwrap().trades, 1);
+    }
+}
@@ -0,0 +1,412 @@
+#![allow(clippy::exhaustive_enums, reason = "Generated by sol! macro")]
+#![allow(clippy::exhaustive_structs, reason = "Generated by sol! macro")]
+
+use std::collections::BTreeMap;
+
+use alloy::contract::RawCallBuilder;
+use alloy::primitives::{Bytes, U256};
+use alloy::providers::Provider;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use anyhow::{Context, Result};
+use clap::Args;
+use polymarket_client_sdk::auth::Signer as _;
+use polymarket_client_sdk::data::types::response::Position;
+use polymarket_client_sdk::types::{Address, B256, Decimal};
+use polymarket_client_sdk::{
+    POLYGON, contract_config, derive_proxy_wallet, wallet_contract_config,
+};
+
+use super::approve::IERC1155;
+use super::balance::{POL_DECIMALS, to_decimal};
+use crate::gas::GasFlags;
+use crate::output::OutputFormat;
+use crate::output::ctf::{
+    Holding, RedeemPlan, RedeemTx, Redemption, format_redeem_plan, print_redeem_plan,
+    print_redeemed,
+};
+use crate::{auth, clients, config, confirm};
+
+sol! {
+    interface IConditionalTokens {
+        function redeemPositions(
+            address collateralToken,
+            bytes32 parentCollectionId,
+            bytes32 conditionId,
+            uint256[] indexSets
+        ) external;
+    }
+
+    interface INegRiskAdapter {
+        function redeemPositions(bytes32 conditionId, uint256[] amounts) external;
+    }
+
+    interface IProxyWalletFactory {
+        struct ProxyCall {
+            uint8 typeCode;
+            address to;
+            uint256 value;
+            bytes data;
+        }
+
+        function proxy(ProxyCall[] calls) external payable returns (bytes[] returnValues);
+    }
+}
+
+/// `ProxyCall.typeCode` for a plain call (1 is CALL, 2 DELEGATECALL).
+const PROXY_CALL: u8 = 1;
+/// Outcome shares have the same 6 decimals as USDC.
+const SHARE_DECIMALS: u8 = 6;
+
+#[derive(Args)]
+pub struct RedeemArgs {
+    /// Condition ID of a resolved market (0x-prefixed 32-byte hex)
+    #[arg(required_unless_present = "all", conflicts_with = "all")]
+    pub condition_id: Option<String>,
+    /// Redeem every resolved market the wallet holds
+    #[arg(long)]
+    pub all: bool,
+    /// Show what would be redeemed and the gas estimate without sending
+    #[arg(long)]
+    pub dry_run: bool,
+    #[command(flatten)]
+    pub gas: GasFlags,
+}
+
+/// One redeemable position, as read off the data API.
+pub(super) struct Held {
+    condition_id: B256,
+    title: String,
+    neg_risk: bool,
+    holding: Holding,
+    value: Decimal,
+}
+
+impl From<&Position> for Held {
+    fn from(p: &Position) -> Self {
+        Self {
+            condition_id: p.condition_id,
+            title: p.title.clone(),
+            neg_risk: p.negative_risk,
+            holding: Holding {
+                outcome: p.outcome.clone(),
+                outcome_index: usize::try_from(p.outcome_index).unwrap_or_default(),
+                token: p.asset,
+                size: p.size,
+                balance: None,
+            },
+            value: p.current_value,
+        }
+    }
+}
+
+/// Groups positions by market, in condition ID order.
+pub(super) fn group(held: impl IntoIterator<Item = Held>) -> Vec<Redemption> {
+    let mut by_condition: BTreeMap<B256, Redemption> = BTreeMap::new();
+    for h in held {
+        let r = by_condition
+            .entry(h.condition_id)
+            .or_insert_with(|| Redemption {
+                condition_id: h.condition_id,
+                title: h.title,
+                neg_risk: h.neg_risk,
+                holdings: Vec::new(),
+                payout: Decimal::ZERO,
+            });
+        r.holdings.push(h.holding);
+        r.payout += h.value;
+    }
+    by_condition.into_values().collect()
+}
+
+/// Reads what `holder` has of each outcome of the neg-risk markets in
+/// `redemptions`, since the adapter redeems exactly the amounts it is given
+/// and the data API's sizes are rounded.
+pub(super) async fn read_balances(redemptions: &mut [Redemption], holder: Address) -> Result<()> {
+    let mut holdings = redemptions
+        .iter_mut()
+        .filter(|r| r.neg_risk)
+        .flat_map(|r| r.holdings.iter_mut())
+        .peekable();
+    if holdings.peek().is_none() {
+        return Ok(());
+    }
+    let config = contract_config(POLYGON, false).context("No contract config for Polygon")?;
+    let ctf = IERC1155::new(
+        config.conditional_tokens,
+        auth::create_readonly_provider().await?,
+    );
+    for h in holdings {
+        let raw = ctf
+            .balanceOf(holder, h.token)
+            .call()
+            .await
+            .with_context(|| format!("Failed to read the balance of token {}", h.token))?;
+        h.size = to_decimal(raw, SHARE_DECIMALS)?;
+        h.balance = Some(raw);
+    }
+    Ok(())
+}
+
+/// The contract to call and its calldata. Standard markets redeem both
+/// outcomes on the CTF contract, which pays out whatever the caller holds;
+/// neg-risk markets go through the adapter, which takes explicit amounts:
+/// the on-chain balances from `read_balances`.
+pub(super) fn redeem_call(r: &Redemption) -> Result<(Address, Bytes)> {
+    if !r.neg_risk {
+        let config = contract_config(POLYGON, false).context("No contract config for Polygon")?;
+        let call = IConditionalTokens::redeemPositionsCall {
+            collateralToken: config.collateral,
+            parentCollectionId: B256::ZERO,
+            conditionId: r.condition_id,
+            indexSets: vec![U256::from(1), U256::from(2)],
+        };
+        return Ok((config.conditional_tokens, call.abi_encode().into()));
+    }
+
+    let config =
+        contract_config(POLYGON, true).context("No neg-risk contract config for Polygon")?;
+    let adapter = config
+        .neg_risk_adapter
+        .context("No neg-risk adapter for Polygon")?;
+    let mut amounts = vec![U256::ZERO; 2];
+    for h in &r.holdings {
+        anyhow::ensure!(
+            h.outcome_index < amounts.len(),
+            "Unexpected outcome index {} in {}",
+            h.outcome_index,
+            r.condition_id
+        );
+        amounts[h.outcome_index] += h.balance.with_context(|| {
+            format!(
+                "No on-chain balance read for {} in {}",
+                h.outcome, r.condition_id
+            )
+        })?;
+    }
+    let call = INegRiskAdapter::redeemPositionsCall {
+        conditionId: r.condition_id,
+        amounts,
+    };
+    Ok((adapter, call.abi_encode().into()))
+}
+
+/// Wraps calls for the signer's proxy wallet into one transaction to the
+/// proxy factory, which forwards them to the caller's proxy.
+fn proxy_call(factory: Address, calls: Vec<(Address, Bytes)>) -> (Address, Bytes) {
+    let calls = calls
+        .into_iter()
+        .map(|(to, data)| IProxyWalletFactory::ProxyCall {
+            typeCode: PROXY_CALL,
+            to,
+            value: U256::ZERO,
+            data,
+        })
+        .collect();
+    let call = IProxyWalletFactory::proxyCall { calls };
+    (factory, call.abi_encode().into())
+}
+
+pub async fn execute(
+    args: RedeemArgs,
+    output: &OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    let condition = args
+        .condition_id
+        .as_deref()
+        .map(super::parse_condition_id)
+        .transpose()?;
+    let gas_settings = args.gas.settings()?;
+
+    let signer = auth::resolve_signer(private_key).await?;
+    let eoa = signer.address();
+    let wallet_type = config::resolve_signature_type(signature_type);
+    // The factory only ever calls the sender's own proxy, so the holder is
+    // the derived proxy even when a different funder is configured.
+    let (holder, factory) = match wallet_type.as_str() {
+        "eoa" => (eoa, None),
+        "proxy" => {
+            let factory = wallet_contract_config(POLYGON)
+                .and_then(|c| c.proxy_factory)
+                .context("No proxy wallet factory for Polygon")?;
+            let proxy = derive_proxy_wallet(eoa, POLYGON)
+                .context("Could not derive proxy wallet address")?;
+            (proxy, Some(factory))
+        }
+        _ => anyhow::bail!(
+            "redeem supports eoa and proxy wallets. Positions in a Safe wallet must be \
+             redeemed by a transaction from the Safe itself."
+        ),
+    };
+
+    let positions = super::portfolio::fetch_positions(&clients::data()?, holder).await?;
+    let mut redemptions = group(
+        positions
+            .iter()
+            .filter(|p| p.redeemable && condition.is_none_or(|c| p.condition_id == c))
+            .map(Held::from),
+    );
+    if redemptions.is_empty() {
+        if let Some(c) = condition {
+            anyhow::bail!("No redeemable position in {c} for {holder}");
+        }
+        println!("No resolved positions to redeem.");
+        return Ok(());
+    }
+
+    read_balances(&mut redemptions, holder).await?;
+    let calls = redemptions
+        .iter()
+        .map(redeem_call)
+        .collect::<Result<Vec<_>>>()?;
+    let txs = match factory {
+        Some(factory) => vec![proxy_call(factory, calls)],
+        None => calls,
+    };
+
+    let provider = auth::provider_with_signer(signer, gas_settings).await?;
+    let mut gas = 0u64;
+    for (to, data) in &txs {
+        gas += RawCallBuilder::new_raw(&provider, data.clone())
+            .from(eoa)
+            .to(*to)
+            .estimate_gas()
+            .await
+            .context("Gas estimation failed; the market may not be resolved on-chain yet")?;
+    }
+    let gas_price = provider
+        .get_gas_price()
+        .await
+        .context("Failed to fetch gas price")?;
+    let cost = to_decimal(U256::from(gas) * U256::from(gas_price), POL_DECIMALS)?;
+
+    let plan = RedeemPlan {
+        holder,
+        wallet_type,
+        redemptions,
+        transactions: txs.len(),
+        gas,
+        gas_price_gwei: to_decimal(U256::from(gas_price), 9)?,
+        cost,
+    };
+    if args.dry_run {
+        return print_redeem_plan(&plan, output);
+    }
+    if !confirm::confirm(&format_redeem_plan(&plan), "Redeem?", output)? {
+        return Ok(());
+    }
+
+    let mut sent = Vec::new();
+    for (to, data) in txs {
+        let pending = RawCallBuilder::new_raw(&provider, data)
+            .from(eoa)
+            .to(to)
+            .send()
+            .await
+            .context("Failed to send redeem transaction")?;
+        let tx_hash = *pending.tx_hash();
+        let receipt = pending
+            .get_receipt()
+            .await
+            .context("Failed to confirm redeem transaction")?;
+        anyhow::ensure!(
+            receipt.status(),
+            "Redeem transaction {tx_hash} reverted in block {}",
+            receipt.block_number.unwrap_or_default()
+        );
+        sent.push(RedeemTx {
+            tx_hash,
+            block_number: receipt.block_number.unwrap_or_default(),
+        });
+    }
+    print_redeemed(&plan, &sent, output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn held(condition: u8, neg_risk: bool, index: usize, size: Decimal, value: Decimal) -> Held {
+        Held {
+            condition_id: B256::repeat_byte(condition),
+            title: format!("Market {condition}"),
+            neg_risk,
+            holding: Holding {
+                outcome: if index == 0 { "Yes" } else { "No" }.into(),
+                outcome_index: index,
+                token: U256::from(index),
+                size,
+                balance: None,
+            },
+            value,
+        }
+    }
+
+    #[test]
+    fn group_merges_outcomes_of_one_market() {
+        let grouped = group([
+            held(2, false, 0, dec!(10), dec!(10)),
+            held(1, false, 0, dec!(5), dec!(0)),
+            held(2, false, 1, dec!(4), dec!(0)),
+        ]);
+        assert_eq!(grouped.len(), 2);
+        assert_eq!(grouped[0].condition_id, B256::repeat_byte(1));
+        assert_eq!(grouped[1].holdings.len(), 2);
+        assert_eq!(grouped[1].payout, dec!(10));
+    }
+
+    #[test]
+    fn standard_markets_redeem_both_index_sets_on_the_ctf() {
+        let r = &group([held(1, false, 0, dec!(10), dec!(10))])[0];
+        let (to, data) = redeem_call(r).unwrap();
+        let config = contract_config(POLYGON, false).unwrap();
+        assert_eq!(to, config.conditional_tokens);
+        let call = IConditionalTokens::redeemPositionsCall::abi_decode(&data).unwrap();
+        assert_eq!(call.collateralToken, config.collateral);
+        assert_eq!(call.parentCollectionId, B256::ZERO);
+        assert_eq!(call.conditionId, B256::repeat_byte(1));
+        assert_eq!(call.indexSets, vec![U256::from(1), U256::from(2)]);
+    }
+
+    #[test]
+    fn neg_risk_markets_redeem_on_chain_balances_on_the_adapter() {
+        let mut r = group([
+            held(1, true, 1, dec!(12.5), dec!(12.5)),
+            held(1, true, 0, dec!(3.12), dec!(0)),
+        ])
+        .remove(0);
+        assert!(redeem_call(&r).is_err());
+
+        r.holdings[0].balance = Some(U256::from(12_500_000u64));
+        r.holdings[1].balance = Some(U256::from(3_123_456u64));
+        let (to, data) = redeem_call(&r).unwrap();
+        assert_eq!(
+            Some(to),
+            contract_config(POLYGON, true).unwrap().neg_risk_adapter
+        );
+        let call = INegRiskAdapter::redeemPositionsCall::abi_decode(&data).unwrap();
+        assert_eq!(
+            call.amounts,
+            vec![U256::from(3_123_456u64), U256::from(12_500_000u64)]
+        );
+    }
+
+    #[test]
+    fn proxy_call_wraps_every_redeem() {
+        let factory = Address::repeat_byte(0xaa);
+        let calls = vec![
+            (Address::repeat_byte(1), Bytes::from(vec![1u8])),
+            (Address::repeat_byte(2), Bytes::from(vec![2u8])),
+        ];
+        let (to, data) = proxy_call(factory, calls);
+        assert_eq!(to, factory);
+        let call = IProxyWalletFactory::proxyCall::abi_decode(&data).unwrap();
+        assert_eq!(call.calls.len(), 2);
+        assert_eq!(call.calls[1].typeCode, PROXY_CALL);
+        assert_eq!(call.calls[1].to, Address::repeat_byte(2));
+        assert_eq!(call.calls[1].data, Bytes::from(vec![2u8]));
+    }
+}
@@ -0,0 +1,319 @@
+//! `replay`: play a file from `record` (or what `record --db` put in the
+//! database) back as `stream` prints it, through the dashboard, or through
+//! a backtest strategy.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use clap::Args;
+use flate2::read::MultiGzDecoder;
+use polymarket_client_sdk::clob::types::response::OrderBookSummaryResponse;
+use polymarket_client_sdk::types::{Decimal, U256};
+use serde_json::{Value, json};
+
+use super::backtest::load_strategy;
+use super::clob::parse_token_id;
+use crate::backtest::{self, PricePoint};
+use crate::db;
+use crate::errors;
+use crate::output::backtest::print_backtest;
+use crate::output::{OutputFormat, print_json_line};
+use crate::tui;
+
+#[derive(Args)]
+pub struct ReplayArgs {
+    /// File written by `record` (.jsonl, or .jsonl.gz)
+    #[arg(required_unless_present = "db")]
+    pub file: Option<PathBuf>,
+    /// Replay what `record --db` put in the local database instead
+    #[arg(long, conflicts_with = "file")]
+    pub db: bool,
+    /// Playback speed, e.g. 10x or 0.5x; `max` doesn't wait between records
+    #[arg(long, default_value = "1x")]
+    pub speed: String,
+    /// Show the recording in the dashboard instead of printing it
+    #[arg(long, conflicts_with = "strategy")]
+    pub tui: bool,
+    /// Run a strategy file (TOML) over the recording, as `backtest` does
+    #[arg(long)]
+    pub strategy: Option<PathBuf>,
+    /// Token to backtest, when the recording has more than one
+    #[arg(long, requires = "strategy")]
+    pub token: Option<String>,
+    /// Starting cash for --strategy, in USDC
+    #[arg(long, default_value = "1000")]
+    pub cash: Decimal,
+}
+
+/// One recorded line.
+pub struct Frame {
+    pub recorded_at: DateTime<Utc>,
+    pub token_id: U256,
+    pub event: Event,
+    /// The line as it was recorded.
+    pub line: Value,
+}
+
+pub enum Event {
+    Book(OrderBookSummaryResponse),
+    Trade { price: Decimal },
+}
+
+/// Parses a speed such as `10x`, `0.5` or `max`. `None` is `max`: as fast
+/// as the records can be read.
+fn parse_speed(s: &str) -> Result<Option<f64>> {
+    let s = s.trim().to_ascii_lowercase();
+    if s == "max" {
+        return Ok(None);
+    }
+    s.trim_end_matches('x')
+        .parse::<f64>()
+        .ok()
+        .filter(|x| x.is_finite() && *x > 0.0)
+        .map(Some)
+        .ok_or_else(|| {
+            errors::validation(format!(
+                "Invalid speed '{s}': expected e.g. 10x, 0.5x or max"
+            ))
+        })
+}
+
+/// Reads a `record` file, gzip-compressed when it ends in `.gz`, oldest
+/// record first.
+fn read_frames(path: &Path) -> Result<Vec<Frame>> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let reader: Box<dyn Read> = if path.extension().is_some_and(|e| e == "gz") {
+        Box::new(MultiGzDecoder::new(file))
+    } else {
+        Box::new(file)
+    };
+    let mut frames = Vec::new();
+    for (i, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line.with_context(|| format!("Failed to read {}", path.display()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let frame = parse_frame(&line)
+            .with_context(|| format!("Invalid record on line {} of {}", i + 1, path.display()))?;
+        frames.push(frame);
+    }
+    anyhow::ensure!(!frames.is_empty(), "No records in {}", path.display());
+    // Trades and snapshots are written as they come, so this is nearly sorted
+    // already; a stable sort keeps ties in file order.
+    frames.sort_by_key(|f| f.recorded_at);
+    Ok(frames)
+}
+
+/// Reads what `record --db` recorded, oldest first.
+fn read_recorded() -> Result<Vec<Frame>> {
+    let conn = db::open()?;
+    let mut stmt = conn.prepare("SELECT seq, record FROM recordings ORDER BY recorded_at, seq")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+    })?;
+    let mut frames = Vec::new();
+    for row in rows {
+        let (seq, record) = row?;
+        frames.push(parse_frame(&record).with_context(|| format!("Invalid recording #{seq}"))?);
+    }
+    anyhow::ensure!(
+        !frames.is_empty(),
+        "Nothing recorded in the database; run `polymarket record --db` first"
+    );
+    Ok(frames)
+}
+
+fn parse_frame(line: &str) -> Result<Frame> {
+    let value: Value = serde_json::from_str(line).context("not JSON")?;
+    let field = |key: &str| {
+        value
+            .get(key)
+            .and_then(Value::as_str)
+            .with_context(|| format!("missing {key}"))
+    };
+    let recorded_at = DateTime::parse_from_rfc3339(field("recorded_at")?)
+        .context("invalid recorded_at")?
+        .to_utc();
+    let token_id = parse_token_id(field("asset_id")?)?;
+    let event = match field("type")? {
+        "book" => {
+            // `record` writes books as `clob book -o json` does; the SDK
+            // wants its timestamp in milliseconds.
+            let timestamp =
+                DateTime::parse_from_rfc3339(field("timestamp")?).context("invalid timestamp")?;
+            let mut book = value.clone();
+            book["timestamp"] = json!(timestamp.timestamp_millis().to_string());
+            Event::Book(serde_json::from_value(book).context("invalid book")?)
+        }
+        "trade" => Event::Trade {
+            price: field("price")?.parse().context("invalid price")?,
+        },
+        other => anyhow::bail!("unknown record type '{other}'"),
+    };
+    Ok(Frame {
+        recorded_at,
+        token_id,
+        event,
+        line: value,
+    })
+}
+
+/// Best bid and ask of a book.
+pub fn best_prices(book: &OrderBookSummaryResponse) -> (Option<Decimal>, Option<Decimal>) {
+    (
+        book.bids.iter().map(|l| l.price).max(),
+        book.asks.iter().map(|l| l.price).min(),
+    )
+}
+
+/// The price series of `token_id`: the midpoint of each book snapshot, and
+/// the price of each trade, with the best bid and ask last seen so fills
+/// cross the spread.
+fn price_points(frames: &[Frame], token_id: U256) -> Vec<PricePoint> {
+    let (mut bid, mut ask) = (None, None);
+    let mut points = Vec::new();
+    for frame in frames.iter().filter(|f| f.token_id == token_id) {
+        let price = match &frame.event {
+            Event::Book(book) => {
+                (bid, ask) = best_prices(book);
+                let (Some(b), Some(a)) = (bid, ask) else {
+                    continue;
+                };
+                (b + a) / Decimal::TWO
+            }
+            Event::Trade { price } => *price,
+        };
+        points.push(PricePoint {
+            timestamp: frame.recorded_at.timestamp(),
+            price,
+            bid,
+            ask,
+        });
+    }
+    points
+}
+
+/// The token to backtest: `--token`, or the only one recorded.
+fn pick_token(frames: &[Frame], token: Option<&str>) -> Result<U256> {
+    if let Some(token) = token {
+        return parse_token_id(token);
+    }
+    let mut tokens: Vec<U256> = frames.iter().map(|f| f.token_id).collect();
+    tokens.sort_unstable();
+    tokens.dedup();
+    match tokens.as_slice() {
+        [only] => Ok(*only),
+        _ => Err(errors::validation(format!(
+            "The recording has {} tokens; pick one with --token: {}",
+            tokens.len(),
+            tokens
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
+
+/// How long to wait before showing `frame`, `speed` times as fast as it was
+/// recorded after `first`.
+pub fn delay(first: DateTime<Utc>, frame: DateTime<Utc>, speed: f64) -> Duration {
+    (frame - first).to_std().unwrap_or_default().div_f64(speed)
+}
+
+pub async fn execute(args: ReplayArgs, output: &OutputFormat) -> Result<()> {
+    let speed = parse_speed(&args.speed)?;
+    let (frames, source) = match &args.file {
+        Some(file) => (read_frames(file)?, file.display().to_string()),
+        None => (read_recorded()?, "the database".to_string()),
+    };
+    if let Some(path) = &args.strategy {
+        anyhow::ensure!(args.cash > Decimal::ZERO, "--cash must be positive");
+        let strategy = load_strategy(path)?;
+        let token_id = pick_token(&frames, args.token.as_deref())?;
+        let points = price_points(&frames, token_id);
+        anyhow::ensure!(
+            !points.is_empty(),
+            "No prices for token {token_id} in {source}"
+        );
+        return print_backtest(&backtest::run(strategy, &points, args.cash), output);
+    }
+
+    if args.tui {
+        return tui::replay(&frames, speed).await;
+    }
+    let started = tokio::time::Instant::now();
+    let first = frames[0].recorded_at;
+    for frame in &frames {
+        if let Some(speed) = speed {
+            tokio::time::sleep_until(started + delay(first, frame.recorded_at, speed)).await;
+        }
+        print_json_line(frame.line.clone());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    const BOOK: &str = r#"{"type":"book","recorded_at":"2025-06-01T12:00:00.000Z","market":"0x0000000000000000000000000000000000000000000000000000000000000001","asset_id":"7","timestamp":"2025-06-01T12:00:00+00:00","bids":[{"price":"0.4","size":"10","cumulative":"10"},{"price":"0.42","size":"5","cumulative":"15"}],"asks":[{"price":"0.46","size":"3","cumulative":"3"}],"min_order_size":"5","neg_risk":false,"tick_size":"0.01","last_trade_price":"0.44"}"#;
+    const TRADE: &str = r#"{"type":"trade","recorded_at":"2025-06-01T12:00:02.500Z","event_type":"last_trade_price","asset_id":"7","price":"0.45","side":"BUY","size":"20"}"#;
+
+    #[test]
+    fn parse_speed_accepts_multipliers_and_max() {
+        assert_eq!(parse_speed("10x").unwrap(), Some(10.0));
+        assert_eq!(parse_speed("0.5").unwrap(), Some(0.5));
+        assert_eq!(parse_speed("MAX").unwrap(), None);
+        assert!(parse_speed("0x").is_err());
+        assert!(parse_speed("fast").is_err());
+    }
+
+    #[test]
+    fn parse_frame_reads_recorded_books_and_trades() {
+        let book = parse_frame(BOOK).unwrap();
+        assert_eq!(book.token_id, U256::from(7));
+        let Event::Book(summary) = &book.event else {
+            panic!("expected a book");
+        };
+        assert_eq!(best_prices(summary), (Some(dec!(0.42)), Some(dec!(0.46))));
+        assert_eq!(summary.timestamp, book.recorded_at);
+
+        let trade = parse_frame(TRADE).unwrap();
+        assert!(matches!(trade.event, Event::Trade { price } if price == dec!(0.45)));
+        assert_eq!(trade.line["side"], "BUY");
+
+        assert!(
+            parse_frame(r#"{"type":"quote","recorded_at":"2025-06-01T12:00:00Z","asset_id":"7"}"#)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn price_points_carry_the_book_into_trades() {
+        let frames = vec![parse_frame(BOOK).unwrap(), parse_frame(TRADE).unwrap()];
+        let points = price_points(&frames, U256::from(7));
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].price, dec!(0.44));
+        assert_eq!(points[1].price, dec!(0.45));
+        assert_eq!(points[1].bid, Some(dec!(0.42)));
+        assert_eq!(points[1].ask, Some(dec!(0.46)));
+        assert!(price_points(&frames, U256::from(8)).is_empty());
+        assert_eq!(pick_token(&frames, None).unwrap(), U256::from(7));
+    }
+
+    #[test]
+    fn delay_scales_by_speed() {
+        let frames = [parse_frame(BOOK).unwrap(), parse_frame(TRADE).unwrap()];
+        let (first, trade) = (frames[0].recorded_at, frames[1].recorded_at);
+        assert_eq!(delay(first, trade, 1.0), Duration::from_millis(2500));
+        assert_eq!(delay(first, trade, 10.0), Duration::from_millis(250));
+        assert_eq!(delay(trade, first, 1.0), Duration::ZERO);
+    }
+}
@@ -0,0 +1,397 @@
+//! `resolution`: where markets stand in UMA's optimistic oracle, from the
+//! status Gamma copies from the resolution adapter, and a watch that reports
+//! when watched or held markets are proposed, disputed or resolved.
+
+#![allow(clippy::exhaustive_structs, reason = "Generated by sol! macro")]
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use alloy::sol;
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use polymarket_client_sdk::gamma::types::request::MarketsRequest;
+use polymarket_client_sdk::gamma::types::response::Market;
+use polymarket_client_sdk::types::{Address, B256, Decimal};
+use polymarket_client_sdk::{data, gamma};
+
+use super::markets::fetch_market_fresh;
+use super::portfolio::fetch_positions;
+use super::wallet::resolve_trading_address;
+use crate::clients;
+use crate::config::{self, KeySource, Notifier, NotifyEvent, ResolutionState};
+use crate::net;
+use crate::notify::{self, Notification};
+use crate::output::OutputFormat;
+use crate::output::resolution::{
+    Resolution, format_resolution_change, print_resolution, print_resolution_change,
+    resolution_event,
+};
+
+/// Condition IDs per Gamma request, to keep the query string short.
+const CONDITIONS_PER_REQUEST: usize = 50;
+
+sol! {
+    #[sol(rpc)]
+    interface IUmaCtfAdapter {
+        struct QuestionData {
+            uint256 requestTimestamp;
+            uint256 reward;
+            uint256 proposalBond;
+            uint256 liveness;
+            uint256 manualResolutionTimestamp;
+            bool resolved;
+            bool paused;
+            bool reset;
+            bool refund;
+            address rewardToken;
+            address creator;
+            bytes ancillaryData;
+        }
+
+        function getQuestion(bytes32 questionID) external view returns (QuestionData memory);
+    }
+}
+
+/// The question as the resolution adapter sent it to UMA: the text UMA's
+/// proposers and voters resolve the market by.
+pub(crate) async fn ancillary_data(adapter: Address, question_id: B256) -> Result<String> {
+    let provider = crate::auth::create_readonly_provider().await?;
+    let adapter = IUmaCtfAdapter::new(adapter, provider);
+    let question = adapter
+        .getQuestion(question_id)
+        .call()
+        .await
+        .context("Failed to read the question from the resolution adapter")?;
+    anyhow::ensure!(
+        !question.ancillaryData.is_empty(),
+        "The resolution adapter has no question {question_id}"
+    );
+    Ok(String::from_utf8_lossy(&question.ancillaryData).into_owned())
+}
+
+#[derive(Args)]
+pub struct ResolutionArgs {
+    #[command(subcommand)]
+    pub command: ResolutionCommand,
+}
+
+#[derive(Subcommand)]
+pub enum ResolutionCommand {
+    /// Show a market's oracle state, challenge period and prices
+    Status {
+        /// Market ID (numeric), slug, or condition ID (0x...)
+        market: String,
+    },
+    /// Check the watchlist and the wallet's positions until interrupted, and
+    /// notify when a market is proposed, disputed or resolved (also sent to
+    /// the profile's notifiers)
+    Watch {
+        /// Seconds between checks
+        #[arg(long, default_value = "300")]
+        interval: u64,
+        /// Also show a desktop notification
+        #[arg(long)]
+        desktop: bool,
+        /// Also POST each change as JSON to this URL
+        #[arg(long)]
+        webhook: Option<String>,
+        /// Check once and exit (for cron)
+        #[arg(long)]
+        once: bool,
+    },
+}
+
+pub async fn execute(
+    args: ResolutionArgs,
+    output: OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    let client = clients::gamma()?;
+    match args.command {
+        ResolutionCommand::Status { market } => {
+            let market = fetch_market_fresh(&client, &market).await?;
+            print_resolution(&resolution(market), &output)
+        }
+        ResolutionCommand::Watch {
+            interval,
+            desktop,
+            webhook,
+            once,
+        } => {
+            anyhow::ensure!(interval > 0, "--interval must be at least 1 second");
+            let notifiers = notify::with_flags(desktop, webhook)?;
+            let holder = match config::key_source(private_key) {
+                KeySource::None => None,
+                _ => Some(resolve_trading_address(private_key, signature_type).await?),
+            };
+            anyhow::ensure!(
+                holder.is_some() || !config::load_watchlist()?.is_empty(),
+                "Nothing to watch. Add markets with `polymarket watch add <market>`, or set up a wallet to follow its positions."
+            );
+            let watch = Watch {
+                gamma: client,
+                data: clients::data()?,
+                holder,
+            };
+            watch
+                .run(&notifiers, Duration::from_secs(interval), once, output)
+                .await
+        }
+    }
+}
+
+/// The state Gamma's oracle `status` and the market's `closed` flag add up
+/// to. A closed market with a winner resolved without the oracle reporting.
+fn resolution_state(status: Option<&str>, closed: bool, settled: bool) -> ResolutionState {
+    match status.map(str::to_ascii_lowercase).as_deref() {
+        Some("resolved") => ResolutionState::Resolved,
+        Some("disputed" | "challenged") => ResolutionState::Disputed,
+        Some("proposed") => ResolutionState::Proposed,
+        _ if closed && settled => ResolutionState::Resolved,
+        _ if closed => ResolutionState::Closed,
+        _ => ResolutionState::Open,
+    }
+}
+
+/// The outcome priced at 1 in a closed market: the one that paid out.
+fn winner(closed: bool, prices: &[(String, Decimal)]) -> Option<String> {
+    if !closed {
+        return None;
+    }
+    prices
+        .iter()
+        .find(|(_, price)| *price == Decimal::ONE)
+        .map(|(outcome, _)| outcome.clone())
+}
+
+fn resolution(market: Market) -> Resolution {
+    let closed = market.closed.unwrap_or(false);
+    let prices: Vec<(String, Decimal)> = market
+        .outcomes
+        .unwrap_or_default()
+        .into_iter()
+        .zip(market.outcome_prices.unwrap_or_default())
+        .collect();
+    let winner = winner(closed, &prices);
+    let state = resolution_state(
+        market.uma_resolution_status.as_deref(),
+        closed,
+        winner.is_some(),
+    );
+    Resolution {
+        market_id: market.id,
+        question: market.question.unwrap_or_default(),
+        condition_id: market.condition_id.map(|c| c.to_string()),
+        state,
+        // A JSON array in a string, e.g. `["proposed","resolved"]`.
+        history: market
+            .uma_resolution_statuses
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default(),
+        challenge_ends: market.uma_end_date.filter(|d| !d.is_empty()),
+        liveness: market.custom_liveness.filter(|&secs| secs > 0),
+        bond: market.uma_bond.filter(|b| !b.is_empty()),
+        reward: market.uma_reward,
+        resolver: market.resolved_by.filter(|a| !a.is_empty()),
+        prices,
+        winner: winner.filter(|_| state == ResolutionState::Resolved),
+    }
+}
+
+/// Records each market's state in `seen`, forgetting markets no longer
+/// watched, and returns those that moved into a proposal, a dispute or a
+/// resolution with the state they left. A market seen for the first time
+/// only sets its starting point.
+fn changes<'a>(
+    seen: &mut BTreeMap<String, ResolutionState>,
+    resolutions: &'a [Resolution],
+) -> Vec<(&'a Resolution, ResolutionState)> {
+    seen.retain(|id, _| resolutions.iter().any(|r| &r.market_id == id));
+    resolutions
+        .iter()
+        .filter_map(|r| {
+            let previous = seen.insert(r.market_id.clone(), r.state)?;
+            let notable = matches!(
+                r.state,
+                ResolutionState::Proposed | ResolutionState::Disputed | ResolutionState::Resolved
+            );
+            (notable && previous != r.state).then_some((r, previous))
+        })
+        .collect()
+}
+
+fn notification(r: &Resolution, previous: ResolutionState) -> Notification {
+    Notification {
+        event: NotifyEvent::Resolution,
+        title: r.question.clone(),
+        body: format_resolution_change(r, previous),
+        payload: resolution_event(r, previous),
+    }
+}
+
+struct Watch {
+    gamma: gamma::Client,
+    data: data::Client,
+    /// Whose positions to follow, if a wallet is set up.
+    holder: Option<Address>,
+}
+
+impl Watch {
+    async fn run(
+        &self,
+        notifiers: &[Notifier],
+        interval: Duration,
+        once: bool,
+        output: OutputFormat,
+    ) -> Result<()> {
+        let mut first = true;
+        loop {
+            match self.check(notifiers, &output).await {
+                Ok(count) if first && !once => {
+                    first = false;
+                    eprintln!("Watching {count} markets for proposals, disputes and resolutions.");
+                }
+                Ok(_) => {}
+                Err(e) if once => return Err(e),
+                Err(e) => eprintln!("Resolution check failed: {e:#}"),
+            }
+            if once {
+                return Ok(());
+            }
+
+            tokio::select! {
+                () = tokio::time::sleep(interval) => {}
+                _ = tokio::signal::ctrl_c() => return Ok(()),
+            }
+        }
+    }
+
+    /// Prints and publishes every change since the last check, which may
+    /// have been an earlier run, and returns how many markets were checked.
+    async fn check(&self, notifiers: &[Notifier], output: &OutputFormat) -> Result<usize> {
+        let resolutions: Vec<Resolution> =
+            self.markets().await?.into_iter().map(resolution).collect();
+        let mut seen = config::load_resolutions()?;
+        let changed = changes(&mut seen, &resolutions);
+        for &(r, previous) in &changed {
+            print_resolution_change(r, previous, output)?;
+            notify::publish(notifiers, &notification(r, previous)).await;
+        }
+        config::save_resolutions(&seen)?;
+        Ok(resolutions.len())
+    }
+
+    /// The watchlist's markets and those the wallet holds, fresh from Gamma.
+    async fn markets(&self) -> Result<Vec<Market>> {
+        // Re-read each round so `watch add`/`remove` apply without a restart.
+        let ids: Vec<String> = config::load_watchlist()?
+            .into_iter()
+            .map(|e| e.id)
+            .collect();
+        let mut markets = Vec::new();
+        if !ids.is_empty() {
+            let request = MarketsRequest::builder()
+                .limit(i32::try_from(ids.len())?)
+                .id(ids)
+                .build();
+            markets.extend(net::retry(|| self.gamma.markets(&request)).await?);
+        }
+        if let Some(holder) = self.holder {
+            let mut conditions: Vec<B256> = fetch_positions(&self.data, holder)
+                .await?
+                .into_iter()
+                .map(|p| p.condition_id)
+                .collect();
+            conditions.sort_unstable();
+            conditions.dedup();
+            let pages = net::fetch_all(conditions.chunks(CONDITIONS_PER_REQUEST), |chunk| {
+                let gamma = &self.gamma;
+                async move {
+                    let request = MarketsRequest::builder()
+                        .limit(i32::try_from(chunk.len())?)
+                        .condition_ids(chunk.to_vec())
+                        .build();
+                    net::retry(|| gamma.markets(&request)).await
+                }
+            })
+            .await?;
+            markets.extend(pages.into_iter().flatten());
+        }
+        markets.sort_by(|a, b| a.id.cmp(&b.id));
+        markets.dedup_by(|a, b| a.id == b.id);
+        Ok(markets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolution(id: &str, state: ResolutionState) -> Resolution {
+        Resolution {
+            market_id: id.into(),
+            question: "Will it rain?".into(),
+            condition_id: None,
+            state,
+            history: Vec::new(),
+            challenge_ends: None,
+            liveness: None,
+            bond: None,
+            reward: None,
+            resolver: None,
+            prices: Vec::new(),
+            winner: None,
+        }
+    }
+
+    #[test]
+    fn state_follows_the_oracle_then_the_market() {
+        use ResolutionState::*;
+        assert_eq!(resolution_state(None, false, false), Open);
+        assert_eq!(resolution_state(None, true, false), Closed);
+        assert_eq!(resolution_state(Some(""), true, true), Resolved);
+        assert_eq!(resolution_state(Some("proposed"), true, false), Proposed);
+        assert_eq!(resolution_state(Some("Challenged"), true, false), Disputed);
+        assert_eq!(resolution_state(Some("resolved"), true, false), Resolved);
+    }
+
+    #[test]
+    fn winner_is_the_outcome_at_one_once_closed() {
+        let prices = vec![
+            ("Yes".to_string(), Decimal::ONE),
+            ("No".to_string(), Decimal::ZERO),
+        ];
+        assert_eq!(winner(true, &prices).as_deref(), Some("Yes"));
+        assert_eq!(winner(false, &prices), None);
+        let trading = vec![("Yes".to_string(), "0.99".parse().unwrap())];
+        assert_eq!(winner(true, &trading), None);
+    }
+
+    #[test]
+    fn changes_report_moves_into_notable_states() {
+        use ResolutionState::*;
+        let mut seen = BTreeMap::from([
+            ("1".to_string(), Closed),
+            ("2".to_string(), Proposed),
+            ("3".to_string(), Open),
+            ("gone".to_string(), Open),
+        ]);
+        let now = [
+            resolution("1", Proposed),
+            resolution("2", Proposed),
+            resolution("3", Closed),
+            resolution("4", Disputed),
+        ];
+        let changed: Vec<(&str, ResolutionState)> = changes(&mut seen, &now)
+            .into_iter()
+            .map(|(r, previous)| (r.market_id.as_str(), previous))
+            .collect();
+        assert_eq!(changed, [("1", Closed)]);
+        assert_eq!(seen.len(), 4);
+        assert_eq!(seen["3"], Closed);
+        assert_eq!(seen["4"], Disputed);
+        assert!(!seen.contains_key("gone"));
+    }
+}
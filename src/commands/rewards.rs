@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::{Days, NaiveDate, Utc};
+use clap::Args;
+use polymarket_client_sdk::clob::types::request::UserRewardsEarningRequest;
+use polymarket_client_sdk::clob::types::response::{
+    OpenOrderResponse, OrdersScoringResponse, UserRewardsEarningResponse,
+};
+use polymarket_client_sdk::types::B256;
+
+use super::portfolio::fetch_open_orders;
+use crate::auth;
+use crate::net;
+use crate::output::OutputFormat;
+use crate::output::rewards::{
+    DailyEarnings, MarketRewards, RewardsReport, ScoringOrder, print_rewards_report,
+};
+
+#[derive(Args)]
+pub struct RewardsArgs {
+    /// Days of payout history to show, ending today
+    #[arg(long, default_value = "7", value_parser = clap::value_parser!(u64).range(1..=90))]
+    pub days: u64,
+}
+
+pub async fn execute(
+    args: RewardsArgs,
+    output: &OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+    let today = Utc::now().date_naive();
+    let request = UserRewardsEarningRequest::builder().date(today).build();
+
+    let dates = history_dates(today, args.days);
+    let (markets, totals, orders) = tokio::try_join!(
+        net::retry(|| client.user_earnings_and_markets_config(&request, None)),
+        net::fetch_all(&dates, |d| {
+            let client = &client;
+            net::retry(move || client.total_earnings_for_user_for_day(*d))
+        }),
+        fetch_open_orders(&client),
+    )?;
+    let scoring = if orders.is_empty() {
+        OrdersScoringResponse::new()
+    } else {
+        let ids: Vec<&str> = orders.iter().map(|o| o.id.as_str()).collect();
+        net::retry(|| client.are_orders_scoring(&ids)).await?
+    };
+
+    let history = dates
+        .into_iter()
+        .zip(totals)
+        .map(|(date, day)| DailyEarnings {
+            date,
+            earnings: day.iter().map(|e| e.earnings * e.asset_rate).sum(),
+        })
+        .collect();
+    let report = RewardsReport {
+        markets: market_rewards(&markets, &orders),
+        history,
+        orders: scoring_orders(&orders, &scoring, &markets),
+    };
+    print_rewards_report(&report, output)
+}
+
+/// The last `days` dates, newest first.
+fn history_dates(today: NaiveDate, days: u64) -> Vec<NaiveDate> {
+    (0..days)
+        .filter_map(|n| today.checked_sub_days(Days::new(n)))
+        .collect()
+}
+
+/// Reward markets the user earns in or has orders on; the endpoint also
+/// lists markets the user has nothing to do with.
+fn market_rewards(
+    markets: &[UserRewardsEarningResponse],
+    orders: &[OpenOrderResponse],
+) -> Vec<MarketRewards> {
+    markets
+        .iter()
+        .map(|m| MarketRewards {
+            condition_id: m.condition_id,
+            question: m.question.clone(),
+            share: m.earning_percentage,
+            earned_today: m.earnings.iter().map(|e| e.earnings * e.asset_rate).sum(),
+            daily_pool: m.rewards_config.iter().map(|c| c.rate_per_day).sum(),
+            max_spread: m.rewards_max_spread,
+            min_size: m.rewards_min_size,
+        })
+        .filter(|m| {
+            !m.earned_today.is_zero()
+                || !m.share.is_zero()
+                || orders.iter().any(|o| o.market == m.condition_id)
+        })
+        .collect()
+}
+
+fn scoring_orders(
+    orders: &[OpenOrderResponse],
+    scoring: &OrdersScoringResponse,
+    markets: &[UserRewardsEarningResponse],
+) -> Vec<ScoringOrder> {
+    let questions: HashMap<B256, &str> = markets
+        .iter()
+        .map(|m| (m.condition_id, m.question.as_str()))
+        .collect();
+    orders
+        .iter()
+        .map(|o| ScoringOrder {
+            id: o.id.clone(),
+            market: questions
+                .get(&o.market)
+                .map_or_else(|| o.market.to_string(), ToString::to_string),
+            outcome: o.outcome.clone(),
+            side: o.side,
+            price: o.price,
+            remaining: o.original_size - o.size_matched,
+            scoring: scoring.get(&o.id).copied().unwrap_or(false),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::clob::open_order;
+    use polymarket_client_sdk::types::Decimal;
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    fn order(id: &str, market: &str) -> OpenOrderResponse {
+        let mut order = open_order("BUY", dec!(0.45), dec!(100), dec!(40));
+        order.id = id.into();
+        order.market = market.parse().unwrap();
+        order
+    }
+
+    fn market(
+        condition: &str,
+        share: &str,
+        earnings: &[(&str, &str)],
+    ) -> UserRewardsEarningResponse {
+        let earnings: Vec<_> = earnings
+            .iter()
+            .map(|(amount, rate)| {
+                json!({
+                    "asset_address": "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174",
+                    "earnings": amount,
+                    "asset_rate": rate,
+                })
+            })
+            .collect();
+        serde_json::from_value(json!({
+            "condition_id": condition,
+            "question": format!("Question {}", &condition[65..]),
+            "market_slug": "",
+            "event_slug": "",
+            "image": "",
+            "rewards_max_spread": "3.5",
+            "rewards_min_size": "50",
+            "market_competitiveness": "1",
+            "tokens": null,
+            "rewards_config": [{
+                "asset_address": "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174",
+                "start_date": "2024-01-01",
+                "end_date": "2500-12-31",
+                "rate_per_day": "25",
+                "total_rewards": "1000",
+            }],
+            "maker_address": "0x0000000000000000000000000000000000000001",
+            "earning_percentage": share,
+            "earnings": earnings,
+        }))
+        .unwrap()
+    }
+
+    const M1: &str = "0x0000000000000000000000000000000000000000000000000000000000000001";
+    const M2: &str = "0x0000000000000000000000000000000000000000000000000000000000000002";
+    const M3: &str = "0x0000000000000000000000000000000000000000000000000000000000000003";
+
+    #[test]
+    fn history_dates_end_today_newest_first() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 2).unwrap();
+        let dates = history_dates(today, 3);
+        assert_eq!(
+            dates,
+            vec![
+                today,
+                NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn market_rewards_keeps_only_the_users_markets() {
+        let markets = [
+            market(M1, "0", &[("2", "0.5"), ("1", "1")]),
+            market(M2, "0", &[]),
+            market(M3, "0", &[]),
+        ];
+        let rewards = market_rewards(&markets, &[order("o1", M3)]);
+        assert_eq!(rewards.len(), 2);
+        assert_eq!(rewards[0].earned_today, Decimal::from(2));
+        assert_eq!(rewards[0].daily_pool, Decimal::from(25));
+        assert_eq!(rewards[1].condition_id, M3.parse::<B256>().unwrap());
+    }
+
+    #[test]
+    fn scoring_orders_name_markets_and_default_to_not_scoring() {
+        let markets = [market(M1, "0.1", &[])];
+        let orders = [order("o1", M1), order("o2", M2)];
+        let scoring = OrdersScoringResponse::from([("o1".to_string(), true)]);
+        let rows = scoring_orders(&orders, &scoring, &markets);
+        assert_eq!(rows[0].market, "Question 1");
+        assert!(rows[0].scoring);
+        assert_eq!(rows[0].remaining, Decimal::from(60));
+        assert_eq!(rows[1].market, M2);
+        assert!(!rows[1].scoring);
+    }
+}
@@ -0,0 +1,284 @@
+//! `scan`: look across active markets for prices worth acting on.
+//!
+//! `scan arb` checks each complete set of outcomes — the two sides of a
+//! binary market, and the Yes side of every market in a neg-risk event —
+//! for best asks that sum under 1 (buy the set, redeem for 1) or best bids
+//! that sum over 1 (split 1 USDC into the set, sell it).
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use polymarket_client_sdk::clob::types::request::OrderBookSummaryRequest;
+use polymarket_client_sdk::clob::types::response::{OrderBookSummaryResponse, OrderSummary};
+use polymarket_client_sdk::gamma::types::request::EventsRequest;
+use polymarket_client_sdk::gamma::types::response::{Event, Market};
+use polymarket_client_sdk::types::{Decimal, U256};
+
+use crate::clients;
+use crate::net;
+use crate::output::OutputFormat;
+use crate::output::scan::{ArbKind, ArbLeg, ArbOpportunity, ArbSide, print_arbs};
+
+/// Order books requested per call.
+const BOOKS_PER_REQUEST: usize = 50;
+
+#[derive(Args)]
+pub struct ScanArgs {
+    #[command(subcommand)]
+    pub command: ScanCommand,
+}
+
+#[derive(Subcommand)]
+pub enum ScanCommand {
+    /// Outcome sets whose best asks sum under 1 or best bids sum over 1
+    Arb {
+        /// Smallest edge per share to report, in USDC (e.g. 0.01)
+        #[arg(long, default_value = "0")]
+        min_edge: Decimal,
+        /// Smallest number of shares available at the quoted prices
+        #[arg(long, default_value = "0")]
+        min_size: Decimal,
+        /// Active events to scan, busiest (24h volume) first
+        #[arg(long, default_value = "100")]
+        limit: i32,
+    },
+}
+
+/// Outcomes that together pay exactly 1 USDC at resolution.
+struct OutcomeSet {
+    kind: ArbKind,
+    market: String,
+    legs: Vec<(String, U256)>,
+}
+
+pub async fn execute(args: ScanArgs, output: &OutputFormat) -> Result<()> {
+    match args.command {
+        ScanCommand::Arb {
+            min_edge,
+            min_size,
+            limit,
+        } => {
+            let gamma = clients::gamma()?;
+            let request = EventsRequest::builder()
+                .limit(limit)
+                .closed(false)
+                .order(vec!["volume24hr".to_string()])
+                .build();
+            let events = net::retry(|| gamma.events(&request)).await?;
+            let sets: Vec<OutcomeSet> = events.iter().flat_map(outcome_sets).collect();
+
+            let tokens: Vec<U256> = sets
+                .iter()
+                .flat_map(|s| s.legs.iter().map(|(_, token)| *token))
+                .collect();
+            let books = order_books(&tokens).await?;
+
+            let mut arbs: Vec<ArbOpportunity> = sets
+                .iter()
+                .flat_map(|set| find_arbs(set, &books, min_edge, min_size))
+                .collect();
+            arbs.sort_by_key(|a| std::cmp::Reverse(a.profit()));
+            print_arbs(&arbs, output)
+        }
+    }
+}
+
+pub(crate) fn tradable(market: &Market) -> bool {
+    market.enable_order_book == Some(true)
+        && market.closed != Some(true)
+        && market.active != Some(false)
+        && market.clob_token_ids.as_ref().is_some_and(|t| t.len() == 2)
+}
+
+/// Every binary market of `event`, plus its Yes outcomes as one set when
+/// the event is neg-risk and all of its markets are still open.
+fn outcome_sets(event: &Event) -> Vec<OutcomeSet> {
+    let markets = event.markets.as_deref().unwrap_or_default();
+    let open: Vec<&Market> = markets.iter().filter(|m| tradable(m)).collect();
+    let mut sets: Vec<OutcomeSet> = open
+        .iter()
+        .map(|m| {
+            let names = m.outcomes.clone().unwrap_or_default();
+            OutcomeSet {
+                kind: ArbKind::Binary,
+                market: m.question.clone().unwrap_or_default(),
+                legs: m
+                    .clob_token_ids
+                    .iter()
+                    .flatten()
+                    .enumerate()
+                    .map(|(i, token)| {
+                        let name = names
+                            .get(i)
+                            .cloned()
+                            .unwrap_or_else(|| format!("Outcome {}", i + 1));
+                        (name, *token)
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    // A closed market's outcome can't be bought, so the rest no longer
+    // cover every result.
+    let complete = markets.iter().all(|m| m.closed != Some(true)) && open.len() == markets.len();
+    if event.neg_risk == Some(true) && complete && open.len() > 1 {
+        sets.push(OutcomeSet {
+            kind: ArbKind::MultiOutcome,
+            market: event.title.clone().unwrap_or_default(),
+            legs: open
+                .iter()
+                .filter_map(|m| {
+                    let yes = *m.clob_token_ids.as_ref()?.first()?;
+                    let name = m
+                        .group_item_title
+                        .clone()
+                        .or_else(|| m.question.clone())
+                        .unwrap_or_default();
+                    Some((name, yes))
+                })
+                .collect(),
+        });
+    }
+    sets
+}
+
+async fn order_books(tokens: &[U256]) -> Result<HashMap<U256, OrderBookSummaryResponse>> {
+    let client = clients::clob()?;
+    let mut books = HashMap::new();
+    for chunk in tokens.chunks(BOOKS_PER_REQUEST) {
+        let requests: Vec<_> = chunk
+            .iter()
+            .map(|t| OrderBookSummaryRequest::builder().token_id(*t).build())
+            .collect();
+        for book in net::retry(|| client.order_books(&requests)).await? {
+            books.insert(book.asset_id, book);
+        }
+    }
+    Ok(books)
+}
+
+fn best(levels: &[OrderSummary], side: ArbSide) -> Option<&OrderSummary> {
+    match side {
+        ArbSide::Buy => levels.iter().min_by_key(|l| l.price),
+        ArbSide::Sell => levels.iter().max_by_key(|l| l.price),
+    }
+}
+
+/// The buy-side and sell-side opportunities in `set`, if its best prices
+/// clear `min_edge` and `min_size`.
+fn find_arbs(
+    set: &OutcomeSet,
+    books: &HashMap<U256, OrderBookSummaryResponse>,
+    min_edge: Decimal,
+    min_size: Decimal,
+) -> Vec<ArbOpportunity> {
+    [ArbSide::Buy, ArbSide::Sell]
+        .into_iter()
+        .filter_map(|side| {
+            let legs = set
+                .legs
+                .iter()
+                .map(|(outcome, token)| {
+                    let book = books.get(token)?;
+                    let levels = match side {
+                        ArbSide::Buy => &book.asks,
+                        ArbSide::Sell => &book.bids,
+                    };
+                    let level = best(levels, side)?;
+                    Some(ArbLeg {
+                        outcome: outcome.clone(),
+                        token_id: *token,
+                        price: level.price,
+                        size: level.size,
+                    })
+                })
+                .collect::<Option<Vec<_>>>()?;
+            let arb = ArbOpportunity {
+                kind: set.kind,
+                market: set.market.clone(),
+                side,
+                legs,
+            };
+            (arb.edge() > Decimal::ZERO && arb.edge() >= min_edge && arb.size() >= min_size)
+                .then_some(arb)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    fn book(token: u64, bids: &[(&str, &str)], asks: &[(&str, &str)]) -> OrderBookSummaryResponse {
+        let levels = |levels: &[(&str, &str)]| -> Vec<serde_json::Value> {
+            levels
+                .iter()
+                .map(|(price, size)| json!({"price": price, "size": size}))
+                .collect()
+        };
+        serde_json::from_value(json!({
+            "market": "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "asset_id": token.to_string(),
+            "timestamp": "1700000000000",
+            "bids": levels(bids),
+            "asks": levels(asks),
+            "min_order_size": "5",
+            "neg_risk": false,
+            "tick_size": "0.01",
+        }))
+        .unwrap()
+    }
+
+    fn set() -> OutcomeSet {
+        OutcomeSet {
+            kind: ArbKind::Binary,
+            market: "Will it rain?".into(),
+            legs: vec![("Yes".into(), U256::from(1)), ("No".into(), U256::from(2))],
+        }
+    }
+
+    #[test]
+    fn asks_under_one_are_a_buy() {
+        let books = HashMap::from([
+            (
+                U256::from(1),
+                book(1, &[("0.40", "10")], &[("0.46", "30"), ("0.45", "20")]),
+            ),
+            (U256::from(2), book(2, &[("0.50", "10")], &[("0.52", "50")])),
+        ]);
+        let arbs = find_arbs(&set(), &books, Decimal::ZERO, Decimal::ZERO);
+        assert_eq!(arbs.len(), 1);
+        let arb = &arbs[0];
+        assert_eq!(arb.side, ArbSide::Buy);
+        assert_eq!(arb.total(), dec!(0.97));
+        assert_eq!(arb.edge(), dec!(0.03));
+        assert_eq!(arb.size(), dec!(20));
+        assert_eq!(arb.profit(), dec!(0.60));
+
+        assert!(find_arbs(&set(), &books, dec!(0.05), Decimal::ZERO).is_empty());
+        assert!(find_arbs(&set(), &books, Decimal::ZERO, dec!(25)).is_empty());
+    }
+
+    #[test]
+    fn bids_over_one_are_a_sell() {
+        let books = HashMap::from([
+            (U256::from(1), book(1, &[("0.55", "10")], &[("0.60", "10")])),
+            (U256::from(2), book(2, &[("0.47", "40")], &[("0.50", "10")])),
+        ]);
+        let arbs = find_arbs(&set(), &books, Decimal::ZERO, Decimal::ZERO);
+        assert_eq!(arbs.len(), 1);
+        assert_eq!(arbs[0].side, ArbSide::Sell);
+        assert_eq!(arbs[0].edge(), dec!(0.02));
+        assert_eq!(arbs[0].size(), dec!(10));
+    }
+
+    #[test]
+    fn a_missing_side_is_no_arb() {
+        let books = HashMap::from([(U256::from(1), book(1, &[], &[("0.10", "10")]))]);
+        assert!(find_arbs(&set(), &books, Decimal::ZERO, Decimal::ZERO).is_empty());
+    }
+}
@@ -5,6 +5,7 @@ use polymarket_client_sdk::gamma::{
     types::request::{SeriesByIdRequest, SeriesListRequest},
 };
 
+use crate::net;
 use crate::output::series::{print_series_detail, print_series_table};
 use crate::output::{OutputFormat, print_json};
 
@@ -63,20 +64,24 @@ pub async fn execute(client: &gamma::Client, args: SeriesArgs, output: OutputFor
                 .maybe_closed(closed)
                 .build();
 
-            let series = client.series(&request).await?;
+            let series = net::retry(|| client.series(&request)).await?;
 
             match output {
-                OutputFormat::Table => print_series_table(&series),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    print_series_table(&series)
+                }
                 OutputFormat::Json => print_json(&series)?,
             }
         }
 
         SeriesCommand::Get { id } => {
             let req = SeriesByIdRequest::builder().id(id).build();
-            let series = client.series_by_id(&req).await?;
+            let series = net::retry(|| client.series_by_id(&req)).await?;
 
             match output {
-                OutputFormat::Table => print_series_detail(&series),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    print_series_detail(&series)
+                }
                 OutputFormat::Json => print_json(&series)?,
             }
         }
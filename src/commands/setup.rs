@@ -7,7 +7,7 @@ use polymarket_client_sdk::auth::{LocalSigner, Signer as _};
 use polymarket_client_sdk::types::Address;
 use polymarket_client_sdk::{POLYGON, derive_safe_wallet};
 
-use super::wallet::normalize_key;
+use super::wallet::{normalize_key, resolve_address};
 use crate::config;
 
 fn print_banner() {
@@ -77,7 +77,7 @@ fn step_header(n: u8, total: u8, label: &str) {
     println!("  {}", "─".repeat(label.len() + 6));
 }
 
-pub fn execute() -> Result<()> {
+pub async fn execute() -> Result<()> {
     print_banner();
 
     let total = 4;
@@ -85,11 +85,7 @@ pub fn execute() -> Result<()> {
     step_header(1, total, "Wallet");
 
     let address = if config::config_exists() {
-        let (key, source) = config::resolve_key(None);
-        if let Some(k) = &key
-            && let Ok(signer) = LocalSigner::from_str(k)
-        {
-            let addr = signer.address();
+        if let Ok((Some(addr), source)) = resolve_address(None).await {
             println!("  ✓ Wallet already configured ({})", source.label());
             println!("    Address: {addr}");
             println!();
@@ -132,7 +128,18 @@ fn setup_wallet() -> Result<Address> {
         (address, hex)
     };
 
-    config::save_wallet(&key_hex, POLYGON, config::DEFAULT_SIGNATURE_TYPE)?;
+    let encrypt = prompt_yn("  Encrypt the key with a passphrase?", true)?;
+    if encrypt {
+        let passphrase = config::read_new_passphrase()?;
+        config::save_encrypted_wallet(
+            &key_hex,
+            POLYGON,
+            config::DEFAULT_SIGNATURE_TYPE,
+            &passphrase,
+        )?;
+    } else {
+        config::save_wallet(&key_hex, POLYGON, config::DEFAULT_SIGNATURE_TYPE)?;
+    }
 
     if has_key {
         println!("  ✓ Wallet imported");
@@ -144,7 +151,11 @@ fn setup_wallet() -> Result<Address> {
 
     if !has_key {
         println!();
-        println!("  ⚠ Back up your private key from the config file.");
+        if encrypt {
+            println!("  ⚠ Back up your keystore file and passphrase.");
+        } else {
+            println!("  ⚠ Back up your private key from the config file.");
+        }
         println!("    If lost, your funds cannot be recovered.");
     }
 
@@ -0,0 +1,155 @@
+//! `size`: how much to stake on an outcome by the Kelly criterion, given
+//! your own probability for it and the price the market asks.
+
+use anyhow::{Context, Result};
+use clap::Args;
+use polymarket_client_sdk::clob::types::Side;
+use polymarket_client_sdk::clob::types::request::PriceRequest;
+use polymarket_client_sdk::types::Decimal;
+use rust_decimal::RoundingStrategy;
+
+use super::balance::trading_usdc;
+use super::clob::{CliSide, ClobArgs, ClobCommand, ExpiryFlags, parse_token_id};
+use crate::clients;
+use crate::config;
+use crate::net;
+use crate::output::OutputFormat;
+use crate::output::size::{Sizing, print_sizing};
+use crate::paper::Account;
+
+#[derive(Args)]
+pub struct SizeArgs {
+    /// Your probability that the outcome happens (0 to 1)
+    #[arg(long)]
+    pub prob: Decimal,
+
+    /// Outcome token ID; its best ask is the price unless --price is given
+    #[arg(long, required_unless_present = "price")]
+    pub token: Option<String>,
+
+    /// Price you'd buy at (0 to 1)
+    #[arg(long)]
+    pub price: Option<Decimal>,
+
+    /// USDC to size against (default: the trading wallet's USDC balance, or
+    /// the paper balance in paper mode)
+    #[arg(long)]
+    pub bankroll: Option<Decimal>,
+
+    /// Share of the full Kelly stake to recommend (e.g. 0.5 for half Kelly)
+    #[arg(long, default_value = "0.5")]
+    pub fraction: Decimal,
+
+    /// Place a limit buy for the recommended stake at the price, after
+    /// showing the order and asking to confirm (authenticated)
+    #[arg(long, requires = "token")]
+    pub place: bool,
+}
+
+/// Share of the bankroll full Kelly stakes on a binary outcome that pays 1:
+/// the edge over the price, divided by what a share can win. No edge means
+/// no bet.
+pub(crate) fn kelly(prob: Decimal, price: Decimal) -> Decimal {
+    ((prob - price) / (Decimal::ONE - price)).max(Decimal::ZERO)
+}
+
+pub async fn execute(
+    args: SizeArgs,
+    output: OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    let unit = Decimal::ZERO..=Decimal::ONE;
+    anyhow::ensure!(
+        unit.contains(&args.prob),
+        "--prob must be between 0 and 1, got {}",
+        args.prob
+    );
+    anyhow::ensure!(
+        args.fraction > Decimal::ZERO && args.fraction <= Decimal::ONE,
+        "--fraction must be above 0 and at most 1, got {}",
+        args.fraction
+    );
+    let price = match (args.price, &args.token) {
+        (Some(price), _) => price,
+        (None, Some(token)) => {
+            let request = PriceRequest::builder()
+                .token_id(parse_token_id(token)?)
+                .side(Side::Buy)
+                .build();
+            let client = clients::clob()?;
+            net::retry(|| client.price(&request))
+                .await
+                .context("Failed to fetch the best ask")?
+                .price
+        }
+        (None, None) => anyhow::bail!("Specify --token or --price"),
+    };
+    anyhow::ensure!(
+        price > Decimal::ZERO && price < Decimal::ONE,
+        "Price must be between 0 and 1, got {price}"
+    );
+    let bankroll = match args.bankroll {
+        Some(bankroll) => bankroll,
+        None if config::paper_mode() => Account::load()?.available_balance(),
+        None => trading_usdc(private_key, signature_type)
+            .await
+            .context("Couldn't read the wallet's balance; give --bankroll instead")?,
+    };
+    anyhow::ensure!(bankroll > Decimal::ZERO, "Bankroll must be positive");
+
+    let kelly = kelly(args.prob, price);
+    let sizing = Sizing {
+        prob: args.prob,
+        price,
+        bankroll,
+        kelly,
+        fraction: args.fraction,
+        stake: (bankroll * kelly * args.fraction)
+            .round_dp_with_strategy(2, RoundingStrategy::ToZero),
+    };
+    print_sizing(&sizing, &output)?;
+
+    let Some(token) = args.token.filter(|_| args.place) else {
+        return Ok(());
+    };
+    anyhow::ensure!(
+        !sizing.stake.is_zero(),
+        "Nothing to place: no edge at this price"
+    );
+    let command = ClobCommand::CreateOrder {
+        token,
+        side: CliSide::Buy,
+        price: Some(price.to_string()),
+        odds: None,
+        size: None,
+        notional: Some(sizing.stake.to_string()),
+        order_type: None,
+        expiry: ExpiryFlags::default(),
+        post_only: false,
+        no_self_cross: false,
+        client_id: None,
+        dry_run: false,
+    };
+    super::clob::execute(ClobArgs { command }, output, private_key, signature_type).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn kelly_stakes_the_edge_over_the_payout() {
+        // 60% at 50¢: (0.6 - 0.5) / 0.5
+        assert_eq!(kelly(dec!(0.6), dec!(0.5)), dec!(0.2));
+        // 30% at 20¢: (0.3 - 0.2) / 0.8
+        assert_eq!(kelly(dec!(0.3), dec!(0.2)), dec!(0.125));
+    }
+
+    #[test]
+    fn kelly_is_zero_without_an_edge() {
+        assert_eq!(kelly(dec!(0.5), dec!(0.5)), Decimal::ZERO);
+        assert_eq!(kelly(dec!(0.4), dec!(0.5)), Decimal::ZERO);
+    }
+}
@@ -2,6 +2,7 @@ use anyhow::Result;
 use clap::{Args, Subcommand};
 use polymarket_client_sdk::gamma::{self, types::request::TeamsRequest};
 
+use crate::net;
 use crate::output::sports::{print_sport_types, print_sports_table, print_teams_table};
 use crate::output::{OutputFormat, print_json};
 
@@ -46,19 +47,23 @@ pub enum SportsCommand {
 pub async fn execute(client: &gamma::Client, args: SportsArgs, output: OutputFormat) -> Result<()> {
     match args.command {
         SportsCommand::List => {
-            let sports = client.sports().await?;
+            let sports = net::retry(|| client.sports()).await?;
 
             match output {
-                OutputFormat::Table => print_sports_table(&sports),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    print_sports_table(&sports)
+                }
                 OutputFormat::Json => print_json(&sports)?,
             }
         }
 
         SportsCommand::MarketTypes => {
-            let types = client.sports_market_types().await?;
+            let types = net::retry(|| client.sports_market_types()).await?;
 
             match output {
-                OutputFormat::Table => print_sport_types(&types),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    print_sport_types(&types)
+                }
                 OutputFormat::Json => print_json(&types)?,
             }
         }
@@ -78,10 +83,12 @@ pub async fn execute(client: &gamma::Client, args: SportsArgs, output: OutputFor
                 .league(league.into_iter().collect::<Vec<_>>())
                 .build();
 
-            let teams = client.teams(&request).await?;
+            let teams = net::retry(|| client.teams(&request)).await?;
 
             match output {
-                OutputFormat::Table => print_teams_table(&teams),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    print_teams_table(&teams)
+                }
                 OutputFormat::Json => print_json(&teams)?,
             }
         }
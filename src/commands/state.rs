@@ -0,0 +1,478 @@
+//! `state`: one file holding every profile and the shared watchlist, alerts
+//! and contacts, to back up a setup or move it to another machine.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use clap::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{self, Alert, ApiCredentials, Config, Contact, WatchEntry};
+use crate::errors;
+use crate::output::OutputFormat;
+use crate::output::state::{ExportSummary, ImportSummary, print_export, print_import};
+
+const BUNDLE_VERSION: u32 = 1;
+
+#[derive(Args)]
+pub struct StateArgs {
+    #[command(subcommand)]
+    pub command: StateCommand,
+}
+
+#[derive(Subcommand)]
+pub enum StateCommand {
+    /// Write profiles, settings, watchlist, alerts and contacts to a file
+    Export {
+        /// File to write
+        file: PathBuf,
+        /// What to do with private keys and API credentials
+        #[arg(long, value_enum, default_value_t = KeyMode::Exclude)]
+        keys: KeyMode,
+        /// Overwrite the file if it exists
+        #[arg(long)]
+        force: bool,
+    },
+    /// Restore a file written by `state export`
+    Import {
+        /// File to read
+        file: PathBuf,
+        /// Replace profiles that already exist, and contacts of the same name
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum KeyMode {
+    /// Leave keys out: profiles keep their settings but need the wallet
+    /// imported again
+    Exclude,
+    /// Encrypt plaintext keys with a passphrase; keystores are copied as they are
+    Encrypt,
+    /// Copy keys as they are saved, plaintext ones included
+    Include,
+}
+
+impl std::fmt::Display for KeyMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Exclude => "excluded",
+            Self::Encrypt => "encrypted",
+            Self::Include => "included",
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Bundle {
+    version: u32,
+    exported_at: DateTime<Utc>,
+    default_profile: String,
+    profiles: BTreeMap<String, BundledProfile>,
+    #[serde(default)]
+    watchlist: Vec<WatchEntry>,
+    #[serde(default)]
+    alerts: Vec<Alert>,
+    #[serde(default)]
+    contacts: Vec<Contact>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BundledProfile {
+    config: Config,
+    /// The V3 keystore holding the profile's key, when it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    keystore: Option<serde_json::Value>,
+}
+
+pub fn execute(args: StateArgs, output: &OutputFormat) -> Result<()> {
+    match args.command {
+        StateCommand::Export { file, keys, force } => {
+            anyhow::ensure!(
+                force || !file.exists(),
+                errors::validation(format!(
+                    "{} already exists. Pass --force to overwrite it.",
+                    file.display()
+                ))
+            );
+            let bundle = export(keys)?;
+            write_bundle(&file, &bundle)?;
+            print_export(
+                &ExportSummary {
+                    file,
+                    profiles: bundle.profiles.keys().cloned().collect(),
+                    keys,
+                    watchlist: bundle.watchlist.len(),
+                    alerts: bundle.alerts.len(),
+                    contacts: bundle.contacts.len(),
+                },
+                output,
+            )
+        }
+        StateCommand::Import { file, force } => {
+            let data = fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            let bundle: Bundle = serde_json::from_str(&data).map_err(|e| {
+                errors::validation(format!("{} is not a state export: {e}", file.display()))
+            })?;
+            anyhow::ensure!(
+                bundle.version == BUNDLE_VERSION,
+                errors::validation(format!(
+                    "{} is a version {} export; this version of the CLI reads version {BUNDLE_VERSION}",
+                    file.display(),
+                    bundle.version
+                ))
+            );
+            let summary = import(bundle, force)?;
+            print_import(&summary, output)
+        }
+    }
+}
+
+fn export(keys: KeyMode) -> Result<Bundle> {
+    let names = config::list_profiles()?;
+    let passphrase = if keys == KeyMode::Encrypt
+        && names
+            .iter()
+            .filter_map(|n| config::load_profile(n))
+            .any(|c| !c.private_key.is_empty() || c.keyring)
+    {
+        Some(config::read_new_passphrase()?)
+    } else {
+        None
+    };
+    let mut profiles = BTreeMap::new();
+    for name in names {
+        let config = config::load_profile(&name)
+            .with_context(|| format!("Invalid profile config: {name}"))?;
+        let keystore = config::read_keystore(&config)?;
+        let profile = bundle_profile(
+            config,
+            keystore,
+            keys,
+            || {
+                let key = config::keyring_key(&name)?;
+                Ok((key, config::keyring_credentials(&name)?))
+            },
+            |key| {
+                let passphrase = passphrase
+                    .as_deref()
+                    .context("No passphrase to encrypt with")?;
+                config::encrypt_key(key, passphrase)
+            },
+        )?;
+        profiles.insert(name, profile);
+    }
+    Ok(Bundle {
+        version: BUNDLE_VERSION,
+        exported_at: Utc::now(),
+        default_profile: config::default_profile(),
+        profiles,
+        watchlist: config::load_watchlist()?,
+        alerts: config::load_alerts()?,
+        contacts: config::load_contacts()?,
+    })
+}
+
+/// A profile as it goes into the bundle under `keys`. `encrypt` turns a
+/// plaintext key into a keystore and its address. API credentials are
+/// secrets too, but can be derived again from the key, so only `include`
+/// keeps them. A keyring profile's key and credentials only exist on this
+/// machine, so unless they're excluded `read_keyring` fetches them and the
+/// profile goes into the bundle as if they were saved in the config.
+fn bundle_profile(
+    mut config: Config,
+    mut keystore: Option<serde_json::Value>,
+    keys: KeyMode,
+    read_keyring: impl FnOnce() -> Result<(String, Option<ApiCredentials>)>,
+    encrypt: impl FnOnce(&str) -> Result<(serde_json::Value, String)>,
+) -> Result<BundledProfile> {
+    if config.keyring && keys != KeyMode::Exclude {
+        let (key, credentials) = read_keyring()?;
+        config.private_key = key;
+        config.api_credentials = credentials;
+    }
+    config.keyring = false;
+    match keys {
+        KeyMode::Include => {}
+        KeyMode::Encrypt => {
+            if !config.private_key.is_empty() {
+                let (encrypted, address) = encrypt(&config.private_key)?;
+                config.private_key.clear();
+                config.address = Some(address);
+                keystore = Some(encrypted);
+            }
+            config.api_credentials = None;
+        }
+        KeyMode::Exclude => {
+            config.private_key.clear();
+            config.api_credentials = None;
+            if !config.watch_only {
+                config.address = None;
+            }
+            keystore = None;
+        }
+    }
+    config.keystore = None;
+    Ok(BundledProfile { config, keystore })
+}
+
+/// Writes the bundle readable only by the user, since it may hold keys.
+fn write_bundle(path: &Path, bundle: &Bundle) -> Result<()> {
+    let json = serde_json::to_string_pretty(bundle)?;
+
+    #[cfg(unix)]
+    {
+        use std::io::Write as _;
+        use std::os::unix::fs::OpenOptionsExt;
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        file.write_all(json.as_bytes())
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    #[cfg(not(unix))]
+    {
+        fs::write(path, &json).with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+fn import(bundle: Bundle, force: bool) -> Result<ImportSummary> {
+    let mut summary = ImportSummary::default();
+    for (name, profile) in bundle.profiles {
+        if !force && config::profile_exists(&name) {
+            summary.skipped.push(name);
+            continue;
+        }
+        config::save_profile(&name, profile.config, profile.keystore.as_ref())?;
+        summary.profiles.push(name);
+    }
+    if config::validate_profile_name(&bundle.default_profile).is_ok()
+        && config::profile_exists(&bundle.default_profile)
+        && (force || !config::profile_exists(&config::default_profile()))
+    {
+        config::set_default_profile(&bundle.default_profile)?;
+    }
+
+    let mut watchlist = config::load_watchlist()?;
+    summary.watchlist = merge_watchlist(&mut watchlist, bundle.watchlist);
+    config::save_watchlist(&watchlist)?;
+
+    let mut alerts = config::load_alerts()?;
+    summary.alerts = merge_alerts(&mut alerts, bundle.alerts);
+    config::save_alerts(&alerts)?;
+
+    let mut contacts = config::load_contacts()?;
+    summary.contacts = merge_contacts(&mut contacts, bundle.contacts, force);
+    config::save_contacts(&contacts)?;
+    Ok(summary)
+}
+
+/// Adds the markets not already watched. Returns how many were added.
+fn merge_watchlist(watchlist: &mut Vec<WatchEntry>, imported: Vec<WatchEntry>) -> usize {
+    let before = watchlist.len();
+    for entry in imported {
+        if !watchlist.iter().any(|w| w.id == entry.id) {
+            watchlist.push(entry);
+        }
+    }
+    watchlist.len() - before
+}
+
+/// Adds the alerts not already set, numbered after the existing ones.
+/// Returns how many were added.
+fn merge_alerts(alerts: &mut Vec<Alert>, imported: Vec<Alert>) -> usize {
+    let before = alerts.len();
+    for alert in imported {
+        let duplicate = alerts.iter().any(|a| {
+            a.token_id == alert.token_id && a.direction == alert.direction && a.price == alert.price
+        });
+        if !duplicate {
+            let id = alerts.iter().map(|a| a.id).max().unwrap_or(0) + 1;
+            alerts.push(Alert { id, ..alert });
+        }
+    }
+    alerts.len() - before
+}
+
+/// Adds contacts under new names; with `force`, an imported contact also
+/// replaces one of the same name. Returns how many were added or replaced.
+fn merge_contacts(contacts: &mut Vec<Contact>, imported: Vec<Contact>, force: bool) -> usize {
+    let mut changed = 0;
+    for contact in imported {
+        match contacts
+            .iter_mut()
+            .find(|c| c.name.eq_ignore_ascii_case(&contact.name))
+        {
+            Some(existing) if force && existing.address != contact.address => {
+                *existing = contact;
+                changed += 1;
+            }
+            Some(_) => {}
+            None => {
+                contacts.push(contact);
+                changed += 1;
+            }
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AlertDirection;
+
+    const KEY: &str = "0x0123456789012345678901234567890123456789012345678901234567890123";
+
+    fn profile(private_key: &str) -> Config {
+        serde_json::from_value(serde_json::json!({
+            "private_key": private_key,
+            "chain_id": 137,
+            "address": "0x0000000000000000000000000000000000000001",
+            "api_credentials": {"address": "0x1", "key": "k", "secret": "s", "passphrase": "p"},
+            "output": "json",
+        }))
+        .unwrap()
+    }
+
+    fn no_keyring() -> Result<(String, Option<ApiCredentials>)> {
+        anyhow::bail!("The OS keyring has no key for profile 'test'")
+    }
+
+    fn fake_encrypt(key: &str) -> Result<(serde_json::Value, String)> {
+        Ok((serde_json::json!({"crypto": key.len()}), "0xabc".into()))
+    }
+
+    #[test]
+    fn excluded_keys_leave_settings_only() {
+        let keystore = Some(serde_json::json!({"crypto": {}}));
+        let p = bundle_profile(
+            profile(KEY),
+            keystore,
+            KeyMode::Exclude,
+            no_keyring,
+            fake_encrypt,
+        )
+        .unwrap();
+        assert!(p.config.private_key.is_empty());
+        assert!(p.config.api_credentials.is_none());
+        assert!(p.config.address.is_none());
+        assert!(p.keystore.is_none());
+        assert_eq!(p.config.output, Some(OutputFormat::Json));
+    }
+
+    #[test]
+    fn encrypted_keys_become_keystores() {
+        let p = bundle_profile(
+            profile(KEY),
+            None,
+            KeyMode::Encrypt,
+            no_keyring,
+            fake_encrypt,
+        )
+        .unwrap();
+        assert!(p.config.private_key.is_empty());
+        assert!(p.config.api_credentials.is_none());
+        assert_eq!(p.config.address.as_deref(), Some("0xabc"));
+        assert_eq!(p.keystore, Some(serde_json::json!({"crypto": 66})));
+
+        let p = bundle_profile(
+            profile(KEY),
+            None,
+            KeyMode::Include,
+            no_keyring,
+            fake_encrypt,
+        )
+        .unwrap();
+        assert_eq!(p.config.private_key, KEY);
+        assert!(p.config.api_credentials.is_some());
+    }
+
+    #[test]
+    fn keyring_keys_are_read_into_the_bundle() {
+        let keyring = || {
+            let mut config = profile("");
+            config.keyring = true;
+            config.api_credentials = None;
+            config
+        };
+        let from_keyring = || Ok((KEY.to_string(), profile(KEY).api_credentials));
+
+        let p = bundle_profile(
+            keyring(),
+            None,
+            KeyMode::Include,
+            from_keyring,
+            fake_encrypt,
+        )
+        .unwrap();
+        assert!(!p.config.keyring);
+        assert_eq!(p.config.private_key, KEY);
+        assert!(p.config.api_credentials.is_some());
+
+        let p = bundle_profile(
+            keyring(),
+            None,
+            KeyMode::Encrypt,
+            from_keyring,
+            fake_encrypt,
+        )
+        .unwrap();
+        assert!(!p.config.keyring);
+        assert!(p.config.private_key.is_empty());
+        assert_eq!(p.keystore, Some(serde_json::json!({"crypto": 66})));
+
+        let p =
+            bundle_profile(keyring(), None, KeyMode::Exclude, no_keyring, fake_encrypt).unwrap();
+        assert!(!p.config.keyring);
+
+        let Err(err) = bundle_profile(keyring(), None, KeyMode::Include, no_keyring, fake_encrypt)
+        else {
+            panic!("exported a keyring profile without its key");
+        };
+        assert!(err.to_string().contains("no key for profile"));
+    }
+
+    #[test]
+    fn merges_skip_what_is_already_there() {
+        let alert = |id, price: &str| Alert {
+            id,
+            market_id: "1".into(),
+            question: "Q".into(),
+            outcome: "Yes".into(),
+            token_id: "7".into(),
+            direction: AlertDirection::Above,
+            price: price.parse().unwrap(),
+        };
+        let mut alerts = vec![alert(1, "0.5"), alert(4, "0.6")];
+        assert_eq!(
+            merge_alerts(&mut alerts, vec![alert(1, "0.6"), alert(2, "0.7")]),
+            1
+        );
+        assert_eq!(alerts[2], alert(5, "0.7"));
+
+        let contact = |name: &str, address: &str| Contact {
+            name: name.into(),
+            address: address.into(),
+        };
+        let mut contacts = vec![contact("savings", "0x1")];
+        let imported = || vec![contact("Savings", "0x2"), contact("exchange", "0x3")];
+        assert_eq!(merge_contacts(&mut contacts, imported(), false), 1);
+        assert_eq!(contacts[0].address, "0x1");
+        assert_eq!(merge_contacts(&mut contacts, imported(), true), 1);
+        assert_eq!(contacts[0].address, "0x2");
+        assert_eq!(contacts.len(), 2);
+    }
+}
@@ -0,0 +1,281 @@
+//! `clob stop`: stop-loss and take-profit orders. The CLOB has no stop
+//! orders, so this polls the midpoint and sends a market order once the
+//! price reaches the trigger.
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::Result;
+use polymarket_client_sdk::auth::Normal;
+use polymarket_client_sdk::auth::state::Authenticated;
+use polymarket_client_sdk::clob;
+use polymarket_client_sdk::clob::types::request::MidpointRequest;
+use polymarket_client_sdk::clob::types::{Amount, OrderType, Side};
+use polymarket_client_sdk::types::{Address, Decimal, U256};
+
+use super::clob::parse_token_id;
+use super::portfolio::fetch_positions;
+use crate::clients;
+use crate::config::{self, AlertDirection, NotifyEvent, WorkingStop};
+use crate::net;
+use crate::notify::{self, Notification};
+use crate::risk::{self, Proposed};
+
+/// How much to trade once triggered.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum StopSize {
+    /// The whole position in the token, looked up when the stop fires
+    All,
+    /// Shares to sell, or USDC to spend on a buy
+    Amount(Decimal),
+}
+
+impl FromStr for StopSize {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.eq_ignore_ascii_case("all") {
+            return Ok(Self::All);
+        }
+        let amount =
+            Decimal::from_str(s).map_err(|_| anyhow::anyhow!("Invalid size: {s} (or use all)"))?;
+        anyhow::ensure!(amount > Decimal::ZERO, "Size must be positive, got {s}");
+        Ok(Self::Amount(amount))
+    }
+}
+
+pub(crate) struct StopOrder {
+    pub token: U256,
+    pub side: Side,
+    pub trigger: Decimal,
+    pub size: StopSize,
+    pub order_type: OrderType,
+}
+
+/// The price moves the stop waits for: a trigger below the current price
+/// fires on a fall (stop-loss on a sell), one above on a rise (take-profit).
+pub(crate) fn direction(current: Decimal, trigger: Decimal) -> AlertDirection {
+    if current > trigger {
+        AlertDirection::Below
+    } else {
+        AlertDirection::Above
+    }
+}
+
+impl StopOrder {
+    /// The stop as stored for the daemon, armed against the current
+    /// midpoint and keeping `--override-risk`. The daemon assigns the ID.
+    pub async fn arm(&self) -> Result<WorkingStop> {
+        let request = MidpointRequest::builder().token_id(self.token).build();
+        let client = clients::clob()?;
+        let mid = net::retry(|| client.midpoint(&request)).await?.mid;
+        Ok(WorkingStop {
+            override_risk: risk::overridden(),
+            ..self.to_working(0, direction(mid, self.trigger))
+        })
+    }
+
+    pub fn to_working(&self, id: u32, direction: AlertDirection) -> WorkingStop {
+        WorkingStop {
+            id,
+            token_id: self.token.to_string(),
+            side: self.side,
+            trigger: self.trigger,
+            size: match self.size {
+                StopSize::All => None,
+                StopSize::Amount(size) => Some(size),
+            },
+            order_type: self.order_type.clone(),
+            direction,
+            override_risk: false,
+        }
+    }
+
+    pub fn from_working(stop: &WorkingStop) -> Result<Self> {
+        Ok(Self {
+            token: parse_token_id(&stop.token_id)?,
+            side: stop.side,
+            trigger: stop.trigger,
+            size: stop.size.map_or(StopSize::All, StopSize::Amount),
+            order_type: stop.order_type.clone(),
+        })
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        anyhow::ensure!(
+            self.trigger > Decimal::ZERO && self.trigger < Decimal::ONE,
+            "--trigger must be between 0 and 1, got {}",
+            self.trigger
+        );
+        anyhow::ensure!(
+            self.side == Side::Sell || self.size != StopSize::All,
+            "--size all only works with --side sell; give a USDC amount to buy"
+        );
+        Ok(())
+    }
+
+    /// Polls the midpoint every `interval` until it reaches the trigger and
+    /// returns it, or `None` if interrupted first. Status lines go to stderr
+    /// so stdout carries only the order result.
+    pub async fn wait_for_trigger(&self, interval: Duration) -> Result<Option<Decimal>> {
+        let client = clients::clob()?;
+        let request = MidpointRequest::builder().token_id(self.token).build();
+        let start = net::retry(|| client.midpoint(&request)).await?.mid;
+        let direction = direction(start, self.trigger);
+        let verb = match direction {
+            AlertDirection::Below => "falls",
+            AlertDirection::Above => "rises",
+        };
+        eprintln!(
+            "Stop armed: {} when the midpoint {verb} to {} (now {start}). Ctrl-C to cancel.",
+            self.side, self.trigger
+        );
+
+        let mut mid = start;
+        while !direction.crossed(mid, self.trigger) {
+            tokio::select! {
+                () = tokio::time::sleep(interval) => {}
+                _ = tokio::signal::ctrl_c() => {
+                    eprintln!("Stop canceled.");
+                    return Ok(None);
+                }
+            }
+            match net::retry(|| client.midpoint(&request)).await {
+                Ok(r) => mid = r.mid,
+                Err(e) => eprintln!("Price check failed: {e}"),
+            }
+        }
+        Ok(Some(mid))
+    }
+
+    /// The market order amount, resolving `all` to `owner`'s current
+    /// position.
+    pub async fn amount(&self, owner: Address) -> Result<Amount> {
+        let size = match self.size {
+            StopSize::Amount(size) => size,
+            StopSize::All => fetch_positions(&clients::data()?, owner)
+                .await?
+                .iter()
+                .find(|p| p.asset == self.token)
+                .map(|p| p.size)
+                .filter(|s| *s > Decimal::ZERO)
+                .ok_or_else(|| anyhow::anyhow!("No position in token {} to sell", self.token))?,
+        };
+        Ok(match self.side {
+            Side::Sell => Amount::shares(size)?,
+            _ => Amount::usdc(size)?,
+        })
+    }
+
+    /// Checks a buy stop's order against the risk limits, with `allow`
+    /// standing in for `--override-risk`. Sells only take exposure away, so
+    /// like `positions close` they are never held back.
+    pub async fn check_risk(
+        &self,
+        client: &clob::Client<Authenticated<Normal>>,
+        owner: impl Future<Output = Result<Address>>,
+        allow: bool,
+    ) -> Result<()> {
+        let (Side::Buy, StopSize::Amount(usdc)) = (self.side, self.size) else {
+            return Ok(());
+        };
+        let order = Proposed::market(self.token, Side::Buy, None, usdc);
+        risk::check_with_override(client, &[order], owner, allow).await
+    }
+
+    /// Tells the profile's notifiers that the stop fired.
+    pub async fn notify_triggered(&self, mid: Decimal) {
+        let notification = Notification {
+            event: NotifyEvent::Order,
+            title: "Stop triggered".into(),
+            body: format!(
+                "{} at market: midpoint {mid} reached {}",
+                self.side, self.trigger
+            ),
+            payload: serde_json::json!({
+                "event": "stop_triggered",
+                "token": self.token.to_string(),
+                "side": self.side.to_string(),
+                "trigger": self.trigger.to_string(),
+                "midpoint": mid.to_string(),
+            }),
+        };
+        notify::publish(&config::notifiers(), &notification).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn order(side: Side, trigger: Decimal, size: StopSize) -> StopOrder {
+        StopOrder {
+            token: U256::from(1),
+            side,
+            trigger,
+            size,
+            order_type: OrderType::FOK,
+        }
+    }
+
+    #[test]
+    fn stop_size_parses_all_and_amounts() {
+        assert_eq!("ALL".parse::<StopSize>().unwrap(), StopSize::All);
+        assert_eq!(
+            "12.5".parse::<StopSize>().unwrap(),
+            StopSize::Amount(dec!(12.5))
+        );
+        assert!("0".parse::<StopSize>().is_err());
+        assert!("lots".parse::<StopSize>().is_err());
+    }
+
+    #[test]
+    fn direction_follows_the_trigger_side() {
+        assert_eq!(direction(dec!(0.42), dec!(0.30)), AlertDirection::Below);
+        assert_eq!(direction(dec!(0.42), dec!(0.60)), AlertDirection::Above);
+        // Already at the trigger: fires on the first check.
+        assert!(direction(dec!(0.30), dec!(0.30)).crossed(dec!(0.30), dec!(0.30)));
+    }
+
+    #[test]
+    fn validate_rejects_buying_all() {
+        assert!(
+            order(Side::Sell, dec!(0.3), StopSize::All)
+                .validate()
+                .is_ok()
+        );
+        let err = order(Side::Buy, dec!(0.3), StopSize::All)
+            .validate()
+            .unwrap_err();
+        assert!(err.to_string().contains("--size all"), "got: {err}");
+        assert!(
+            order(Side::Sell, dec!(1.2), StopSize::All)
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn working_stop_round_trips() {
+        for size in [StopSize::All, StopSize::Amount(dec!(25))] {
+            let stop = order(Side::Sell, dec!(0.3), size);
+            let working = stop.to_working(4, AlertDirection::Below);
+            assert_eq!(working.size.is_none(), size == StopSize::All);
+            let json = serde_json::to_string(&working).unwrap();
+            let back: WorkingStop = serde_json::from_str(&json).unwrap();
+            assert_eq!(back, working);
+            let restored = StopOrder::from_working(&back).unwrap();
+            assert_eq!(restored.token, stop.token);
+            assert_eq!(restored.size, size);
+            assert_eq!(restored.order_type, OrderType::FOK);
+        }
+        // Stops saved before the flag existed load without an override.
+        let old = r#"{"id":1,"token_id":"1","side":"BUY","trigger":"0.3","size":"5","order_type":"FOK","direction":"above"}"#;
+        assert!(
+            !serde_json::from_str::<WorkingStop>(old)
+                .unwrap()
+                .override_risk
+        );
+    }
+}
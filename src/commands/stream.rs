@@ -0,0 +1,499 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use futures_util::{SinkExt, StreamExt};
+use polymarket_client_sdk::auth::ExposeSecret as _;
+use serde_json::{Value, json};
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
+
+use super::clob::parse_token_ids;
+use super::markets::fetch_market;
+use super::parse_condition_id;
+use crate::auth;
+use crate::clients;
+use crate::config::{self, Notifier, NotifyEvent, NotifyFormat};
+use crate::health;
+use crate::metrics;
+use crate::notify::{self, Notification};
+use crate::output::{self, print_json_line};
+
+const MARKET_WS_URL: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/market";
+const USER_WS_URL: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/user";
+/// The server drops connections that stay silent for more than about ten seconds.
+const PING_INTERVAL: Duration = Duration::from_secs(10);
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+#[derive(Args)]
+pub struct StreamArgs {
+    #[command(subcommand)]
+    pub command: StreamCommand,
+
+    /// Serve /healthz, /status and /metrics at http://ADDR, e.g.
+    /// 127.0.0.1:9464
+    #[arg(long, value_name = "ADDR", global = true)]
+    pub serve: Option<SocketAddr>,
+}
+
+#[derive(Subcommand)]
+pub enum StreamCommand {
+    /// Stream order book snapshots and price changes as newline-delimited JSON
+    Book {
+        /// Token IDs (comma-separated numeric strings)
+        token_ids: String,
+    },
+    /// Stream trades as newline-delimited JSON
+    Trades {
+        /// Token IDs (comma-separated numeric strings)
+        token_ids: String,
+    },
+    /// Stream your own order updates and fills (authenticated)
+    User {
+        /// Only these markets (comma-separated condition IDs; default: all)
+        #[arg(long)]
+        markets: Option<String>,
+        /// Also send fills and order updates to the profile's notifiers
+        #[arg(long)]
+        notify: bool,
+        /// Show a desktop notification when one of your orders fills
+        #[arg(long)]
+        notify_fills: bool,
+        /// Ring the terminal bell when one of your orders fills
+        #[arg(long)]
+        bell: bool,
+    },
+}
+
+#[derive(Clone, Copy)]
+enum Feed {
+    Book,
+    Trades,
+    User,
+}
+
+impl Feed {
+    fn url(self) -> &'static str {
+        match self {
+            Feed::Book | Feed::Trades => MARKET_WS_URL,
+            Feed::User => USER_WS_URL,
+        }
+    }
+
+    fn wants(self, event_type: &str) -> bool {
+        match self {
+            Feed::Book => matches!(event_type, "book" | "price_change"),
+            Feed::Trades => event_type == "last_trade_price",
+            Feed::User => matches!(event_type, "order" | "trade"),
+        }
+    }
+}
+
+/// Splits a channel frame into the events `feed` is interested in.
+/// The server sends either a single event object or an array of them.
+fn frame_events(frame: &str, feed: Feed) -> Vec<Value> {
+    let Ok(value) = serde_json::from_str::<Value>(frame) else {
+        return Vec::new();
+    };
+    let events = match value {
+        Value::Array(items) => items,
+        other => vec![other],
+    };
+    events
+        .into_iter()
+        .filter(|e| {
+            e.get("event_type")
+                .and_then(Value::as_str)
+                .is_some_and(|t| feed.wants(t))
+        })
+        .collect()
+}
+
+/// What to tell the profile's notifiers about a user channel event: fills
+/// when matched or failed (not every settlement step), and every order
+/// placement, update and cancellation. `market` is the market's question,
+/// when it could be looked up.
+fn user_notification(event: &Value, market: Option<&str>) -> Option<Notification> {
+    let field = |key: &str| event.get(key).and_then(Value::as_str).unwrap_or("?");
+    let (kind, name, title, body) = match field("event_type") {
+        "trade" => {
+            let title = match field("status").to_ascii_uppercase().as_str() {
+                "MATCHED" => "Order filled",
+                "FAILED" => "Fill failed",
+                _ => return None,
+            };
+            let body = format!(
+                "{} {} {} @ {}",
+                field("side"),
+                field("size"),
+                field("outcome"),
+                field("price")
+            );
+            (NotifyEvent::Fill, "fill", title, body)
+        }
+        "order" => {
+            let title = match field("type").to_ascii_uppercase().as_str() {
+                "PLACEMENT" => "Order placed",
+                "UPDATE" => "Order updated",
+                "CANCELLATION" => "Order canceled",
+                _ => "Order",
+            };
+            let body = format!(
+                "{} {} {} @ {}, {} matched",
+                field("side"),
+                field("original_size"),
+                field("outcome"),
+                field("price"),
+                field("size_matched")
+            );
+            (NotifyEvent::Order, "order", title, body)
+        }
+        _ => return None,
+    };
+    let body = match market {
+        Some(market) => format!("{market}\n{body}"),
+        None => body,
+    };
+    Some(Notification {
+        event: kind,
+        title: title.into(),
+        body,
+        payload: json!({"event": name, "data": event}),
+    })
+}
+
+/// Whether `event` is one of the user's orders being matched.
+fn is_fill(event: &Value) -> bool {
+    event.get("event_type").and_then(Value::as_str) == Some("trade")
+        && event
+            .get("status")
+            .and_then(Value::as_str)
+            .is_some_and(|s| s.eq_ignore_ascii_case("MATCHED"))
+}
+
+fn next_backoff(current: Duration) -> Duration {
+    (current * 2).min(MAX_BACKOFF)
+}
+
+fn market_subscription(token_ids: &str) -> Result<String> {
+    let assets_ids: Vec<String> = parse_token_ids(token_ids)?
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    Ok(json!({"type": "market", "assets_ids": assets_ids}).to_string())
+}
+
+/// Builds the user channel subscription, authenticating with the API
+/// credentials derived from the configured wallet.
+async fn user_subscription(
+    markets: Option<&str>,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<String> {
+    let markets: Vec<String> = markets
+        .map(|m| {
+            m.split(',')
+                .map(|id| parse_condition_id(id.trim()).map(|id| id.to_string()))
+                .collect::<Result<_>>()
+        })
+        .transpose()?
+        .unwrap_or_default();
+    let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+    let credentials = client.credentials();
+    Ok(json!({
+        "type": "user",
+        "markets": markets,
+        "auth": {
+            "apiKey": credentials.key().to_string(),
+            "secret": credentials.secret().expose_secret(),
+            "passphrase": credentials.passphrase().expose_secret(),
+        },
+    })
+    .to_string())
+}
+
+pub async fn execute(
+    args: StreamArgs,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    let mut notifiers = Vec::new();
+    let mut ring_bell = false;
+    let subscriptions = match &args.command {
+        StreamCommand::Book { token_ids } | StreamCommand::Trades { token_ids } => {
+            parse_token_ids(token_ids)?
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        }
+        StreamCommand::User { markets, .. } => markets.as_deref().map_or_else(
+            || vec!["user".to_string()],
+            |m| m.split(',').map(|id| id.trim().to_string()).collect(),
+        ),
+    };
+    let (feed, subscribe) = match args.command {
+        StreamCommand::Book { token_ids } => (Feed::Book, market_subscription(&token_ids)?),
+        StreamCommand::Trades { token_ids } => (Feed::Trades, market_subscription(&token_ids)?),
+        StreamCommand::User {
+            markets,
+            notify,
+            notify_fills,
+            bell,
+        } => {
+            if notify {
+                notifiers = config::notifiers();
+                anyhow::ensure!(
+                    !notifiers.is_empty(),
+                    "No notifiers configured. Add a `notifications` list to the profile config."
+                );
+                notify::validate(&notifiers)?;
+            }
+            if notify_fills {
+                notifiers.push(Notifier {
+                    format: NotifyFormat::Desktop,
+                    url: None,
+                    events: vec![NotifyEvent::Fill],
+                });
+            }
+            ring_bell = bell;
+            let subscribe =
+                user_subscription(markets.as_deref(), private_key, signature_type).await?;
+            (Feed::User, subscribe)
+        }
+    };
+    if let Some(addr) = args.serve {
+        health::serve(addr, "stream").await?;
+        health::set_subscriptions(subscriptions);
+        health::set_problem(Some("Connecting".into()));
+    }
+    // The global `--raw` prints frames exactly as the server sent them.
+    let raw = output::raw();
+
+    follow(&subscribe, feed, |text, events| {
+        if raw {
+            println!("{text}");
+        } else {
+            for event in events {
+                print_json_line(event.clone());
+            }
+        }
+        if ring_bell && events.iter().any(is_fill) {
+            eprint!("\x07");
+        }
+        if !notifiers.is_empty() {
+            forward(&notifiers, events);
+        }
+    })
+    .await
+}
+
+/// Sends each trade in `token_ids` to `on_trade` as it happens, until
+/// Ctrl-C. Used by `record` to tape trades alongside its book snapshots.
+pub(crate) async fn follow_trades(token_ids: &str, mut on_trade: impl FnMut(Value)) -> Result<()> {
+    let subscribe = market_subscription(token_ids)?;
+    follow(&subscribe, Feed::Trades, |_, events| {
+        events.iter().cloned().for_each(&mut on_trade);
+    })
+    .await
+}
+
+/// Keeps a subscription open, reconnecting with backoff when it drops, and
+/// hands each frame and the events `feed` wants from it to `on_frame`.
+/// Returns on Ctrl-C.
+async fn follow(
+    subscribe: &str,
+    feed: Feed,
+    mut on_frame: impl FnMut(&str, &[Value]),
+) -> Result<()> {
+    // Both rustls crypto backends end up enabled through our dependencies, so
+    // one has to be picked explicitly before the first TLS handshake.
+    let _ = rustls::crypto::ring::default_provider().install_default();
+
+    let mut backoff = INITIAL_BACKOFF;
+    loop {
+        let result = tokio::select! {
+            r = run_session(subscribe, feed, &mut on_frame, &mut backoff) => r,
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        };
+        let problem = match result {
+            Ok(()) => "Stream closed by server".to_string(),
+            Err(e) => format!("Stream disconnected: {e}"),
+        };
+        eprintln!("{problem}; reconnecting in {}s", backoff.as_secs());
+        health::set_problem(Some(problem));
+        tokio::select! {
+            () = tokio::time::sleep(backoff) => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+        metrics::ws_reconnect();
+        backoff = next_backoff(backoff);
+    }
+}
+
+/// Runs one connection until the server closes it or it errors. The backoff
+/// is reset as soon as a subscription goes through.
+async fn run_session(
+    subscribe: &str,
+    feed: Feed,
+    on_frame: &mut impl FnMut(&str, &[Value]),
+    backoff: &mut Duration,
+) -> Result<()> {
+    tracing::debug!(url = feed.url(), "connecting");
+    let (ws, _) = connect_async(feed.url()).await?;
+    let (mut write, mut read) = ws.split();
+    tracing::debug!(
+        subscription = %crate::logging::redact_json(subscribe),
+        "subscribing"
+    );
+    write.send(Message::text(subscribe)).await?;
+    *backoff = INITIAL_BACKOFF;
+    health::set_problem(None);
+
+    let mut ping = tokio::time::interval(PING_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = ping.tick() => write.send(Message::text("PING")).await?,
+            msg = read.next() => {
+                let Some(msg) = msg else {
+                    tracing::debug!("stream ended");
+                    return Ok(());
+                };
+                match msg? {
+                    Message::Text(text) if text.as_str() == "PONG" => {}
+                    Message::Text(text) => {
+                        tracing::trace!(frame = text.as_str(), "received");
+                        health::event();
+                        on_frame(&text, &frame_events(&text, feed));
+                    }
+                    Message::Close(frame) => {
+                        tracing::debug!(?frame, "server closed the connection");
+                        return Ok(());
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Publishes in the background so a slow webhook (or market lookup) can't
+/// hold up reading (and pinging) the socket.
+fn forward(notifiers: &[Notifier], events: &[Value]) {
+    for event in events {
+        if user_notification(event, None).is_none() {
+            continue;
+        }
+        let notifiers = notifiers.to_vec();
+        let event = event.clone();
+        tokio::spawn(async move {
+            let market = market_question(&event).await;
+            if let Some(notification) = user_notification(&event, market.as_deref()) {
+                notify::publish(&notifiers, &notification).await;
+            }
+        });
+    }
+}
+
+/// The question of the market `event` belongs to, from the market cache
+/// when it's there. `None` if it can't be looked up.
+async fn market_question(event: &Value) -> Option<String> {
+    let condition_id = event.get("market").and_then(Value::as_str)?;
+    let market = fetch_market(&clients::gamma().ok()?, condition_id)
+        .await
+        .ok()?;
+    market.question
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_events_unwraps_arrays_and_filters_by_feed() {
+        let frame = r#"[
+            {"event_type": "book", "asset_id": "1"},
+            {"event_type": "last_trade_price", "asset_id": "1", "price": "0.5"},
+            {"event_type": "tick_size_change", "asset_id": "1"}
+        ]"#;
+        let book = frame_events(frame, Feed::Book);
+        assert_eq!(book.len(), 1);
+        assert_eq!(book[0]["event_type"], "book");
+        let trades = frame_events(frame, Feed::Trades);
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0]["price"], "0.5");
+    }
+
+    #[test]
+    fn frame_events_accepts_single_object() {
+        let frame = r#"{"event_type": "price_change", "market": "0x1"}"#;
+        assert_eq!(frame_events(frame, Feed::Book).len(), 1);
+    }
+
+    #[test]
+    fn frame_events_ignores_non_json() {
+        assert!(frame_events("PONG", Feed::Book).is_empty());
+        assert!(frame_events("INVALID OPERATION", Feed::Trades).is_empty());
+    }
+
+    #[test]
+    fn frame_events_user_feed_keeps_orders_and_fills() {
+        let frame = r#"[
+            {"event_type": "order", "type": "PLACEMENT"},
+            {"event_type": "trade", "status": "MATCHED"},
+            {"event_type": "book"}
+        ]"#;
+        assert_eq!(frame_events(frame, Feed::User).len(), 2);
+    }
+
+    #[test]
+    fn user_notification_for_matched_fills_only() {
+        let matched = json!({"event_type": "trade", "status": "MATCHED", "side": "BUY",
+            "size": "10", "outcome": "Yes", "price": "0.55"});
+        let n = user_notification(&matched, None).unwrap();
+        assert_eq!(n.event, NotifyEvent::Fill);
+        assert_eq!(n.title, "Order filled");
+        assert_eq!(n.body, "BUY 10 Yes @ 0.55");
+        assert_eq!(n.payload["event"], "fill");
+        assert_eq!(n.payload["data"]["price"], "0.55");
+        assert!(is_fill(&matched));
+
+        let mined = json!({"event_type": "trade", "status": "MINED"});
+        assert!(user_notification(&mined, None).is_none());
+        assert!(!is_fill(&mined));
+    }
+
+    #[test]
+    fn user_notification_names_order_changes() {
+        let canceled = json!({"event_type": "order", "type": "CANCELLATION", "side": "SELL",
+            "original_size": "5", "outcome": "No", "price": "0.4", "size_matched": "0"});
+        let n = user_notification(&canceled, None).unwrap();
+        assert_eq!(n.event, NotifyEvent::Order);
+        assert_eq!(n.title, "Order canceled");
+        assert_eq!(n.body, "SELL 5 No @ 0.4, 0 matched");
+        assert!(!is_fill(&canceled));
+    }
+
+    #[test]
+    fn user_notification_names_the_market() {
+        let matched = json!({"event_type": "trade", "status": "MATCHED", "side": "SELL",
+            "size": "4", "outcome": "No", "price": "0.3"});
+        let n = user_notification(&matched, Some("Will it rain?")).unwrap();
+        assert_eq!(n.body, "Will it rain?\nSELL 4 No @ 0.3");
+    }
+
+    #[test]
+    fn market_subscription_lists_assets() {
+        let sub: Value = serde_json::from_str(&market_subscription("1, 2").unwrap()).unwrap();
+        assert_eq!(sub["type"], "market");
+        assert_eq!(sub["assets_ids"], json!(["1", "2"]));
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_cap() {
+        assert_eq!(next_backoff(INITIAL_BACKOFF), Duration::from_secs(2));
+        assert_eq!(next_backoff(Duration::from_secs(20)), MAX_BACKOFF);
+        assert_eq!(next_backoff(MAX_BACKOFF), MAX_BACKOFF);
+    }
+}
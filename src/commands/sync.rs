@@ -0,0 +1,908 @@
+//! `sync`: an index of the wallet's on-chain history, built from Polygon
+//! logs rather than the data API, whose feeds stop paging after 10,000
+//! entries.
+//!
+//! Four queries per block range cover it: ERC-1155 transfers of outcome
+//! tokens out of and into the wallet, splits, merges and redemptions on the
+//! CTF contract and the neg-risk adapter, and the exchanges' fills of the
+//! wallet's orders. The index is saved to the database as it grows (the
+//! `sync_*` tables, see [`crate::db`]), so an interrupted sync picks up
+//! where it stopped.
+//! `sync positions`, `sync trades` and `tax report --synced` read it.
+
+#![allow(clippy::exhaustive_structs, reason = "Generated by sol! macro")]
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use alloy::providers::Provider;
+use alloy::sol;
+use alloy::sol_types::SolEvent;
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use polymarket_client_sdk::data::types::response::Activity;
+use polymarket_client_sdk::data::types::{ActivityType, Side as TradeSide};
+use polymarket_client_sdk::gamma;
+use polymarket_client_sdk::types::{Address, B256, Decimal, U256};
+use polymarket_client_sdk::{POLYGON, contract_config};
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::{Deserialize, Serialize};
+
+use super::balance::to_decimal;
+use super::markets::fetch_market;
+use super::wallet::resolve_trading_address;
+use crate::auth;
+use crate::clients;
+use crate::config;
+use crate::db;
+use crate::errors;
+use crate::net;
+use crate::output::OutputFormat;
+use crate::output::sync::{
+    SyncSummary, SyncedPosition, SyncedTrade, print_sync_summary, print_synced_positions,
+    print_synced_trades,
+};
+
+/// Where a new index starts: mid-2022, before the CLOB exchanges were
+/// deployed. History from the older AMM markets needs an earlier
+/// `--from-block`.
+const DEFAULT_FROM_BLOCK: u64 = 33_000_000;
+/// Smallest block range tried before giving up on an RPC error.
+const MIN_CHUNK: u64 = 100;
+/// Block ranges scanned between saves when nothing is found.
+const SAVE_EVERY: u64 = 100;
+/// Outcome tokens and USDC both have 6 decimals.
+const DECIMALS: u8 = 6;
+
+sol! {
+    #[sol(rpc)]
+    interface IConditionalTokens {
+        event TransferSingle(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value);
+        event TransferBatch(address indexed operator, address indexed from, address indexed to, uint256[] ids, uint256[] values);
+        event PositionSplit(address indexed stakeholder, address collateralToken, bytes32 indexed parentCollectionId, bytes32 indexed conditionId, uint256[] partition, uint256 amount);
+        event PositionsMerge(address indexed stakeholder, address collateralToken, bytes32 indexed parentCollectionId, bytes32 indexed conditionId, uint256[] partition, uint256 amount);
+        event PayoutRedemption(address indexed redeemer, address indexed collateralToken, bytes32 indexed parentCollectionId, bytes32 conditionId, uint256[] indexSets, uint256 payout);
+    }
+
+    interface INegRiskAdapter {
+        event PositionSplit(address indexed stakeholder, bytes32 indexed conditionId, uint256 amount);
+        event PositionsMerge(address indexed stakeholder, bytes32 indexed conditionId, uint256 amount);
+        event PayoutRedemption(address indexed redeemer, bytes32 indexed conditionId, uint256[] amounts, uint256 payout);
+    }
+
+    #[sol(rpc)]
+    interface ICtfExchange {
+        event OrderFilled(bytes32 indexed orderHash, address indexed maker, address indexed taker, uint256 makerAssetId, uint256 takerAssetId, uint256 makerAmountFilled, uint256 takerAmountFilled, uint256 fee);
+    }
+}
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct SyncArgs {
+    #[command(subcommand)]
+    pub command: Option<SyncCommand>,
+
+    /// First block to scan when nothing is synced yet
+    #[arg(long, default_value_t = DEFAULT_FROM_BLOCK)]
+    pub from_block: u64,
+
+    /// Blocks per log query; halved while the RPC rejects the range
+    #[arg(long, default_value = "10000")]
+    pub chunk: u64,
+
+    /// Discard the index and scan again from --from-block
+    #[arg(long)]
+    pub rebuild: bool,
+}
+
+#[derive(Subcommand)]
+pub enum SyncCommand {
+    /// Outcome token balances from the synced transfers
+    Positions,
+    /// Fills of the wallet's orders from the synced history, oldest first
+    Trades,
+}
+
+/// One thing that happened to the wallet on-chain.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChainEvent {
+    pub block: u64,
+    pub log_index: u64,
+    pub timestamp: i64,
+    pub tx: B256,
+    #[serde(flatten)]
+    pub kind: EventKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EventKind {
+    /// Shares of `token` into the wallet, or out of it when negative.
+    Transfer {
+        token: U256,
+        shares: Decimal,
+    },
+    /// A fill of one of the wallet's orders. The exchange takes `fee` from
+    /// what the order receives: shares on a buy, USDC on a sell.
+    Fill {
+        side: TradeSide,
+        token: U256,
+        shares: Decimal,
+        usdc: Decimal,
+        fee: Decimal,
+    },
+    Split {
+        condition_id: B256,
+        usdc: Decimal,
+    },
+    Merge {
+        condition_id: B256,
+        usdc: Decimal,
+    },
+    Redemption {
+        condition_id: B256,
+        usdc: Decimal,
+    },
+}
+
+/// The synced history of one wallet.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SyncIndex {
+    pub address: Address,
+    /// Last block scanned; `None` before the first range is.
+    pub synced_to: Option<u64>,
+    /// Oldest first.
+    pub events: Vec<ChainEvent>,
+}
+
+impl SyncIndex {
+    fn new(address: Address) -> Self {
+        Self {
+            address,
+            synced_to: None,
+            events: Vec::new(),
+        }
+    }
+
+    /// Every token the wallet has held or traded.
+    pub fn tokens(&self) -> HashSet<U256> {
+        self.events
+            .iter()
+            .filter_map(|e| match e.kind {
+                EventKind::Transfer { token, .. } | EventKind::Fill { token, .. } => Some(token),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Net shares held of each token, leaving out those that net to zero.
+    pub fn balances(&self) -> BTreeMap<U256, Decimal> {
+        let mut balances: BTreeMap<U256, Decimal> = BTreeMap::new();
+        for e in &self.events {
+            if let EventKind::Transfer { token, shares } = e.kind {
+                *balances.entry(token).or_default() += shares;
+            }
+        }
+        balances.retain(|_, shares| !shares.is_zero());
+        balances
+    }
+}
+
+/// The wallet's index, or `None` if it has never been synced.
+pub(crate) fn load_index(address: Address) -> Result<Option<SyncIndex>> {
+    read_index(&db::open()?, address).context("Failed to read the sync index")
+}
+
+fn wallet_key(address: Address) -> String {
+    address.to_string().to_lowercase()
+}
+
+fn read_index(conn: &Connection, address: Address) -> Result<Option<SyncIndex>> {
+    let key = wallet_key(address);
+    let Some(synced_to) = conn
+        .query_row(
+            "SELECT synced_to FROM sync_wallets WHERE address = ?1",
+            [&key],
+            |row| row.get(0),
+        )
+        .optional()?
+    else {
+        return Ok(None);
+    };
+    let events = conn
+        .prepare(
+            "SELECT block, log_index, timestamp, tx, kind FROM sync_events
+             WHERE address = ?1 ORDER BY seq",
+        )?
+        .query_map([&key], |row| {
+            Ok(ChainEvent {
+                block: row.get(0)?,
+                log_index: row.get(1)?,
+                timestamp: row.get(2)?,
+                tx: db::parsed(row, 3)?,
+                kind: db::json(row, 4)?,
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(Some(SyncIndex {
+        address,
+        synced_to,
+        events,
+    }))
+}
+
+/// Records that `address` has been scanned to `synced_to` and adds
+/// `events`, the ones found since the last write, to its index.
+pub(crate) fn write_index(
+    conn: &Connection,
+    address: Address,
+    synced_to: Option<u64>,
+    events: &[ChainEvent],
+) -> Result<()> {
+    let key = wallet_key(address);
+    conn.execute(
+        "INSERT INTO sync_wallets (address, synced_to) VALUES (?1, ?2)
+         ON CONFLICT (address) DO UPDATE SET synced_to = excluded.synced_to",
+        params![key, synced_to],
+    )?;
+    let mut insert = conn.prepare(
+        "INSERT INTO sync_events (address, block, log_index, timestamp, tx, kind)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?;
+    for e in events {
+        insert.execute(params![
+            key,
+            e.block,
+            e.log_index,
+            e.timestamp,
+            e.tx.to_string(),
+            serde_json::to_string(&e.kind)?,
+        ])?;
+    }
+    Ok(())
+}
+
+fn clear_index(conn: &Connection, address: Address) -> Result<()> {
+    let key = wallet_key(address);
+    conn.execute("DELETE FROM sync_events WHERE address = ?1", [&key])?;
+    conn.execute("DELETE FROM sync_wallets WHERE address = ?1", [&key])?;
+    Ok(())
+}
+
+/// Writes what `index` has gained since `saved` events were written.
+fn save_index(conn: &mut Connection, index: &SyncIndex, saved: &mut usize) -> Result<()> {
+    let tx = conn.transaction()?;
+    write_index(&tx, index.address, index.synced_to, &index.events[*saved..])?;
+    tx.commit().context("Failed to write the sync index")?;
+    *saved = index.events.len();
+    Ok(())
+}
+
+/// The wallet's index, or an error saying to run `sync` first.
+pub(crate) fn synced_index(address: Address) -> Result<SyncIndex> {
+    load_index(address)?.ok_or_else(|| {
+        errors::validation(format!(
+            "Nothing synced for {address} yet. Run `polymarket sync` first."
+        ))
+    })
+}
+
+pub async fn execute(
+    args: SyncArgs,
+    output: &OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    let address = resolve_trading_address(private_key, signature_type).await?;
+    match args.command {
+        None => {
+            anyhow::ensure!(
+                args.chunk >= MIN_CHUNK,
+                errors::validation(format!("--chunk must be at least {MIN_CHUNK}"))
+            );
+            let index = match load_index(address)? {
+                Some(index) if !args.rebuild => index,
+                _ => SyncIndex::new(address),
+            };
+            let summary = sync(index, args.from_block, args.chunk).await?;
+            print_sync_summary(&summary, output)
+        }
+        Some(SyncCommand::Positions) => {
+            let index = synced_index(address)?;
+            let balances = index.balances();
+            let markets = token_markets(balances.keys().copied()).await;
+            let positions: Vec<SyncedPosition> = balances
+                .into_iter()
+                .map(|(token, shares)| SyncedPosition {
+                    market: markets.get(&token).cloned(),
+                    token,
+                    shares,
+                })
+                .collect();
+            print_synced_positions(&positions, index.synced_to, output)
+        }
+        Some(SyncCommand::Trades) => {
+            let index = synced_index(address)?;
+            let fills: Vec<_> = index
+                .events
+                .iter()
+                .filter_map(|e| match &e.kind {
+                    EventKind::Fill {
+                        side,
+                        token,
+                        shares,
+                        usdc,
+                        fee,
+                    } => Some((e, side, *token, *shares, *usdc, *fee)),
+                    _ => None,
+                })
+                .collect();
+            let markets = token_markets(fills.iter().map(|f| f.2).collect::<HashSet<_>>()).await;
+            let trades: Vec<SyncedTrade> = fills
+                .into_iter()
+                .map(|(e, side, token, shares, usdc, fee)| SyncedTrade {
+                    timestamp: e.timestamp,
+                    tx: e.tx,
+                    side: side.clone(),
+                    market: markets.get(&token).cloned(),
+                    token,
+                    shares,
+                    usdc,
+                    fee,
+                })
+                .collect();
+            print_synced_trades(&trades, output)
+        }
+    }
+}
+
+/// Scans from where `index` stopped to the latest block, saving as it
+/// goes.
+async fn sync(mut index: SyncIndex, from_block: u64, chunk: u64) -> Result<SyncSummary> {
+    let provider = auth::create_readonly_provider().await?;
+    let contracts = Contracts::polygon()?;
+    let latest = provider
+        .get_block_number()
+        .await
+        .context("Failed to get the latest block")?;
+    let start = index.synced_to.map_or(from_block, |b| b + 1);
+    let before = index.events.len();
+    let mut conn = db::open()?;
+    // A fresh index, as with --rebuild, replaces what was there.
+    if index.synced_to.is_none() {
+        clear_index(&conn, index.address)?;
+    }
+    let mut saved = before;
+    let mut chunk = chunk;
+    let mut from = start;
+    let mut unsaved = 0;
+    let mut timestamps = HashMap::new();
+    eprintln!(
+        "Syncing {} from block {start} to {latest}. Ctrl-C stops; the next sync resumes.",
+        index.address
+    );
+    while from <= latest {
+        let to = (from + chunk - 1).min(latest);
+        let events = match scan(&provider, &contracts, index.address, from, to).await {
+            Ok(logs) => decode(&provider, index.address, logs, &mut timestamps).await?,
+            Err(e) if chunk > MIN_CHUNK => {
+                tracing::debug!("Blocks {from}-{to} failed, trying fewer: {e:#}");
+                chunk = (chunk / 2).max(MIN_CHUNK);
+                continue;
+            }
+            Err(e) => {
+                save_index(&mut conn, &index, &mut saved)?;
+                return Err(e.context(format!("Failed to scan blocks {from}-{to}")));
+            }
+        };
+        let found = !events.is_empty();
+        index.events.extend(events);
+        index.synced_to = Some(to);
+        unsaved += 1;
+        if found || unsaved >= SAVE_EVERY {
+            save_index(&mut conn, &index, &mut saved)?;
+            unsaved = 0;
+            eprintln!(
+                "Block {to} of {latest}: {} events",
+                index.events.len() - before
+            );
+        }
+        from = to + 1;
+    }
+    save_index(&mut conn, &index, &mut saved)?;
+    Ok(SyncSummary {
+        address: index.address,
+        from_block: start,
+        synced_to: index.synced_to,
+        new_events: index.events.len() - before,
+        total_events: index.events.len(),
+    })
+}
+
+/// The contracts whose logs make up the wallet's history.
+struct Contracts {
+    ctf: Address,
+    neg_risk_adapter: Address,
+    exchanges: Vec<Address>,
+}
+
+impl Contracts {
+    fn polygon() -> Result<Self> {
+        let config = contract_config(POLYGON, false).context("No contract config for Polygon")?;
+        let neg_risk =
+            contract_config(POLYGON, true).context("No neg-risk contract config for Polygon")?;
+        Ok(Self {
+            ctf: config.conditional_tokens,
+            neg_risk_adapter: neg_risk
+                .neg_risk_adapter
+                .context("No neg-risk adapter for Polygon")?,
+            exchanges: vec![config.exchange, neg_risk.exchange],
+        })
+    }
+}
+
+/// The parts of an RPC log the index keeps.
+struct RawLog {
+    block: u64,
+    log_index: u64,
+    tx: B256,
+    /// Not every RPC includes it.
+    timestamp: Option<u64>,
+    inner: alloy::primitives::Log,
+}
+
+/// The wallet's logs in blocks `from` to `to`, in chain order, each once.
+async fn scan<P: Provider>(
+    provider: &P,
+    contracts: &Contracts,
+    wallet: Address,
+    from: u64,
+    to: u64,
+) -> Result<Vec<RawLog>> {
+    let ctf = IConditionalTokens::new(contracts.ctf, provider);
+    let exchange = ICtfExchange::new(contracts.exchanges[0], provider);
+    let transfers = || {
+        ctf.TransferSingle_filter()
+            .event_signature(vec![
+                IConditionalTokens::TransferSingle::SIGNATURE_HASH,
+                IConditionalTokens::TransferBatch::SIGNATURE_HASH,
+            ])
+            .from_block(from)
+            .to_block(to)
+    };
+    let transfers_out = transfers().topic2(wallet);
+    let transfers_in = transfers().topic3(wallet);
+    let positions = ctf
+        .PositionSplit_filter()
+        .address(vec![contracts.ctf, contracts.neg_risk_adapter])
+        .event_signature(vec![
+            IConditionalTokens::PositionSplit::SIGNATURE_HASH,
+            IConditionalTokens::PositionsMerge::SIGNATURE_HASH,
+            IConditionalTokens::PayoutRedemption::SIGNATURE_HASH,
+            INegRiskAdapter::PositionSplit::SIGNATURE_HASH,
+            INegRiskAdapter::PositionsMerge::SIGNATURE_HASH,
+            INegRiskAdapter::PayoutRedemption::SIGNATURE_HASH,
+        ])
+        .topic1(wallet)
+        .from_block(from)
+        .to_block(to);
+    let fills = exchange
+        .OrderFilled_filter()
+        .address(contracts.exchanges.clone())
+        .topic2(wallet)
+        .from_block(from)
+        .to_block(to);
+    let (transfers_out, transfers_in, positions, fills) = tokio::try_join!(
+        transfers_out.query_raw(),
+        transfers_in.query_raw(),
+        positions.query_raw(),
+        fills.query_raw(),
+    )?;
+    let mut logs = BTreeMap::new();
+    for log in [transfers_out, transfers_in, positions, fills]
+        .into_iter()
+        .flatten()
+    {
+        // Pending logs have no position yet.
+        if let (Some(block), Some(log_index), Some(tx)) =
+            (log.block_number, log.log_index, log.transaction_hash)
+        {
+            logs.insert(
+                (block, log_index),
+                RawLog {
+                    block,
+                    log_index,
+                    tx,
+                    timestamp: log.block_timestamp,
+                    inner: log.inner,
+                },
+            );
+        }
+    }
+    Ok(logs.into_values().collect())
+}
+
+fn shares(value: U256) -> Result<Decimal> {
+    to_decimal(value, DECIMALS)
+}
+
+/// What a log means for `wallet`; a batch transfer is one event per token.
+fn event_kinds(wallet: Address, log: &alloy::primitives::Log) -> Result<Vec<EventKind>> {
+    let Some(topic) = log.topics().first() else {
+        return Ok(Vec::new());
+    };
+    let sign = |from: Address, to: Address, amount: Decimal| {
+        // Sent to itself: no change.
+        match (from == wallet, to == wallet) {
+            (true, false) => Some(-amount),
+            (false, true) => Some(amount),
+            _ => None,
+        }
+    };
+    let kinds = match *topic {
+        IConditionalTokens::TransferSingle::SIGNATURE_HASH => {
+            let e = IConditionalTokens::TransferSingle::decode_log(log)?.data;
+            sign(e.from, e.to, shares(e.value)?)
+                .map(|shares| EventKind::Transfer {
+                    token: e.id,
+                    shares,
+                })
+                .into_iter()
+                .collect()
+        }
+        IConditionalTokens::TransferBatch::SIGNATURE_HASH => {
+            let e = IConditionalTokens::TransferBatch::decode_log(log)?.data;
+            let mut kinds = Vec::new();
+            for (token, value) in e.ids.iter().zip(&e.values) {
+                if let Some(shares) = sign(e.from, e.to, shares(*value)?) {
+                    kinds.push(EventKind::Transfer {
+                        token: *token,
+                        shares,
+                    });
+                }
+            }
+            kinds
+        }
+        IConditionalTokens::PositionSplit::SIGNATURE_HASH => {
+            let e = IConditionalTokens::PositionSplit::decode_log(log)?.data;
+            vec![EventKind::Split {
+                condition_id: e.conditionId,
+                usdc: shares(e.amount)?,
+            }]
+        }
+        IConditionalTokens::PositionsMerge::SIGNATURE_HASH => {
+            let e = IConditionalTokens::PositionsMerge::decode_log(log)?.data;
+            vec![EventKind::Merge {
+                condition_id: e.conditionId,
+                usdc: shares(e.amount)?,
+            }]
+        }
+        IConditionalTokens::PayoutRedemption::SIGNATURE_HASH => {
+            let e = IConditionalTokens::PayoutRedemption::decode_log(log)?.data;
+            vec![EventKind::Redemption {
+                condition_id: e.conditionId,
+                usdc: shares(e.payout)?,
+            }]
+        }
+        INegRiskAdapter::PositionSplit::SIGNATURE_HASH => {
+            let e = INegRiskAdapter::PositionSplit::decode_log(log)?.data;
+            vec![EventKind::Split {
+                condition_id: e.conditionId,
+                usdc: shares(e.amount)?,
+            }]
+        }
+        INegRiskAdapter::PositionsMerge::SIGNATURE_HASH => {
+            let e = INegRiskAdapter::PositionsMerge::decode_log(log)?.data;
+            vec![EventKind::Merge {
+                condition_id: e.conditionId,
+                usdc: shares(e.amount)?,
+            }]
+        }
+        INegRiskAdapter::PayoutRedemption::SIGNATURE_HASH => {
+            let e = INegRiskAdapter::PayoutRedemption::decode_log(log)?.data;
+            vec![EventKind::Redemption {
+                condition_id: e.conditionId,
+                usdc: shares(e.payout)?,
+            }]
+        }
+        ICtfExchange::OrderFilled::SIGNATURE_HASH => {
+            let e = ICtfExchange::OrderFilled::decode_log(log)?.data;
+            if e.maker != wallet {
+                return Ok(Vec::new());
+            }
+            // The maker asset is what the order gives: USDC (ID 0) on a buy.
+            let kind = if e.makerAssetId.is_zero() {
+                EventKind::Fill {
+                    side: TradeSide::Buy,
+                    token: e.takerAssetId,
+                    shares: shares(e.takerAmountFilled)?,
+                    usdc: shares(e.makerAmountFilled)?,
+                    fee: shares(e.fee)?,
+                }
+            } else {
+                EventKind::Fill {
+                    side: TradeSide::Sell,
+                    token: e.makerAssetId,
+                    shares: shares(e.makerAmountFilled)?,
+                    usdc: shares(e.takerAmountFilled)?,
+                    fee: shares(e.fee)?,
+                }
+            };
+            vec![kind]
+        }
+        _ => Vec::new(),
+    };
+    Ok(kinds)
+}
+
+/// The events in `logs`, timestamped from the log when the RPC includes it
+/// and from its block otherwise.
+async fn decode<P: Provider>(
+    provider: &P,
+    wallet: Address,
+    logs: Vec<RawLog>,
+    timestamps: &mut HashMap<u64, i64>,
+) -> Result<Vec<ChainEvent>> {
+    let mut events = Vec::new();
+    for log in logs {
+        let kinds = event_kinds(wallet, &log.inner)?;
+        if kinds.is_empty() {
+            continue;
+        }
+        let timestamp = match log.timestamp {
+            Some(t) => i64::try_from(t).context("Invalid block timestamp")?,
+            None => block_timestamp(provider, log.block, timestamps).await?,
+        };
+        events.extend(kinds.into_iter().map(|kind| ChainEvent {
+            block: log.block,
+            log_index: log.log_index,
+            timestamp,
+            tx: log.tx,
+            kind,
+        }));
+    }
+    Ok(events)
+}
+
+async fn block_timestamp<P: Provider>(
+    provider: &P,
+    block: u64,
+    timestamps: &mut HashMap<u64, i64>,
+) -> Result<i64> {
+    if let Some(t) = timestamps.get(&block) {
+        return Ok(*t);
+    }
+    let header = provider
+        .get_block_by_number(block.into())
+        .await?
+        .with_context(|| format!("Block {block} not found"))?
+        .header;
+    let t = i64::try_from(header.timestamp).context("Invalid block timestamp")?;
+    timestamps.insert(block, t);
+    Ok(t)
+}
+
+/// The market and outcome of each token, for naming them. Lookups that
+/// fail, and all of them offline, are left out.
+pub(crate) async fn token_markets(
+    tokens: impl IntoIterator<Item = U256>,
+) -> HashMap<U256, TokenMarket> {
+    let mut markets = HashMap::new();
+    if config::offline() {
+        return markets;
+    }
+    let Ok(client) = clients::gamma() else {
+        return markets;
+    };
+    let tokens: Vec<U256> = tokens.into_iter().collect();
+    let found = net::fetch_all(&tokens, |token| lookup(&client, *token)).await;
+    for (token, market) in tokens.iter().zip(found.unwrap_or_default()) {
+        if let Some(market) = market {
+            markets.insert(*token, market);
+        }
+    }
+    markets
+}
+
+/// A token's market: its question, outcome and condition ID.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenMarket {
+    pub question: String,
+    pub outcome: Option<String>,
+    pub condition_id: Option<B256>,
+}
+
+async fn lookup(client: &gamma::Client, token: U256) -> Result<Option<TokenMarket>> {
+    let Ok(market) = fetch_market(client, &token.to_string()).await else {
+        return Ok(None);
+    };
+    let outcome = market
+        .clob_token_ids
+        .iter()
+        .flatten()
+        .position(|t| *t == token)
+        .and_then(|i| market.outcomes.as_ref()?.get(i).cloned());
+    Ok(Some(TokenMarket {
+        question: market.question.unwrap_or_default(),
+        outcome,
+        condition_id: market.condition_id,
+    }))
+}
+
+/// The index as the data API's activity feed, oldest first, for `tax
+/// report --synced`. Fees come off what each fill received. Splits, merges
+/// and redemptions are matched to outcomes through `markets`.
+pub(crate) fn activity(index: &SyncIndex, markets: &HashMap<U256, TokenMarket>) -> Vec<Activity> {
+    let titles: HashMap<B256, &str> = markets
+        .values()
+        .filter_map(|m| Some((m.condition_id?, m.question.as_str())))
+        .collect();
+    let base = |e: &ChainEvent, kind: ActivityType, size: Decimal, usdc: Decimal| {
+        Activity::builder()
+            .proxy_wallet(index.address)
+            .timestamp(e.timestamp)
+            .activity_type(kind)
+            .size(size)
+            .usdc_size(usdc)
+            .transaction_hash(e.tx)
+            .build()
+    };
+    let mut activity = Vec::new();
+    for e in &index.events {
+        let a = match &e.kind {
+            EventKind::Transfer { .. } => continue,
+            EventKind::Fill {
+                side,
+                token,
+                shares,
+                usdc,
+                fee,
+            } => {
+                let (size, net) = match side {
+                    TradeSide::Buy => (*shares - *fee, *usdc),
+                    _ => (*shares, *usdc - *fee),
+                };
+                let mut a = base(e, ActivityType::Trade, size, net);
+                a.side = Some(side.clone());
+                a.asset = Some(*token);
+                if !shares.is_zero() {
+                    a.price = Some(*usdc / *shares);
+                }
+                if let Some(m) = markets.get(token) {
+                    a.condition_id = m.condition_id;
+                    a.title = Some(m.question.clone());
+                    a.outcome.clone_from(&m.outcome);
+                }
+                a
+            }
+            EventKind::Split { condition_id, usdc }
+            | EventKind::Merge { condition_id, usdc }
+            | EventKind::Redemption { condition_id, usdc } => {
+                let kind = match e.kind {
+                    EventKind::Split { .. } => ActivityType::Split,
+                    EventKind::Merge { .. } => ActivityType::Merge,
+                    _ => ActivityType::Redeem,
+                };
+                let mut a = base(e, kind, *usdc, *usdc);
+                a.condition_id = Some(*condition_id);
+                a.title = titles.get(condition_id).map(ToString::to_string);
+                a
+            }
+        };
+        activity.push(a);
+    }
+    activity
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn event(block: u64, kind: EventKind) -> ChainEvent {
+        ChainEvent {
+            block,
+            log_index: 0,
+            timestamp: 1_700_000_000 + i64::try_from(block).unwrap(),
+            tx: B256::with_last_byte(u8::try_from(block).unwrap()),
+            kind,
+        }
+    }
+
+    fn index(events: Vec<ChainEvent>) -> SyncIndex {
+        SyncIndex {
+            address: Address::with_last_byte(1),
+            synced_to: Some(100),
+            events,
+        }
+    }
+
+    fn fill(side: TradeSide, shares: Decimal, usdc: Decimal, fee: Decimal) -> EventKind {
+        EventKind::Fill {
+            side,
+            token: U256::from(7),
+            shares,
+            usdc,
+            fee,
+        }
+    }
+
+    #[test]
+    fn balances_net_transfers_and_drop_closed_tokens() {
+        let transfer = |token: u64, shares: Decimal| EventKind::Transfer {
+            token: U256::from(token),
+            shares,
+        };
+        let index = index(vec![
+            event(1, transfer(7, dec!(100))),
+            event(2, transfer(7, dec!(-40))),
+            event(3, transfer(8, dec!(5))),
+            event(4, transfer(8, dec!(-5))),
+        ]);
+        assert_eq!(
+            index.balances().into_iter().collect::<Vec<_>>(),
+            vec![(U256::from(7), dec!(60))]
+        );
+    }
+
+    #[test]
+    fn events_round_trip_through_the_index_file_format() {
+        let e = event(3, fill(TradeSide::Sell, dec!(10), dec!(6), dec!(0.1)));
+        let json = serde_json::to_value(&e).unwrap();
+        assert_eq!(json["type"], "fill");
+        assert_eq!(json["side"], "SELL");
+        assert_eq!(serde_json::from_value::<ChainEvent>(json).unwrap(), e);
+    }
+
+    #[test]
+    fn activity_takes_fees_off_what_each_fill_received() {
+        let markets = HashMap::from([(
+            U256::from(7),
+            TokenMarket {
+                question: "Will it rain?".into(),
+                outcome: Some("Yes".into()),
+                condition_id: Some(B256::with_last_byte(9)),
+            },
+        )]);
+        let index = index(vec![
+            event(1, fill(TradeSide::Buy, dec!(100), dec!(40), dec!(1))),
+            event(2, fill(TradeSide::Sell, dec!(50), dec!(30), dec!(0.5))),
+            event(
+                3,
+                EventKind::Redemption {
+                    condition_id: B256::with_last_byte(9),
+                    usdc: dec!(50),
+                },
+            ),
+        ]);
+        let a = activity(&index, &markets);
+        assert_eq!((a[0].size, a[0].usdc_size), (dec!(99), dec!(40)));
+        assert_eq!(a[0].title.as_deref(), Some("Will it rain?"));
+        assert_eq!((a[1].size, a[1].usdc_size), (dec!(50), dec!(29.5)));
+        assert!(matches!(a[2].activity_type, ActivityType::Redeem));
+        assert_eq!(a[2].condition_id, Some(B256::with_last_byte(9)));
+        assert_eq!(a[2].title.as_deref(), Some("Will it rain?"));
+    }
+
+    #[test]
+    fn index_writes_append_and_a_clear_forgets_the_wallet() {
+        let conn = db::open_in_memory();
+        let address = Address::with_last_byte(1);
+        assert!(read_index(&conn, address).unwrap().is_none());
+        let first = event(1, fill(TradeSide::Buy, dec!(10), dec!(4), dec!(0.1)));
+        let second = event(
+            2,
+            EventKind::Redemption {
+                condition_id: B256::with_last_byte(9),
+                usdc: dec!(5),
+            },
+        );
+        write_index(&conn, address, Some(1), std::slice::from_ref(&first)).unwrap();
+        write_index(&conn, address, Some(2), std::slice::from_ref(&second)).unwrap();
+        let index = read_index(&conn, address).unwrap().unwrap();
+        assert_eq!(index.synced_to, Some(2));
+        assert_eq!(index.events, [first, second]);
+
+        clear_index(&conn, address).unwrap();
+        assert!(read_index(&conn, address).unwrap().is_none());
+    }
+}
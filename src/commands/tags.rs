@@ -9,6 +9,7 @@ use polymarket_client_sdk::gamma::{
 };
 
 use super::is_numeric_id;
+use crate::net;
 use crate::output::tags::{print_related_tags_table, print_tag_detail, print_tags_table};
 use crate::output::{OutputFormat, print_json};
 
@@ -75,10 +76,12 @@ pub async fn execute(client: &gamma::Client, args: TagsArgs, output: OutputForma
                 .maybe_ascending(if ascending { Some(true) } else { None })
                 .build();
 
-            let tags = client.tags(&request).await?;
+            let tags = net::retry(|| client.tags(&request)).await?;
 
             match output {
-                OutputFormat::Table => print_tags_table(&tags),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    print_tags_table(&tags)
+                }
                 OutputFormat::Json => print_json(&tags)?,
             }
         }
@@ -87,14 +90,16 @@ pub async fn execute(client: &gamma::Client, args: TagsArgs, output: OutputForma
             let is_numeric = is_numeric_id(&id);
             let tag = if is_numeric {
                 let req = TagByIdRequest::builder().id(id).build();
-                client.tag_by_id(&req).await?
+                net::retry(|| client.tag_by_id(&req)).await?
             } else {
                 let req = TagBySlugRequest::builder().slug(id).build();
-                client.tag_by_slug(&req).await?
+                net::retry(|| client.tag_by_slug(&req)).await?
             };
 
             match output {
-                OutputFormat::Table => print_tag_detail(&tag),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    print_tag_detail(&tag)
+                }
                 OutputFormat::Json => print_json(&tag)?,
             }
         }
@@ -106,17 +111,19 @@ pub async fn execute(client: &gamma::Client, args: TagsArgs, output: OutputForma
                     .id(id)
                     .maybe_omit_empty(omit_empty)
                     .build();
-                client.related_tags_by_id(&req).await?
+                net::retry(|| client.related_tags_by_id(&req)).await?
             } else {
                 let req = RelatedTagsBySlugRequest::builder()
                     .slug(id)
                     .maybe_omit_empty(omit_empty)
                     .build();
-                client.related_tags_by_slug(&req).await?
+                net::retry(|| client.related_tags_by_slug(&req)).await?
             };
 
             match output {
-                OutputFormat::Table => print_related_tags_table(&related),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    print_related_tags_table(&related)
+                }
                 OutputFormat::Json => print_json(&related)?,
             }
         }
@@ -128,17 +135,19 @@ pub async fn execute(client: &gamma::Client, args: TagsArgs, output: OutputForma
                     .id(id)
                     .maybe_omit_empty(omit_empty)
                     .build();
-                client.tags_related_to_tag_by_id(&req).await?
+                net::retry(|| client.tags_related_to_tag_by_id(&req)).await?
             } else {
                 let req = RelatedTagsBySlugRequest::builder()
                     .slug(id)
                     .maybe_omit_empty(omit_empty)
                     .build();
-                client.tags_related_to_tag_by_slug(&req).await?
+                net::retry(|| client.tags_related_to_tag_by_slug(&req)).await?
             };
 
             match output {
-                OutputFormat::Table => print_tags_table(&tags),
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                    print_tags_table(&tags)
+                }
                 OutputFormat::Json => print_json(&tags)?,
             }
         }
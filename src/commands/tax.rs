@@ -0,0 +1,395 @@
+//! `tax report`: realized gains and losses for a calendar year, matched lot
+//! by lot from the wallet's trade and redemption history.
+//!
+//! Every activity since the wallet's first is replayed: buys and splits open
+//! lots, and sells, merges and redemptions close them by the chosen method.
+//! A merge or redemption closes shares of every outcome at once for a single
+//! USDC amount, so it is reported as one row. Shares the history doesn't
+//! show being bought (e.g. transferred in) have no cost basis.
+
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Utc};
+use clap::{Args, Subcommand, ValueEnum};
+use polymarket_client_sdk::data::types::response::Activity;
+use polymarket_client_sdk::data::types::{ActivityType, Side as TradeSide};
+use polymarket_client_sdk::types::{B256, Decimal, U256};
+
+use super::sync;
+use super::wallet::resolve_trading_address;
+use crate::clients;
+use crate::output::OutputFormat;
+use crate::output::tax::{Disposal, print_tax_report};
+
+/// Days a lot must be held for its gain to be long-term.
+const LONG_TERM_DAYS: i64 = 365;
+
+#[derive(Args)]
+pub struct TaxArgs {
+    #[command(subcommand)]
+    pub command: TaxCommand,
+}
+
+#[derive(Subcommand)]
+pub enum TaxCommand {
+    /// Realized gains and losses for a year, one row per lot closed
+    Report {
+        /// Calendar year, e.g. 2024
+        #[arg(long)]
+        year: i32,
+        /// Which shares a sale closes: oldest first, newest first, or all at
+        /// their average cost
+        #[arg(long, value_enum, default_value_t = LotMethod::Fifo)]
+        method: LotMethod,
+        /// Use the history `polymarket sync` indexed from the chain instead
+        /// of the activity API, which stops after 10,000 entries
+        #[arg(long)]
+        synced: bool,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum LotMethod {
+    Fifo,
+    Lifo,
+    Avg,
+}
+
+impl std::fmt::Display for LotMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Fifo => "fifo",
+            Self::Lifo => "lifo",
+            Self::Avg => "avg",
+        })
+    }
+}
+
+pub async fn execute(
+    args: TaxArgs,
+    output: &OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    match args.command {
+        TaxCommand::Report {
+            year,
+            method,
+            synced,
+        } => {
+            anyhow::ensure!(
+                (2020..=Utc::now().year()).contains(&year),
+                crate::errors::validation(format!(
+                    "--year must be between 2020 and {}",
+                    Utc::now().year()
+                ))
+            );
+            let address = resolve_trading_address(private_key, signature_type).await?;
+            let activity = if synced {
+                let index = sync::synced_index(address)?;
+                let markets = sync::token_markets(index.tokens()).await;
+                sync::activity(&index, &markets)
+            } else {
+                super::portfolio::fetch_activity(&clients::data()?, address).await?
+            };
+            let disposals: Vec<Disposal> = realize(&activity, method)
+                .into_iter()
+                .filter(|d| d.disposed.year() == year)
+                .collect();
+            print_tax_report(year, &method.to_string(), &disposals, output)
+        }
+    }
+}
+
+/// Shares bought together, at `cost` in total.
+#[derive(Clone, Debug)]
+struct Lot {
+    /// `None` for shares the history doesn't show being bought.
+    acquired: Option<DateTime<Utc>>,
+    shares: Decimal,
+    cost: Decimal,
+}
+
+/// The open lots of each asset.
+struct Lots {
+    method: LotMethod,
+    held: HashMap<U256, VecDeque<Lot>>,
+}
+
+impl Lots {
+    fn new(method: LotMethod) -> Self {
+        Self {
+            method,
+            held: HashMap::new(),
+        }
+    }
+
+    fn open(&mut self, asset: U256, lot: Lot) {
+        let lots = self.held.entry(asset).or_default();
+        lots.push_back(lot);
+        // Average cost: every share carries the same basis, and lots keep
+        // their dates for the holding period.
+        if self.method == LotMethod::Avg {
+            let shares: Decimal = lots.iter().map(|l| l.shares).sum();
+            let cost: Decimal = lots.iter().map(|l| l.cost).sum();
+            if !shares.is_zero() {
+                for l in lots.iter_mut() {
+                    l.cost = cost * l.shares / shares;
+                }
+            }
+        }
+    }
+
+    fn shares(&self, asset: U256) -> Decimal {
+        self.held
+            .get(&asset)
+            .map_or(Decimal::ZERO, |lots| lots.iter().map(|l| l.shares).sum())
+    }
+
+    /// Closes `shares` of `asset` and returns the lots they came from. What
+    /// the open lots don't cover comes back as a lot with no cost.
+    fn close(&mut self, asset: U256, mut shares: Decimal) -> Vec<Lot> {
+        let mut closed = Vec::new();
+        let lots = self.held.entry(asset).or_default();
+        while shares > Decimal::ZERO {
+            let next = match self.method {
+                LotMethod::Fifo | LotMethod::Avg => lots.front_mut(),
+                LotMethod::Lifo => lots.back_mut(),
+            };
+            let Some(lot) = next else {
+                closed.push(Lot {
+                    acquired: None,
+                    shares,
+                    cost: Decimal::ZERO,
+                });
+                break;
+            };
+            if lot.shares <= shares {
+                shares -= lot.shares;
+                let lot = match self.method {
+                    LotMethod::Fifo | LotMethod::Avg => lots.pop_front(),
+                    LotMethod::Lifo => lots.pop_back(),
+                };
+                closed.extend(lot);
+            } else {
+                let cost = lot.cost * shares / lot.shares;
+                lot.shares -= shares;
+                lot.cost -= cost;
+                closed.push(Lot {
+                    acquired: lot.acquired,
+                    shares,
+                    cost,
+                });
+                break;
+            }
+        }
+        closed
+    }
+}
+
+fn long_term(acquired: Option<DateTime<Utc>>, disposed: DateTime<Utc>) -> bool {
+    acquired.is_some_and(|a| (disposed - a).num_days() > LONG_TERM_DAYS)
+}
+
+fn describe(a: &Activity) -> String {
+    let title = a.title.as_deref().unwrap_or("Unknown market");
+    match &a.outcome {
+        Some(outcome) => format!("{title} ({outcome})"),
+        None => title.to_string(),
+    }
+}
+
+/// Replays `activity` (oldest first) and returns every disposal in it.
+fn realize(activity: &[Activity], method: LotMethod) -> Vec<Disposal> {
+    // The feed doesn't name the outcomes a split, merge or redemption
+    // touches: take those the wallet has traded.
+    let mut outcomes: HashMap<B256, BTreeSet<U256>> = HashMap::new();
+    for a in activity {
+        if let (Some(condition), Some(asset)) = (a.condition_id, a.asset) {
+            outcomes.entry(condition).or_default().insert(asset);
+        }
+    }
+    let outcomes_of = |a: &Activity| -> Vec<U256> {
+        a.condition_id
+            .and_then(|c| outcomes.get(&c))
+            .map(|o| o.iter().copied().collect())
+            .unwrap_or_default()
+    };
+
+    let mut lots = Lots::new(method);
+    let mut disposals = Vec::new();
+    for a in activity {
+        let Some(time) = DateTime::from_timestamp(a.timestamp, 0) else {
+            continue;
+        };
+        match (&a.activity_type, &a.side, a.asset) {
+            (ActivityType::Trade, Some(TradeSide::Buy), Some(asset)) => lots.open(
+                asset,
+                Lot {
+                    acquired: Some(time),
+                    shares: a.size,
+                    cost: a.usdc_size,
+                },
+            ),
+            (ActivityType::Trade, Some(TradeSide::Sell), Some(asset)) => {
+                if a.size.is_zero() {
+                    continue;
+                }
+                for lot in lots.close(asset, a.size) {
+                    disposals.push(Disposal {
+                        description: describe(a),
+                        acquired: lot.acquired,
+                        disposed: time,
+                        shares: lot.shares,
+                        proceeds: a.usdc_size * lot.shares / a.size,
+                        cost_basis: lot.cost,
+                        long_term: long_term(lot.acquired, time),
+                    });
+                }
+            }
+            (ActivityType::Split, ..) => {
+                let assets = outcomes_of(a);
+                let Ok(n) = u32::try_from(assets.len()) else {
+                    continue;
+                };
+                if n == 0 {
+                    continue;
+                }
+                for asset in assets {
+                    lots.open(
+                        asset,
+                        Lot {
+                            acquired: Some(time),
+                            shares: a.size,
+                            cost: a.usdc_size / Decimal::from(n),
+                        },
+                    );
+                }
+            }
+            (ActivityType::Merge | ActivityType::Redeem, ..) => {
+                let redeem = matches!(a.activity_type, ActivityType::Redeem);
+                let closed: Vec<Lot> = outcomes_of(a)
+                    .into_iter()
+                    .flat_map(|asset| {
+                        let shares = if redeem { lots.shares(asset) } else { a.size };
+                        lots.close(asset, shares)
+                    })
+                    .collect();
+                if closed.is_empty() && a.usdc_size.is_zero() {
+                    continue;
+                }
+                let first = closed.first().and_then(|l| l.acquired);
+                let same_day = closed
+                    .iter()
+                    .all(|l| l.acquired.map(|t| t.date_naive()) == first.map(|t| t.date_naive()));
+                disposals.push(Disposal {
+                    description: format!(
+                        "{} ({})",
+                        a.title.as_deref().unwrap_or("Unknown market"),
+                        if redeem { "redeemed" } else { "merged" }
+                    ),
+                    acquired: if same_day { first } else { None },
+                    disposed: time,
+                    shares: closed.iter().map(|l| l.shares).sum(),
+                    proceeds: a.usdc_size,
+                    cost_basis: closed.iter().map(|l| l.cost).sum(),
+                    long_term: !closed.is_empty()
+                        && closed.iter().all(|l| long_term(l.acquired, time)),
+                });
+            }
+            _ => {}
+        }
+    }
+    disposals
+}
+
+#[cfg(test)]
+mod tests {
+    use polymarket_client_sdk::types::Address;
+
+    use super::*;
+
+    fn activity(time: i64, kind: ActivityType, size: i64, usdc: i64) -> Activity {
+        Activity::builder()
+            .proxy_wallet(Address::ZERO)
+            .timestamp(time)
+            .condition_id(B256::with_last_byte(1))
+            .activity_type(kind)
+            .size(Decimal::from(size))
+            .usdc_size(Decimal::from(usdc))
+            .transaction_hash(B256::ZERO)
+            .title("Will it rain?".to_string())
+            .build()
+    }
+
+    fn trade(time: i64, side: TradeSide, asset: u64, size: i64, usdc: i64) -> Activity {
+        let mut a = activity(time, ActivityType::Trade, size, usdc);
+        a.side = Some(side);
+        a.asset = Some(U256::from(asset));
+        a
+    }
+
+    const DAY: i64 = 86_400;
+
+    fn history() -> Vec<Activity> {
+        vec![
+            trade(0, TradeSide::Buy, 1, 100, 20),
+            trade(DAY, TradeSide::Buy, 1, 100, 60),
+            trade(2 * DAY, TradeSide::Sell, 1, 150, 75),
+        ]
+    }
+
+    #[test]
+    fn fifo_closes_the_oldest_lots_first() {
+        let d = realize(&history(), LotMethod::Fifo);
+        assert_eq!(d.len(), 2);
+        assert_eq!(d[0].shares, Decimal::from(100));
+        assert_eq!(d[0].cost_basis, Decimal::from(20));
+        assert_eq!(d[0].proceeds, Decimal::from(50));
+        assert_eq!(d[1].shares, Decimal::from(50));
+        assert_eq!(d[1].cost_basis, Decimal::from(30));
+        let gain: Decimal = d.iter().map(Disposal::gain).sum();
+        assert_eq!(gain, Decimal::from(25));
+    }
+
+    #[test]
+    fn lifo_closes_the_newest_lots_first() {
+        let d = realize(&history(), LotMethod::Lifo);
+        assert_eq!(d[0].acquired, DateTime::from_timestamp(DAY, 0));
+        assert_eq!(d[0].cost_basis, Decimal::from(60));
+        assert_eq!(d[1].cost_basis, Decimal::from(10));
+        let gain: Decimal = d.iter().map(Disposal::gain).sum();
+        assert_eq!(gain, Decimal::from(5));
+    }
+
+    #[test]
+    fn avg_gives_every_share_the_same_basis() {
+        let d = realize(&history(), LotMethod::Avg);
+        let basis: Decimal = d.iter().map(|d| d.cost_basis).sum();
+        assert_eq!(basis, Decimal::from(60));
+        assert!(
+            d.iter()
+                .all(|d| d.cost_basis / d.shares == Decimal::new(4, 1))
+        );
+    }
+
+    #[test]
+    fn redemption_closes_every_outcome_and_uncovered_sales_have_no_basis() {
+        let activity = vec![
+            trade(0, TradeSide::Buy, 1, 100, 30),
+            trade(0, TradeSide::Buy, 2, 50, 35),
+            trade(DAY, TradeSide::Sell, 2, 60, 30),
+            activity(400 * DAY, ActivityType::Redeem, 100, 100),
+        ];
+        let d = realize(&activity, LotMethod::Fifo);
+        assert_eq!(d.len(), 3);
+        // 10 shares sold that were never bought.
+        assert_eq!(d[1].acquired, None);
+        assert_eq!(d[1].cost_basis, Decimal::ZERO);
+        assert_eq!(d[2].description, "Will it rain? (redeemed)");
+        assert_eq!(d[2].cost_basis, Decimal::from(30));
+        assert_eq!(d[2].gain(), Decimal::from(70));
+        assert!(d[2].long_term);
+    }
+}
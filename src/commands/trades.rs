@@ -0,0 +1,254 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+use clap::{Args, Subcommand};
+use polymarket_client_sdk::clob::types::request::TradesRequest;
+use polymarket_client_sdk::data;
+use polymarket_client_sdk::data::types::MarketFilter;
+use polymarket_client_sdk::data::types::response::Trade;
+
+use super::clob::parse_token_id;
+use super::{parse_condition_id, parse_date};
+use crate::auth;
+use crate::clients;
+use crate::net;
+use crate::output::OutputFormat;
+use crate::output::clob::{END_CURSOR, print_trade_history};
+use crate::output::data::print_trades;
+
+/// Trades requested per page of a market's tape.
+const TAPE_PAGE_SIZE: i32 = 500;
+
+/// The Data API serves no page past this offset.
+const TAPE_MAX_OFFSET: i32 = 10_000;
+
+#[derive(Args)]
+pub struct TradesArgs {
+    #[command(subcommand)]
+    pub command: TradesCommand,
+}
+
+#[derive(Subcommand)]
+pub enum TradesCommand {
+    /// List your fill history (authenticated)
+    List {
+        /// Filter by market condition ID
+        #[arg(long)]
+        market: Option<String>,
+        /// Filter by asset/token ID
+        #[arg(long)]
+        asset: Option<String>,
+        /// Only fills on or after this date (YYYY-MM-DD, UTC)
+        #[arg(long)]
+        since: Option<String>,
+        /// Only fills on or before this date (YYYY-MM-DD, UTC)
+        #[arg(long)]
+        until: Option<String>,
+        /// Output format (defaults to --output)
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+    /// Download a market's public trade tape, oldest first
+    Market {
+        /// Market condition ID (0x-prefixed)
+        condition_id: String,
+        /// Only trades on or after this date (YYYY-MM-DD, UTC)
+        #[arg(long)]
+        from: Option<String>,
+        /// Only trades on or before this date (YYYY-MM-DD, UTC)
+        #[arg(long)]
+        to: Option<String>,
+        /// Output format (defaults to --output)
+        #[arg(long)]
+        format: Option<OutputFormat>,
+    },
+}
+
+/// Converts an inclusive `since`/`until` day range into the `after`/`before`
+/// unix timestamps the CLOB trades endpoint expects.
+fn date_range(
+    since: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+) -> Result<(Option<i64>, Option<i64>)> {
+    if let (Some(since), Some(until)) = (since, until)
+        && since > until
+    {
+        anyhow::bail!("--since must not be after --until");
+    }
+    let start_of = |d: NaiveDate| d.and_hms_opt(0, 0, 0).map(|t| t.and_utc().timestamp());
+    let after = since.and_then(start_of);
+    let before = until
+        .and_then(|d| d.succ_opt())
+        .and_then(start_of)
+        .map(|t| t - 1);
+    Ok((after, before))
+}
+
+/// `trades` between `after` and `before` (inclusive), oldest first. Pages
+/// fetched by offset overlap when new trades arrive mid-download, so
+/// repeats are dropped.
+fn tape(mut trades: Vec<Trade>, after: Option<i64>, before: Option<i64>) -> Vec<Trade> {
+    trades.retain(|t| {
+        after.is_none_or(|a| t.timestamp >= a) && before.is_none_or(|b| t.timestamp <= b)
+    });
+    trades.sort_by_key(|t| t.timestamp);
+    let mut seen = std::collections::HashSet::new();
+    trades.retain(|t| {
+        seen.insert((
+            t.transaction_hash,
+            t.proxy_wallet,
+            t.asset,
+            t.side.to_string(),
+            t.size,
+            t.price,
+        ))
+    });
+    trades
+}
+
+pub async fn execute(
+    args: TradesArgs,
+    output: OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    match args.command {
+        TradesCommand::List {
+            market,
+            asset,
+            since,
+            until,
+            format,
+        } => {
+            let (after, before) = date_range(
+                since.as_deref().map(parse_date).transpose()?,
+                until.as_deref().map(parse_date).transpose()?,
+            )?;
+            let request = TradesRequest::builder()
+                .maybe_market(market.map(|m| parse_condition_id(&m)).transpose()?)
+                .maybe_asset_id(asset.map(|a| parse_token_id(&a)).transpose()?)
+                .maybe_after(after)
+                .maybe_before(before)
+                .build();
+
+            let client = auth::authenticated_clob_client(private_key, signature_type).await?;
+            let mut trades = Vec::new();
+            let mut cursor = None;
+            loop {
+                let page = net::retry(|| client.trades(&request, cursor.clone())).await?;
+                trades.extend(page.data);
+                if page.next_cursor == END_CURSOR || page.next_cursor.is_empty() {
+                    break;
+                }
+                cursor = Some(page.next_cursor);
+            }
+            trades.sort_by_key(|t| t.match_time);
+
+            print_trade_history(&trades, &format.unwrap_or(output))?;
+        }
+        TradesCommand::Market {
+            condition_id,
+            from,
+            to,
+            format,
+        } => {
+            let from = from.as_deref().map(parse_date).transpose()?;
+            let to = to.as_deref().map(parse_date).transpose()?;
+            if let (Some(from), Some(to)) = (from, to) {
+                anyhow::ensure!(from <= to, "--from must not be after --to");
+            }
+            let (after, before) = date_range(from, to)?;
+            let market = parse_condition_id(&condition_id)?;
+
+            let client = clients::data()?;
+            let mut trades = Vec::new();
+            let mut offset = 0;
+            loop {
+                let request = data::types::request::TradesRequest::builder()
+                    .filter(MarketFilter::markets([market]))
+                    .limit(TAPE_PAGE_SIZE)?
+                    .offset(offset)?
+                    .build();
+                let page = net::retry(|| client.trades(&request)).await?;
+                let len = page.len();
+                // Newest first, so a page that reaches back past `after`
+                // is the last one needed.
+                let reached_start = after.is_some_and(|a| page.iter().any(|t| t.timestamp < a));
+                trades.extend(page);
+                if reached_start || len < TAPE_PAGE_SIZE as usize {
+                    break;
+                }
+                offset += TAPE_PAGE_SIZE;
+                if offset > TAPE_MAX_OFFSET {
+                    eprintln!(
+                        "Stopped at the Data API's paging limit; trades older than the \
+                         most recent {TAPE_MAX_OFFSET} are not included."
+                    );
+                    break;
+                }
+            }
+
+            print_trades(&tape(trades, after, before), &format.unwrap_or(output))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(s: &str) -> NaiveDate {
+        parse_date(s).unwrap()
+    }
+
+    #[test]
+    fn date_range_open_ended() {
+        assert_eq!(date_range(None, None).unwrap(), (None, None));
+    }
+
+    #[test]
+    fn date_range_until_is_inclusive() {
+        let (after, before) = date_range(Some(day("2024-01-01")), Some(day("2024-01-01"))).unwrap();
+        assert_eq!(after, Some(1_704_067_200));
+        assert_eq!(before, Some(1_704_067_200 + 86_399));
+    }
+
+    fn trade(timestamp: i64, tx: u8) -> Trade {
+        serde_json::from_value(serde_json::json!({
+            "proxyWallet": "0x0000000000000000000000000000000000000001",
+            "side": "BUY",
+            "asset": "1",
+            "conditionId": "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "size": "10",
+            "price": "0.5",
+            "timestamp": timestamp,
+            "title": "Will it rain?",
+            "slug": "rain",
+            "icon": "",
+            "eventSlug": "rain",
+            "outcome": "Yes",
+            "outcomeIndex": 0,
+            "transactionHash": format!("0x{tx:064x}"),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn tape_is_oldest_first_within_range_without_repeats() {
+        // Pages arrive newest first; a trade landing mid-download shifts
+        // the second page so its first entry repeats.
+        let trades = vec![trade(300, 3), trade(200, 2), trade(200, 2), trade(100, 1)];
+        let tape = tape(trades, Some(150), Some(300));
+        let times: Vec<i64> = tape.iter().map(|t| t.timestamp).collect();
+        assert_eq!(times, [200, 300]);
+    }
+
+    #[test]
+    fn date_range_rejects_inverted_range() {
+        let err = date_range(Some(day("2024-02-01")), Some(day("2024-01-01")))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("--since"), "got: {err}");
+    }
+}
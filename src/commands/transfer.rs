@@ -0,0 +1,91 @@
+use anyhow::{Context, Result};
+use clap::Args;
+use clap_complete::engine::ArgValueCandidates;
+
+use super::approve::{IERC20, USDC_ADDRESS};
+use super::completions::contact_candidates;
+use super::ctf::parse_usdc_amount;
+use crate::auth;
+use crate::confirm;
+use crate::gas::{GasFlags, parse_gwei};
+use crate::output::OutputFormat;
+use crate::output::transfer::{TransferResult, print_transfer};
+
+#[derive(Args)]
+pub struct TransferArgs {
+    /// Recipient address (0x...) or saved contact name
+    #[arg(add = ArgValueCandidates::new(contact_candidates))]
+    pub to: String,
+    /// Amount in USDC (e.g. 25.5)
+    pub amount: String,
+    /// Legacy gas price in gwei, instead of EIP-1559 fees
+    #[arg(long, conflicts_with_all = ["max_fee", "priority_fee"])]
+    pub gas_price: Option<String>,
+    /// Nonce to use (default: next pending nonce)
+    #[arg(long, conflicts_with = "replace")]
+    pub nonce: Option<u64>,
+    /// Wait for the transaction to be mined before returning
+    #[arg(long)]
+    pub wait: bool,
+    #[command(flatten)]
+    pub gas: GasFlags,
+}
+
+pub async fn execute(
+    args: TransferArgs,
+    output: &OutputFormat,
+    private_key: Option<&str>,
+) -> Result<()> {
+    let to = super::resolve_address(&args.to)?;
+    let amount = parse_usdc_amount(&args.amount)?;
+    let gas_price = args
+        .gas_price
+        .as_deref()
+        .map(|s| parse_gwei(s, "gas price"))
+        .transpose()?;
+    let gas = args.gas.settings()?;
+    if !confirm::ask(
+        &format!("Send {} USDC to {to}?", args.amount.trim()),
+        output,
+    )? {
+        return Ok(());
+    }
+
+    let provider = auth::create_provider(private_key, gas).await?;
+    let usdc = IERC20::new(USDC_ADDRESS, provider);
+
+    let mut call = usdc.transfer(to, amount);
+    if let Some(gas_price) = gas_price {
+        call = call.gas_price(gas_price);
+    }
+    if let Some(nonce) = args.nonce {
+        call = call.nonce(nonce);
+    }
+    let pending = call.send().await.context("Failed to send USDC transfer")?;
+    let tx_hash = *pending.tx_hash();
+
+    let block_number = if args.wait {
+        let receipt = pending
+            .get_receipt()
+            .await
+            .context("Failed to confirm USDC transfer")?;
+        anyhow::ensure!(
+            receipt.status(),
+            "USDC transfer {tx_hash} reverted in block {}",
+            receipt.block_number.unwrap_or_default()
+        );
+        receipt.block_number
+    } else {
+        None
+    };
+
+    print_transfer(
+        &TransferResult {
+            to,
+            amount: args.amount.trim().parse()?,
+            tx_hash,
+            block_number,
+        },
+        output,
+    )
+}
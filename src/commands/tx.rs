@@ -0,0 +1,347 @@
+//! `tx`: the state of a transaction on Polygon, speeding up a stuck one by
+//! resending it with the same nonce and higher fees, and the journal of
+//! transactions the CLI has signed.
+
+use std::str::FromStr;
+
+use alloy::network::{Ethereum, Network, TransactionBuilder, TransactionResponse as _};
+use alloy::primitives::{B256, TxKind, U256};
+use alloy::providers::Provider;
+use alloy::signers::Signer as _;
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use polymarket_client_sdk::types::Decimal;
+
+use super::balance::{POL_DECIMALS, to_decimal};
+use super::gas::gwei;
+use crate::auth;
+use crate::config::{self, SentTx};
+use crate::confirm;
+use crate::errors;
+use crate::gas::GasFlags;
+use crate::net;
+use crate::nonces;
+use crate::output::OutputFormat;
+use crate::output::tx::{
+    SpeedupResult, TxState, TxStatus, print_speedup, print_transactions, print_tx_status,
+};
+
+#[derive(Args)]
+pub struct TxArgs {
+    #[command(subcommand)]
+    pub command: TxCommand,
+}
+
+#[derive(Subcommand)]
+pub enum TxCommand {
+    /// Show whether a transaction is pending, mined, or was replaced
+    Status {
+        /// Transaction hash (0x...)
+        hash: String,
+    },
+    /// Resend a pending transaction with the same nonce and higher fees
+    Speedup {
+        /// Hash of the pending transaction (0x...)
+        hash: String,
+        /// Max fee per unit of gas in gwei (default: estimated, and at least
+        /// an eighth over the original)
+        #[arg(long)]
+        max_fee: Option<String>,
+        /// Priority fee per unit of gas in gwei (default: estimated, and at
+        /// least an eighth over the original)
+        #[arg(long)]
+        priority_fee: Option<String>,
+        /// Headroom on the estimated base fee
+        #[arg(long, default_value = "1.2")]
+        gas_multiplier: Decimal,
+    },
+    /// Transactions the CLI has signed, newest first
+    #[command(visible_alias = "ls")]
+    List {
+        /// How many to show
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+}
+
+pub async fn execute(args: TxArgs, output: &OutputFormat, private_key: Option<&str>) -> Result<()> {
+    match args.command {
+        TxCommand::Status { hash } => status(parse_hash(&hash)?, output).await,
+        TxCommand::Speedup {
+            hash,
+            max_fee,
+            priority_fee,
+            gas_multiplier,
+        } => {
+            let flags = GasFlags {
+                gas_limit: None,
+                max_fee,
+                priority_fee,
+                gas_multiplier,
+                replace: false,
+            };
+            speedup(parse_hash(&hash)?, &flags, output, private_key).await
+        }
+        TxCommand::List { limit } => {
+            let mut journal = config::load_transactions()?;
+            journal.reverse();
+            journal.truncate(limit);
+            print_transactions(&journal, output)
+        }
+    }
+}
+
+fn parse_hash(s: &str) -> Result<B256> {
+    B256::from_str(s.trim())
+        .map_err(|_| errors::validation(format!("Invalid transaction hash: {s}")))
+}
+
+async fn status(hash: B256, output: &OutputFormat) -> Result<()> {
+    let provider = auth::create_readonly_provider().await?;
+    let (tx, receipt) = tokio::try_join!(
+        async {
+            provider
+                .get_transaction_by_hash(hash)
+                .await
+                .context("Failed to fetch transaction")
+        },
+        async {
+            provider
+                .get_transaction_receipt(hash)
+                .await
+                .context("Failed to fetch transaction receipt")
+        },
+    )?;
+    let mut status = TxStatus {
+        hash,
+        state: TxState::Pending,
+        from: None,
+        to: None,
+        nonce: None,
+        max_fee: None,
+        priority_fee: None,
+        block: None,
+        confirmations: None,
+        gas_used: None,
+        fee_paid: None,
+    };
+    let sent = match &tx {
+        Some(tx) => Some(nonces::entry(tx.from(), tx.inner.inner())),
+        None => nonces::find(hash)?,
+    };
+    if let Some(sent) = &sent {
+        status.from = Some(sent.from);
+        status.to = sent.to;
+        status.nonce = Some(sent.nonce);
+        status.max_fee = sent.max_fee.or(sent.gas_price).map(gwei).transpose()?;
+        status.priority_fee = sent.priority_fee.map(gwei).transpose()?;
+    }
+
+    if let Some(receipt) = receipt {
+        let block = receipt.block_number.unwrap_or_default();
+        let latest = provider
+            .get_block_number()
+            .await
+            .context("Failed to fetch the latest block")?;
+        status.state = if receipt.status() {
+            TxState::Confirmed
+        } else {
+            TxState::Reverted
+        };
+        status.from = Some(receipt.from);
+        status.to = receipt.to;
+        status.block = Some(block);
+        status.confirmations = Some(latest.saturating_sub(block) + 1);
+        status.gas_used = Some(receipt.gas_used);
+        status.fee_paid = Some(to_decimal(
+            U256::from(receipt.gas_used) * U256::from(receipt.effective_gas_price),
+            POL_DECIMALS,
+        )?);
+    } else if tx.is_none() {
+        // The node doesn't know it: either another transaction took its
+        // nonce, or it never made it into the mempool.
+        let Some(sent) = &sent else {
+            anyhow::bail!(errors::validation(format!(
+                "Transaction {hash} not found on Polygon"
+            )));
+        };
+        let mined = provider
+            .get_transaction_count(sent.from)
+            .latest()
+            .await
+            .context("Failed to fetch the wallet's nonce")?;
+        status.state = if mined > sent.nonce {
+            TxState::Replaced
+        } else {
+            TxState::Dropped
+        };
+    }
+    print_tx_status(&status, output)
+}
+
+async fn speedup(
+    hash: B256,
+    flags: &GasFlags,
+    output: &OutputFormat,
+    private_key: Option<&str>,
+) -> Result<()> {
+    let settings = flags.settings()?;
+    let signer = auth::resolve_signer(private_key).await?;
+    let wallet = signer.address();
+    let provider = auth::provider_with_signer(signer, settings).await?;
+
+    let (tx, receipt) = tokio::try_join!(
+        async {
+            provider
+                .get_transaction_by_hash(hash)
+                .await
+                .context("Failed to fetch transaction")
+        },
+        async {
+            provider
+                .get_transaction_receipt(hash)
+                .await
+                .context("Failed to fetch transaction receipt")
+        },
+    )?;
+    if let Some(receipt) = receipt {
+        anyhow::bail!(errors::validation(format!(
+            "Transaction {hash} was already mined in block {}",
+            receipt.block_number.unwrap_or_default()
+        )));
+    }
+    // A transaction the node dropped can still be resent from the journal.
+    let original = match tx {
+        Some(tx) => nonces::entry(tx.from(), tx.inner.inner()),
+        None => nonces::find(hash)?.ok_or_else(|| {
+            errors::validation(format!("Transaction {hash} not found on Polygon"))
+        })?,
+    };
+    anyhow::ensure!(
+        original.from == wallet,
+        errors::auth(format!(
+            "Transaction {hash} was sent by {}, not this wallet ({wallet})",
+            original.from
+        ))
+    );
+    let mined = provider
+        .get_transaction_count(wallet)
+        .latest()
+        .await
+        .context("Failed to fetch the wallet's nonce")?;
+    anyhow::ensure!(
+        mined <= original.nonce,
+        errors::validation(format!(
+            "Nonce {} was already used by another transaction; {hash} was replaced",
+            original.nonce
+        ))
+    );
+
+    let estimate = provider
+        .estimate_eip1559_fees()
+        .await
+        .context("Failed to estimate EIP-1559 fees")?;
+    let (max_fee, priority_fee) = replacement_fees(
+        &original,
+        settings.fees(estimate.max_fee_per_gas, estimate.max_priority_fee_per_gas),
+        (settings.max_fee.is_some(), settings.priority_fee.is_some()),
+    );
+
+    if !confirm::confirm(
+        &format!(
+            "Replace {hash} (nonce {} from {wallet}) paying up to {} gwei, tip {} gwei",
+            original.nonce,
+            gwei(max_fee)?,
+            gwei(priority_fee)?
+        ),
+        "Send it?",
+        output,
+    )? {
+        return Ok(());
+    }
+    let request = <Ethereum as Network>::TransactionRequest::default()
+        .with_from(wallet)
+        .with_kind(original.to.map_or(TxKind::Create, TxKind::Call))
+        .with_chain_id(original.chain_id)
+        .with_nonce(original.nonce)
+        .with_value(original.value)
+        .with_input(original.input.clone())
+        .with_gas_limit(original.gas_limit)
+        .with_max_fee_per_gas(max_fee)
+        .with_max_priority_fee_per_gas(priority_fee);
+    let pending = net::once(provider.send_transaction(request))
+        .await
+        .context("Failed to send the replacement transaction")?;
+
+    print_speedup(
+        &SpeedupResult {
+            replaced: hash,
+            hash: *pending.tx_hash(),
+            nonce: original.nonce,
+            max_fee: gwei(max_fee)?,
+            priority_fee: gwei(priority_fee)?,
+        },
+        output,
+    )
+}
+
+/// The fees for a replacement of `original`: `estimated`, raised to outbid
+/// it unless the fee was `given`.
+fn replacement_fees(
+    original: &SentTx,
+    (mut max_fee, mut priority_fee): (u128, u128),
+    (max_given, priority_given): (bool, bool),
+) -> (u128, u128) {
+    let (min_max, min_priority) = nonces::outbid(original);
+    if !priority_given {
+        priority_fee = priority_fee.max(min_priority);
+    }
+    if !max_given {
+        max_fee = max_fee.max(min_max).max(priority_fee);
+    }
+    (max_fee, priority_fee)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{Address, Bytes};
+
+    use super::*;
+
+    const GWEI: u128 = 1_000_000_000;
+
+    #[test]
+    fn replacement_outbids_unless_fees_are_given() {
+        let original = SentTx {
+            hash: B256::ZERO,
+            chain_id: 137,
+            from: Address::ZERO,
+            nonce: 3,
+            to: None,
+            value: U256::ZERO,
+            input: Bytes::new(),
+            gas_limit: 21_000,
+            max_fee: Some(80 * GWEI),
+            priority_fee: Some(40 * GWEI),
+            gas_price: None,
+            sent_at: chrono::Utc::now(),
+        };
+        let low = (60 * GWEI, 30 * GWEI);
+        assert_eq!(
+            replacement_fees(&original, low, (false, false)),
+            (90 * GWEI + 1, 45 * GWEI + 1)
+        );
+        let high = (200 * GWEI, 50 * GWEI);
+        assert_eq!(replacement_fees(&original, high, (false, false)), high);
+        assert_eq!(replacement_fees(&original, low, (true, true)), low);
+    }
+
+    #[test]
+    fn parse_hash_rejects_addresses() {
+        assert!(parse_hash("0x1234567890abcdef1234567890abcdef12345678").is_err());
+        assert!(
+            parse_hash("0x0000000000000000000000000000000000000000000000000000000000000001")
+                .is_ok()
+        );
+    }
+}
@@ -1,14 +1,20 @@
 use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use anyhow::{Context, Result, bail};
-use clap::{Args, Subcommand};
+use clap::{Args, Subcommand, ValueEnum};
+use clap_complete::engine::ArgValueCandidates;
 use polymarket_client_sdk::auth::LocalSigner;
 use polymarket_client_sdk::auth::Signer as _;
-use polymarket_client_sdk::{POLYGON, derive_safe_wallet};
+use polymarket_client_sdk::types::Address;
+use polymarket_client_sdk::{POLYGON, derive_proxy_wallet, derive_safe_wallet};
 
-use crate::config;
-use crate::output::OutputFormat;
+use super::completions::profile_candidates;
+use crate::config::{self, KeySource};
+use crate::confirm;
+use crate::errors;
+use crate::output::{OutputFormat, print_json_line};
 
 #[derive(Args)]
 pub struct WalletArgs {
@@ -20,37 +26,90 @@ pub struct WalletArgs {
 pub enum WalletCommand {
     /// Generate a new random wallet and save to config
     Create {
-        /// Overwrite existing wallet
-        #[arg(long)]
+        /// Replace an existing wallet without asking (same as --yes)
+        #[arg(long, hide = true)]
         force: bool,
         /// Signature type: eoa, proxy, or gnosis-safe (default)
         #[arg(long, default_value = "gnosis-safe")]
         signature_type: String,
+        /// Store the key in a passphrase-encrypted keystore (same as
+        /// --storage keystore)
+        #[arg(long)]
+        encrypt: bool,
+        /// Where to store the key
+        #[arg(long, value_enum, conflicts_with = "encrypt")]
+        storage: Option<KeyStorage>,
     },
-    /// Import an existing private key
+    /// Import an existing private key, or an address to watch
     Import {
-        /// Private key (hex, with or without 0x prefix)
+        /// Private key (hex, with or without 0x prefix), or with --watch-only
+        /// the address (or contact name) to follow
         key: String,
-        /// Overwrite existing wallet
-        #[arg(long)]
+        /// Replace an existing wallet without asking (same as --yes)
+        #[arg(long, hide = true)]
         force: bool,
         /// Signature type: eoa, proxy, or gnosis-safe (default)
         #[arg(long, default_value = "gnosis-safe")]
         signature_type: String,
+        /// Store the key in a passphrase-encrypted keystore (same as
+        /// --storage keystore)
+        #[arg(long)]
+        encrypt: bool,
+        /// Where to store the key
+        #[arg(long, value_enum, conflicts_with = "encrypt")]
+        storage: Option<KeyStorage>,
+        /// Follow an address's positions and PnL without storing a key
+        #[arg(long, conflicts_with_all = ["encrypt", "storage"])]
+        watch_only: bool,
+    },
+    /// Encrypt the plaintext key in the config with a passphrase
+    Encrypt,
+    /// Decrypt the keystore back to a plaintext key in the config
+    Decrypt,
+    /// Print the private key or write it to a new encrypted keystore file
+    #[command(group(clap::ArgGroup::new("export_to").required(true).args(["show_private_key", "keystore"])))]
+    Export {
+        /// Print the private key
+        #[arg(long)]
+        show_private_key: bool,
+        /// Write an encrypted keystore file to this path
+        #[arg(long)]
+        keystore: Option<PathBuf>,
     },
     /// Show the address of the configured wallet
     Address,
     /// Show wallet info (address, config path, key source)
     Show,
-    /// Delete all config and keys (fresh install)
+    /// Trade for a proxy or Safe wallet created on polymarket.com
+    LinkProxy {
+        /// Proxy wallet address (shown on polymarket.com under your profile) or contact name
+        #[arg(required_unless_present = "unlink")]
+        address: Option<String>,
+        /// Signature type the proxy uses: proxy (email/magic login) or
+        /// gnosis-safe (browser wallet login)
+        #[arg(long, value_parser = ["proxy", "gnosis-safe"])]
+        signature_type: Option<String>,
+        /// Remove the link and go back to the derived proxy wallet
+        #[arg(long, conflicts_with_all = ["address", "signature_type"])]
+        unlink: bool,
+    },
+    /// List wallet profiles
+    List,
+    /// Set the default wallet profile
+    Use {
+        /// Profile name
+        #[arg(add = ArgValueCandidates::new(profile_candidates))]
+        profile: String,
+    },
+    /// Delete all profiles, keys and config (fresh install)
     Reset {
-        /// Skip confirmation prompt
-        #[arg(long)]
+        /// Delete without asking (same as --yes)
+        #[arg(long, hide = true)]
         force: bool,
     },
 }
 
-pub fn execute(
+pub async fn execute(
     args: WalletArgs,
     output: &OutputFormat,
     private_key_flag: Option<&str>,
@@ -59,26 +118,78 @@ pub fn execute(
         WalletCommand::Create {
             force,
             signature_type,
-        } => cmd_create(output, force, &signature_type),
+            encrypt,
+            storage,
+        } => cmd_create(
+            output,
+            force,
+            &signature_type,
+            KeyStorage::chosen(encrypt, storage),
+        ),
         WalletCommand::Import {
             key,
             force,
+            watch_only: true,
+            ..
+        } => cmd_import_watch_only(&key, output, force),
+        WalletCommand::Import {
+            key,
+            force,
+            signature_type,
+            encrypt,
+            storage,
+            watch_only: false,
+        } => cmd_import(
+            &key,
+            output,
+            force,
+            &signature_type,
+            KeyStorage::chosen(encrypt, storage),
+        ),
+        WalletCommand::Encrypt => cmd_encrypt(output),
+        WalletCommand::Decrypt => cmd_decrypt(output),
+        WalletCommand::Export {
+            show_private_key,
+            keystore,
+        } => cmd_export(
+            output,
+            private_key_flag,
+            show_private_key,
+            keystore.as_deref(),
+        ),
+        WalletCommand::Address => cmd_address(output, private_key_flag).await,
+        WalletCommand::Show => cmd_show(output, private_key_flag).await,
+        WalletCommand::LinkProxy {
+            address,
             signature_type,
-        } => cmd_import(&key, output, force, &signature_type),
-        WalletCommand::Address => cmd_address(output, private_key_flag),
-        WalletCommand::Show => cmd_show(output, private_key_flag),
+            unlink,
+        } => cmd_link_proxy(
+            output,
+            address.as_deref(),
+            signature_type.as_deref(),
+            unlink,
+        ),
+        WalletCommand::List => cmd_list(output),
+        WalletCommand::Use { profile } => cmd_use(output, &profile),
         WalletCommand::Reset { force } => cmd_reset(output, force),
     }
 }
 
-fn guard_overwrite(force: bool) -> Result<()> {
-    if !force && config::config_exists() {
-        bail!(
-            "A wallet already exists at {}. Use --force to overwrite.",
-            config::config_path()?.display()
-        );
+/// Asks before the profile's wallet is replaced. `--force` predates the
+/// global `--yes` and is still accepted in its place.
+fn confirm_overwrite(force: bool, output: &OutputFormat) -> Result<bool> {
+    if force || !config::load_config().is_some_and(|c| c.has_wallet()) {
+        return Ok(true);
     }
-    Ok(())
+    confirm::ask(
+        &format!(
+            "A wallet already exists for profile '{}' at {}. Replace it? Its key is lost unless \
+             you have a backup.",
+            config::active_profile(),
+            config::config_path()?.display()
+        ),
+        output,
+    )
 }
 
 pub(crate) fn normalize_key(key: &str) -> String {
@@ -89,8 +200,116 @@ pub(crate) fn normalize_key(key: &str) -> String {
     }
 }
 
-fn cmd_create(output: &OutputFormat, force: bool, signature_type: &str) -> Result<()> {
-    guard_overwrite(force)?;
+/// Where `wallet create` and `wallet import` put the key.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum KeyStorage {
+    /// In plaintext in the profile's config file
+    Config,
+    /// In a passphrase-encrypted keystore next to the config
+    Keystore,
+    /// In the OS keyring: macOS Keychain or the Linux Secret Service
+    Keyring,
+}
+
+impl KeyStorage {
+    /// `--storage`, or `--encrypt` from before it existed.
+    fn chosen(encrypt: bool, storage: Option<Self>) -> Self {
+        match storage {
+            Some(storage) => storage,
+            None if encrypt => Self::Keystore,
+            None => Self::Config,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Config => "config",
+            Self::Keystore => "keystore",
+            Self::Keyring => "keyring",
+        }
+    }
+}
+
+fn store_key(key: &str, signature_type: &str, storage: KeyStorage) -> Result<()> {
+    match storage {
+        KeyStorage::Config => config::save_wallet(key, POLYGON, signature_type),
+        KeyStorage::Keystore => {
+            let passphrase = config::read_new_passphrase()?;
+            config::save_encrypted_wallet(key, POLYGON, signature_type, &passphrase)
+        }
+        KeyStorage::Keyring => config::save_keyring_wallet(key, POLYGON, signature_type),
+    }
+}
+
+/// Resolves the wallet address. Encrypted wallets store their address in the
+/// config, so this never prompts for a passphrase; a Ledger is asked for it.
+pub(crate) async fn resolve_address(
+    private_key_flag: Option<&str>,
+) -> Result<(Option<Address>, KeySource)> {
+    let source = config::key_source(private_key_flag);
+    if matches!(source, KeySource::WatchOnly) {
+        return Ok((config::watch_only_address()?, source));
+    }
+    if matches!(source, KeySource::Ledger) {
+        let signer = crate::auth::resolve_signer(private_key_flag).await?;
+        return Ok((Some(signer.address()), source));
+    }
+    if matches!(source, KeySource::Keystore | KeySource::Keyring) {
+        let address = config::load_config()
+            .and_then(|c| c.address)
+            .map(|a| super::parse_address(&a))
+            .transpose()?;
+        return Ok((address, source));
+    }
+
+    let (key, source) = config::resolve_key(private_key_flag)?;
+    let address = key
+        .map(|k| {
+            LocalSigner::from_str(&k)
+                .context("Invalid private key")
+                .map(|s| s.address())
+        })
+        .transpose()?;
+    Ok((address, source))
+}
+
+/// Address that holds the wallet's funds and positions: the followed address
+/// of a watch-only wallet, the EOA itself for `eoa`, otherwise the linked
+/// (see `config::resolve_funder`) or derived proxy or Safe wallet.
+pub(crate) async fn resolve_trading_address(
+    private_key_flag: Option<&str>,
+    signature_type_flag: Option<&str>,
+) -> Result<Address> {
+    if matches!(config::key_source(private_key_flag), KeySource::WatchOnly)
+        && let Some(address) = config::watch_only_address()?
+    {
+        return Ok(address);
+    }
+    let signature_type = config::resolve_signature_type(signature_type_flag);
+    if signature_type != "eoa"
+        && let Some(funder) = config::resolve_funder()?
+    {
+        return Ok(funder);
+    }
+    let (address, _) = resolve_address(private_key_flag).await?;
+    let address = address.ok_or_else(|| errors::auth(config::NO_WALLET_MSG))?;
+    let derived = match signature_type.as_str() {
+        "eoa" => return Ok(address),
+        "proxy" => derive_proxy_wallet(address, POLYGON),
+        _ => derive_safe_wallet(address, POLYGON),
+    };
+    derived.context("Could not derive proxy wallet address")
+}
+
+fn cmd_create(
+    output: &OutputFormat,
+    force: bool,
+    signature_type: &str,
+    storage: KeyStorage,
+) -> Result<()> {
+    if !confirm_overwrite(force, output)? {
+        return Ok(());
+    }
 
     let signer = LocalSigner::random().with_chain_id(Some(POLYGON));
     let address = signer.address();
@@ -101,40 +320,62 @@ fn cmd_create(output: &OutputFormat, force: bool, signature_type: &str) -> Resul
         write!(key_hex, "{b:02x}").unwrap();
     }
 
-    config::save_wallet(&key_hex, POLYGON, signature_type)?;
+    store_key(&key_hex, signature_type, storage)?;
     let config_path = config::config_path()?;
     let proxy_addr = derive_safe_wallet(address, POLYGON);
 
     match output {
         OutputFormat::Json => {
-            println!(
-                "{}",
-                serde_json::json!({
-                    "address": address.to_string(),
-                    "proxy_address": proxy_addr.map(|a| a.to_string()),
-                    "signature_type": signature_type,
-                    "config_path": config_path.display().to_string(),
-                })
-            );
+            print_json_line(serde_json::json!({
+                "address": address.to_string(),
+                "proxy_address": proxy_addr.map(|a| a.to_string()),
+                "signature_type": signature_type,
+                "profile": config::active_profile(),
+                "config_path": config_path.display().to_string(),
+                "encrypted": storage == KeyStorage::Keystore,
+                "storage": storage.name(),
+            }));
         }
-        OutputFormat::Table => {
+        OutputFormat::Plain => println!("{address}"),
+        OutputFormat::Table | OutputFormat::Csv => {
             println!("Wallet created successfully!");
             println!("Address:        {address}");
             if let Some(proxy) = proxy_addr {
                 println!("Proxy wallet:   {proxy}");
             }
             println!("Signature type: {signature_type}");
+            println!("Profile:        {}", config::active_profile());
             println!("Config:         {}", config_path.display());
             println!();
-            println!("IMPORTANT: Back up your private key from the config file.");
+            if storage == KeyStorage::Keystore {
+                println!(
+                    "IMPORTANT: Back up {} and your passphrase.",
+                    config::keystore_path()?.display()
+                );
+            } else if storage == KeyStorage::Keyring {
+                println!(
+                    "IMPORTANT: The key is only in the OS keyring. Back it up with \
+                     `polymarket wallet export`."
+                );
+            } else {
+                println!("IMPORTANT: Back up your private key from the config file.");
+            }
             println!("           If lost, your funds cannot be recovered.");
         }
     }
     Ok(())
 }
 
-fn cmd_import(key: &str, output: &OutputFormat, force: bool, signature_type: &str) -> Result<()> {
-    guard_overwrite(force)?;
+fn cmd_import(
+    key: &str,
+    output: &OutputFormat,
+    force: bool,
+    signature_type: &str,
+    storage: KeyStorage,
+) -> Result<()> {
+    if !confirm_overwrite(force, output)? {
+        return Ok(());
+    }
 
     let normalized = normalize_key(key);
     let signer = LocalSigner::from_str(&normalized)
@@ -142,137 +383,424 @@ fn cmd_import(key: &str, output: &OutputFormat, force: bool, signature_type: &st
         .with_chain_id(Some(POLYGON));
     let address = signer.address();
 
-    config::save_wallet(&normalized, POLYGON, signature_type)?;
+    store_key(&normalized, signature_type, storage)?;
     let config_path = config::config_path()?;
     let proxy_addr = derive_safe_wallet(address, POLYGON);
 
     match output {
         OutputFormat::Json => {
-            println!(
-                "{}",
-                serde_json::json!({
-                    "address": address.to_string(),
-                    "proxy_address": proxy_addr.map(|a| a.to_string()),
-                    "signature_type": signature_type,
-                    "config_path": config_path.display().to_string(),
-                })
-            );
+            print_json_line(serde_json::json!({
+                "address": address.to_string(),
+                "proxy_address": proxy_addr.map(|a| a.to_string()),
+                "signature_type": signature_type,
+                "profile": config::active_profile(),
+                "config_path": config_path.display().to_string(),
+                "encrypted": storage == KeyStorage::Keystore,
+                "storage": storage.name(),
+            }));
         }
-        OutputFormat::Table => {
+        OutputFormat::Plain => println!("{address}"),
+        OutputFormat::Table | OutputFormat::Csv => {
             println!("Wallet imported successfully!");
             println!("Address:        {address}");
             if let Some(proxy) = proxy_addr {
                 println!("Proxy wallet:   {proxy}");
             }
             println!("Signature type: {signature_type}");
+            println!("Profile:        {}", config::active_profile());
             println!("Config:         {}", config_path.display());
         }
     }
     Ok(())
 }
 
-fn cmd_address(output: &OutputFormat, private_key_flag: Option<&str>) -> Result<()> {
-    let (key, _) = config::resolve_key(private_key_flag);
-    let key = key.ok_or_else(|| anyhow::anyhow!("{}", config::NO_WALLET_MSG))?;
+fn cmd_import_watch_only(address: &str, output: &OutputFormat, force: bool) -> Result<()> {
+    if !confirm_overwrite(force, output)? {
+        return Ok(());
+    }
+    let address = super::resolve_address(address)?;
+    config::save_watch_only(address)?;
+    let config_path = config::config_path()?;
+
+    match output {
+        OutputFormat::Json => {
+            print_json_line(serde_json::json!({
+                "address": address.to_string(),
+                "watch_only": true,
+                "profile": config::active_profile(),
+                "config_path": config_path.display().to_string(),
+            }));
+        }
+        OutputFormat::Plain => println!("{address}"),
+        OutputFormat::Table | OutputFormat::Csv => {
+            println!("Watching {address}. No key is stored, so this profile can't sign.");
+            println!("Profile:        {}", config::active_profile());
+            println!("Config:         {}", config_path.display());
+        }
+    }
+    Ok(())
+}
+
+fn cmd_encrypt(output: &OutputFormat) -> Result<()> {
+    let cfg = config::load_config().ok_or_else(|| errors::auth(config::NO_WALLET_MSG))?;
+    anyhow::ensure!(!cfg.watch_only, errors::auth(config::WATCH_ONLY_MSG));
+    if cfg.is_encrypted() {
+        bail!("Wallet is already encrypted.");
+    }
+    if cfg.keyring {
+        bail!("Wallet is stored in the OS keyring.");
+    }
+
+    let passphrase = config::read_new_passphrase()?;
+    config::save_encrypted_wallet(
+        &cfg.private_key,
+        cfg.chain_id,
+        &cfg.signature_type,
+        &passphrase,
+    )?;
+    let keystore_path = config::keystore_path()?;
+
+    match output {
+        OutputFormat::Json => {
+            print_json_line(serde_json::json!({
+                "encrypted": true,
+                "keystore_path": keystore_path.display().to_string(),
+            }));
+        }
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+            println!("Wallet encrypted.");
+            println!("Keystore: {}", keystore_path.display());
+        }
+    }
+    Ok(())
+}
+
+fn cmd_decrypt(output: &OutputFormat) -> Result<()> {
+    let cfg = config::load_config().ok_or_else(|| errors::auth(config::NO_WALLET_MSG))?;
+    if !cfg.is_encrypted() {
+        bail!("Wallet is not encrypted.");
+    }
 
+    let key = config::unlock_keystore(&cfg)?;
+    config::save_wallet(&key, cfg.chain_id, &cfg.signature_type)?;
+    let config_path = config::config_path()?;
+
+    match output {
+        OutputFormat::Json => {
+            print_json_line(serde_json::json!({
+                "encrypted": false,
+                "config_path": config_path.display().to_string(),
+            }));
+        }
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+            println!("Wallet decrypted. The private key is stored in plaintext again.");
+            println!("Config: {}", config_path.display());
+        }
+    }
+    Ok(())
+}
+
+/// What `wallet export` asks to be typed before the key leaves the config.
+const EXPORT_PHRASE: &str = "export my key";
+
+fn cmd_export(
+    output: &OutputFormat,
+    private_key_flag: Option<&str>,
+    show_private_key: bool,
+    keystore: Option<&Path>,
+) -> Result<()> {
+    anyhow::ensure!(
+        !config::ledger_enabled(),
+        "A Ledger's key never leaves the device, so it can't be exported."
+    );
+    if let Some(path) = keystore {
+        anyhow::ensure!(!path.exists(), "{} already exists", path.display());
+    }
+    let (key, _) = config::resolve_key(private_key_flag)?;
+    let key = key.ok_or_else(|| errors::auth(config::NO_WALLET_MSG))?;
     let signer = LocalSigner::from_str(&key).context("Invalid private key")?;
-    let address = signer.address();
+    confirm::phrase(
+        "Anyone with this key controls the wallet and its funds. Never share it or paste it \
+         into a website.",
+        EXPORT_PHRASE,
+    )?;
+
+    if show_private_key {
+        let key = signer.to_bytes().to_string();
+        match output {
+            OutputFormat::Json => print_json_line(serde_json::json!({
+                "address": signer.address().to_string(),
+                "private_key": key,
+            })),
+            OutputFormat::Plain => println!("{key}"),
+            OutputFormat::Table | OutputFormat::Csv => {
+                println!("Address:     {}", signer.address());
+                println!("Private key: {key}");
+            }
+        }
+        return Ok(());
+    }
+
+    let path = keystore.expect("clap requires --show-private-key or --keystore");
+    let passphrase = config::read_new_passphrase()?;
+    let address = config::export_keystore(path, &key, &passphrase)?;
+    match output {
+        OutputFormat::Json => print_json_line(serde_json::json!({
+            "address": address,
+            "keystore_path": path.display().to_string(),
+        })),
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+            println!("Wrote the keystore for {address} to {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+async fn cmd_address(output: &OutputFormat, private_key_flag: Option<&str>) -> Result<()> {
+    let (address, _) = resolve_address(private_key_flag).await?;
+    let address = address.ok_or_else(|| errors::auth(config::NO_WALLET_MSG))?;
 
     match output {
         OutputFormat::Json => {
-            println!("{}", serde_json::json!({"address": address.to_string()}));
+            print_json_line(serde_json::json!({"address": address.to_string()}));
         }
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             println!("{address}");
         }
     }
     Ok(())
 }
 
-fn cmd_show(output: &OutputFormat, private_key_flag: Option<&str>) -> Result<()> {
-    let (key, source) = config::resolve_key(private_key_flag);
-    let signer = key.as_deref().and_then(|k| LocalSigner::from_str(k).ok());
-    let address = signer.as_ref().map(|s| s.address().to_string());
-    let proxy_addr = signer
-        .as_ref()
-        .and_then(|s| derive_safe_wallet(s.address(), POLYGON))
+async fn cmd_show(output: &OutputFormat, private_key_flag: Option<&str>) -> Result<()> {
+    let source = config::key_source(private_key_flag);
+    let signer_addr = resolve_address(private_key_flag)
+        .await
+        .ok()
+        .and_then(|(a, _)| a);
+    let address = signer_addr.map(|a| a.to_string());
+    let watch_only = matches!(source, KeySource::WatchOnly);
+    let linked = config::resolve_funder()?.filter(|_| !watch_only);
+    let proxy_addr = linked
+        .or_else(|| signer_addr.and_then(|a| derive_safe_wallet(a, POLYGON)))
+        .filter(|_| !watch_only)
         .map(|a| a.to_string());
 
     let sig_type = config::resolve_signature_type(None);
-    let config_path = config::config_path()?;
+    let profile = config::active_profile();
+    let config_path = if config::no_config() {
+        None
+    } else {
+        Some(config::config_path()?.display().to_string())
+    };
 
     match output {
         OutputFormat::Json => {
-            println!(
-                "{}",
-                serde_json::json!({
-                    "address": address,
-                    "proxy_address": proxy_addr,
-                    "signature_type": sig_type,
-                    "config_path": config_path.display().to_string(),
-                    "source": source.label(),
-                    "configured": address.is_some(),
-                })
-            );
+            print_json_line(serde_json::json!({
+                "address": address,
+                "proxy_address": proxy_addr,
+                "proxy_linked": linked.is_some(),
+                "signature_type": sig_type,
+                "profile": profile,
+                "config_path": config_path,
+                "source": source.label(),
+                "watch_only": watch_only,
+                "configured": address.is_some(),
+            }));
         }
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             match &address {
                 Some(addr) => println!("Address:        {addr}"),
                 None => println!("Address:        (not configured)"),
             }
             if let Some(proxy) = &proxy_addr {
-                println!("Proxy wallet:   {proxy}");
+                let note = if linked.is_some() { " (linked)" } else { "" };
+                println!("Proxy wallet:   {proxy}{note}");
             }
             println!("Signature type: {sig_type}");
-            println!("Config path:    {}", config_path.display());
+            println!("Profile:        {profile}");
+            println!(
+                "Config path:    {}",
+                config_path.as_deref().unwrap_or("none (--no-config)")
+            );
             println!("Key source:     {}", source.label());
         }
     }
     Ok(())
 }
 
-fn cmd_reset(output: &OutputFormat, force: bool) -> Result<()> {
-    if !config::config_exists() {
+fn cmd_link_proxy(
+    output: &OutputFormat,
+    address: Option<&str>,
+    signature_type: Option<&str>,
+    unlink: bool,
+) -> Result<()> {
+    let profile = config::active_profile();
+    if unlink {
+        config::save_funder(None, None)?;
         match output {
-            OutputFormat::Table => println!("Nothing to reset. No config found."),
             OutputFormat::Json => {
+                print_json_line(serde_json::json!({"proxy_address": null, "profile": profile}));
+            }
+            OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                println!("Unlinked proxy wallet from profile '{profile}'.")
+            }
+        }
+        return Ok(());
+    }
+
+    let address = super::resolve_address(address.unwrap_or_default())?;
+    // An EOA can't have a funder, so default to the website's Safe wallets.
+    let saved = config::load_config().map(|c| c.signature_type);
+    let signature_type = match saved.as_deref() {
+        Some("eoa") => Some(signature_type.unwrap_or(config::DEFAULT_SIGNATURE_TYPE)),
+        _ => signature_type,
+    };
+    config::save_funder(Some(address), signature_type)?;
+    let sig_type = config::load_config()
+        .map(|c| c.signature_type)
+        .unwrap_or_default();
+
+    match output {
+        OutputFormat::Json => {
+            print_json_line(serde_json::json!({
+                "proxy_address": address.to_string(),
+                "signature_type": sig_type,
+                "profile": profile,
+            }));
+        }
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+            println!("Linked proxy wallet {address} to profile '{profile}'.");
+            println!("Signature type: {sig_type}");
+        }
+    }
+    Ok(())
+}
+
+fn cmd_list(output: &OutputFormat) -> Result<()> {
+    let default = config::default_profile();
+    let active = config::active_profile();
+    let profiles: Vec<_> = config::list_profiles()?
+        .into_iter()
+        .map(|name| {
+            let cfg = config::load_profile(&name);
+            let address = cfg.as_ref().and_then(|c| {
+                c.address.clone().or_else(|| {
+                    LocalSigner::from_str(&c.private_key)
+                        .ok()
+                        .map(|s| s.address().to_string())
+                })
+            });
+            let is_default = name == default;
+            let is_active = name == active;
+            (name, address, cfg, is_default, is_active)
+        })
+        .collect();
+
+    match output {
+        OutputFormat::Json => {
+            let items: Vec<_> = profiles
+                .iter()
+                .map(|(name, address, cfg, is_default, is_active)| {
+                    serde_json::json!({
+                        "profile": name,
+                        "address": address,
+                        "signature_type": cfg.as_ref().map(|c| c.signature_type.as_str()),
+                        "encrypted": cfg.as_ref().is_some_and(config::Config::is_encrypted),
+                        "keyring": cfg.as_ref().is_some_and(|c| c.keyring),
+                        "default": is_default,
+                        "active": is_active,
+                    })
+                })
+                .collect();
+            print_json_line(serde_json::json!(items));
+        }
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+            if profiles.is_empty() {
+                println!("No profiles found. Run `polymarket wallet create --profile <name>`.");
+                return Ok(());
+            }
+            for (name, address, cfg, is_default, is_active) in &profiles {
+                let marker = if *is_active { "*" } else { " " };
+                let mut notes = Vec::new();
+                if *is_default {
+                    notes.push("default");
+                }
+                if cfg.as_ref().is_some_and(config::Config::is_encrypted) {
+                    notes.push("encrypted");
+                }
+                if cfg.as_ref().is_some_and(|c| c.keyring) {
+                    notes.push("keyring");
+                }
+                let notes = if notes.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({})", notes.join(", "))
+                };
                 println!(
-                    "{}",
-                    serde_json::json!({"reset": false, "reason": "no config found"})
+                    "{marker} {name:<16} {}{notes}",
+                    address.as_deref().unwrap_or("(invalid config)")
                 );
             }
         }
-        return Ok(());
     }
+    Ok(())
+}
 
-    if !force {
-        use std::io::{self, BufRead, Write};
-        print!("This will delete all keys and config. Are you sure? [y/N] ");
-        io::stdout().flush()?;
-        let mut input = String::new();
-        io::stdin().lock().read_line(&mut input)?;
-        if !matches!(input.trim().to_lowercase().as_str(), "y" | "yes") {
-            println!("Aborted.");
-            return Ok(());
+fn cmd_use(output: &OutputFormat, profile: &str) -> Result<()> {
+    config::validate_profile_name(profile)?;
+    if !config::profile_exists(profile) {
+        bail!(
+            "Profile '{profile}' not found. Run `polymarket wallet create --profile {profile}` first."
+        );
+    }
+    config::set_default_profile(profile)?;
+
+    match output {
+        OutputFormat::Json => {
+            print_json_line(serde_json::json!({"default_profile": profile}));
+        }
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+            println!("Default profile set to '{profile}'.");
+        }
+    }
+    Ok(())
+}
+
+fn cmd_reset(output: &OutputFormat, force: bool) -> Result<()> {
+    if config::list_profiles()?.is_empty() {
+        match output {
+            OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+                println!("Nothing to reset. No config found.")
+            }
+            OutputFormat::Json => {
+                print_json_line(serde_json::json!({"reset": false, "reason": "no config found"}));
+            }
         }
+        return Ok(());
     }
 
-    let path = config::config_path()?;
+    if !force
+        && !confirm::ask(
+            "This will delete all profiles, keys and config. Are you sure?",
+            output,
+        )?
+    {
+        return Ok(());
+    }
+
+    let path = config::config_dir()?;
     config::delete_config()?;
 
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             println!("Config deleted: {}", path.display());
             println!("All keys and settings have been removed.");
         }
         OutputFormat::Json => {
-            println!(
-                "{}",
-                serde_json::json!({
-                    "reset": true,
-                    "deleted": path.display().to_string(),
-                })
-            );
+            print_json_line(serde_json::json!({
+                "reset": true,
+                "deleted": path.display().to_string(),
+            }));
         }
     }
     Ok(())
@@ -0,0 +1,85 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use clap_complete::engine::ArgValueCandidates;
+use polymarket_client_sdk::gamma::{self, types::request::MarketsRequest};
+
+use super::completions::watchlist_candidates;
+use super::markets::fetch_market;
+use crate::config::{self, WatchEntry};
+use crate::net;
+use crate::output::OutputFormat;
+use crate::output::watch::{print_watch_prices, print_watchlist};
+
+#[derive(Args)]
+pub struct WatchArgs {
+    #[command(subcommand)]
+    pub command: WatchCommand,
+}
+
+#[derive(Subcommand)]
+pub enum WatchCommand {
+    /// Add a market to the watchlist
+    Add {
+        /// Market ID (numeric), slug, condition ID (0x...), or CLOB token ID
+        market: String,
+    },
+    /// Remove a market from the watchlist
+    #[command(visible_alias = "rm")]
+    Remove {
+        /// Market ID, slug, or condition ID as shown by `watch list`
+        #[arg(add = ArgValueCandidates::new(watchlist_candidates))]
+        market: String,
+    },
+    /// List watched markets
+    #[command(visible_alias = "ls")]
+    List,
+    /// Current prices and 24h change for all watched markets
+    Prices,
+}
+
+pub async fn execute(client: &gamma::Client, args: WatchArgs, output: OutputFormat) -> Result<()> {
+    match args.command {
+        WatchCommand::Add { market } => {
+            let mut entries = config::load_watchlist()?;
+            let m = fetch_market(client, &market).await?;
+            let question = m.question.unwrap_or_default();
+            if entries.iter().any(|e| e.id == m.id) {
+                println!("Already watching: {question}");
+                return Ok(());
+            }
+            entries.push(WatchEntry {
+                id: m.id,
+                slug: m.slug,
+                condition_id: m.condition_id.map(|c| c.to_string()),
+                question: question.clone(),
+            });
+            config::save_watchlist(&entries)?;
+            println!("Watching: {question}");
+        }
+        WatchCommand::Remove { market } => {
+            let mut entries = config::load_watchlist()?;
+            let before = entries.len();
+            entries.retain(|e| !e.matches(&market));
+            anyhow::ensure!(entries.len() < before, "Not on watchlist: {market}");
+            config::save_watchlist(&entries)?;
+            println!("Removed {market} from the watchlist");
+        }
+        WatchCommand::List => print_watchlist(&config::load_watchlist()?, &output)?,
+        WatchCommand::Prices => {
+            let entries = config::load_watchlist()?;
+            if entries.is_empty() {
+                return print_watch_prices(&[], &output);
+            }
+            let ids: Vec<String> = entries.iter().map(|e| e.id.clone()).collect();
+            let request = MarketsRequest::builder()
+                .id(ids)
+                .limit(i32::try_from(entries.len())?)
+                .build();
+            let mut markets = net::retry(|| client.markets(&request)).await?;
+            // Keep the order markets were added in.
+            markets.sort_by_key(|m| entries.iter().position(|e| e.id == m.id));
+            print_watch_prices(&markets, &output)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,110 @@
+//! Whether this version of the CLI still works with the API. The CLOB
+//! advertises the version of its API at `GET /version`; this build speaks
+//! `API_VERSION`, the one its order structs and signing were written for.
+//! Commands ask at most once a day (see `cache::COMPATIBILITY`) and warn on
+//! stderr when the two differ; `doctor` asks every time.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::cache;
+use crate::config;
+
+const SKIP_ENV_VAR: &str = "POLYMARKET_SKIP_VERSION_CHECK";
+
+/// Kept short: the check runs next to every command and must not hold one
+/// up when the API is slow.
+const TIMEOUT: Duration = Duration::from_secs(3);
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Version of the CLOB API this build was written against.
+pub const API_VERSION: u64 = 1;
+
+/// What `GET /version` answers.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiVersion {
+    pub version: u64,
+}
+
+impl ApiVersion {
+    /// Why this build doesn't work with the API, or `None` if it does.
+    pub fn problem(&self) -> Option<String> {
+        (self.version != API_VERSION).then(|| {
+            format!(
+                "The CLOB API is at version {}, but polymarket v{VERSION} was written for \
+                 version {API_VERSION} and is known not to work with it",
+                self.version
+            )
+        })
+    }
+}
+
+/// The API's version, straight from the CLOB.
+pub async fn fetch() -> Result<ApiVersion> {
+    let url = format!("{}/version", config::clob_url().trim_end_matches('/'));
+    let client = reqwest::Client::builder()
+        .timeout(TIMEOUT)
+        .build()
+        .context("Failed to build HTTP client")?;
+    let version = client
+        .get(&url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+        .with_context(|| format!("Invalid version from {url}"))?;
+    Ok(version)
+}
+
+/// Whether the check is off: `--skip-version-check`, a non-empty
+/// `POLYMARKET_SKIP_VERSION_CHECK`, or offline mode.
+pub fn skipped(flag: bool) -> bool {
+    flag || config::offline() || std::env::var_os(SKIP_ENV_VAR).is_some_and(|v| !v.is_empty())
+}
+
+/// Prints a warning on stderr if this version is known not to work.
+/// Failing to reach the API is not worth a warning: the command reports its
+/// own network errors.
+pub async fn warn_if_incompatible() {
+    let Ok(api) = cache::get_or_fetch(cache::COMPATIBILITY, "version", fetch()).await else {
+        return;
+    };
+    if let Some(problem) = api.problem() {
+        eprintln!("Warning: {problem}. Run `polymarket upgrade`.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn other_api_versions_are_flagged() {
+        assert!(
+            ApiVersion {
+                version: API_VERSION
+            }
+            .problem()
+            .is_none()
+        );
+        let problem = ApiVersion {
+            version: API_VERSION + 1,
+        }
+        .problem()
+        .unwrap();
+        assert!(problem.starts_with(&format!(
+            "The CLOB API is at version {}, but polymarket v{VERSION}",
+            API_VERSION + 1
+        )));
+    }
+
+    #[test]
+    fn reads_the_version_response() {
+        let api: ApiVersion = serde_json::from_str(r#"{"version": 1}"#).unwrap();
+        assert_eq!(api.version, 1);
+    }
+}
@@ -1,106 +1,1579 @@
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, Once};
 
+use alloy::primitives::{B256, Bytes, U256};
 use anyhow::{Context, Result};
+use polymarket_client_sdk::auth::LocalSigner;
+use polymarket_client_sdk::clob::types::{OrderType, Side};
+use polymarket_client_sdk::types::{Address, Decimal};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+use crate::db;
+use crate::errors;
+use crate::keyring;
+use crate::logging::LogLevel;
+use crate::odds::OddsFormat;
+use crate::output::OutputFormat;
+use crate::output::style::Theme;
+
 const ENV_VAR: &str = "POLYMARKET_PRIVATE_KEY";
 const SIG_TYPE_ENV_VAR: &str = "POLYMARKET_SIGNATURE_TYPE";
+const PASSPHRASE_ENV_VAR: &str = "POLYMARKET_PASSPHRASE";
+const PROFILE_ENV_VAR: &str = "POLYMARKET_PROFILE";
+const CONFIG_DIR_ENV_VAR: &str = "POLYMARKET_CONFIG_DIR";
+const OUTPUT_ENV_VAR: &str = "POLYMARKET_OUTPUT";
+const ODDS_FORMAT_ENV_VAR: &str = "POLYMARKET_ODDS_FORMAT";
+const THEME_ENV_VAR: &str = "POLYMARKET_THEME";
+const ORDER_TYPE_ENV_VAR: &str = "POLYMARKET_ORDER_TYPE";
+const MARKET_ORDER_TYPE_ENV_VAR: &str = "POLYMARKET_MARKET_ORDER_TYPE";
+const BOOK_DEPTH_ENV_VAR: &str = "POLYMARKET_BOOK_DEPTH";
+const OFFLINE_ENV_VAR: &str = "POLYMARKET_OFFLINE";
+const NO_CONFIG_ENV_VAR: &str = "POLYMARKET_NO_CONFIG";
+pub const DEFAULT_PROFILE: &str = "default";
+const PROFILES_DIR: &str = "profiles";
+const DEFAULT_PROFILE_FILE: &str = "default_profile";
+const KEYSTORE_SUFFIX: &str = ".keystore.json";
+/// Suffix of the keyring account holding a profile's API credentials; the
+/// key itself is under the profile's name.
+const KEYRING_CREDENTIALS_SUFFIX: &str = "/api-credentials";
+const LEGACY_CONFIG_FILE: &str = "config.json";
+const LEGACY_KEYSTORE_FILE: &str = "keystore.json";
+const WATCHLIST_FILE: &str = "watchlist.json";
+const STOPS_FILE: &str = "stops.json";
+const TWAP_FILE: &str = "twap.json";
+const CONTACTS_FILE: &str = "contacts.json";
+const RESOLUTIONS_FILE: &str = "resolutions.json";
+const COPY_DRY_RUNS_FILE: &str = "copy_dry_runs.json";
+const SESSIONS_DIR: &str = "sessions";
+const CLIENT_ORDERS_DIR: &str = "client_orders";
+const RUN_DIR: &str = "run";
+const TRANSACTIONS_FILE: &str = "transactions.json";
+/// How many sent transactions the journal keeps.
+const TRANSACTIONS_KEPT: usize = 200;
 pub const DEFAULT_SIGNATURE_TYPE: &str = "gnosis-safe";
+pub const DEFAULT_RPC_URL: &str = "https://polygon.drpc.org";
+pub const DEFAULT_CLOB_URL: &str = "https://clob.polymarket.com";
+pub const DEFAULT_GAMMA_URL: &str = "https://gamma-api.polymarket.com";
+pub const DEFAULT_DATA_URL: &str = "https://data-api.polymarket.com";
+
+static PROFILE_OVERRIDE: Mutex<Option<String>> = Mutex::new(None);
+static USE_LEDGER: AtomicBool = AtomicBool::new(false);
+static PAPER: AtomicBool = AtomicBool::new(false);
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+static NO_CONFIG: AtomicBool = AtomicBool::new(false);
+static FUNDER_OVERRIDE: Mutex<Option<Address>> = Mutex::new(None);
+static ENDPOINT_OVERRIDES: Mutex<Endpoints> = Mutex::new(Endpoints {
+    rpc_url: None,
+    clob_url: None,
+    gamma_url: None,
+    data_url: None,
+});
+static NETWORK_OVERRIDES: Mutex<Network> = Mutex::new(Network {
+    timeout: None,
+    retries: None,
+    backoff: None,
+    rate_limit: None,
+});
+
+pub const NO_WALLET_MSG: &str =
+    "No wallet configured. Run `polymarket wallet create` or `polymarket wallet import <key>`";
+
+pub const NO_CONFIG_MSG: &str = "The config directory isn't used with --no-config or \
+     POLYMARKET_NO_CONFIG: pass settings as flags or POLYMARKET_* environment variables instead.";
+
+pub const WATCH_ONLY_MSG: &str = "This profile is a watch-only wallet: it has no private key, so it \
+     can't sign. Import the key with `polymarket wallet import <key> --yes`, or use another profile.";
+
+#[derive(Serialize, Deserialize)]
+pub struct Config {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub private_key: String,
+    pub chain_id: u64,
+    #[serde(default = "default_signature_type")]
+    pub signature_type: String,
+    /// Keystore file (relative to the profiles dir) holding the encrypted key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keystore: Option<String>,
+    /// Wallet address, stored next to an encrypted key so it can be shown
+    /// without asking for the passphrase.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// Set by `wallet import --watch-only`: `address` is a trading wallet to
+    /// follow, and there is no key to sign with.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub watch_only: bool,
+    /// Set by `wallet import --storage keyring`: the key, and any saved API
+    /// credentials, are in the OS keyring instead of this file (see
+    /// `keyring.rs`). `address` is stored so it can be shown without
+    /// unlocking the keyring.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub keyring: bool,
+    /// CLOB API credentials saved by `apikey create/derive --save`, used
+    /// instead of deriving a key on every authenticated call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_credentials: Option<ApiCredentials>,
+    /// Proxy or Safe wallet linked with `wallet link-proxy`, used as the
+    /// funder instead of the wallet derived from the signer (e.g. one created
+    /// by the Polymarket website).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub funder: Option<String>,
+    /// Custom service endpoints, e.g. a private RPC provider or a staging
+    /// CLOB. Unset entries use the public Polymarket endpoints.
+    #[serde(default, skip_serializing_if = "Endpoints::is_empty")]
+    pub endpoints: Endpoints,
+    /// Timeout and retry defaults for network calls (see `net.rs`).
+    #[serde(default, skip_serializing_if = "Network::is_empty")]
+    pub network: Network,
+    /// Diagnostic logging used when `-v` and `--log-level` aren't given (see
+    /// `logging.rs`).
+    #[serde(default, skip_serializing_if = "Logging::is_empty")]
+    pub log: Logging,
+    /// Where alerts, fills and order updates are sent (see `notify.rs`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notifications: Vec<Notifier>,
+    /// `paper` fills orders against the live book into a local account
+    /// instead of sending them (see `paper.rs`).
+    #[serde(default, skip_serializing_if = "TradingMode::is_live")]
+    pub mode: TradingMode,
+    /// Output format used when `--output` isn't given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<OutputFormat>,
+    /// How table output shows prices when `--odds-format` isn't given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub odds_format: Option<OddsFormat>,
+    /// Colors of table output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<Theme>,
+    /// Defaults for command flags that aren't given.
+    #[serde(default, skip_serializing_if = "Defaults::is_empty")]
+    pub defaults: Defaults,
+    /// Limits orders are checked against before they are sent (see
+    /// `risk.rs`).
+    #[serde(default, skip_serializing_if = "Risk::is_empty")]
+    pub risk: Risk,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TradingMode {
+    #[default]
+    Live,
+    Paper,
+}
+
+impl TradingMode {
+    fn is_live(&self) -> bool {
+        *self == Self::Live
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Endpoints {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clob_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gamma_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_url: Option<String>,
+}
+
+/// Durations are strings such as `10s` or `500ms`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Network {
+    /// How long one request may take before it is abandoned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+    /// How many times a failed read is tried again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+    /// Wait before the first retry; doubled for each one after it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backoff: Option<String>,
+    /// Most API requests sent per second; 0 sends them unthrottled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<u32>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Logging {
+    /// Level to log at; unset leaves logging off.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<LogLevel>,
+    /// File to append to instead of stderr, relative to the config directory
+    /// unless absolute.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+}
+
+/// Per-command defaults, each overridden by its flag or environment variable.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Defaults {
+    /// `--order-type` of `clob create-order` and `post-orders`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order_type: Option<OrderType>,
+    /// `--order-type` of `clob market-order` and `stop`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub market_order_type: Option<OrderType>,
+    /// `--depth` of `clob book`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub book_depth: Option<usize>,
+}
+
+/// Most an order may commit, in USDC notional. Unset limits don't apply.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Risk {
+    /// Any one order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_order_size: Option<Decimal>,
+    /// Held in and bid for in one market, counting the order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_market_notional: Option<Decimal>,
+    /// Held in and bid for across every market, counting the order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_total_exposure: Option<Decimal>,
+}
+
+/// A large market order worked in slices by `order twap`. Saved after every
+/// slice, so an interrupted run can be picked up with `order twap resume`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TwapJob {
+    pub id: u32,
+    pub token_id: String,
+    pub side: Side,
+    /// USDC to spend on a buy, shares to sell.
+    pub total: Decimal,
+    /// Most sent in one slice, in the units of `total`.
+    pub slice: Decimal,
+    /// Slices planned; the job ends after the last, filled or not.
+    pub slices: u32,
+    /// Seconds between slices before jitter.
+    pub interval_secs: u64,
+    /// Largest random change to each wait, as a fraction of the interval.
+    pub jitter: Decimal,
+    pub order_type: OrderType,
+    /// Slices sent so far.
+    #[serde(default)]
+    pub sent: u32,
+    #[serde(default)]
+    pub filled_shares: Decimal,
+    #[serde(default)]
+    pub filled_usdc: Decimal,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TwapJob {
+    /// Filled so far, in the units of `total`.
+    pub fn filled(&self) -> Decimal {
+        if self.side == Side::Sell {
+            self.filled_shares
+        } else {
+            self.filled_usdc
+        }
+    }
+
+    pub fn remaining(&self) -> Decimal {
+        (self.total - self.filled()).max(Decimal::ZERO)
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.sent >= self.slices || self.remaining().is_zero()
+    }
+
+    pub fn avg_price(&self) -> Option<Decimal> {
+        (!self.filled_shares.is_zero()).then(|| self.filled_usdc / self.filled_shares)
+    }
+}
+
+/// A stop-loss or take-profit order handed to the daemon with
+/// `clob stop --daemon`, kept until it fires or is canceled.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WorkingStop {
+    pub id: u32,
+    pub token_id: String,
+    pub side: Side,
+    pub trigger: Decimal,
+    /// Shares to sell or USDC to spend; `None` sells the whole position.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<Decimal>,
+    pub order_type: OrderType,
+    /// Which way the midpoint has to move, fixed when the stop was added.
+    pub direction: AlertDirection,
+    /// Armed with `--override-risk`, so a buy goes out over the risk
+    /// limits when it fires.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub override_risk: bool,
+}
+
+/// A notification destination from the profile's `notifications` list.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Notifier {
+    pub format: NotifyFormat,
+    /// Webhook URL; not used by `desktop`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Events to send; empty means all of them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<NotifyEvent>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotifyFormat {
+    /// The event's JSON, POSTed as is
+    Webhook,
+    Discord,
+    Slack,
+    Desktop,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotifyEvent {
+    Alert,
+    Fill,
+    Order,
+    Resolution,
+}
+
+impl Notifier {
+    pub fn wants(&self, event: NotifyEvent) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+}
+
+/// API credentials are tied to the signer that created them, so its address
+/// is stored alongside to avoid presenting them for a different key.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ApiCredentials {
+    pub address: String,
+    pub key: String,
+    pub secret: String,
+    pub passphrase: String,
+}
+
+/// A named address saved with `contacts add`, usable wherever a transfer
+/// recipient or funder is given.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Contact {
+    pub name: String,
+    pub address: String,
+}
+
+/// A market saved with `watch add`. The question is kept so the watchlist can
+/// be listed without a network round trip.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WatchEntry {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition_id: Option<String>,
+    pub question: String,
+}
+
+impl WatchEntry {
+    /// Whether `reference` names this market by ID, slug or condition ID.
+    pub fn matches(&self, reference: &str) -> bool {
+        self.id == reference
+            || self.slug.as_deref() == Some(reference)
+            || self
+                .condition_id
+                .as_deref()
+                .is_some_and(|c| c.eq_ignore_ascii_case(reference))
+    }
+}
+
+/// A price alert saved with `alert add` and checked by `alert run`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Alert {
+    pub id: u32,
+    pub market_id: String,
+    pub question: String,
+    pub outcome: String,
+    pub token_id: String,
+    pub direction: AlertDirection,
+    pub price: Decimal,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertDirection {
+    Above,
+    Below,
+}
+
+impl std::fmt::Display for AlertDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Above => "above",
+            Self::Below => "below",
+        })
+    }
+}
+
+impl AlertDirection {
+    /// Whether `price` is on the alerting side of `threshold`.
+    pub fn crossed(self, price: Decimal, threshold: Decimal) -> bool {
+        match self {
+            Self::Above => price >= threshold,
+            Self::Below => price <= threshold,
+        }
+    }
+}
+
+/// Where a market is in UMA's optimistic oracle: trading, closed and waiting
+/// for an answer, answered and open to challenge, challenged, or settled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResolutionState {
+    Open,
+    Closed,
+    Proposed,
+    Disputed,
+    Resolved,
+}
+
+impl std::fmt::Display for ResolutionState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Open => "open",
+            Self::Closed => "closed",
+            Self::Proposed => "proposed",
+            Self::Disputed => "disputed",
+            Self::Resolved => "resolved",
+        })
+    }
+}
+
+/// A transaction the CLI signed, kept so a stuck one can be found and
+/// replaced with the same nonce.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SentTx {
+    pub hash: B256,
+    pub chain_id: u64,
+    pub from: Address,
+    pub nonce: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Address>,
+    pub value: U256,
+    pub input: Bytes,
+    pub gas_limit: u64,
+    /// In wei, for an EIP-1559 transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fee: Option<u128>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority_fee: Option<u128>,
+    /// In wei, for a legacy transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<u128>,
+    pub sent_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// An order sent with `--client-id`, kept so a retried command can't submit
+/// it twice. Shared by all profiles; client IDs are meant to be unique.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ClientOrder {
+    pub client_id: String,
+    /// Hash of the signed order, which the CLOB uses as its ID.
+    pub order_id: B256,
+    pub token_id: String,
+    pub side: Side,
+    pub submitted_at: chrono::DateTime<chrono::Utc>,
+    /// The CLOB's status for the order. `None` while it is being posted, or
+    /// if posting failed without an answer: it may or may not have arrived.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+}
+
+impl Config {
+    /// A profile with no wallet yet, e.g. one used with a Ledger or set up
+    /// with `config set` first.
+    fn empty() -> Self {
+        Self {
+            private_key: String::new(),
+            chain_id: polymarket_client_sdk::POLYGON,
+            signature_type: default_signature_type(),
+            keystore: None,
+            address: None,
+            watch_only: false,
+            keyring: false,
+            api_credentials: None,
+            funder: None,
+            endpoints: Endpoints::default(),
+            log: Logging::default(),
+            network: Network::default(),
+            notifications: Vec::new(),
+            mode: TradingMode::Live,
+            output: None,
+            odds_format: None,
+            theme: None,
+            defaults: Defaults::default(),
+            risk: Risk::default(),
+        }
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.keystore.is_some()
+    }
+
+    pub fn has_wallet(&self) -> bool {
+        self.is_encrypted() || self.keyring || !self.private_key.is_empty() || self.watch_only
+    }
+
+    /// Signer address, from the stored address or the plaintext key.
+    fn signer_address(&self) -> Option<String> {
+        self.address.clone().or_else(|| {
+            LocalSigner::from_str(&self.private_key)
+                .ok()
+                .map(|s| s.address().to_string())
+        })
+    }
+}
+
+impl Endpoints {
+    pub fn is_empty(&self) -> bool {
+        self.rpc_url.is_none()
+            && self.clob_url.is_none()
+            && self.gamma_url.is_none()
+            && self.data_url.is_none()
+    }
+
+    /// Fills every endpoint left unset here from `fallback`.
+    fn or(self, fallback: Endpoints) -> Endpoints {
+        Endpoints {
+            rpc_url: self.rpc_url.or(fallback.rpc_url),
+            clob_url: self.clob_url.or(fallback.clob_url),
+            gamma_url: self.gamma_url.or(fallback.gamma_url),
+            data_url: self.data_url.or(fallback.data_url),
+        }
+    }
+}
+
+impl Network {
+    pub fn is_empty(&self) -> bool {
+        self.timeout.is_none()
+            && self.retries.is_none()
+            && self.backoff.is_none()
+            && self.rate_limit.is_none()
+    }
+
+    fn or(self, fallback: Network) -> Network {
+        Network {
+            timeout: self.timeout.or(fallback.timeout),
+            retries: self.retries.or(fallback.retries),
+            backoff: self.backoff.or(fallback.backoff),
+            rate_limit: self.rate_limit.or(fallback.rate_limit),
+        }
+    }
+}
+
+impl Logging {
+    pub fn is_empty(&self) -> bool {
+        self.level.is_none() && self.file.is_none()
+    }
+}
+
+impl Defaults {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Risk {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn default_signature_type() -> String {
+    DEFAULT_SIGNATURE_TYPE.to_string()
+}
+
+pub enum KeySource {
+    Flag,
+    EnvVar,
+    ConfigFile,
+    Keystore,
+    Keyring,
+    Ledger,
+    WatchOnly,
+    None,
+}
+
+/// What signs on the wallet's behalf.
+pub enum SignerBackend {
+    /// A hex private key, from whichever source `resolve_key` picked.
+    PrivateKey(String),
+    /// The first Ledger Live account on a connected Ledger.
+    Ledger,
+}
+
+impl KeySource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Flag => "--private-key flag",
+            Self::EnvVar => "POLYMARKET_PRIVATE_KEY env var",
+            Self::ConfigFile => "config file",
+            Self::Keystore => "encrypted keystore",
+            Self::Keyring => "OS keyring",
+            Self::Ledger => "Ledger device",
+            Self::WatchOnly => "none (watch-only)",
+            Self::None => "not configured",
+        }
+    }
+}
+
+/// `POLYMARKET_CONFIG_DIR`, or `polymarket` in the platform's config
+/// directory: `$XDG_CONFIG_HOME` (or `~/.config`) on Linux, `~/Library/
+/// Application Support` on macOS, `%APPDATA%` on Windows. A config directory
+/// at the old `~/.config/polymarket` is moved there the first time this is
+/// resolved, and used where it is if it can't be.
+///
+/// Fails with `--no-config`, so nothing reads or writes the directory: reads
+/// that fall back to defaults get them, and writes fail.
+pub fn config_dir() -> Result<PathBuf> {
+    static MIGRATE: Once = Once::new();
+    anyhow::ensure!(!no_config(), errors::validation(NO_CONFIG_MSG));
+    if let Ok(dir) = std::env::var(CONFIG_DIR_ENV_VAR)
+        && !dir.is_empty()
+    {
+        return Ok(PathBuf::from(dir));
+    }
+    let dir = dirs::config_dir()
+        .context("Could not determine the config directory")?
+        .join("polymarket");
+    let Some(legacy) = dirs::home_dir().map(|home| home.join(".config").join("polymarket")) else {
+        return Ok(dir);
+    };
+    MIGRATE.call_once(|| {
+        if let Err(e) = migrate_config_dir(&legacy, &dir) {
+            eprintln!(
+                "Warning: failed to move {} to {}: {e:#}",
+                legacy.display(),
+                dir.display()
+            );
+        }
+    });
+    Ok(if !dir.exists() && legacy.exists() {
+        legacy
+    } else {
+        dir
+    })
+}
+
+/// Moves the config directory from `legacy` to `dir`, unless they are the
+/// same place or `dir` is already in use. Returns whether it was moved.
+fn migrate_config_dir(legacy: &Path, dir: &Path) -> Result<bool> {
+    if legacy == dir || !legacy.exists() || dir.exists() {
+        return Ok(false);
+    }
+    if let Some(parent) = dir.parent() {
+        fs::create_dir_all(parent).context("Failed to create the config directory's parent")?;
+    }
+    fs::rename(legacy, dir).context("Failed to move the config directory")?;
+    Ok(true)
+}
+
+/// Directory holding one `<name>.json` config (and optional
+/// `<name>.keystore.json`) per profile. A pre-profiles `config.json` is moved
+/// into the `default` profile the first time this is resolved.
+fn profiles_dir() -> Result<PathBuf> {
+    static MIGRATE: Once = Once::new();
+    let dir = config_dir()?;
+    MIGRATE.call_once(|| {
+        if let Err(e) = migrate_legacy_layout(&dir) {
+            eprintln!(
+                "Warning: failed to migrate {} to profiles: {e:#}",
+                dir.join(LEGACY_CONFIG_FILE).display()
+            );
+        }
+    });
+    Ok(dir.join(PROFILES_DIR))
+}
+
+/// Moves `config.json` / `keystore.json` in `dir` to `profiles/default.*`.
+/// Returns whether anything was migrated.
+fn migrate_legacy_layout(dir: &Path) -> Result<bool> {
+    let legacy = dir.join(LEGACY_CONFIG_FILE);
+    let profiles = dir.join(PROFILES_DIR);
+    let target = profiles.join(format!("{DEFAULT_PROFILE}.json"));
+    if !legacy.exists() || target.exists() {
+        return Ok(false);
+    }
+
+    let data = fs::read_to_string(&legacy).context("Failed to read legacy config")?;
+    let mut config: Config = serde_json::from_str(&data).context("Invalid legacy config")?;
+    ensure_dir(&profiles)?;
+
+    if config.keystore.is_some() {
+        let name = keystore_file(DEFAULT_PROFILE);
+        fs::rename(dir.join(LEGACY_KEYSTORE_FILE), profiles.join(&name))
+            .context("Failed to move keystore file")?;
+        config.keystore = Some(name);
+    }
+    write_config_to(&target, &config)?;
+    fs::remove_file(&legacy).context("Failed to remove legacy config")?;
+    Ok(true)
+}
+
+/// Profile names become file names, so keep them to a safe character set.
+pub fn validate_profile_name(name: &str) -> Result<()> {
+    anyhow::ensure!(
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+        "Invalid profile name '{name}': use letters, digits, '-' or '_'"
+    );
+    Ok(())
+}
+
+/// Sets the profile selected by the global `--profile` flag.
+pub fn set_profile_override(profile: Option<String>) -> Result<()> {
+    if let Some(name) = &profile {
+        validate_profile_name(name)?;
+    }
+    *PROFILE_OVERRIDE.lock().unwrap_or_else(|e| e.into_inner()) = profile;
+    Ok(())
+}
+
+pub fn profile_override() -> Option<String> {
+    PROFILE_OVERRIDE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Profile marked as default by `wallet use`, or `default`.
+pub fn default_profile() -> String {
+    config_dir()
+        .ok()
+        .and_then(|dir| fs::read_to_string(dir.join(DEFAULT_PROFILE_FILE)).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| validate_profile_name(s).is_ok())
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+pub fn set_default_profile(name: &str) -> Result<()> {
+    validate_profile_name(name)?;
+    let dir = ensure_dir(&config_dir()?)?;
+    fs::write(dir.join(DEFAULT_PROFILE_FILE), format!("{name}\n"))
+        .context("Failed to write default profile")
+}
+
+/// Priority: `--profile` flag > env var > default profile.
+pub fn active_profile() -> String {
+    if let Some(name) = profile_override() {
+        return name;
+    }
+    if let Ok(name) = std::env::var(PROFILE_ENV_VAR)
+        && validate_profile_name(&name).is_ok()
+    {
+        return name;
+    }
+    default_profile()
+}
+
+pub fn profile_path(name: &str) -> Result<PathBuf> {
+    Ok(profiles_dir()?.join(format!("{name}.json")))
+}
+
+fn keystore_file(profile: &str) -> String {
+    format!("{profile}{KEYSTORE_SUFFIX}")
+}
+
+pub fn config_path() -> Result<PathBuf> {
+    profile_path(&active_profile())
+}
+
+pub fn profile_exists(name: &str) -> bool {
+    profile_path(name).is_ok_and(|p| p.exists())
+}
+
+pub fn config_exists() -> bool {
+    profile_exists(&active_profile())
+}
+
+/// Names of all saved profiles, sorted.
+pub fn list_profiles() -> Result<Vec<String>> {
+    let dir = profiles_dir()?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut names: Vec<String> = fs::read_dir(&dir)
+        .context("Failed to read profiles directory")?
+        .filter_map(|entry| {
+            let file_name = entry.ok()?.file_name().into_string().ok()?;
+            if file_name.ends_with(KEYSTORE_SUFFIX) {
+                return None;
+            }
+            file_name.strip_suffix(".json").map(str::to_string)
+        })
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+/// Removes the whole config directory, including every profile.
+pub fn delete_config() -> Result<()> {
+    let dir = config_dir()?;
+    if dir.exists() {
+        fs::remove_dir_all(&dir).context("Failed to remove config directory")?;
+    }
+    Ok(())
+}
+
+pub fn load_profile(name: &str) -> Option<Config> {
+    let path = profile_path(name).ok()?;
+    let data = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+pub fn load_config() -> Option<Config> {
+    load_profile(&active_profile())
+}
+
+/// Priority: CLI flag > env var > config file > default ("proxy").
+pub fn resolve_signature_type(cli_flag: Option<&str>) -> String {
+    if let Some(st) = cli_flag {
+        return st.to_string();
+    }
+    if let Ok(st) = std::env::var(SIG_TYPE_ENV_VAR)
+        && !st.is_empty()
+    {
+        return st;
+    }
+    if let Some(config) = load_config() {
+        return config.signature_type;
+    }
+    DEFAULT_SIGNATURE_TYPE.to_string()
+}
+
+/// Saved API credentials in the active profile that still belong to
+/// `address`, so re-saving the same key (e.g. encrypting it) keeps them.
+fn credentials_for(address: &str) -> Option<ApiCredentials> {
+    saved_api_credentials().filter(|c| c.address.eq_ignore_ascii_case(address))
+}
+
+/// API credentials saved in the active profile, from the OS keyring for a
+/// keyring profile. One that can't be read is treated as not saved.
+pub fn saved_api_credentials() -> Option<ApiCredentials> {
+    let config = load_config()?;
+    if !config.keyring {
+        return config.api_credentials;
+    }
+    let saved = keyring::get(&credentials_account(&active_profile()))
+        .inspect_err(|e| tracing::debug!("Failed to read API credentials from the keyring: {e:#}"))
+        .ok()??;
+    serde_json::from_str(&saved).ok()
+}
+
+/// Private key of keyring profile `profile`, failing if the keyring has none.
+pub fn keyring_key(profile: &str) -> Result<String> {
+    keyring::get(profile)?.with_context(|| {
+        format!(
+            "The OS keyring has no key for profile '{profile}'. Import it again with \
+                 `polymarket wallet import <key> --storage keyring --yes`"
+        )
+    })
+}
+
+/// API credentials of keyring profile `profile`, if any were saved.
+pub fn keyring_credentials(profile: &str) -> Result<Option<ApiCredentials>> {
+    keyring::get(&credentials_account(profile))?
+        .map(|saved| {
+            serde_json::from_str(&saved).with_context(|| {
+                format!("The OS keyring holds unreadable API credentials for profile '{profile}'")
+            })
+        })
+        .transpose()
+}
+
+fn credentials_account(profile: &str) -> String {
+    format!("{profile}{KEYRING_CREDENTIALS_SUFFIX}")
+}
+
+/// Linked proxy wallet in the active profile if it belongs to `address`, so
+/// re-saving the same key keeps the link but importing another key drops it.
+fn funder_for(address: &str) -> Option<String> {
+    load_config()
+        .filter(|c| {
+            c.signer_address()
+                .is_some_and(|a| a.eq_ignore_ascii_case(address))
+        })
+        .and_then(|c| c.funder)
+}
+
+/// Endpoints in the active profile, kept when its wallet is replaced.
+fn saved_endpoints() -> Endpoints {
+    load_config().map(|c| c.endpoints).unwrap_or_default()
+}
+
+fn saved_network() -> Network {
+    load_config().map(|c| c.network).unwrap_or_default()
+}
+
+/// The active profile's `log` settings.
+pub fn logging() -> Logging {
+    load_config().map(|c| c.log).unwrap_or_default()
+}
+
+/// Notifiers configured in the active profile. Also kept when its wallet is
+/// replaced.
+pub fn notifiers() -> Vec<Notifier> {
+    load_config().map(|c| c.notifications).unwrap_or_default()
+}
+
+fn saved_mode() -> TradingMode {
+    load_config().map(|c| c.mode).unwrap_or_default()
+}
+
+fn saved_output() -> Option<OutputFormat> {
+    load_config().and_then(|c| c.output)
+}
+
+fn saved_odds_format() -> Option<OddsFormat> {
+    load_config().and_then(|c| c.odds_format)
+}
+
+fn saved_theme() -> Option<Theme> {
+    load_config().and_then(|c| c.theme)
+}
+
+fn saved_defaults() -> Defaults {
+    load_config().map(|c| c.defaults).unwrap_or_default()
+}
+
+/// The active profile's risk limits; none without a config.
+pub fn risk_limits() -> Risk {
+    load_config().map(|c| c.risk).unwrap_or_default()
+}
+
+pub fn save_wallet(key: &str, chain_id: u64, signature_type: &str) -> Result<()> {
+    let address = LocalSigner::from_str(key)
+        .context("Invalid private key")?
+        .address()
+        .to_string();
+    let config = Config {
+        private_key: key.to_string(),
+        chain_id,
+        signature_type: signature_type.to_string(),
+        keystore: None,
+        address: None,
+        watch_only: false,
+        keyring: false,
+        api_credentials: credentials_for(&address),
+        funder: funder_for(&address),
+        endpoints: saved_endpoints(),
+        network: saved_network(),
+        log: logging(),
+        notifications: notifiers(),
+        mode: saved_mode(),
+        output: saved_output(),
+        odds_format: saved_odds_format(),
+        theme: saved_theme(),
+        defaults: saved_defaults(),
+        risk: risk_limits(),
+    };
+    let forget = forget_keyring();
+    write_config(&config)?;
+    forget()?;
+    remove_keystore()
+}
+
+/// Replaces the active profile's wallet with a watch-only `address`. Only
+/// settings that don't belong to a key are kept.
+pub fn save_watch_only(address: Address) -> Result<()> {
+    let config = Config {
+        address: Some(address.to_string()),
+        watch_only: true,
+        endpoints: saved_endpoints(),
+        network: saved_network(),
+        log: logging(),
+        notifications: notifiers(),
+        mode: saved_mode(),
+        output: saved_output(),
+        odds_format: saved_odds_format(),
+        theme: saved_theme(),
+        defaults: saved_defaults(),
+        risk: risk_limits(),
+        ..Config::empty()
+    };
+    let forget = forget_keyring();
+    write_config(&config)?;
+    forget()?;
+    remove_keystore()
+}
+
+/// The followed address when the active profile is watch-only.
+pub fn watch_only_address() -> Result<Option<Address>> {
+    load_config()
+        .filter(|c| c.watch_only)
+        .and_then(|c| c.address)
+        .map(|a| {
+            a.parse()
+                .with_context(|| format!("Invalid watch-only address in config: {a}"))
+        })
+        .transpose()
+}
+
+/// Encrypts `key` into a V3 keystore file next to the profile config and
+/// points the config at it. The plaintext key is never written to disk.
+pub fn save_encrypted_wallet(
+    key: &str,
+    chain_id: u64,
+    signature_type: &str,
+    passphrase: &str,
+) -> Result<()> {
+    let dir = ensure_dir(&profiles_dir()?)?;
+    let name = keystore_file(&active_profile());
+    let address = encrypt_key_to(&dir, &name, key, passphrase)?;
+
+    let config = Config {
+        private_key: String::new(),
+        chain_id,
+        signature_type: signature_type.to_string(),
+        keystore: Some(name),
+        api_credentials: credentials_for(&address),
+        funder: funder_for(&address),
+        address: Some(address),
+        watch_only: false,
+        keyring: false,
+        endpoints: saved_endpoints(),
+        network: saved_network(),
+        log: logging(),
+        notifications: notifiers(),
+        mode: saved_mode(),
+        output: saved_output(),
+        odds_format: saved_odds_format(),
+        theme: saved_theme(),
+        defaults: saved_defaults(),
+        risk: risk_limits(),
+    };
+    let forget = forget_keyring();
+    write_config(&config)?;
+    forget()
+}
+
+/// Stores `key` in the OS keyring under the active profile's name and points
+/// the config at it. Saved API credentials for the same key move with it.
+pub fn save_keyring_wallet(key: &str, chain_id: u64, signature_type: &str) -> Result<()> {
+    let address = LocalSigner::from_str(key)
+        .context("Invalid private key")?
+        .address()
+        .to_string();
+    let profile = active_profile();
+    let credentials = credentials_for(&address);
+    let funder = funder_for(&address);
+    keyring::set(&profile, key)?;
+    match &credentials {
+        Some(credentials) => keyring::set(
+            &credentials_account(&profile),
+            &serde_json::to_string(credentials)?,
+        )?,
+        None => keyring::delete(&credentials_account(&profile))?,
+    }
+    let config = Config {
+        chain_id,
+        signature_type: signature_type.to_string(),
+        address: Some(address),
+        keyring: true,
+        funder,
+        endpoints: saved_endpoints(),
+        network: saved_network(),
+        log: logging(),
+        notifications: notifiers(),
+        mode: saved_mode(),
+        output: saved_output(),
+        odds_format: saved_odds_format(),
+        theme: saved_theme(),
+        defaults: saved_defaults(),
+        risk: risk_limits(),
+        ..Config::empty()
+    };
+    write_config(&config)?;
+    remove_keystore()
+}
+
+/// Returns what removes the active profile's keyring entries once the wallet
+/// replacing them is saved. Decided up front, while the config still says
+/// whether there are any.
+fn forget_keyring() -> impl FnOnce() -> Result<()> {
+    let profile = load_config()
+        .filter(|c| c.keyring)
+        .map(|_| active_profile());
+    move || {
+        let Some(profile) = profile else {
+            return Ok(());
+        };
+        keyring::delete(&profile)?;
+        keyring::delete(&credentials_account(&profile))
+    }
+}
+
+/// Writes profile `name`, and the keystore its key is encrypted in, replacing
+/// whatever was saved under that name.
+pub fn save_profile(
+    name: &str,
+    mut config: Config,
+    keystore: Option<&serde_json::Value>,
+) -> Result<()> {
+    validate_profile_name(name)?;
+    ensure_dir(&config_dir()?)?;
+    let dir = ensure_dir(&profiles_dir()?)?;
+    let keystore_path = dir.join(keystore_file(name));
+    config.keystore = match keystore {
+        Some(keystore) => {
+            fs::write(&keystore_path, serde_json::to_string_pretty(keystore)?)
+                .context("Failed to write keystore file")?;
+            Some(keystore_file(name))
+        }
+        None => {
+            if keystore_path.exists() {
+                fs::remove_file(&keystore_path).context("Failed to remove keystore file")?;
+            }
+            None
+        }
+    };
+    write_config_to(&profile_path(name)?, &config)
+}
+
+/// The keystore `config` keeps its key in, if it has one.
+pub fn read_keystore(config: &Config) -> Result<Option<serde_json::Value>> {
+    let Some(name) = &config.keystore else {
+        return Ok(None);
+    };
+    let path = profiles_dir()?.join(name);
+    let data = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read keystore {}", path.display()))?;
+    serde_json::from_str(&data)
+        .map(Some)
+        .with_context(|| format!("Invalid keystore: {}", path.display()))
+}
+
+/// Encrypts `key` into a V3 keystore that isn't saved anywhere, returning it
+/// with the wallet address.
+pub fn encrypt_key(key: &str, passphrase: &str) -> Result<(serde_json::Value, String)> {
+    let dir = ensure_dir(&std::env::temp_dir().join(format!("polymarket-{}", std::process::id())))?;
+    let encrypted = encrypt_key_to(&dir, "keystore.json", key, passphrase).and_then(|address| {
+        let data = fs::read_to_string(dir.join("keystore.json"))
+            .context("Failed to read keystore file")?;
+        Ok((serde_json::from_str(&data)?, address))
+    });
+    let _ = fs::remove_dir_all(&dir);
+    encrypted
+}
+
+/// The active profile's config for updating. A Ledger leaves no key to
+/// store, so its profile may not exist yet.
+fn load_config_for_update() -> Result<Config> {
+    match load_config() {
+        Some(config) => Ok(config),
+        None if ledger_enabled() => Ok(Config::empty()),
+        None => Err(errors::auth(NO_WALLET_MSG)),
+    }
+}
+
+/// Stores (or with `None`, clears) API credentials in the active profile.
+pub fn save_api_credentials(credentials: Option<ApiCredentials>) -> Result<()> {
+    let mut config = load_config_for_update()?;
+    if config.keyring {
+        let account = credentials_account(&active_profile());
+        return match credentials {
+            Some(credentials) => keyring::set(&account, &serde_json::to_string(&credentials)?),
+            None => keyring::delete(&account),
+        };
+    }
+    config.api_credentials = credentials;
+    write_config(&config)
+}
+
+/// Links (or with `None`, unlinks) a proxy wallet to the active profile,
+/// optionally switching its signature type to match.
+pub fn save_funder(funder: Option<Address>, signature_type: Option<&str>) -> Result<()> {
+    let mut config = load_config_for_update()?;
+    config.funder = funder.map(|a| a.to_string());
+    if let Some(st) = signature_type {
+        config.signature_type = st.to_string();
+    }
+    write_config(&config)
+}
+
+/// The active profile's config as JSON, for `config show/set/unset`. A
+/// profile that doesn't exist yet starts out empty.
+pub fn load_config_json() -> Result<serde_json::Value> {
+    let path = config_path()?;
+    let config = if path.exists() {
+        let data = fs::read_to_string(&path).context("Failed to read config file")?;
+        serde_json::from_str(&data)
+            .with_context(|| format!("Invalid config file: {}", path.display()))?
+    } else {
+        Config::empty()
+    };
+    Ok(serde_json::to_value(config)?)
+}
+
+/// Checks `value` is a valid profile config and saves it to the active
+/// profile.
+pub fn save_config_json(value: serde_json::Value) -> Result<()> {
+    let config: Config = serde_json::from_value(value)?;
+    write_config(&config)
+}
+
+/// Lets `edit` change a draft of the active profile's config, then saves it
+/// if it is still valid. The draft sits next to the profile, readable only
+/// by the user, and is left in place for another try if it isn't valid.
+pub fn edit_config(edit: impl FnOnce(&Path) -> Result<()>) -> Result<()> {
+    let current: Config = serde_json::from_value(load_config_json()?)?;
+    ensure_dir(&config_dir()?)?;
+    ensure_dir(&profiles_dir()?)?;
+    let draft = profiles_dir()?.join(format!("{}.json.edit", active_profile()));
+    write_config_to(&draft, &current)?;
+    edit(&draft)?;
+    let data = fs::read_to_string(&draft).context("Failed to read edited config")?;
+    let edited: Config = serde_json::from_str(&data).map_err(|e| {
+        anyhow::anyhow!(
+            "Config not saved: {e}. Your edits are in {}",
+            draft.display()
+        )
+    })?;
+    write_config(&edited)?;
+    fs::remove_file(&draft).context("Failed to remove config draft")
+}
+
+fn watchlist_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join(WATCHLIST_FILE))
+}
+
+/// The watchlist is shared by all profiles. A missing file is an empty list.
+pub fn load_watchlist() -> Result<Vec<WatchEntry>> {
+    let path = watchlist_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = fs::read_to_string(&path).context("Failed to read watchlist")?;
+    serde_json::from_str(&data).with_context(|| format!("Invalid watchlist: {}", path.display()))
+}
+
+pub fn save_watchlist(entries: &[WatchEntry]) -> Result<()> {
+    ensure_dir(&config_dir()?)?;
+    let json = serde_json::to_string_pretty(entries)?;
+    fs::write(watchlist_path()?, json).context("Failed to write watchlist")
+}
+
+/// Alerts are kept in the database and shared by all profiles.
+pub fn load_alerts() -> Result<Vec<Alert>> {
+    read_alerts(&db::open()?).context("Failed to read alerts")
+}
+
+pub fn save_alerts(alerts: &[Alert]) -> Result<()> {
+    let mut conn = db::open()?;
+    let tx = conn.transaction()?;
+    write_alerts(&tx, alerts).context("Failed to write alerts")?;
+    Ok(tx.commit()?)
+}
+
+pub(crate) fn read_alerts(conn: &rusqlite::Connection) -> Result<Vec<Alert>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, market_id, question, outcome, token_id, direction, price
+         FROM alerts ORDER BY id",
+    )?;
+    let alerts = stmt
+        .query_map([], |row| {
+            Ok(Alert {
+                id: row.get(0)?,
+                market_id: row.get(1)?,
+                question: row.get(2)?,
+                outcome: row.get(3)?,
+                token_id: row.get(4)?,
+                direction: db::named(row, 5)?,
+                price: db::parsed(row, 6)?,
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(alerts)
+}
+
+/// Replaces the saved alerts with `alerts`.
+pub(crate) fn write_alerts(conn: &rusqlite::Connection, alerts: &[Alert]) -> Result<()> {
+    conn.execute("DELETE FROM alerts", [])?;
+    let mut insert = conn.prepare(
+        "INSERT INTO alerts (id, market_id, question, outcome, token_id, direction, price)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+    )?;
+    for a in alerts {
+        insert.execute(rusqlite::params![
+            a.id,
+            a.market_id,
+            a.question,
+            a.outcome,
+            a.token_id,
+            db::name(&a.direction)?,
+            a.price.to_string(),
+        ])?;
+    }
+    Ok(())
+}
+
+fn stops_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join(STOPS_FILE))
+}
+
+/// Stops waiting in the daemon. A missing file means none.
+pub fn load_stops() -> Result<Vec<WorkingStop>> {
+    let path = stops_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = fs::read_to_string(&path).context("Failed to read stops")?;
+    serde_json::from_str(&data).with_context(|| format!("Invalid stops file: {}", path.display()))
+}
+
+pub fn save_stops(stops: &[WorkingStop]) -> Result<()> {
+    ensure_dir(&config_dir()?)?;
+    let json = serde_json::to_string_pretty(stops)?;
+    fs::write(stops_path()?, json).context("Failed to write stops")
+}
+
+fn twap_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join(TWAP_FILE))
+}
+
+/// TWAP jobs, finished or not. A missing file means none.
+pub fn load_twaps() -> Result<Vec<TwapJob>> {
+    let path = twap_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = fs::read_to_string(&path).context("Failed to read TWAP jobs")?;
+    serde_json::from_str(&data)
+        .with_context(|| format!("Invalid TWAP jobs file: {}", path.display()))
+}
+
+pub fn save_twaps(jobs: &[TwapJob]) -> Result<()> {
+    ensure_dir(&config_dir()?)?;
+    let json = serde_json::to_string_pretty(jobs)?;
+    fs::write(twap_path()?, json).context("Failed to write TWAP jobs")
+}
+
+fn contacts_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join(CONTACTS_FILE))
+}
+
+/// The address book is shared by all profiles. A missing file means none.
+pub fn load_contacts() -> Result<Vec<Contact>> {
+    let path = contacts_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = fs::read_to_string(&path).context("Failed to read contacts")?;
+    serde_json::from_str(&data)
+        .with_context(|| format!("Invalid contacts file: {}", path.display()))
+}
+
+pub fn save_contacts(contacts: &[Contact]) -> Result<()> {
+    ensure_dir(&config_dir()?)?;
+    let json = serde_json::to_string_pretty(contacts)?;
+    fs::write(contacts_path()?, json).context("Failed to write contacts")
+}
+
+fn resolutions_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join(RESOLUTIONS_FILE))
+}
 
-pub const NO_WALLET_MSG: &str =
-    "No wallet configured. Run `polymarket wallet create` or `polymarket wallet import <key>`";
+/// Directory for the cached CLOB sessions, one file per profile.
+pub fn sessions_dir() -> Result<PathBuf> {
+    Ok(config_dir()?.join(SESSIONS_DIR))
+}
 
-#[derive(Serialize, Deserialize)]
-pub struct Config {
-    pub private_key: String,
-    pub chain_id: u64,
-    #[serde(default = "default_signature_type")]
-    pub signature_type: String,
+/// Directory holding one file per `--client-id` order.
+pub fn client_orders_dir() -> Result<PathBuf> {
+    Ok(config_dir()?.join(CLIENT_ORDERS_DIR))
 }
 
-fn default_signature_type() -> String {
-    DEFAULT_SIGNATURE_TYPE.to_string()
+/// Directory holding one file per running long-lived command (see
+/// `procs`).
+pub fn run_dir() -> Result<PathBuf> {
+    Ok(config_dir()?.join(RUN_DIR))
 }
 
-pub enum KeySource {
-    Flag,
-    EnvVar,
-    ConfigFile,
-    None,
+/// Client IDs become file names, so keep them to a safe character set.
+pub fn validate_client_id(id: &str) -> Result<()> {
+    anyhow::ensure!(
+        !id.is_empty()
+            && id.len() <= 64
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+        errors::validation(format!(
+            "Invalid client ID '{id}': use up to 64 letters, digits, '-' or '_', e.g. a UUID"
+        ))
+    );
+    Ok(())
 }
 
-impl KeySource {
-    pub fn label(&self) -> &'static str {
-        match self {
-            Self::Flag => "--private-key flag",
-            Self::EnvVar => "POLYMARKET_PRIVATE_KEY env var",
-            Self::ConfigFile => "config file",
-            Self::None => "not configured",
-        }
-    }
+fn client_order_path(id: &str) -> Result<PathBuf> {
+    validate_client_id(id)?;
+    Ok(client_orders_dir()?.join(format!("{id}.json")))
 }
 
-fn config_dir() -> Result<PathBuf> {
-    let home = dirs::home_dir().context("Could not determine home directory")?;
-    Ok(home.join(".config").join("polymarket"))
+pub fn load_client_order(id: &str) -> Result<Option<ClientOrder>> {
+    let path = client_order_path(id)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let data = fs::read_to_string(&path).context("Failed to read client order")?;
+    serde_json::from_str(&data)
+        .map(Some)
+        .with_context(|| format!("Invalid client order: {}", path.display()))
 }
 
-pub fn config_path() -> Result<PathBuf> {
-    Ok(config_dir()?.join("config.json"))
+/// Records `order` under its client ID, unless one is already there. The
+/// file is created exclusively, so of two commands racing with the same ID
+/// only one gets `true`.
+pub fn reserve_client_order(order: &ClientOrder) -> Result<bool> {
+    use std::io::Write as _;
+    ensure_dir(&client_orders_dir()?)?;
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(client_order_path(&order.client_id)?);
+    let mut file = match file {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => return Ok(false),
+        Err(e) => return Err(e).context("Failed to create client order"),
+    };
+    file.write_all(serde_json::to_string_pretty(order)?.as_bytes())
+        .context("Failed to write client order")?;
+    Ok(true)
 }
 
-pub fn config_exists() -> bool {
-    config_path().is_ok_and(|p| p.exists())
+pub fn save_client_order(order: &ClientOrder) -> Result<()> {
+    let json = serde_json::to_string_pretty(order)?;
+    fs::write(client_order_path(&order.client_id)?, json).context("Failed to write client order")
 }
 
-pub fn delete_config() -> Result<()> {
-    let dir = config_dir()?;
-    if dir.exists() {
-        fs::remove_dir_all(&dir).context("Failed to remove config directory")?;
+/// Frees a client ID whose order the CLOB turned down, so it can be retried.
+pub fn remove_client_order(id: &str) -> Result<()> {
+    let path = client_order_path(id)?;
+    if path.exists() {
+        fs::remove_file(&path).context("Failed to remove client order")?;
     }
     Ok(())
 }
 
-pub fn load_config() -> Option<Config> {
-    let path = config_path().ok()?;
-    let data = fs::read_to_string(path).ok()?;
-    serde_json::from_str(&data).ok()
+/// File name of the active profile's cached session in [`sessions_dir`].
+pub fn session_file() -> String {
+    format!("{}.json", active_profile())
 }
 
-/// Priority: CLI flag > env var > config file > default ("proxy").
-pub fn resolve_signature_type(cli_flag: Option<&str>) -> String {
-    if let Some(st) = cli_flag {
-        return st.to_string();
+/// The resolution state `resolution watch` last saw for each market, keyed
+/// by market ID. A missing file means none have been seen.
+pub fn load_resolutions() -> Result<BTreeMap<String, ResolutionState>> {
+    let path = resolutions_path()?;
+    if !path.exists() {
+        return Ok(BTreeMap::new());
     }
-    if let Ok(st) = std::env::var(SIG_TYPE_ENV_VAR)
-        && !st.is_empty()
-    {
-        return st;
+    let data = fs::read_to_string(&path).context("Failed to read resolution states")?;
+    serde_json::from_str(&data)
+        .with_context(|| format!("Invalid resolution states file: {}", path.display()))
+}
+
+pub fn save_resolutions(states: &BTreeMap<String, ResolutionState>) -> Result<()> {
+    ensure_dir(&config_dir()?)?;
+    let json = serde_json::to_string_pretty(states)?;
+    fs::write(resolutions_path()?, json).context("Failed to write resolution states")
+}
+
+fn copy_dry_runs_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join(COPY_DRY_RUNS_FILE))
+}
+
+/// When a `copy watch` dry run last followed each wallet, keyed by its
+/// lowercase address. A missing file means none have been.
+pub fn load_copy_dry_runs() -> Result<BTreeMap<String, chrono::DateTime<chrono::Utc>>> {
+    let path = copy_dry_runs_path()?;
+    if !path.exists() {
+        return Ok(BTreeMap::new());
     }
-    if let Some(config) = load_config() {
-        return config.signature_type;
+    let data = fs::read_to_string(&path).context("Failed to read copy dry runs")?;
+    serde_json::from_str(&data)
+        .with_context(|| format!("Invalid copy dry runs file: {}", path.display()))
+}
+
+pub fn save_copy_dry_runs(runs: &BTreeMap<String, chrono::DateTime<chrono::Utc>>) -> Result<()> {
+    ensure_dir(&config_dir()?)?;
+    let json = serde_json::to_string_pretty(runs)?;
+    fs::write(copy_dry_runs_path()?, json).context("Failed to write copy dry runs")
+}
+
+fn transactions_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join(TRANSACTIONS_FILE))
+}
+
+/// The journal of signed transactions, oldest first, shared by all profiles.
+pub fn load_transactions() -> Result<Vec<SentTx>> {
+    let path = transactions_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
     }
-    DEFAULT_SIGNATURE_TYPE.to_string()
+    let data = fs::read_to_string(&path).context("Failed to read transaction journal")?;
+    serde_json::from_str(&data)
+        .with_context(|| format!("Invalid transaction journal: {}", path.display()))
 }
 
-pub fn save_wallet(key: &str, chain_id: u64, signature_type: &str) -> Result<()> {
-    let dir = config_dir()?;
-    fs::create_dir_all(&dir).context("Failed to create config directory")?;
+/// Saves the journal, dropping all but the newest entries.
+pub fn save_transactions(transactions: &[SentTx]) -> Result<()> {
+    ensure_dir(&config_dir()?)?;
+    let kept = &transactions[transactions.len().saturating_sub(TRANSACTIONS_KEPT)..];
+    let json = serde_json::to_string_pretty(kept)?;
+    fs::write(transactions_path()?, json).context("Failed to write transaction journal")
+}
+
+fn remove_keystore() -> Result<()> {
+    let path = keystore_path()?;
+    if path.exists() {
+        fs::remove_file(&path).context("Failed to remove keystore file")?;
+    }
+    Ok(())
+}
+
+pub fn ensure_dir(dir: &Path) -> Result<PathBuf> {
+    fs::create_dir_all(dir).context("Failed to create config directory")?;
 
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
-        fs::set_permissions(&dir, fs::Permissions::from_mode(0o700))?;
+        fs::set_permissions(dir, fs::Permissions::from_mode(0o700))?;
     }
 
-    let config = Config {
-        private_key: key.to_string(),
-        chain_id,
-        signature_type: signature_type.to_string(),
-    };
-    let json = serde_json::to_string_pretty(&config)?;
-    let path = config_path()?;
+    Ok(dir.to_path_buf())
+}
+
+/// Writes the active profile's config. The first profile saved becomes the
+/// default unless a `default` profile already exists.
+fn write_config(config: &Config) -> Result<()> {
+    let profile = active_profile();
+    ensure_dir(&config_dir()?)?;
+    ensure_dir(&profiles_dir()?)?;
+    write_config_to(&profile_path(&profile)?, config)?;
+
+    let pointer = config_dir()?.join(DEFAULT_PROFILE_FILE);
+    if profile != DEFAULT_PROFILE && !pointer.exists() && !profile_exists(DEFAULT_PROFILE) {
+        set_default_profile(&profile)?;
+    }
+    Ok(())
+}
+
+fn write_config_to(path: &Path, config: &Config) -> Result<()> {
+    let json = serde_json::to_string_pretty(config)?;
 
     #[cfg(unix)]
     {
@@ -111,7 +1584,7 @@ pub fn save_wallet(key: &str, chain_id: u64, signature_type: &str) -> Result<()>
             .create(true)
             .truncate(true)
             .mode(0o600)
-            .open(&path)
+            .open(path)
             .context("Failed to create config file")?;
         file.write_all(json.as_bytes())
             .context("Failed to write config file")?;
@@ -119,26 +1592,362 @@ pub fn save_wallet(key: &str, chain_id: u64, signature_type: &str) -> Result<()>
 
     #[cfg(not(unix))]
     {
-        fs::write(&path, &json).context("Failed to write config file")?;
+        fs::write(path, &json).context("Failed to write config file")?;
+    }
+
+    Ok(())
+}
+
+/// Writes `key` as a keystore file named `name` in `dir`, returning the
+/// wallet address.
+fn encrypt_key_to(dir: &Path, name: &str, key: &str, passphrase: &str) -> Result<String> {
+    let signer = LocalSigner::from_str(key).context("Invalid private key")?;
+    LocalSigner::encrypt_keystore(
+        dir,
+        &mut rand::thread_rng(),
+        signer.to_bytes(),
+        passphrase,
+        Some(name),
+    )
+    .context("Failed to write keystore file")?;
+    Ok(signer.address().to_string())
+}
+
+/// Writes `key` as a new keystore file at `path`, returning the wallet
+/// address. An existing file is never overwritten.
+pub fn export_keystore(path: &Path, key: &str, passphrase: &str) -> Result<String> {
+    anyhow::ensure!(!path.exists(), "{} already exists", path.display());
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .with_context(|| format!("Invalid keystore path: {}", path.display()))?;
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => ensure_dir(dir)?,
+        _ => PathBuf::from("."),
+    };
+    encrypt_key_to(&dir, name, key, passphrase)
+}
+
+fn decrypt_key_from(path: &Path, passphrase: &str) -> Result<String> {
+    let signer = LocalSigner::decrypt_keystore(path, passphrase)
+        .map_err(|_| anyhow::anyhow!("Failed to decrypt keystore: wrong passphrase?"))?;
+    Ok(signer.to_bytes().to_string())
+}
+
+/// Reads the passphrase from `POLYMARKET_PASSPHRASE`, falling back to an
+/// interactive prompt.
+pub fn read_passphrase(prompt: &str) -> Result<String> {
+    if let Ok(p) = std::env::var(PASSPHRASE_ENV_VAR)
+        && !p.is_empty()
+    {
+        return Ok(p);
+    }
+    rpassword::prompt_password(prompt)
+        .context("Failed to read passphrase (set POLYMARKET_PASSPHRASE for non-interactive use)")
+}
+
+/// Like `read_passphrase`, but asks twice when prompting so a typo can't lock
+/// the user out of a freshly encrypted key.
+pub fn read_new_passphrase() -> Result<String> {
+    if let Ok(p) = std::env::var(PASSPHRASE_ENV_VAR)
+        && !p.is_empty()
+    {
+        return Ok(p);
+    }
+    let passphrase = read_passphrase("New passphrase: ")?;
+    anyhow::ensure!(!passphrase.is_empty(), "Passphrase cannot be empty");
+    let confirm = read_passphrase("Confirm passphrase: ")?;
+    anyhow::ensure!(passphrase == confirm, "Passphrases do not match");
+    Ok(passphrase)
+}
+
+pub fn keystore_path() -> Result<PathBuf> {
+    Ok(profiles_dir()?.join(keystore_file(&active_profile())))
+}
+
+/// Decrypts the configured keystore, prompting for the passphrase if needed.
+pub fn unlock_keystore(config: &Config) -> Result<String> {
+    let name = config
+        .keystore
+        .as_deref()
+        .context("Config has no keystore")?;
+    let path = profiles_dir()?.join(name);
+    let passphrase = read_passphrase("Keystore passphrase: ")?;
+    decrypt_key_from(&path, &passphrase)
+}
+
+/// Routes signing to a Ledger device (the global `--ledger` flag).
+pub fn set_ledger(enabled: bool) {
+    USE_LEDGER.store(enabled, Ordering::Relaxed);
+}
+
+pub fn ledger_enabled() -> bool {
+    USE_LEDGER.load(Ordering::Relaxed)
+}
+
+/// Simulates trading (the global `--paper` flag).
+pub fn set_paper(enabled: bool) {
+    PAPER.store(enabled, Ordering::Relaxed);
+}
+
+pub fn paper_flag() -> bool {
+    PAPER.load(Ordering::Relaxed)
+}
+
+/// Priority: `--paper` flag > `mode` in the active profile.
+pub fn paper_mode() -> bool {
+    paper_flag() || saved_mode() == TradingMode::Paper
+}
+
+/// Skips requests the command itself doesn't need, such as the version
+/// check (the global `--offline` flag).
+pub fn set_offline(enabled: bool) {
+    OFFLINE.store(enabled, Ordering::Relaxed);
+}
+
+/// Priority: `--offline` flag > a non-empty `POLYMARKET_OFFLINE`.
+pub fn offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+        || std::env::var_os(OFFLINE_ENV_VAR).is_some_and(|v| !v.is_empty())
+}
+
+/// Keeps away from the config directory (the global `--no-config` flag), for
+/// CI pipelines and throwaway containers.
+pub fn set_no_config(enabled: bool) {
+    NO_CONFIG.store(enabled, Ordering::Relaxed);
+}
+
+pub fn no_config_flag() -> bool {
+    NO_CONFIG.load(Ordering::Relaxed)
+}
+
+/// Priority: `--no-config` flag > a non-empty `POLYMARKET_NO_CONFIG`.
+pub fn no_config() -> bool {
+    no_config_flag() || std::env::var_os(NO_CONFIG_ENV_VAR).is_some_and(|v| !v.is_empty())
+}
+
+/// Sets the funder given by the global `--funder` flag.
+pub fn set_funder_override(funder: Option<Address>) {
+    *FUNDER_OVERRIDE.lock().unwrap_or_else(|e| e.into_inner()) = funder;
+}
+
+pub fn funder_override() -> Option<Address> {
+    *FUNDER_OVERRIDE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Priority: `--funder` flag > proxy wallet linked in the active profile.
+pub fn resolve_funder() -> Result<Option<Address>> {
+    if let Some(funder) = funder_override() {
+        return Ok(Some(funder));
     }
+    load_config()
+        .and_then(|c| c.funder)
+        .map(|a| Address::from_str(&a).with_context(|| format!("Invalid linked proxy wallet: {a}")))
+        .transpose()
+}
 
+/// Sets the endpoints given by the global `--rpc-url` and `--clob-url` flags.
+pub fn set_endpoint_overrides(overrides: Endpoints) -> Result<()> {
+    for (flag, url) in [
+        ("--rpc-url", &overrides.rpc_url),
+        ("--clob-url", &overrides.clob_url),
+    ] {
+        if let Some(url) = url {
+            reqwest::Url::parse(url).with_context(|| format!("Invalid {flag}: {url}"))?;
+        }
+    }
+    *ENDPOINT_OVERRIDES.lock().unwrap_or_else(|e| e.into_inner()) = overrides;
     Ok(())
 }
 
-/// Priority: CLI flag > env var > config file.
-pub fn resolve_key(cli_flag: Option<&str>) -> (Option<String>, KeySource) {
+pub fn endpoint_overrides() -> Endpoints {
+    ENDPOINT_OVERRIDES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Priority: CLI flag > active profile's `endpoints` (defaults are applied by
+/// the `*_url` accessors).
+pub fn endpoints() -> Endpoints {
+    endpoint_overrides().or(saved_endpoints())
+}
+
+/// Sets the timeout and retry settings given by the global `--timeout`,
+/// `--retries` and `--backoff` flags.
+pub fn set_network_overrides(overrides: Network) {
+    *NETWORK_OVERRIDES.lock().unwrap_or_else(|e| e.into_inner()) = overrides;
+}
+
+pub fn network_overrides() -> Network {
+    NETWORK_OVERRIDES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Priority: CLI flag > active profile's `network` (defaults are applied in
+/// `net.rs`).
+pub fn network() -> Network {
+    network_overrides().or(saved_network())
+}
+
+/// A setting from `env_var`, read the way the profile's JSON is: as a string
+/// (`json`, `GTC`) or, failing that, as a JSON value (`10`).
+fn env_setting<T: DeserializeOwned>(env_var: &str) -> Result<Option<T>> {
+    let Ok(value) = std::env::var(env_var) else {
+        return Ok(None);
+    };
+    serde_json::from_value(serde_json::Value::String(value.clone()))
+        .or_else(|_| serde_json::from_str(&value))
+        .map(Some)
+        .map_err(|_| anyhow::anyhow!("Invalid {env_var}: {value}"))
+}
+
+/// Rejects order types the SDK only knows as `Unknown`.
+fn known_order_type(order_type: OrderType, setting: &str) -> Result<OrderType> {
+    anyhow::ensure!(
+        !matches!(order_type, OrderType::Unknown(_)),
+        "Invalid {setting}: {order_type}. Use GTC, FOK, GTD or FAK"
+    );
+    Ok(order_type)
+}
+
+/// Priority (once `--output` is ruled out): env var > active profile's
+/// `output` > table.
+pub fn output_format() -> Result<OutputFormat> {
+    if let Some(output) = env_setting(OUTPUT_ENV_VAR)? {
+        return Ok(output);
+    }
+    Ok(saved_output().unwrap_or(OutputFormat::Table))
+}
+
+/// Priority (once `--odds-format` is ruled out): env var > active
+/// profile's `odds_format` > price.
+pub fn odds_format() -> Result<OddsFormat> {
+    if let Some(format) = env_setting(ODDS_FORMAT_ENV_VAR)? {
+        return Ok(format);
+    }
+    Ok(saved_odds_format().unwrap_or_default())
+}
+
+/// Priority: env var > active profile's `theme` > default.
+pub fn theme() -> Result<Theme> {
+    if let Some(theme) = env_setting(THEME_ENV_VAR)? {
+        return Ok(theme);
+    }
+    Ok(saved_theme().unwrap_or_default())
+}
+
+/// Order type for limit orders without `--order-type`: env var > profile's
+/// `defaults.order_type` > GTC.
+pub fn limit_order_type() -> Result<OrderType> {
+    match env_setting(ORDER_TYPE_ENV_VAR)? {
+        Some(order_type) => known_order_type(order_type, ORDER_TYPE_ENV_VAR),
+        None => saved_defaults().order_type.map_or(Ok(OrderType::GTC), |t| {
+            known_order_type(t, "defaults.order_type")
+        }),
+    }
+}
+
+/// Order type for market orders without `--order-type`: env var > profile's
+/// `defaults.market_order_type` > FOK.
+pub fn market_order_type() -> Result<OrderType> {
+    match env_setting(MARKET_ORDER_TYPE_ENV_VAR)? {
+        Some(order_type) => known_order_type(order_type, MARKET_ORDER_TYPE_ENV_VAR),
+        None => saved_defaults()
+            .market_order_type
+            .map_or(Ok(OrderType::FOK), |t| {
+                known_order_type(t, "defaults.market_order_type")
+            }),
+    }
+}
+
+/// Order book levels shown without `--depth`: env var > profile's
+/// `defaults.book_depth` > all of them.
+pub fn book_depth() -> Result<Option<usize>> {
+    Ok(env_setting(BOOK_DEPTH_ENV_VAR)?.or(saved_defaults().book_depth))
+}
+
+pub fn rpc_url() -> String {
+    endpoints()
+        .rpc_url
+        .unwrap_or_else(|| DEFAULT_RPC_URL.to_string())
+}
+
+pub fn clob_url() -> String {
+    endpoints()
+        .clob_url
+        .unwrap_or_else(|| DEFAULT_CLOB_URL.to_string())
+}
+
+pub fn gamma_url() -> String {
+    endpoints()
+        .gamma_url
+        .unwrap_or_else(|| DEFAULT_GAMMA_URL.to_string())
+}
+
+pub fn data_url() -> String {
+    endpoints()
+        .data_url
+        .unwrap_or_else(|| DEFAULT_DATA_URL.to_string())
+}
+
+/// Which source `resolve_signer_backend` would use, without decrypting
+/// anything.
+pub fn key_source(cli_flag: Option<&str>) -> KeySource {
+    if ledger_enabled() {
+        return KeySource::Ledger;
+    }
+    if cli_flag.is_some() {
+        return KeySource::Flag;
+    }
+    if std::env::var(ENV_VAR).is_ok_and(|k| !k.is_empty()) {
+        return KeySource::EnvVar;
+    }
+    match load_config() {
+        Some(c) if c.watch_only => KeySource::WatchOnly,
+        Some(c) if c.is_encrypted() => KeySource::Keystore,
+        Some(c) if c.keyring => KeySource::Keyring,
+        Some(c) if !c.private_key.is_empty() => KeySource::ConfigFile,
+        _ => KeySource::None,
+    }
+}
+
+/// Priority: `--ledger` > private key (see `resolve_key`).
+pub fn resolve_signer_backend(cli_flag: Option<&str>) -> Result<(SignerBackend, KeySource)> {
+    if ledger_enabled() {
+        return Ok((SignerBackend::Ledger, KeySource::Ledger));
+    }
+    let (key, source) = resolve_key(cli_flag)?;
+    let key = key.ok_or_else(|| errors::auth(NO_WALLET_MSG))?;
+    Ok((SignerBackend::PrivateKey(key), source))
+}
+
+/// Priority: CLI flag > env var > config file (decrypting a keystore, or
+/// reading the OS keyring, if needed).
+pub fn resolve_key(cli_flag: Option<&str>) -> Result<(Option<String>, KeySource)> {
     if let Some(key) = cli_flag {
-        return (Some(key.to_string()), KeySource::Flag);
+        return Ok((Some(key.to_string()), KeySource::Flag));
     }
     if let Ok(key) = std::env::var(ENV_VAR)
         && !key.is_empty()
     {
-        return (Some(key), KeySource::EnvVar);
+        return Ok((Some(key), KeySource::EnvVar));
     }
     if let Some(config) = load_config() {
-        return (Some(config.private_key), KeySource::ConfigFile);
+        anyhow::ensure!(!config.watch_only, errors::auth(WATCH_ONLY_MSG));
+        if config.is_encrypted() {
+            return Ok((Some(unlock_keystore(&config)?), KeySource::Keystore));
+        }
+        if config.keyring {
+            return Ok((Some(keyring_key(&active_profile())?), KeySource::Keyring));
+        }
+        if !config.private_key.is_empty() {
+            return Ok((Some(config.private_key), KeySource::ConfigFile));
+        }
     }
-    (None, KeySource::None)
+    Ok((None, KeySource::None))
 }
 
 #[cfg(test)]
@@ -161,7 +1970,7 @@ mod tests {
     fn resolve_key_flag_overrides_env() {
         let _lock = ENV_LOCK.lock().unwrap();
         unsafe { set(ENV_VAR, "env_key") };
-        let (key, source) = resolve_key(Some("flag_key"));
+        let (key, source) = resolve_key(Some("flag_key")).unwrap();
         assert_eq!(key.unwrap(), "flag_key");
         assert!(matches!(source, KeySource::Flag));
         unsafe { unset(ENV_VAR) };
@@ -171,7 +1980,7 @@ mod tests {
     fn resolve_key_env_var_returns_env_value() {
         let _lock = ENV_LOCK.lock().unwrap();
         unsafe { set(ENV_VAR, "env_key_value") };
-        let (key, source) = resolve_key(None);
+        let (key, source) = resolve_key(None).unwrap();
         assert_eq!(key.unwrap(), "env_key_value");
         assert!(matches!(source, KeySource::EnvVar));
         unsafe { unset(ENV_VAR) };
@@ -181,7 +1990,7 @@ mod tests {
     fn resolve_key_skips_empty_env_var() {
         let _lock = ENV_LOCK.lock().unwrap();
         unsafe { set(ENV_VAR, "") };
-        let (_, source) = resolve_key(None);
+        let (_, source) = resolve_key(None).unwrap();
         assert!(!matches!(source, KeySource::EnvVar));
         unsafe { unset(ENV_VAR) };
     }
@@ -209,4 +2018,283 @@ mod tests {
         let result = resolve_signature_type(None);
         assert!(!result.is_empty());
     }
+
+    const TEST_KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("polymarket-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn keystore_roundtrip_recovers_key() {
+        let dir = temp_dir("keystore-roundtrip");
+        let address = encrypt_key_to(&dir, "ks.json", TEST_KEY, "hunter2").unwrap();
+        let key = decrypt_key_from(&dir.join("ks.json"), "hunter2").unwrap();
+        assert_eq!(key, TEST_KEY);
+        assert_eq!(
+            address,
+            LocalSigner::from_str(TEST_KEY)
+                .unwrap()
+                .address()
+                .to_string()
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn keystore_wrong_passphrase_fails() {
+        let dir = temp_dir("keystore-wrong-pass");
+        encrypt_key_to(&dir, "ks.json", TEST_KEY, "right").unwrap();
+        let err = decrypt_key_from(&dir.join("ks.json"), "wrong")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("passphrase"), "got: {err}");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn config_without_private_key_deserializes() {
+        let config: Config = serde_json::from_str(
+            r#"{"chain_id": 137, "keystore": "keystore.json", "address": "0x1"}"#,
+        )
+        .unwrap();
+        assert!(config.is_encrypted());
+        assert!(config.private_key.is_empty());
+    }
+
+    #[test]
+    fn config_api_credentials_roundtrip() {
+        let config: Config = serde_json::from_str(
+            r#"{"chain_id": 137, "private_key": "0xabc", "api_credentials":
+                {"address": "0x1", "key": "k", "secret": "s", "passphrase": "p"}}"#,
+        )
+        .unwrap();
+        let creds = config.api_credentials.as_ref().unwrap();
+        assert_eq!(creds.key, "k");
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["api_credentials"]["passphrase"], "p");
+
+        let bare: Config = serde_json::from_str(r#"{"chain_id": 137}"#).unwrap();
+        let json = serde_json::to_value(&bare).unwrap();
+        assert!(json.get("api_credentials").is_none());
+    }
+
+    #[test]
+    fn config_funder_roundtrip() {
+        let config: Config = serde_json::from_str(
+            r#"{"chain_id": 137, "private_key": "0xabc", "funder": "0x1111111111111111111111111111111111111111"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.funder.as_deref(),
+            Some("0x1111111111111111111111111111111111111111")
+        );
+        let bare: Config = serde_json::from_str(r#"{"chain_id": 137}"#).unwrap();
+        let json = serde_json::to_value(&bare).unwrap();
+        assert!(json.get("funder").is_none());
+    }
+
+    #[test]
+    fn config_endpoints_roundtrip() {
+        let config: Config = serde_json::from_str(
+            r#"{"chain_id": 137, "endpoints": {"clob_url": "https://clob.staging.example"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.endpoints.clob_url.as_deref(),
+            Some("https://clob.staging.example")
+        );
+        let json = serde_json::to_value(&config).unwrap();
+        assert!(json["endpoints"].get("rpc_url").is_none());
+
+        let bare: Config = serde_json::from_str(r#"{"chain_id": 137}"#).unwrap();
+        assert!(bare.endpoints.is_empty());
+        let json = serde_json::to_value(&bare).unwrap();
+        assert!(json.get("endpoints").is_none());
+    }
+
+    #[test]
+    fn config_notifications_parse() {
+        let config: Config = serde_json::from_str(
+            r#"{"chain_id": 137, "notifications": [
+                {"format": "discord", "url": "https://discord.com/api/webhooks/1/x", "events": ["fill"]},
+                {"format": "desktop"}
+            ]}"#,
+        )
+        .unwrap();
+        let [discord, desktop] = config.notifications.as_slice() else {
+            panic!("expected two notifiers");
+        };
+        assert_eq!(discord.format, NotifyFormat::Discord);
+        assert!(discord.wants(NotifyEvent::Fill));
+        assert!(!discord.wants(NotifyEvent::Alert));
+        assert_eq!(desktop.url, None);
+        assert!(desktop.wants(NotifyEvent::Order));
+
+        let bare: Config = serde_json::from_str(r#"{"chain_id": 137}"#).unwrap();
+        let json = serde_json::to_value(&bare).unwrap();
+        assert!(json.get("notifications").is_none());
+    }
+
+    #[test]
+    fn config_mode_defaults_to_live() {
+        let bare: Config = serde_json::from_str(r#"{"chain_id": 137}"#).unwrap();
+        assert_eq!(bare.mode, TradingMode::Live);
+        assert!(serde_json::to_value(&bare).unwrap().get("mode").is_none());
+
+        let paper: Config = serde_json::from_str(r#"{"chain_id": 137, "mode": "paper"}"#).unwrap();
+        assert_eq!(paper.mode, TradingMode::Paper);
+        assert_eq!(serde_json::to_value(&paper).unwrap()["mode"], "paper");
+    }
+
+    #[test]
+    fn endpoint_flags_override_profile() {
+        let flags = Endpoints {
+            rpc_url: Some("https://rpc.flag".into()),
+            ..Endpoints::default()
+        };
+        let profile = Endpoints {
+            rpc_url: Some("https://rpc.profile".into()),
+            clob_url: Some("https://clob.profile".into()),
+            ..Endpoints::default()
+        };
+        let merged = flags.or(profile);
+        assert_eq!(merged.rpc_url.as_deref(), Some("https://rpc.flag"));
+        assert_eq!(merged.clob_url.as_deref(), Some("https://clob.profile"));
+        assert!(merged.gamma_url.is_none());
+    }
+
+    #[test]
+    fn endpoint_override_rejects_invalid_url() {
+        let err = set_endpoint_overrides(Endpoints {
+            clob_url: Some("not a url".into()),
+            ..Endpoints::default()
+        })
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("--clob-url"), "got: {err}");
+    }
+
+    #[test]
+    fn ledger_takes_priority_over_private_key() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        set_ledger(true);
+        assert!(matches!(key_source(Some("flag_key")), KeySource::Ledger));
+        let (backend, source) = resolve_signer_backend(Some("flag_key")).unwrap();
+        assert!(matches!(backend, SignerBackend::Ledger));
+        assert!(matches!(source, KeySource::Ledger));
+        set_ledger(false);
+        let (backend, _) = resolve_signer_backend(Some("flag_key")).unwrap();
+        assert!(matches!(backend, SignerBackend::PrivateKey(k) if k == "flag_key"));
+    }
+
+    #[test]
+    fn watch_entry_matches_id_slug_or_condition() {
+        let entry = WatchEntry {
+            id: "12".into(),
+            slug: Some("will-it-rain".into()),
+            condition_id: Some("0xABCD".into()),
+            question: "Will it rain?".into(),
+        };
+        assert!(entry.matches("12"));
+        assert!(entry.matches("will-it-rain"));
+        assert!(entry.matches("0xabcd"));
+        assert!(!entry.matches("will-it-snow"));
+    }
+
+    #[test]
+    fn alert_direction_crossed_is_inclusive() {
+        let threshold: Decimal = "0.70".parse().unwrap();
+        assert!(AlertDirection::Above.crossed("0.70".parse().unwrap(), threshold));
+        assert!(!AlertDirection::Above.crossed("0.69".parse().unwrap(), threshold));
+        assert!(AlertDirection::Below.crossed("0.7".parse().unwrap(), threshold));
+        assert!(!AlertDirection::Below.crossed("0.71".parse().unwrap(), threshold));
+    }
+
+    #[test]
+    fn alert_roundtrips_with_lowercase_direction() {
+        let alert = Alert {
+            id: 1,
+            market_id: "12".into(),
+            question: "Will it rain?".into(),
+            outcome: "Yes".into(),
+            token_id: "123".into(),
+            direction: AlertDirection::Above,
+            price: "0.7".parse().unwrap(),
+        };
+        let json = serde_json::to_string(&alert).unwrap();
+        assert!(json.contains(r#""direction":"above""#), "got: {json}");
+        assert_eq!(serde_json::from_str::<Alert>(&json).unwrap(), alert);
+    }
+
+    #[test]
+    fn profile_names_reject_path_characters() {
+        assert!(validate_profile_name("trading").is_ok());
+        assert!(validate_profile_name("cold_storage-2").is_ok());
+        assert!(validate_profile_name("").is_err());
+        assert!(validate_profile_name("../etc").is_err());
+        assert!(validate_profile_name("a/b").is_err());
+    }
+
+    #[test]
+    fn active_profile_flag_overrides_env() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        unsafe { set(PROFILE_ENV_VAR, "from-env") };
+        assert_eq!(active_profile(), "from-env");
+        set_profile_override(Some("from-flag".into())).unwrap();
+        assert_eq!(active_profile(), "from-flag");
+        set_profile_override(None).unwrap();
+        unsafe { unset(PROFILE_ENV_VAR) };
+    }
+
+    #[test]
+    fn set_profile_override_rejects_invalid_name() {
+        assert!(set_profile_override(Some("../x".into())).is_err());
+    }
+
+    #[test]
+    fn legacy_config_migrates_to_default_profile() {
+        let dir = temp_dir("legacy-migrate");
+        encrypt_key_to(&dir, LEGACY_KEYSTORE_FILE, TEST_KEY, "pw").unwrap();
+        fs::write(
+            dir.join(LEGACY_CONFIG_FILE),
+            r#"{"chain_id": 137, "keystore": "keystore.json", "address": "0x1"}"#,
+        )
+        .unwrap();
+
+        assert!(migrate_legacy_layout(&dir).unwrap());
+        assert!(!dir.join(LEGACY_CONFIG_FILE).exists());
+
+        let profiles = dir.join(PROFILES_DIR);
+        let data = fs::read_to_string(profiles.join("default.json")).unwrap();
+        let config: Config = serde_json::from_str(&data).unwrap();
+        assert_eq!(config.keystore.as_deref(), Some("default.keystore.json"));
+        let key = decrypt_key_from(&profiles.join("default.keystore.json"), "pw").unwrap();
+        assert_eq!(key, TEST_KEY);
+
+        assert!(!migrate_legacy_layout(&dir).unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn config_dir_moves_once_to_the_platform_location() {
+        let root = temp_dir("config-dir-migrate");
+        let legacy = root.join(".config").join("polymarket");
+        let dir = root.join("Library").join("polymarket");
+        fs::create_dir_all(legacy.join(PROFILES_DIR)).unwrap();
+        fs::write(legacy.join(CONTACTS_FILE), "[]").unwrap();
+
+        assert!(migrate_config_dir(&legacy, &dir).unwrap());
+        assert!(!legacy.exists());
+        assert!(dir.join(CONTACTS_FILE).exists());
+        assert!(dir.join(PROFILES_DIR).is_dir());
+
+        // An existing config at the new place is never overwritten.
+        fs::create_dir_all(&legacy).unwrap();
+        assert!(!migrate_config_dir(&legacy, &dir).unwrap());
+        assert!(!migrate_config_dir(&dir, &dir).unwrap());
+        fs::remove_dir_all(&root).unwrap();
+    }
 }
@@ -0,0 +1,98 @@
+//! Confirmation before commands that move funds or can't be undone: placing
+//! and cancelling orders, transfers, redeeming, and replacing or deleting a
+//! wallet. The global `--yes` answers every prompt, for scripts.
+//!
+//! Prompts go to stderr and the answer is read from stdin, so `echo y |`
+//! works too. Without a terminal or `--yes`, stdin is at its end and the
+//! command fails instead of going ahead unasked.
+
+use std::io::{self, BufRead, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::Result;
+
+use crate::output::OutputFormat;
+
+static ASSUME_YES: AtomicBool = AtomicBool::new(false);
+
+pub fn set_assume_yes(yes: bool) {
+    ASSUME_YES.store(yes, Ordering::Relaxed);
+}
+
+pub fn assume_yes() -> bool {
+    ASSUME_YES.load(Ordering::Relaxed)
+}
+
+/// Prints what is about to happen. JSON and plain output keep stdout for the
+/// result, so it goes to stderr there.
+pub fn preview(preview: &str, output: &OutputFormat) {
+    if matches!(output, OutputFormat::Json | OutputFormat::Plain) {
+        eprintln!("{preview}");
+    } else {
+        println!("{preview}");
+    }
+}
+
+/// Prints `preview`, then asks `question` unless `--yes` was given. False
+/// means the user said no, and "Aborted." has been printed.
+pub fn confirm(preview: &str, question: &str, output: &OutputFormat) -> Result<bool> {
+    self::preview(preview, output);
+    ask(question, output)
+}
+
+/// Asks a yes/no `question` unless `--yes` was given.
+pub fn ask(question: &str, output: &OutputFormat) -> Result<bool> {
+    if assume_yes() {
+        return Ok(true);
+    }
+    let answer = read_answer(&format!("{question} [y/N] "))?;
+    let yes = is_yes(&answer);
+    if !yes {
+        self::preview("Aborted.", output);
+    }
+    Ok(yes)
+}
+
+/// Asks for `phrase` to be typed back, for steps worse than a mistyped `y`
+/// (such as printing a private key). Anything else cancels with an error.
+pub fn phrase(warning: &str, phrase: &str) -> Result<()> {
+    if assume_yes() {
+        return Ok(());
+    }
+    eprintln!("{warning}");
+    let answer = read_answer(&format!("Type '{phrase}' to continue: "))?;
+    anyhow::ensure!(
+        answer.trim() == phrase,
+        "Cancelled. Pass --yes to skip the confirmation in scripts."
+    );
+    Ok(())
+}
+
+fn read_answer(prompt: &str) -> Result<String> {
+    eprint!("{prompt}");
+    io::stderr().flush()?;
+    let mut input = String::new();
+    if io::stdin().lock().read_line(&mut input)? == 0 {
+        eprintln!();
+        anyhow::bail!("No answer on stdin. Pass --yes to confirm without a prompt.");
+    }
+    Ok(input)
+}
+
+fn is_yes(answer: &str) -> bool {
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_y_or_yes_confirms() {
+        assert!(is_yes("y\n"));
+        assert!(is_yes(" YES "));
+        assert!(!is_yes("\n"));
+        assert!(!is_yes("n"));
+        assert!(!is_yes("yep"));
+    }
+}
@@ -0,0 +1,146 @@
+//! The daemon's protocol: one JSON request per line over a Unix socket in
+//! the config directory, answered with one JSON response line.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config::{self, WorkingStop};
+
+const SOCKET_FILE: &str = "daemon.sock";
+/// Adding a stop may look up a position, so allow more than a round trip.
+const REPLY_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub const NOT_RUNNING_MSG: &str =
+    "The daemon is not running. Start it with `polymarket daemon run`.";
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum Request {
+    Status,
+    /// Work a stop; the daemon assigns its ID.
+    AddStop {
+        stop: WorkingStop,
+    },
+    ListStops,
+    CancelStop {
+        id: u32,
+    },
+    Shutdown,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Response {
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub data: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Response {
+    pub fn ok(data: impl Serialize) -> Self {
+        Self {
+            ok: true,
+            data: serde_json::to_value(data).unwrap_or_default(),
+            error: None,
+        }
+    }
+
+    pub fn error(e: &anyhow::Error) -> Self {
+        Self {
+            ok: false,
+            data: Value::Null,
+            error: Some(format!("{e:#}")),
+        }
+    }
+
+    fn into_result(self) -> Result<Value> {
+        if self.ok {
+            Ok(self.data)
+        } else {
+            anyhow::bail!(
+                "{}",
+                self.error.as_deref().unwrap_or("Daemon request failed")
+            )
+        }
+    }
+}
+
+/// What `daemon status` reports.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Status {
+    pub pid: u32,
+    pub address: String,
+    pub uptime_secs: u64,
+    pub alerts: usize,
+    pub stops: usize,
+    pub socket: String,
+}
+
+pub fn socket_path() -> Result<PathBuf> {
+    Ok(config::config_dir()?.join(SOCKET_FILE))
+}
+
+/// Sends `request` to the daemon and returns its data.
+pub async fn request(request: &Request) -> Result<Value> {
+    try_request(request)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("{NOT_RUNNING_MSG}"))
+}
+
+/// Like [`request`], but `None` when no daemon is listening.
+#[cfg(unix)]
+pub async fn try_request(request: &Request) -> Result<Option<Value>> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let Ok(stream) = tokio::net::UnixStream::connect(socket_path()?).await else {
+        return Ok(None);
+    };
+    let (read, mut write) = stream.into_split();
+    let mut line = serde_json::to_string(request)?;
+    line.push('\n');
+    write.write_all(line.as_bytes()).await?;
+
+    let mut reply = String::new();
+    tokio::time::timeout(REPLY_TIMEOUT, BufReader::new(read).read_line(&mut reply))
+        .await
+        .map_err(|_| anyhow::anyhow!("The daemon did not answer"))??;
+    anyhow::ensure!(!reply.is_empty(), "The daemon closed the connection");
+    let response: Response = serde_json::from_str(&reply)
+        .map_err(|e| anyhow::anyhow!("Invalid daemon response: {e}"))?;
+    response.into_result().map(Some)
+}
+
+#[cfg(not(unix))]
+pub async fn try_request(_request: &Request) -> Result<Option<Value>> {
+    anyhow::bail!("The daemon needs Unix domain sockets, which this platform lacks")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_are_tagged_by_cmd() {
+        let json = serde_json::to_string(&Request::CancelStop { id: 3 }).unwrap();
+        assert_eq!(json, r#"{"cmd":"cancel_stop","id":3}"#);
+        let parsed: Request = serde_json::from_str(r#"{"cmd":"status"}"#).unwrap();
+        assert_eq!(parsed, Request::Status);
+        assert!(serde_json::from_str::<Request>(r#"{"cmd":"launch"}"#).is_err());
+    }
+
+    #[test]
+    fn error_responses_become_errors() {
+        let response = Response::error(&anyhow::anyhow!("No stop with ID 9"));
+        let line = serde_json::to_string(&response).unwrap();
+        assert_eq!(line, r#"{"ok":false,"error":"No stop with ID 9"}"#);
+        let parsed: Response = serde_json::from_str(&line).unwrap();
+        let err = parsed.into_result().unwrap_err();
+        assert_eq!(err.to_string(), "No stop with ID 9");
+        assert_eq!(Response::ok(7).into_result().unwrap(), serde_json::json!(7));
+    }
+}
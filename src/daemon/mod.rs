@@ -0,0 +1,597 @@
+//! `polymarket daemon`: a long-running process that authenticates once and
+//! works stops and price alerts, so they outlive the terminal that created
+//! them. Other invocations reach it over a Unix socket (see [`ipc`]).
+
+pub mod ipc;
+pub mod service;
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use self::ipc::Request;
+use self::service::{Runner, ServiceOptions};
+use crate::config::WorkingStop;
+use crate::confirm;
+use crate::output::OutputFormat;
+use crate::output::daemon::{
+    print_daemon_status, print_service_installed, print_service_removed, print_stops,
+};
+
+#[derive(Args)]
+pub struct DaemonArgs {
+    #[command(subcommand)]
+    pub command: DaemonCommand,
+}
+
+#[derive(Subcommand)]
+pub enum DaemonCommand {
+    /// Run the daemon in the foreground until Ctrl-C or `daemon shutdown`
+    Run {
+        /// Seconds between price checks
+        #[arg(long, default_value = "5")]
+        interval: u64,
+        /// Serve Prometheus metrics at http://ADDR/metrics, e.g.
+        /// 127.0.0.1:9464
+        #[arg(long, value_name = "ADDR")]
+        metrics_addr: Option<SocketAddr>,
+        /// Serve /healthz, /status and /metrics at http://ADDR, e.g.
+        /// 127.0.0.1:9465
+        #[arg(long, value_name = "ADDR")]
+        serve: Option<SocketAddr>,
+    },
+    /// Show whether the daemon is running and what it is working
+    Status,
+    /// List stops handed to the daemon with `clob stop --daemon`
+    Stops,
+    /// Cancel a stop the daemon is working
+    Cancel {
+        /// Stop ID as shown by `daemon stops`
+        id: u32,
+    },
+    /// Ask the running daemon to exit
+    Shutdown,
+    /// Write a user service (systemd on Linux, launchd on macOS) that keeps
+    /// the daemon or alert runner going and restarts it after a failure
+    InstallService {
+        /// Which command the service runs
+        #[arg(long, value_enum, default_value_t = Runner::Daemon)]
+        runner: Runner,
+        /// Seconds between price checks (default: the runner's own)
+        #[arg(long)]
+        interval: Option<u64>,
+        /// Serve health and metrics at this address, e.g. 127.0.0.1:9465
+        #[arg(long, value_name = "ADDR")]
+        serve: Option<SocketAddr>,
+    },
+    /// Remove the service `install-service` wrote
+    UninstallService {
+        /// Which runner's service to remove
+        #[arg(long, value_enum, default_value_t = Runner::Daemon)]
+        runner: Runner,
+    },
+}
+
+pub async fn execute(
+    args: DaemonArgs,
+    output: OutputFormat,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    match args.command {
+        DaemonCommand::Run {
+            interval,
+            metrics_addr,
+            serve,
+        } => {
+            anyhow::ensure!(interval > 0, "--interval must be at least 1 second");
+            server::run(
+                Duration::from_secs(interval),
+                metrics_addr,
+                serve,
+                output,
+                private_key,
+                signature_type,
+            )
+            .await
+        }
+        DaemonCommand::Status => {
+            let status = match ipc::try_request(&Request::Status).await? {
+                Some(data) => Some(serde_json::from_value(data)?),
+                None => None,
+            };
+            print_daemon_status(status.as_ref(), &output)
+        }
+        DaemonCommand::Stops => {
+            let stops: Vec<WorkingStop> =
+                serde_json::from_value(ipc::request(&Request::ListStops).await?)?;
+            print_stops(&stops, &output)
+        }
+        DaemonCommand::Cancel { id } => {
+            ipc::request(&Request::CancelStop { id }).await?;
+            println!("Canceled stop #{id}");
+            Ok(())
+        }
+        DaemonCommand::Shutdown => {
+            ipc::request(&Request::Shutdown).await?;
+            println!("Daemon stopped");
+            Ok(())
+        }
+        DaemonCommand::InstallService {
+            runner,
+            interval,
+            serve,
+        } => {
+            anyhow::ensure!(
+                interval != Some(0),
+                crate::errors::validation("--interval must be at least 1 second")
+            );
+            let (_, path) = service::service_path(runner)?;
+            if path.exists()
+                && !confirm::ask(
+                    &format!("{} already exists. Replace it?", path.display()),
+                    &output,
+                )?
+            {
+                println!("Aborted.");
+                return Ok(());
+            }
+            let installed = service::install(&ServiceOptions {
+                runner,
+                interval,
+                serve,
+            })?;
+            print_service_installed(&installed, &output)
+        }
+        DaemonCommand::UninstallService { runner } => {
+            print_service_removed(runner, service::uninstall(runner)?.as_ref(), &output)
+        }
+    }
+}
+
+/// What became of a triggered stop's order.
+pub enum Fired {
+    /// Accepted under this order ID.
+    Placed(String),
+    /// Not sent, or turned down by the exchange; the stop stays armed and
+    /// tries again on the next check.
+    Failed(anyhow::Error),
+    /// Over the profile's risk limits. The stop is dropped.
+    Refused(anyhow::Error),
+    /// The request failed on the way, so the order may have reached the
+    /// exchange. The stop is dropped rather than risk sending it twice.
+    Unknown(anyhow::Error),
+}
+
+#[cfg(unix)]
+mod server {
+    use std::collections::HashMap;
+    use std::net::SocketAddr;
+    use std::path::Path;
+    use std::time::{Duration, Instant};
+
+    use anyhow::Result;
+    use polymarket_client_sdk::auth::Normal;
+    use polymarket_client_sdk::auth::state::Authenticated;
+    use polymarket_client_sdk::clob;
+    use polymarket_client_sdk::clob::types::{OrderStatusType, SignedOrder};
+    use polymarket_client_sdk::data::types::request::ValueRequest;
+    use polymarket_client_sdk::types::{Address, Decimal};
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::{UnixListener, UnixStream};
+
+    use super::Fired;
+    use super::ipc::{self, Request, Response};
+    use crate::auth::{self, WalletSigner};
+    use crate::clients;
+    use crate::commands::alert::{AlertChecker, fetch_midpoints};
+    use crate::commands::portfolio::fetch_open_orders;
+    use crate::commands::stop::StopOrder;
+    use crate::commands::wallet::resolve_trading_address;
+    use crate::config::{self, Notifier, WorkingStop};
+    use crate::errors::{self, ErrorClass};
+    use crate::health;
+    use crate::metrics;
+    use crate::net;
+    use crate::notify;
+    use crate::output::OutputFormat;
+    use crate::output::daemon::{print_daemon_log, print_stop_fired};
+    use crate::procs;
+
+    /// How long a client gets to send its request line.
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+    /// How often the exposure and open orders are refreshed when served.
+    const ACCOUNT_INTERVAL: Duration = Duration::from_secs(60);
+
+    struct Daemon {
+        signer: WalletSigner,
+        client: clob::Client<Authenticated<Normal>>,
+        prices: clob::Client,
+        address: Address,
+        notifiers: Vec<Notifier>,
+        alerts: AlertChecker,
+        stops: Vec<WorkingStop>,
+        started: Instant,
+        output: OutputFormat,
+        /// When the exposure and open orders served were last refreshed.
+        account_at: Option<Instant>,
+        /// Whether metrics or status are served.
+        serving: bool,
+    }
+
+    pub(super) async fn run(
+        interval: Duration,
+        metrics_addr: Option<SocketAddr>,
+        serve: Option<SocketAddr>,
+        output: OutputFormat,
+        private_key: Option<&str>,
+        signature_type: Option<&str>,
+    ) -> Result<()> {
+        let path = ipc::socket_path()?;
+        // Unlock the wallet before claiming the socket, so a failed
+        // passphrase leaves nothing behind.
+        let signer = auth::resolve_signer(private_key).await?;
+        let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+        let address = resolve_trading_address(private_key, signature_type).await?;
+        let notifiers = config::notifiers();
+        notify::validate(&notifiers)?;
+        let listener = bind(&path)?;
+        let _running = procs::register("daemon run");
+        if let Some(addr) = metrics_addr {
+            metrics::serve(addr).await?;
+        }
+        if let Some(addr) = serve {
+            health::serve(addr, "daemon").await?;
+        }
+
+        let mut daemon = Daemon {
+            signer,
+            client,
+            prices: clients::clob()?,
+            address,
+            notifiers,
+            alerts: AlertChecker::default(),
+            stops: config::load_stops()?,
+            started: Instant::now(),
+            output,
+            account_at: None,
+            serving: metrics_addr.is_some() || serve.is_some(),
+        };
+        print_daemon_log(
+            &format!(
+                "Daemon running for {address} on {} ({} stops). Ctrl-C to exit.",
+                path.display(),
+                daemon.stops.len()
+            ),
+            &daemon.output,
+        );
+
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let result = loop {
+            tokio::select! {
+                _ = ticker.tick() => daemon.check().await,
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => {
+                        if daemon.serve(stream).await {
+                            break Ok(());
+                        }
+                    }
+                    Err(e) => break Err(e.into()),
+                },
+                _ = tokio::signal::ctrl_c() => break Ok(()),
+            }
+        };
+        let _ = std::fs::remove_file(&path);
+        print_daemon_log("Daemon stopped.", &daemon.output);
+        result
+    }
+
+    /// Binds the socket, replacing one left behind by a daemon that died.
+    /// Only the owner may connect: whoever can reach the socket can trade.
+    fn bind(path: &Path) -> Result<UnixListener> {
+        use std::os::unix::fs::PermissionsExt;
+
+        if path.exists() {
+            anyhow::ensure!(
+                std::os::unix::net::UnixStream::connect(path).is_err(),
+                "The daemon is already running ({})",
+                path.display()
+            );
+            std::fs::remove_file(path)?;
+        }
+        if let Some(dir) = path.parent() {
+            config::ensure_dir(dir)?;
+        }
+        let listener = UnixListener::bind(path)
+            .map_err(|e| anyhow::anyhow!("Failed to listen on {}: {e}", path.display()))?;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+        Ok(listener)
+    }
+
+    impl Daemon {
+        /// One round of price checks: fires due alerts, then sends the
+        /// market order for every stop whose trigger was reached. A stop
+        /// whose order fails stays armed and is retried next round.
+        async fn check(&mut self) {
+            if self.serving {
+                self.refresh_account().await;
+            }
+            // Re-read so `alert add`/`remove` apply without a restart.
+            let alerts = config::load_alerts().unwrap_or_else(|e| {
+                eprintln!("{e:#}");
+                Vec::new()
+            });
+            let tokens: Vec<&str> = alerts
+                .iter()
+                .map(|a| a.token_id.as_str())
+                .chain(self.stops.iter().map(|s| s.token_id.as_str()))
+                .collect();
+            health::set_subscriptions(tokens.iter().map(ToString::to_string).collect());
+            if tokens.is_empty() {
+                return;
+            }
+            let prices = match fetch_midpoints(&self.prices, &tokens).await {
+                Ok(prices) => prices,
+                Err(e) => {
+                    eprintln!("Price check failed: {e:#}");
+                    health::set_problem(Some(format!("Price check failed: {e:#}")));
+                    return;
+                }
+            };
+            health::set_problem(None);
+            health::event();
+            if let Err(e) = self
+                .alerts
+                .check(&alerts, &prices, &self.notifiers, &self.output)
+                .await
+            {
+                eprintln!("{e:#}");
+            }
+
+            for (stop, mid) in due_stops(&self.stops, &prices) {
+                let fired = self.fire(&stop).await;
+                if let Err(e) = print_stop_fired(&stop, mid, &fired, &self.output) {
+                    eprintln!("{e:#}");
+                }
+                if let Fired::Placed(_) = fired
+                    && let Ok(order) = StopOrder::from_working(&stop)
+                {
+                    order.notify_triggered(mid).await;
+                }
+                if !matches!(fired, Fired::Failed(_)) {
+                    self.stops.retain(|s| s.id != stop.id);
+                    self.save_stops();
+                }
+            }
+        }
+
+        /// Updates the exposure metric from the wallet's position value,
+        /// and the open order count `/status` shows, at most once per
+        /// [`ACCOUNT_INTERVAL`].
+        async fn refresh_account(&mut self) {
+            if self
+                .account_at
+                .is_some_and(|at| at.elapsed() < ACCOUNT_INTERVAL)
+            {
+                return;
+            }
+            self.account_at = Some(Instant::now());
+            let request = ValueRequest::builder().user(self.address).build();
+            let value = match clients::data() {
+                Ok(client) => net::retry(|| client.value(&request)).await,
+                Err(e) => Err(e),
+            };
+            match value {
+                Ok(value) => metrics::set_exposure(value.iter().map(|v| v.value).sum()),
+                Err(e) => eprintln!("Exposure check failed: {e:#}"),
+            }
+            match fetch_open_orders(&self.client).await {
+                Ok(orders) => health::set_open_orders(orders.len()),
+                Err(e) => eprintln!("Open orders check failed: {e:#}"),
+            }
+        }
+
+        /// Sends the stop's market order on the held session.
+        async fn fire(&self, stop: &WorkingStop) -> Fired {
+            if let Err(e) = self.check_risk(stop).await {
+                return match errors::classify(&e) {
+                    ErrorClass::Validation => Fired::Refused(e),
+                    _ => Fired::Failed(e),
+                };
+            }
+            let signed = match self.sign(stop).await {
+                Ok(signed) => signed,
+                Err(e) => return Fired::Failed(e),
+            };
+            match net::once(self.client.post_order(signed)).await {
+                Ok(response) if response.success => {
+                    metrics::order_placed(response.status == OrderStatusType::Matched);
+                    Fired::Placed(response.order_id)
+                }
+                Ok(response) => Fired::Failed(anyhow::anyhow!(
+                    "{}",
+                    response
+                        .error_msg
+                        .filter(|m| !m.is_empty())
+                        .unwrap_or_else(|| "Order rejected".into())
+                )),
+                Err(e) => Fired::Unknown(e),
+            }
+        }
+
+        async fn check_risk(&self, stop: &WorkingStop) -> Result<()> {
+            let owner = async { Ok(self.address) };
+            StopOrder::from_working(stop)?
+                .check_risk(&self.client, owner, stop.override_risk)
+                .await
+        }
+
+        async fn sign(&self, stop: &WorkingStop) -> Result<SignedOrder> {
+            let order = StopOrder::from_working(stop)?;
+            let signable = self
+                .client
+                .market_order()
+                .token_id(order.token)
+                .side(order.side)
+                .amount(order.amount(self.address).await?)
+                .order_type(order.order_type.clone())
+                .build()
+                .await?;
+            auth::sign_order(&self.client, &self.signer, signable).await
+        }
+
+        fn save_stops(&self) {
+            if let Err(e) = config::save_stops(&self.stops) {
+                eprintln!("{e:#}");
+            }
+        }
+
+        /// Answers one client. Returns true when it asked the daemon to exit.
+        async fn serve(&mut self, stream: UnixStream) -> bool {
+            let (read, mut write) = stream.into_split();
+            let mut line = String::new();
+            let read =
+                tokio::time::timeout(REQUEST_TIMEOUT, BufReader::new(read).read_line(&mut line))
+                    .await;
+            if !matches!(read, Ok(Ok(n)) if n > 0) {
+                return false;
+            }
+
+            let request = serde_json::from_str::<Request>(&line)
+                .map_err(|e| anyhow::anyhow!("Invalid request: {e}"));
+            let shutdown = matches!(request, Ok(Request::Shutdown));
+            let response = match request {
+                Ok(request) => self.handle(request).await,
+                Err(e) => Err(e),
+            }
+            .map_or_else(|e| Response::error(&e), Response::ok);
+
+            let mut reply = serde_json::to_string(&response).unwrap_or_default();
+            reply.push('\n');
+            if let Err(e) = write.write_all(reply.as_bytes()).await {
+                eprintln!("Failed to answer client: {e}");
+            }
+            shutdown
+        }
+
+        async fn handle(&mut self, request: Request) -> Result<serde_json::Value> {
+            Ok(match request {
+                Request::Status => serde_json::to_value(ipc::Status {
+                    pid: std::process::id(),
+                    address: self.address.to_string(),
+                    uptime_secs: self.started.elapsed().as_secs(),
+                    alerts: config::load_alerts().map(|a| a.len()).unwrap_or(0),
+                    stops: self.stops.len(),
+                    socket: ipc::socket_path()?.display().to_string(),
+                })?,
+                Request::AddStop { mut stop } => {
+                    let order = StopOrder::from_working(&stop)?;
+                    order.validate()?;
+                    // Fail now rather than when the stop fires.
+                    order.amount(self.address).await?;
+                    stop.id = self.stops.iter().map(|s| s.id).max().unwrap_or(0) + 1;
+                    self.stops.push(stop.clone());
+                    config::save_stops(&self.stops)?;
+                    print_daemon_log(
+                        &format!(
+                            "Stop #{}: {} when the midpoint reaches {}",
+                            stop.id, stop.side, stop.trigger
+                        ),
+                        &self.output,
+                    );
+                    serde_json::to_value(stop)?
+                }
+                Request::ListStops => serde_json::to_value(&self.stops)?,
+                Request::CancelStop { id } => {
+                    let before = self.stops.len();
+                    self.stops.retain(|s| s.id != id);
+                    anyhow::ensure!(self.stops.len() < before, "No stop with ID {id}");
+                    config::save_stops(&self.stops)?;
+                    print_daemon_log(&format!("Stop #{id} canceled"), &self.output);
+                    serde_json::Value::Null
+                }
+                Request::Shutdown => serde_json::Value::Null,
+            })
+        }
+    }
+
+    /// Stops whose trigger the midpoint has reached, with that midpoint.
+    fn due_stops(
+        stops: &[WorkingStop],
+        prices: &HashMap<String, Decimal>,
+    ) -> Vec<(WorkingStop, Decimal)> {
+        stops
+            .iter()
+            .filter_map(|s| {
+                let mid = *prices.get(&s.token_id)?;
+                s.direction
+                    .crossed(mid, s.trigger)
+                    .then(|| (s.clone(), mid))
+            })
+            .collect()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use polymarket_client_sdk::clob::types::{OrderType, Side};
+
+        use super::*;
+        use crate::config::AlertDirection;
+
+        fn stop(id: u32, token: &str, direction: AlertDirection, trigger: &str) -> WorkingStop {
+            WorkingStop {
+                id,
+                token_id: token.into(),
+                side: Side::Sell,
+                trigger: trigger.parse().unwrap(),
+                size: None,
+                order_type: OrderType::FOK,
+                direction,
+                override_risk: false,
+            }
+        }
+
+        #[test]
+        fn due_stops_follow_each_direction() {
+            let stops = [
+                stop(1, "10", AlertDirection::Below, "0.30"),
+                stop(2, "10", AlertDirection::Above, "0.60"),
+                stop(3, "20", AlertDirection::Below, "0.50"),
+                stop(4, "30", AlertDirection::Below, "0.90"),
+            ];
+            let prices = HashMap::from([
+                ("10".to_string(), "0.30".parse().unwrap()),
+                ("20".to_string(), "0.55".parse().unwrap()),
+            ]);
+            let due: Vec<u32> = due_stops(&stops, &prices)
+                .iter()
+                .map(|(s, _)| s.id)
+                .collect();
+            // Token 30 has no price this round, so its stop waits.
+            assert_eq!(due, [1]);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod server {
+    use std::time::Duration;
+
+    use anyhow::Result;
+
+    use crate::output::OutputFormat;
+
+    pub(super) async fn run(
+        _interval: Duration,
+        _metrics_addr: Option<std::net::SocketAddr>,
+        _serve: Option<std::net::SocketAddr>,
+        _output: OutputFormat,
+        _private_key: Option<&str>,
+        _signature_type: Option<&str>,
+    ) -> Result<()> {
+        anyhow::bail!("The daemon needs Unix domain sockets, which this platform lacks")
+    }
+}
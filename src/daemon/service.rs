@@ -0,0 +1,391 @@
+//! `daemon install-service`: a per-user systemd unit (Linux) or launchd
+//! agent (macOS) that keeps `daemon run` or `alert run` going across
+//! logouts and reboots, restarting it when it exits with an error.
+//!
+//! The service runs this binary with the profile and config directory in
+//! effect when it was installed. Secrets are never written into it: a
+//! keystore passphrase goes in `service.env` in the config directory
+//! (systemd), or the profile should hold a plain key or Ledger (launchd).
+
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::config;
+
+/// Environment variables the service carries over from the install, when
+/// set. Keys and passphrases are deliberately not among them.
+const FORWARDED_ENV: [&str; 3] = [
+    "POLYMARKET_CONFIG_DIR",
+    "POLYMARKET_PROFILE",
+    "POLYMARKET_SIGNATURE_TYPE",
+];
+
+/// Optional file of `KEY=value` lines the systemd unit loads, for secrets.
+const ENV_FILE: &str = "service.env";
+
+/// Seconds to wait before restarting a failed runner.
+const RESTART_SECS: u32 = 10;
+
+/// Which long-running command the service runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Runner {
+    /// `daemon run`: stops and alerts on one session
+    Daemon,
+    /// `alert run`: price alerts only, no wallet needed
+    Alerts,
+}
+
+impl Runner {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Daemon => "daemon",
+            Self::Alerts => "alerts",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Self::Daemon => "Polymarket CLI daemon (stops and alerts)",
+            Self::Alerts => "Polymarket CLI price alerts",
+        }
+    }
+
+    fn command(self) -> [&'static str; 2] {
+        match self {
+            Self::Daemon => ["daemon", "run"],
+            Self::Alerts => ["alert", "run"],
+        }
+    }
+}
+
+/// How the service should run its command.
+pub struct ServiceOptions {
+    pub runner: Runner,
+    pub interval: Option<u64>,
+    pub serve: Option<SocketAddr>,
+}
+
+/// A service file written, and how to start or stop it.
+pub struct Service {
+    pub manager: &'static str,
+    pub path: PathBuf,
+    pub next_steps: Vec<String>,
+}
+
+/// The service's command line: this binary, the active profile, and the
+/// runner with its options.
+fn exec_args(exe: &Path, options: &ServiceOptions) -> Vec<String> {
+    let mut args = vec![exe.display().to_string()];
+    if let Some(profile) = config::profile_override() {
+        args.extend(["--profile".into(), profile]);
+    }
+    args.extend(options.runner.command().map(String::from));
+    if let Some(interval) = options.interval {
+        args.extend(["--interval".into(), interval.to_string()]);
+    }
+    if let Some(addr) = options.serve {
+        let flag = match options.runner {
+            Runner::Daemon => "--serve",
+            Runner::Alerts => "--metrics-addr",
+        };
+        args.extend([flag.into(), addr.to_string()]);
+    }
+    args
+}
+
+fn forwarded_env() -> Vec<(&'static str, String)> {
+    FORWARDED_ENV
+        .iter()
+        .filter_map(|k| Some((*k, std::env::var(k).ok().filter(|v| !v.is_empty())?)))
+        .collect()
+}
+
+/// Quotes an argument for a systemd `ExecStart=` line.
+fn systemd_quote(arg: &str) -> String {
+    if !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "/._-:=@,+".contains(c))
+    {
+        return arg.to_string();
+    }
+    let escaped = arg
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('%', "%%")
+        .replace('$', "$$");
+    format!("\"{escaped}\"")
+}
+
+fn systemd_unit(
+    description: &str,
+    args: &[String],
+    env: &[(&str, String)],
+    env_file: &Path,
+) -> String {
+    let mut unit = String::new();
+    let _ = writeln!(unit, "[Unit]");
+    let _ = writeln!(unit, "Description={description}");
+    let _ = writeln!(unit, "Wants=network-online.target");
+    let _ = writeln!(unit, "After=network-online.target");
+    let _ = writeln!(unit);
+    let _ = writeln!(unit, "[Service]");
+    let _ = writeln!(unit, "Type=simple");
+    let exec: Vec<String> = args.iter().map(|a| systemd_quote(a)).collect();
+    let _ = writeln!(unit, "ExecStart={}", exec.join(" "));
+    for (key, value) in env {
+        let _ = writeln!(
+            unit,
+            "Environment={}",
+            systemd_quote(&format!("{key}={value}"))
+        );
+    }
+    // The leading '-' makes the file optional.
+    let _ = writeln!(
+        unit,
+        "EnvironmentFile=-{}",
+        systemd_quote(&env_file.display().to_string())
+    );
+    let _ = writeln!(unit, "Restart=on-failure");
+    let _ = writeln!(unit, "RestartSec={RESTART_SECS}");
+    let _ = writeln!(unit);
+    let _ = writeln!(unit, "[Install]");
+    let _ = writeln!(unit, "WantedBy=default.target");
+    unit
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn launchd_plist(label: &str, args: &[String], env: &[(&str, String)], log: &Path) -> String {
+    let mut plist = String::new();
+    let _ = writeln!(plist, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = writeln!(
+        plist,
+        r#"<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">"#
+    );
+    let _ = writeln!(plist, r#"<plist version="1.0">"#);
+    let _ = writeln!(plist, "<dict>");
+    let _ = writeln!(
+        plist,
+        "  <key>Label</key>\n  <string>{}</string>",
+        xml_escape(label)
+    );
+    let _ = writeln!(plist, "  <key>ProgramArguments</key>\n  <array>");
+    for arg in args {
+        let _ = writeln!(plist, "    <string>{}</string>", xml_escape(arg));
+    }
+    let _ = writeln!(plist, "  </array>");
+    if !env.is_empty() {
+        let _ = writeln!(plist, "  <key>EnvironmentVariables</key>\n  <dict>");
+        for (key, value) in env {
+            let _ = writeln!(
+                plist,
+                "    <key>{key}</key>\n    <string>{}</string>",
+                xml_escape(value)
+            );
+        }
+        let _ = writeln!(plist, "  </dict>");
+    }
+    let _ = writeln!(plist, "  <key>RunAtLoad</key>\n  <true/>");
+    // Restart after a crash or error exit, not after a clean shutdown.
+    let _ = writeln!(
+        plist,
+        "  <key>KeepAlive</key>\n  <dict>\n    <key>SuccessfulExit</key>\n    <false/>\n  </dict>"
+    );
+    let _ = writeln!(
+        plist,
+        "  <key>ThrottleInterval</key>\n  <integer>{RESTART_SECS}</integer>"
+    );
+    let log = xml_escape(&log.display().to_string());
+    let _ = writeln!(
+        plist,
+        "  <key>StandardOutPath</key>\n  <string>{log}</string>"
+    );
+    let _ = writeln!(
+        plist,
+        "  <key>StandardErrorPath</key>\n  <string>{log}</string>"
+    );
+    let _ = writeln!(plist, "</dict>");
+    let _ = writeln!(plist, "</plist>");
+    plist
+}
+
+fn systemd_unit_name(runner: Runner) -> String {
+    format!("polymarket-{}.service", runner.name())
+}
+
+fn launchd_label(runner: Runner) -> String {
+    format!("com.polymarket.cli.{}", runner.name())
+}
+
+/// Where the service file for `runner` goes on this platform, and which
+/// service manager reads it.
+pub fn service_path(runner: Runner) -> Result<(&'static str, PathBuf)> {
+    if cfg!(target_os = "macos") {
+        let home = dirs::home_dir().context("Could not determine the home directory")?;
+        Ok((
+            "launchd",
+            home.join("Library/LaunchAgents")
+                .join(format!("{}.plist", launchd_label(runner))),
+        ))
+    } else if cfg!(target_os = "linux") {
+        let dir = dirs::config_dir().context("Could not determine the config directory")?;
+        Ok((
+            "systemd",
+            dir.join("systemd/user").join(systemd_unit_name(runner)),
+        ))
+    } else {
+        anyhow::bail!("Services can be installed on Linux (systemd) and macOS (launchd) only")
+    }
+}
+
+/// Writes the service file for `options`, replacing any already there.
+pub fn install(options: &ServiceOptions) -> Result<Service> {
+    let (manager, path) = service_path(options.runner)?;
+    let exe = std::env::current_exe().context("Failed to determine the executable's path")?;
+    let args = exec_args(&exe, options);
+    let env = forwarded_env();
+    let config_dir = config::config_dir()?;
+    let (contents, next_steps) = if manager == "launchd" {
+        let label = launchd_label(options.runner);
+        let log = config_dir.join(format!("{}.log", options.runner.name()));
+        let path = path.display();
+        (
+            launchd_plist(&label, &args, &env, &log),
+            vec![
+                format!("launchctl bootstrap gui/$(id -u) {path}"),
+                format!("launchctl print gui/$(id -u)/{label}"),
+            ],
+        )
+    } else {
+        let unit = systemd_unit_name(options.runner);
+        (
+            systemd_unit(
+                options.runner.description(),
+                &args,
+                &env,
+                &config_dir.join(ENV_FILE),
+            ),
+            vec![
+                "systemctl --user daemon-reload".into(),
+                format!("systemctl --user enable --now {unit}"),
+                format!("journalctl --user -u {unit} -f"),
+            ],
+        )
+    };
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    std::fs::write(&path, contents)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(Service {
+        manager,
+        path,
+        next_steps,
+    })
+}
+
+/// Removes the service file for `runner`. Returns it with the commands
+/// that stop the running service, or `None` if none was installed.
+pub fn uninstall(runner: Runner) -> Result<Option<Service>> {
+    let (manager, path) = service_path(runner)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let next_steps = if manager == "launchd" {
+        vec![format!(
+            "launchctl bootout gui/$(id -u)/{}",
+            launchd_label(runner)
+        )]
+    } else {
+        vec![
+            format!(
+                "systemctl --user disable --now {}",
+                systemd_unit_name(runner)
+            ),
+            "systemctl --user daemon-reload".into(),
+        ]
+    };
+    std::fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    Ok(Some(Service {
+        manager,
+        path,
+        next_steps,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args() -> Vec<String> {
+        [
+            "/opt/polymarket bin/polymarket",
+            "daemon",
+            "run",
+            "--interval",
+            "5",
+        ]
+        .map(String::from)
+        .to_vec()
+    }
+
+    #[test]
+    fn systemd_unit_quotes_what_needs_it_and_restarts_on_failure() {
+        let unit = systemd_unit(
+            "Test",
+            &args(),
+            &[("POLYMARKET_PROFILE", "bot 1".into())],
+            Path::new("/home/u/.config/polymarket/service.env"),
+        );
+        assert!(
+            unit.contains(r#"ExecStart="/opt/polymarket bin/polymarket" daemon run --interval 5"#)
+        );
+        assert!(unit.contains(r#"Environment="POLYMARKET_PROFILE=bot 1""#));
+        assert!(unit.contains("EnvironmentFile=-/home/u/.config/polymarket/service.env"));
+        assert!(unit.contains("Restart=on-failure\nRestartSec=10"));
+        assert_eq!(systemd_quote("100%"), r#""100%%""#);
+    }
+
+    #[test]
+    fn launchd_plist_escapes_and_keeps_alive_after_errors() {
+        let plist = launchd_plist(
+            "com.polymarket.cli.daemon",
+            &args(),
+            &[("POLYMARKET_CONFIG_DIR", "/tmp/a&b".into())],
+            Path::new("/tmp/daemon.log"),
+        );
+        assert!(plist.contains("<string>/opt/polymarket bin/polymarket</string>"));
+        assert!(plist.contains("<string>/tmp/a&amp;b</string>"));
+        assert!(plist.contains("<key>SuccessfulExit</key>\n    <false/>"));
+    }
+
+    #[test]
+    fn exec_args_pass_the_runner_options() {
+        let options = ServiceOptions {
+            runner: Runner::Alerts,
+            interval: Some(60),
+            serve: Some("127.0.0.1:9464".parse().unwrap()),
+        };
+        assert_eq!(
+            exec_args(Path::new("/bin/pm"), &options)[1..],
+            [
+                "alert",
+                "run",
+                "--interval",
+                "60",
+                "--metrics-addr",
+                "127.0.0.1:9464"
+            ]
+        );
+    }
+}
@@ -0,0 +1,449 @@
+//! The local SQLite database, `polymarket.db` in the config directory. It
+//! holds the alerts, the paper account, the `sync` index and, with
+//! `record --db`, recorded books and trades. The other stores stay JSON
+//! files of their own.
+//!
+//! The tables are created and changed by [`MIGRATIONS`], which run in
+//! order, once each, whenever a connection is opened on a database that
+//! hasn't had them; the schema version is SQLite's `user_version`. A change
+//! to a table appends a migration rather than editing an old one.
+//!
+//! Each table is read and written by the module that owns it. Decimals,
+//! token IDs and addresses are kept as text, so nothing is rounded.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use rusqlite::types::Type;
+use rusqlite::{Connection, Row, Transaction, TransactionBehavior};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::cache;
+use crate::commands::sync::{self, SyncIndex};
+use crate::config::{self, Alert};
+use crate::paper::Account;
+
+const DB_FILE: &str = "polymarket.db";
+/// How long a write waits for another process's to finish.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A step from one schema version to the next. `run` gets the config
+/// directory too, for migrations that bring in files, and returns the files
+/// it took in; they are renamed with `.imported` once the migration has
+/// committed.
+struct Migration {
+    version: u32,
+    description: &'static str,
+    run: fn(&Transaction, &Path) -> Result<Vec<PathBuf>>,
+}
+
+/// In order; the last one's version is the current schema.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Create the alerts, paper account, sync index and recordings tables",
+        run: |tx, _| {
+            tx.execute_batch(SCHEMA_V1)?;
+            Ok(Vec::new())
+        },
+    },
+    Migration {
+        version: 2,
+        description: "Import alerts.json, paper.json and the index/ directory",
+        run: import_json_stores,
+    },
+];
+
+const SCHEMA_V1: &str = "
+CREATE TABLE alerts (
+    id INTEGER PRIMARY KEY,
+    market_id TEXT NOT NULL,
+    question TEXT NOT NULL,
+    outcome TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    direction TEXT NOT NULL,
+    price TEXT NOT NULL
+);
+CREATE TABLE paper_account (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    balance TEXT NOT NULL,
+    starting_balance TEXT NOT NULL,
+    next_id INTEGER NOT NULL
+);
+CREATE TABLE paper_positions (
+    token_id TEXT PRIMARY KEY,
+    size TEXT NOT NULL,
+    avg_price TEXT NOT NULL
+);
+CREATE TABLE paper_orders (
+    id TEXT PRIMARY KEY,
+    token_id TEXT NOT NULL,
+    side TEXT NOT NULL,
+    price TEXT NOT NULL,
+    size TEXT NOT NULL,
+    filled TEXT NOT NULL,
+    order_type TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE paper_fills (
+    seq INTEGER PRIMARY KEY,
+    order_id TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    side TEXT NOT NULL,
+    price TEXT NOT NULL,
+    size TEXT NOT NULL,
+    timestamp TEXT NOT NULL
+);
+CREATE TABLE sync_wallets (
+    address TEXT PRIMARY KEY,
+    synced_to INTEGER
+);
+CREATE TABLE sync_events (
+    seq INTEGER PRIMARY KEY,
+    address TEXT NOT NULL REFERENCES sync_wallets (address),
+    block INTEGER NOT NULL,
+    log_index INTEGER NOT NULL,
+    timestamp INTEGER NOT NULL,
+    tx TEXT NOT NULL,
+    kind TEXT NOT NULL
+);
+CREATE INDEX sync_events_address ON sync_events (address, seq);
+CREATE TABLE recordings (
+    seq INTEGER PRIMARY KEY,
+    recorded_at TEXT NOT NULL,
+    type TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    record TEXT NOT NULL
+);
+CREATE INDEX recordings_recorded_at ON recordings (recorded_at);
+";
+
+/// The tables `db stats` counts, in the order it lists them.
+const TABLES: &[&str] = &[
+    "alerts",
+    "paper_account",
+    "paper_positions",
+    "paper_orders",
+    "paper_fills",
+    "sync_wallets",
+    "sync_events",
+    "recordings",
+];
+
+pub fn path() -> Result<PathBuf> {
+    Ok(config::config_dir()?.join(DB_FILE))
+}
+
+/// Opens the database, creating it and bringing it up to the current
+/// schema first if need be.
+pub fn open() -> Result<Connection> {
+    let dir = config::ensure_dir(&config::config_dir()?)?;
+    open_in(&dir, MIGRATIONS)
+}
+
+fn open_in(dir: &Path, migrations: &[Migration]) -> Result<Connection> {
+    let path = dir.join(DB_FILE);
+    let mut conn =
+        Connection::open(&path).with_context(|| format!("Failed to open {}", path.display()))?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    for description in migrate(&mut conn, dir, migrations)? {
+        tracing::debug!("Migrated {}: {description}", path.display());
+    }
+    Ok(conn)
+}
+
+fn user_version(conn: &Connection) -> Result<u32> {
+    Ok(conn.pragma_query_value(None, "user_version", |row| row.get(0))?)
+}
+
+/// Runs the migrations the database hasn't had, all in one transaction, so
+/// a failed one leaves it as it was. Returns the descriptions of those run.
+fn migrate(
+    conn: &mut Connection,
+    dir: &Path,
+    migrations: &[Migration],
+) -> Result<Vec<&'static str>> {
+    let path = dir.join(DB_FILE);
+    let latest = migrations.last().map_or(0, |m| m.version);
+    if user_version(conn)? == latest {
+        return Ok(Vec::new());
+    }
+    // Taking the write lock first keeps two processes from both migrating.
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    let from = user_version(&tx)?;
+    anyhow::ensure!(
+        from <= latest,
+        "{} is at schema version {from}, newer than this CLI's {latest}; upgrade polymarket to use it",
+        path.display()
+    );
+    let (mut ran, mut imported) = (Vec::new(), Vec::new());
+    for m in migrations.iter().filter(|m| m.version > from) {
+        imported.extend((m.run)(&tx, dir).map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to migrate {} to schema version {}: {e:#}",
+                path.display(),
+                m.version
+            )
+        })?);
+        tx.pragma_update(None, "user_version", m.version)?;
+        ran.push(m.description);
+    }
+    tx.commit()?;
+    // Kept rather than deleted, in case something was read wrong; they can
+    // be deleted by hand.
+    for path in imported {
+        let mut to = path.clone().into_os_string();
+        to.push(".imported");
+        fs::rename(&path, &to).with_context(|| format!("Failed to rename {}", path.display()))?;
+    }
+    Ok(ran)
+}
+
+/// Copies the stores kept as JSON before the database into it.
+fn import_json_stores(tx: &Transaction, dir: &Path) -> Result<Vec<PathBuf>> {
+    fn read<T: DeserializeOwned>(path: &Path) -> Result<Option<T>> {
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let data = fs::read_to_string(path)?;
+        serde_json::from_str(&data)
+            .map(Some)
+            .with_context(|| format!("Invalid {}", path.display()))
+    }
+    let mut imported = Vec::new();
+
+    let path = dir.join("alerts.json");
+    if let Some(alerts) = read::<Vec<Alert>>(&path)? {
+        config::write_alerts(tx, &alerts)?;
+        imported.push(path);
+    }
+    let path = dir.join("paper.json");
+    if let Some(account) = read::<Account>(&path)? {
+        account.write(tx)?;
+        imported.push(path);
+    }
+    if let Ok(entries) = fs::read_dir(dir.join("index")) {
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_none_or(|e| e != "json") {
+                continue;
+            }
+            if let Some(index) = read::<SyncIndex>(&path)? {
+                sync::write_index(tx, index.address, index.synced_to, &index.events)?;
+                imported.push(path);
+            }
+        }
+    }
+    // Where the JSON stores recorded their version; the database has its own.
+    let path = dir.join("schema_version");
+    if path.is_file() {
+        imported.push(path);
+    }
+    Ok(imported)
+}
+
+/// A database with the current schema that lives only as long as the
+/// connection, for tests of the modules that own the tables.
+#[cfg(test)]
+pub fn open_in_memory() -> Connection {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrate(&mut conn, Path::new("/nonexistent"), MIGRATIONS).unwrap();
+    conn
+}
+
+/// Reads a column kept as text with `FromStr`, such as a decimal or a
+/// token ID.
+pub fn parsed<T>(row: &Row, idx: usize) -> rusqlite::Result<T>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    let text: String = row.get(idx)?;
+    text.parse()
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(idx, Type::Text, Box::new(e)))
+}
+
+/// The name a unit enum such as a side serializes to, e.g. `BUY`.
+pub fn name<T: Serialize>(value: &T) -> Result<String> {
+    match serde_json::to_value(value)? {
+        serde_json::Value::String(name) => Ok(name),
+        other => anyhow::bail!("{other} is not a name"),
+    }
+}
+
+/// Reads a column written with [`name`], or as JSON with [`json`].
+pub fn named<T: DeserializeOwned>(row: &Row, idx: usize) -> rusqlite::Result<T> {
+    let text: String = row.get(idx)?;
+    serde_json::from_value(serde_json::Value::String(text))
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(idx, Type::Text, Box::new(e)))
+}
+
+/// Reads a column holding a JSON document.
+pub fn json<T: DeserializeOwned>(row: &Row, idx: usize) -> rusqlite::Result<T> {
+    let text: String = row.get(idx)?;
+    serde_json::from_str(&text)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(idx, Type::Text, Box::new(e)))
+}
+
+/// The database's size and what is in it.
+#[derive(Debug)]
+pub struct Stats {
+    pub path: PathBuf,
+    pub version: u32,
+    pub bytes: u64,
+    /// Bytes in free pages, which `vacuum` gives back.
+    pub free_bytes: u64,
+    /// Each table with its row count.
+    pub tables: Vec<(&'static str, u64)>,
+}
+
+pub fn stats() -> Result<Stats> {
+    let conn = open()?;
+    let page_size: u64 = conn.pragma_query_value(None, "page_size", |row| row.get(0))?;
+    let pages: u64 = conn.pragma_query_value(None, "page_count", |row| row.get(0))?;
+    let free: u64 = conn.pragma_query_value(None, "freelist_count", |row| row.get(0))?;
+    let tables = TABLES
+        .iter()
+        .map(|&table| {
+            let rows = conn.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+                row.get(0)
+            })?;
+            Ok((table, rows))
+        })
+        .collect::<Result<_>>()?;
+    Ok(Stats {
+        path: path()?,
+        version: user_version(&conn)?,
+        bytes: page_size * pages,
+        free_bytes: page_size * free,
+        tables,
+    })
+}
+
+/// What `vacuum` removed.
+#[derive(Debug, Default)]
+pub struct Vacuumed {
+    pub stale_cache_entries: usize,
+    pub bytes_freed: u64,
+}
+
+/// Drops cache entries past their TTL and rebuilds the database without
+/// its free pages. Rows are left alone: the paper account and the index
+/// are the user's even when old.
+pub fn vacuum() -> Result<Vacuumed> {
+    let (stale_cache_entries, cache_bytes) = cache::prune()?;
+    let conn = open()?;
+    let size = |conn: &Connection| -> Result<u64> {
+        let page_size: u64 = conn.pragma_query_value(None, "page_size", |row| row.get(0))?;
+        let pages: u64 = conn.pragma_query_value(None, "page_count", |row| row.get(0))?;
+        Ok(page_size * pages)
+    };
+    let before = size(&conn)?;
+    conn.execute_batch("VACUUM")
+        .context("Failed to vacuum the database")?;
+    let after = size(&conn)?;
+    Ok(Vacuumed {
+        stale_cache_entries,
+        bytes_freed: cache_bytes + before.saturating_sub(after),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("polymarket-db-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn migrations_run_once_and_in_order() {
+        let dir = temp_dir("migrate");
+        let migrations = [
+            Migration {
+                version: 1,
+                description: "one",
+                run: |tx, _| {
+                    tx.execute_batch("CREATE TABLE log (step TEXT)")?;
+                    Ok(Vec::new())
+                },
+            },
+            Migration {
+                version: 2,
+                description: "two",
+                run: |tx, _| {
+                    tx.execute_batch("INSERT INTO log VALUES ('2')")?;
+                    Ok(Vec::new())
+                },
+            },
+        ];
+        let mut conn = open_in(&dir, &migrations).unwrap();
+        assert_eq!(user_version(&conn).unwrap(), 2);
+        assert!(migrate(&mut conn, &dir, &migrations).unwrap().is_empty());
+        let rows: u32 = conn
+            .query_row("SELECT COUNT(*) FROM log", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(rows, 1);
+        assert!(migrate(&mut conn, &dir, &migrations[..1]).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn a_failed_migration_leaves_the_database_as_it_was() {
+        let dir = temp_dir("rollback");
+        let migrations = [
+            Migration {
+                version: 1,
+                description: "one",
+                run: |tx, _| {
+                    tx.execute_batch("CREATE TABLE t (x)")?;
+                    Ok(Vec::new())
+                },
+            },
+            Migration {
+                version: 2,
+                description: "broken",
+                run: |_, _| anyhow::bail!("broken"),
+            },
+        ];
+        assert!(open_in(&dir, &migrations).is_err());
+        let conn = open_in(&dir, &migrations[..0]).unwrap();
+        assert_eq!(user_version(&conn).unwrap(), 0);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn json_stores_are_imported_once() {
+        let dir = temp_dir("import");
+        fs::write(
+            dir.join("alerts.json"),
+            r#"[{"id":3,"market_id":"1","question":"Q?","outcome":"Yes","token_id":"7","direction":"above","price":"0.6"}]"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("paper.json"),
+            r#"{"balance":"900","starting_balance":"1000","positions":{"7":{"size":"10","avg_price":"0.5"}}}"#,
+        )
+        .unwrap();
+        fs::write(dir.join("schema_version"), "1\n").unwrap();
+        let conn = open_in(&dir, MIGRATIONS).unwrap();
+        assert_eq!(user_version(&conn).unwrap(), MIGRATIONS.len() as u32);
+
+        let alerts = config::read_alerts(&conn).unwrap();
+        assert_eq!((alerts.len(), alerts[0].id), (1, 3));
+        let account = Account::read(&conn).unwrap().unwrap();
+        assert_eq!(account.balance.to_string(), "900");
+        assert_eq!(account.positions["7"].size.to_string(), "10");
+        assert!(!dir.join("alerts.json").exists());
+        assert!(dir.join("alerts.json.imported").exists());
+        assert!(dir.join("schema_version.imported").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,195 @@
+//! How a failed command is reported: `Error: ...` on stderr, or under
+//! `-o json` an `{"error": {"code": ..., "message": ...}}` object on stdout,
+//! with an exit code per class of failure so scripts can tell a bad flag
+//! from a dropped connection.
+
+use std::fmt;
+
+use alloy::transports::{RpcError, TransportErrorKind};
+use polymarket_client_sdk::error::{Kind, Status, StatusCode};
+use serde_json::json;
+
+use crate::output::{self, OutputFormat};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorClass {
+    /// Anything not covered below, such as an unreadable config file.
+    General,
+    /// Bad input, rejected by the CLI or by the API.
+    Validation,
+    /// No wallet, a watch-only wallet, or credentials the API refused.
+    Auth,
+    /// A timeout, a connection failure, rate limiting or a server error.
+    Network,
+    /// Not enough USDC, shares or gas for the request.
+    InsufficientBalance,
+}
+
+impl ErrorClass {
+    /// The `code` in JSON error output.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::General => "error",
+            Self::Validation => "validation",
+            Self::Auth => "auth",
+            Self::Network => "network",
+            Self::InsufficientBalance => "insufficient_balance",
+        }
+    }
+
+    /// 2 matches the exit code of a usage error caught while parsing flags.
+    pub fn exit_code(self) -> u8 {
+        match self {
+            Self::General => 1,
+            Self::Validation => 2,
+            Self::Auth => 3,
+            Self::Network => 4,
+            Self::InsufficientBalance => 5,
+        }
+    }
+}
+
+/// An error raised by the CLI with its class stated, for failures that
+/// can't be told apart by their cause.
+#[derive(Debug)]
+struct Failure {
+    class: ErrorClass,
+    message: String,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Failure {}
+
+fn failure(class: ErrorClass, message: impl fmt::Display) -> anyhow::Error {
+    Failure {
+        class,
+        message: message.to_string(),
+    }
+    .into()
+}
+
+pub fn validation(message: impl fmt::Display) -> anyhow::Error {
+    failure(ErrorClass::Validation, message)
+}
+
+pub fn auth(message: impl fmt::Display) -> anyhow::Error {
+    failure(ErrorClass::Auth, message)
+}
+
+pub fn insufficient_balance(message: impl fmt::Display) -> anyhow::Error {
+    failure(ErrorClass::InsufficientBalance, message)
+}
+
+/// Phrases the CLOB and the RPC node use when a wallet can't cover a
+/// request; neither gives a code for it.
+const BALANCE_PHRASES: &[&str] = &[
+    "not enough balance",
+    "insufficient balance",
+    "insufficient funds",
+];
+
+/// The class of `err`, from the first cause in its chain that says.
+pub fn classify(err: &anyhow::Error) -> ErrorClass {
+    let text = format!("{err:#}").to_lowercase();
+    if BALANCE_PHRASES.iter().any(|p| text.contains(p)) {
+        return ErrorClass::InsufficientBalance;
+    }
+    err.chain()
+        .find_map(|e| {
+            if let Some(f) = e.downcast_ref::<Failure>() {
+                return Some(f.class);
+            }
+            if let Some(status) = e.downcast_ref::<Status>() {
+                return Some(status_class(status.status_code));
+            }
+            if let Some(e) = e.downcast_ref::<polymarket_client_sdk::error::Error>() {
+                match e.kind() {
+                    Kind::Validation => return Some(ErrorClass::Validation),
+                    Kind::Geoblock => return Some(ErrorClass::Auth),
+                    Kind::WebSocket => return Some(ErrorClass::Network),
+                    _ => {}
+                }
+            }
+            if e.downcast_ref::<reqwest::Error>()
+                .is_some_and(|e| e.is_timeout() || e.is_connect() || e.is_request())
+                || e.downcast_ref::<RpcError<TransportErrorKind>>()
+                    .is_some_and(RpcError::is_transport_error)
+                || e.downcast_ref::<crate::net::TimedOut>().is_some()
+            {
+                return Some(ErrorClass::Network);
+            }
+            None
+        })
+        .unwrap_or(ErrorClass::General)
+}
+
+fn status_class(status: StatusCode) -> ErrorClass {
+    if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+        ErrorClass::Auth
+    } else if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+        ErrorClass::Network
+    } else if status.is_client_error() {
+        ErrorClass::Validation
+    } else {
+        ErrorClass::General
+    }
+}
+
+/// Prints `err` in the current output format and returns its class.
+pub fn report(err: &anyhow::Error) -> ErrorClass {
+    let class = classify(err);
+    match output::format() {
+        OutputFormat::Json => println!(
+            "{}",
+            json!({"error": {"code": class.code(), "message": err.to_string()}})
+        ),
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+            eprintln!("Error: {err}");
+        }
+    }
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stated_classes_survive_context() {
+        use anyhow::Context as _;
+        let err = Err::<(), _>(auth("No wallet configured"))
+            .context("Failed to sign")
+            .unwrap_err();
+        assert_eq!(classify(&err), ErrorClass::Auth);
+        assert_eq!(
+            classify(&anyhow::anyhow!("Something else")),
+            ErrorClass::General
+        );
+    }
+
+    #[test]
+    fn api_statuses_and_balance_messages_are_classified() {
+        let status = |code: u16, message: &str| -> anyhow::Error {
+            polymarket_client_sdk::error::Error::status(
+                StatusCode::from_u16(code).unwrap(),
+                reqwest::Method::POST,
+                "/order".into(),
+                message,
+            )
+            .into()
+        };
+        assert_eq!(classify(&status(401, "Unauthorized")), ErrorClass::Auth);
+        assert_eq!(classify(&status(404, "not found")), ErrorClass::Validation);
+        assert_eq!(classify(&status(503, "unavailable")), ErrorClass::Network);
+        assert_eq!(
+            classify(&status(400, "not enough balance / allowance")),
+            ErrorClass::InsufficientBalance
+        );
+        assert_eq!(ErrorClass::Validation.exit_code(), 2);
+    }
+}
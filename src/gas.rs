@@ -0,0 +1,307 @@
+//! How on-chain commands pay for gas: the `--gas-limit`, `--max-fee`,
+//! `--priority-fee` and `--gas-multiplier` flags, and the provider filler
+//! that applies them.
+//!
+//! The filler takes the place of alloy's gas filler, so the settings reach
+//! every transaction the provider sends, the SDK's CTF client included.
+//! Whatever isn't given is estimated: the gas limit with `eth_estimateGas`
+//! and the EIP-1559 fees from recent blocks, with the multiplier as headroom
+//! on the gas limit and the base fee. A transaction reusing the nonce of
+//! one in the journal outbids it (see [`crate::nonces`]).
+
+use alloy::network::{Ethereum, Network, TransactionBuilder};
+use alloy::primitives::utils::parse_units;
+use alloy::providers::fillers::{FillerControlFlow, TxFiller};
+use alloy::providers::{Provider, SendableTx};
+use alloy::transports::TransportResult;
+use anyhow::{Context, Result};
+use clap::Args;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::config::SentTx;
+
+pub const DEFAULT_MULTIPLIER: Decimal = Decimal::from_parts(12, 0, 0, false, 1);
+
+#[derive(Args, Clone)]
+#[command(next_help_heading = "Gas")]
+pub struct GasFlags {
+    /// Gas limit for each transaction (default: estimated, times --gas-multiplier)
+    #[arg(long)]
+    pub gas_limit: Option<u64>,
+    /// Most to pay per unit of gas in gwei, priority fee included (default:
+    /// estimated)
+    #[arg(long)]
+    pub max_fee: Option<String>,
+    /// Tip per unit of gas for the block producer in gwei (default: estimated)
+    #[arg(long)]
+    pub priority_fee: Option<String>,
+    /// Headroom on the estimated gas limit and base fee
+    #[arg(long, default_value = "1.2")]
+    pub gas_multiplier: Decimal,
+    /// Replace the oldest pending transaction from the wallet: reuse its
+    /// nonce and outbid its fees
+    #[arg(long)]
+    pub replace: bool,
+}
+
+impl GasFlags {
+    pub fn settings(&self) -> Result<GasSettings> {
+        let max_fee = self
+            .max_fee
+            .as_deref()
+            .map(|s| parse_gwei(s, "max fee"))
+            .transpose()?;
+        let priority_fee = self
+            .priority_fee
+            .as_deref()
+            .map(|s| parse_gwei(s, "priority fee"))
+            .transpose()?;
+        if let (Some(max), Some(priority)) = (max_fee, priority_fee) {
+            anyhow::ensure!(
+                priority <= max,
+                crate::errors::validation("--priority-fee can't be more than --max-fee")
+            );
+        }
+        anyhow::ensure!(
+            self.gas_limit != Some(0),
+            crate::errors::validation("--gas-limit must be positive")
+        );
+        anyhow::ensure!(
+            self.gas_multiplier >= Decimal::ONE,
+            crate::errors::validation("--gas-multiplier must be at least 1")
+        );
+        Ok(GasSettings {
+            gas_limit: self.gas_limit,
+            max_fee,
+            priority_fee,
+            multiplier: self.gas_multiplier,
+            replace: self.replace,
+        })
+    }
+}
+
+/// A gwei amount as wei.
+pub fn parse_gwei(s: &str, what: &str) -> Result<u128> {
+    let gwei: Decimal = s
+        .trim()
+        .parse()
+        .with_context(|| format!("Invalid {what}: {s}"))?;
+    anyhow::ensure!(gwei > Decimal::ZERO, "The {what} must be positive");
+    let wei = parse_units(&gwei.to_string(), "gwei")
+        .with_context(|| format!("Invalid {what}: {s}"))?
+        .get_absolute();
+    u128::try_from(wei).map_err(|_| anyhow::anyhow!("The {what} is too large: {s}"))
+}
+
+/// Gas settings for the transactions a provider sends; `None` is estimated.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GasSettings {
+    pub gas_limit: Option<u64>,
+    /// In wei.
+    pub max_fee: Option<u128>,
+    /// In wei.
+    pub priority_fee: Option<u128>,
+    pub multiplier: Decimal,
+    /// Reuse the nonce of the oldest pending transaction.
+    pub replace: bool,
+}
+
+impl Default for GasSettings {
+    fn default() -> Self {
+        Self {
+            gas_limit: None,
+            max_fee: None,
+            priority_fee: None,
+            multiplier: DEFAULT_MULTIPLIER,
+            replace: false,
+        }
+    }
+}
+
+impl GasSettings {
+    /// `value` times the multiplier, rounded up.
+    fn scale(&self, value: u128) -> u128 {
+        (Decimal::from(value) * self.multiplier)
+            .round_dp_with_strategy(0, RoundingStrategy::AwayFromZero)
+            .to_u128()
+            .unwrap_or(u128::MAX)
+    }
+
+    fn gas_limit(&self, estimate: u64) -> u64 {
+        u64::try_from(self.scale(u128::from(estimate))).unwrap_or(u64::MAX)
+    }
+
+    /// The fees to offer given the node's estimate. The estimate's headroom
+    /// over its tip, about twice the base fee, is scaled by the multiplier.
+    /// A given max fee caps the tip.
+    pub fn fees(&self, max_fee: u128, priority_fee: u128) -> (u128, u128) {
+        let priority = self.priority_fee.unwrap_or(priority_fee);
+        match self.max_fee {
+            Some(max) => (max, priority.min(max)),
+            None => (
+                self.scale(max_fee.saturating_sub(priority_fee))
+                    .saturating_add(priority),
+                priority,
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Fees {
+    /// `--gas-price` on `transfer`.
+    Legacy(u128),
+    Eip1559 {
+        max_fee: u128,
+        priority_fee: u128,
+    },
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GasFill {
+    gas_limit: u64,
+    fees: Fees,
+}
+
+/// Fills the gas limit and fees from [`GasSettings`], estimating what they
+/// leave out.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GasFiller(pub GasSettings);
+
+impl TxFiller<Ethereum> for GasFiller {
+    type Fillable = GasFill;
+
+    fn status(&self, tx: &<Ethereum as Network>::TransactionRequest) -> FillerControlFlow {
+        let priced = tx.gas_price().is_some()
+            || (tx.max_fee_per_gas().is_some() && tx.max_priority_fee_per_gas().is_some());
+        if priced && tx.gas_limit().is_some() {
+            FillerControlFlow::Finished
+        } else if tx.nonce().is_none() {
+            // The fees depend on whether the nonce replaces a transaction.
+            FillerControlFlow::Missing(vec![("Gas", vec!["nonce"])])
+        } else {
+            FillerControlFlow::Ready
+        }
+    }
+
+    fn fill_sync(&self, _tx: &mut SendableTx<Ethereum>) {}
+
+    async fn prepare<P: Provider<Ethereum>>(
+        &self,
+        provider: &P,
+        tx: &<Ethereum as Network>::TransactionRequest,
+    ) -> TransportResult<Self::Fillable> {
+        let settings = self.0;
+        let gas_limit = async {
+            TransportResult::Ok(match tx.gas_limit().or(settings.gas_limit) {
+                Some(limit) => limit,
+                None => settings.gas_limit(provider.estimate_gas(tx.clone()).await?),
+            })
+        };
+        let given_max = tx.max_fee_per_gas().or(settings.max_fee);
+        let given_priority = tx.max_priority_fee_per_gas().or(settings.priority_fee);
+        let fees = async {
+            if let Some(price) = tx.gas_price() {
+                return TransportResult::Ok(Fees::Legacy(price));
+            }
+            if let (Some(max_fee), Some(priority_fee)) = (given_max, given_priority) {
+                return Ok(Fees::Eip1559 {
+                    max_fee,
+                    priority_fee,
+                });
+            }
+            let estimate = provider.estimate_eip1559_fees().await?;
+            let (mut max_fee, mut priority_fee) =
+                settings.fees(estimate.max_fee_per_gas, estimate.max_priority_fee_per_gas);
+            if let Some(prior) = replaced(tx) {
+                let (min_max, min_priority) = crate::nonces::outbid(&prior);
+                if given_priority.is_none() {
+                    priority_fee = priority_fee.max(min_priority);
+                }
+                if given_max.is_none() {
+                    max_fee = max_fee.max(min_max).max(priority_fee);
+                }
+            }
+            Ok(Fees::Eip1559 {
+                max_fee,
+                priority_fee,
+            })
+        };
+        let (gas_limit, fees) = tokio::try_join!(gas_limit, fees)?;
+        Ok(GasFill { gas_limit, fees })
+    }
+
+    async fn fill(
+        &self,
+        fillable: Self::Fillable,
+        mut tx: SendableTx<Ethereum>,
+    ) -> TransportResult<SendableTx<Ethereum>> {
+        if let Some(builder) = tx.as_mut_builder() {
+            builder.set_gas_limit(fillable.gas_limit);
+            match fillable.fees {
+                Fees::Legacy(price) => builder.set_gas_price(price),
+                Fees::Eip1559 {
+                    max_fee,
+                    priority_fee,
+                } => {
+                    builder.set_max_fee_per_gas(max_fee);
+                    builder.set_max_priority_fee_per_gas(priority_fee);
+                }
+            }
+        }
+        Ok(tx)
+    }
+}
+
+/// The journaled transaction `tx` would replace, if its nonce was used
+/// before.
+fn replaced(tx: &<Ethereum as Network>::TransactionRequest) -> Option<SentTx> {
+    let (Some(chain_id), Some(from), Some(nonce)) = (tx.chain_id(), tx.from(), tx.nonce()) else {
+        return None;
+    };
+    crate::nonces::sent(chain_id, from, nonce)
+        .inspect_err(|e| tracing::warn!("Failed to read the transaction journal: {e:#}"))
+        .ok()
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GWEI: u128 = 1_000_000_000;
+
+    #[test]
+    fn parse_gwei_whole_and_fractional() {
+        assert_eq!(parse_gwei("30", "gas price").unwrap(), 30 * GWEI);
+        assert_eq!(parse_gwei("1.5", "gas price").unwrap(), 1_500_000_000);
+    }
+
+    #[test]
+    fn parse_gwei_rejects_bad_input() {
+        assert!(parse_gwei("0", "gas price").is_err());
+        assert!(parse_gwei("-1", "gas price").is_err());
+        assert!(parse_gwei("fast", "gas price").is_err());
+    }
+
+    #[test]
+    fn estimates_get_headroom_and_given_values_win() {
+        let settings = GasSettings::default();
+        assert_eq!(settings.gas_limit(100_000), 120_000);
+        assert_eq!(settings.gas_limit(7), 9);
+        // 60 gwei of headroom over a 30 gwei tip, scaled to 72.
+        assert_eq!(settings.fees(90 * GWEI, 30 * GWEI), (102 * GWEI, 30 * GWEI));
+
+        let tip = GasSettings {
+            priority_fee: Some(50 * GWEI),
+            ..settings
+        };
+        assert_eq!(tip.fees(90 * GWEI, 30 * GWEI), (122 * GWEI, 50 * GWEI));
+        let capped = GasSettings {
+            max_fee: Some(40 * GWEI),
+            ..tip
+        };
+        assert_eq!(capped.fees(90 * GWEI, 30 * GWEI), (40 * GWEI, 40 * GWEI));
+    }
+}
@@ -0,0 +1,111 @@
+//! What a long-running command (`daemon run`, `stream`) is doing, served
+//! with `--serve` as `/healthz` for a supervisor's liveness probe and
+//! `/status` for a person or script, next to `/metrics`.
+//!
+//! The command reports into process-wide state as it goes: what it is
+//! subscribed to, when it last saw an event, and whatever is keeping it
+//! from working, which turns `/healthz` into a 503 until it clears.
+
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde_json::json;
+
+use crate::http::{self, Reply, Route};
+use crate::metrics;
+
+struct State {
+    command: &'static str,
+    started: Option<Instant>,
+    subscriptions: Vec<String>,
+    last_event: Option<DateTime<Utc>>,
+    open_orders: Option<usize>,
+    problem: Option<String>,
+}
+
+static STATE: Mutex<State> = Mutex::new(State {
+    command: "",
+    started: None,
+    subscriptions: Vec::new(),
+    last_event: None,
+    open_orders: None,
+    problem: None,
+});
+
+fn with_state<T>(f: impl FnOnce(&mut State) -> T) -> T {
+    f(&mut STATE.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+const ROUTES: &[Route] = &[("/healthz", healthz), ("/status", status), metrics::ROUTE];
+
+/// Starts serving `/healthz`, `/status` and `/metrics` for `command` on
+/// `addr` in the background.
+pub async fn serve(addr: SocketAddr, command: &'static str) -> Result<()> {
+    with_state(|s| {
+        s.command = command;
+        s.started = Some(Instant::now());
+    });
+    http::serve(addr, ROUTES).await
+}
+
+/// What the command is following: token IDs, markets or a channel.
+pub fn set_subscriptions(subscriptions: Vec<String>) {
+    with_state(|s| s.subscriptions = subscriptions);
+}
+
+/// Something came in: a frame, or a round of prices.
+pub fn event() {
+    with_state(|s| s.last_event = Some(Utc::now()));
+}
+
+pub fn set_open_orders(count: usize) {
+    with_state(|s| s.open_orders = Some(count));
+}
+
+/// Marks the command unhealthy for `problem`, or healthy again for `None`.
+pub fn set_problem(problem: Option<String>) {
+    with_state(|s| s.problem = problem);
+}
+
+fn healthz() -> Reply {
+    with_state(|s| match &s.problem {
+        Some(problem) => Reply::text(503, format!("{problem}\n")),
+        None => Reply::text(200, "ok\n"),
+    })
+}
+
+fn status() -> Reply {
+    with_state(|s| {
+        Reply::json(&json!({
+            "command": s.command,
+            "pid": std::process::id(),
+            "uptime_secs": s.started.map(|t| t.elapsed().as_secs()),
+            "healthy": s.problem.is_none(),
+            "problem": s.problem,
+            "subscriptions": s.subscriptions,
+            "last_event": s.last_event.map(|t| t.to_rfc3339()),
+            "open_orders": s.open_orders,
+        }))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn healthz_follows_the_reported_problem() {
+        assert_eq!(healthz().status, 200);
+        set_problem(Some("Stream disconnected".into()));
+        let reply = healthz();
+        assert_eq!(
+            (reply.status, reply.body.as_str()),
+            (503, "Stream disconnected\n")
+        );
+        set_problem(None);
+        assert_eq!(healthz().status, 200);
+    }
+}
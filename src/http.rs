@@ -0,0 +1,131 @@
+//! A minimal HTTP server for the endpoints long-running commands expose
+//! (`/metrics`, `/healthz`, `/status`). Each route answers a GET from
+//! process-wide state, so one request line is all it needs to read.
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// How long a client gets to send its request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+/// Requests are read up to here at most; nothing served needs a body.
+const MAX_REQUEST: usize = 8192;
+
+pub struct Reply {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl Reply {
+    pub fn text(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: body.into(),
+        }
+    }
+
+    pub fn json(value: &serde_json::Value) -> Self {
+        Self {
+            status: 200,
+            content_type: "application/json",
+            body: format!("{value}\n"),
+        }
+    }
+}
+
+/// A path and what answers it.
+pub type Route = (&'static str, fn() -> Reply);
+
+/// Serves `routes` on `addr` in the background. Fails only if the address
+/// can't be bound.
+pub async fn serve(addr: SocketAddr, routes: &'static [Route]) -> Result<()> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("Failed to listen on {addr}"))?;
+    tokio::spawn(async move {
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                continue;
+            };
+            tokio::spawn(async move {
+                if let Err(e) = answer(stream, routes).await {
+                    tracing::debug!("HTTP request failed: {e:#}");
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+async fn answer(mut stream: TcpStream, routes: &[Route]) -> Result<()> {
+    let mut request = Vec::new();
+    let mut buf = [0; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < MAX_REQUEST {
+        let n = tokio::time::timeout(REQUEST_TIMEOUT, stream.read(&mut buf))
+            .await
+            .context("Timed out reading the request")??;
+        if n == 0 {
+            break;
+        }
+        request.extend_from_slice(&buf[..n]);
+    }
+    let request = String::from_utf8_lossy(&request);
+    let reply = route(request.lines().next().unwrap_or_default(), routes);
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        reply.status,
+        reason(reply.status),
+        reply.content_type,
+        reply.body.len(),
+        reply.body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// The reply to a request line: the matching route's, or a 404 or 405.
+fn route(request_line: &str, routes: &[Route]) -> Reply {
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Reply::text(400, "Bad request\n");
+    };
+    let path = path.split('?').next().unwrap_or_default();
+    match routes.iter().find(|(p, _)| *p == path) {
+        Some(_) if method != "GET" => Reply::text(405, "Method not allowed\n"),
+        Some((_, handler)) => handler(),
+        None => Reply::text(404, "Not found\n"),
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        503 => "Service Unavailable",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROUTES: &[Route] = &[("/ping", || Reply::text(200, "pong"))];
+
+    #[test]
+    fn requests_are_routed_by_path_and_method() {
+        assert_eq!(route("GET /ping HTTP/1.1", ROUTES).body, "pong");
+        assert_eq!(route("GET /ping?x=1 HTTP/1.1", ROUTES).status, 200);
+        assert_eq!(route("POST /ping HTTP/1.1", ROUTES).status, 405);
+        assert_eq!(route("GET / HTTP/1.1", ROUTES).status, 404);
+        assert_eq!(route("", ROUTES).status, 400);
+    }
+}
@@ -0,0 +1,51 @@
+//! Secrets kept in the OS secret store instead of the config directory: the
+//! macOS Keychain, the Windows Credential Manager, or the Secret Service
+//! (GNOME Keyring, KWallet) on Linux, through the `keyring` crate.
+//!
+//! Entries are generic passwords under the `polymarket-cli` service, one per
+//! account name.
+
+use ::keyring::{Entry, Error};
+use anyhow::{Context, Result};
+
+const SERVICE: &str = "polymarket-cli";
+
+fn entry(account: &str) -> Result<Entry> {
+    Entry::new(SERVICE, account).with_context(|| format!("OS keyring error for {account}"))
+}
+
+/// Stores `secret` for `account`, replacing any secret already there.
+pub fn set(account: &str, secret: &str) -> Result<()> {
+    entry(account)?
+        .set_password(secret)
+        .with_context(|| format!("Failed to store {account} in the OS keyring"))
+}
+
+/// The secret stored for `account`, or `None` if there is none.
+pub fn get(account: &str) -> Result<Option<String>> {
+    match entry(account)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(Error::NoEntry) => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {account} from the OS keyring")),
+    }
+}
+
+/// Removes the secret stored for `account`. One that isn't there is fine.
+pub fn delete(account: &str) -> Result<()> {
+    match entry(account)?.delete_credential() {
+        Ok(()) | Err(Error::NoEntry) => Ok(()),
+        Err(e) => Err(e).with_context(|| format!("Failed to remove {account} from the OS keyring")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_entries_read_as_none() {
+        ::keyring::set_default_credential_builder(::keyring::mock::default_credential_builder());
+        assert_eq!(get("nobody").unwrap(), None);
+        delete("nobody").unwrap();
+    }
+}
@@ -0,0 +1,269 @@
+//! Diagnostic logging for `-v`, `-vv` and `--log-level`, or the profile's
+//! `log.level`: HTTP requests, WebSocket traffic and signing, written to
+//! stderr or the profile's `log.file`.
+//!
+//! Events are emitted with `tracing`, including those from alloy's RPC
+//! transport. The logger is installed once, switched off, and each command
+//! sets its level and destination, so a `-v` on one shell command doesn't
+//! carry over to the next.
+
+use std::fmt::{self, Write as _};
+use std::fs::{File, OpenOptions};
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU8, AtomicU64, Ordering};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{Event, Level, Metadata, Subscriber};
+
+use crate::config;
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    /// `-v` logs at debug and `-vv` (or more) at trace.
+    pub fn from_verbosity(count: u8) -> Option<Self> {
+        match count {
+            0 => None,
+            1 => Some(Self::Debug),
+            _ => Some(Self::Trace),
+        }
+    }
+
+    /// Whether an event at `level` is logged. `tracing` orders levels by
+    /// verbosity, so trace is the greatest.
+    fn enables(self, level: Level) -> bool {
+        let max = match self {
+            Self::Error => Level::ERROR,
+            Self::Warn => Level::WARN,
+            Self::Info => Level::INFO,
+            Self::Debug => Level::DEBUG,
+            Self::Trace => Level::TRACE,
+        };
+        level <= max
+    }
+}
+
+/// Field names whose values never reach the log.
+const SECRET_FIELDS: &[&str] = &[
+    "private_key",
+    "key",
+    "api_key",
+    "apikey",
+    "secret",
+    "passphrase",
+    "password",
+    "authorization",
+];
+
+const OFF: u8 = 0;
+
+/// 0 when logging is off, otherwise `LogLevel as u8 + 1`.
+static LEVEL: AtomicU8 = AtomicU8::new(OFF);
+
+static SINK: Mutex<Option<File>> = Mutex::new(None);
+
+struct Logger {
+    next_span: AtomicU64,
+}
+
+/// Installs the logger, switched off. Called once, before any command runs.
+pub fn install() {
+    let logger = Logger {
+        next_span: AtomicU64::new(1),
+    };
+    let _ = tracing::subscriber::set_global_default(logger);
+}
+
+/// Turns logging on at `level` for the current command, or off with `None`.
+/// The level comes from `-v`/`--log-level`, then the profile's `log.level`;
+/// events go to the profile's `log.file`, or stderr if it isn't set.
+pub fn configure(level: Option<LogLevel>) -> Result<()> {
+    let settings = config::logging();
+    let level = level.or(settings.level);
+    let file = match (level, settings.file) {
+        (Some(_), Some(path)) => Some(open_log_file(&path)?),
+        _ => None,
+    };
+    *SINK.lock().unwrap_or_else(|e| e.into_inner()) = file;
+    LEVEL.store(level.map_or(OFF, |l| l as u8 + 1), Ordering::Relaxed);
+    Ok(())
+}
+
+/// Opens `path` for appending. A relative path is taken from the config
+/// directory, where the profiles are.
+fn open_log_file(path: &str) -> Result<File> {
+    let mut full = PathBuf::from(path);
+    if full.is_relative() {
+        full = config::config_dir()?.join(full);
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&full)
+        .with_context(|| format!("Failed to open log file {}", full.display()))
+}
+
+fn current_level() -> Option<LogLevel> {
+    match LEVEL.load(Ordering::Relaxed) {
+        1 => Some(LogLevel::Error),
+        2 => Some(LogLevel::Warn),
+        3 => Some(LogLevel::Info),
+        4 => Some(LogLevel::Debug),
+        5 => Some(LogLevel::Trace),
+        _ => None,
+    }
+}
+
+impl Subscriber for Logger {
+    /// Always asked again, since the level changes between shell commands.
+    fn register_callsite(&self, _: &'static Metadata<'static>) -> Interest {
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        current_level().is_some_and(|l| l.enables(*metadata.level()))
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(self.next_span.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Line::default();
+        event.record(&mut fields);
+        let line = fields.render(event.metadata());
+        let mut sink = SINK.lock().unwrap_or_else(|e| e.into_inner());
+        match sink.as_mut() {
+            Some(file) => {
+                let _ = writeln!(file, "{line}");
+            }
+            None => eprintln!("{line}"),
+        }
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+/// An event's message and its other fields, with secrets redacted.
+#[derive(Default)]
+struct Line {
+    message: String,
+    fields: String,
+}
+
+impl Line {
+    /// `2026-01-02T03:04:05.678Z DEBUG polymarket::net: message key=value`
+    fn render(&self, metadata: &Metadata<'_>) -> String {
+        format!(
+            "{} {:>5} {}: {}{}",
+            chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ"),
+            metadata.level(),
+            metadata.target(),
+            self.message,
+            self.fields
+        )
+    }
+}
+
+impl Visit for Line {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else if is_secret(field.name()) {
+            let _ = write!(self.fields, " {}=<redacted>", field.name());
+        } else {
+            let _ = write!(self.fields, " {}={value:?}", field.name());
+        }
+    }
+}
+
+fn is_secret(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    SECRET_FIELDS.contains(&name.as_str())
+}
+
+/// `json` with the values of secret fields replaced, at any depth, for
+/// logging a payload such as the user channel's subscription.
+pub fn redact_json(json: &str) -> String {
+    fn redact(value: &mut Value) {
+        match value {
+            Value::Object(object) => {
+                for (name, value) in object {
+                    if is_secret(name) {
+                        *value = Value::String("<redacted>".into());
+                    } else {
+                        redact(value);
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(redact),
+            _ => {}
+        }
+    }
+    match serde_json::from_str::<Value>(json) {
+        Ok(mut value) => {
+            redact(&mut value);
+            value.to_string()
+        }
+        Err(_) => "<not JSON, not logged>".into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verbosity_maps_to_levels() {
+        assert_eq!(LogLevel::from_verbosity(0), None);
+        assert_eq!(LogLevel::from_verbosity(1), Some(LogLevel::Debug));
+        assert_eq!(LogLevel::from_verbosity(3), Some(LogLevel::Trace));
+        assert!(LogLevel::Debug.enables(Level::INFO));
+        assert!(LogLevel::Debug.enables(Level::DEBUG));
+        assert!(!LogLevel::Debug.enables(Level::TRACE));
+        assert!(!LogLevel::Error.enables(Level::WARN));
+    }
+
+    #[test]
+    fn secret_fields_are_redacted() {
+        assert!(is_secret("passphrase"));
+        assert!(is_secret("SECRET"));
+        assert!(!is_secret("signer"));
+        assert!(!is_secret("keystore"));
+        assert_eq!(
+            redact_json(r#"{"type":"user","auth":{"apiKey":"k","secret":"s","passphrase":"p"}}"#),
+            r#"{"auth":{"apiKey":"<redacted>","passphrase":"<redacted>","secret":"<redacted>"},"type":"user"}"#
+        );
+    }
+}
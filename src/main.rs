@@ -1,12 +1,37 @@
 mod auth;
+mod backtest;
+mod cache;
+mod clients;
 mod commands;
+mod compat;
 mod config;
+mod confirm;
+mod daemon;
+mod db;
+mod errors;
+mod gas;
+mod health;
+mod http;
+mod keyring;
+mod logging;
+mod metrics;
+mod net;
+mod nonces;
+mod notify;
+mod odds;
 mod output;
+mod paper;
+mod procs;
+mod risk;
+mod session;
 mod shell;
+mod tui;
 
 use std::process::ExitCode;
 
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::CompleteEnv;
+use clap_complete::engine::ArgValueCandidates;
 use output::OutputFormat;
 
 #[derive(Parser)]
@@ -15,17 +40,126 @@ pub(crate) struct Cli {
     #[command(subcommand)]
     command: Commands,
 
-    /// Output format: table or json
-    #[arg(short, long, global = true, default_value = "table")]
-    pub(crate) output: OutputFormat,
+    /// Output format: table, json, csv (list commands) or plain (essential value only).
+    /// Overrides POLYMARKET_OUTPUT and the profile's `output` (default table)
+    #[arg(short, long, global = true)]
+    pub(crate) output: Option<OutputFormat>,
+
+    /// How table output shows prices: price (0.42), probability (42%),
+    /// decimal (2.38) or american (+138). Overrides POLYMARKET_ODDS_FORMAT
+    /// and the profile's `odds_format` (default price)
+    #[arg(long, global = true, value_name = "FORMAT")]
+    odds_format: Option<odds::OddsFormat>,
+
+    /// Leave out the header row of CSV output
+    #[arg(long, global = true)]
+    no_header: bool,
+
+    /// Show exact values: numbers in tables as they are (1234567.891 rather
+    /// than $1.2M or 1,234,567.89), and `stream` frames as received
+    #[arg(long, global = true)]
+    raw: bool,
+
+    /// Color table output: auto (when stdout is a terminal and NO_COLOR
+    /// isn't set), always or never
+    #[arg(long, global = true, value_name = "WHEN", default_value = "auto")]
+    color: output::style::ColorChoice,
+
+    /// Show times in UTC instead of local time
+    #[arg(long, global = true)]
+    utc: bool,
+
+    /// Layout for dates and times in table output, as a strftime string,
+    /// e.g. "%d/%m/%Y %H:%M"
+    #[arg(long, global = true, value_name = "FORMAT")]
+    date_format: Option<String>,
+
+    /// Keep only these fields of JSON output (comma-separated; dotted paths
+    /// reach into nested objects, e.g. address,balances.usdc). Implies
+    /// `-o json` unless another format is given
+    #[arg(long, global = true, value_delimiter = ',')]
+    fields: Vec<String>,
 
     /// Private key (overrides env var and config file)
     #[arg(long, global = true)]
     private_key: Option<String>,
 
+    /// Sign with a connected Ledger (Ethereum app, first Ledger Live account)
+    #[arg(long, global = true, conflicts_with = "private_key")]
+    ledger: bool,
+
+    /// Simulate orders against the live order book instead of sending them
+    #[arg(long, global = true)]
+    paper: bool,
+
+    /// Don't make requests the command itself doesn't need, such as the
+    /// daily version check (also POLYMARKET_OFFLINE)
+    #[arg(long, global = true)]
+    offline: bool,
+
+    /// Never read or write the config directory; take everything from flags
+    /// and environment variables (also POLYMARKET_NO_CONFIG)
+    #[arg(long, global = true)]
+    no_config: bool,
+
+    /// Don't check whether this version still works with the API (also
+    /// POLYMARKET_SKIP_VERSION_CHECK)
+    #[arg(long, global = true)]
+    skip_version_check: bool,
+
     /// Signature type: eoa, proxy, or gnosis-safe
     #[arg(long, global = true)]
     signature_type: Option<String>,
+
+    /// Proxy or Safe wallet to trade for, or a contact name (overrides the one linked with `wallet link-proxy`)
+    #[arg(long, global = true)]
+    funder: Option<String>,
+
+    /// Wallet profile to use (overrides POLYMARKET_PROFILE and the default profile)
+    #[arg(long, global = true, add = ArgValueCandidates::new(commands::completions::profile_candidates))]
+    profile: Option<String>,
+
+    /// Polygon RPC URL (overrides the profile's endpoints.rpc_url)
+    #[arg(long, global = true)]
+    rpc_url: Option<String>,
+
+    /// CLOB API URL (overrides the profile's endpoints.clob_url)
+    #[arg(long, global = true)]
+    clob_url: Option<String>,
+
+    /// Give up on a request after this long, e.g. 10s (default 30s)
+    #[arg(long, global = true, value_name = "DURATION")]
+    timeout: Option<String>,
+
+    /// Retry failed reads this many times (default 2)
+    #[arg(long, global = true)]
+    retries: Option<u32>,
+
+    /// Wait before the first retry, doubled for each one after it (default 500ms)
+    #[arg(long, global = true, value_name = "DURATION")]
+    backoff: Option<String>,
+
+    /// Send at most this many API requests per second, 0 for no limit (default 25)
+    #[arg(long, global = true, value_name = "PER_SECOND")]
+    rate_limit: Option<u32>,
+
+    /// Answer yes to confirmation prompts, e.g. in scripts
+    #[arg(short, long, global = true)]
+    yes: bool,
+
+    /// Send orders over the profile's risk limits (see `config set risk.*`)
+    /// instead of refusing them
+    #[arg(long, global = true)]
+    override_risk: bool,
+
+    /// Log HTTP requests, WebSocket events and signing to stderr: -v for
+    /// debug, -vv for trace
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Log at this level instead (overrides -v and the profile's log.level)
+    #[arg(long, global = true, value_name = "LEVEL")]
+    log_level: Option<logging::LogLevel>,
 }
 
 #[derive(Subcommand)]
@@ -46,158 +180,516 @@ enum Commands {
     Comments(commands::comments::CommentsArgs),
     /// Look up public profiles
     Profiles(commands::profiles::ProfilesArgs),
+    /// Public trader leaderboard by profit or volume, and one trader's stats
+    Leaderboard(commands::leaderboard::LeaderboardArgs),
     /// Sports metadata and teams
     Sports(commands::sports::SportsArgs),
     /// Check and set contract approvals for trading
     Approve(commands::approve::ApproveArgs),
+    /// USDC and POL balances on Polygon
+    Balance(commands::balance::BalanceArgs),
+    /// Show or set the USDC/CTF allowances required before trading
+    Allowance(commands::allowance::AllowanceArgs),
+    /// Send USDC from the configured wallet on Polygon
+    Transfer(commands::transfer::TransferArgs),
+    /// Current gas prices on Polygon
+    Gas(commands::gas::GasArgs),
+    /// On-chain transactions: check one, speed up a stuck one, list recent
+    Tx(commands::tx::TxArgs),
+    /// Address book: names for transfer recipients and funders
+    Contacts(commands::contacts::ContactsArgs),
+    /// Create, derive, list and delete CLOB API keys
+    Apikey(commands::apikey::ApikeyArgs),
+    /// Cached CLOB session: log in, log out, check when it expires
+    Auth(commands::auth::AuthArgs),
     /// Interact with the CLOB (order book, trading, balances)
     Clob(commands::clob::ClobArgs),
+    /// Place, list and cancel orders, or work large ones: TWAP slicing over
+    /// time, or a ladder of limit orders
+    Order(commands::order::OrderArgs),
+    /// Follow another wallet's trades and mirror them, scaled and capped
+    Copy(commands::copy::CopyArgs),
+    /// Best bid, ask, midpoint, spread and last trade for each outcome
+    Quote(commands::quote::QuoteArgs),
+    /// A token's order book, or its analytics: depth near the midpoint,
+    /// imbalance, VWAP and slippage
+    Book(commands::book::BookArgs),
+    /// Taker fee rates for a market's outcomes, and the fee on an order
+    Fees(commands::fees::FeesArgs),
+    /// Convert between price, implied probability, decimal and American odds
+    Convert(commands::convert::ConvertArgs),
+    /// Kelly stake for an outcome from your probability, the price and your bankroll
+    Size(commands::size::SizeArgs),
+    /// Replay a saved trade tape or price history through a strategy file
+    Backtest(commands::backtest::BacktestArgs),
+    /// Scan active markets for arbitrage across outcomes
+    Scan(commands::scan::ScanArgs),
     /// CTF operations: split, merge, redeem positions
     Ctf(commands::ctf::CtfArgs),
+    /// Redeem resolved markets the wallet holds for USDC
+    Redeem(commands::redeem::RedeemArgs),
+    /// Oracle resolution: proposals, disputes and outcomes
+    Resolution(commands::resolution::ResolutionArgs),
     /// Query on-chain data (positions, trades, leaderboards)
     Data(commands::data::DataArgs),
     /// Bridge assets from other chains to Polymarket
     Bridge(commands::bridge::BridgeArgs),
+    /// Fund the trading wallet with USDC from Ethereum mainnet
+    Deposit(commands::deposit::DepositArgs),
+    /// Stream live order book and trade updates over WebSocket
+    Stream(commands::stream::StreamArgs),
+    /// Record order book snapshots and trades to a file for research
+    Record(commands::record::RecordArgs),
+    /// Play a recording back as a stream, in the dashboard, or through a strategy
+    Replay(commands::replay::ReplayArgs),
+    /// Export your trade history, or a market's public trade tape
+    Trades(commands::trades::TradesArgs),
     /// Manage wallet and authentication
     Wallet(commands::wallet::WalletArgs),
+    /// View and change the profile's settings
+    Config(commands::config::ConfigArgs),
+    /// Export or import profiles, settings, watchlist, alerts and contacts
+    State(commands::state::StateArgs),
+    /// Keep a watchlist of markets and check their prices
+    Watch(commands::watch::WatchArgs),
+    /// Price alerts: save thresholds and get notified when they are crossed
+    Alert(commands::alert::AlertArgs),
+    /// Background process that holds one session and works stops and alerts
+    Daemon(daemon::DaemonArgs),
+    /// Emergency stop: stop the daemon, cancel every open order and
+    /// optionally sell all positions at market
+    Panic(commands::panic::PanicArgs),
+    /// Paper trading account used by --paper: balance, positions, fills, reset
+    Paper(commands::paper::PaperArgs),
+    /// Portfolio summary: balance, positions, open orders and PnL; value
+    /// over time with `portfolio history`
+    Portfolio(commands::portfolio::PortfolioArgs),
+    /// Open positions of the configured wallet or another address; sell out
+    /// of them with `positions close`
+    Positions(commands::positions::PositionsArgs),
+    /// Realized gains and losses for a tax year, by FIFO, LIFO or average cost
+    Tax(commands::tax::TaxArgs),
+    /// Index the wallet's on-chain history from Polygon logs, for positions,
+    /// trades and tax reports beyond what the APIs return
+    Sync(commands::sync::SyncArgs),
+    /// Liquidity rewards: today's accrual per market, payouts, scoring orders
+    Rewards(commands::rewards::RewardsArgs),
+    /// Full-screen dashboard: markets, order book, positions and orders
+    Tui(tui::TuiArgs),
+    /// Manage the disk cache of market metadata
+    Cache(commands::cache::CacheArgs),
+    /// Local data in the config directory: sizes, schema version, vacuum
+    Db(commands::db::DbArgs),
     /// Check API health status
     Status,
+    /// Diagnose the setup: config, key, file permissions, connectivity,
+    /// clock skew and approvals
+    Doctor,
+    /// Generate shell completions (bash, zsh, fish, powershell, elvish)
+    Completions(commands::completions::CompletionsArgs),
     /// Update to the latest version
     Upgrade,
 }
 
 #[tokio::main]
 async fn main() -> ExitCode {
+    // Answers completion requests from the scripts `completions` generates.
+    CompleteEnv::with_factory(Cli::command)
+        .var(commands::completions::COMPLETE_ENV_VAR)
+        .complete();
+
     let cli = Cli::parse();
-    let output = cli.output;
+    logging::install();
 
     if let Err(e) = run(cli).await {
-        match output {
-            OutputFormat::Json => {
-                println!("{}", serde_json::json!({"error": e.to_string()}));
-            }
-            OutputFormat::Table => {
-                eprintln!("Error: {e}");
-            }
-        }
-        return ExitCode::FAILURE;
+        return ExitCode::from(errors::report(&e).exit_code());
     }
 
     ExitCode::SUCCESS
 }
 
 #[allow(clippy::too_many_lines)]
-pub(crate) async fn run(cli: Cli) -> anyhow::Result<()> {
-    match cli.command {
-        Commands::Setup => commands::setup::execute(),
+pub(crate) async fn run(mut cli: Cli) -> anyhow::Result<()> {
+    if !cli.fields.is_empty() && cli.output.is_none() {
+        cli.output = Some(OutputFormat::Json);
+    }
+    output::set_format(cli.output.unwrap_or(OutputFormat::Table));
+    output::set_csv_header(!cli.no_header);
+    output::set_raw(cli.raw);
+    anyhow::ensure!(
+        cli.fields
+            .iter()
+            .all(|f| f.split('.').all(|part| !part.is_empty())),
+        errors::validation("--fields takes field names such as address or balances.usdc")
+    );
+    output::set_fields(cli.fields.clone());
+    output::time::set_utc(cli.utc);
+    output::time::set_date_format(cli.date_format.clone())?;
+    confirm::set_assume_yes(cli.yes);
+    risk::set_override(cli.override_risk);
+    if cli.ledger {
+        config::set_ledger(true);
+    }
+    if cli.paper {
+        config::set_paper(true);
+    }
+    if cli.offline {
+        config::set_offline(true);
+    }
+    if cli.no_config {
+        config::set_no_config(true);
+    }
+    if cli.profile.is_some() {
+        config::set_profile_override(cli.profile)?;
+    }
+    if let Some(funder) = &cli.funder {
+        let funder = commands::resolve_address(funder)
+            .map_err(|e| anyhow::anyhow!("Invalid --funder: {e}"))?;
+        config::set_funder_override(Some(funder));
+    }
+    if cli.rpc_url.is_some() || cli.clob_url.is_some() {
+        config::set_endpoint_overrides(config::Endpoints {
+            rpc_url: cli.rpc_url,
+            clob_url: cli.clob_url,
+            ..config::Endpoints::default()
+        })?;
+    }
+    if cli.timeout.is_some()
+        || cli.retries.is_some()
+        || cli.backoff.is_some()
+        || cli.rate_limit.is_some()
+    {
+        config::set_network_overrides(config::Network {
+            timeout: cli.timeout,
+            retries: cli.retries,
+            backoff: cli.backoff,
+            rate_limit: cli.rate_limit,
+        });
+        net::Policy::current()?;
+    }
+    // Read after `--profile` is applied, so they come from that profile.
+    logging::configure(
+        cli.log_level
+            .or_else(|| logging::LogLevel::from_verbosity(cli.verbose)),
+    )?;
+    let output = match cli.output {
+        Some(output) => output,
+        None => config::output_format()?,
+    };
+    output::set_format(output);
+    output::set_odds_format(match cli.odds_format {
+        Some(format) => format,
+        None => config::odds_format()?,
+    });
+    output::style::set_color(cli.color, config::theme()?);
+
+    // Runs next to the command and warns as soon as it knows, so a command
+    // that runs until interrupted (`stream`, `daemon run`, ...) shows it up
+    // front. A quicker command waits for it before exiting. Commands that
+    // work without the API don't check.
+    let version_check = (!compat::skipped(cli.skip_version_check)
+        && !matches!(
+            cli.command,
+            Commands::Shell
+                | Commands::Tui(_)
+                | Commands::Doctor
+                | Commands::Completions(_)
+                | Commands::Upgrade
+                | Commands::Convert(_)
+                | Commands::Size(_)
+                | Commands::Config(_)
+                | Commands::Contacts(_)
+        ))
+    .then(|| tokio::spawn(compat::warn_if_incompatible()));
+
+    let result = match cli.command {
+        Commands::Setup => commands::setup::execute().await,
         Commands::Shell => {
             Box::pin(shell::run_shell()).await;
             Ok(())
         }
         Commands::Markets(args) => {
-            commands::markets::execute(
-                &polymarket_client_sdk::gamma::Client::default(),
+            commands::markets::execute(&clients::gamma()?, args, output).await
+        }
+        Commands::Events(args) => commands::events::execute(&clients::gamma()?, args, output).await,
+        Commands::Tags(args) => commands::tags::execute(&clients::gamma()?, args, output).await,
+        Commands::Series(args) => commands::series::execute(&clients::gamma()?, args, output).await,
+        Commands::Comments(args) => {
+            commands::comments::execute(&clients::gamma()?, args, output).await
+        }
+        Commands::Profiles(args) => {
+            commands::profiles::execute(&clients::gamma()?, args, output).await
+        }
+        Commands::Sports(args) => commands::sports::execute(&clients::gamma()?, args, output).await,
+        Commands::Approve(args) => {
+            commands::approve::execute(args, output, cli.private_key.as_deref()).await
+        }
+        Commands::Balance(args) => {
+            commands::balance::execute(
                 args,
-                cli.output,
+                &output,
+                cli.private_key.as_deref(),
+                cli.signature_type.as_deref(),
             )
             .await
         }
-        Commands::Events(args) => {
-            commands::events::execute(
-                &polymarket_client_sdk::gamma::Client::default(),
+        Commands::Allowance(args) => {
+            commands::allowance::execute(args, output, cli.private_key.as_deref()).await
+        }
+        Commands::Transfer(args) => {
+            commands::transfer::execute(args, &output, cli.private_key.as_deref()).await
+        }
+        Commands::Gas(args) => commands::gas::execute(args, &output).await,
+        Commands::Tx(args) => {
+            commands::tx::execute(args, &output, cli.private_key.as_deref()).await
+        }
+        Commands::Contacts(args) => commands::contacts::execute(args, &output),
+        Commands::Apikey(args) => {
+            commands::apikey::execute(
                 args,
-                cli.output,
+                &output,
+                cli.private_key.as_deref(),
+                cli.signature_type.as_deref(),
             )
             .await
         }
-        Commands::Tags(args) => {
-            commands::tags::execute(
-                &polymarket_client_sdk::gamma::Client::default(),
+        Commands::Auth(args) => {
+            commands::auth::execute(args, &output, cli.private_key.as_deref()).await
+        }
+        Commands::Clob(args) => {
+            commands::clob::execute(
                 args,
-                cli.output,
+                output,
+                cli.private_key.as_deref(),
+                cli.signature_type.as_deref(),
             )
             .await
         }
-        Commands::Series(args) => {
-            commands::series::execute(
-                &polymarket_client_sdk::gamma::Client::default(),
+        Commands::Order(args) => {
+            commands::order::execute(
                 args,
-                cli.output,
+                &output,
+                cli.private_key.as_deref(),
+                cli.signature_type.as_deref(),
             )
             .await
         }
-        Commands::Comments(args) => {
-            commands::comments::execute(
-                &polymarket_client_sdk::gamma::Client::default(),
+        Commands::Quote(args) => commands::quote::execute(args, &output).await,
+        Commands::Book(args) => commands::book::execute(args, &output).await,
+        Commands::Fees(args) => commands::fees::execute(args, &output).await,
+        Commands::Convert(args) => commands::convert::execute(&args, &output),
+        Commands::Size(args) => {
+            commands::size::execute(
                 args,
-                cli.output,
+                output,
+                cli.private_key.as_deref(),
+                cli.signature_type.as_deref(),
             )
             .await
         }
-        Commands::Profiles(args) => {
-            commands::profiles::execute(
-                &polymarket_client_sdk::gamma::Client::default(),
+        Commands::Backtest(args) => commands::backtest::execute(args, &output),
+        Commands::Scan(args) => commands::scan::execute(args, &output).await,
+        Commands::Ctf(args) => {
+            commands::ctf::execute(args, output, cli.private_key.as_deref()).await
+        }
+        Commands::Redeem(args) => {
+            commands::redeem::execute(
                 args,
-                cli.output,
+                &output,
+                cli.private_key.as_deref(),
+                cli.signature_type.as_deref(),
             )
             .await
         }
-        Commands::Sports(args) => {
-            commands::sports::execute(
-                &polymarket_client_sdk::gamma::Client::default(),
+        Commands::Data(args) => {
+            commands::data::execute(
+                &clients::data()?,
                 args,
-                cli.output,
+                output,
+                cli.private_key.as_deref(),
+                cli.signature_type.as_deref(),
             )
             .await
         }
-        Commands::Approve(args) => {
-            commands::approve::execute(args, cli.output, cli.private_key.as_deref()).await
+        Commands::Leaderboard(args) => {
+            commands::leaderboard::execute(&clients::data()?, args, &output).await
         }
-        Commands::Clob(args) => {
-            commands::clob::execute(
+        Commands::Deposit(args) => {
+            commands::deposit::execute(
                 args,
-                cli.output,
+                &output,
                 cli.private_key.as_deref(),
                 cli.signature_type.as_deref(),
             )
             .await
         }
-        Commands::Ctf(args) => {
-            commands::ctf::execute(args, cli.output, cli.private_key.as_deref()).await
+        Commands::Bridge(args) => {
+            commands::bridge::execute(
+                &polymarket_client_sdk::bridge::Client::default(),
+                args,
+                output,
+            )
+            .await
         }
-        Commands::Data(args) => {
-            commands::data::execute(
-                &polymarket_client_sdk::data::Client::default(),
+        Commands::Stream(args) => {
+            commands::stream::execute(
                 args,
-                cli.output,
+                cli.private_key.as_deref(),
+                cli.signature_type.as_deref(),
             )
             .await
         }
-        Commands::Bridge(args) => {
-            commands::bridge::execute(
-                &polymarket_client_sdk::bridge::Client::default(),
+        Commands::Record(args) => commands::record::execute(args, &output).await,
+        Commands::Replay(args) => commands::replay::execute(args, &output).await,
+        Commands::Trades(args) => {
+            commands::trades::execute(
                 args,
-                cli.output,
+                output,
+                cli.private_key.as_deref(),
+                cli.signature_type.as_deref(),
             )
             .await
         }
         Commands::Wallet(args) => {
-            commands::wallet::execute(args, &cli.output, cli.private_key.as_deref())
+            commands::wallet::execute(args, &output, cli.private_key.as_deref()).await
+        }
+        Commands::Config(args) => commands::config::execute(args, &output),
+        Commands::State(args) => commands::state::execute(args, &output),
+        Commands::Watch(args) => commands::watch::execute(&clients::gamma()?, args, output).await,
+        Commands::Resolution(args) => {
+            commands::resolution::execute(
+                args,
+                output,
+                cli.private_key.as_deref(),
+                cli.signature_type.as_deref(),
+            )
+            .await
+        }
+        Commands::Alert(args) => commands::alert::execute(&clients::gamma()?, args, output).await,
+        Commands::Daemon(args) => {
+            daemon::execute(
+                args,
+                output,
+                cli.private_key.as_deref(),
+                cli.signature_type.as_deref(),
+            )
+            .await
+        }
+        Commands::Panic(args) => {
+            anyhow::ensure!(
+                !config::paper_mode(),
+                "{}",
+                commands::paper::UNSUPPORTED_MSG
+            );
+            commands::panic::execute(
+                args,
+                &output,
+                cli.private_key.as_deref(),
+                cli.signature_type.as_deref(),
+            )
+            .await
+        }
+        Commands::Paper(args) => commands::paper::execute(args, output).await,
+        Commands::Portfolio(args) if config::paper_mode() && args.command.is_none() => {
+            commands::paper::status(&output).await
+        }
+        Commands::Portfolio(args) => {
+            anyhow::ensure!(
+                !config::paper_mode(),
+                "{}",
+                commands::paper::UNSUPPORTED_MSG
+            );
+            commands::portfolio::execute(
+                args,
+                &output,
+                cli.private_key.as_deref(),
+                cli.signature_type.as_deref(),
+            )
+            .await
+        }
+        Commands::Positions(args) => {
+            commands::positions::execute(
+                args,
+                output,
+                cli.private_key.as_deref(),
+                cli.signature_type.as_deref(),
+            )
+            .await
+        }
+        Commands::Copy(args) => {
+            commands::copy::execute(
+                args,
+                &output,
+                cli.private_key.as_deref(),
+                cli.signature_type.as_deref(),
+            )
+            .await
+        }
+        Commands::Tax(args) => {
+            anyhow::ensure!(
+                !config::paper_mode(),
+                "{}",
+                commands::paper::UNSUPPORTED_MSG
+            );
+            commands::tax::execute(
+                args,
+                &output,
+                cli.private_key.as_deref(),
+                cli.signature_type.as_deref(),
+            )
+            .await
+        }
+        Commands::Sync(args) => {
+            anyhow::ensure!(
+                !config::paper_mode(),
+                "{}",
+                commands::paper::UNSUPPORTED_MSG
+            );
+            commands::sync::execute(
+                args,
+                &output,
+                cli.private_key.as_deref(),
+                cli.signature_type.as_deref(),
+            )
+            .await
+        }
+        Commands::Rewards(args) => {
+            commands::rewards::execute(
+                args,
+                &output,
+                cli.private_key.as_deref(),
+                cli.signature_type.as_deref(),
+            )
+            .await
+        }
+        Commands::Tui(args) => {
+            tui::execute(
+                args,
+                cli.private_key.as_deref(),
+                cli.signature_type.as_deref(),
+            )
+            .await
         }
+        Commands::Completions(args) => commands::completions::execute(&args),
         Commands::Upgrade => commands::upgrade::execute(),
+        Commands::Cache(args) => commands::cache::execute(&args, &output),
+        Commands::Db(args) => commands::db::execute(&args, &output),
+        Commands::Doctor => commands::doctor::execute(&output, cli.private_key.as_deref()).await,
         Commands::Status => {
-            let status = polymarket_client_sdk::gamma::Client::default()
-                .status()
-                .await?;
-            match cli.output {
+            let status = clients::gamma()?.status().await?;
+            match output {
                 OutputFormat::Json => {
-                    println!("{}", serde_json::json!({"status": status}));
+                    output::print_json_line(serde_json::json!({"status": status}));
                 }
-                OutputFormat::Table => {
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
                     println!("API Status: {status}");
                 }
             }
             Ok(())
         }
+    };
+    if let Some(check) = version_check {
+        let _ = check.await;
     }
+    result
 }
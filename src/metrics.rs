@@ -0,0 +1,185 @@
+//! Counters for the long-running commands (`daemon run`, `alert run`),
+//! served in the Prometheus text format on `--metrics-addr` so a bot can be
+//! watched from Grafana.
+//!
+//! The counters are process-wide and cost an atomic add when nothing
+//! scrapes them; `serve` is only started when the flag is given.
+
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use anyhow::Result;
+use polymarket_client_sdk::types::Decimal;
+
+use crate::http::{self, Reply};
+
+/// Upper bounds of the API latency buckets, in seconds.
+const LATENCY_BUCKETS: [f64; 9] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+static ORDERS_PLACED: AtomicU64 = AtomicU64::new(0);
+static ORDERS_FILLED: AtomicU64 = AtomicU64::new(0);
+static ALERTS_FIRED: AtomicU64 = AtomicU64::new(0);
+static WS_RECONNECTS: AtomicU64 = AtomicU64::new(0);
+static LATENCY: Histogram = Histogram::new();
+static EXPOSURE: Mutex<Option<Decimal>> = Mutex::new(None);
+
+struct Histogram {
+    buckets: [AtomicU64; LATENCY_BUCKETS.len()],
+    count: AtomicU64,
+    sum_micros: AtomicU64,
+}
+
+impl Histogram {
+    const fn new() -> Self {
+        Self {
+            buckets: [const { AtomicU64::new(0) }; LATENCY_BUCKETS.len()],
+            count: AtomicU64::new(0),
+            sum_micros: AtomicU64::new(0),
+        }
+    }
+
+    fn observe(&self, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        // Buckets are cumulative: an observation counts in every one it fits.
+        for (bucket, le) in self.buckets.iter().zip(LATENCY_BUCKETS) {
+            if secs <= le {
+                bucket.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        let micros = u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX);
+        self.sum_micros.fetch_add(micros, Ordering::Relaxed);
+    }
+}
+
+/// An order the CLOB accepted; `filled` when it matched straight away.
+pub fn order_placed(filled: bool) {
+    ORDERS_PLACED.fetch_add(1, Ordering::Relaxed);
+    if filled {
+        ORDERS_FILLED.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+pub fn alert_fired() {
+    ALERTS_FIRED.fetch_add(1, Ordering::Relaxed);
+}
+
+pub fn ws_reconnect() {
+    WS_RECONNECTS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// How long one API request attempt took, whether or not it succeeded.
+pub fn api_request(elapsed: Duration) {
+    LATENCY.observe(elapsed);
+}
+
+/// The wallet's current position value in USDC.
+pub fn set_exposure(usdc: Decimal) {
+    *EXPOSURE.lock().unwrap_or_else(|e| e.into_inner()) = Some(usdc);
+}
+
+fn counter(out: &mut String, name: &str, help: &str, value: u64) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} counter");
+    let _ = writeln!(out, "{name} {value}");
+}
+
+/// Every metric in the Prometheus text exposition format.
+pub fn render() -> String {
+    let mut out = String::new();
+    counter(
+        &mut out,
+        "polymarket_orders_placed_total",
+        "Orders the CLOB accepted.",
+        ORDERS_PLACED.load(Ordering::Relaxed),
+    );
+    counter(
+        &mut out,
+        "polymarket_orders_filled_total",
+        "Orders that matched when placed.",
+        ORDERS_FILLED.load(Ordering::Relaxed),
+    );
+    counter(
+        &mut out,
+        "polymarket_alerts_fired_total",
+        "Price alerts fired.",
+        ALERTS_FIRED.load(Ordering::Relaxed),
+    );
+    counter(
+        &mut out,
+        "polymarket_websocket_reconnects_total",
+        "WebSocket connections re-opened after dropping.",
+        WS_RECONNECTS.load(Ordering::Relaxed),
+    );
+
+    let name = "polymarket_api_request_duration_seconds";
+    let _ = writeln!(out, "# HELP {name} API request attempts by duration.");
+    let _ = writeln!(out, "# TYPE {name} histogram");
+    for (bucket, le) in LATENCY.buckets.iter().zip(LATENCY_BUCKETS) {
+        let _ = writeln!(
+            out,
+            "{name}_bucket{{le=\"{le}\"}} {}",
+            bucket.load(Ordering::Relaxed)
+        );
+    }
+    let count = LATENCY.count.load(Ordering::Relaxed);
+    let _ = writeln!(out, "{name}_bucket{{le=\"+Inf\"}} {count}");
+    let sum = Duration::from_micros(LATENCY.sum_micros.load(Ordering::Relaxed));
+    let _ = writeln!(out, "{name}_sum {}", sum.as_secs_f64());
+    let _ = writeln!(out, "{name}_count {count}");
+
+    // Left out until it is known, rather than reported as zero.
+    if let Some(exposure) = *EXPOSURE.lock().unwrap_or_else(|e| e.into_inner()) {
+        let name = "polymarket_exposure_usdc";
+        let _ = writeln!(
+            out,
+            "# HELP {name} Current value of the wallet's positions."
+        );
+        let _ = writeln!(out, "# TYPE {name} gauge");
+        let _ = writeln!(out, "{name} {exposure}");
+    }
+    out
+}
+
+/// The `/metrics` route.
+pub const ROUTE: http::Route = ("/metrics", || Reply {
+    status: 200,
+    content_type: "text/plain; version=0.0.4",
+    body: render(),
+});
+
+/// Serves `GET /metrics` on `addr` in the background.
+pub async fn serve(addr: SocketAddr) -> Result<()> {
+    http::serve(addr, &[ROUTE]).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latency_buckets_are_cumulative() {
+        let h = Histogram::new();
+        h.observe(Duration::from_millis(80));
+        h.observe(Duration::from_millis(700));
+        let counts: Vec<u64> = h
+            .buckets
+            .iter()
+            .map(|b| b.load(Ordering::Relaxed))
+            .collect();
+        assert_eq!(counts, [0, 1, 1, 1, 2, 2, 2, 2, 2]);
+        assert_eq!(h.count.load(Ordering::Relaxed), 2);
+        assert_eq!(h.sum_micros.load(Ordering::Relaxed), 780_000);
+    }
+
+    #[test]
+    fn render_writes_the_text_format() {
+        order_placed(true);
+        let text = render();
+        assert!(text.contains("# TYPE polymarket_orders_placed_total counter"));
+        assert!(text.contains("polymarket_api_request_duration_seconds_bucket{le=\"+Inf\"}"));
+    }
+}
@@ -0,0 +1,418 @@
+//! Timeouts, retries and rate limiting for network calls, from the global
+//! `--timeout`, `--retries`, `--backoff` and `--rate-limit` flags or the
+//! profile's `network` settings.
+//!
+//! The SDK clients build their own HTTP clients, so the policy is applied
+//! around each call: reads go through [`retry`], and requests that must not
+//! be sent twice (orders, cancels, key changes) through [`once`], which only
+//! times out. Both draw from one token bucket, so a bulk command stays under
+//! the API rate limits however it fans out. The RPC provider gets the same
+//! timeouts and retries as transport layers.
+
+use std::fmt;
+use std::future::Future;
+use std::panic::Location;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use alloy::rpc::client::{ClientBuilder, RpcClient};
+use alloy::transports::layers::{RateLimitRetryPolicy, RetryBackoffLayer};
+use alloy::transports::{RpcError, TransportErrorKind};
+use anyhow::{Context, Result};
+use futures_util::{StreamExt as _, TryStreamExt as _};
+use polymarket_client_sdk::error::{Status, StatusCode};
+
+use crate::config::{self, Network};
+use crate::metrics;
+
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+pub const DEFAULT_RETRIES: u32 = 2;
+pub const DEFAULT_BACKOFF: Duration = Duration::from_millis(500);
+/// Longest wait between two attempts, however many retries are allowed.
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+/// API requests per second when `network.rate_limit` isn't set. Gamma's
+/// market listings allow about 30 a second, the tightest of the limits the
+/// CLI runs into.
+pub const DEFAULT_RATE_LIMIT: u32 = 25;
+/// Most requests [`fetch_all`] has in flight at once: enough to hide
+/// latency, few enough to stay clear of the APIs' rate limits.
+pub const MAX_CONCURRENT: usize = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Policy {
+    pub timeout: Duration,
+    pub retries: u32,
+    pub backoff: Duration,
+    /// Requests per second; 0 for no limit.
+    pub rate_limit: u32,
+}
+
+impl Policy {
+    /// The policy from the flags and the active profile.
+    pub fn current() -> Result<Self> {
+        Self::from_settings(&config::network())
+    }
+
+    fn from_settings(settings: &Network) -> Result<Self> {
+        Ok(Self {
+            timeout: parse_setting(settings.timeout.as_deref(), "timeout")?
+                .unwrap_or(DEFAULT_TIMEOUT),
+            retries: settings.retries.unwrap_or(DEFAULT_RETRIES),
+            backoff: parse_setting(settings.backoff.as_deref(), "backoff")?
+                .unwrap_or(DEFAULT_BACKOFF),
+            rate_limit: settings.rate_limit.unwrap_or(DEFAULT_RATE_LIMIT),
+        })
+    }
+
+    /// Wait before retry number `attempt` (from 0): the backoff, doubled for
+    /// each earlier retry.
+    fn delay(&self, attempt: u32) -> Duration {
+        self.backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(MAX_BACKOFF)
+    }
+}
+
+fn parse_setting(value: Option<&str>, name: &str) -> Result<Option<Duration>> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    let duration = crate::commands::parse_duration(value)
+        .with_context(|| format!("Invalid {name}"))?
+        .to_std()
+        .with_context(|| format!("Invalid {name}: {value}"))?;
+    anyhow::ensure!(!duration.is_zero(), "Invalid {name}: must be positive");
+    Ok(Some(duration))
+}
+
+/// A token bucket holding up to a second's worth of requests and refilled
+/// at `rate` a second. Tokens go negative when requests queue up, so each
+/// waits its turn.
+struct Bucket {
+    rate: u32,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn new(rate: u32, now: Instant) -> Self {
+        Self {
+            rate,
+            tokens: f64::from(rate),
+            updated: now,
+        }
+    }
+
+    /// Takes a token at `now`. Returns how long to wait before sending, and
+    /// the whole tokens left for later requests.
+    fn take(&mut self, now: Instant) -> (Duration, u32) {
+        let rate = f64::from(self.rate);
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(rate) - 1.0;
+        self.updated = now;
+        let wait = Duration::from_secs_f64((-self.tokens).max(0.0) / rate);
+        // Whole tokens between 0 and the rate, so the cast is exact.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let left = self.tokens.max(0.0).floor() as u32;
+        (wait, left)
+    }
+}
+
+static BUCKET: Mutex<Option<Bucket>> = Mutex::new(None);
+
+/// Waits for the process's turn to send a request under `rate` a second,
+/// returning the requests left in this second's budget. `None` when there
+/// is no limit.
+async fn throttle(rate: u32, caller: &Location<'_>) -> Option<u32> {
+    if rate == 0 {
+        return None;
+    }
+    let now = Instant::now();
+    let (wait, left) = {
+        let mut bucket = BUCKET.lock().unwrap_or_else(|e| e.into_inner());
+        let bucket = match bucket.as_mut() {
+            Some(b) if b.rate == rate => b,
+            _ => bucket.insert(Bucket::new(rate, now)),
+        };
+        bucket.take(now)
+    };
+    if !wait.is_zero() {
+        tracing::debug!(%caller, ?wait, rate, "rate limit reached, waiting");
+        tokio::time::sleep(wait).await;
+    }
+    Some(left)
+}
+
+#[derive(Debug)]
+pub(crate) struct TimedOut(Duration);
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Request timed out after {:?} (raise it with --timeout)",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for TimedOut {}
+
+/// Whether `err` is worth another attempt: a timeout, a connection failure,
+/// rate limiting or a server error. API errors such as a 404 or a rejected
+/// request are returned straight away.
+fn is_transient(err: &anyhow::Error) -> bool {
+    err.chain().any(|e| {
+        if e.downcast_ref::<TimedOut>().is_some() {
+            return true;
+        }
+        if let Some(status) = e.downcast_ref::<Status>() {
+            return status.status_code.is_server_error()
+                || status.status_code == StatusCode::TOO_MANY_REQUESTS;
+        }
+        e.downcast_ref::<reqwest::Error>()
+            .is_some_and(|e| e.is_timeout() || e.is_connect() || e.is_request())
+    })
+}
+
+/// Runs a read, retrying transient failures with exponential backoff. Each
+/// attempt gets the full timeout. Attempts are logged against the caller's
+/// source location, since the SDK doesn't log its requests.
+#[track_caller]
+pub fn retry<T, E, F, Fut>(mut call: F) -> impl Future<Output = Result<T>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: Into<anyhow::Error>,
+{
+    let caller = Location::caller();
+    async move {
+        let policy = Policy::current()?;
+        let mut attempt = 0;
+        loop {
+            let budget = throttle(policy.rate_limit, caller).await;
+            let started = Instant::now();
+            let result = tokio::time::timeout(policy.timeout, call()).await;
+            metrics::api_request(started.elapsed());
+            let err = match result {
+                Ok(Ok(value)) => {
+                    tracing::debug!(%caller, attempt, elapsed = ?started.elapsed(), ?budget, "request succeeded");
+                    return Ok(value);
+                }
+                Ok(Err(e)) => e.into(),
+                Err(_) => TimedOut(policy.timeout).into(),
+            };
+            if attempt >= policy.retries || !is_transient(&err) {
+                tracing::debug!(%caller, attempt, elapsed = ?started.elapsed(), error = %format!("{err:#}"), "request failed");
+                return Err(err);
+            }
+            let delay = policy.delay(attempt);
+            tracing::warn!(%caller, attempt, error = %format!("{err:#}"), ?delay, "request failed, retrying");
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// Runs a request that must not be repeated, under the timeout only.
+#[track_caller]
+pub fn once<T, E>(call: impl Future<Output = Result<T, E>>) -> impl Future<Output = Result<T>>
+where
+    E: Into<anyhow::Error>,
+{
+    let caller = Location::caller();
+    async move {
+        let policy = Policy::current()?;
+        let budget = throttle(policy.rate_limit, caller).await;
+        let started = Instant::now();
+        let result = match tokio::time::timeout(policy.timeout, call).await {
+            Ok(result) => result.map_err(Into::into),
+            Err(_) => Err(TimedOut(policy.timeout).into()),
+        };
+        metrics::api_request(started.elapsed());
+        match &result {
+            Ok(_) => {
+                tracing::debug!(%caller, elapsed = ?started.elapsed(), ?budget, "request succeeded")
+            }
+            Err(e) => {
+                tracing::debug!(%caller, elapsed = ?started.elapsed(), error = %format!("{e:#}"), "request failed");
+            }
+        }
+        result
+    }
+}
+
+/// Runs `fetch` on each item, up to [`MAX_CONCURRENT`] at a time, and
+/// returns the results in the items' order, or the first error. For reads
+/// that fan out per market or token, which would crawl one at a time.
+pub async fn fetch_all<I, T, F, Fut>(items: I, fetch: F) -> Result<Vec<T>>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    futures_util::stream::iter(items)
+        .map(fetch)
+        .buffered(MAX_CONCURRENT)
+        .try_collect()
+        .await
+}
+
+/// An HTTP client for requests the CLI makes itself.
+pub fn http_client() -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .timeout(Policy::current()?.timeout)
+        .build()
+        .context("Failed to build HTTP client")
+}
+
+/// A JSON-RPC client for `url` with the policy's timeout, retrying rate
+/// limits, server errors and failed connections.
+pub fn rpc_client(url: &str) -> Result<RpcClient> {
+    let policy = Policy::current()?;
+    let url = url
+        .parse()
+        .with_context(|| format!("Invalid RPC URL: {url}"))?;
+    let http = alloy::transports::http::reqwest::Client::builder()
+        .timeout(policy.timeout)
+        .build()
+        .context("Failed to build RPC client")?;
+    let retry_policy = RateLimitRetryPolicy::default()
+        .or(|e| matches!(e, RpcError::Transport(TransportErrorKind::Custom(_))));
+    let backoff_ms = u64::try_from(policy.backoff.as_millis()).unwrap_or(u64::MAX);
+    Ok(ClientBuilder::default()
+        .layer(RetryBackoffLayer::new_with_policy(
+            policy.retries,
+            backoff_ms,
+            u64::MAX,
+            retry_policy,
+        ))
+        .http_with_client(http, url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn settings(timeout: Option<&str>, retries: Option<u32>, backoff: Option<&str>) -> Network {
+        Network {
+            timeout: timeout.map(Into::into),
+            retries,
+            backoff: backoff.map(Into::into),
+            rate_limit: None,
+        }
+    }
+
+    #[test]
+    fn bucket_allows_a_burst_then_spaces_requests() {
+        let start = Instant::now();
+        let mut bucket = Bucket::new(4, start);
+        for left in [3, 2, 1, 0] {
+            assert_eq!(bucket.take(start), (Duration::ZERO, left));
+        }
+        // The fifth waits for a token, the sixth for the one after it.
+        assert_eq!(bucket.take(start), (Duration::from_millis(250), 0));
+        assert_eq!(bucket.take(start), (Duration::from_millis(500), 0));
+        // A quiet spell refills the bucket, but never past one second's worth.
+        let later = start + Duration::from_secs(10);
+        assert_eq!(bucket.take(later), (Duration::ZERO, 3));
+    }
+
+    #[test]
+    fn policy_defaults_and_overrides() {
+        let policy = Policy::from_settings(&Network::default()).unwrap();
+        assert_eq!(policy.timeout, DEFAULT_TIMEOUT);
+        assert_eq!(policy.retries, DEFAULT_RETRIES);
+        assert_eq!(policy.rate_limit, DEFAULT_RATE_LIMIT);
+
+        let policy = Policy::from_settings(&settings(Some("10s"), Some(0), Some("250ms"))).unwrap();
+        assert_eq!(policy.timeout, Duration::from_secs(10));
+        assert_eq!(policy.retries, 0);
+        assert_eq!(policy.backoff, Duration::from_millis(250));
+    }
+
+    #[test]
+    fn policy_rejects_bad_durations() {
+        let err = Policy::from_settings(&settings(Some("soon"), None, None)).unwrap_err();
+        assert!(
+            format!("{err:#}").contains("Invalid timeout"),
+            "got: {err:#}"
+        );
+        assert!(Policy::from_settings(&settings(None, None, Some("0s"))).is_err());
+    }
+
+    #[test]
+    fn delay_doubles_up_to_the_cap() {
+        let policy = Policy {
+            timeout: DEFAULT_TIMEOUT,
+            retries: 10,
+            backoff: Duration::from_millis(500),
+            rate_limit: DEFAULT_RATE_LIMIT,
+        };
+        assert_eq!(policy.delay(0), Duration::from_millis(500));
+        assert_eq!(policy.delay(2), Duration::from_secs(2));
+        assert_eq!(policy.delay(9), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn only_transient_errors_are_retried() {
+        let timed_out = anyhow::Error::from(TimedOut(DEFAULT_TIMEOUT));
+        assert!(is_transient(&timed_out.context("Failed to fetch book")));
+        let server = polymarket_client_sdk::error::Error::status(
+            StatusCode::BAD_GATEWAY,
+            reqwest::Method::GET,
+            "/book".into(),
+            "",
+        );
+        assert!(is_transient(&server.into()));
+        let not_found = polymarket_client_sdk::error::Error::status(
+            StatusCode::NOT_FOUND,
+            reqwest::Method::GET,
+            "/book".into(),
+            "",
+        );
+        assert!(!is_transient(&not_found.into()));
+        assert!(!is_transient(&anyhow::anyhow!("Invalid token ID")));
+    }
+
+    #[tokio::test]
+    async fn retry_gives_up_on_permanent_errors() {
+        let calls = AtomicU32::new(0);
+        let result: Result<()> = retry(|| async {
+            calls.fetch_add(1, Ordering::Relaxed);
+            Err(anyhow::anyhow!("Market not found"))
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn fetch_all_keeps_order_and_bounds_concurrency() {
+        let in_flight = AtomicU32::new(0);
+        let most = AtomicU32::new(0);
+        let results = fetch_all(0..20u64, |i| {
+            let (in_flight, most) = (&in_flight, &most);
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                most.fetch_max(now, Ordering::SeqCst);
+                // Later items finish first, so order comes from fetch_all.
+                tokio::time::sleep(Duration::from_millis(20 - i)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(i * 2)
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(results, (0..20).map(|i| i * 2).collect::<Vec<_>>());
+        assert_eq!(most.load(Ordering::SeqCst), MAX_CONCURRENT as u32);
+
+        let failed = fetch_all([1, 2, 3], |i| async move {
+            anyhow::ensure!(i != 2, "no {i}");
+            Ok(i)
+        })
+        .await;
+        assert!(failed.is_err());
+    }
+}
@@ -0,0 +1,203 @@
+//! Nonces for on-chain transactions, and the journal that lets a stuck one
+//! be replaced.
+//!
+//! Every transaction the CLI signs is recorded in `transactions.json`. A
+//! transaction is replaced by sending another with the same nonce, which
+//! nodes only accept if it pays at least 10% more; `--replace` picks the
+//! nonce of the oldest pending transaction, and the gas filler outbids
+//! whatever the journal holds for a reused nonce.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use alloy::consensus::{Transaction as _, TxEnvelope, TypedTransaction};
+use alloy::network::{Ethereum, EthereumWallet, Network, NetworkWallet};
+use alloy::primitives::{Address, B256};
+use alloy::providers::Provider;
+use alloy::providers::fillers::NonceManager;
+use alloy::transports::{RpcError, TransportResult};
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::config::{self, SentTx};
+
+/// `fee` raised by an eighth, comfortably over the 10% nodes require of a
+/// replacement.
+pub fn bump(fee: u128) -> u128 {
+    fee.saturating_add(fee / 8).saturating_add(1)
+}
+
+/// The least a replacement for `prior` must offer as max fee and priority
+/// fee. A legacy gas price stands for both.
+pub fn outbid(prior: &SentTx) -> (u128, u128) {
+    let max_fee = prior.max_fee.or(prior.gas_price).unwrap_or_default();
+    let priority_fee = prior.priority_fee.or(prior.gas_price).unwrap_or_default();
+    (bump(max_fee), bump(priority_fee))
+}
+
+/// The newest journaled transaction from `from` with `nonce`.
+pub fn sent(chain_id: u64, from: Address, nonce: u64) -> Result<Option<SentTx>> {
+    Ok(config::load_transactions()?
+        .into_iter()
+        .rev()
+        .find(|tx| tx.chain_id == chain_id && tx.from == from && tx.nonce == nonce))
+}
+
+/// The journaled transaction with `hash`.
+pub fn find(hash: B256) -> Result<Option<SentTx>> {
+    Ok(config::load_transactions()?
+        .into_iter()
+        .find(|tx| tx.hash == hash))
+}
+
+/// The journal entry for `envelope`, signed by `from`.
+pub fn entry(from: Address, envelope: &TxEnvelope) -> SentTx {
+    SentTx {
+        hash: *envelope.tx_hash(),
+        chain_id: envelope.chain_id().unwrap_or_default(),
+        from,
+        nonce: envelope.nonce(),
+        to: envelope.to(),
+        value: envelope.value(),
+        input: envelope.input().clone(),
+        gas_limit: envelope.gas_limit(),
+        max_fee: (!envelope.is_legacy()).then(|| envelope.max_fee_per_gas()),
+        priority_fee: envelope.max_priority_fee_per_gas(),
+        gas_price: envelope.gas_price(),
+        sent_at: chrono::Utc::now(),
+    }
+}
+
+fn record(from: Address, envelope: &TxEnvelope) -> Result<()> {
+    let mut journal = config::load_transactions()?;
+    journal.push(entry(from, envelope));
+    config::save_transactions(&journal)
+}
+
+/// A wallet that journals every transaction it signs.
+#[derive(Clone, Debug)]
+pub struct RecordingWallet(pub EthereumWallet);
+
+impl NetworkWallet<Ethereum> for RecordingWallet {
+    fn default_signer_address(&self) -> Address {
+        NetworkWallet::<Ethereum>::default_signer_address(&self.0)
+    }
+
+    fn has_signer_for(&self, address: &Address) -> bool {
+        NetworkWallet::<Ethereum>::has_signer_for(&self.0, address)
+    }
+
+    fn signer_addresses(&self) -> impl Iterator<Item = Address> {
+        NetworkWallet::<Ethereum>::signer_addresses(&self.0)
+    }
+
+    async fn sign_transaction_from(
+        &self,
+        sender: Address,
+        tx: TypedTransaction,
+    ) -> alloy::signers::Result<TxEnvelope> {
+        let envelope =
+            NetworkWallet::<Ethereum>::sign_transaction_from(&self.0, sender, tx).await?;
+        // A journal that can't be written shouldn't stop the transaction.
+        if let Err(e) = record(sender, &envelope) {
+            tracing::warn!(
+                "Failed to journal transaction {}: {e:#}",
+                envelope.tx_hash()
+            );
+        }
+        Ok(envelope)
+    }
+}
+
+/// Hands out nonces from the node's pending count, never one this process
+/// already used. With `replace`, the first transaction instead takes the
+/// nonce of the oldest one still pending.
+#[derive(Clone, Debug, Default)]
+pub struct TrackedNonces {
+    replace: Arc<AtomicBool>,
+    used: Arc<Mutex<HashMap<Address, u64>>>,
+}
+
+impl TrackedNonces {
+    pub fn new(replace: bool) -> Self {
+        Self {
+            replace: Arc::new(AtomicBool::new(replace)),
+            used: Arc::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl NonceManager for TrackedNonces {
+    async fn get_next_nonce<P, N>(&self, provider: &P, address: Address) -> TransportResult<u64>
+    where
+        P: Provider<N>,
+        N: Network,
+    {
+        let pending = provider.get_transaction_count(address).pending().await?;
+        let nonce = if self.replace.swap(false, Ordering::Relaxed) {
+            let mined = provider.get_transaction_count(address).latest().await?;
+            if mined >= pending {
+                return Err(RpcError::local_usage_str(&format!(
+                    "{address} has no pending transaction to replace"
+                )));
+            }
+            mined
+        } else {
+            let used = self.used.lock().expect("nonce lock poisoned");
+            used.get(&address)
+                .map_or(pending, |last| pending.max(last + 1))
+        };
+        self.used
+            .lock()
+            .expect("nonce lock poisoned")
+            .insert(address, nonce);
+        Ok(nonce)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{Bytes, U256};
+
+    use super::*;
+
+    const GWEI: u128 = 1_000_000_000;
+
+    fn sent_tx(
+        max_fee: Option<u128>,
+        priority_fee: Option<u128>,
+        gas_price: Option<u128>,
+    ) -> SentTx {
+        SentTx {
+            hash: B256::ZERO,
+            chain_id: 137,
+            from: Address::ZERO,
+            nonce: 7,
+            to: None,
+            value: U256::ZERO,
+            input: Bytes::new(),
+            gas_limit: 21_000,
+            max_fee,
+            priority_fee,
+            gas_price,
+            sent_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn replacements_pay_over_ten_percent_more() {
+        assert_eq!(bump(80 * GWEI), 90 * GWEI + 1);
+        assert!(bump(7) * 10 > 7 * 11);
+        assert_eq!(
+            outbid(&sent_tx(Some(80 * GWEI), Some(40 * GWEI), None)),
+            (90 * GWEI + 1, 45 * GWEI + 1)
+        );
+        // A legacy price is both the max fee and the tip.
+        assert_eq!(
+            outbid(&sent_tx(None, None, Some(40 * GWEI))),
+            (45 * GWEI + 1, 45 * GWEI + 1)
+        );
+    }
+}
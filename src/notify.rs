@@ -0,0 +1,184 @@
+//! Notifications that leave the terminal: desktop pop-ups and webhook POSTs,
+//! sent to the notifiers in the profile's `notifications` list.
+
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use serde_json::{Value, json};
+
+use crate::config::{Notifier, NotifyEvent, NotifyFormat};
+
+/// Something worth telling the user about, with a short human-readable
+/// summary for chat and desktop notifiers and the full event for webhooks.
+pub struct Notification {
+    pub event: NotifyEvent,
+    pub title: String,
+    pub body: String,
+    pub payload: Value,
+}
+
+/// Sends `notification` to every notifier that wants its event. Failures
+/// are reported on stderr so a broken webhook doesn't stop a long-running
+/// command.
+pub async fn publish(notifiers: &[Notifier], notification: &Notification) {
+    for notifier in notifiers.iter().filter(|n| n.wants(notification.event)) {
+        if let Err(e) = send(notifier, notification).await {
+            eprintln!("Notification failed: {e:#}");
+        }
+    }
+}
+
+async fn send(notifier: &Notifier, notification: &Notification) -> Result<()> {
+    if notifier.format == NotifyFormat::Desktop {
+        return desktop(&notification.title, &notification.body);
+    }
+    let url = notifier
+        .url
+        .as_deref()
+        .with_context(|| format!("{:?} notifier has no url", notifier.format))?;
+    webhook(url, &payload(notifier.format, notification)).await
+}
+
+/// The request body for a webhook-style notifier.
+fn payload(format: NotifyFormat, notification: &Notification) -> Value {
+    let Notification { title, body, .. } = notification;
+    match format {
+        NotifyFormat::Discord => json!({"content": format!("**{title}**\n{body}")}),
+        NotifyFormat::Slack => json!({"text": format!("*{title}*\n{body}")}),
+        NotifyFormat::Webhook | NotifyFormat::Desktop => notification.payload.clone(),
+    }
+}
+
+/// Checks that every webhook-style notifier has a usable URL.
+pub fn validate(notifiers: &[Notifier]) -> Result<()> {
+    for n in notifiers
+        .iter()
+        .filter(|n| n.format != NotifyFormat::Desktop)
+    {
+        let url = n
+            .url
+            .as_deref()
+            .with_context(|| format!("{:?} notifier has no url", n.format))?;
+        reqwest::Url::parse(url).map_err(|e| anyhow::anyhow!("Invalid notifier url {url}: {e}"))?;
+    }
+    Ok(())
+}
+
+/// The profile's notifiers, plus a desktop notifier for `--desktop` and a
+/// webhook for `--webhook`.
+pub fn with_flags(desktop: bool, webhook: Option<String>) -> Result<Vec<Notifier>> {
+    if let Some(url) = &webhook {
+        reqwest::Url::parse(url).map_err(|e| anyhow::anyhow!("Invalid --webhook: {url} ({e})"))?;
+    }
+    let mut notifiers = crate::config::notifiers();
+    validate(&notifiers)?;
+    if desktop {
+        notifiers.push(Notifier {
+            format: NotifyFormat::Desktop,
+            url: None,
+            events: Vec::new(),
+        });
+    }
+    if let Some(url) = webhook {
+        notifiers.push(Notifier {
+            format: NotifyFormat::Webhook,
+            url: Some(url),
+            events: Vec::new(),
+        });
+    }
+    Ok(notifiers)
+}
+
+/// Shows a desktop notification with `notify-send` (Linux) or `osascript`
+/// (macOS).
+pub fn desktop(title: &str, body: &str) -> Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification {} with title {}",
+            applescript_string(body),
+            applescript_string(title)
+        );
+        let mut c = Command::new("osascript");
+        c.arg("-e").arg(script);
+        c
+    } else {
+        let mut c = Command::new("notify-send");
+        c.arg(title).arg(body);
+        c
+    };
+    let program = command.get_program().to_string_lossy().into_owned();
+    let status = command
+        .status()
+        .with_context(|| format!("Failed to run {program}"))?;
+    anyhow::ensure!(status.success(), "{program} exited with {status}");
+    Ok(())
+}
+
+fn applescript_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// POSTs `payload` as JSON and fails on a non-2xx response.
+pub async fn webhook(url: &str, payload: &Value) -> Result<()> {
+    crate::net::http_client()?
+        .post(url)
+        .json(payload)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| anyhow::anyhow!("Webhook {url} failed: {e}"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification() -> Notification {
+        Notification {
+            event: NotifyEvent::Fill,
+            title: "Fill".into(),
+            body: "BUY 10 @ 0.5".into(),
+            payload: json!({"event": "fill", "price": "0.5"}),
+        }
+    }
+
+    #[test]
+    fn payload_formats_chat_messages() {
+        let n = notification();
+        assert_eq!(
+            payload(NotifyFormat::Discord, &n),
+            json!({"content": "**Fill**\nBUY 10 @ 0.5"})
+        );
+        assert_eq!(
+            payload(NotifyFormat::Slack, &n),
+            json!({"text": "*Fill*\nBUY 10 @ 0.5"})
+        );
+        assert_eq!(payload(NotifyFormat::Webhook, &n), n.payload);
+    }
+
+    #[test]
+    fn validate_requires_urls_except_desktop() {
+        let notifier = |format, url: Option<&str>| Notifier {
+            format,
+            url: url.map(Into::into),
+            events: Vec::new(),
+        };
+        assert!(validate(&[notifier(NotifyFormat::Desktop, None)]).is_ok());
+        assert!(validate(&[notifier(NotifyFormat::Slack, None)]).is_err());
+        assert!(validate(&[notifier(NotifyFormat::Webhook, Some("not a url"))]).is_err());
+        assert!(
+            validate(&[notifier(
+                NotifyFormat::Discord,
+                Some("https://discord.com/api/webhooks/1/x")
+            )])
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn applescript_string_escapes_quotes() {
+        assert_eq!(applescript_string(r#"say "hi""#), r#""say \"hi\"""#);
+        assert_eq!(applescript_string(r"a\b"), r#""a\\b""#);
+    }
+}
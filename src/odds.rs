@@ -0,0 +1,172 @@
+//! Prices as the other conventions bettors quote them in: implied
+//! probability (42%), decimal odds (2.38) and American odds (+138).
+//!
+//! A Polymarket price is the probability the market implies, so each
+//! convention is a function of it. `--odds-format` picks the one table
+//! output uses; JSON and CSV keep the price, so scripts aren't affected.
+
+use anyhow::Result;
+use polymarket_client_sdk::types::Decimal;
+use rust_decimal::RoundingStrategy;
+use serde::{Deserialize, Serialize};
+
+use crate::errors;
+
+const HUNDRED: Decimal = Decimal::ONE_HUNDRED;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OddsFormat {
+    /// 0.42
+    #[default]
+    Price,
+    /// 42%
+    Probability,
+    /// 2.38: the payout per dollar staked, stake included
+    Decimal,
+    /// +138: the profit on $100, or -150: the stake to win $100
+    American,
+}
+
+impl std::fmt::Display for OddsFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Price => "price",
+            Self::Probability => "probability",
+            Self::Decimal => "decimal odds",
+            Self::American => "American odds",
+        })
+    }
+}
+
+/// Decimal odds for `price`, unrounded. `None` for a price of zero.
+pub fn decimal_odds(price: Decimal) -> Option<Decimal> {
+    (price > Decimal::ZERO).then(|| Decimal::ONE / price)
+}
+
+/// American odds for `price`, unrounded: positive below even money,
+/// negative above it. `None` for a price of zero or one.
+pub fn american_odds(price: Decimal) -> Option<Decimal> {
+    if price <= Decimal::ZERO || price >= Decimal::ONE {
+        return None;
+    }
+    let against = Decimal::ONE - price;
+    Some(if price <= Decimal::new(5, 1) {
+        against / price * HUNDRED
+    } else {
+        -(price / against * HUNDRED)
+    })
+}
+
+/// `price` in `format`, rounded for display. A price the format can't
+/// express (decimal odds of 0, American odds of 0 or 1) is shown as is.
+pub fn format(price: Decimal, format: OddsFormat) -> String {
+    let shown = match format {
+        OddsFormat::Price => None,
+        OddsFormat::Probability => Some(format!("{}%", (price * HUNDRED).round_dp(2).normalize())),
+        OddsFormat::Decimal => decimal_odds(price).map(|d| format!("{:.2}", d.round_dp(2))),
+        OddsFormat::American => american_odds(price).map(|a| {
+            let a = a.round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero);
+            if a.is_sign_negative() {
+                a.to_string()
+            } else {
+                format!("+{a}")
+            }
+        }),
+    };
+    shown.unwrap_or_else(|| price.to_string())
+}
+
+/// Parses `s` as a price in `format`. Without one it is told from the
+/// value: a trailing `%` is a probability, a leading sign American odds, a
+/// number of 1 or more decimal odds and anything below 1 a price.
+pub fn parse(s: &str, format: Option<OddsFormat>) -> Result<Decimal> {
+    let s = s.trim();
+    let format = format.unwrap_or_else(|| {
+        if s.ends_with('%') {
+            OddsFormat::Probability
+        } else if s.starts_with(['+', '-']) {
+            OddsFormat::American
+        } else if s.parse::<Decimal>().is_ok_and(|d| d >= Decimal::ONE) {
+            OddsFormat::Decimal
+        } else {
+            OddsFormat::Price
+        }
+    });
+    let number = |s: &str| -> Result<Decimal> {
+        s.trim()
+            .parse()
+            .map_err(|_| errors::validation(format!("Invalid {format}: {s}")))
+    };
+    let price = match format {
+        OddsFormat::Price => number(s)?,
+        OddsFormat::Probability => number(s.trim_end_matches('%'))? / HUNDRED,
+        OddsFormat::Decimal => {
+            let odds = number(s)?;
+            anyhow::ensure!(
+                odds >= Decimal::ONE,
+                errors::validation("Decimal odds must be at least 1")
+            );
+            Decimal::ONE / odds
+        }
+        OddsFormat::American => {
+            let odds = number(s)?;
+            anyhow::ensure!(
+                odds.abs() >= HUNDRED,
+                errors::validation("American odds must be +100 or more, or -100 or less")
+            );
+            if odds.is_sign_negative() {
+                -odds / (HUNDRED - odds)
+            } else {
+                HUNDRED / (odds + HUNDRED)
+            }
+        }
+    };
+    anyhow::ensure!(
+        (Decimal::ZERO..=Decimal::ONE).contains(&price),
+        errors::validation(format!("{s} is not a price between 0 and 1"))
+    );
+    Ok(price)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn formats_each_convention() {
+        let price = dec!(0.42);
+        assert_eq!(format(price, OddsFormat::Price), "0.42");
+        assert_eq!(format(price, OddsFormat::Probability), "42%");
+        assert_eq!(format(price, OddsFormat::Decimal), "2.38");
+        assert_eq!(format(price, OddsFormat::American), "+138");
+        assert_eq!(format(dec!(0.6), OddsFormat::American), "-150");
+        assert_eq!(format(dec!(0.5), OddsFormat::American), "+100");
+        // Certain outcomes have no American odds.
+        assert_eq!(format(Decimal::ONE, OddsFormat::American), "1");
+        assert_eq!(format(Decimal::ZERO, OddsFormat::Decimal), "0");
+    }
+
+    #[test]
+    fn parses_and_infers_each_convention() {
+        assert_eq!(parse("0.42", None).unwrap(), dec!(0.42));
+        assert_eq!(parse("42%", None).unwrap(), dec!(0.42));
+        assert_eq!(parse("2.5", None).unwrap(), dec!(0.4));
+        assert_eq!(parse("+150", None).unwrap(), dec!(0.4));
+        assert_eq!(parse("-150", None).unwrap(), dec!(0.6));
+        assert_eq!(parse("150", Some(OddsFormat::American)).unwrap(), dec!(0.4));
+        assert_eq!(
+            parse("0.5", Some(OddsFormat::Probability)).unwrap(),
+            dec!(0.005)
+        );
+    }
+
+    #[test]
+    fn parse_rejects_impossible_values() {
+        assert!(parse("+50", None).is_err());
+        assert!(parse("0.5", Some(OddsFormat::Decimal)).is_err());
+        assert!(parse("120%", None).is_err());
+        assert!(parse("cheap", None).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+use polymarket_client_sdk::types::Decimal;
+use serde_json::json;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+use super::{OutputFormat, format_price, print_csv, truncate};
+use crate::config::Alert;
+
+pub fn print_alerts(alerts: &[Alert], output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            if alerts.is_empty() {
+                println!(
+                    "No alerts. Add one with `polymarket alert add --market <id> --above <price>`."
+                );
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "ID")]
+                id: u32,
+                #[tabled(rename = "Question")]
+                question: String,
+                #[tabled(rename = "Outcome")]
+                outcome: String,
+                #[tabled(rename = "Condition")]
+                condition: String,
+            }
+            let rows: Vec<Row> = alerts
+                .iter()
+                .map(|a| Row {
+                    id: a.id,
+                    question: truncate(&a.question, 50),
+                    outcome: a.outcome.clone(),
+                    condition: format!("{} {}", a.direction, format_price(a.price)),
+                })
+                .collect();
+            let table = Table::new(rows).with(Style::rounded()).to_string();
+            println!("{table}");
+        }
+        OutputFormat::Json => super::print_json(&alerts)?,
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = alerts
+                .iter()
+                .map(|a| {
+                    vec![
+                        a.id.to_string(),
+                        a.market_id.clone(),
+                        a.question.clone(),
+                        a.outcome.clone(),
+                        a.token_id.clone(),
+                        a.direction.to_string(),
+                        a.price.to_string(),
+                    ]
+                })
+                .collect();
+            print_csv(
+                &[
+                    "id",
+                    "market_id",
+                    "question",
+                    "outcome",
+                    "token_id",
+                    "direction",
+                    "price",
+                ],
+                &rows,
+            );
+        }
+    }
+    Ok(())
+}
+
+pub fn print_alert_added(alert: &Alert, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Csv => println!(
+            "Alert #{}: {} — {} {} {}",
+            alert.id, alert.question, alert.outcome, alert.direction, alert.price
+        ),
+        OutputFormat::Plain => println!("{}", alert.id),
+        OutputFormat::Json => super::print_json(alert)?,
+    }
+    Ok(())
+}
+
+/// A fired alert, as printed by `alert run -o json` and POSTed to webhooks.
+pub fn alert_event(alert: &Alert, price: Decimal) -> serde_json::Value {
+    json!({
+        "event": "alert",
+        "id": alert.id,
+        "market_id": alert.market_id,
+        "question": alert.question,
+        "outcome": alert.outcome,
+        "token_id": alert.token_id,
+        "direction": alert.direction,
+        "threshold": alert.price.to_string(),
+        "price": price.to_string(),
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+/// One line per fired alert; JSON output is newline-delimited.
+pub fn print_alert_event(
+    alert: &Alert,
+    price: Decimal,
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Json => println!("{}", alert_event(alert, price)),
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => println!(
+            "{}  #{} {} — {} at {} ({} {})",
+            super::time::format_timestamp(chrono::Utc::now()),
+            alert.id,
+            alert.question,
+            alert.outcome,
+            price,
+            alert.direction,
+            alert.price
+        ),
+    }
+    Ok(())
+}
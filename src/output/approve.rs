@@ -69,10 +69,10 @@ pub fn print_approval_status(statuses: &[ApprovalStatus], output: &OutputFormat)
                     obj
                 })
                 .collect();
-            println!("{}", serde_json::to_string_pretty(&json)?);
+            super::print_json(&json)?;
             Ok(())
         }
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             let rows: Vec<ApprovalRow> = statuses
                 .iter()
                 .map(|s| ApprovalRow {
@@ -0,0 +1,87 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use polymarket_client_sdk::types::Address;
+use serde_json::json;
+
+use super::{OutputFormat, print_detail_table};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SessionState {
+    Active,
+    Expired,
+    None,
+    /// A Ledger, which has no key to encrypt a session with.
+    Unsupported,
+}
+
+impl SessionState {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Expired => "expired",
+            Self::None => "none",
+            Self::Unsupported => "unsupported",
+        }
+    }
+}
+
+pub struct SessionStatus {
+    pub profile: String,
+    pub wallet: Address,
+    pub state: SessionState,
+    pub api_key: Option<String>,
+    pub created: Option<DateTime<Utc>>,
+    pub expires: Option<DateTime<Utc>>,
+    /// The profile has an API key saved with `apikey --save`, which is used
+    /// instead of any session.
+    pub saved_key: bool,
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    super::time::format_datetime_zoned(time)
+}
+
+pub fn print_session_status(status: &SessionStatus, output: &OutputFormat) -> Result<()> {
+    match output {
+        OutputFormat::Plain => println!("{}", status.state.as_str()),
+        OutputFormat::Json => super::print_json(&json!({
+            "profile": status.profile,
+            "wallet": status.wallet.to_string(),
+            "state": status.state.as_str(),
+            "api_key": status.api_key,
+            "created": status.created.map(|t| t.to_rfc3339()),
+            "expires": status.expires.map(|t| t.to_rfc3339()),
+            "saved_key": status.saved_key,
+        }))?,
+        OutputFormat::Table | OutputFormat::Csv => {
+            let state = match status.state {
+                SessionState::Active => "Active",
+                SessionState::Expired => "Expired (renewed on the next authenticated command)",
+                SessionState::None => "None (started by the next authenticated command)",
+                SessionState::Unsupported => "Not cached for a Ledger",
+            };
+            let mut rows = vec![
+                ["Profile".into(), status.profile.clone()],
+                ["Wallet".into(), status.wallet.to_string()],
+                ["Session".into(), state.into()],
+            ];
+            if let Some(key) = &status.api_key {
+                rows.push(["API key".into(), key.clone()]);
+            }
+            if let Some(t) = status.created {
+                rows.push(["Started".into(), format_time(t)]);
+            }
+            if let Some(t) = status.expires {
+                rows.push(["Expires".into(), format_time(t)]);
+            }
+            if status.saved_key {
+                rows.push([
+                    "Note".into(),
+                    "The profile's saved API key is used instead of the session".into(),
+                ]);
+            }
+            print_detail_table(rows);
+        }
+    }
+    Ok(())
+}
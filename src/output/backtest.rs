@@ -0,0 +1,131 @@
+use polymarket_client_sdk::types::Decimal;
+use serde_json::json;
+
+use super::{OutputFormat, print_csv, print_detail_table};
+use crate::backtest::{Fill, Report, Signal};
+
+fn percent(d: Decimal) -> String {
+    format!("{}%", (d * Decimal::ONE_HUNDRED).round_dp(2).normalize())
+}
+
+fn usdc(d: Decimal) -> String {
+    let d = d.round_dp(2);
+    if d.is_sign_negative() && !d.is_zero() {
+        format!("-${:.2}", d.abs())
+    } else {
+        format!("${d:.2}")
+    }
+}
+
+fn date(timestamp: Option<i64>) -> String {
+    timestamp
+        .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+        .map_or_else(|| "—".into(), super::time::format_datetime)
+}
+
+fn side(fill: &Fill) -> &'static str {
+    match fill.signal {
+        Signal::Buy => "buy",
+        Signal::Sell => "sell",
+    }
+}
+
+fn fill_to_json(fill: &Fill) -> serde_json::Value {
+    json!({
+        "timestamp": fill.timestamp,
+        "time": super::time::iso_from_unix(fill.timestamp),
+        "side": side(fill),
+        "price": fill.price.to_string(),
+        "shares": fill.shares.round_dp(6).normalize().to_string(),
+        "usdc": fill.usdc.round_dp(6).normalize().to_string(),
+        "fee": fill.fee.round_dp(6).normalize().to_string(),
+    })
+}
+
+/// The summary as a table, or the simulated fills under `-o csv`.
+pub fn print_backtest(report: &Report, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Plain => println!("{}", report.pnl().round_dp(2)),
+        OutputFormat::Table => {
+            let rows = vec![
+                ["Strategy".into(), report.name.clone()],
+                [
+                    "Period".into(),
+                    format!("{} to {}", date(report.first), date(report.last)),
+                ],
+                ["Prices".into(), report.points.to_string()],
+                ["Starting cash".into(), usdc(report.start_cash)],
+                ["Final equity".into(), usdc(report.final_equity)],
+                [
+                    "PnL".into(),
+                    format!("{} ({})", usdc(report.pnl()), percent(report.return_pct())),
+                ],
+                [
+                    "Max drawdown".into(),
+                    format!(
+                        "{} ({})",
+                        usdc(report.max_drawdown),
+                        percent(report.max_drawdown_pct)
+                    ),
+                ],
+                ["Fills".into(), report.fills.len().to_string()],
+                [
+                    "Round trips".into(),
+                    format!("{} ({} won)", report.round_trips.len(), report.wins()),
+                ],
+                [
+                    "Win rate".into(),
+                    report.win_rate().map_or_else(|| "—".into(), percent),
+                ],
+                ["Fees".into(), usdc(report.fees)],
+                [
+                    "Open position".into(),
+                    format!("{} shares", report.open_shares.round_dp(2).normalize()),
+                ],
+            ];
+            print_detail_table(rows);
+            if !report.open_shares.is_zero() {
+                println!("The open position is valued at the last price.");
+            }
+        }
+        OutputFormat::Json => super::print_json(&json!({
+            "strategy": report.name,
+            "prices": report.points,
+            "from": report.first,
+            "to": report.last,
+            "start_cash": report.start_cash.to_string(),
+            "final_equity": report.final_equity.round_dp(6).normalize().to_string(),
+            "pnl": report.pnl().round_dp(6).normalize().to_string(),
+            "return": report.return_pct().round_dp(6).normalize().to_string(),
+            "max_drawdown": report.max_drawdown.round_dp(6).normalize().to_string(),
+            "max_drawdown_pct": report.max_drawdown_pct.round_dp(6).normalize().to_string(),
+            "round_trips": report.round_trips.len(),
+            "wins": report.wins(),
+            "win_rate": report.win_rate().map(|r| r.round_dp(6).normalize().to_string()),
+            "fees": report.fees.round_dp(6).normalize().to_string(),
+            "open_shares": report.open_shares.round_dp(6).normalize().to_string(),
+            "fills": report.fills.iter().map(fill_to_json).collect::<Vec<_>>(),
+        }))?,
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = report
+                .fills
+                .iter()
+                .map(|f| {
+                    vec![
+                        f.timestamp.to_string(),
+                        side(f).into(),
+                        f.price.to_string(),
+                        f.shares.round_dp(6).normalize().to_string(),
+                        f.usdc.round_dp(6).normalize().to_string(),
+                        f.fee.round_dp(6).normalize().to_string(),
+                    ]
+                })
+                .collect();
+            print_csv(
+                &["timestamp", "side", "price", "shares", "usdc", "fee"],
+                &rows,
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use polymarket_client_sdk::types::{Address, Decimal};
+use serde_json::json;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+use super::OutputFormat;
+
+pub struct WalletBalance {
+    pub label: &'static str,
+    pub address: Address,
+    pub usdc: Decimal,
+    pub pol: Decimal,
+}
+
+pub fn print_balances(balances: &[WalletBalance], output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "Wallet")]
+                label: &'static str,
+                #[tabled(rename = "Address")]
+                address: String,
+                #[tabled(rename = "USDC")]
+                usdc: String,
+                #[tabled(rename = "POL")]
+                pol: String,
+            }
+            let rows: Vec<Row> = balances
+                .iter()
+                .map(|b| Row {
+                    label: b.label,
+                    address: b.address.to_string(),
+                    usdc: format!("{:.2}", b.usdc),
+                    pol: format!("{:.4}", b.pol),
+                })
+                .collect();
+            let table = Table::new(rows).with(Style::rounded()).to_string();
+            println!("{table}");
+        }
+        OutputFormat::Json => {
+            let data: Vec<_> = balances
+                .iter()
+                .map(|b| {
+                    json!({
+                        "wallet": b.label.to_lowercase().replace(' ', "_"),
+                        "address": b.address.to_string(),
+                        "usdc": b.usdc.to_string(),
+                        "pol": b.pol.to_string(),
+                    })
+                })
+                .collect();
+            super::print_json(&data)?;
+        }
+    }
+    Ok(())
+}
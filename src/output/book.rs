@@ -0,0 +1,251 @@
+use polymarket_client_sdk::clob::types::Side;
+use polymarket_client_sdk::types::{Decimal, U256};
+use serde_json::json;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+use super::{
+    OutputFormat, csv_opt, format_decimal, format_number, format_price, print_csv,
+    print_detail_table,
+};
+
+/// Shares and USDC resting on each side within `cents` of the midpoint,
+/// or in the whole book when `cents` is `None`.
+pub struct Depth {
+    pub cents: Option<Decimal>,
+    pub bid_shares: Decimal,
+    pub bid_usdc: Decimal,
+    pub ask_shares: Decimal,
+    pub ask_usdc: Decimal,
+}
+
+impl Depth {
+    /// (bids − asks) / (bids + asks) in shares: 1 is all bids, −1 all asks.
+    pub fn imbalance(&self) -> Option<Decimal> {
+        let total = self.bid_shares + self.ask_shares;
+        (!total.is_zero()).then(|| (self.bid_shares - self.ask_shares) / total)
+    }
+
+    fn band(&self) -> String {
+        self.cents
+            .map_or_else(|| "Whole book".into(), |c| format!("±{}¢", c.normalize()))
+    }
+}
+
+/// What taking `size` shares on `side` from the book would do.
+pub struct FillEstimate {
+    pub side: Side,
+    pub size: Decimal,
+    pub filled: Decimal,
+    /// USDC paid (buy) or received (sell).
+    pub usdc: Decimal,
+    /// The last level the order reaches.
+    pub worst: Option<Decimal>,
+}
+
+impl FillEstimate {
+    pub fn vwap(&self) -> Option<Decimal> {
+        (!self.filled.is_zero()).then(|| self.usdc / self.filled)
+    }
+
+    /// How much worse than `mid` the average fill is, per share.
+    pub fn slippage(&self, mid: Decimal) -> Option<Decimal> {
+        let vwap = self.vwap()?;
+        Some(match self.side {
+            Side::Sell => mid - vwap,
+            _ => vwap - mid,
+        })
+    }
+}
+
+pub struct BookStats {
+    pub token_id: U256,
+    pub bid: Option<Decimal>,
+    pub ask: Option<Decimal>,
+    pub depth: Vec<Depth>,
+    pub fill: Option<FillEstimate>,
+}
+
+impl BookStats {
+    pub fn mid(&self) -> Option<Decimal> {
+        Some((self.bid? + self.ask?) / Decimal::TWO)
+    }
+
+    pub fn spread(&self) -> Option<Decimal> {
+        Some(self.ask? - self.bid?)
+    }
+
+    fn slippage(&self) -> Option<Decimal> {
+        self.fill.as_ref()?.slippage(self.mid()?)
+    }
+
+    fn slippage_pct(&self) -> Option<Decimal> {
+        let mid = self.mid().filter(|m| !m.is_zero())?;
+        Some(self.slippage()? / mid * Decimal::ONE_HUNDRED)
+    }
+}
+
+fn price(d: Option<Decimal>) -> String {
+    d.map_or_else(|| "—".into(), format_price)
+}
+
+fn ratio(d: Option<Decimal>) -> String {
+    d.map_or_else(|| "—".into(), |d| format!("{:+.2}", d.round_dp(2)))
+}
+
+fn cents(d: Decimal) -> String {
+    format!("{:+}¢", (d * Decimal::ONE_HUNDRED).round_dp(2).normalize())
+}
+
+pub fn print_book_stats(stats: &BookStats, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            print_detail_table(vec![
+                ["Token".into(), stats.token_id.to_string()],
+                ["Best bid".into(), price(stats.bid)],
+                ["Best ask".into(), price(stats.ask)],
+                ["Midpoint".into(), price(stats.mid())],
+                ["Spread".into(), price(stats.spread())],
+            ]);
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "Depth")]
+                band: String,
+                #[tabled(rename = "Bid Shares")]
+                bid_shares: String,
+                #[tabled(rename = "Bid USDC")]
+                bid_usdc: String,
+                #[tabled(rename = "Ask Shares")]
+                ask_shares: String,
+                #[tabled(rename = "Ask USDC")]
+                ask_usdc: String,
+                #[tabled(rename = "Imbalance")]
+                imbalance: String,
+            }
+            let rows: Vec<Row> = stats
+                .depth
+                .iter()
+                .map(|d| Row {
+                    band: d.band(),
+                    bid_shares: format_number(d.bid_shares),
+                    bid_usdc: format_decimal(d.bid_usdc),
+                    ask_shares: format_number(d.ask_shares),
+                    ask_usdc: format_decimal(d.ask_usdc),
+                    imbalance: ratio(d.imbalance()),
+                })
+                .collect();
+            println!("{}", Table::new(rows).with(Style::rounded()));
+            if let Some(fill) = &stats.fill {
+                let mut rows = vec![
+                    [
+                        "Order".into(),
+                        format!("{} {} shares", fill.side, fill.size.normalize()),
+                    ],
+                    ["Fillable".into(), fill.filled.normalize().to_string()],
+                    [
+                        "VWAP".into(),
+                        fill.vwap()
+                            .map_or_else(|| "—".into(), |v| format!("{:.4}", v.round_dp(4))),
+                    ],
+                    ["USDC".into(), format_decimal(fill.usdc)],
+                    ["Worst price".into(), price(fill.worst)],
+                ];
+                if let Some(slippage) = stats.slippage() {
+                    let pct = stats.slippage_pct().map_or_else(String::new, |p| {
+                        format!(" ({}%)", p.round_dp(2).normalize())
+                    });
+                    rows.push([
+                        "Slippage vs mid".into(),
+                        format!("{}{pct}", cents(slippage)),
+                    ]);
+                }
+                if fill.filled < fill.size {
+                    rows.push([
+                        "Unfilled".into(),
+                        format!(
+                            "{} shares: not enough liquidity",
+                            (fill.size - fill.filled).normalize()
+                        ),
+                    ]);
+                }
+                print_detail_table(rows);
+            }
+        }
+        OutputFormat::Plain => {
+            // The VWAP when an order was given, otherwise the midpoint.
+            let value = match &stats.fill {
+                Some(fill) => fill.vwap(),
+                None => stats.mid(),
+            };
+            println!(
+                "{}",
+                value.map_or_else(|| "—".into(), |v| v.round_dp(6).normalize().to_string())
+            );
+        }
+        OutputFormat::Json => {
+            let depth: Vec<_> = stats
+                .depth
+                .iter()
+                .map(|d| {
+                    json!({
+                        "within_cents": d.cents.map(|c| c.normalize().to_string()),
+                        "bid_shares": d.bid_shares.to_string(),
+                        "bid_usdc": d.bid_usdc.to_string(),
+                        "ask_shares": d.ask_shares.to_string(),
+                        "ask_usdc": d.ask_usdc.to_string(),
+                        "imbalance": d.imbalance().map(|i| i.round_dp(6).to_string()),
+                    })
+                })
+                .collect();
+            let fill = stats.fill.as_ref().map(|f| {
+                json!({
+                    "side": f.side.to_string(),
+                    "size": f.size.to_string(),
+                    "filled": f.filled.to_string(),
+                    "vwap": f.vwap().map(|v| v.round_dp(6).normalize().to_string()),
+                    "usdc": f.usdc.to_string(),
+                    "worst_price": f.worst.map(|p| p.to_string()),
+                    "slippage": stats.slippage().map(|s| s.round_dp(6).normalize().to_string()),
+                    "slippage_pct": stats.slippage_pct().map(|p| p.round_dp(4).normalize().to_string()),
+                })
+            });
+            super::print_json(&json!({
+                "token_id": stats.token_id.to_string(),
+                "bid": stats.bid.map(|d| d.to_string()),
+                "ask": stats.ask.map(|d| d.to_string()),
+                "mid": stats.mid().map(|d| d.to_string()),
+                "spread": stats.spread().map(|d| d.to_string()),
+                "depth": depth,
+                "order": fill,
+            }))?;
+        }
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = stats
+                .depth
+                .iter()
+                .map(|d| {
+                    vec![
+                        csv_opt(d.cents.map(|c| c.normalize())),
+                        d.bid_shares.to_string(),
+                        d.bid_usdc.to_string(),
+                        d.ask_shares.to_string(),
+                        d.ask_usdc.to_string(),
+                        csv_opt(d.imbalance().map(|i| i.round_dp(6))),
+                    ]
+                })
+                .collect();
+            print_csv(
+                &[
+                    "within_cents",
+                    "bid_shares",
+                    "bid_usdc",
+                    "ask_shares",
+                    "ask_usdc",
+                    "imbalance",
+                ],
+                &rows,
+            );
+        }
+    }
+    Ok(())
+}
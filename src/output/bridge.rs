@@ -1,8 +1,11 @@
 #![allow(clippy::items_after_statements)]
 
+use alloy::primitives::B256;
 use polymarket_client_sdk::bridge::types::{
-    DepositResponse, DepositTransactionStatus, StatusResponse, SupportedAssetsResponse,
+    DepositResponse, DepositTransaction, DepositTransactionStatus, StatusResponse,
+    SupportedAssetsResponse,
 };
+use polymarket_client_sdk::types::{Address, Decimal};
 use serde_json::json;
 use tabled::settings::Style;
 use tabled::{Table, Tabled};
@@ -11,7 +14,7 @@ use super::{OutputFormat, detail_field, format_decimal, print_detail_table};
 
 pub fn print_deposit(response: &DepositResponse, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             let mut rows = Vec::new();
             detail_field!(rows, "EVM", format!("{}", response.address.evm));
             detail_field!(rows, "Solana", response.address.svm.clone());
@@ -39,7 +42,7 @@ pub fn print_supported_assets(
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if response.supported_assets.is_empty() {
                 println!("No supported assets found.");
                 return Ok(());
@@ -96,6 +99,96 @@ pub fn print_supported_assets(
     Ok(())
 }
 
+/// Where to send USDC on Ethereum so it reaches `wallet` on Polygon.
+pub struct DepositPlan {
+    pub wallet: Address,
+    pub deposit_address: Address,
+    pub token: Address,
+    /// Smallest deposit the bridge accepts, if it lists one.
+    pub minimum: Option<Decimal>,
+}
+
+pub struct DepositResult {
+    pub wallet: Address,
+    pub amount: Decimal,
+    /// The Ethereum transfer, when `deposit` sent it.
+    pub tx_hash: Option<B256>,
+    /// The bridge's last status, unless it wasn't waited for.
+    pub status: Option<DepositTransactionStatus>,
+}
+
+pub fn print_deposit_plan(plan: &DepositPlan, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        // The plan comes before the transfer's result, which owns stdout.
+        OutputFormat::Json | OutputFormat::Plain => {
+            eprintln!(
+                "Send USDC on Ethereum to {} to fund {}",
+                plan.deposit_address, plan.wallet
+            );
+        }
+        OutputFormat::Table | OutputFormat::Csv => {
+            let mut rows = vec![
+                ["Send to".into(), plan.deposit_address.to_string()],
+                ["Network".into(), "Ethereum mainnet".into()],
+                ["Token".into(), format!("USDC ({})", plan.token)],
+                ["Credited to".into(), plan.wallet.to_string()],
+            ];
+            if let Some(min) = plan.minimum {
+                rows.push(["Minimum".into(), format!("{min} USDC")]);
+            }
+            print_detail_table(rows);
+            println!(
+                "The USDC arrives in the wallet on Polygon, usually within minutes. Send it with `polymarket deposit --amount <USDC>`, or from any Ethereum wallet and follow it with `polymarket deposit --wait`."
+            );
+        }
+    }
+    Ok(())
+}
+
+/// A line on stderr each time the deposit moves along.
+pub fn print_deposit_progress(tx: &DepositTransaction) {
+    eprintln!(
+        "{}  {}",
+        super::time::format_clock(chrono::Utc::now()),
+        format_status(&tx.status)
+    );
+}
+
+pub fn print_deposit_result(result: &DepositResult, output: &OutputFormat) -> anyhow::Result<()> {
+    let status = result.status.as_ref().map(format_status);
+    let etherscan = result
+        .tx_hash
+        .map(|h| format!("https://etherscan.io/tx/{h}"));
+    match output {
+        OutputFormat::Json => super::print_json(&json!({
+            "wallet": result.wallet.to_string(),
+            "amount": result.amount.to_string(),
+            "transaction_hash": result.tx_hash.map(|h| h.to_string()),
+            "status": status,
+            "etherscan": etherscan,
+        }))?,
+        OutputFormat::Plain => println!("{}", status.unwrap_or("Sent")),
+        OutputFormat::Table | OutputFormat::Csv => {
+            let mut rows = vec![
+                ["Wallet".into(), result.wallet.to_string()],
+                ["Amount".into(), format!("{} USDC", result.amount)],
+            ];
+            if let Some(h) = result.tx_hash {
+                rows.push(["Tx Hash".into(), h.to_string()]);
+            }
+            rows.push([
+                "Status".into(),
+                status.map_or_else(|| "Sent; on its way through the bridge".into(), Into::into),
+            ]);
+            if let Some(url) = etherscan {
+                rows.push(["Etherscan".into(), url]);
+            }
+            print_detail_table(rows);
+        }
+    }
+    Ok(())
+}
+
 fn format_status(s: &DepositTransactionStatus) -> &'static str {
     match s {
         DepositTransactionStatus::DepositDetected => "Detected",
@@ -110,7 +203,7 @@ fn format_status(s: &DepositTransactionStatus) -> &'static str {
 
 pub fn print_status(response: &StatusResponse, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if response.transactions.is_empty() {
                 println!("No transactions found.");
                 return Ok(());
@@ -1,29 +1,39 @@
 #![allow(clippy::items_after_statements)]
 
-use polymarket_client_sdk::auth::Credentials;
+use chrono::{DateTime, Utc};
+use polymarket_client_sdk::auth::{Credentials, ExposeSecret as _};
 use polymarket_client_sdk::clob::types::response::{
     ApiKeysResponse, BalanceAllowanceResponse, BanStatusResponse, CancelOrdersResponse,
     CurrentRewardResponse, FeeRateResponse, GeoblockResponse, LastTradePriceResponse,
     LastTradesPricesResponse, MarketResponse, MarketRewardResponse, MidpointResponse,
     MidpointsResponse, NegRiskResponse, NotificationResponse, OpenOrderResponse,
-    OrderBookSummaryResponse, OrderScoringResponse, OrdersScoringResponse, Page, PostOrderResponse,
-    PriceHistoryResponse, PriceResponse, PricesResponse, RewardsPercentagesResponse,
-    SimplifiedMarketResponse, SpreadResponse, SpreadsResponse, TickSizeResponse,
-    TotalUserEarningResponse, TradeResponse, UserEarningResponse, UserRewardsEarningResponse,
+    OrderBookSummaryResponse, OrderScoringResponse, OrderSummary, OrdersScoringResponse, Page,
+    PostOrderResponse, PriceHistoryResponse, PriceResponse, PricesResponse,
+    RewardsPercentagesResponse, SimplifiedMarketResponse, SpreadResponse, SpreadsResponse,
+    TickSizeResponse, TotalUserEarningResponse, TradeResponse, UserEarningResponse,
+    UserRewardsEarningResponse,
 };
-use polymarket_client_sdk::types::Decimal;
+use polymarket_client_sdk::clob::types::{OrderType, Side, SignedOrder, TraderSide};
+use polymarket_client_sdk::types::{B256, Decimal, U256};
 use serde_json::json;
 use tabled::settings::Style;
 use tabled::{Table, Tabled};
 
-use super::{OutputFormat, format_decimal, truncate};
+use super::style::{self, Tone};
+use super::time::{format_datetime, format_datetime_zoned, format_timestamp, iso_from_unix};
+use super::{OutputFormat, format_decimal, format_number, format_price, truncate};
+use crate::commands::fees::taker_fee;
+use crate::odds::OddsFormat;
+use crate::procs::Process;
 
 /// Base64-encoded empty cursor returned by the CLOB API when there are no more pages.
-const END_CURSOR: &str = "LTE=";
+pub(crate) const END_CURSOR: &str = "LTE=";
 
 pub fn print_ok(result: &str, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => println!("CLOB API: {result}"),
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+            println!("CLOB API: {result}")
+        }
         OutputFormat::Json => {
             super::print_json(&json!({"status": result}))?;
         }
@@ -33,7 +43,10 @@ pub fn print_ok(result: &str, output: &OutputFormat) -> anyhow::Result<()> {
 
 pub fn print_price(result: &PriceResponse, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => println!("Price: {}", result.price),
+        OutputFormat::Table | OutputFormat::Csv => {
+            println!("Price: {}", format_price(result.price))
+        }
+        OutputFormat::Plain => println!("{}", result.price),
         OutputFormat::Json => {
             super::print_json(&json!({"price": result.price.to_string()}))?;
         }
@@ -43,7 +56,7 @@ pub fn print_price(result: &PriceResponse, output: &OutputFormat) -> anyhow::Res
 
 pub fn print_batch_prices(result: &PricesResponse, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             let Some(prices) = &result.prices else {
                 println!("No prices available.");
                 return Ok(());
@@ -67,7 +80,7 @@ pub fn print_batch_prices(result: &PricesResponse, output: &OutputFormat) -> any
                     rows.push(Row {
                         token_id: truncate(&token_id.to_string(), 20),
                         side: side.to_string(),
-                        price: price.to_string(),
+                        price: format_price(*price),
                     });
                 }
             }
@@ -95,7 +108,10 @@ pub fn print_batch_prices(result: &PricesResponse, output: &OutputFormat) -> any
 
 pub fn print_midpoint(result: &MidpointResponse, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => println!("Midpoint: {}", result.mid),
+        OutputFormat::Table | OutputFormat::Csv => {
+            println!("Midpoint: {}", format_price(result.mid))
+        }
+        OutputFormat::Plain => println!("{}", result.mid),
         OutputFormat::Json => {
             super::print_json(&json!({"midpoint": result.mid.to_string()}))?;
         }
@@ -105,7 +121,7 @@ pub fn print_midpoint(result: &MidpointResponse, output: &OutputFormat) -> anyho
 
 pub fn print_midpoints(result: &MidpointsResponse, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if result.midpoints.is_empty() {
                 println!("No midpoints available.");
                 return Ok(());
@@ -122,7 +138,7 @@ pub fn print_midpoints(result: &MidpointsResponse, output: &OutputFormat) -> any
                 .iter()
                 .map(|(id, mid)| Row {
                     token_id: truncate(&id.to_string(), 20),
-                    midpoint: mid.to_string(),
+                    midpoint: format_price(*mid),
                 })
                 .collect();
             let table = Table::new(rows).with(Style::rounded()).to_string();
@@ -142,7 +158,8 @@ pub fn print_midpoints(result: &MidpointsResponse, output: &OutputFormat) -> any
 
 pub fn print_spread(result: &SpreadResponse, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => println!("Spread: {}", result.spread),
+        OutputFormat::Table | OutputFormat::Csv => println!("Spread: {}", result.spread),
+        OutputFormat::Plain => println!("{}", result.spread),
         OutputFormat::Json => {
             super::print_json(&json!({"spread": result.spread.to_string()}))?;
         }
@@ -152,7 +169,7 @@ pub fn print_spread(result: &SpreadResponse, output: &OutputFormat) -> anyhow::R
 
 pub fn print_spreads(result: &SpreadsResponse, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             let Some(spreads) = &result.spreads else {
                 println!("No spreads available.");
                 return Ok(());
@@ -191,23 +208,76 @@ pub fn print_spreads(result: &SpreadsResponse, output: &OutputFormat) -> anyhow:
     Ok(())
 }
 
-fn order_book_to_json(book: &OrderBookSummaryResponse) -> serde_json::Value {
-    let bids: Vec<_> = book
-        .bids
-        .iter()
-        .map(|o| json!({"price": o.price.to_string(), "size": o.size.to_string()}))
-        .collect();
-    let asks: Vec<_> = book
-        .asks
-        .iter()
-        .map(|o| json!({"price": o.price.to_string(), "size": o.size.to_string()}))
-        .collect();
+/// One side of the book, best price first, with a running size total.
+pub(crate) struct BookLevel {
+    pub(crate) price: Decimal,
+    pub(crate) size: Decimal,
+    pub(crate) cumulative: Decimal,
+}
+
+pub(crate) fn book_side(
+    levels: &[OrderSummary],
+    best_is_highest: bool,
+    depth: Option<usize>,
+) -> Vec<BookLevel> {
+    let mut sorted: Vec<&OrderSummary> = levels.iter().collect();
+    sorted.sort_by(|a, b| {
+        if best_is_highest {
+            b.price.cmp(&a.price)
+        } else {
+            a.price.cmp(&b.price)
+        }
+    });
+
+    let mut cumulative = Decimal::ZERO;
+    sorted
+        .into_iter()
+        .take(depth.unwrap_or(usize::MAX))
+        .map(|o| {
+            cumulative += o.size;
+            BookLevel {
+                price: o.price,
+                size: o.size,
+                cumulative,
+            }
+        })
+        .collect()
+}
+
+/// Midpoint and spread from the best bid and ask, if both sides are quoted.
+pub(crate) fn mid_and_spread(bids: &[BookLevel], asks: &[BookLevel]) -> Option<(Decimal, Decimal)> {
+    let bid = bids.first()?.price;
+    let ask = asks.first()?.price;
+    Some(((bid + ask) / Decimal::TWO, ask - bid))
+}
+
+pub(crate) fn order_book_to_json(
+    book: &OrderBookSummaryResponse,
+    depth: Option<usize>,
+) -> serde_json::Value {
+    let bids = book_side(&book.bids, true, depth);
+    let asks = book_side(&book.asks, false, depth);
+    let mid_spread = mid_and_spread(&bids, &asks);
+    let levels_json = |levels: &[BookLevel]| -> Vec<serde_json::Value> {
+        levels
+            .iter()
+            .map(|l| {
+                json!({
+                    "price": l.price.to_string(),
+                    "size": l.size.to_string(),
+                    "cumulative": l.cumulative.to_string(),
+                })
+            })
+            .collect()
+    };
     json!({
         "market": book.market.to_string(),
         "asset_id": book.asset_id.to_string(),
         "timestamp": book.timestamp.to_rfc3339(),
-        "bids": bids,
-        "asks": asks,
+        "bids": levels_json(&bids),
+        "asks": levels_json(&asks),
+        "midpoint": mid_spread.map(|(m, _)| m.to_string()),
+        "spread": mid_spread.map(|(_, s)| s.to_string()),
         "min_order_size": book.min_order_size.to_string(),
         "neg_risk": book.neg_risk,
         "tick_size": book.tick_size.as_decimal().to_string(),
@@ -215,20 +285,31 @@ fn order_book_to_json(book: &OrderBookSummaryResponse) -> serde_json::Value {
     })
 }
 
+/// Prints a book with bids and asks sorted best-first, limited to `depth`
+/// levels per side.
 pub fn print_order_book(
     result: &OrderBookSummaryResponse,
+    depth: Option<usize>,
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+            let bids = book_side(&result.bids, true, depth);
+            let asks = book_side(&result.asks, false, depth);
+
             println!("Market: {}", result.market);
             println!("Asset: {}", result.asset_id);
             println!(
                 "Last Trade: {}",
-                result
-                    .last_trade_price
-                    .map_or("—".into(), |p| p.to_string())
+                result.last_trade_price.map_or("—".into(), format_price)
             );
+            match mid_and_spread(&bids, &asks) {
+                Some((mid, spread)) => {
+                    println!("Midpoint: {}", format_price(mid));
+                    println!("Spread: {spread}");
+                }
+                None => println!("Midpoint: —"),
+            }
             println!();
 
             #[derive(Tabled)]
@@ -237,44 +318,44 @@ pub fn print_order_book(
                 price: String,
                 #[tabled(rename = "Size")]
                 size: String,
+                #[tabled(rename = "Cumulative")]
+                cumulative: String,
             }
+            let to_rows = |levels: &[BookLevel]| -> Vec<Row> {
+                levels
+                    .iter()
+                    .map(|l| Row {
+                        price: format_price(l.price),
+                        size: format_number(l.size),
+                        cumulative: l.cumulative.to_string(),
+                    })
+                    .collect()
+            };
 
-            if result.bids.is_empty() {
+            if bids.is_empty() {
                 println!("No bids.");
             } else {
                 println!("Bids:");
-                let rows: Vec<Row> = result
-                    .bids
-                    .iter()
-                    .map(|o| Row {
-                        price: o.price.to_string(),
-                        size: o.size.to_string(),
-                    })
-                    .collect();
-                let table = Table::new(rows).with(Style::rounded()).to_string();
+                let mut table = Table::new(to_rows(&bids));
+                table.with(Style::rounded());
+                style::color_column(&mut table, 0, 1, bids.iter().map(|_| Some(Tone::Up)));
                 println!("{table}");
             }
 
             println!();
 
-            if result.asks.is_empty() {
+            if asks.is_empty() {
                 println!("No asks.");
             } else {
                 println!("Asks:");
-                let rows: Vec<Row> = result
-                    .asks
-                    .iter()
-                    .map(|o| Row {
-                        price: o.price.to_string(),
-                        size: o.size.to_string(),
-                    })
-                    .collect();
-                let table = Table::new(rows).with(Style::rounded()).to_string();
+                let mut table = Table::new(to_rows(&asks));
+                table.with(Style::rounded());
+                style::color_column(&mut table, 0, 1, asks.iter().map(|_| Some(Tone::Down)));
                 println!("{table}");
             }
         }
         OutputFormat::Json => {
-            super::print_json(&order_book_to_json(result))?;
+            super::print_json(&order_book_to_json(result, depth))?;
         }
     }
     Ok(())
@@ -285,7 +366,7 @@ pub fn print_order_books(
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if result.is_empty() {
                 println!("No order books found.");
                 return Ok(());
@@ -294,11 +375,14 @@ pub fn print_order_books(
                 if i > 0 {
                     println!();
                 }
-                print_order_book(book, output)?;
+                print_order_book(book, None, output)?;
             }
         }
         OutputFormat::Json => {
-            let data: Vec<_> = result.iter().map(order_book_to_json).collect();
+            let data: Vec<_> = result
+                .iter()
+                .map(|book| order_book_to_json(book, None))
+                .collect();
             super::print_json(&data)?;
         }
     }
@@ -310,7 +394,14 @@ pub fn print_last_trade(
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => println!("Last Trade: {} ({})", result.price, result.side),
+        OutputFormat::Table | OutputFormat::Csv => {
+            println!(
+                "Last Trade: {} ({})",
+                format_price(result.price),
+                result.side
+            )
+        }
+        OutputFormat::Plain => println!("{}", result.price),
         OutputFormat::Json => {
             super::print_json(&json!({
                 "price": result.price.to_string(),
@@ -326,7 +417,7 @@ pub fn print_last_trades_prices(
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if result.is_empty() {
                 println!("No last trade prices found.");
                 return Ok(());
@@ -344,7 +435,7 @@ pub fn print_last_trades_prices(
                 .iter()
                 .map(|t| Row {
                     token_id: truncate(&t.token_id.to_string(), 20),
-                    price: t.price.to_string(),
+                    price: format_price(t.price),
                     side: t.side.to_string(),
                 })
                 .collect();
@@ -370,7 +461,7 @@ pub fn print_last_trades_prices(
 
 pub fn print_clob_market(result: &MarketResponse, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             let mut rows = vec![
                 ["Question".into(), result.question.clone()],
                 ["Description".into(), truncate(&result.description, 80)],
@@ -401,7 +492,9 @@ pub fn print_clob_market(result: &MarketResponse, output: &OutputFormat) -> anyh
                     format!("Token ({})", token.outcome),
                     format!(
                         "ID: {} | Price: {} | Winner: {}",
-                        token.token_id, token.price, token.winner
+                        token.token_id,
+                        format_price(token.price),
+                        token.winner
                     ),
                 ]);
             }
@@ -419,7 +512,7 @@ pub fn print_clob_markets(
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if result.data.is_empty() {
                 println!("No markets found.");
                 return Ok(());
@@ -463,7 +556,7 @@ pub fn print_simplified_markets(
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if result.data.is_empty() {
                 println!("No markets found.");
                 return Ok(());
@@ -509,9 +602,10 @@ pub fn print_simplified_markets(
 
 pub fn print_tick_size(result: &TickSizeResponse, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv => {
             println!("Tick size: {}", result.minimum_tick_size.as_decimal());
         }
+        OutputFormat::Plain => println!("{}", result.minimum_tick_size.as_decimal()),
         OutputFormat::Json => {
             super::print_json(&json!({
                 "minimum_tick_size": result.minimum_tick_size.as_decimal().to_string(),
@@ -523,7 +617,7 @@ pub fn print_tick_size(result: &TickSizeResponse, output: &OutputFormat) -> anyh
 
 pub fn print_fee_rate(result: &FeeRateResponse, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             println!("Fee rate: {} bps", result.base_fee);
         }
         OutputFormat::Json => {
@@ -537,7 +631,9 @@ pub fn print_fee_rate(result: &FeeRateResponse, output: &OutputFormat) -> anyhow
 
 pub fn print_neg_risk(result: &NegRiskResponse, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => println!("Neg risk: {}", result.neg_risk),
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+            println!("Neg risk: {}", result.neg_risk)
+        }
         OutputFormat::Json => {
             super::print_json(&json!({"neg_risk": result.neg_risk}))?;
         }
@@ -550,7 +646,7 @@ pub fn print_price_history(
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if result.history.is_empty() {
                 println!("No price history found.");
                 return Ok(());
@@ -567,10 +663,8 @@ pub fn print_price_history(
                 .iter()
                 .map(|p| Row {
                     timestamp: chrono::DateTime::from_timestamp(p.t, 0)
-                        .map_or(p.t.to_string(), |dt| {
-                            dt.format("%Y-%m-%d %H:%M").to_string()
-                        }),
-                    price: p.p.to_string(),
+                        .map_or(p.t.to_string(), format_datetime),
+                    price: format_price(p.p),
                 })
                 .collect();
             let table = Table::new(rows).with(Style::rounded()).to_string();
@@ -580,7 +674,13 @@ pub fn print_price_history(
             let data: Vec<_> = result
                 .history
                 .iter()
-                .map(|p| json!({"timestamp": p.t, "price": p.p.to_string()}))
+                .map(|p| {
+                    json!({
+                        "timestamp": p.t,
+                        "time": iso_from_unix(p.t),
+                        "price": p.p.to_string(),
+                    })
+                })
                 .collect();
             super::print_json(&data)?;
         }
@@ -590,20 +690,20 @@ pub fn print_price_history(
 
 pub fn print_server_time(timestamp: i64, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             let dt = chrono::DateTime::from_timestamp(timestamp, 0);
             match dt {
                 Some(dt) => {
-                    println!(
-                        "Server time: {} ({timestamp})",
-                        dt.format("%Y-%m-%d %H:%M:%S UTC")
-                    );
+                    println!("Server time: {} ({timestamp})", format_timestamp(dt));
                 }
                 None => println!("Server time: {timestamp}"),
             }
         }
         OutputFormat::Json => {
-            super::print_json(&json!({"timestamp": timestamp}))?;
+            super::print_json(&json!({
+                "timestamp": timestamp,
+                "time": iso_from_unix(timestamp),
+            }))?;
         }
     }
     Ok(())
@@ -611,7 +711,7 @@ pub fn print_server_time(timestamp: i64, output: &OutputFormat) -> anyhow::Resul
 
 pub fn print_geoblock(result: &GeoblockResponse, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             println!("Blocked: {}", result.blocked);
             println!("IP: {}", result.ip);
             println!("Country: {}", result.country);
@@ -629,8 +729,22 @@ pub fn print_geoblock(result: &GeoblockResponse, output: &OutputFormat) -> anyho
     Ok(())
 }
 
+/// Orders without an expiry come back with a zero timestamp.
+fn format_expiration(expiration: DateTime<Utc>) -> String {
+    if expiration.timestamp() == 0 {
+        "—".into()
+    } else {
+        format_datetime(expiration)
+    }
+}
+
 pub fn print_orders(result: &Page<OpenOrderResponse>, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
+        OutputFormat::Plain => {
+            for o in &result.data {
+                println!("{}", o.id);
+            }
+        }
         OutputFormat::Table => {
             if result.data.is_empty() {
                 println!("No open orders.");
@@ -648,10 +762,14 @@ pub fn print_orders(result: &Page<OpenOrderResponse>, output: &OutputFormat) ->
                 original_size: String,
                 #[tabled(rename = "Matched")]
                 size_matched: String,
+                #[tabled(rename = "Remaining")]
+                remaining: String,
                 #[tabled(rename = "Status")]
                 status: String,
                 #[tabled(rename = "Type")]
                 order_type: String,
+                #[tabled(rename = "Expires")]
+                expiration: String,
             }
             let rows: Vec<Row> = result
                 .data
@@ -659,11 +777,13 @@ pub fn print_orders(result: &Page<OpenOrderResponse>, output: &OutputFormat) ->
                 .map(|o| Row {
                     id: truncate(&o.id, 12),
                     side: o.side.to_string(),
-                    price: o.price.to_string(),
-                    original_size: o.original_size.to_string(),
-                    size_matched: o.size_matched.to_string(),
+                    price: format_price(o.price),
+                    original_size: format_number(o.original_size),
+                    size_matched: format_number(o.size_matched),
+                    remaining: format_number(o.original_size - o.size_matched),
                     status: o.status.to_string(),
                     order_type: o.order_type.to_string(),
+                    expiration: format_expiration(o.expiration),
                 })
                 .collect();
             let table = Table::new(rows).with(Style::rounded()).to_string();
@@ -686,6 +806,7 @@ pub fn print_orders(result: &Page<OpenOrderResponse>, output: &OutputFormat) ->
                         "price": o.price.to_string(),
                         "original_size": o.original_size.to_string(),
                         "size_matched": o.size_matched.to_string(),
+                        "remaining_size": (o.original_size - o.size_matched).to_string(),
                         "outcome": o.outcome,
                         "order_type": o.order_type.to_string(),
                         "created_at": o.created_at.to_rfc3339(),
@@ -696,26 +817,75 @@ pub fn print_orders(result: &Page<OpenOrderResponse>, output: &OutputFormat) ->
             let wrapper = json!({"data": data, "next_cursor": result.next_cursor});
             super::print_json(&wrapper)?;
         }
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = result
+                .data
+                .iter()
+                .map(|o| {
+                    vec![
+                        o.id.clone(),
+                        o.status.to_string(),
+                        o.market.to_string(),
+                        o.asset_id.to_string(),
+                        o.outcome.clone(),
+                        o.side.to_string(),
+                        o.price.to_string(),
+                        o.original_size.to_string(),
+                        o.size_matched.to_string(),
+                        (o.original_size - o.size_matched).to_string(),
+                        o.order_type.to_string(),
+                        o.created_at.to_rfc3339(),
+                        o.expiration.to_rfc3339(),
+                    ]
+                })
+                .collect();
+            super::print_csv(
+                &[
+                    "id",
+                    "status",
+                    "market",
+                    "asset_id",
+                    "outcome",
+                    "side",
+                    "price",
+                    "original_size",
+                    "size_matched",
+                    "remaining_size",
+                    "order_type",
+                    "created_at",
+                    "expiration",
+                ],
+                &rows,
+            );
+            print_csv_cursor(&result.next_cursor);
+        }
     }
     Ok(())
 }
 
+/// The next page's cursor goes to stderr so it doesn't break the CSV.
+fn print_csv_cursor(next_cursor: &str) {
+    if next_cursor != END_CURSOR {
+        eprintln!("Next cursor: {next_cursor}");
+    }
+}
+
 pub fn print_order_detail(result: &OpenOrderResponse, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             let rows = vec![
                 ["ID".into(), result.id.clone()],
                 ["Status".into(), result.status.to_string()],
                 ["Market".into(), result.market.to_string()],
                 ["Asset ID".into(), result.asset_id.to_string()],
                 ["Side".into(), result.side.to_string()],
-                ["Price".into(), result.price.to_string()],
-                ["Original Size".into(), result.original_size.to_string()],
-                ["Size Matched".into(), result.size_matched.to_string()],
+                ["Price".into(), format_price(result.price)],
+                ["Original Size".into(), format_number(result.original_size)],
+                ["Size Matched".into(), format_number(result.size_matched)],
                 ["Outcome".into(), result.outcome.clone()],
                 ["Order Type".into(), result.order_type.to_string()],
                 ["Created".into(), result.created_at.to_rfc3339()],
-                ["Expiration".into(), result.expiration.to_rfc3339()],
+                ["Expiration".into(), format_expiration(result.expiration)],
                 ["Trades".into(), result.associate_trades.join(", ")],
             ];
             super::print_detail_table(rows);
@@ -767,7 +937,8 @@ pub fn print_post_order_result(
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Plain => println!("{}", result.order_id),
+        OutputFormat::Table | OutputFormat::Csv => {
             println!("Order ID: {}", result.order_id);
             println!("Status: {}", result.status);
             println!("Success: {}", result.success);
@@ -786,12 +957,151 @@ pub fn print_post_order_result(
     Ok(())
 }
 
+/// Result of `create-order` / `market-order`, with the locally computed hash.
+pub fn print_placed_order(
+    hash: B256,
+    result: &PostOrderResponse,
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Plain => println!("{}", result.order_id),
+        OutputFormat::Table | OutputFormat::Csv => {
+            println!("Order hash: {hash}");
+            print_post_order_result(result, output)?;
+        }
+        OutputFormat::Json => {
+            let mut data = post_order_to_json(result);
+            data["order_hash"] = json!(hash.to_string());
+            super::print_json(&data)?;
+        }
+    }
+    Ok(())
+}
+
+fn preview_lines(lines: &[(&str, String)]) -> String {
+    let mut text = String::from("Order preview");
+    for (label, value) in lines {
+        text.push_str(&format!("\n  {:<10}{value}", format!("{label}:")));
+    }
+    text
+}
+
+/// Summary of a limit order, shown before asking to submit it, or before a
+/// paper or dry-run order built from `--odds`/`--notional`. The fee is what the order pays
+/// if it fills as a taker, at the token's `fee_rate_bps`.
+pub fn format_limit_order_preview(
+    side: Side,
+    price: Decimal,
+    size: Decimal,
+    fee_rate_bps: u32,
+    order_type: &OrderType,
+    expiration: Option<DateTime<Utc>>,
+) -> String {
+    let odds = match super::odds_format() {
+        OddsFormat::Price => format!("decimal odds {}", (Decimal::ONE / price).round_dp(2)),
+        format => format!("{format} {}", format_price(price)),
+    };
+    let total = (price * size).round_dp(2);
+    let total_label = if side == Side::Sell {
+        "Proceeds"
+    } else {
+        "Cost"
+    };
+    let mut lines = vec![
+        ("Side", side.to_string()),
+        ("Price", format!("{price} ({odds})")),
+        ("Size", format!("{size} shares")),
+        (total_label, format!("${total} USDC")),
+    ];
+    if fee_rate_bps == 0 {
+        lines.push(("Fee", "none (0 bps)".to_string()));
+    } else {
+        let fee = taker_fee(price, size, fee_rate_bps);
+        lines.push((
+            "Fee",
+            format!(
+                "~${} USDC if filled as taker ({})",
+                fee.round_dp(2),
+                super::fees::format_fee_rate(fee_rate_bps)
+            ),
+        ));
+        // Buyers pay the fee in shares, sellers out of the proceeds.
+        let net = if side == Side::Sell {
+            format!("~${} USDC", (price * size - fee).round_dp(2))
+        } else {
+            format!("~{} shares", (size - fee / price).round_dp(2))
+        };
+        lines.push(("Net", net));
+    }
+    lines.push(("Type", order_type.to_string()));
+    if let Some(expiration) = expiration {
+        lines.push(("Expires", format_datetime_zoned(expiration)));
+    }
+    preview_lines(&lines)
+}
+
+/// Summary of a market order, shown before asking to submit it.
+pub fn format_market_order_preview(
+    side: Side,
+    amount: Decimal,
+    fee_rate_bps: u32,
+    order_type: &OrderType,
+    expected_price: Option<Decimal>,
+) -> String {
+    let unit = if side == Side::Sell { "shares" } else { "USDC" };
+    let price = expected_price.map_or_else(
+        || "market".to_string(),
+        |p| format!("market (about {} on average)", p.round_dp(4).normalize()),
+    );
+    preview_lines(&[
+        ("Side", side.to_string()),
+        ("Amount", format!("{amount} {unit}")),
+        ("Price", price),
+        ("Fee rate", super::fees::format_fee_rate(fee_rate_bps)),
+        ("Type", order_type.to_string()),
+    ])
+}
+
+/// A signed order that was not submitted (`--dry-run`).
+pub fn print_signed_order(
+    signed: &SignedOrder,
+    hash: B256,
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Plain => println!("{hash}"),
+        OutputFormat::Table | OutputFormat::Csv => {
+            let o = &signed.order;
+            let side = if o.side == 0 { "BUY" } else { "SELL" };
+            println!("Dry run — order signed but not submitted.");
+            println!("Order hash: {hash}");
+            println!("Token: {}", o.tokenId);
+            println!("Side: {side}");
+            println!("Type: {}", signed.order_type);
+            println!("Maker amount: {}", o.makerAmount);
+            println!("Taker amount: {}", o.takerAmount);
+            println!("Maker: {}", o.maker);
+            println!("Signer: {}", o.signer);
+            println!("Expiration: {}", o.expiration);
+            println!("Signature: {}", signed.signature);
+        }
+        OutputFormat::Json => {
+            super::print_json(&json!({
+                "dry_run": true,
+                "order_hash": hash.to_string(),
+                "order": signed,
+            }))?;
+        }
+    }
+    Ok(())
+}
+
 pub fn print_post_orders_result(
     results: &[PostOrderResponse],
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             for (i, r) in results.iter().enumerate() {
                 if i > 0 {
                     println!("---");
@@ -807,29 +1117,196 @@ pub fn print_post_orders_result(
     Ok(())
 }
 
+/// Outcome of one order in `clob batch`: the order as read from the file,
+/// then its hash once signed and the CLOB's response once posted.
+pub struct BatchOrderResult {
+    pub index: usize,
+    pub token: U256,
+    pub side: Side,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub order_hash: Option<B256>,
+    pub order_id: Option<String>,
+    pub status: Option<String>,
+    pub error: Option<String>,
+}
+
+impl BatchOrderResult {
+    pub(crate) fn new(index: usize, order: &crate::commands::batch::BatchOrder) -> Self {
+        Self {
+            index,
+            token: order.token,
+            side: order.side,
+            price: order.price,
+            size: order.size,
+            order_hash: None,
+            order_id: None,
+            status: None,
+            error: None,
+        }
+    }
+
+    pub fn set_response(&mut self, r: &PostOrderResponse) {
+        self.order_id = Some(r.order_id.clone()).filter(|id| !id.is_empty());
+        self.status = Some(r.status.to_string());
+        if !r.success {
+            self.error = Some(
+                r.error_msg
+                    .clone()
+                    .filter(|e| !e.is_empty())
+                    .unwrap_or_else(|| "rejected".into()),
+            );
+        }
+    }
+
+    pub fn success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+fn batch_result_to_json(r: &BatchOrderResult) -> serde_json::Value {
+    json!({
+        "index": r.index,
+        "token": r.token.to_string(),
+        "side": r.side.to_string(),
+        "price": r.price.to_string(),
+        "size": r.size.to_string(),
+        "success": r.success(),
+        "order_hash": r.order_hash.map(|h| h.to_string()),
+        "order_id": r.order_id,
+        "status": r.status,
+        "error": r.error,
+    })
+}
+
+pub fn print_batch_results(
+    results: &[BatchOrderResult],
+    dry_run: bool,
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    let ok = results.iter().filter(|r| r.success()).count();
+    match output {
+        OutputFormat::Plain => {
+            for r in results.iter().filter(|r| r.success()) {
+                match (&r.order_id, &r.order_hash) {
+                    (Some(id), _) => println!("{id}"),
+                    (None, Some(hash)) => println!("{hash}"),
+                    (None, None) => {}
+                }
+            }
+        }
+        OutputFormat::Table | OutputFormat::Csv => {
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "#")]
+                index: usize,
+                #[tabled(rename = "Token")]
+                token: String,
+                #[tabled(rename = "Side")]
+                side: String,
+                #[tabled(rename = "Price")]
+                price: String,
+                #[tabled(rename = "Size")]
+                size: String,
+                #[tabled(rename = "Status")]
+                status: String,
+                #[tabled(rename = "Order ID / Error")]
+                detail: String,
+            }
+            let rows: Vec<Row> = results
+                .iter()
+                .map(|r| Row {
+                    index: r.index,
+                    token: truncate(&r.token.to_string(), 14),
+                    side: r.side.to_string(),
+                    price: format_price(r.price),
+                    size: format_number(r.size),
+                    status: match (&r.error, &r.status) {
+                        (Some(_), _) => "FAILED".into(),
+                        (None, Some(status)) => status.clone(),
+                        (None, None) => "SIGNED".into(),
+                    },
+                    detail: match (&r.error, &r.order_id, &r.order_hash) {
+                        (Some(e), _, _) => truncate(e, 60),
+                        (None, Some(id), _) => id.clone(),
+                        (None, None, Some(hash)) => hash.to_string(),
+                        (None, None, None) => "—".into(),
+                    },
+                })
+                .collect();
+            let table = Table::new(rows).with(Style::rounded()).to_string();
+            println!("{table}");
+            let verb = if dry_run { "signed" } else { "placed" };
+            println!("{ok} of {} orders {verb}", results.len());
+        }
+        OutputFormat::Json => {
+            let data: Vec<_> = results.iter().map(batch_result_to_json).collect();
+            super::print_json(&json!({
+                "dry_run": dry_run,
+                "succeeded": ok,
+                "failed": results.len() - ok,
+                "results": data,
+            }))?;
+        }
+    }
+    Ok(())
+}
+
+/// Per-order outcome of a cancel request. IDs that were asked for but appear
+/// in neither list of the response are reported as failures.
+fn cancel_results(result: &CancelOrdersResponse, requested: &[&str]) -> Vec<serde_json::Value> {
+    let mut failed: Vec<_> = result.not_canceled.iter().collect();
+    failed.sort();
+
+    let missing = requested.iter().filter(|id| {
+        !result.canceled.iter().any(|c| c == *id) && !result.not_canceled.contains_key(**id)
+    });
+
+    result
+        .canceled
+        .iter()
+        .map(|id| json!({"order_id": id, "success": true}))
+        .chain(
+            failed
+                .into_iter()
+                .map(|(id, reason)| json!({"order_id": id, "success": false, "reason": reason})),
+        )
+        .chain(missing.map(
+            |id| json!({"order_id": id, "success": false, "reason": "not reported by the CLOB"}),
+        ))
+        .collect()
+}
+
 pub fn print_cancel_result(
     result: &CancelOrdersResponse,
+    requested: &[&str],
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
+    let results = cancel_results(result, requested);
     match output {
-        OutputFormat::Table => {
-            if !result.canceled.is_empty() {
-                println!("Canceled: {}", result.canceled.join(", "));
-            }
-            if !result.not_canceled.is_empty() {
-                println!("Not canceled:");
-                for (id, reason) in &result.not_canceled {
-                    println!("  {id}: {reason}");
-                }
+        OutputFormat::Plain => {
+            for id in &result.canceled {
+                println!("{id}");
             }
-            if result.canceled.is_empty() && result.not_canceled.is_empty() {
+        }
+        OutputFormat::Table | OutputFormat::Csv => {
+            if results.is_empty() {
                 println!("No orders to cancel.");
+                return Ok(());
+            }
+            for r in &results {
+                let id = r["order_id"].as_str().unwrap_or_default();
+                match r["reason"].as_str() {
+                    None => println!("Canceled:     {id}"),
+                    Some(reason) => println!("Not canceled: {id} ({reason})"),
+                }
             }
         }
         OutputFormat::Json => {
             let data = json!({
                 "canceled": result.canceled,
                 "not_canceled": result.not_canceled,
+                "results": results,
             });
             super::print_json(&data)?;
         }
@@ -837,75 +1314,284 @@ pub fn print_cancel_result(
     Ok(())
 }
 
+/// What `panic` did: `daemon` says how stopping the daemon went, then the
+/// running commands signalled (with why one couldn't be stopped), the
+/// orders canceled and, with `--close-positions`, the sell orders sent.
+pub fn print_panic(
+    daemon: &str,
+    processes: &[(Process, Option<String>)],
+    canceled: &CancelOrdersResponse,
+    closed: Option<&[BatchOrderResult]>,
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    if let OutputFormat::Json = output {
+        let processes: Vec<_> = processes
+            .iter()
+            .map(|(p, error)| {
+                json!({
+                    "pid": p.pid,
+                    "command": p.command,
+                    "stopped": error.is_none(),
+                    "error": error,
+                })
+            })
+            .collect();
+        let data = json!({
+            "daemon": daemon,
+            "processes": processes,
+            "canceled": canceled.canceled,
+            "not_canceled": canceled.not_canceled,
+            "closed": closed.map(|r| r.iter().map(batch_result_to_json).collect::<Vec<_>>()),
+        });
+        return super::print_json(&data);
+    }
+    if !matches!(output, OutputFormat::Plain) {
+        println!("Daemon:       {daemon}");
+    }
+    for (p, error) in processes {
+        match error {
+            None if !matches!(output, OutputFormat::Plain) => {
+                println!("Stopped:      {} (pid {})", p.command, p.pid);
+            }
+            None => {}
+            // Left running, so it goes to stderr even with plain output.
+            Some(e) => eprintln!("Not stopped:  {} (pid {}): {e}", p.command, p.pid),
+        }
+    }
+    print_cancel_result(canceled, &[], output)?;
+    match closed {
+        Some(results) if !results.is_empty() => print_batch_results(results, false, output),
+        Some(_) if !matches!(output, OutputFormat::Plain) => {
+            println!("No open positions to close.");
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Your part in a trade. A trade's own side, price and size are the taker
+/// order's; when you were a maker, yours are in the `maker_orders` that are
+/// yours, and makers pay no fee.
+#[derive(Debug, PartialEq)]
+struct OwnFill {
+    trader_side: &'static str,
+    asset_id: U256,
+    outcome: String,
+    side: Side,
+    price: Decimal,
+    size: Decimal,
+    fee_rate_bps: Decimal,
+    fee: Decimal,
+}
+
+impl OwnFill {
+    fn of(t: &TradeResponse) -> Self {
+        let mine: Vec<_> = if t.trader_side == TraderSide::Maker {
+            let by_key: Vec<_> = t
+                .maker_orders
+                .iter()
+                .filter(|m| m.owner == t.owner)
+                .collect();
+            if by_key.is_empty() {
+                t.maker_orders
+                    .iter()
+                    .filter(|m| m.maker_address == t.maker_address)
+                    .collect()
+            } else {
+                by_key
+            }
+        } else {
+            Vec::new()
+        };
+        let Some(first) = mine.first() else {
+            return Self {
+                trader_side: "TAKER",
+                asset_id: t.asset_id,
+                outcome: t.outcome.clone(),
+                side: t.side,
+                price: t.price,
+                size: t.size,
+                fee_rate_bps: t.fee_rate_bps,
+                fee: taker_fee(t.price, t.size, t.fee_rate_bps),
+            };
+        };
+        // Several of your orders can fill in one trade; their prices are
+        // averaged by size.
+        let size: Decimal = mine.iter().map(|m| m.matched_amount).sum();
+        let price = if size.is_zero() {
+            first.price
+        } else {
+            mine.iter()
+                .map(|m| m.price * m.matched_amount)
+                .sum::<Decimal>()
+                / size
+        };
+        Self {
+            trader_side: "MAKER",
+            asset_id: first.asset_id,
+            outcome: first.outcome.clone(),
+            side: first.side,
+            price,
+            size,
+            fee_rate_bps: Decimal::ZERO,
+            fee: Decimal::ZERO,
+        }
+    }
+}
+
+fn trade_to_json(t: &TradeResponse) -> serde_json::Value {
+    let fill = OwnFill::of(t);
+    json!({
+        "id": t.id,
+        "taker_order_id": t.taker_order_id,
+        "market": t.market.to_string(),
+        "asset_id": fill.asset_id.to_string(),
+        "side": fill.side.to_string(),
+        "size": fill.size.to_string(),
+        "price": fill.price.to_string(),
+        "fee_rate_bps": fill.fee_rate_bps.to_string(),
+        "fee": fill.fee.to_string(),
+        "status": t.status.to_string(),
+        "match_time": t.match_time.to_rfc3339(),
+        "outcome": fill.outcome,
+        "trader_side": fill.trader_side,
+        "transaction_hash": t.transaction_hash.to_string(),
+    })
+}
+
+fn print_trades_table(trades: &[TradeResponse]) {
+    #[derive(Tabled)]
+    struct Row {
+        #[tabled(rename = "ID")]
+        id: String,
+        #[tabled(rename = "Market")]
+        market: String,
+        #[tabled(rename = "Role")]
+        role: String,
+        #[tabled(rename = "Side")]
+        side: String,
+        #[tabled(rename = "Price")]
+        price: String,
+        #[tabled(rename = "Size")]
+        size: String,
+        #[tabled(rename = "Fee")]
+        fee: String,
+        #[tabled(rename = "Status")]
+        status: String,
+        #[tabled(rename = "Time")]
+        match_time: String,
+        #[tabled(rename = "Tx")]
+        transaction_hash: String,
+    }
+    let rows: Vec<Row> = trades
+        .iter()
+        .map(|t| (t, OwnFill::of(t)))
+        .map(|(t, fill)| Row {
+            id: truncate(&t.id, 12),
+            market: truncate(&t.market.to_string(), 12),
+            role: fill.trader_side.to_lowercase(),
+            side: fill.side.to_string(),
+            price: format_price(fill.price),
+            size: format_number(fill.size),
+            fee: format!("{:.4}", fill.fee),
+            status: t.status.to_string(),
+            match_time: format_datetime(t.match_time),
+            transaction_hash: truncate(&t.transaction_hash.to_string(), 12),
+        })
+        .collect();
+    let table = Table::new(rows).with(Style::rounded()).to_string();
+    println!("{table}");
+}
+
 pub fn print_trades(result: &Page<TradeResponse>, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Plain => {
             if result.data.is_empty() {
                 println!("No trades found.");
                 return Ok(());
             }
-            #[derive(Tabled)]
-            struct Row {
-                #[tabled(rename = "ID")]
-                id: String,
-                #[tabled(rename = "Side")]
-                side: String,
-                #[tabled(rename = "Price")]
-                price: String,
-                #[tabled(rename = "Size")]
-                size: String,
-                #[tabled(rename = "Status")]
-                status: String,
-                #[tabled(rename = "Time")]
-                match_time: String,
-            }
-            let rows: Vec<Row> = result
-                .data
-                .iter()
-                .map(|t| Row {
-                    id: truncate(&t.id, 12),
-                    side: t.side.to_string(),
-                    price: t.price.to_string(),
-                    size: t.size.to_string(),
-                    status: t.status.to_string(),
-                    match_time: t.match_time.format("%Y-%m-%d %H:%M").to_string(),
-                })
-                .collect();
-            let table = Table::new(rows).with(Style::rounded()).to_string();
-            println!("{table}");
+            print_trades_table(&result.data);
             if result.next_cursor != END_CURSOR {
                 println!("Next cursor: {}", result.next_cursor);
             }
         }
         OutputFormat::Json => {
-            let data: Vec<_> = result
-                .data
-                .iter()
-                .map(|t| {
-                    json!({
-                        "id": t.id,
-                        "taker_order_id": t.taker_order_id,
-                        "market": t.market.to_string(),
-                        "asset_id": t.asset_id.to_string(),
-                        "side": t.side.to_string(),
-                        "size": t.size.to_string(),
-                        "price": t.price.to_string(),
-                        "fee_rate_bps": t.fee_rate_bps.to_string(),
-                        "status": t.status.to_string(),
-                        "match_time": t.match_time.to_rfc3339(),
-                        "outcome": t.outcome,
-                        "trader_side": format!("{:?}", t.trader_side),
-                        "transaction_hash": t.transaction_hash.to_string(),
-                    })
-                })
-                .collect();
+            let data: Vec<_> = result.data.iter().map(trade_to_json).collect();
             let wrapper = json!({"data": data, "next_cursor": result.next_cursor});
             super::print_json(&wrapper)?;
         }
+        OutputFormat::Csv => {
+            print_trades_csv(&result.data);
+            print_csv_cursor(&result.next_cursor);
+        }
     }
     Ok(())
 }
 
+/// Full trade history, already paged through, so there is no cursor to show.
+pub fn print_trade_history(trades: &[TradeResponse], output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            if trades.is_empty() {
+                println!("No trades found.");
+                return Ok(());
+            }
+            print_trades_table(trades);
+            let fees = trades.iter().map(|t| OwnFill::of(t).fee).sum::<Decimal>();
+            println!("{} trades, {:.4} USDC in fees", trades.len(), fees);
+        }
+        OutputFormat::Json => {
+            let data: Vec<_> = trades.iter().map(trade_to_json).collect();
+            super::print_json(&data)?;
+        }
+        OutputFormat::Csv => print_trades_csv(trades),
+    }
+    Ok(())
+}
+
+/// Trade history as CSV, one fill per line, for spreadsheets and tax tools.
+fn print_trades_csv(trades: &[TradeResponse]) {
+    let rows: Vec<Vec<String>> = trades
+        .iter()
+        .map(|t| {
+            let fill = OwnFill::of(t);
+            vec![
+                t.id.clone(),
+                t.match_time.to_rfc3339(),
+                t.market.to_string(),
+                fill.asset_id.to_string(),
+                fill.outcome,
+                fill.trader_side.to_string(),
+                fill.side.to_string(),
+                fill.price.to_string(),
+                fill.size.to_string(),
+                fill.fee_rate_bps.to_string(),
+                fill.fee.to_string(),
+                t.status.to_string(),
+                t.transaction_hash.to_string(),
+            ]
+        })
+        .collect();
+    super::print_csv(
+        &[
+            "id",
+            "match_time",
+            "market",
+            "asset_id",
+            "outcome",
+            "trader_side",
+            "side",
+            "price",
+            "size",
+            "fee_rate_bps",
+            "fee",
+            "status",
+            "transaction_hash",
+        ],
+        &rows,
+    );
+}
+
 /// USDC uses 6 decimal places on-chain.
 const USDC_DECIMALS: u32 = 6;
 
@@ -917,7 +1603,8 @@ pub fn print_balance(
     let divisor = Decimal::from(10u64.pow(USDC_DECIMALS));
     let human_balance = result.balance / divisor;
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Plain => println!("{human_balance}"),
+        OutputFormat::Table | OutputFormat::Csv => {
             if is_collateral {
                 println!("Balance: {}", format_decimal(human_balance));
             } else {
@@ -951,7 +1638,7 @@ pub fn print_notifications(
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if result.is_empty() {
                 println!("No notifications.");
                 return Ok(());
@@ -975,8 +1662,8 @@ pub fn print_notifications(
                     notif_type: n.r#type.to_string(),
                     question: truncate(&n.payload.question, 40),
                     side: n.payload.side.to_string(),
-                    price: n.payload.price.to_string(),
-                    size: n.payload.matched_size.to_string(),
+                    price: format_price(n.payload.price),
+                    size: format_number(n.payload.matched_size),
                 })
                 .collect();
             let table = Table::new(rows).with(Style::rounded()).to_string();
@@ -1011,7 +1698,7 @@ pub fn print_rewards(
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if result.data.is_empty() {
                 println!("No reward earnings found.");
                 return Ok(());
@@ -1070,7 +1757,7 @@ pub fn print_earnings(
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if result.is_empty() {
                 println!("No earnings data found.");
                 return Ok(());
@@ -1109,7 +1796,7 @@ pub fn print_user_earnings_markets(
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if result.is_empty() {
                 println!("No earnings data found.");
                 return Ok(());
@@ -1186,7 +1873,7 @@ pub fn print_reward_percentages(
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if result.is_empty() {
                 println!("No reward percentages found.");
                 return Ok(());
@@ -1224,7 +1911,7 @@ pub fn print_current_rewards(
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if result.data.is_empty() {
                 println!("No current rewards found.");
                 return Ok(());
@@ -1287,7 +1974,7 @@ pub fn print_market_reward(
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if result.data.is_empty() {
                 println!("No market reward data found.");
                 return Ok(());
@@ -1305,7 +1992,9 @@ pub fn print_market_reward(
                 for token in &r.tokens {
                     println!(
                         "  Token ({}): {} | Price: {}",
-                        token.outcome, token.token_id, token.price
+                        token.outcome,
+                        token.token_id,
+                        format_price(token.price)
                     );
                 }
             }
@@ -1356,7 +2045,9 @@ pub fn print_order_scoring(
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => println!("Scoring: {}", result.scoring),
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+            println!("Scoring: {}", result.scoring)
+        }
         OutputFormat::Json => {
             super::print_json(&json!({"scoring": result.scoring}))?;
         }
@@ -1369,7 +2060,7 @@ pub fn print_orders_scoring(
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if result.is_empty() {
                 println!("No scoring data.");
                 return Ok(());
@@ -1403,7 +2094,7 @@ pub fn print_api_keys(result: &ApiKeysResponse, output: &OutputFormat) -> anyhow
     // We use Debug output as the only available representation.
     let debug = format!("{result:?}");
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             println!("API Keys: {debug}");
         }
         OutputFormat::Json => {
@@ -1418,7 +2109,9 @@ pub fn print_delete_api_key(
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => println!("API key deleted: {result}"),
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+            println!("API key deleted: {result}")
+        }
         OutputFormat::Json => {
             super::print_json(result)?;
         }
@@ -1428,7 +2121,7 @@ pub fn print_delete_api_key(
 
 pub fn print_create_api_key(result: &Credentials, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             println!("API Key: {}", result.key());
             println!("Secret: [redacted]");
             println!("Passphrase: [redacted]");
@@ -1444,12 +2137,41 @@ pub fn print_create_api_key(result: &Credentials, output: &OutputFormat) -> anyh
     Ok(())
 }
 
+/// Full credentials, secret included, for provisioning bots.
+pub fn print_api_credentials(
+    result: &Credentials,
+    saved: bool,
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    let secret = result.secret().expose_secret();
+    let passphrase = result.passphrase().expose_secret();
+    match output {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+            println!("API Key: {}", result.key());
+            println!("Secret: {secret}");
+            println!("Passphrase: {passphrase}");
+            if saved {
+                println!("Saved to profile '{}'.", crate::config::active_profile());
+            }
+        }
+        OutputFormat::Json => {
+            super::print_json(&json!({
+                "api_key": result.key().to_string(),
+                "secret": secret,
+                "passphrase": passphrase,
+                "saved": saved,
+            }))?;
+        }
+    }
+    Ok(())
+}
+
 pub fn print_account_status(
     result: &BanStatusResponse,
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             println!(
                 "Account status: {}",
                 if result.closed_only {
@@ -1465,3 +2187,216 @@ pub fn print_account_status(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(trader_side: &str) -> TradeResponse {
+        serde_json::from_value(json!({
+            "id": "1",
+            "taker_order_id": "0xtaker",
+            "market": "0x000000000000000000000000000000000000000000000000000000006d61726b",
+            "asset_id": "7",
+            "side": "BUY",
+            "size": "100",
+            "fee_rate_bps": "100",
+            "price": "0.4",
+            "status": "MATCHED",
+            "match_time": "1705322096",
+            "last_update": "1705322130",
+            "outcome": "Yes",
+            "bucket_index": 0,
+            "owner": "11111111-1111-1111-1111-111111111111",
+            "maker_address": "0x2222222222222222222222222222222222222222",
+            "maker_orders": [
+                {
+                    "order_id": "0xmine",
+                    "owner": "11111111-1111-1111-1111-111111111111",
+                    "maker_address": "0x2222222222222222222222222222222222222222",
+                    "matched_amount": "30",
+                    "price": "0.6",
+                    "fee_rate_bps": "0",
+                    "asset_id": "8",
+                    "outcome": "No",
+                    "side": "BUY"
+                },
+                {
+                    "order_id": "0xtheirs",
+                    "owner": "33333333-3333-3333-3333-333333333333",
+                    "maker_address": "0x4444444444444444444444444444444444444444",
+                    "matched_amount": "70",
+                    "price": "0.4",
+                    "fee_rate_bps": "0",
+                    "asset_id": "7",
+                    "outcome": "Yes",
+                    "side": "SELL"
+                }
+            ],
+            "transaction_hash": "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcd",
+            "trader_side": trader_side
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn taker_fills_use_the_trade_and_pay_the_fee() {
+        let fill = OwnFill::of(&trade("TAKER"));
+        assert_eq!(fill.trader_side, "TAKER");
+        assert_eq!((fill.side, fill.size), (Side::Buy, Decimal::from(100)));
+        assert_eq!(fill.fee, "0.4".parse::<Decimal>().unwrap());
+    }
+
+    #[test]
+    fn maker_fills_use_your_maker_orders_and_pay_no_fee() {
+        let fill = OwnFill::of(&trade("MAKER"));
+        assert_eq!(fill.trader_side, "MAKER");
+        assert_eq!(fill.side, Side::Buy);
+        assert_eq!(fill.asset_id, U256::from(8));
+        assert_eq!(fill.outcome, "No");
+        assert_eq!(fill.size, Decimal::from(30));
+        assert_eq!(fill.price, "0.6".parse::<Decimal>().unwrap());
+        assert_eq!(fill.fee, Decimal::ZERO);
+    }
+
+    #[test]
+    fn limit_order_preview_shows_odds_and_cost() {
+        let preview = format_limit_order_preview(
+            Side::Buy,
+            "0.42".parse().unwrap(),
+            "119.04".parse().unwrap(),
+            0,
+            &OrderType::GTC,
+            None,
+        );
+        assert!(preview.contains("0.42 (decimal odds 2.38)"), "{preview}");
+        assert!(preview.contains("Cost:     $50.00 USDC"), "{preview}");
+        assert!(preview.contains("119.04 shares"), "{preview}");
+        assert!(preview.contains("Fee:      none (0 bps)"), "{preview}");
+        assert!(!preview.contains("Net:"), "{preview}");
+    }
+
+    #[test]
+    fn limit_order_preview_shows_fee_and_net() {
+        let sell = format_limit_order_preview(
+            Side::Sell,
+            "0.40".parse().unwrap(),
+            "100".parse().unwrap(),
+            200,
+            &OrderType::GTC,
+            None,
+        );
+        assert!(sell.contains("Proceeds: $40.00 USDC"), "{sell}");
+        assert!(
+            sell.contains("Fee:      ~$0.80 USDC if filled as taker (200 bps (2%))"),
+            "{sell}"
+        );
+        assert!(sell.contains("Net:      ~$39.20 USDC"), "{sell}");
+
+        let buy = format_limit_order_preview(
+            Side::Buy,
+            "0.40".parse().unwrap(),
+            "100".parse().unwrap(),
+            200,
+            &OrderType::GTC,
+            None,
+        );
+        assert!(buy.contains("Net:      ~98.00 shares"), "{buy}");
+    }
+
+    #[test]
+    fn limit_order_preview_shows_expiry_of_gtd_orders() {
+        let expiration = DateTime::from_timestamp(1_700_000_000, 0);
+        let preview = format_limit_order_preview(
+            Side::Buy,
+            "0.40".parse().unwrap(),
+            "100".parse().unwrap(),
+            0,
+            &OrderType::GTD,
+            expiration,
+        );
+        assert!(preview.contains("Type:     GTD"), "{preview}");
+        assert!(preview.contains("Expires:  2023-11-"), "{preview}");
+    }
+
+    fn cancel_response(val: serde_json::Value) -> CancelOrdersResponse {
+        serde_json::from_value(val).unwrap()
+    }
+
+    #[test]
+    fn cancel_results_reports_each_order() {
+        let resp = cancel_response(json!({
+            "canceled": ["a"],
+            "not_canceled": {"c": "already filled", "b": "not found"}
+        }));
+        let results = cancel_results(&resp, &[]);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], json!({"order_id": "a", "success": true}));
+        assert_eq!(results[1]["order_id"], "b");
+        assert_eq!(results[1]["success"], false);
+        assert_eq!(results[2]["reason"], "already filled");
+    }
+
+    fn levels(pairs: &[(&str, &str)]) -> Vec<OrderSummary> {
+        serde_json::from_value(json!(
+            pairs
+                .iter()
+                .map(|(p, s)| json!({"price": p, "size": s}))
+                .collect::<Vec<_>>()
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn book_side_sorts_best_first_with_cumulative() {
+        let bids = book_side(
+            &levels(&[("0.40", "10"), ("0.45", "5"), ("0.42", "1")]),
+            true,
+            None,
+        );
+        let prices: Vec<_> = bids.iter().map(|l| l.price.to_string()).collect();
+        assert_eq!(prices, ["0.45", "0.42", "0.40"]);
+        assert_eq!(bids[2].cumulative, Decimal::from(16));
+
+        let asks = book_side(&levels(&[("0.60", "3"), ("0.55", "2")]), false, None);
+        assert_eq!(asks[0].price.to_string(), "0.55");
+        assert_eq!(asks[1].cumulative, Decimal::from(5));
+    }
+
+    #[test]
+    fn book_side_respects_depth() {
+        let bids = book_side(
+            &levels(&[("0.40", "10"), ("0.45", "5"), ("0.42", "1")]),
+            true,
+            Some(2),
+        );
+        assert_eq!(bids.len(), 2);
+        assert_eq!(bids[1].cumulative, Decimal::from(6));
+    }
+
+    #[test]
+    fn mid_and_spread_needs_both_sides() {
+        let bids = book_side(&levels(&[("0.40", "1")]), true, None);
+        let asks = book_side(&levels(&[("0.50", "1")]), false, None);
+        let (mid, spread) = mid_and_spread(&bids, &asks).unwrap();
+        assert_eq!(mid.to_string(), "0.45");
+        assert_eq!(spread.to_string(), "0.10");
+        assert!(mid_and_spread(&bids, &[]).is_none());
+    }
+
+    #[test]
+    fn format_expiration_zero_is_never() {
+        assert_eq!(format_expiration(DateTime::UNIX_EPOCH), "—");
+        let t = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        assert_eq!(format_expiration(t), "2023-11-14 22:13");
+    }
+
+    #[test]
+    fn cancel_results_flags_unreported_ids() {
+        let resp = cancel_response(json!({"canceled": ["a"]}));
+        let results = cancel_results(&resp, &["a", "z"]);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1]["order_id"], "z");
+        assert_eq!(results[1]["success"], false);
+    }
+}
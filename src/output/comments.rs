@@ -37,7 +37,7 @@ fn comment_to_row(c: &Comment) -> CommentRow {
             .map_or_else(|| "—".into(), |n| n.to_string()),
         created: c
             .created_at
-            .map_or_else(|| "—".into(), |d| d.format("%Y-%m-%d %H:%M").to_string()),
+            .map_or_else(|| "—".into(), super::time::format_datetime),
     }
 }
 
@@ -0,0 +1,94 @@
+use serde_json::{Value, json};
+
+use super::{OutputFormat, print_detail_table};
+
+/// `settings` flattened to dotted keys, with lists left as JSON.
+fn flatten(prefix: &str, settings: &Value, rows: &mut Vec<[String; 2]>) {
+    match settings {
+        Value::Object(object) => {
+            for (key, value) in object {
+                let key = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(&key, value, rows);
+            }
+        }
+        Value::String(s) => rows.push([prefix.to_string(), s.clone()]),
+        other => rows.push([prefix.to_string(), other.to_string()]),
+    }
+}
+
+pub fn print_config(
+    profile: &str,
+    default_profile: &str,
+    settings: &Value,
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            let mut rows = vec![
+                ["profile".to_string(), profile.to_string()],
+                ["default_profile".to_string(), default_profile.to_string()],
+            ];
+            flatten("", settings, &mut rows);
+            print_detail_table(rows);
+        }
+        OutputFormat::Csv => {
+            let mut rows = Vec::new();
+            flatten("", settings, &mut rows);
+            let rows: Vec<Vec<String>> = rows.into_iter().map(Vec::from).collect();
+            super::print_csv(&["key", "value"], &rows);
+        }
+        OutputFormat::Json => super::print_json(&json!({
+            "profile": profile,
+            "default_profile": default_profile,
+            "settings": settings,
+        }))?,
+    }
+    Ok(())
+}
+
+/// Confirms `config set` (with the new value) or `config unset`.
+pub fn print_setting_changed(
+    key: &str,
+    value: Option<&Value>,
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Json => super::print_json_line(json!({"key": key, "value": value})),
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => match value {
+            Some(Value::String(s)) => println!("Set {key} to {s}."),
+            Some(value) => println!("Set {key} to {value}."),
+            None => println!("Unset {key}."),
+        },
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flatten_uses_dotted_keys() {
+        let mut rows = Vec::new();
+        flatten(
+            "",
+            &json!({"chain_id": 137, "network": {"timeout": "10s"}, "notifications": [{"format": "desktop"}]}),
+            &mut rows,
+        );
+        assert_eq!(
+            rows,
+            vec![
+                ["chain_id".to_string(), "137".to_string()],
+                ["network.timeout".to_string(), "10s".to_string()],
+                [
+                    "notifications".to_string(),
+                    r#"[{"format":"desktop"}]"#.to_string()
+                ],
+            ]
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+use super::{OutputFormat, print_csv};
+use crate::config::Contact;
+
+pub fn print_contacts(contacts: &[Contact], output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            if contacts.is_empty() {
+                println!("No contacts. Add one with `polymarket contacts add <name> <address>`.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "Name")]
+                name: String,
+                #[tabled(rename = "Address")]
+                address: String,
+            }
+            let rows: Vec<Row> = contacts
+                .iter()
+                .map(|c| Row {
+                    name: c.name.clone(),
+                    address: c.address.clone(),
+                })
+                .collect();
+            println!("{}", Table::new(rows).with(Style::rounded()));
+        }
+        OutputFormat::Plain => {
+            for c in contacts {
+                println!("{}\t{}", c.name, c.address);
+            }
+        }
+        OutputFormat::Json => super::print_json(&contacts)?,
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = contacts
+                .iter()
+                .map(|c| vec![c.name.clone(), c.address.clone()])
+                .collect();
+            print_csv(&["name", "address"], &rows);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use polymarket_client_sdk::types::Decimal;
+use serde_json::json;
+
+use super::{OutputFormat, print_detail_table};
+use crate::odds::{self, OddsFormat};
+
+/// `price` in `format`, with the price itself to four places: one read
+/// from odds rarely has fewer.
+fn shown(price: Decimal, format: OddsFormat) -> String {
+    match format {
+        OddsFormat::Price => price.round_dp(4).normalize().to_string(),
+        _ => odds::format(price, format),
+    }
+}
+
+pub fn print_conversion(price: Decimal, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Plain => println!("{}", shown(price, super::odds_format())),
+        OutputFormat::Table | OutputFormat::Csv => print_detail_table(vec![
+            ["Price".into(), shown(price, OddsFormat::Price)],
+            ["Probability".into(), shown(price, OddsFormat::Probability)],
+            ["Decimal odds".into(), shown(price, OddsFormat::Decimal)],
+            ["American odds".into(), shown(price, OddsFormat::American)],
+        ]),
+        OutputFormat::Json => super::print_json(&json!({
+            "price": price.round_dp(6).normalize().to_string(),
+            "probability": (price * Decimal::ONE_HUNDRED).round_dp(4).normalize().to_string(),
+            "decimal_odds": odds::decimal_odds(price).map(|d| d.round_dp(4).normalize().to_string()),
+            "american_odds": odds::american_odds(price).map(|a| a.round_dp(2).normalize().to_string()),
+        }))?,
+    }
+    Ok(())
+}
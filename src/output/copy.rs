@@ -0,0 +1,211 @@
+use polymarket_client_sdk::clob::types::Side;
+use polymarket_client_sdk::data::types::response::Trade;
+use polymarket_client_sdk::types::Decimal;
+use serde_json::json;
+
+use super::{OutputFormat, truncate};
+
+/// What `copy watch` did about one of the followed wallet's trades.
+pub enum CopyAction {
+    Skipped(String),
+    /// Dry run: what would have been mirrored, filled at the wallet's price.
+    Simulated {
+        side: Side,
+        shares: Decimal,
+        usdc: Decimal,
+        exposure: Decimal,
+    },
+    Filled {
+        side: Side,
+        shares: Decimal,
+        usdc: Decimal,
+        exposure: Decimal,
+    },
+    Failed {
+        side: Side,
+        error: String,
+    },
+}
+
+pub struct CopyEvent {
+    pub trade: Trade,
+    pub action: CopyAction,
+}
+
+impl CopyEvent {
+    pub fn skipped(trade: &Trade, reason: String) -> Self {
+        Self {
+            trade: trade.clone(),
+            action: CopyAction::Skipped(reason),
+        }
+    }
+
+    pub fn simulated(
+        trade: &Trade,
+        side: Side,
+        shares: Decimal,
+        usdc: Decimal,
+        exposure: Decimal,
+    ) -> Self {
+        Self {
+            trade: trade.clone(),
+            action: CopyAction::Simulated {
+                side,
+                shares,
+                usdc,
+                exposure,
+            },
+        }
+    }
+
+    pub fn filled(
+        trade: &Trade,
+        side: Side,
+        shares: Decimal,
+        usdc: Decimal,
+        exposure: Decimal,
+    ) -> Self {
+        Self {
+            trade: trade.clone(),
+            action: CopyAction::Filled {
+                side,
+                shares,
+                usdc,
+                exposure,
+            },
+        }
+    }
+
+    pub fn failed(trade: &Trade, side: Side, error: String) -> Self {
+        Self {
+            trade: trade.clone(),
+            action: CopyAction::Failed { side, error },
+        }
+    }
+}
+
+fn verb(side: Side, dry_run: bool) -> &'static str {
+    match (side, dry_run) {
+        (Side::Sell, true) => "would sell",
+        (Side::Sell, false) => "sold",
+        (_, true) => "would buy",
+        (_, false) => "bought",
+    }
+}
+
+fn action_text(action: &CopyAction) -> String {
+    match action {
+        CopyAction::Skipped(reason) => format!("skipped: {reason}"),
+        CopyAction::Simulated {
+            side,
+            shares,
+            usdc,
+            exposure,
+        }
+        | CopyAction::Filled {
+            side,
+            shares,
+            usdc,
+            exposure,
+        } => format!(
+            "{} {} shares for ${usdc:.2}  ·  exposure ${exposure:.2}",
+            verb(*side, matches!(action, CopyAction::Simulated { .. })),
+            shares.round_dp(2).normalize()
+        ),
+        CopyAction::Failed { side, error } => {
+            format!("{} failed: {error}", side.to_string().to_lowercase())
+        }
+    }
+}
+
+fn event_json(e: &CopyEvent, now: chrono::DateTime<chrono::Local>) -> serde_json::Value {
+    let t = &e.trade;
+    let mut line = json!({
+        "time": now.to_rfc3339(),
+        "trade": {
+            "side": t.side.to_string(),
+            "title": t.title,
+            "outcome": t.outcome,
+            "asset": t.asset.to_string(),
+            "size": t.size.to_string(),
+            "price": t.price.to_string(),
+            "timestamp": t.timestamp,
+            "transaction_hash": t.transaction_hash.to_string(),
+        },
+    });
+    match &e.action {
+        CopyAction::Skipped(reason) => {
+            line["action"] = json!("skipped");
+            line["reason"] = json!(reason);
+        }
+        CopyAction::Simulated {
+            side,
+            shares,
+            usdc,
+            exposure,
+        }
+        | CopyAction::Filled {
+            side,
+            shares,
+            usdc,
+            exposure,
+        } => {
+            let simulated = matches!(e.action, CopyAction::Simulated { .. });
+            line["action"] = json!(if simulated { "simulated" } else { "filled" });
+            line["side"] = json!(side.to_string());
+            line["shares"] = json!(shares.to_string());
+            line["usdc"] = json!(usdc.to_string());
+            line["exposure"] = json!(exposure.to_string());
+        }
+        CopyAction::Failed { side, error } => {
+            line["action"] = json!("failed");
+            line["side"] = json!(side.to_string());
+            line["error"] = json!(error);
+        }
+    }
+    line
+}
+
+/// One line per trade the followed wallet makes, as it is seen.
+pub fn print_copy_event(e: &CopyEvent, output: &OutputFormat) {
+    let now = chrono::Local::now();
+    match output {
+        OutputFormat::Json => super::print_json_line(event_json(e, now)),
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+            let t = &e.trade;
+            println!(
+                "{}  {} {} {} @ {}  {}  →  {}",
+                super::time::format_clock(now),
+                t.side,
+                t.size.round_dp(2).normalize(),
+                t.outcome,
+                t.price.normalize(),
+                truncate(&t.title, 40),
+                action_text(&e.action)
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn action_text_says_what_was_or_would_be_done() {
+        let dec = |s: &str| s.parse::<Decimal>().unwrap();
+        assert_eq!(
+            action_text(&CopyAction::Simulated {
+                side: Side::Buy,
+                shares: dec("80.004"),
+                usdc: dec("40"),
+                exposure: dec("40"),
+            }),
+            "would buy 80 shares for $40.00  ·  exposure $40.00"
+        );
+        assert_eq!(
+            action_text(&CopyAction::Skipped("max exposure reached".into())),
+            "skipped: max exposure reached"
+        );
+    }
+}
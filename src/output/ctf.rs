@@ -1,7 +1,198 @@
 use alloy::primitives::{B256, U256};
 use anyhow::Result;
+use polymarket_client_sdk::types::{Address, Decimal};
+use serde_json::json;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
 
-use super::{OutputFormat, print_detail_table};
+use super::{OutputFormat, print_detail_table, truncate};
+
+/// Shares held in one outcome of a resolved market.
+pub struct Holding {
+    pub outcome: String,
+    pub outcome_index: usize,
+    pub token: U256,
+    pub size: Decimal,
+    /// Exact shares held on-chain, in base units; read for neg-risk markets,
+    /// which redeem the amounts they are given.
+    pub balance: Option<U256>,
+}
+
+/// A resolved market to redeem, with everything the wallet holds in it.
+pub struct Redemption {
+    pub condition_id: B256,
+    pub title: String,
+    pub neg_risk: bool,
+    pub holdings: Vec<Holding>,
+    /// USDC the winning shares pay out.
+    pub payout: Decimal,
+}
+
+pub struct RedeemPlan {
+    pub holder: Address,
+    pub wallet_type: String,
+    pub redemptions: Vec<Redemption>,
+    pub transactions: usize,
+    pub gas: u64,
+    pub gas_price_gwei: Decimal,
+    /// Estimated fee in POL.
+    pub cost: Decimal,
+}
+
+pub struct RedeemTx {
+    pub tx_hash: B256,
+    pub block_number: u64,
+}
+
+impl RedeemPlan {
+    fn payout(&self) -> Decimal {
+        self.redemptions.iter().map(|r| r.payout).sum()
+    }
+}
+
+fn format_holdings(holdings: &[Holding]) -> String {
+    holdings
+        .iter()
+        .map(|h| format!("{} {}", h.size, h.outcome))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The markets to redeem and the estimated gas, shown before sending.
+pub fn format_redeem_plan(plan: &RedeemPlan) -> String {
+    #[derive(Tabled)]
+    struct Row {
+        #[tabled(rename = "Market")]
+        market: String,
+        #[tabled(rename = "Condition")]
+        condition: String,
+        #[tabled(rename = "Shares")]
+        shares: String,
+        #[tabled(rename = "Payout")]
+        payout: String,
+    }
+    let rows: Vec<Row> = plan
+        .redemptions
+        .iter()
+        .map(|r| Row {
+            market: truncate(&r.title, 40),
+            condition: truncate(&r.condition_id.to_string(), 14),
+            shares: format_holdings(&r.holdings),
+            payout: format!("${}", r.payout.round_dp(2)),
+        })
+        .collect();
+    let table = Table::new(rows).with(Style::rounded()).to_string();
+    format!(
+        "{table}\n  Wallet:       {} ({})\n  Payout:       ${} USDC\n  \
+         Transactions: {}\n  Est. gas:     {} (~{} POL at {} gwei)",
+        plan.holder,
+        plan.wallet_type,
+        plan.payout().round_dp(2),
+        plan.transactions,
+        plan.gas,
+        plan.cost.round_dp(6),
+        plan.gas_price_gwei.round_dp(2),
+    )
+}
+
+fn redemptions_json(plan: &RedeemPlan) -> Vec<serde_json::Value> {
+    plan.redemptions
+        .iter()
+        .map(|r| {
+            let holdings: Vec<_> = r
+                .holdings
+                .iter()
+                .map(|h| {
+                    json!({
+                        "outcome": h.outcome,
+                        "outcome_index": h.outcome_index,
+                        "size": h.size.to_string(),
+                    })
+                })
+                .collect();
+            json!({
+                "condition_id": r.condition_id.to_string(),
+                "title": r.title,
+                "neg_risk": r.neg_risk,
+                "holdings": holdings,
+                "payout": r.payout.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// `redeem --dry-run`: the plan without sending anything.
+pub fn print_redeem_plan(plan: &RedeemPlan, output: &OutputFormat) -> Result<()> {
+    match output {
+        OutputFormat::Json => super::print_json(&json!({
+            "dry_run": true,
+            "wallet": plan.holder.to_string(),
+            "wallet_type": plan.wallet_type,
+            "redemptions": redemptions_json(plan),
+            "payout": plan.payout().to_string(),
+            "transactions": plan.transactions,
+            "gas": plan.gas,
+            "gas_price_gwei": plan.gas_price_gwei.to_string(),
+            "estimated_cost_pol": plan.cost.to_string(),
+        }))?,
+        OutputFormat::Plain => {
+            for r in &plan.redemptions {
+                println!("{}", r.condition_id);
+            }
+        }
+        OutputFormat::Table | OutputFormat::Csv => {
+            println!("{}", format_redeem_plan(plan));
+            println!("Dry run: nothing was sent.");
+        }
+    }
+    Ok(())
+}
+
+pub fn print_redeemed(plan: &RedeemPlan, sent: &[RedeemTx], output: &OutputFormat) -> Result<()> {
+    match output {
+        OutputFormat::Json => {
+            let transactions: Vec<_> = sent
+                .iter()
+                .map(|t| {
+                    json!({
+                        "transaction_hash": t.tx_hash.to_string(),
+                        "block_number": t.block_number,
+                        "polygonscan": format!("https://polygonscan.com/tx/{}", t.tx_hash),
+                    })
+                })
+                .collect();
+            super::print_json(&json!({
+                "wallet": plan.holder.to_string(),
+                "redemptions": redemptions_json(plan),
+                "payout": plan.payout().to_string(),
+                "transactions": transactions,
+            }))?;
+        }
+        OutputFormat::Plain => {
+            for t in sent {
+                println!("{}", t.tx_hash);
+            }
+        }
+        OutputFormat::Table | OutputFormat::Csv => {
+            println!(
+                "Redeemed {} market(s) for ${} USDC",
+                plan.redemptions.len(),
+                plan.payout().round_dp(2)
+            );
+            for t in sent {
+                print_detail_table(vec![
+                    ["Tx Hash".into(), t.tx_hash.to_string()],
+                    ["Block".into(), t.block_number.to_string()],
+                    [
+                        "Polygonscan".into(),
+                        format!("https://polygonscan.com/tx/{}", t.tx_hash),
+                    ],
+                ]);
+            }
+        }
+    }
+    Ok(())
+}
 
 pub fn print_tx_result(
     operation: &str,
@@ -17,10 +208,10 @@ pub fn print_tx_result(
                 "block_number": block_number,
                 "polygonscan": format!("https://polygonscan.com/tx/{tx_hash}"),
             });
-            println!("{}", serde_json::to_string_pretty(&json)?);
+            super::print_json(&json)?;
             Ok(())
         }
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             let rows = vec![
                 ["Operation".into(), operation.to_string()],
                 ["Tx Hash".into(), format!("{tx_hash}")],
@@ -42,10 +233,10 @@ pub fn print_condition_id(condition_id: B256, output: &OutputFormat) -> Result<(
             let json = serde_json::json!({
                 "condition_id": format!("{condition_id}"),
             });
-            println!("{}", serde_json::to_string_pretty(&json)?);
+            super::print_json(&json)?;
             Ok(())
         }
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             println!("Condition ID: {condition_id}");
             Ok(())
         }
@@ -58,10 +249,10 @@ pub fn print_collection_id(collection_id: B256, output: &OutputFormat) -> Result
             let json = serde_json::json!({
                 "collection_id": format!("{collection_id}"),
             });
-            println!("{}", serde_json::to_string_pretty(&json)?);
+            super::print_json(&json)?;
             Ok(())
         }
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             println!("Collection ID: {collection_id}");
             Ok(())
         }
@@ -74,10 +265,10 @@ pub fn print_position_id(position_id: U256, output: &OutputFormat) -> Result<()>
             let json = serde_json::json!({
                 "position_id": position_id.to_string(),
             });
-            println!("{}", serde_json::to_string_pretty(&json)?);
+            super::print_json(&json)?;
             Ok(())
         }
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             println!("Position ID: {position_id}");
             Ok(())
         }
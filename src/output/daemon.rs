@@ -0,0 +1,251 @@
+use polymarket_client_sdk::types::Decimal;
+use serde_json::json;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+use super::time::format_timestamp;
+use super::{OutputFormat, print_csv};
+use crate::config::WorkingStop;
+use crate::daemon::Fired;
+use crate::daemon::ipc::Status;
+use crate::daemon::service::{Runner, Service};
+
+fn timestamp() -> String {
+    format_timestamp(chrono::Utc::now())
+}
+
+fn size_label(stop: &WorkingStop) -> String {
+    stop.size.map_or_else(|| "all".into(), |s| s.to_string())
+}
+
+pub fn print_daemon_status(status: Option<&Status>, output: &OutputFormat) -> anyhow::Result<()> {
+    match (output, status) {
+        (OutputFormat::Json, None) => super::print_json(&json!({"running": false}))?,
+        (OutputFormat::Json, Some(s)) => {
+            let mut data = serde_json::to_value(s)?;
+            data["running"] = json!(true);
+            super::print_json(&data)?;
+        }
+        (_, None) => println!("Daemon is not running."),
+        (_, Some(s)) => {
+            println!("Daemon running (pid {})", s.pid);
+            println!("  Address: {}", s.address);
+            println!("  Uptime:  {}s", s.uptime_secs);
+            println!("  Stops:   {}", s.stops);
+            println!("  Alerts:  {}", s.alerts);
+            println!("  Socket:  {}", s.socket);
+        }
+    }
+    Ok(())
+}
+
+pub fn print_stops(stops: &[WorkingStop], output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            if stops.is_empty() {
+                println!("No stops. Add one with `polymarket clob stop ... --daemon`.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "ID")]
+                id: u32,
+                #[tabled(rename = "Token")]
+                token: String,
+                #[tabled(rename = "Side")]
+                side: String,
+                #[tabled(rename = "Trigger")]
+                trigger: String,
+                #[tabled(rename = "Size")]
+                size: String,
+                #[tabled(rename = "Type")]
+                order_type: String,
+            }
+            let rows: Vec<Row> = stops
+                .iter()
+                .map(|s| Row {
+                    id: s.id,
+                    token: super::truncate(&s.token_id, 20),
+                    side: s.side.to_string(),
+                    trigger: format!("{} {}", s.direction, s.trigger),
+                    size: size_label(s),
+                    order_type: s.order_type.to_string(),
+                })
+                .collect();
+            let table = Table::new(rows).with(Style::rounded()).to_string();
+            println!("{table}");
+        }
+        OutputFormat::Json => super::print_json(&stops)?,
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = stops
+                .iter()
+                .map(|s| {
+                    vec![
+                        s.id.to_string(),
+                        s.token_id.clone(),
+                        s.side.to_string(),
+                        s.direction.to_string(),
+                        s.trigger.to_string(),
+                        size_label(s),
+                        s.order_type.to_string(),
+                    ]
+                })
+                .collect();
+            print_csv(
+                &[
+                    "id",
+                    "token_id",
+                    "side",
+                    "direction",
+                    "trigger",
+                    "size",
+                    "order_type",
+                ],
+                &rows,
+            );
+        }
+        OutputFormat::Plain => {
+            for s in stops {
+                println!("{}", s.id);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn print_stop_added(stop: &WorkingStop, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Csv => println!(
+            "Stop #{} handed to the daemon: {} {} when the midpoint goes {} {}",
+            stop.id,
+            stop.side,
+            size_label(stop),
+            stop.direction,
+            stop.trigger
+        ),
+        OutputFormat::Plain => println!("{}", stop.id),
+        OutputFormat::Json => super::print_json(stop)?,
+    }
+    Ok(())
+}
+
+/// A line in the daemon's log; JSON output is newline-delimited.
+pub fn print_daemon_log(message: &str, output: &OutputFormat) {
+    match output {
+        OutputFormat::Json => super::print_json_line(json!({
+            "event": "log",
+            "message": message,
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+        })),
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+            println!("{}  {message}", timestamp());
+        }
+    }
+}
+
+/// The outcome of a triggered stop: the order ID, or why it will be retried.
+pub fn print_stop_fired(
+    stop: &WorkingStop,
+    mid: Decimal,
+    fired: &Fired,
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Json => {
+            let mut event = json!({
+                "event": "stop",
+                "id": stop.id,
+                "token_id": stop.token_id,
+                "side": stop.side.to_string(),
+                "trigger": stop.trigger.to_string(),
+                "midpoint": mid.to_string(),
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+            });
+            match fired {
+                Fired::Placed(order_id) => event["order_id"] = json!(order_id),
+                Fired::Failed(e) => event["error"] = json!(format!("{e:#}")),
+                Fired::Refused(e) => {
+                    event["error"] = json!(format!("{e:#}"));
+                    event["refused"] = json!(true);
+                }
+                Fired::Unknown(e) => {
+                    event["error"] = json!(format!("{e:#}"));
+                    event["unknown"] = json!(true);
+                }
+            }
+            println!("{event}");
+        }
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => match fired {
+            Fired::Placed(order_id) => println!(
+                "{}  Stop #{} triggered at {mid}: {} order {order_id}",
+                timestamp(),
+                stop.id,
+                stop.side
+            ),
+            Fired::Failed(e) => println!(
+                "{}  Stop #{} triggered at {mid} but the order failed ({e:#}); retrying",
+                timestamp(),
+                stop.id
+            ),
+            Fired::Refused(e) => println!(
+                "{}  Stop #{} triggered at {mid}: {e:#}; removed",
+                timestamp(),
+                stop.id
+            ),
+            Fired::Unknown(e) => println!(
+                "{}  Stop #{} triggered at {mid} but the order's fate is unknown ({e:#}); \
+                 check `clob orders`. The stop is removed so it isn't sent twice",
+                timestamp(),
+                stop.id
+            ),
+        },
+    }
+    Ok(())
+}
+
+pub fn print_service_installed(service: &Service, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Json => super::print_json(&json!({
+            "manager": service.manager,
+            "path": service.path.display().to_string(),
+            "next_steps": service.next_steps,
+        }))?,
+        _ => {
+            println!(
+                "Wrote {} service {}",
+                service.manager,
+                service.path.display()
+            );
+            println!("Start it with:");
+            for step in &service.next_steps {
+                println!("  {step}");
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn print_service_removed(
+    runner: Runner,
+    service: Option<&Service>,
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    match (output, service) {
+        (OutputFormat::Json, None) => super::print_json(&json!({"removed": false}))?,
+        (OutputFormat::Json, Some(s)) => super::print_json(&json!({
+            "removed": true,
+            "manager": s.manager,
+            "path": s.path.display().to_string(),
+            "next_steps": s.next_steps,
+        }))?,
+        (_, None) => println!("No {} service is installed.", runner.name()),
+        (_, Some(s)) => {
+            println!("Removed {}", s.path.display());
+            println!("If it is running, stop it with:");
+            for step in &s.next_steps {
+                println!("  {step}");
+            }
+        }
+    }
+    Ok(())
+}
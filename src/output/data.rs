@@ -8,7 +8,9 @@ use serde_json::json;
 use tabled::settings::Style;
 use tabled::{Table, Tabled};
 
-use super::{OutputFormat, format_decimal, truncate};
+use super::style;
+use super::time::iso_from_unix;
+use super::{OutputFormat, format_decimal, format_number, format_price_with, truncate};
 
 fn format_market(m: &Market) -> String {
     match m {
@@ -18,9 +20,51 @@ fn format_market(m: &Market) -> String {
     }
 }
 
+pub(super) const POSITION_FIELDS: [&str; 17] = [
+    "title",
+    "slug",
+    "outcome",
+    "outcome_index",
+    "size",
+    "avg_price",
+    "initial_value",
+    "current_value",
+    "cash_pnl",
+    "percent_pnl",
+    "realized_pnl",
+    "cur_price",
+    "condition_id",
+    "proxy_wallet",
+    "redeemable",
+    "mergeable",
+    "neg_risk",
+];
+
+pub(super) fn position_json(p: &Position) -> serde_json::Value {
+    json!({
+        "title": p.title,
+        "slug": p.slug,
+        "outcome": p.outcome,
+        "outcome_index": p.outcome_index,
+        "size": p.size.to_string(),
+        "avg_price": p.avg_price.to_string(),
+        "initial_value": p.initial_value.to_string(),
+        "current_value": p.current_value.to_string(),
+        "cash_pnl": p.cash_pnl.to_string(),
+        "percent_pnl": p.percent_pnl.to_string(),
+        "realized_pnl": p.realized_pnl.to_string(),
+        "cur_price": p.cur_price.to_string(),
+        "condition_id": p.condition_id.to_string(),
+        "proxy_wallet": p.proxy_wallet.to_string(),
+        "redeemable": p.redeemable,
+        "mergeable": p.mergeable,
+        "neg_risk": p.negative_risk,
+    })
+}
+
 pub fn print_positions(positions: &[Position], output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Plain => {
             if positions.is_empty() {
                 println!("No positions found.");
                 return Ok(());
@@ -35,9 +79,11 @@ pub fn print_positions(positions: &[Position], output: &OutputFormat) -> anyhow:
                 size: String,
                 #[tabled(rename = "Avg Price")]
                 avg_price: String,
+                #[tabled(rename = "Mark")]
+                cur_price: String,
                 #[tabled(rename = "Current")]
                 current_value: String,
-                #[tabled(rename = "PnL")]
+                #[tabled(rename = "Unrealized PnL")]
                 pnl: String,
             }
             let rows: Vec<Row> = positions
@@ -45,51 +91,73 @@ pub fn print_positions(positions: &[Position], output: &OutputFormat) -> anyhow:
                 .map(|p| Row {
                     title: truncate(&p.title, 40),
                     outcome: p.outcome.clone(),
-                    size: format!("{:.2}", p.size),
-                    avg_price: format!("{:.4}", p.avg_price),
+                    size: format_number(p.size),
+                    avg_price: format_price_with(p.avg_price, |p| format!("{p:.4}")),
+                    cur_price: format_price_with(p.cur_price, |p| format!("{p:.4}")),
                     current_value: format_decimal(p.current_value),
                     pnl: format!("{:.2}", p.cash_pnl),
                 })
                 .collect();
-            let table = Table::new(rows).with(Style::rounded()).to_string();
+            let mut table = Table::new(rows);
+            table.with(Style::rounded());
+            style::color_column(
+                &mut table,
+                6,
+                1,
+                positions.iter().map(|p| style::sign(p.cash_pnl)),
+            );
             println!("{table}");
         }
         OutputFormat::Json => {
-            let data: Vec<_> = positions
-                .iter()
-                .map(|p| {
-                    json!({
-                        "title": p.title,
-                        "slug": p.slug,
-                        "outcome": p.outcome,
-                        "outcome_index": p.outcome_index,
-                        "size": p.size.to_string(),
-                        "avg_price": p.avg_price.to_string(),
-                        "initial_value": p.initial_value.to_string(),
-                        "current_value": p.current_value.to_string(),
-                        "cash_pnl": p.cash_pnl.to_string(),
-                        "percent_pnl": p.percent_pnl.to_string(),
-                        "realized_pnl": p.realized_pnl.to_string(),
-                        "cur_price": p.cur_price.to_string(),
-                        "condition_id": p.condition_id.to_string(),
-                        "proxy_wallet": p.proxy_wallet.to_string(),
-                        "redeemable": p.redeemable,
-                        "mergeable": p.mergeable,
-                    })
-                })
-                .collect();
+            let data: Vec<_> = positions.iter().map(position_json).collect();
             super::print_json(&data)?;
         }
+        OutputFormat::Csv => {
+            let data: Vec<_> = positions.iter().map(position_json).collect();
+            super::print_csv_objects(&POSITION_FIELDS, &data);
+        }
     }
     Ok(())
 }
 
+const CLOSED_POSITION_FIELDS: [&str; 12] = [
+    "title",
+    "slug",
+    "outcome",
+    "outcome_index",
+    "avg_price",
+    "total_bought",
+    "realized_pnl",
+    "cur_price",
+    "condition_id",
+    "proxy_wallet",
+    "timestamp",
+    "time",
+];
+
+fn closed_position_json(p: &ClosedPosition) -> serde_json::Value {
+    json!({
+        "title": p.title,
+        "slug": p.slug,
+        "outcome": p.outcome,
+        "outcome_index": p.outcome_index,
+        "avg_price": p.avg_price.to_string(),
+        "total_bought": p.total_bought.to_string(),
+        "realized_pnl": p.realized_pnl.to_string(),
+        "cur_price": p.cur_price.to_string(),
+        "condition_id": p.condition_id.to_string(),
+        "proxy_wallet": p.proxy_wallet.to_string(),
+        "timestamp": p.timestamp,
+        "time": iso_from_unix(p.timestamp),
+    })
+}
+
 pub fn print_closed_positions(
     positions: &[ClosedPosition],
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Plain => {
             if positions.is_empty() {
                 println!("No closed positions found.");
                 return Ok(());
@@ -110,41 +178,35 @@ pub fn print_closed_positions(
                 .map(|p| Row {
                     title: truncate(&p.title, 40),
                     outcome: p.outcome.clone(),
-                    avg_price: format!("{:.4}", p.avg_price),
+                    avg_price: format_price_with(p.avg_price, |p| format!("{p:.4}")),
                     realized_pnl: format!("{:.2}", p.realized_pnl),
                 })
                 .collect();
-            let table = Table::new(rows).with(Style::rounded()).to_string();
+            let mut table = Table::new(rows);
+            table.with(Style::rounded());
+            style::color_column(
+                &mut table,
+                3,
+                1,
+                positions.iter().map(|p| style::sign(p.realized_pnl)),
+            );
             println!("{table}");
         }
         OutputFormat::Json => {
-            let data: Vec<_> = positions
-                .iter()
-                .map(|p| {
-                    json!({
-                        "title": p.title,
-                        "slug": p.slug,
-                        "outcome": p.outcome,
-                        "outcome_index": p.outcome_index,
-                        "avg_price": p.avg_price.to_string(),
-                        "total_bought": p.total_bought.to_string(),
-                        "realized_pnl": p.realized_pnl.to_string(),
-                        "cur_price": p.cur_price.to_string(),
-                        "condition_id": p.condition_id.to_string(),
-                        "proxy_wallet": p.proxy_wallet.to_string(),
-                        "timestamp": p.timestamp,
-                    })
-                })
-                .collect();
+            let data: Vec<_> = positions.iter().map(closed_position_json).collect();
             super::print_json(&data)?;
         }
+        OutputFormat::Csv => {
+            let data: Vec<_> = positions.iter().map(closed_position_json).collect();
+            super::print_csv_objects(&CLOSED_POSITION_FIELDS, &data);
+        }
     }
     Ok(())
 }
 
 pub fn print_value(values: &[Value], output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if values.is_empty() {
                 println!("No value data found.");
                 return Ok(());
@@ -179,7 +241,9 @@ pub fn print_value(values: &[Value], output: &OutputFormat) -> anyhow::Result<()
 
 pub fn print_traded(t: &Traded, output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => println!("{}: {} markets traded", t.user, t.traded),
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+            println!("{}: {} markets traded", t.user, t.traded)
+        }
         OutputFormat::Json => {
             super::print_json(&json!({
                 "user": t.user.to_string(),
@@ -190,9 +254,41 @@ pub fn print_traded(t: &Traded, output: &OutputFormat) -> anyhow::Result<()> {
     Ok(())
 }
 
+const TRADE_FIELDS: [&str; 12] = [
+    "title",
+    "slug",
+    "side",
+    "outcome",
+    "outcome_index",
+    "size",
+    "price",
+    "timestamp",
+    "time",
+    "condition_id",
+    "proxy_wallet",
+    "transaction_hash",
+];
+
+fn trade_json(t: &Trade) -> serde_json::Value {
+    json!({
+        "title": t.title,
+        "slug": t.slug,
+        "side": t.side.to_string(),
+        "outcome": t.outcome,
+        "outcome_index": t.outcome_index,
+        "size": t.size.to_string(),
+        "price": t.price.to_string(),
+        "timestamp": t.timestamp,
+        "time": iso_from_unix(t.timestamp),
+        "condition_id": t.condition_id.to_string(),
+        "proxy_wallet": t.proxy_wallet.to_string(),
+        "transaction_hash": t.transaction_hash.to_string(),
+    })
+}
+
 pub fn print_trades(trades: &[Trade], output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Plain => {
             if trades.is_empty() {
                 println!("No trades found.");
                 return Ok(());
@@ -216,41 +312,52 @@ pub fn print_trades(trades: &[Trade], output: &OutputFormat) -> anyhow::Result<(
                     title: truncate(&t.title, 40),
                     side: t.side.to_string(),
                     outcome: t.outcome.clone(),
-                    size: format!("{:.2}", t.size),
-                    price: format!("{:.4}", t.price),
+                    size: format_number(t.size),
+                    price: format_price_with(t.price, |p| format!("{p:.4}")),
                 })
                 .collect();
             let table = Table::new(rows).with(Style::rounded()).to_string();
             println!("{table}");
         }
         OutputFormat::Json => {
-            let data: Vec<_> = trades
-                .iter()
-                .map(|t| {
-                    json!({
-                        "title": t.title,
-                        "slug": t.slug,
-                        "side": t.side.to_string(),
-                        "outcome": t.outcome,
-                        "outcome_index": t.outcome_index,
-                        "size": t.size.to_string(),
-                        "price": t.price.to_string(),
-                        "timestamp": t.timestamp,
-                        "condition_id": t.condition_id.to_string(),
-                        "proxy_wallet": t.proxy_wallet.to_string(),
-                        "transaction_hash": t.transaction_hash.to_string(),
-                    })
-                })
-                .collect();
+            let data: Vec<_> = trades.iter().map(trade_json).collect();
             super::print_json(&data)?;
         }
+        OutputFormat::Csv => {
+            let data: Vec<_> = trades.iter().map(trade_json).collect();
+            super::print_csv_objects(&TRADE_FIELDS, &data);
+        }
     }
     Ok(())
 }
 
+const ACTIVITY_FIELDS: [&str; 8] = [
+    "activity_type",
+    "title",
+    "size",
+    "usdc_size",
+    "timestamp",
+    "time",
+    "transaction_hash",
+    "proxy_wallet",
+];
+
+fn activity_json(a: &Activity) -> serde_json::Value {
+    json!({
+        "activity_type": a.activity_type.to_string(),
+        "title": a.title,
+        "size": a.size.to_string(),
+        "usdc_size": a.usdc_size.to_string(),
+        "timestamp": a.timestamp,
+        "time": iso_from_unix(a.timestamp),
+        "transaction_hash": a.transaction_hash.to_string(),
+        "proxy_wallet": a.proxy_wallet.to_string(),
+    })
+}
+
 pub fn print_activity(activity: &[Activity], output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Plain => {
             if activity.is_empty() {
                 println!("No activity found.");
                 return Ok(());
@@ -273,7 +380,7 @@ pub fn print_activity(activity: &[Activity], output: &OutputFormat) -> anyhow::R
                 .map(|a| Row {
                     activity_type: a.activity_type.to_string(),
                     title: truncate(a.title.as_deref().unwrap_or("—"), 35),
-                    size: format!("{:.2}", a.size),
+                    size: format_number(a.size),
                     usdc_size: format_decimal(a.usdc_size),
                     tx: truncate(&a.transaction_hash.to_string(), 14),
                 })
@@ -282,29 +389,20 @@ pub fn print_activity(activity: &[Activity], output: &OutputFormat) -> anyhow::R
             println!("{table}");
         }
         OutputFormat::Json => {
-            let data: Vec<_> = activity
-                .iter()
-                .map(|a| {
-                    json!({
-                        "activity_type": a.activity_type.to_string(),
-                        "title": a.title,
-                        "size": a.size.to_string(),
-                        "usdc_size": a.usdc_size.to_string(),
-                        "timestamp": a.timestamp,
-                        "transaction_hash": a.transaction_hash.to_string(),
-                        "proxy_wallet": a.proxy_wallet.to_string(),
-                    })
-                })
-                .collect();
+            let data: Vec<_> = activity.iter().map(activity_json).collect();
             super::print_json(&data)?;
         }
+        OutputFormat::Csv => {
+            let data: Vec<_> = activity.iter().map(activity_json).collect();
+            super::print_csv_objects(&ACTIVITY_FIELDS, &data);
+        }
     }
     Ok(())
 }
 
 pub fn print_holders(meta_holders: &[MetaHolder], output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if meta_holders.is_empty() {
                 println!("No holders found.");
                 return Ok(());
@@ -367,7 +465,7 @@ pub fn print_holders(meta_holders: &[MetaHolder], output: &OutputFormat) -> anyh
 
 pub fn print_open_interest(oi: &[OpenInterest], output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if oi.is_empty() {
                 println!("No open interest data found.");
                 return Ok(());
@@ -402,7 +500,7 @@ pub fn print_open_interest(oi: &[OpenInterest], output: &OutputFormat) -> anyhow
 
 pub fn print_live_volume(volume: &[LiveVolume], output: &OutputFormat) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if volume.is_empty() {
                 println!("No volume data found.");
                 return Ok(());
@@ -451,7 +549,7 @@ pub fn print_leaderboard(
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if entries.is_empty() {
                 println!("No leaderboard entries found.");
                 return Ok(());
@@ -503,7 +601,7 @@ pub fn print_builder_leaderboard(
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if entries.is_empty() {
                 println!("No builder leaderboard entries found.");
                 return Ok(());
@@ -555,7 +653,7 @@ pub fn print_builder_volume(
     output: &OutputFormat,
 ) -> anyhow::Result<()> {
     match output {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
             if entries.is_empty() {
                 println!("No builder volume data found.");
                 return Ok(());
@@ -576,7 +674,7 @@ pub fn print_builder_volume(
             let rows: Vec<Row> = entries
                 .iter()
                 .map(|e| Row {
-                    date: e.dt.format("%Y-%m-%d").to_string(),
+                    date: super::time::format_date(e.dt),
                     builder: truncate(&e.builder, 25),
                     volume: format_decimal(e.volume),
                     active_users: e.active_users.to_string(),
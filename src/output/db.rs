@@ -0,0 +1,103 @@
+use anyhow::Result;
+use serde_json::json;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+use super::{OutputFormat, print_csv, print_detail_table};
+use crate::db::{Stats, Vacuumed};
+
+const TABLE_FIELDS: [&str; 2] = ["table", "rows"];
+
+/// Bytes in powers of 1024, as `record --rotate-size` takes them.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+pub fn print_db_stats(stats: &Stats, output: &OutputFormat) -> Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "Table")]
+                table: &'static str,
+                #[tabled(rename = "Rows")]
+                rows: u64,
+            }
+            let rows: Vec<Row> = stats
+                .tables
+                .iter()
+                .map(|&(table, rows)| Row { table, rows })
+                .collect();
+            let mut table = Table::new(rows);
+            table.with(Style::rounded());
+            println!("{table}");
+            print_detail_table(vec![
+                ["Database".into(), stats.path.display().to_string()],
+                ["Schema Version".into(), stats.version.to_string()],
+                ["Size".into(), format_bytes(stats.bytes)],
+                ["Reclaimable".into(), format_bytes(stats.free_bytes)],
+            ]);
+        }
+        OutputFormat::Json => {
+            let tables: Vec<_> = stats
+                .tables
+                .iter()
+                .map(|(table, rows)| json!({"table": table, "rows": rows}))
+                .collect();
+            super::print_json(&json!({
+                "path": stats.path.display().to_string(),
+                "schema_version": stats.version,
+                "bytes": stats.bytes,
+                "free_bytes": stats.free_bytes,
+                "tables": tables,
+            }))?;
+        }
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = stats
+                .tables
+                .iter()
+                .map(|(table, rows)| vec![table.to_string(), rows.to_string()])
+                .collect();
+            print_csv(&TABLE_FIELDS, &rows);
+        }
+    }
+    Ok(())
+}
+
+pub fn print_vacuumed(v: &Vacuumed, output: &OutputFormat) -> Result<()> {
+    match output {
+        OutputFormat::Json => super::print_json(&json!({
+            "stale_cache_entries": v.stale_cache_entries,
+            "bytes_freed": v.bytes_freed,
+        }))?,
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => println!(
+            "Removed {} stale cache entries and compacted the database, freeing {}.",
+            v.stale_cache_entries,
+            format_bytes(v.bytes_freed)
+        ),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_use_the_largest_whole_unit() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MB");
+    }
+}
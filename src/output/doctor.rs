@@ -0,0 +1,165 @@
+use serde_json::json;
+use tabled::settings::object::Columns;
+use tabled::settings::{Modify, Style, Width};
+use tabled::{Table, Tabled};
+
+use super::style::{self, Tone};
+use super::{OutputFormat, print_csv};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    /// Works, but something is off or missing, such as a watch-only wallet.
+    Warn,
+    Fail,
+    /// Not run, because a check it depends on failed.
+    Skipped,
+}
+
+impl CheckStatus {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Warn => "warn",
+            Self::Fail => "fail",
+            Self::Skipped => "skipped",
+        }
+    }
+
+    fn tone(self) -> Option<Tone> {
+        match self {
+            Self::Ok => Some(Tone::Up),
+            Self::Fail => Some(Tone::Down),
+            Self::Warn | Self::Skipped => None,
+        }
+    }
+}
+
+/// One line of `doctor`: what was checked, how it went, and what to do
+/// about it when it didn't.
+pub struct Check {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    pub fix: Option<String>,
+}
+
+impl Check {
+    pub fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Ok,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    pub fn warn(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    pub fn fail(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    pub fn skipped(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Skipped,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+}
+
+pub fn print_doctor(checks: &[Check], output: &OutputFormat) -> anyhow::Result<()> {
+    let failed = checks
+        .iter()
+        .filter(|c| c.status == CheckStatus::Fail)
+        .count();
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "Check")]
+                name: &'static str,
+                #[tabled(rename = "Status")]
+                status: &'static str,
+                #[tabled(rename = "Detail")]
+                detail: String,
+            }
+            let rows: Vec<Row> = checks
+                .iter()
+                .map(|c| Row {
+                    name: c.name,
+                    status: c.status.label(),
+                    detail: c.detail.clone(),
+                })
+                .collect();
+            let mut table = Table::new(rows);
+            table
+                .with(Style::rounded())
+                .with(Modify::new(Columns::last()).with(Width::wrap(80)));
+            style::color_column(&mut table, 1, 1, checks.iter().map(|c| c.status.tone()));
+            println!("{table}");
+            let fixes: Vec<_> = checks
+                .iter()
+                .filter_map(|c| Some((c.name, c.fix.as_deref()?)))
+                .collect();
+            if !fixes.is_empty() {
+                println!();
+                for (name, fix) in fixes {
+                    println!("{name}: {fix}");
+                }
+            }
+            println!();
+            match failed {
+                0 => println!("No problems found."),
+                1 => println!("1 check failed."),
+                n => println!("{n} checks failed."),
+            }
+        }
+        OutputFormat::Json => {
+            let data: Vec<_> = checks
+                .iter()
+                .map(|c| {
+                    json!({
+                        "name": c.name,
+                        "status": c.status.label(),
+                        "detail": c.detail,
+                        "fix": c.fix,
+                    })
+                })
+                .collect();
+            super::print_json(&json!({
+                "ok": failed == 0,
+                "checks": data,
+            }))?;
+        }
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = checks
+                .iter()
+                .map(|c| {
+                    vec![
+                        c.name.to_string(),
+                        c.status.label().to_string(),
+                        c.detail.clone(),
+                        c.fix.clone().unwrap_or_default(),
+                    ]
+                })
+                .collect();
+            print_csv(&["name", "status", "detail", "fix"], &rows);
+        }
+    }
+    Ok(())
+}
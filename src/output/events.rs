@@ -1,8 +1,17 @@
-use polymarket_client_sdk::gamma::types::response::Event;
+use chrono::{DateTime, Local};
+use polymarket_client_sdk::gamma::types::response::{Event, Market};
+use polymarket_client_sdk::types::Decimal;
+use serde_json::json;
 use tabled::settings::Style;
 use tabled::{Table, Tabled};
 
-use super::{detail_field, format_decimal, print_detail_table, truncate};
+use super::markets::market_status;
+use super::quote::Quote;
+use super::time::format_clock;
+use super::{
+    OutputFormat, csv_header, csv_lines, csv_opt, detail_field, format_decimal, format_price,
+    print_csv, print_detail_table, truncate,
+};
 
 #[derive(Tabled)]
 struct EventRow {
@@ -54,6 +63,35 @@ pub fn print_events_table(events: &[Event]) {
     println!("{table}");
 }
 
+pub fn print_events_csv(events: &[Event]) {
+    let rows: Vec<Vec<String>> = events
+        .iter()
+        .map(|e| {
+            vec![
+                e.id.clone(),
+                csv_opt(e.slug.as_ref()),
+                csv_opt(e.title.as_ref()),
+                csv_opt(e.markets.as_ref().map(Vec::len)),
+                csv_opt(e.volume),
+                csv_opt(e.liquidity),
+                event_status(e).into(),
+            ]
+        })
+        .collect();
+    print_csv(
+        &[
+            "id",
+            "slug",
+            "title",
+            "markets",
+            "volume",
+            "liquidity",
+            "status",
+        ],
+        &rows,
+    );
+}
+
 #[allow(clippy::too_many_lines)]
 pub fn print_event_detail(e: &Event) {
     let mut rows: Vec<[String; 2]> = Vec::new();
@@ -72,16 +110,7 @@ pub fn print_event_detail(e: &Event) {
         "Markets",
         e.markets
             .as_ref()
-            .map(|m| {
-                if m.is_empty() {
-                    "None".into()
-                } else {
-                    m.iter()
-                        .filter_map(|mkt| mkt.question.as_deref())
-                        .collect::<Vec<_>>()
-                        .join(" | ")
-                }
-            })
+            .map(|m| m.len().to_string())
             .unwrap_or_default()
     );
     detail_field!(
@@ -167,13 +196,322 @@ pub fn print_event_detail(e: &Event) {
     );
 
     print_detail_table(rows);
+    if let Some(markets) = e.markets.as_deref().filter(|m| !m.is_empty()) {
+        println!();
+        print_event_markets(markets);
+    }
+}
+
+/// "Yes 62.0¢ · No 38.0¢" for a market's outcomes and prices.
+fn outcome_prices(m: &Market) -> String {
+    let names = m.outcomes.as_deref().unwrap_or_default();
+    let prices = m.outcome_prices.as_deref().unwrap_or_default();
+    if names.is_empty() {
+        return "—".into();
+    }
+    names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| match prices.get(i) {
+            Some(p) => format!("{name} {:.1}¢", p * Decimal::from(100)),
+            None => name.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+/// The event's markets, then their combined volume.
+fn print_event_markets(markets: &[Market]) {
+    #[derive(Tabled)]
+    struct Row {
+        #[tabled(rename = "Market")]
+        market: String,
+        #[tabled(rename = "Outcomes")]
+        outcomes: String,
+        #[tabled(rename = "Volume")]
+        volume: String,
+        #[tabled(rename = "Volume (24hr)")]
+        volume_24hr: String,
+        #[tabled(rename = "Status")]
+        status: String,
+        #[tabled(rename = "ID")]
+        id: String,
+    }
+    let rows: Vec<Row> = markets
+        .iter()
+        .map(|m| Row {
+            // Multi-market events label each market by its group item, e.g. a
+            // candidate's name, which is shorter than the full question.
+            market: truncate(
+                m.group_item_title
+                    .as_deref()
+                    .filter(|t| !t.is_empty())
+                    .or(m.question.as_deref())
+                    .unwrap_or("—"),
+                40,
+            ),
+            outcomes: outcome_prices(m),
+            volume: m.volume_num.map_or_else(|| "—".into(), format_decimal),
+            volume_24hr: m.volume_24hr.map_or_else(|| "—".into(), format_decimal),
+            status: market_status(m).into(),
+            id: m.id.clone(),
+        })
+        .collect();
+    let table = Table::new(rows).with(Style::rounded()).to_string();
+    println!("{table}");
+
+    let (volume, volume_24hr) = aggregate_volume(markets);
+    println!(
+        "{} markets · total volume {} · 24hr {}",
+        markets.len(),
+        format_decimal(volume),
+        format_decimal(volume_24hr)
+    );
+}
+
+/// Summed all-time and 24h volume over `markets`; missing values count as 0.
+fn aggregate_volume(markets: &[Market]) -> (Decimal, Decimal) {
+    markets
+        .iter()
+        .fold((Decimal::ZERO, Decimal::ZERO), |(v, d), m| {
+            (
+                v + m.volume_num.unwrap_or_default(),
+                d + m.volume_24hr.unwrap_or_default(),
+            )
+        })
+}
+
+/// Each outcome of an event priced by its book, most likely first.
+pub struct EventOdds {
+    pub event: String,
+    pub quotes: Vec<Quote>,
+}
+
+impl EventOdds {
+    pub fn new(event: String, mut quotes: Vec<Quote>) -> Self {
+        quotes.sort_by_key(|q| std::cmp::Reverse(Self::implied(q)));
+        Self { event, quotes }
+    }
+
+    /// The probability the book implies: its midpoint, or the last trade
+    /// when one side is empty.
+    pub fn implied(quote: &Quote) -> Option<Decimal> {
+        quote.mid().or(quote.last)
+    }
+
+    /// Sum of the implied probabilities; 1 for a fair book.
+    pub fn total(&self) -> Decimal {
+        self.quotes.iter().filter_map(Self::implied).sum()
+    }
+
+    /// How far the implied probabilities add up past 1.
+    pub fn overround(&self) -> Decimal {
+        self.total() - Decimal::ONE
+    }
+
+    /// The implied probability scaled so they all add up to 1.
+    pub fn normalized(&self, quote: &Quote) -> Option<Decimal> {
+        let total = self.total();
+        (!total.is_zero()).then_some(Self::implied(quote)? / total)
+    }
+
+    /// What buying one share of every outcome costs at the asks, when they
+    /// all have one.
+    pub fn ask_total(&self) -> Option<Decimal> {
+        self.quotes.iter().map(|q| q.ask).sum()
+    }
+}
+
+fn percent(d: Option<Decimal>) -> String {
+    d.map_or_else(
+        || "—".into(),
+        |d| format!("{}%", (d * Decimal::ONE_HUNDRED).round_dp(1)),
+    )
+}
+
+fn signed_percent(d: Decimal) -> String {
+    format!("{:+}%", (d * Decimal::ONE_HUNDRED).round_dp(2).normalize())
+}
+
+fn odds_json(odds: &EventOdds) -> serde_json::Value {
+    let outcomes: Vec<_> = odds
+        .quotes
+        .iter()
+        .map(|q| {
+            json!({
+                "outcome": q.outcome,
+                "token_id": q.token_id.to_string(),
+                "bid": q.bid.map(|d| d.to_string()),
+                "ask": q.ask.map(|d| d.to_string()),
+                "implied": EventOdds::implied(q).map(|d| d.to_string()),
+                "normalized": odds.normalized(q).map(|d| d.round_dp(6).to_string()),
+            })
+        })
+        .collect();
+    json!({
+        "event": odds.event,
+        "total": odds.total().to_string(),
+        "overround": odds.overround().to_string(),
+        "ask_total": odds.ask_total().map(|d| d.to_string()),
+        "outcomes": outcomes,
+    })
+}
+
+fn odds_csv_rows(odds: &EventOdds) -> Vec<Vec<String>> {
+    odds.quotes
+        .iter()
+        .map(|q| {
+            vec![
+                q.outcome.clone(),
+                q.token_id.to_string(),
+                csv_opt(q.bid),
+                csv_opt(q.ask),
+                csv_opt(EventOdds::implied(q)),
+                csv_opt(odds.normalized(q).map(|d| d.round_dp(6))),
+            ]
+        })
+        .collect()
+}
+
+const ODDS_CSV_HEADERS: [&str; 6] = ["outcome", "token_id", "bid", "ask", "implied", "normalized"];
+
+pub fn print_event_odds(odds: &EventOdds, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "Outcome")]
+                outcome: String,
+                #[tabled(rename = "Bid")]
+                bid: String,
+                #[tabled(rename = "Ask")]
+                ask: String,
+                #[tabled(rename = "Implied")]
+                implied: String,
+                #[tabled(rename = "Normalized")]
+                normalized: String,
+            }
+            let price = |d: Option<Decimal>| d.map_or_else(|| "—".into(), format_price);
+            let rows: Vec<Row> = odds
+                .quotes
+                .iter()
+                .map(|q| Row {
+                    outcome: truncate(&q.outcome, 50),
+                    bid: price(q.bid),
+                    ask: price(q.ask),
+                    implied: percent(EventOdds::implied(q)),
+                    normalized: percent(odds.normalized(q)),
+                })
+                .collect();
+            println!("{}", odds.event);
+            println!("{}", Table::new(rows).with(Style::rounded()));
+            let mut summary = vec![
+                ["Implied total".into(), percent(Some(odds.total()))],
+                ["Overround".into(), signed_percent(odds.overround())],
+            ];
+            if let Some(cost) = odds.ask_total() {
+                summary.push([
+                    "Cost of every outcome".into(),
+                    format!("${}", cost.normalize()),
+                ]);
+            }
+            print_detail_table(summary);
+        }
+        OutputFormat::Plain => println!("{}", plain_odds(odds)),
+        OutputFormat::Json => super::print_json(&odds_json(odds))?,
+        OutputFormat::Csv => print_csv(&ODDS_CSV_HEADERS, &odds_csv_rows(odds)),
+    }
+    Ok(())
+}
+
+fn plain_odds(odds: &EventOdds) -> String {
+    let outcomes: Vec<String> = odds
+        .quotes
+        .iter()
+        .map(|q| format!("{} {}", q.outcome, percent(odds.normalized(q))))
+        .collect();
+    format!(
+        "{}  overround {}",
+        outcomes.join(" · "),
+        signed_percent(odds.overround())
+    )
+}
+
+/// One round of `events odds --watch`, stamped with `at`. `first` marks the
+/// first round, which carries the CSV header.
+pub fn print_event_odds_tick(
+    odds: &EventOdds,
+    at: DateTime<Local>,
+    first: bool,
+    output: &OutputFormat,
+) {
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            println!("{}  {}", format_clock(at), plain_odds(odds));
+        }
+        OutputFormat::Json => {
+            let mut line = odds_json(odds);
+            line["time"] = json!(at.to_rfc3339());
+            super::print_json_line(line);
+        }
+        OutputFormat::Csv => {
+            let headers: Vec<&str> = std::iter::once("time").chain(ODDS_CSV_HEADERS).collect();
+            let rows: Vec<Vec<String>> = odds_csv_rows(odds)
+                .into_iter()
+                .map(|row| std::iter::once(at.to_rfc3339()).chain(row).collect())
+                .collect();
+            for line in csv_lines(&headers, &rows, first && csv_header()) {
+                println!("{line}");
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use polymarket_client_sdk::types::U256;
     use serde_json::json;
 
+    fn odds_quote(outcome: &str, bid: &str, ask: &str) -> Quote {
+        Quote {
+            market: "Who wins?".into(),
+            outcome: outcome.into(),
+            token_id: U256::from(1),
+            bid: bid.parse().ok(),
+            ask: ask.parse().ok(),
+            last: None,
+        }
+    }
+
+    #[test]
+    fn event_odds_normalize_and_measure_overround() {
+        let odds = EventOdds::new(
+            "Who wins?".into(),
+            vec![
+                odds_quote("B", "0.30", "0.34"),
+                odds_quote("A", "0.58", "0.62"),
+                odds_quote("C", "0.09", "0.11"),
+            ],
+        );
+        let names: Vec<_> = odds.quotes.iter().map(|q| q.outcome.as_str()).collect();
+        assert_eq!(names, ["A", "B", "C"]);
+        // Midpoints 0.60 + 0.32 + 0.10 = 1.02.
+        assert_eq!(odds.total(), "1.02".parse().unwrap());
+        assert_eq!(odds.overround(), "0.02".parse().unwrap());
+        assert_eq!(
+            odds.normalized(&odds.quotes[0]).unwrap().round_dp(4),
+            "0.5882".parse().unwrap()
+        );
+        assert_eq!(odds.ask_total(), Some("1.07".parse().unwrap()));
+        assert_eq!(signed_percent(odds.overround()), "+2%");
+
+        let one_sided = EventOdds::new("Who wins?".into(), vec![odds_quote("A", "0.5", "")]);
+        assert_eq!(one_sided.total(), Decimal::ZERO);
+        assert_eq!(one_sided.ask_total(), None);
+        assert_eq!(one_sided.normalized(&one_sided.quotes[0]), None);
+    }
+
     fn make_event(val: serde_json::Value) -> Event {
         serde_json::from_value(val).unwrap()
     }
@@ -234,6 +572,33 @@ mod tests {
         assert_eq!(event_to_row(&e).title.chars().count(), 60);
     }
 
+    fn make_market(val: serde_json::Value) -> Market {
+        serde_json::from_value(val).unwrap()
+    }
+
+    #[test]
+    fn outcome_prices_pairs_names_with_cents() {
+        let m = make_market(json!({
+            "id": "1",
+            "outcomes": "[\"Yes\", \"No\"]",
+            "outcomePrices": "[\"0.62\", \"0.38\"]"
+        }));
+        assert_eq!(outcome_prices(&m), "Yes 62.0¢ · No 38.0¢");
+        assert_eq!(outcome_prices(&make_market(json!({"id": "2"}))), "—");
+    }
+
+    #[test]
+    fn aggregate_volume_sums_markets() {
+        let markets = vec![
+            make_market(json!({"id": "1", "volumeNum": 1000, "volume24hr": 10})),
+            make_market(json!({"id": "2", "volumeNum": 500})),
+        ];
+        assert_eq!(
+            aggregate_volume(&markets),
+            (Decimal::from(1500), Decimal::from(10))
+        );
+    }
+
     #[test]
     fn row_formats_volume() {
         let e = make_event(json!({"id": "1", "volume": "2500000"}));
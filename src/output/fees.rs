@@ -0,0 +1,139 @@
+use polymarket_client_sdk::types::{Decimal, U256};
+use serde_json::json;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+use super::{OutputFormat, format_price_with, print_csv, truncate};
+
+/// The order the fee column is worked out for.
+pub struct FeeEstimate {
+    pub price: Decimal,
+    pub size: Decimal,
+}
+
+impl FeeEstimate {
+    /// One share at 50¢, where the fee is highest.
+    pub fn worst_case() -> Self {
+        Self {
+            price: Decimal::new(5, 1),
+            size: Decimal::ONE,
+        }
+    }
+}
+
+pub struct FeeRate {
+    pub market: String,
+    pub outcome: String,
+    pub token_id: U256,
+    pub fee_rate_bps: u32,
+    /// USDC value of the fee on the estimated order.
+    pub fee: Decimal,
+}
+
+/// A rate in basis points with its percentage, e.g. "200 bps (2%)".
+pub fn format_fee_rate(bps: u32) -> String {
+    format!(
+        "{bps} bps ({}%)",
+        Decimal::new(i64::from(bps), 2).normalize()
+    )
+}
+
+pub fn print_fees(
+    rates: &[FeeRate],
+    estimate: &FeeEstimate,
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "Market")]
+                market: String,
+                #[tabled(rename = "Outcome")]
+                outcome: String,
+                #[tabled(rename = "Taker Fee Rate")]
+                rate: String,
+                #[tabled(rename = "Fee")]
+                fee: String,
+            }
+            let rows: Vec<Row> = rates
+                .iter()
+                .map(|r| Row {
+                    market: truncate(&r.market, 50),
+                    outcome: r.outcome.clone(),
+                    rate: format_fee_rate(r.fee_rate_bps),
+                    fee: format!("${}", r.fee.round_dp(4)),
+                })
+                .collect();
+            println!(
+                "Fee on {} shares at {}, if filled as taker",
+                estimate.size.normalize(),
+                format_price_with(estimate.price, |p| p.normalize().to_string())
+            );
+            println!("{}", Table::new(rows).with(Style::rounded()));
+        }
+        OutputFormat::Json => {
+            let rates: Vec<_> = rates
+                .iter()
+                .map(|r| {
+                    json!({
+                        "market": r.market,
+                        "outcome": r.outcome,
+                        "token_id": r.token_id.to_string(),
+                        "fee_rate_bps": r.fee_rate_bps,
+                        "price": estimate.price.to_string(),
+                        "size": estimate.size.to_string(),
+                        "fee": r.fee.to_string(),
+                    })
+                })
+                .collect();
+            super::print_json(&rates)?;
+        }
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = rates
+                .iter()
+                .map(|r| {
+                    vec![
+                        r.market.clone(),
+                        r.outcome.clone(),
+                        r.token_id.to_string(),
+                        r.fee_rate_bps.to_string(),
+                        estimate.price.to_string(),
+                        estimate.size.to_string(),
+                        r.fee.to_string(),
+                    ]
+                })
+                .collect();
+            print_csv(
+                &[
+                    "market",
+                    "outcome",
+                    "token_id",
+                    "fee_rate_bps",
+                    "price",
+                    "size",
+                    "fee",
+                ],
+                &rows,
+            );
+        }
+        OutputFormat::Plain => {
+            for r in rates {
+                println!("{}\t{}", r.outcome, r.fee_rate_bps);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fee_rate_shows_bps_and_percent() {
+        assert_eq!(format_fee_rate(200), "200 bps (2%)");
+        assert_eq!(format_fee_rate(25), "25 bps (0.25%)");
+        assert_eq!(format_fee_rate(0), "0 bps (0%)");
+    }
+}
@@ -0,0 +1,47 @@
+use anyhow::Result;
+use polymarket_client_sdk::types::Decimal;
+use serde_json::json;
+
+use super::{OutputFormat, print_detail_table};
+
+/// Fees per unit of gas in gwei.
+pub struct GasPrices {
+    pub block: u64,
+    pub base_fee: Decimal,
+    pub priority_fee: Decimal,
+    /// The max fee a transaction would offer.
+    pub max_fee: Decimal,
+    /// `eth_gasPrice`, for legacy transactions.
+    pub gas_price: Decimal,
+    /// POL for a USDC transfer at the base fee plus the priority fee.
+    pub transfer_cost: Decimal,
+}
+
+pub fn print_gas_prices(prices: &GasPrices, output: &OutputFormat) -> Result<()> {
+    match output {
+        OutputFormat::Plain => println!("{}", prices.max_fee.round_dp(2)),
+        OutputFormat::Json => super::print_json(&json!({
+            "block": prices.block,
+            "base_fee_gwei": prices.base_fee.to_string(),
+            "priority_fee_gwei": prices.priority_fee.to_string(),
+            "max_fee_gwei": prices.max_fee.to_string(),
+            "gas_price_gwei": prices.gas_price.to_string(),
+            "transfer_cost_pol": prices.transfer_cost.to_string(),
+        }))?,
+        OutputFormat::Table | OutputFormat::Csv => {
+            let gwei = |d: Decimal| format!("{} gwei", d.round_dp(2));
+            print_detail_table(vec![
+                ["Block".into(), prices.block.to_string()],
+                ["Base fee".into(), gwei(prices.base_fee)],
+                ["Priority fee".into(), gwei(prices.priority_fee)],
+                ["Max fee".into(), gwei(prices.max_fee)],
+                ["Gas price (legacy)".into(), gwei(prices.gas_price)],
+                [
+                    "USDC transfer".into(),
+                    format!("~{} POL", prices.transfer_cost.round_dp(6)),
+                ],
+            ]);
+        }
+    }
+    Ok(())
+}
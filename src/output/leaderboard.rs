@@ -0,0 +1,160 @@
+use polymarket_client_sdk::data::types::response::{Position, TraderLeaderboardEntry};
+use polymarket_client_sdk::types::{Address, Decimal};
+use serde_json::json;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+use super::data::{POSITION_FIELDS, position_json, print_positions};
+use super::portfolio::format_pnl;
+use super::style;
+use super::{OutputFormat, csv_opt, format_decimal, print_csv, print_toned_detail_table, truncate};
+
+const LEADERBOARD_FIELDS: [&str; 5] = ["rank", "proxy_wallet", "user_name", "pnl", "volume"];
+
+pub fn print_leaderboard(
+    entries: &[TraderLeaderboardEntry],
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            if entries.is_empty() {
+                println!("No traders found.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "#")]
+                rank: i32,
+                #[tabled(rename = "Trader")]
+                trader: String,
+                #[tabled(rename = "Wallet")]
+                wallet: String,
+                #[tabled(rename = "Profit")]
+                pnl: String,
+                #[tabled(rename = "Volume")]
+                volume: String,
+            }
+            let rows: Vec<Row> = entries
+                .iter()
+                .map(|e| Row {
+                    rank: e.rank,
+                    trader: truncate(e.user_name.as_deref().unwrap_or("—"), 24),
+                    wallet: e.proxy_wallet.to_string(),
+                    pnl: format_pnl(e.pnl),
+                    volume: format_decimal(e.vol),
+                })
+                .collect();
+            let mut table = Table::new(rows);
+            table.with(Style::rounded());
+            style::color_column(&mut table, 3, 1, entries.iter().map(|e| style::sign(e.pnl)));
+            println!("{table}");
+        }
+        OutputFormat::Json => {
+            let data: Vec<_> = entries
+                .iter()
+                .map(|e| {
+                    json!({
+                        "rank": e.rank,
+                        "proxy_wallet": e.proxy_wallet.to_string(),
+                        "user_name": e.user_name,
+                        "pnl": e.pnl.to_string(),
+                        "volume": e.vol.to_string(),
+                    })
+                })
+                .collect();
+            super::print_json(&data)?;
+        }
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = entries
+                .iter()
+                .map(|e| {
+                    vec![
+                        e.rank.to_string(),
+                        e.proxy_wallet.to_string(),
+                        csv_opt(e.user_name.as_deref()),
+                        e.pnl.to_string(),
+                        e.vol.to_string(),
+                    ]
+                })
+                .collect();
+            print_csv(&LEADERBOARD_FIELDS, &rows);
+        }
+    }
+    Ok(())
+}
+
+/// A trader's public record over one leaderboard period, and what they
+/// hold now.
+pub struct TraderProfile {
+    pub address: Address,
+    pub period: &'static str,
+    pub user_name: Option<String>,
+    /// Rank by profit, or `None` when the trader isn't on the leaderboard.
+    pub pnl_rank: Option<i32>,
+    pub volume_rank: Option<i32>,
+    pub pnl: Decimal,
+    pub volume: Decimal,
+    pub positions_value: Decimal,
+    pub markets_traded: i32,
+    /// Largest open positions first.
+    pub positions: Vec<Position>,
+}
+
+fn rank(r: Option<i32>) -> String {
+    r.map_or_else(|| "Unranked".into(), |r| format!("#{r}"))
+}
+
+pub fn print_trader(p: &TraderProfile, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            let rows = vec![
+                ["Trader".into(), p.user_name.clone().unwrap_or("—".into())],
+                ["Wallet".into(), p.address.to_string()],
+                ["Period".into(), p.period.into()],
+                ["Profit".into(), format_pnl(p.pnl)],
+                ["Profit Rank".into(), rank(p.pnl_rank)],
+                ["Volume".into(), format_decimal(p.volume)],
+                ["Volume Rank".into(), rank(p.volume_rank)],
+                ["Positions Value".into(), format_decimal(p.positions_value)],
+                ["Markets Traded".into(), p.markets_traded.to_string()],
+            ];
+            let mut tones = vec![None; rows.len()];
+            tones[3] = style::sign(p.pnl);
+            print_toned_detail_table(rows, &tones);
+            println!();
+            println!("Largest positions:");
+            print_positions(&p.positions, output)?;
+        }
+        OutputFormat::Json => {
+            let positions: Vec<_> = p.positions.iter().map(position_json).collect();
+            super::print_json(&json!({
+                "address": p.address.to_string(),
+                "period": p.period,
+                "user_name": p.user_name,
+                "pnl": p.pnl.to_string(),
+                "pnl_rank": p.pnl_rank,
+                "volume": p.volume.to_string(),
+                "volume_rank": p.volume_rank,
+                "positions_value": p.positions_value.to_string(),
+                "markets_traded": p.markets_traded,
+                "positions": positions,
+            }))?;
+        }
+        OutputFormat::Csv => {
+            let data: Vec<_> = p.positions.iter().map(position_json).collect();
+            super::print_csv_objects(&POSITION_FIELDS, &data);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rank_shows_unranked_traders() {
+        assert_eq!(rank(Some(3)), "#3");
+        assert_eq!(rank(None), "Unranked");
+    }
+}
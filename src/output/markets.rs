@@ -1,9 +1,18 @@
+use chrono::DateTime;
+use polymarket_client_sdk::data::types::Side;
+use polymarket_client_sdk::data::types::response::{Holder, Trade};
 use polymarket_client_sdk::gamma::types::response::Market;
 use polymarket_client_sdk::types::Decimal;
-use tabled::settings::Style;
+use tabled::settings::object::Columns;
+use tabled::settings::{Modify, Style, Width};
 use tabled::{Table, Tabled};
 
-use super::{detail_field, format_decimal, print_detail_table, truncate};
+use super::style;
+use super::time::{format_datetime, iso_from_unix};
+use super::{
+    OutputFormat, csv_opt, detail_field, format_decimal, format_number, format_price_with,
+    print_csv, print_detail_table, truncate,
+};
 
 #[derive(Tabled)]
 struct MarketRow {
@@ -19,7 +28,7 @@ struct MarketRow {
     status: String,
 }
 
-fn market_status(m: &Market) -> &'static str {
+pub(crate) fn market_status(m: &Market) -> &'static str {
     if m.closed == Some(true) {
         "Closed"
     } else if m.active == Some(true) {
@@ -29,13 +38,21 @@ fn market_status(m: &Market) -> &'static str {
     }
 }
 
+/// Width each market's column wraps at in `markets compare`.
+const COMPARE_WIDTH: usize = 30;
+
+/// A price in cents, as market lists show it.
+pub(super) fn cents(price: Decimal) -> String {
+    format!("{:.2}¢", price * Decimal::from(100))
+}
+
 fn market_to_row(m: &Market) -> MarketRow {
     let question = m.question.as_deref().unwrap_or("—");
     let price_yes = m
         .outcome_prices
         .as_ref()
         .and_then(|p| p.first())
-        .map_or_else(|| "—".into(), |p| format!("{:.2}¢", p * Decimal::from(100)));
+        .map_or_else(|| "—".into(), |p| format_price_with(*p, cents));
 
     MarketRow {
         question: truncate(question, 60),
@@ -56,32 +73,292 @@ pub fn print_markets_table(markets: &[Market]) {
     println!("{table}");
 }
 
+fn yes_price(m: &Market) -> Option<Decimal> {
+    m.outcome_prices.as_ref().and_then(|p| p.first()).copied()
+}
+
+pub fn print_markets_csv(markets: &[Market]) {
+    let rows: Vec<Vec<String>> = markets
+        .iter()
+        .map(|m| {
+            vec![
+                m.id.clone(),
+                csv_opt(m.slug.as_ref()),
+                csv_opt(m.question.as_ref()),
+                csv_opt(m.condition_id.as_ref()),
+                csv_opt(yes_price(m)),
+                csv_opt(m.volume_num),
+                csv_opt(m.liquidity_num),
+                market_status(m).into(),
+            ]
+        })
+        .collect();
+    print_csv(
+        &[
+            "id",
+            "slug",
+            "question",
+            "condition_id",
+            "price_yes",
+            "volume",
+            "liquidity",
+            "status",
+        ],
+        &rows,
+    );
+}
+
+/// A price change in cents with its sign, e.g. `+4.5¢`.
+fn format_change(change: Decimal) -> String {
+    let cents = (change * Decimal::from(100)).round_dp(1);
+    if cents.is_sign_positive() && !cents.is_zero() {
+        format!("+{cents:.1}¢")
+    } else {
+        format!("{cents:.1}¢")
+    }
+}
+
+const TOP_FIELDS: [&str; 9] = [
+    "rank",
+    "id",
+    "slug",
+    "question",
+    "condition_id",
+    "price_yes",
+    "change_24h",
+    "volume_24h",
+    "liquidity",
+];
+
+fn top_market_json(rank: usize, m: &Market) -> serde_json::Value {
+    serde_json::json!({
+        "rank": rank,
+        "id": m.id,
+        "slug": m.slug,
+        "question": m.question,
+        "condition_id": m.condition_id,
+        "price_yes": yes_price(m).map(|d| d.to_string()),
+        "change_24h": m.one_day_price_change.map(|d| d.to_string()),
+        "volume_24h": m.volume_24hr.map(|d| d.to_string()),
+        "liquidity": m.liquidity_num.map(|d| d.to_string()),
+    })
+}
+
+/// `markets top`: the markets in rank order, with the numbers they are
+/// ranked by.
+pub fn print_top_markets(markets: &[Market], output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            if markets.is_empty() {
+                println!("No markets found.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "#")]
+                rank: usize,
+                #[tabled(rename = "Question")]
+                question: String,
+                #[tabled(rename = "Price (Yes)")]
+                price_yes: String,
+                #[tabled(rename = "24h Change")]
+                change: String,
+                #[tabled(rename = "24h Volume")]
+                volume: String,
+                #[tabled(rename = "Liquidity")]
+                liquidity: String,
+            }
+            let rows: Vec<Row> = markets
+                .iter()
+                .enumerate()
+                .map(|(i, m)| Row {
+                    rank: i + 1,
+                    question: truncate(m.question.as_deref().unwrap_or("—"), 50),
+                    price_yes: yes_price(m)
+                        .map_or_else(|| "—".into(), |p| format_price_with(p, cents)),
+                    change: m
+                        .one_day_price_change
+                        .map_or_else(|| "—".into(), format_change),
+                    volume: m.volume_24hr.map_or_else(|| "—".into(), format_decimal),
+                    liquidity: m.liquidity_num.map_or_else(|| "—".into(), format_decimal),
+                })
+                .collect();
+            let mut table = Table::new(rows);
+            table.with(Style::rounded());
+            style::color_column(
+                &mut table,
+                3,
+                1,
+                markets
+                    .iter()
+                    .map(|m| m.one_day_price_change.and_then(style::sign)),
+            );
+            println!("{table}");
+        }
+        OutputFormat::Json => {
+            let data: Vec<_> = markets
+                .iter()
+                .enumerate()
+                .map(|(i, m)| top_market_json(i + 1, m))
+                .collect();
+            super::print_json(&data)?;
+        }
+        OutputFormat::Csv => {
+            let data: Vec<_> = markets
+                .iter()
+                .enumerate()
+                .map(|(i, m)| top_market_json(i + 1, m))
+                .collect();
+            super::print_csv_objects(&TOP_FIELDS, &data);
+        }
+    }
+    Ok(())
+}
+
+const COMPARE_FIELDS: [&str; 13] = [
+    "id",
+    "slug",
+    "question",
+    "price_yes",
+    "best_bid",
+    "best_ask",
+    "spread",
+    "last_trade_price",
+    "change_24h",
+    "volume",
+    "volume_24h",
+    "liquidity",
+    "end_date",
+];
+
+fn compare_json(m: &Market) -> serde_json::Value {
+    let prices: serde_json::Map<String, serde_json::Value> = m
+        .outcomes
+        .iter()
+        .flatten()
+        .zip(m.outcome_prices.iter().flatten())
+        .map(|(outcome, price)| (outcome.clone(), price.to_string().into()))
+        .collect();
+    serde_json::json!({
+        "id": m.id,
+        "slug": m.slug,
+        "question": m.question,
+        "prices": prices,
+        "price_yes": yes_price(m).map(|d| d.to_string()),
+        "best_bid": m.best_bid.map(|d| d.to_string()),
+        "best_ask": m.best_ask.map(|d| d.to_string()),
+        "spread": m.spread.map(|d| d.to_string()),
+        "last_trade_price": m.last_trade_price.map(|d| d.to_string()),
+        "change_24h": m.one_day_price_change.map(|d| d.to_string()),
+        "volume": m.volume_num.map(|d| d.to_string()),
+        "volume_24h": m.volume_24hr.map(|d| d.to_string()),
+        "liquidity": m.liquidity_num.map(|d| d.to_string()),
+        "end_date": m.end_date.map(|d| d.to_rfc3339()),
+        "status": market_status(m),
+    })
+}
+
+/// The comparison table: a row per field, a column per market.
+fn comparison_rows(markets: &[Market]) -> Vec<Vec<String>> {
+    let dash = || "—".to_string();
+    let four =
+        |v: Option<Decimal>| v.map_or_else(dash, |v| format_price_with(v, |v| format!("{v:.4}")));
+    let row = |label: &str, value: &dyn Fn(&Market) -> String| -> Vec<String> {
+        std::iter::once(label.to_string())
+            .chain(markets.iter().map(value))
+            .collect()
+    };
+    vec![
+        row("Market", &|m| m.question.clone().unwrap_or_else(dash)),
+        row("Prices", &|m| {
+            let prices: Vec<String> = m
+                .outcomes
+                .iter()
+                .flatten()
+                .zip(m.outcome_prices.iter().flatten())
+                .map(|(outcome, price)| format!("{outcome} {}", format_price_with(*price, cents)))
+                .collect();
+            if prices.is_empty() {
+                dash()
+            } else {
+                prices.join("\n")
+            }
+        }),
+        row("Best Bid", &|m| four(m.best_bid)),
+        row("Best Ask", &|m| four(m.best_ask)),
+        row("Spread", &|m| {
+            m.spread.map_or_else(dash, |v| format!("{v:.4}"))
+        }),
+        row("Last Trade", &|m| four(m.last_trade_price)),
+        row("24h Change", &|m| {
+            m.one_day_price_change.map_or_else(dash, format_change)
+        }),
+        row("Volume", &|m| {
+            m.volume_num.map_or_else(dash, format_decimal)
+        }),
+        row("Volume (24hr)", &|m| {
+            m.volume_24hr.map_or_else(dash, format_decimal)
+        }),
+        row("Liquidity", &|m| {
+            m.liquidity_num.map_or_else(dash, format_decimal)
+        }),
+        row("Ends", &|m| {
+            m.end_date.map_or_else(dash, super::time::format_date)
+        }),
+        row("Status", &|m| market_status(m).to_string()),
+    ]
+}
+
+/// `markets compare`: the markets side by side in table output, one row
+/// each in JSON and CSV.
+pub fn print_market_comparison(markets: &[Market], output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            let table = Table::from_iter(comparison_rows(markets))
+                .with(Style::rounded())
+                .with(Modify::new(Columns::new(1..)).with(Width::wrap(COMPARE_WIDTH)))
+                .to_string();
+            println!("{table}");
+        }
+        OutputFormat::Json => {
+            let data: Vec<_> = markets.iter().map(compare_json).collect();
+            super::print_json(&data)?;
+        }
+        OutputFormat::Csv => {
+            let data: Vec<_> = markets.iter().map(compare_json).collect();
+            super::print_csv_objects(&COMPARE_FIELDS, &data);
+        }
+    }
+    Ok(())
+}
+
+/// One row per outcome: "Outcome: Yes" → "0.6500  token 1234…".
+fn outcome_rows(m: &Market) -> Vec<[String; 2]> {
+    let outcomes = m.outcomes.as_deref().unwrap_or_default();
+    let prices = m.outcome_prices.as_deref().unwrap_or_default();
+    let tokens = m.clob_token_ids.as_deref().unwrap_or_default();
+
+    outcomes
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let price = prices
+                .get(i)
+                .map(|p| format_price_with(*p, |p| format!("{p:.4}")));
+            let token = tokens.get(i).map(|t| format!("token {t}"));
+            let detail = [price, token].into_iter().flatten().collect::<Vec<_>>();
+            [format!("Outcome: {name}"), detail.join("  ")]
+        })
+        .collect()
+}
+
 pub fn print_market_detail(m: &Market) {
     let mut rows: Vec<[String; 2]> = Vec::new();
 
     detail_field!(rows, "ID", m.id.clone());
     detail_field!(rows, "Question", m.question.clone().unwrap_or_default());
     detail_field!(rows, "Slug", m.slug.clone().unwrap_or_default());
-    detail_field!(
-        rows,
-        "Outcomes",
-        m.outcomes
-            .as_ref()
-            .map(|o| o.join(", "))
-            .unwrap_or_default()
-    );
-    detail_field!(
-        rows,
-        "Prices",
-        m.outcome_prices
-            .as_ref()
-            .map(|p| p
-                .iter()
-                .map(|v| format!("{v:.4}"))
-                .collect::<Vec<_>>()
-                .join(", "))
-            .unwrap_or_default()
-    );
+    rows.extend(outcome_rows(m));
     detail_field!(
         rows,
         "Volume",
@@ -100,12 +377,16 @@ pub fn print_market_detail(m: &Market) {
     detail_field!(
         rows,
         "Best Bid",
-        m.best_bid.map(|v| format!("{v:.4}")).unwrap_or_default()
+        m.best_bid
+            .map(|v| format_price_with(v, |v| format!("{v:.4}")))
+            .unwrap_or_default()
     );
     detail_field!(
         rows,
         "Best Ask",
-        m.best_ask.map(|v| format!("{v:.4}")).unwrap_or_default()
+        m.best_ask
+            .map(|v| format_price_with(v, |v| format!("{v:.4}")))
+            .unwrap_or_default()
     );
     detail_field!(
         rows,
@@ -125,18 +406,6 @@ pub fn print_market_detail(m: &Market) {
         "Condition ID",
         m.condition_id.map(|c| format!("{c}")).unwrap_or_default()
     );
-    detail_field!(
-        rows,
-        "CLOB Token IDs",
-        m.clob_token_ids
-            .as_ref()
-            .map(|ids| ids
-                .iter()
-                .map(|id| format!("{id}"))
-                .collect::<Vec<_>>()
-                .join(", "))
-            .unwrap_or_default()
-    );
     detail_field!(
         rows,
         "Start Date",
@@ -161,6 +430,394 @@ pub fn print_market_detail(m: &Market) {
     print_detail_table(rows);
 }
 
+/// A `markets search` result with its relevance score.
+pub struct SearchHit {
+    pub score: f64,
+    pub market: Market,
+}
+
+pub fn print_search_results(hits: &[SearchHit], output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            if hits.is_empty() {
+                println!("No markets found.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "#")]
+                rank: usize,
+                #[tabled(rename = "ID")]
+                id: String,
+                #[tabled(rename = "Slug")]
+                slug: String,
+                #[tabled(rename = "Question")]
+                question: String,
+                #[tabled(rename = "Price (Yes)")]
+                price_yes: String,
+                #[tabled(rename = "Status")]
+                status: String,
+            }
+            let rows: Vec<Row> = hits
+                .iter()
+                .enumerate()
+                .map(|(i, h)| {
+                    let row = market_to_row(&h.market);
+                    Row {
+                        rank: i + 1,
+                        id: h.market.id.clone(),
+                        slug: truncate(h.market.slug.as_deref().unwrap_or("—"), 40),
+                        question: truncate(h.market.question.as_deref().unwrap_or("—"), 50),
+                        price_yes: row.price_yes,
+                        status: row.status,
+                    }
+                })
+                .collect();
+            let table = Table::new(rows).with(Style::rounded()).to_string();
+            println!("{table}");
+        }
+        OutputFormat::Json => {
+            let data: Vec<_> = hits
+                .iter()
+                .enumerate()
+                .map(|(i, h)| {
+                    let m = &h.market;
+                    serde_json::json!({
+                        "rank": i + 1,
+                        "score": (h.score * 1000.0).round() / 1000.0,
+                        "id": m.id,
+                        "slug": m.slug,
+                        "condition_id": m.condition_id,
+                        "question": m.question,
+                        "outcomes": m.outcomes,
+                        "outcome_prices": m.outcome_prices,
+                        "clob_token_ids": m.clob_token_ids,
+                        "volume_24hr": m.volume_24hr,
+                        "active": m.active,
+                        "closed": m.closed,
+                    })
+                })
+                .collect();
+            super::print_json(&data)?;
+        }
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = hits
+                .iter()
+                .enumerate()
+                .map(|(i, h)| {
+                    let m = &h.market;
+                    vec![
+                        (i + 1).to_string(),
+                        format!("{:.3}", h.score),
+                        m.id.clone(),
+                        csv_opt(m.slug.as_ref()),
+                        csv_opt(m.question.as_ref()),
+                        csv_opt(yes_price(m)),
+                        csv_opt(m.volume_24hr),
+                        market_status(m).into(),
+                    ]
+                })
+                .collect();
+            print_csv(
+                &[
+                    "rank",
+                    "score",
+                    "id",
+                    "slug",
+                    "question",
+                    "price_yes",
+                    "volume_24hr",
+                    "status",
+                ],
+                &rows,
+            );
+        }
+    }
+    Ok(())
+}
+
+/// What a market resolves by: its rules as Gamma has them, and the
+/// question the resolution adapter sent to UMA.
+pub struct MarketSource {
+    pub market_id: String,
+    pub question: String,
+    pub question_id: Option<String>,
+    /// The adapter that resolves the market.
+    pub resolver: Option<String>,
+    pub rules: Option<String>,
+    pub resolution_source: Option<String>,
+    /// Links in the resolution source and the rules, in order.
+    pub links: Vec<String>,
+    /// The UMA ancillary data, or why it couldn't be read.
+    pub ancillary_data: Result<String, String>,
+}
+
+fn print_section(title: &str, body: &str) {
+    println!();
+    println!("{title}:");
+    println!("{}", body.trim());
+}
+
+pub fn print_market_source(s: &MarketSource, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Csv => {
+            println!("{}", s.question);
+            let mut about = format!("Market {}", s.market_id);
+            if let Some(resolver) = &s.resolver {
+                about.push_str(&format!(", resolved by {resolver}"));
+            }
+            println!("{about}");
+            print_section("Rules", s.rules.as_deref().unwrap_or("None given."));
+            if let Some(source) = &s.resolution_source {
+                print_section("Resolution source", source);
+            }
+            if !s.links.is_empty() {
+                print_section("Links", &s.links.join("\n"));
+            }
+            match &s.ancillary_data {
+                Ok(data) => print_section("UMA ancillary data", data),
+                Err(e) => print_section("UMA ancillary data", &format!("Unavailable: {e}")),
+            }
+        }
+        OutputFormat::Plain => {
+            if let Some(rules) = &s.rules {
+                println!("{}", rules.trim());
+            }
+        }
+        OutputFormat::Json => {
+            let (ancillary_data, ancillary_data_error) = match &s.ancillary_data {
+                Ok(data) => (Some(data.as_str()), None),
+                Err(e) => (None, Some(e.as_str())),
+            };
+            super::print_json(&serde_json::json!({
+                "market_id": s.market_id,
+                "question": s.question,
+                "question_id": s.question_id,
+                "resolver": s.resolver,
+                "rules": s.rules,
+                "resolution_source": s.resolution_source,
+                "links": s.links,
+                "ancillary_data": ancillary_data,
+                "ancillary_data_error": ancillary_data_error,
+            }))?;
+        }
+    }
+    Ok(())
+}
+
+/// The largest holders of one of a market's outcomes.
+pub struct OutcomeHolders {
+    pub outcome: String,
+    pub token_id: String,
+    pub holders: Vec<Holder>,
+}
+
+fn holder_name(name: Option<&str>, pseudonym: Option<&str>) -> String {
+    name.or(pseudonym).unwrap_or("—").to_string()
+}
+
+const HOLDER_FIELDS: [&str; 7] = [
+    "outcome",
+    "rank",
+    "proxy_wallet",
+    "name",
+    "pseudonym",
+    "amount",
+    "token_id",
+];
+
+fn holder_json(o: &OutcomeHolders, rank: usize, h: &Holder) -> serde_json::Value {
+    serde_json::json!({
+        "outcome": o.outcome,
+        "rank": rank,
+        "proxy_wallet": h.proxy_wallet.to_string(),
+        "name": h.name,
+        "pseudonym": h.pseudonym,
+        "amount": h.amount.to_string(),
+        "token_id": o.token_id,
+    })
+}
+
+pub fn print_market_holders(
+    question: &str,
+    outcomes: &[OutcomeHolders],
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            println!("{question}");
+            if outcomes.iter().all(|o| o.holders.is_empty()) {
+                println!("No holders found.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "#")]
+                rank: usize,
+                #[tabled(rename = "Wallet")]
+                wallet: String,
+                #[tabled(rename = "Name")]
+                name: String,
+                #[tabled(rename = "Shares")]
+                shares: String,
+            }
+            for o in outcomes.iter().filter(|o| !o.holders.is_empty()) {
+                let rows: Vec<Row> = o
+                    .holders
+                    .iter()
+                    .enumerate()
+                    .map(|(i, h)| Row {
+                        rank: i + 1,
+                        wallet: h.proxy_wallet.to_string(),
+                        name: truncate(&holder_name(h.name.as_deref(), h.pseudonym.as_deref()), 24),
+                        shares: format_number(h.amount),
+                    })
+                    .collect();
+                println!();
+                println!("{}:", o.outcome);
+                println!("{}", Table::new(rows).with(Style::rounded()));
+            }
+        }
+        OutputFormat::Json => {
+            let data: Vec<_> = outcomes
+                .iter()
+                .map(|o| {
+                    let holders: Vec<_> = o
+                        .holders
+                        .iter()
+                        .enumerate()
+                        .map(|(i, h)| holder_json(o, i + 1, h))
+                        .collect();
+                    serde_json::json!({"outcome": o.outcome, "token_id": o.token_id, "holders": holders})
+                })
+                .collect();
+            super::print_json(&data)?;
+        }
+        OutputFormat::Csv => {
+            let data: Vec<_> = outcomes
+                .iter()
+                .flat_map(|o| {
+                    o.holders
+                        .iter()
+                        .enumerate()
+                        .map(move |(i, h)| holder_json(o, i + 1, h))
+                })
+                .collect();
+            super::print_csv_objects(&HOLDER_FIELDS, &data);
+        }
+    }
+    Ok(())
+}
+
+/// What a trade cost or raised, in USDC.
+fn trade_value(t: &Trade) -> Decimal {
+    t.size * t.price
+}
+
+const MARKET_TRADE_FIELDS: [&str; 12] = [
+    "time",
+    "timestamp",
+    "proxy_wallet",
+    "name",
+    "pseudonym",
+    "side",
+    "outcome",
+    "outcome_index",
+    "size",
+    "price",
+    "value",
+    "transaction_hash",
+];
+
+fn market_trade_json(t: &Trade) -> serde_json::Value {
+    serde_json::json!({
+        "time": iso_from_unix(t.timestamp),
+        "timestamp": t.timestamp,
+        "proxy_wallet": t.proxy_wallet.to_string(),
+        "name": t.name,
+        "pseudonym": t.pseudonym,
+        "side": t.side.to_string(),
+        "outcome": t.outcome,
+        "outcome_index": t.outcome_index,
+        "size": t.size.to_string(),
+        "price": t.price.to_string(),
+        "value": trade_value(t).to_string(),
+        "transaction_hash": t.transaction_hash.to_string(),
+    })
+}
+
+pub fn print_market_activity(
+    question: &str,
+    trades: &[Trade],
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            println!("{question}");
+            if trades.is_empty() {
+                println!("No trades found.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "Time")]
+                time: String,
+                #[tabled(rename = "Trader")]
+                trader: String,
+                #[tabled(rename = "Side")]
+                side: String,
+                #[tabled(rename = "Outcome")]
+                outcome: String,
+                #[tabled(rename = "Shares")]
+                shares: String,
+                #[tabled(rename = "Price")]
+                price: String,
+                #[tabled(rename = "Value")]
+                value: String,
+            }
+            let rows: Vec<Row> = trades
+                .iter()
+                .map(|t| Row {
+                    time: DateTime::from_timestamp(t.timestamp, 0)
+                        .map_or_else(|| "—".into(), format_datetime),
+                    trader: match t.name.as_deref().or(t.pseudonym.as_deref()) {
+                        Some(name) => truncate(name, 24),
+                        None => truncate(&t.proxy_wallet.to_string(), 14),
+                    },
+                    side: t.side.to_string(),
+                    outcome: t.outcome.clone(),
+                    shares: format_number(t.size),
+                    price: format_price_with(t.price, |p| format!("{p:.4}")),
+                    value: format_decimal(trade_value(t)),
+                })
+                .collect();
+            let mut table = Table::new(rows);
+            table.with(Style::rounded());
+            style::color_column(
+                &mut table,
+                2,
+                1,
+                trades.iter().map(|t| match t.side {
+                    Side::Buy => Some(style::Tone::Up),
+                    Side::Sell => Some(style::Tone::Down),
+                    _ => None,
+                }),
+            );
+            println!();
+            println!("{table}");
+        }
+        OutputFormat::Json => {
+            let data: Vec<_> = trades.iter().map(market_trade_json).collect();
+            super::print_json(&data)?;
+        }
+        OutputFormat::Csv => {
+            let data: Vec<_> = trades.iter().map(market_trade_json).collect();
+            super::print_csv_objects(&MARKET_TRADE_FIELDS, &data);
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -213,6 +870,25 @@ mod tests {
         assert_eq!(market_to_row(&m).price_yes, "65.00¢");
     }
 
+    #[test]
+    fn comparison_puts_each_market_in_a_column() {
+        let a = make_market(json!({
+            "id": "1",
+            "question": "BTC above 100k by June?",
+            "outcomes": "[\"Yes\",\"No\"]",
+            "outcomePrices": "[\"0.4\",\"0.6\"]",
+            "endDate": "2025-06-30T12:00:00Z"
+        }));
+        let b = make_market(json!({"id": "2", "question": "BTC above 100k by December?"}));
+        let rows = comparison_rows(&[a, b]);
+        assert!(rows.iter().all(|r| r.len() == 3));
+        assert_eq!(rows[0][2], "BTC above 100k by December?");
+        assert_eq!(rows[1][1], "Yes 40.00¢\nNo 60.00¢");
+        assert_eq!(rows[1][2], "—");
+        let ends = rows.iter().find(|r| r[0] == "Ends").unwrap();
+        assert_eq!(ends[1], "2025-06-30");
+    }
+
     #[test]
     fn row_truncates_long_question() {
         let long_q = "a".repeat(100);
@@ -229,6 +905,33 @@ mod tests {
         assert_eq!(row.liquidity, "$2.5K");
     }
 
+    #[test]
+    fn outcome_rows_pair_prices_and_tokens() {
+        let m = make_market(json!({
+            "id": "1",
+            "outcomes": "[\"Yes\",\"No\"]",
+            "outcomePrices": "[\"0.65\",\"0.35\"]",
+            "clobTokenIds": "[\"111\",\"222\"]"
+        }));
+        let rows = outcome_rows(&m);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[0],
+            ["Outcome: Yes".to_string(), "0.6500  token 111".into()]
+        );
+        assert_eq!(
+            rows[1],
+            ["Outcome: No".to_string(), "0.3500  token 222".into()]
+        );
+    }
+
+    #[test]
+    fn outcome_rows_tolerate_missing_tokens() {
+        let m = make_market(json!({"id": "1", "outcomes": "[\"Yes\",\"No\"]"}));
+        let rows = outcome_rows(&m);
+        assert_eq!(rows[0][1], "");
+    }
+
     #[test]
     fn row_propagates_status() {
         let m = make_market(json!({"id": "1", "active": true}));
@@ -1,26 +1,184 @@
+pub mod alert;
 pub mod approve;
+pub mod auth;
+pub mod backtest;
+pub mod balance;
+pub mod book;
 pub mod bridge;
 pub mod clob;
 pub mod comments;
+pub mod config;
+pub mod contacts;
+pub mod convert;
+pub mod copy;
 pub mod ctf;
+pub mod daemon;
 pub mod data;
+pub mod db;
+pub mod doctor;
 pub mod events;
+pub mod fees;
+pub mod gas;
+pub mod leaderboard;
 pub mod markets;
+pub mod order;
+pub mod paper;
+pub mod portfolio;
 pub mod profiles;
+pub mod quote;
+pub mod record;
+pub mod resolution;
+pub mod rewards;
+pub mod scan;
 pub mod series;
+pub mod size;
 pub mod sports;
+pub mod state;
+pub mod style;
+pub mod sync;
 pub mod tags;
+pub mod tax;
+pub mod time;
+pub mod transfer;
+pub mod tx;
+pub mod watch;
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use polymarket_client_sdk::types::Decimal;
 use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
 use tabled::Table;
 use tabled::settings::object::Columns;
 use tabled::settings::{Modify, Style, Width};
 
-#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+use crate::odds::OddsFormat;
+
+/// How results are printed. `Csv` applies to list commands and `Plain` to
+/// commands with one essential value (an order ID, an address, a price);
+/// other commands print their table instead.
+#[derive(Clone, Copy, Debug, PartialEq, clap::ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum OutputFormat {
     Table,
     Json,
+    Csv,
+    Plain,
+}
+
+/// Whether CSV output starts with a header row (`--no-header` clears it).
+static CSV_HEADER: AtomicBool = AtomicBool::new(true);
+
+pub fn set_csv_header(enabled: bool) {
+    CSV_HEADER.store(enabled, Ordering::Relaxed);
+}
+
+pub fn csv_header() -> bool {
+    CSV_HEADER.load(Ordering::Relaxed)
+}
+
+/// Whether output shows exact values (`--raw`): numbers in tables aren't
+/// rounded or abbreviated, and `stream` prints frames as they came.
+static RAW: AtomicBool = AtomicBool::new(false);
+
+pub fn set_raw(enabled: bool) {
+    RAW.store(enabled, Ordering::Relaxed);
+}
+
+pub fn raw() -> bool {
+    RAW.load(Ordering::Relaxed)
+}
+
+/// Format of the command being run, once resolved from the flag, env var
+/// and profile, so its error is reported the same way.
+static FORMAT: Mutex<OutputFormat> = Mutex::new(OutputFormat::Table);
+
+pub fn set_format(format: OutputFormat) {
+    *FORMAT.lock().unwrap_or_else(|e| e.into_inner()) = format;
+}
+
+pub fn format() -> OutputFormat {
+    *FORMAT.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// How table output shows prices (`--odds-format`).
+static ODDS_FORMAT: Mutex<OddsFormat> = Mutex::new(OddsFormat::Price);
+
+pub fn set_odds_format(format: OddsFormat) {
+    *ODDS_FORMAT.lock().unwrap_or_else(|e| e.into_inner()) = format;
+}
+
+pub fn odds_format() -> OddsFormat {
+    *ODDS_FORMAT.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Fields JSON output is cut down to (`--fields`), each a dotted path.
+static FIELDS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+pub fn set_fields(fields: Vec<String>) {
+    *FIELDS.lock().unwrap_or_else(|e| e.into_inner()) = fields;
+}
+
+fn fields() -> Vec<String> {
+    FIELDS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Keeps only `fields` of `value`, each a dotted path such as `a.b`. Arrays
+/// are cut down element by element, and a missing field comes out as null,
+/// so every object has the same keys.
+fn project(value: &serde_json::Value, fields: &[Vec<&str>]) -> serde_json::Value {
+    use serde_json::Value;
+    match value {
+        Value::Array(items) => Value::Array(items.iter().map(|v| project(v, fields)).collect()),
+        Value::Object(map) => {
+            let mut out = serde_json::Map::new();
+            for field in fields {
+                let key = field[0];
+                if out.contains_key(key) {
+                    continue;
+                }
+                let rest: Vec<Vec<&str>> = fields
+                    .iter()
+                    .filter(|f| f[0] == key)
+                    .map(|f| f[1..].to_vec())
+                    .collect();
+                let value = map.get(key).unwrap_or(&Value::Null);
+                let value = if rest.iter().any(Vec::is_empty) {
+                    value.clone()
+                } else {
+                    project(value, &rest)
+                };
+                out.insert(key.to_string(), value);
+            }
+            Value::Object(out)
+        }
+        other => other.clone(),
+    }
+}
+
+/// `value` cut down to `--fields`, when given.
+fn projected(value: serde_json::Value) -> serde_json::Value {
+    let fields = fields();
+    if fields.is_empty() {
+        return value;
+    }
+    let paths: Vec<Vec<&str>> = fields.iter().map(|f| f.split('.').collect()).collect();
+    project(&value, &paths)
+}
+
+/// A price for table output, in the `--odds-format` convention.
+pub fn format_price(price: Decimal) -> String {
+    format_price_with(price, |p| p.to_string())
+}
+
+/// [`format_price`], with `as_price` showing it when the convention is the
+/// price itself (e.g. in cents, or to a fixed number of places).
+pub fn format_price_with(price: Decimal, as_price: impl FnOnce(Decimal) -> String) -> String {
+    match odds_format() {
+        OddsFormat::Price => as_price(price),
+        format => crate::odds::format(price, format),
+    }
 }
 
 pub fn truncate(s: &str, max: usize) -> String {
@@ -32,7 +190,16 @@ pub fn truncate(s: &str, max: usize) -> String {
     truncated
 }
 
+/// A USDC amount such as a volume or liquidity, abbreviated: $1.2M, $3.4K,
+/// $56.78. The exact amount with `--raw`.
 pub fn format_decimal(n: Decimal) -> String {
+    if raw() {
+        return n.normalize().to_string();
+    }
+    abbreviated(n)
+}
+
+fn abbreviated(n: Decimal) -> String {
     let f = n.to_f64().unwrap_or(0.0);
     if f >= 1_000_000.0 {
         format!("${:.1}M", f / 1_000_000.0)
@@ -43,20 +210,113 @@ pub fn format_decimal(n: Decimal) -> String {
     }
 }
 
+/// A number of shares for table output, grouped in thousands and rounded to
+/// two decimals: 1,234,567.89. The exact number with `--raw`.
+pub fn format_number(n: Decimal) -> String {
+    if raw() {
+        return n.normalize().to_string();
+    }
+    grouped(n.round_dp(2).normalize())
+}
+
+fn grouped(n: Decimal) -> String {
+    let s = n.abs().to_string();
+    let (whole, fraction) = s
+        .split_once('.')
+        .map_or((s.as_str(), None), |(w, f)| (w, Some(f)));
+    let mut out = String::new();
+    if n.is_sign_negative() && !n.is_zero() {
+        out.push('-');
+    }
+    for (i, digit) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        out.push('.');
+        out.push_str(fraction);
+    }
+    out
+}
+
 pub fn print_json(data: &impl serde::Serialize) -> anyhow::Result<()> {
-    println!("{}", serde_json::to_string_pretty(data)?);
+    let value = projected(serde_json::to_value(data)?);
+    println!("{}", serde_json::to_string_pretty(&value)?);
     Ok(())
 }
 
+/// Prints `value` as JSON on one line, for output that goes on printing (a
+/// stream, a watch) or a short status.
+pub fn print_json_line(value: serde_json::Value) {
+    println!("{}", projected(value));
+}
+
 pub fn print_detail_table(rows: Vec<[String; 2]>) {
-    let table = Table::from_iter(rows)
+    print_toned_detail_table(rows, &[]);
+}
+
+/// [`print_detail_table`] with values colored, one tone per row.
+pub fn print_toned_detail_table(rows: Vec<[String; 2]>, tones: &[Option<style::Tone>]) {
+    let mut table = Table::from_iter(rows);
+    table
         .with(Style::rounded())
         .with(Modify::new(Columns::first()).with(Width::wrap(20)))
-        .with(Modify::new(Columns::last()).with(Width::wrap(80)))
-        .to_string();
+        .with(Modify::new(Columns::last()).with(Width::wrap(80)));
+    style::color_column(&mut table, 1, 0, tones.iter().copied());
     println!("{table}");
 }
 
+/// Quotes a CSV field if it contains a delimiter, quote or newline.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn csv_lines(headers: &[&str], rows: &[Vec<String>], header: bool) -> Vec<String> {
+    let header = header.then(|| headers.join(","));
+    header
+        .into_iter()
+        .chain(rows.iter().map(|row| {
+            let fields: Vec<String> = row.iter().map(|f| csv_field(f)).collect();
+            fields.join(",")
+        }))
+        .collect()
+}
+
+pub fn print_csv(headers: &[&str], rows: &[Vec<String>]) {
+    for line in csv_lines(headers, rows, csv_header()) {
+        println!("{line}");
+    }
+}
+
+/// Prints flat JSON objects as CSV, one column per field in `fields`, so a
+/// list's CSV columns match the keys of its JSON output.
+pub fn print_csv_objects(fields: &[&str], objects: &[serde_json::Value]) {
+    let rows: Vec<Vec<String>> = objects
+        .iter()
+        .map(|o| fields.iter().map(|f| json_csv_field(&o[*f])).collect())
+        .collect();
+    print_csv(fields, &rows);
+}
+
+fn json_csv_field(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// An optional value as a CSV field: empty when absent.
+pub fn csv_opt(value: Option<impl ToString>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
 macro_rules! detail_field {
     ($rows:expr, $label:expr, $val:expr) => {
         $rows.push([$label.into(), $val]);
@@ -69,6 +329,65 @@ pub(crate) use detail_field;
 mod tests {
     use super::*;
     use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    #[test]
+    fn project_keeps_dotted_paths_through_arrays() {
+        let value = json!([
+            {"address": "0x1", "balances": {"usdc": "5", "matic": "1"}, "extra": true},
+            {"address": "0x2"},
+        ]);
+        let fields = [vec!["address"], vec!["balances", "usdc"]];
+        assert_eq!(
+            project(&value, &fields),
+            json!([
+                {"address": "0x1", "balances": {"usdc": "5"}},
+                {"address": "0x2", "balances": null},
+            ])
+        );
+        // A whole object wins over a path into it.
+        let fields = [vec!["balances", "usdc"], vec!["balances"]];
+        assert_eq!(
+            project(&value[0], &fields),
+            json!({"balances": {"usdc": "5", "matic": "1"}})
+        );
+        assert_eq!(project(&json!("0.5"), &fields), json!("0.5"));
+    }
+
+    #[test]
+    fn csv_field_plain_unquoted() {
+        assert_eq!(csv_field("0.52"), "0.52");
+    }
+
+    #[test]
+    fn csv_field_quotes_delimiters_and_quotes() {
+        assert_eq!(csv_field("Yes, or no"), "\"Yes, or no\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn csv_lines_with_and_without_header() {
+        let rows = vec![vec!["1".to_string(), "a, b".to_string()]];
+        assert_eq!(
+            csv_lines(&["id", "name"], &rows, true),
+            ["id,name", "1,\"a, b\""]
+        );
+        assert_eq!(csv_lines(&["id", "name"], &rows, false), ["1,\"a, b\""]);
+    }
+
+    #[test]
+    fn json_csv_field_unquotes_strings_and_blanks_nulls() {
+        assert_eq!(json_csv_field(&serde_json::json!("0.52")), "0.52");
+        assert_eq!(json_csv_field(&serde_json::json!(12)), "12");
+        assert_eq!(json_csv_field(&serde_json::json!(true)), "true");
+        assert_eq!(json_csv_field(&serde_json::Value::Null), "");
+    }
+
+    #[test]
+    fn csv_opt_empty_when_missing() {
+        assert_eq!(csv_opt(Some(dec!(0.5))), "0.5");
+        assert_eq!(csv_opt(None::<Decimal>), "");
+    }
 
     #[test]
     fn truncate_shorter_than_max_unchanged() {
@@ -106,6 +425,15 @@ mod tests {
         assert_eq!(truncate("café!", 3), "ca\u{2026}");
     }
 
+    #[test]
+    fn grouped_inserts_thousands_separators() {
+        assert_eq!(grouped(dec!(1234567.89)), "1,234,567.89");
+        assert_eq!(grouped(dec!(123456)), "123,456");
+        assert_eq!(grouped(dec!(999)), "999");
+        assert_eq!(grouped(dec!(-1000.5)), "-1,000.5");
+        assert_eq!(grouped(dec!(0.25)), "0.25");
+    }
+
     #[test]
     fn format_decimal_millions() {
         assert_eq!(format_decimal(dec!(1_500_000)), "$1.5M");
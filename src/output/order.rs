@@ -0,0 +1,368 @@
+use polymarket_client_sdk::clob::types::Side;
+use polymarket_client_sdk::clob::types::response::OpenOrderResponse;
+use polymarket_client_sdk::types::Decimal;
+use serde_json::json;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+use super::{OutputFormat, csv_opt, format_price, print_csv, print_detail_table, truncate};
+use crate::commands::batch::BatchOrder;
+use crate::config::TwapJob;
+
+/// `total` in its units: USDC for a buy, shares for a sell.
+fn amount(side: Side, d: Decimal) -> String {
+    if side == Side::Sell {
+        format!("{} shares", d.normalize())
+    } else {
+        format!("${d:.2}")
+    }
+}
+
+fn percent(job: &TwapJob) -> Decimal {
+    (job.filled() / job.total * Decimal::ONE_HUNDRED).round_dp(0)
+}
+
+fn avg(job: &TwapJob) -> String {
+    job.avg_price()
+        .map_or_else(|| "—".into(), |p| p.round_dp(4).normalize().to_string())
+}
+
+/// `secs` as e.g. `2m 24s`.
+fn every(secs: u64) -> String {
+    match (secs / 60, secs % 60) {
+        (0, s) => format!("{s}s"),
+        (m, 0) => format!("{m}m"),
+        (m, s) => format!("{m}m {s}s"),
+    }
+}
+
+fn job_to_json(job: &TwapJob) -> serde_json::Value {
+    json!({
+        "id": job.id,
+        "token_id": job.token_id,
+        "side": job.side.to_string(),
+        "total": job.total.to_string(),
+        "filled": job.filled().to_string(),
+        "remaining": job.remaining().to_string(),
+        "filled_shares": job.filled_shares.to_string(),
+        "filled_usdc": job.filled_usdc.to_string(),
+        "avg_price": job.avg_price().map(|p| p.round_dp(6).to_string()),
+        "slices_sent": job.sent,
+        "slices": job.slices,
+        "done": job.is_done(),
+        "started_at": job.started_at.to_rfc3339(),
+    })
+}
+
+/// What a new or resumed job will do. Status goes to stderr so stdout
+/// carries only the slices.
+pub fn print_twap_plan(job: &TwapJob) {
+    eprintln!(
+        "TWAP #{}: {} {} in {} slices of up to {}, one every {} (±{}%). \
+         Ctrl-C pauses it.",
+        job.id,
+        job.side.to_string().to_lowercase(),
+        amount(job.side, job.remaining()),
+        job.slices - job.sent,
+        amount(job.side, job.slice),
+        every(job.interval_secs),
+        (job.jitter * Decimal::ONE_HUNDRED).normalize(),
+    );
+}
+
+pub fn print_twap_paused(job: &TwapJob) {
+    eprintln!(
+        "Paused TWAP #{} after {} of {} slices. Resume with `polymarket order twap resume {}`.",
+        job.id, job.sent, job.slices, job.id
+    );
+}
+
+/// One slice as it fills: a line per slice, NDJSON for `--output json`.
+pub fn print_twap_slice(
+    job: &TwapJob,
+    shares: Decimal,
+    usdc: Decimal,
+    error: Option<&str>,
+    output: &OutputFormat,
+) {
+    let now = chrono::Local::now();
+    match output {
+        OutputFormat::Json => {
+            let mut line = job_to_json(job);
+            line["time"] = json!(now.to_rfc3339());
+            line["slice"] = json!(job.sent);
+            line["slice_shares"] = json!(shares.to_string());
+            line["slice_usdc"] = json!(usdc.to_string());
+            line["error"] = json!(error);
+            super::print_json_line(line);
+        }
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+            let fill = match error {
+                Some(e) => format!("not filled: {e}"),
+                None if shares.is_zero() => "not filled".into(),
+                None => format!(
+                    "{} {} shares for ${usdc:.2} at {}",
+                    if job.side == Side::Sell {
+                        "sold"
+                    } else {
+                        "bought"
+                    },
+                    shares.normalize(),
+                    (usdc / shares).round_dp(4).normalize()
+                ),
+            };
+            println!(
+                "{}  slice {}/{}  {fill}  ·  {} of {} ({}%)  ·  avg {}",
+                super::time::format_clock(now),
+                job.sent,
+                job.slices,
+                amount(job.side, job.filled()),
+                amount(job.side, job.total),
+                percent(job),
+                avg(job),
+            );
+        }
+    }
+}
+
+pub fn print_twap_done(job: &TwapJob, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Json => super::print_json(&job_to_json(job))?,
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+            let verb = if job.side == Side::Sell {
+                "sold"
+            } else {
+                "bought"
+            };
+            println!(
+                "TWAP #{} finished: {verb} {} shares for ${:.2}, avg price {}.",
+                job.id,
+                job.filled_shares.normalize(),
+                job.filled_usdc,
+                avg(job),
+            );
+            if !job.remaining().is_zero() {
+                println!("{} was not filled.", amount(job.side, job.remaining()));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn print_twap_jobs(jobs: &[TwapJob], output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            if jobs.is_empty() {
+                println!("No TWAP jobs.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "ID")]
+                id: u32,
+                #[tabled(rename = "Token")]
+                token: String,
+                #[tabled(rename = "Side")]
+                side: String,
+                #[tabled(rename = "Filled")]
+                filled: String,
+                #[tabled(rename = "Slices")]
+                slices: String,
+                #[tabled(rename = "Avg Price")]
+                avg: String,
+                #[tabled(rename = "Status")]
+                status: String,
+            }
+            let rows: Vec<Row> = jobs
+                .iter()
+                .map(|j| Row {
+                    id: j.id,
+                    token: truncate(&j.token_id, 14),
+                    side: j.side.to_string(),
+                    filled: format!(
+                        "{} of {} ({}%)",
+                        amount(j.side, j.filled()),
+                        amount(j.side, j.total),
+                        percent(j)
+                    ),
+                    slices: format!("{}/{}", j.sent, j.slices),
+                    avg: avg(j),
+                    status: if j.is_done() { "done" } else { "paused" }.into(),
+                })
+                .collect();
+            println!("{}", Table::new(rows).with(Style::rounded()));
+        }
+        OutputFormat::Json => {
+            let jobs: Vec<_> = jobs.iter().map(job_to_json).collect();
+            super::print_json(&jobs)?;
+        }
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = jobs
+                .iter()
+                .map(|j| {
+                    vec![
+                        j.id.to_string(),
+                        j.token_id.clone(),
+                        j.side.to_string(),
+                        j.total.to_string(),
+                        j.filled().to_string(),
+                        j.filled_shares.to_string(),
+                        j.filled_usdc.to_string(),
+                        csv_opt(j.avg_price().map(|p| p.round_dp(6))),
+                        j.sent.to_string(),
+                        j.slices.to_string(),
+                        j.is_done().to_string(),
+                    ]
+                })
+                .collect();
+            print_csv(
+                &[
+                    "id",
+                    "token_id",
+                    "side",
+                    "total",
+                    "filled",
+                    "filled_shares",
+                    "filled_usdc",
+                    "avg_price",
+                    "slices_sent",
+                    "slices",
+                    "done",
+                ],
+                &rows,
+            );
+        }
+    }
+    Ok(())
+}
+
+/// What `order amend` will change, shown before the old order is cancelled.
+pub fn format_amend_preview(old: &OpenOrderResponse, price: Decimal, size: Decimal) -> String {
+    let remaining = old.original_size - old.size_matched;
+    format!(
+        "Amend {} order {}\n  Price:  {} -> {}\n  Size:   {} -> {} shares\n  \
+         The order is cancelled and placed again, behind others at its price.",
+        old.side,
+        old.id,
+        format_price(old.price),
+        format_price(price),
+        remaining.normalize(),
+        size.normalize(),
+    )
+}
+
+pub struct AmendResult {
+    pub old_id: String,
+    pub new_id: String,
+    pub side: Side,
+    pub old_price: Decimal,
+    /// What was left unfilled of the old order.
+    pub old_size: Decimal,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub status: String,
+    pub error: Option<String>,
+}
+
+pub fn print_amend(result: &AmendResult, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Plain => println!("{}", result.new_id),
+        OutputFormat::Json => super::print_json(&json!({
+            "cancelled_order_id": result.old_id,
+            "order_id": result.new_id,
+            "side": result.side.to_string(),
+            "old_price": result.old_price.to_string(),
+            "old_size": result.old_size.normalize().to_string(),
+            "price": result.price.to_string(),
+            "size": result.size.normalize().to_string(),
+            "status": result.status,
+            "error": result.error,
+        }))?,
+        OutputFormat::Table | OutputFormat::Csv => {
+            let mut rows = vec![
+                ["Cancelled".into(), result.old_id.clone()],
+                ["New Order".into(), result.new_id.clone()],
+                ["Side".into(), result.side.to_string()],
+                [
+                    "Price".into(),
+                    format!(
+                        "{} -> {}",
+                        format_price(result.old_price),
+                        format_price(result.price)
+                    ),
+                ],
+                [
+                    "Size".into(),
+                    format!(
+                        "{} -> {}",
+                        result.old_size.normalize(),
+                        result.size.normalize()
+                    ),
+                ],
+                ["Status".into(), result.status.clone()],
+            ];
+            if let Some(error) = &result.error {
+                rows.push(["Error".into(), error.clone()]);
+            }
+            print_detail_table(rows);
+        }
+    }
+    Ok(())
+}
+
+/// The orders `order ladder` is about to place, with what they cost (a buy)
+/// or bring in (a sell) if they all fill.
+pub fn format_ladder(orders: &[BatchOrder]) -> String {
+    #[derive(Tabled)]
+    struct Row {
+        #[tabled(rename = "Level")]
+        level: usize,
+        #[tabled(rename = "Side")]
+        side: String,
+        #[tabled(rename = "Price")]
+        price: String,
+        #[tabled(rename = "Size")]
+        size: String,
+        #[tabled(rename = "Value")]
+        value: String,
+    }
+    let rows: Vec<Row> = orders
+        .iter()
+        .enumerate()
+        .map(|(i, o)| Row {
+            level: i + 1,
+            side: o.side.to_string(),
+            price: format_price(o.price),
+            size: super::format_number(o.size),
+            value: format!("${:.2}", o.price * o.size),
+        })
+        .collect();
+    let shares: Decimal = orders.iter().map(|o| o.size).sum();
+    let value: Decimal = orders.iter().map(|o| o.price * o.size).sum();
+    let table = Table::new(rows).with(Style::rounded()).to_string();
+    let Some(first) = orders.first() else {
+        return table;
+    };
+    format!(
+        "{table}\n  Token:  {}\n  Total:  {} shares, ${value:.2} USDC {}",
+        first.token,
+        shares.normalize(),
+        if first.side == Side::Sell {
+            "if all sell"
+        } else {
+            "if all buy"
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_reads_naturally() {
+        assert_eq!(every(45), "45s");
+        assert_eq!(every(120), "2m");
+        assert_eq!(every(144), "2m 24s");
+    }
+}
@@ -0,0 +1,312 @@
+use polymarket_client_sdk::clob::types::Side;
+use polymarket_client_sdk::types::Decimal;
+use serde_json::json;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+use super::portfolio::format_pnl;
+use super::style::{color_column, sign};
+use super::{
+    OutputFormat, csv_opt, format_decimal, format_number, format_price, format_price_with,
+    print_csv, print_toned_detail_table, truncate,
+};
+use crate::paper::{Fill, PaperOrder, Placed};
+
+/// A paper position marked to the current midpoint.
+pub struct PaperPosition {
+    pub token_id: String,
+    pub size: Decimal,
+    pub avg_price: Decimal,
+    pub midpoint: Option<Decimal>,
+    pub value: Decimal,
+    pub pnl: Decimal,
+}
+
+pub struct PaperSummary {
+    pub balance: Decimal,
+    pub starting_balance: Decimal,
+    pub positions: Vec<PaperPosition>,
+    pub open_orders: usize,
+    pub equity: Decimal,
+    pub pnl: Decimal,
+}
+
+pub fn print_paper_placed(placed: &Placed, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Plain => println!("{}", placed.order_id),
+        OutputFormat::Table | OutputFormat::Csv => {
+            let verb = if placed.side == Side::Buy {
+                "paid"
+            } else {
+                "received"
+            };
+            match placed.avg_price {
+                Some(avg) => println!(
+                    "Paper order {}: {} {} shares at avg {avg:.4} ({verb} {} USDC)",
+                    placed.order_id,
+                    placed.side,
+                    placed.filled,
+                    placed.value.round_dp(2)
+                ),
+                None => println!("Paper order {}: nothing filled yet", placed.order_id),
+            }
+            if !placed.resting.is_zero() {
+                println!("  {} shares resting on the paper book", placed.resting);
+            }
+        }
+        OutputFormat::Json => super::print_json(placed)?,
+    }
+    Ok(())
+}
+
+pub fn print_paper_orders(orders: &[PaperOrder], output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            if orders.is_empty() {
+                println!("No open paper orders.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "ID")]
+                id: String,
+                #[tabled(rename = "Token")]
+                token: String,
+                #[tabled(rename = "Side")]
+                side: String,
+                #[tabled(rename = "Price")]
+                price: String,
+                #[tabled(rename = "Size")]
+                size: String,
+                #[tabled(rename = "Filled")]
+                filled: String,
+                #[tabled(rename = "Type")]
+                order_type: String,
+            }
+            let rows: Vec<Row> = orders
+                .iter()
+                .map(|o| Row {
+                    id: o.id.clone(),
+                    token: truncate(&o.token_id, 20),
+                    side: o.side.to_string(),
+                    price: format_price(o.price),
+                    size: format_number(o.size),
+                    filled: o.filled.to_string(),
+                    order_type: o.order_type.to_string(),
+                })
+                .collect();
+            let table = Table::new(rows).with(Style::rounded()).to_string();
+            println!("{table}");
+        }
+        OutputFormat::Json => super::print_json(&orders)?,
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = orders
+                .iter()
+                .map(|o| {
+                    vec![
+                        o.id.clone(),
+                        o.token_id.clone(),
+                        o.side.to_string(),
+                        o.price.to_string(),
+                        o.size.to_string(),
+                        o.filled.to_string(),
+                        o.order_type.to_string(),
+                        o.created_at.to_rfc3339(),
+                    ]
+                })
+                .collect();
+            print_csv(
+                &[
+                    "id",
+                    "token_id",
+                    "side",
+                    "price",
+                    "size",
+                    "filled",
+                    "order_type",
+                    "created_at",
+                ],
+                &rows,
+            );
+        }
+        OutputFormat::Plain => {
+            for o in orders {
+                println!("{}", o.id);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn print_paper_summary(s: &PaperSummary, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            let rows = vec![
+                ["Mode".into(), "paper".into()],
+                ["USDC Balance".into(), format_decimal(s.balance)],
+                [
+                    "Starting Balance".into(),
+                    format_decimal(s.starting_balance),
+                ],
+                ["Positions".into(), s.positions.len().to_string()],
+                ["Open Orders".into(), s.open_orders.to_string()],
+                ["Total Value".into(), format_decimal(s.equity)],
+                ["PnL".into(), format_pnl(s.pnl)],
+            ];
+            let mut tones = vec![None; rows.len()];
+            tones[6] = sign(s.pnl);
+            print_toned_detail_table(rows, &tones);
+            if s.positions.is_empty() {
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "Token")]
+                token: String,
+                #[tabled(rename = "Shares")]
+                size: String,
+                #[tabled(rename = "Avg Price")]
+                avg_price: String,
+                #[tabled(rename = "Midpoint")]
+                midpoint: String,
+                #[tabled(rename = "Value")]
+                value: String,
+                #[tabled(rename = "PnL")]
+                pnl: String,
+            }
+            let rows: Vec<Row> = s
+                .positions
+                .iter()
+                .map(|p| Row {
+                    token: truncate(&p.token_id, 20),
+                    size: format_number(p.size),
+                    avg_price: format_price_with(p.avg_price, |p| format!("{p:.4}")),
+                    midpoint: p.midpoint.map_or_else(|| "—".into(), format_price),
+                    value: format_decimal(p.value),
+                    pnl: format_pnl(p.pnl),
+                })
+                .collect();
+            let mut table = Table::new(rows);
+            table.with(Style::rounded());
+            color_column(&mut table, 5, 1, s.positions.iter().map(|p| sign(p.pnl)));
+            println!("{table}");
+        }
+        OutputFormat::Json => {
+            let positions: Vec<_> = s
+                .positions
+                .iter()
+                .map(|p| {
+                    json!({
+                        "token_id": p.token_id,
+                        "size": p.size.to_string(),
+                        "avg_price": p.avg_price.to_string(),
+                        "midpoint": p.midpoint.map(|m| m.to_string()),
+                        "value": p.value.to_string(),
+                        "pnl": p.pnl.to_string(),
+                    })
+                })
+                .collect();
+            super::print_json(&json!({
+                "mode": "paper",
+                "usdc_balance": s.balance.to_string(),
+                "starting_balance": s.starting_balance.to_string(),
+                "open_orders": s.open_orders,
+                "total_value": s.equity.to_string(),
+                "pnl": s.pnl.to_string(),
+                "positions": positions,
+            }))?;
+        }
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = s
+                .positions
+                .iter()
+                .map(|p| {
+                    vec![
+                        p.token_id.clone(),
+                        p.size.to_string(),
+                        p.avg_price.to_string(),
+                        csv_opt(p.midpoint),
+                        p.value.to_string(),
+                        p.pnl.to_string(),
+                    ]
+                })
+                .collect();
+            print_csv(
+                &["token_id", "size", "avg_price", "midpoint", "value", "pnl"],
+                &rows,
+            );
+        }
+    }
+    Ok(())
+}
+
+pub fn print_paper_fills(fills: &[Fill], output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            if fills.is_empty() {
+                println!("No paper fills yet.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "Time")]
+                time: String,
+                #[tabled(rename = "Order")]
+                order: String,
+                #[tabled(rename = "Token")]
+                token: String,
+                #[tabled(rename = "Side")]
+                side: String,
+                #[tabled(rename = "Price")]
+                price: String,
+                #[tabled(rename = "Size")]
+                size: String,
+            }
+            let rows: Vec<Row> = fills
+                .iter()
+                .map(|f| Row {
+                    time: super::time::format_timestamp(f.timestamp),
+                    order: f.order_id.clone(),
+                    token: truncate(&f.token_id, 20),
+                    side: f.side.to_string(),
+                    price: format_price(f.price),
+                    size: format_number(f.size),
+                })
+                .collect();
+            let table = Table::new(rows).with(Style::rounded()).to_string();
+            println!("{table}");
+        }
+        OutputFormat::Json => super::print_json(&fills)?,
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = fills
+                .iter()
+                .map(|f| {
+                    vec![
+                        f.timestamp.to_rfc3339(),
+                        f.order_id.clone(),
+                        f.token_id.clone(),
+                        f.side.to_string(),
+                        f.price.to_string(),
+                        f.size.to_string(),
+                    ]
+                })
+                .collect();
+            print_csv(
+                &["timestamp", "order_id", "token_id", "side", "price", "size"],
+                &rows,
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Resting orders that filled since the last command, reported on stderr so
+/// they don't mix with the command's own output.
+pub fn report_resting_fills(fills: &[Fill]) {
+    for f in fills {
+        eprintln!(
+            "Paper order {} filled: {} {} at {}",
+            f.order_id, f.side, f.size, f.price
+        );
+    }
+}
@@ -0,0 +1,318 @@
+use chrono::{DateTime, Utc};
+use polymarket_client_sdk::clob::types::OrderType;
+use polymarket_client_sdk::types::{Address, Decimal, U256};
+use rust_decimal::prelude::ToPrimitive;
+use serde_json::json;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+use super::style::sign;
+use super::{
+    OutputFormat, format_decimal, format_number, format_price, print_csv, print_toned_detail_table,
+    truncate,
+};
+
+/// Size of the `portfolio history` chart, in characters.
+const CHART_WIDTH: usize = 60;
+const CHART_HEIGHT: usize = 12;
+
+pub struct PortfolioSummary {
+    pub address: Address,
+    pub usdc_balance: Decimal,
+    pub positions: usize,
+    pub positions_value: Decimal,
+    pub unrealized_pnl: Decimal,
+    pub realized_pnl: Decimal,
+    pub open_orders: usize,
+    pub buy_order_exposure: Decimal,
+    pub sell_order_exposure: Decimal,
+    pub total_value: Decimal,
+}
+
+pub fn format_pnl(v: Decimal) -> String {
+    if v.is_sign_negative() {
+        format!("-{}", format_decimal(-v))
+    } else {
+        format!("+{}", format_decimal(v))
+    }
+}
+
+pub fn print_portfolio(s: &PortfolioSummary, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+            let rows = vec![
+                ["Address".into(), s.address.to_string()],
+                ["USDC Balance".into(), format_decimal(s.usdc_balance)],
+                ["Positions".into(), s.positions.to_string()],
+                ["Positions Value".into(), format_decimal(s.positions_value)],
+                ["Total Value".into(), format_decimal(s.total_value)],
+                ["Unrealized PnL".into(), format_pnl(s.unrealized_pnl)],
+                ["Realized PnL".into(), format_pnl(s.realized_pnl)],
+                ["Open Orders".into(), s.open_orders.to_string()],
+                ["Buy Exposure".into(), format_decimal(s.buy_order_exposure)],
+                [
+                    "Sell Exposure".into(),
+                    format_decimal(s.sell_order_exposure),
+                ],
+            ];
+            let mut tones = vec![None; rows.len()];
+            tones[5] = sign(s.unrealized_pnl);
+            tones[6] = sign(s.realized_pnl);
+            print_toned_detail_table(rows, &tones);
+        }
+        OutputFormat::Json => {
+            super::print_json(&json!({
+                "address": s.address.to_string(),
+                "usdc_balance": s.usdc_balance.to_string(),
+                "positions": s.positions,
+                "positions_value": s.positions_value.to_string(),
+                "total_value": s.total_value.to_string(),
+                "unrealized_pnl": s.unrealized_pnl.to_string(),
+                "realized_pnl": s.realized_pnl.to_string(),
+                "open_orders": s.open_orders,
+                "buy_order_exposure": s.buy_order_exposure.to_string(),
+                "sell_order_exposure": s.sell_order_exposure.to_string(),
+            }))?;
+        }
+    }
+    Ok(())
+}
+
+/// A position `positions close` is about to sell, and what it should fetch.
+pub struct PositionExit {
+    pub token: U256,
+    pub title: String,
+    pub outcome: String,
+    pub shares: Decimal,
+    /// The sell order's limit price.
+    pub price: Decimal,
+    /// Shares the book takes right away at that price.
+    pub filling: Decimal,
+    /// USDC those shares bring in, before fees.
+    pub proceeds: Decimal,
+}
+
+/// The sell orders `positions close` will submit and their expected proceeds,
+/// shown before asking to go ahead.
+pub fn format_close_preview(exits: &[PositionExit], order_type: &OrderType) -> String {
+    #[derive(Tabled)]
+    struct Row {
+        #[tabled(rename = "Market")]
+        market: String,
+        #[tabled(rename = "Outcome")]
+        outcome: String,
+        #[tabled(rename = "Shares")]
+        shares: String,
+        #[tabled(rename = "Price")]
+        price: String,
+        #[tabled(rename = "Fills Now")]
+        filling: String,
+        #[tabled(rename = "Proceeds")]
+        proceeds: String,
+    }
+    let rows: Vec<Row> = exits
+        .iter()
+        .map(|e| Row {
+            market: truncate(&e.title, 40),
+            outcome: e.outcome.clone(),
+            shares: format_number(e.shares),
+            price: format_price(e.price),
+            filling: format_number(e.filling),
+            proceeds: format_decimal(e.proceeds),
+        })
+        .collect();
+    let total: Decimal = exits.iter().map(|e| e.proceeds).sum();
+    let resting: Decimal = exits.iter().map(|e| e.shares - e.filling).sum();
+    let mut text = format!(
+        "{}\n{} {order_type} sell orders, expected proceeds {} before fees",
+        Table::new(rows).with(Style::rounded()),
+        exits.len(),
+        format_decimal(total),
+    );
+    if resting > Decimal::ZERO {
+        let unfilled = if *order_type == OrderType::GTC {
+            "rest on the book"
+        } else {
+            "go unfilled"
+        };
+        text.push_str(&format!(
+            "\n{} shares aren't bid for at these prices and would {unfilled}",
+            format_number(resting)
+        ));
+    }
+    text
+}
+
+/// The portfolio at one point of `portfolio history`, in USDC.
+pub struct HistoryPoint {
+    pub time: DateTime<Utc>,
+    /// Shares held, marked at the price then.
+    pub positions_value: Decimal,
+    pub cash: Decimal,
+    pub total_value: Decimal,
+    /// What trading has made so far: USDC in from activity plus what's held.
+    pub pnl: Decimal,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HistoryChart {
+    Value,
+    Pnl,
+}
+
+pub fn print_history(
+    points: &[HistoryPoint],
+    chart: HistoryChart,
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            let (Some(first), Some(last)) = (points.first(), points.last()) else {
+                return Ok(());
+            };
+            let (title, values): (&str, Vec<f64>) = match chart {
+                HistoryChart::Value => (
+                    "Portfolio value",
+                    points.iter().map(|p| to_f64(p.total_value)).collect(),
+                ),
+                HistoryChart::Pnl => ("PnL", points.iter().map(|p| to_f64(p.pnl)).collect()),
+            };
+            println!("{title}, {} to {}", day(first.time), day(last.time));
+            println!("{}", ascii_chart(&values, CHART_WIDTH, CHART_HEIGHT));
+            print_toned_detail_table(
+                vec![
+                    [
+                        "Value".into(),
+                        format!(
+                            "{} -> {}",
+                            format_decimal(first.total_value),
+                            format_decimal(last.total_value)
+                        ),
+                    ],
+                    ["Positions".into(), format_decimal(last.positions_value)],
+                    ["Cash".into(), format_decimal(last.cash)],
+                    ["PnL".into(), format_pnl(last.pnl)],
+                    ["PnL over period".into(), format_pnl(last.pnl - first.pnl)],
+                ],
+                &[None, None, None, sign(last.pnl), sign(last.pnl - first.pnl)],
+            );
+        }
+        OutputFormat::Plain => {
+            for p in points {
+                println!("{}\t{}", p.time.to_rfc3339(), p.total_value.round_dp(2));
+            }
+        }
+        OutputFormat::Json => {
+            let rows: Vec<_> = points
+                .iter()
+                .map(|p| {
+                    json!({
+                        "time": p.time.to_rfc3339(),
+                        "positions_value": p.positions_value.round_dp(6).to_string(),
+                        "cash": p.cash.round_dp(6).to_string(),
+                        "total_value": p.total_value.round_dp(6).to_string(),
+                        "pnl": p.pnl.round_dp(6).to_string(),
+                    })
+                })
+                .collect();
+            super::print_json(&rows)?;
+        }
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = points
+                .iter()
+                .map(|p| {
+                    vec![
+                        p.time.to_rfc3339(),
+                        p.positions_value.round_dp(6).to_string(),
+                        p.cash.round_dp(6).to_string(),
+                        p.total_value.round_dp(6).to_string(),
+                        p.pnl.round_dp(6).to_string(),
+                    ]
+                })
+                .collect();
+            print_csv(
+                &["time", "positions_value", "cash", "total_value", "pnl"],
+                &rows,
+            );
+        }
+    }
+    Ok(())
+}
+
+fn to_f64(d: Decimal) -> f64 {
+    d.to_f64().unwrap_or(0.0)
+}
+
+fn day(t: DateTime<Utc>) -> String {
+    super::time::format_date(t)
+}
+
+/// `values` plotted as a line of dots, at most `width` columns wide (taking
+/// evenly spaced values when there are more), with the top, middle and
+/// bottom of the range labelled.
+fn ascii_chart(values: &[f64], width: usize, height: usize) -> String {
+    if values.is_empty() || width == 0 || height < 2 {
+        return String::new();
+    }
+    let columns: Vec<f64> = if values.len() <= width {
+        values.to_vec()
+    } else {
+        (0..width)
+            .map(|i| values[i * (values.len() - 1) / (width - 1).max(1)])
+            .collect()
+    };
+    let low = columns.iter().copied().fold(f64::INFINITY, f64::min);
+    let high = columns.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = (high - low).max(f64::EPSILON);
+    let row_of = |v: f64| ((high - v) / range * (height - 1) as f64).round() as usize;
+
+    let label = |row: usize| -> String {
+        let v = high - range * row as f64 / (height - 1) as f64;
+        if row == 0 || row == height - 1 || row == (height - 1) / 2 {
+            format_decimal(Decimal::try_from(v).unwrap_or_default())
+        } else {
+            String::new()
+        }
+    };
+    let labels: Vec<String> = (0..height).map(label).collect();
+    let pad = labels.iter().map(String::len).max().unwrap_or(0);
+    (0..height)
+        .map(|row| {
+            let line: String = columns
+                .iter()
+                .map(|&v| if row_of(v) == row { '•' } else { ' ' })
+                .collect();
+            format!("{:>pad$} │{}", labels[row], line.trim_end())
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_pnl_signs() {
+        assert_eq!(format_pnl(Decimal::from(12)), "+$12.00");
+        assert_eq!(format_pnl(Decimal::from(-1500)), "-$1.5K");
+    }
+
+    #[test]
+    fn ascii_chart_puts_extremes_on_the_edges() {
+        let chart = ascii_chart(&[0.0, 5.0, 10.0], 60, 3);
+        let lines: Vec<&str> = chart.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("$10.00 │") && lines[0].ends_with("  •"));
+        assert!(lines[1].ends_with(" •"));
+        assert!(lines[2].starts_with(" $0.00 │•"));
+    }
+
+    #[test]
+    fn ascii_chart_samples_long_series() {
+        let values: Vec<f64> = (0..500).map(f64::from).collect();
+        let chart = ascii_chart(&values, 40, 5);
+        let dots: usize = chart.lines().map(|l| l.matches('•').count()).sum();
+        assert_eq!(dots, 40);
+    }
+}
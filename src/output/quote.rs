@@ -0,0 +1,202 @@
+use chrono::{DateTime, Local};
+use polymarket_client_sdk::types::{Decimal, U256};
+use serde_json::json;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+use super::time::format_clock;
+use super::{OutputFormat, csv_header, csv_lines, csv_opt, format_price_with, print_csv, truncate};
+
+/// Top of the book and last trade for one outcome.
+pub struct Quote {
+    pub market: String,
+    pub outcome: String,
+    pub token_id: U256,
+    pub bid: Option<Decimal>,
+    pub ask: Option<Decimal>,
+    pub last: Option<Decimal>,
+}
+
+impl Quote {
+    pub fn mid(&self) -> Option<Decimal> {
+        Some((self.bid? + self.ask?) / Decimal::TWO)
+    }
+
+    pub fn spread(&self) -> Option<Decimal> {
+        Some(self.ask? - self.bid?)
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        json!({
+            "market": self.market,
+            "outcome": self.outcome,
+            "token_id": self.token_id.to_string(),
+            "bid": self.bid.map(|d| d.to_string()),
+            "ask": self.ask.map(|d| d.to_string()),
+            "mid": self.mid().map(|d| d.to_string()),
+            "spread": self.spread().map(|d| d.to_string()),
+            "last": self.last.map(|d| d.to_string()),
+        })
+    }
+
+    fn csv_row(&self) -> Vec<String> {
+        vec![
+            self.market.clone(),
+            self.outcome.clone(),
+            self.token_id.to_string(),
+            csv_opt(self.bid),
+            csv_opt(self.ask),
+            csv_opt(self.mid()),
+            csv_opt(self.spread()),
+            csv_opt(self.last),
+        ]
+    }
+}
+
+const CSV_HEADERS: [&str; 8] = [
+    "market", "outcome", "token_id", "bid", "ask", "mid", "spread", "last",
+];
+
+fn price(d: Option<Decimal>) -> String {
+    d.map_or_else(|| "—".into(), |d| d.normalize().to_string())
+}
+
+fn odds(d: Option<Decimal>) -> String {
+    d.map_or_else(
+        || "—".into(),
+        |d| format_price_with(d, |d| d.normalize().to_string()),
+    )
+}
+
+pub fn print_quotes(quotes: &[Quote], output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "Market")]
+                market: String,
+                #[tabled(rename = "Outcome")]
+                outcome: String,
+                #[tabled(rename = "Bid")]
+                bid: String,
+                #[tabled(rename = "Ask")]
+                ask: String,
+                #[tabled(rename = "Mid")]
+                mid: String,
+                #[tabled(rename = "Spread")]
+                spread: String,
+                #[tabled(rename = "Last")]
+                last: String,
+            }
+            let rows: Vec<Row> = quotes
+                .iter()
+                .map(|q| Row {
+                    market: truncate(&q.market, 50),
+                    outcome: q.outcome.clone(),
+                    bid: odds(q.bid),
+                    ask: odds(q.ask),
+                    mid: odds(q.mid()),
+                    spread: price(q.spread()),
+                    last: odds(q.last),
+                })
+                .collect();
+            println!("{}", Table::new(rows).with(Style::rounded()));
+        }
+        OutputFormat::Json => {
+            let quotes: Vec<_> = quotes.iter().map(Quote::to_json).collect();
+            super::print_json(&quotes)?;
+        }
+        OutputFormat::Csv => {
+            let rows: Vec<_> = quotes.iter().map(Quote::csv_row).collect();
+            print_csv(&CSV_HEADERS, &rows);
+        }
+        OutputFormat::Plain => {
+            for q in quotes {
+                println!("{}", plain_line(q));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn plain_line(q: &Quote) -> String {
+    format!(
+        "{} · {}  bid {}  ask {}  mid {}  spread {}  last {}",
+        truncate(&q.market, 40),
+        q.outcome,
+        price(q.bid),
+        price(q.ask),
+        price(q.mid()),
+        price(q.spread()),
+        price(q.last),
+    )
+}
+
+/// One round of `quote --watch`: a line per outcome, stamped with `at`.
+/// `first` marks the first round, which carries the CSV header.
+pub fn print_quote_tick(quotes: &[Quote], at: DateTime<Local>, first: bool, output: &OutputFormat) {
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            for q in quotes {
+                println!("{}  {}", format_clock(at), plain_line(q));
+            }
+        }
+        OutputFormat::Json => {
+            for q in quotes {
+                let mut line = q.to_json();
+                line["time"] = json!(at.to_rfc3339());
+                super::print_json_line(line);
+            }
+        }
+        OutputFormat::Csv => {
+            let headers: Vec<&str> = std::iter::once("time").chain(CSV_HEADERS).collect();
+            let rows: Vec<Vec<String>> = quotes
+                .iter()
+                .map(|q| {
+                    let mut row = vec![at.to_rfc3339()];
+                    row.extend(q.csv_row());
+                    row
+                })
+                .collect();
+            for line in csv_lines(&headers, &rows, first && csv_header()) {
+                println!("{line}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn quote(bid: Option<&str>, ask: Option<&str>) -> Quote {
+        Quote {
+            market: "Will it rain?".into(),
+            outcome: "Yes".into(),
+            token_id: U256::from(1),
+            bid: bid.map(|d| Decimal::from_str(d).unwrap()),
+            ask: ask.map(|d| Decimal::from_str(d).unwrap()),
+            last: None,
+        }
+    }
+
+    #[test]
+    fn mid_and_spread_need_both_sides() {
+        let q = quote(Some("0.45"), Some("0.48"));
+        assert_eq!(q.mid(), Some(Decimal::from_str("0.465").unwrap()));
+        assert_eq!(q.spread(), Some(Decimal::from_str("0.03").unwrap()));
+        let q = quote(Some("0.45"), None);
+        assert_eq!(q.mid(), None);
+        assert_eq!(q.spread(), None);
+    }
+
+    #[test]
+    fn plain_line_marks_missing_prices() {
+        let q = quote(Some("0.450"), None);
+        assert_eq!(
+            plain_line(&q),
+            "Will it rain? · Yes  bid 0.45  ask —  mid —  spread —  last —"
+        );
+    }
+}
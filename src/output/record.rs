@@ -0,0 +1,37 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use serde_json::json;
+
+use super::{OutputFormat, print_detail_table};
+
+/// What a `record` session wrote: every file, rotated ones first, and how
+/// many book snapshots and trades went into them.
+#[derive(Default)]
+pub struct RecordSummary {
+    pub files: Vec<PathBuf>,
+    pub books: usize,
+    pub trades: usize,
+}
+
+pub fn print_record(summary: &RecordSummary, output: &OutputFormat) -> Result<()> {
+    let files: Vec<String> = summary
+        .files
+        .iter()
+        .map(|f| f.display().to_string())
+        .collect();
+    match output {
+        OutputFormat::Plain => println!("{}", files.join("\n")),
+        OutputFormat::Json => super::print_json(&json!({
+            "files": files,
+            "book_snapshots": summary.books,
+            "trades": summary.trades,
+        }))?,
+        OutputFormat::Table | OutputFormat::Csv => print_detail_table(vec![
+            ["Files".into(), files.join("\n")],
+            ["Book snapshots".into(), summary.books.to_string()],
+            ["Trades".into(), summary.trades.to_string()],
+        ]),
+    }
+    Ok(())
+}
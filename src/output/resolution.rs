@@ -0,0 +1,175 @@
+use chrono::{DateTime, Utc};
+use polymarket_client_sdk::types::Decimal;
+use serde_json::json;
+
+use super::time::{format_datetime_zoned, format_timestamp};
+use super::{OutputFormat, format_price_with, print_detail_table};
+use crate::config::ResolutionState;
+
+/// A market's place in UMA's optimistic oracle, as Gamma reports it.
+pub struct Resolution {
+    pub market_id: String,
+    pub question: String,
+    pub condition_id: Option<String>,
+    pub state: ResolutionState,
+    /// Every status the oracle has reported, oldest first.
+    pub history: Vec<String>,
+    /// When the current proposal stops being open to dispute.
+    pub challenge_ends: Option<String>,
+    /// Seconds a proposal stays open to dispute.
+    pub liveness: Option<i32>,
+    pub bond: Option<String>,
+    pub reward: Option<Decimal>,
+    /// Address of the adapter that resolves the market.
+    pub resolver: Option<String>,
+    pub prices: Vec<(String, Decimal)>,
+    /// The outcome that paid out, once resolved.
+    pub winner: Option<String>,
+}
+
+fn describe(state: ResolutionState) -> &'static str {
+    match state {
+        ResolutionState::Open => "Open for trading",
+        ResolutionState::Closed => "Closed, awaiting a proposal",
+        ResolutionState::Proposed => "Proposed, open to dispute",
+        ResolutionState::Disputed => "Disputed, awaiting UMA's vote",
+        ResolutionState::Resolved => "Resolved",
+    }
+}
+
+/// `2h`, `90m` or `45s`.
+fn format_liveness(secs: i32) -> String {
+    if secs % 3600 == 0 {
+        format!("{}h", secs / 3600)
+    } else if secs % 60 == 0 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+/// Gamma's timestamp in local time, or as given if it doesn't parse.
+fn format_time(time: &str) -> String {
+    time.parse::<DateTime<Utc>>()
+        .map_or_else(|_| time.to_string(), format_datetime_zoned)
+}
+
+fn format_prices(prices: &[(String, Decimal)]) -> String {
+    prices
+        .iter()
+        .map(|(outcome, price)| {
+            format!(
+                "{outcome} {}",
+                format_price_with(*price, |p| p.normalize().to_string())
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn resolution_to_json(r: &Resolution) -> serde_json::Value {
+    json!({
+        "market_id": r.market_id,
+        "question": r.question,
+        "condition_id": r.condition_id,
+        "state": r.state,
+        "history": r.history,
+        "challenge_ends": r.challenge_ends,
+        "liveness": r.liveness,
+        "bond": r.bond,
+        "reward": r.reward.map(|d| d.to_string()),
+        "resolver": r.resolver,
+        "prices": r.prices.iter().map(|(outcome, price)| {
+            json!({"outcome": outcome, "price": price.to_string()})
+        }).collect::<Vec<_>>(),
+        "winner": r.winner,
+    })
+}
+
+pub fn print_resolution(r: &Resolution, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Plain => println!("{}", r.state),
+        OutputFormat::Json => super::print_json(&resolution_to_json(r))?,
+        OutputFormat::Table | OutputFormat::Csv => {
+            let mut rows = vec![
+                ["Market".into(), r.question.clone()],
+                ["Market ID".into(), r.market_id.clone()],
+            ];
+            if let Some(c) = &r.condition_id {
+                rows.push(["Condition ID".into(), c.clone()]);
+            }
+            rows.push(["State".into(), describe(r.state).into()]);
+            if !r.history.is_empty() {
+                rows.push(["History".into(), r.history.join(" → ")]);
+            }
+            if let Some(t) = &r.challenge_ends {
+                rows.push(["Challenge ends".into(), format_time(t)]);
+            }
+            if let Some(secs) = r.liveness {
+                rows.push(["Challenge period".into(), format_liveness(secs)]);
+            }
+            if let Some(b) = &r.bond {
+                rows.push(["Bond".into(), format!("{b} USDC")]);
+            }
+            if let Some(reward) = r.reward {
+                rows.push(["Reward".into(), format!("{} USDC", reward.normalize())]);
+            }
+            if let Some(a) = &r.resolver {
+                rows.push(["Resolver".into(), a.clone()]);
+            }
+            if !r.prices.is_empty() {
+                rows.push(["Prices".into(), format_prices(&r.prices)]);
+            }
+            if let Some(w) = &r.winner {
+                rows.push(["Winner".into(), w.clone()]);
+            }
+            print_detail_table(rows);
+        }
+    }
+    Ok(())
+}
+
+/// The event `resolution watch` prints and sends when a market moves from
+/// `previous` to its current state.
+pub fn resolution_event(r: &Resolution, previous: ResolutionState) -> serde_json::Value {
+    json!({
+        "event": "resolution",
+        "market_id": r.market_id,
+        "condition_id": r.condition_id,
+        "question": r.question,
+        "state": r.state,
+        "previous": previous,
+        "challenge_ends": r.challenge_ends,
+        "winner": r.winner,
+        "timestamp": Utc::now().to_rfc3339(),
+    })
+}
+
+/// `Proposed (was closed), challenge ends 2026-01-02 03:04 UTC`
+pub fn format_resolution_change(r: &Resolution, previous: ResolutionState) -> String {
+    let mut line = format!("{} (was {previous})", describe(r.state));
+    if let Some(w) = &r.winner {
+        line.push_str(&format!(": {w} won"));
+    } else if let (ResolutionState::Proposed, Some(t)) = (r.state, &r.challenge_ends) {
+        line.push_str(&format!(", challenge ends {}", format_time(t)));
+    }
+    line
+}
+
+/// One line per change; JSON output is newline-delimited.
+pub fn print_resolution_change(
+    r: &Resolution,
+    previous: ResolutionState,
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Json => println!("{}", resolution_event(r, previous)),
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => println!(
+            "{}  {} — {}",
+            format_timestamp(Utc::now()),
+            r.question,
+            format_resolution_change(r, previous)
+        ),
+    }
+    Ok(())
+}
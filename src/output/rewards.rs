@@ -0,0 +1,240 @@
+use chrono::NaiveDate;
+use polymarket_client_sdk::clob::types::Side;
+use polymarket_client_sdk::types::{B256, Decimal};
+use serde_json::json;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+use super::{OutputFormat, format_price, print_csv, truncate};
+
+/// Liquidity rewards the user is accruing in one market today.
+pub struct MarketRewards {
+    pub condition_id: B256,
+    pub question: String,
+    /// The user's share of the market's rewards, in percent.
+    pub share: Decimal,
+    /// USD value of today's earnings so far.
+    pub earned_today: Decimal,
+    /// Rewards the market pays out per day, across all makers.
+    pub daily_pool: Decimal,
+    pub max_spread: Decimal,
+    pub min_size: Decimal,
+}
+
+pub struct DailyEarnings {
+    pub date: NaiveDate,
+    pub earnings: Decimal,
+}
+
+/// A resting order and whether it currently earns rewards.
+pub struct ScoringOrder {
+    pub id: String,
+    pub market: String,
+    pub outcome: String,
+    pub side: Side,
+    pub price: Decimal,
+    pub remaining: Decimal,
+    pub scoring: bool,
+}
+
+pub struct RewardsReport {
+    pub markets: Vec<MarketRewards>,
+    /// Newest first.
+    pub history: Vec<DailyEarnings>,
+    pub orders: Vec<ScoringOrder>,
+}
+
+impl RewardsReport {
+    fn earned_today(&self) -> Decimal {
+        self.markets.iter().map(|m| m.earned_today).sum()
+    }
+}
+
+fn usd(d: Decimal) -> String {
+    format!("${}", d.round_dp(2))
+}
+
+pub fn print_rewards_report(r: &RewardsReport, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            print_markets_table(&r.markets);
+            print_history_table(&r.history);
+            print_orders_table(&r.orders);
+        }
+        OutputFormat::Json => {
+            let markets: Vec<_> = r
+                .markets
+                .iter()
+                .map(|m| {
+                    json!({
+                        "condition_id": m.condition_id.to_string(),
+                        "question": m.question,
+                        "share_percent": m.share.to_string(),
+                        "earned_today": m.earned_today.to_string(),
+                        "daily_pool": m.daily_pool.to_string(),
+                        "max_spread": m.max_spread.to_string(),
+                        "min_size": m.min_size.to_string(),
+                    })
+                })
+                .collect();
+            let history: Vec<_> = r
+                .history
+                .iter()
+                .map(|d| json!({"date": d.date.to_string(), "earnings": d.earnings.to_string()}))
+                .collect();
+            let orders: Vec<_> = r
+                .orders
+                .iter()
+                .map(|o| {
+                    json!({
+                        "id": o.id,
+                        "market": o.market,
+                        "outcome": o.outcome,
+                        "side": o.side.to_string(),
+                        "price": o.price.to_string(),
+                        "remaining": o.remaining.to_string(),
+                        "scoring": o.scoring,
+                    })
+                })
+                .collect();
+            super::print_json(&json!({
+                "earned_today": r.earned_today().to_string(),
+                "markets": markets,
+                "history": history,
+                "orders": orders,
+            }))?;
+        }
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = r
+                .markets
+                .iter()
+                .map(|m| {
+                    vec![
+                        m.condition_id.to_string(),
+                        m.question.clone(),
+                        m.share.to_string(),
+                        m.earned_today.to_string(),
+                        m.daily_pool.to_string(),
+                        m.max_spread.to_string(),
+                        m.min_size.to_string(),
+                    ]
+                })
+                .collect();
+            print_csv(
+                &[
+                    "condition_id",
+                    "question",
+                    "share_percent",
+                    "earned_today",
+                    "daily_pool",
+                    "max_spread",
+                    "min_size",
+                ],
+                &rows,
+            );
+        }
+        OutputFormat::Plain => println!("{}", r.earned_today()),
+    }
+    Ok(())
+}
+
+fn print_markets_table(markets: &[MarketRewards]) {
+    if markets.is_empty() {
+        println!("No rewards accruing today.");
+        return;
+    }
+    #[derive(Tabled)]
+    struct Row {
+        #[tabled(rename = "Market")]
+        market: String,
+        #[tabled(rename = "Share")]
+        share: String,
+        #[tabled(rename = "Earned Today")]
+        earned: String,
+        #[tabled(rename = "Daily Pool")]
+        pool: String,
+        #[tabled(rename = "Max Spread")]
+        max_spread: String,
+        #[tabled(rename = "Min Size")]
+        min_size: String,
+    }
+    let rows: Vec<Row> = markets
+        .iter()
+        .map(|m| Row {
+            market: truncate(&m.question, 50),
+            share: format!("{}%", m.share.round_dp(2)),
+            earned: usd(m.earned_today),
+            pool: usd(m.daily_pool),
+            max_spread: format!("{}¢", m.max_spread),
+            min_size: m.min_size.to_string(),
+        })
+        .collect();
+    println!("Today's accrual");
+    println!("{}", Table::new(rows).with(Style::rounded()));
+}
+
+fn print_history_table(history: &[DailyEarnings]) {
+    #[derive(Tabled)]
+    struct Row {
+        #[tabled(rename = "Date")]
+        date: String,
+        #[tabled(rename = "Earned")]
+        earned: String,
+    }
+    let rows: Vec<Row> = history
+        .iter()
+        .map(|d| Row {
+            date: d.date.to_string(),
+            earned: usd(d.earnings),
+        })
+        .collect();
+    let total: Decimal = history.iter().map(|d| d.earnings).sum();
+    println!(
+        "\nPayouts (last {} days, {} total)",
+        history.len(),
+        usd(total)
+    );
+    println!("{}", Table::new(rows).with(Style::rounded()));
+}
+
+fn print_orders_table(orders: &[ScoringOrder]) {
+    if orders.is_empty() {
+        println!("\nNo resting orders.");
+        return;
+    }
+    #[derive(Tabled)]
+    struct Row {
+        #[tabled(rename = "Order")]
+        id: String,
+        #[tabled(rename = "Market")]
+        market: String,
+        #[tabled(rename = "Outcome")]
+        outcome: String,
+        #[tabled(rename = "Side")]
+        side: String,
+        #[tabled(rename = "Price")]
+        price: String,
+        #[tabled(rename = "Remaining")]
+        remaining: String,
+        #[tabled(rename = "Scoring")]
+        scoring: String,
+    }
+    let rows: Vec<Row> = orders
+        .iter()
+        .map(|o| Row {
+            id: truncate(&o.id, 14),
+            market: truncate(&o.market, 40),
+            outcome: o.outcome.clone(),
+            side: o.side.to_string(),
+            price: format_price(o.price),
+            remaining: o.remaining.to_string(),
+            scoring: if o.scoring { "yes" } else { "no" }.into(),
+        })
+        .collect();
+    let qualifying = orders.iter().filter(|o| o.scoring).count();
+    println!(
+        "\nResting orders ({qualifying} of {} scoring)",
+        orders.len()
+    );
+    println!("{}", Table::new(rows).with(Style::rounded()));
+}
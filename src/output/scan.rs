@@ -0,0 +1,190 @@
+use polymarket_client_sdk::types::{Decimal, U256};
+use serde_json::json;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+use super::{OutputFormat, print_csv, truncate};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArbKind {
+    /// The Yes and No sides of one market.
+    Binary,
+    /// The Yes side of every market in a neg-risk event.
+    MultiOutcome,
+}
+
+impl ArbKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Binary => "binary",
+            Self::MultiOutcome => "multi-outcome",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArbSide {
+    /// Buy every outcome at the best ask; the set pays 1 at resolution.
+    Buy,
+    /// Sell every outcome at the best bid; a complete set costs 1 to make.
+    Sell,
+}
+
+impl ArbSide {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Buy => "buy",
+            Self::Sell => "sell",
+        }
+    }
+}
+
+/// One outcome of an opportunity, at the top of its book.
+pub struct ArbLeg {
+    pub outcome: String,
+    pub token_id: U256,
+    pub price: Decimal,
+    pub size: Decimal,
+}
+
+pub struct ArbOpportunity {
+    pub kind: ArbKind,
+    pub market: String,
+    pub side: ArbSide,
+    pub legs: Vec<ArbLeg>,
+}
+
+impl ArbOpportunity {
+    /// What one share of every outcome costs (buy) or fetches (sell).
+    pub fn total(&self) -> Decimal {
+        self.legs.iter().map(|l| l.price).sum()
+    }
+
+    /// Profit per complete set, before fees.
+    pub fn edge(&self) -> Decimal {
+        match self.side {
+            ArbSide::Buy => Decimal::ONE - self.total(),
+            ArbSide::Sell => self.total() - Decimal::ONE,
+        }
+    }
+
+    /// Complete sets available at these prices: the thinnest leg.
+    pub fn size(&self) -> Decimal {
+        self.legs.iter().map(|l| l.size).min().unwrap_or_default()
+    }
+
+    pub fn profit(&self) -> Decimal {
+        self.edge() * self.size()
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        json!({
+            "market": self.market,
+            "kind": self.kind.label(),
+            "side": self.side.label(),
+            "total": self.total().to_string(),
+            "edge": self.edge().to_string(),
+            "size": self.size().to_string(),
+            "profit": self.profit().to_string(),
+            "legs": self.legs.iter().map(|l| json!({
+                "outcome": l.outcome,
+                "token_id": l.token_id.to_string(),
+                "price": l.price.to_string(),
+                "size": l.size.to_string(),
+            })).collect::<Vec<_>>(),
+        })
+    }
+}
+
+fn usdc(d: Decimal) -> String {
+    format!("${d:.2}")
+}
+
+pub fn print_arbs(arbs: &[ArbOpportunity], output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table => {
+            if arbs.is_empty() {
+                println!("No arbitrage found.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "Market")]
+                market: String,
+                #[tabled(rename = "Type")]
+                kind: String,
+                #[tabled(rename = "Side")]
+                side: String,
+                #[tabled(rename = "Outcomes")]
+                outcomes: usize,
+                #[tabled(rename = "Sum")]
+                total: String,
+                #[tabled(rename = "Edge")]
+                edge: String,
+                #[tabled(rename = "Size")]
+                size: String,
+                #[tabled(rename = "Profit")]
+                profit: String,
+            }
+            let rows: Vec<Row> = arbs
+                .iter()
+                .map(|a| Row {
+                    market: truncate(&a.market, 50),
+                    kind: a.kind.label().into(),
+                    side: a.side.label().into(),
+                    outcomes: a.legs.len(),
+                    total: a.total().normalize().to_string(),
+                    edge: a.edge().normalize().to_string(),
+                    size: super::format_number(a.size()),
+                    profit: usdc(a.profit()),
+                })
+                .collect();
+            println!("{}", Table::new(rows).with(Style::rounded()));
+            println!("Edges are per share, before fees.");
+        }
+        OutputFormat::Json => {
+            let arbs: Vec<_> = arbs.iter().map(ArbOpportunity::to_json).collect();
+            super::print_json(&arbs)?;
+        }
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = arbs
+                .iter()
+                .flat_map(|a| {
+                    a.legs.iter().map(move |l| {
+                        vec![
+                            a.market.clone(),
+                            a.kind.label().into(),
+                            a.side.label().into(),
+                            a.edge().to_string(),
+                            a.size().to_string(),
+                            l.outcome.clone(),
+                            l.token_id.to_string(),
+                            l.price.to_string(),
+                            l.size.to_string(),
+                        ]
+                    })
+                })
+                .collect();
+            print_csv(
+                &[
+                    "market", "kind", "side", "edge", "size", "outcome", "token_id", "price",
+                    "leg_size",
+                ],
+                &rows,
+            );
+        }
+        OutputFormat::Plain => {
+            for a in arbs {
+                println!(
+                    "{} {} {}  edge {}  size {}",
+                    a.side.label(),
+                    a.kind.label(),
+                    truncate(&a.market, 50),
+                    a.edge().normalize(),
+                    a.size().normalize(),
+                );
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use polymarket_client_sdk::types::Decimal;
+use serde_json::json;
+
+use super::{OutputFormat, format_price_with, print_detail_table};
+
+/// A Kelly stake recommendation from `size`.
+pub struct Sizing {
+    pub prob: Decimal,
+    pub price: Decimal,
+    pub bankroll: Decimal,
+    /// Full Kelly, as a share of the bankroll.
+    pub kelly: Decimal,
+    pub fraction: Decimal,
+    /// USDC to stake: bankroll × Kelly × fraction, in whole cents.
+    pub stake: Decimal,
+}
+
+impl Sizing {
+    pub fn edge(&self) -> Decimal {
+        self.prob - self.price
+    }
+
+    pub fn shares(&self) -> Decimal {
+        (self.stake / self.price).round_dp(2)
+    }
+
+    /// Expected profit of the stake if `prob` is right.
+    pub fn expected_profit(&self) -> Decimal {
+        (self.shares() * self.edge()).round_dp(2)
+    }
+}
+
+fn percent(d: Decimal) -> String {
+    format!("{}%", (d * Decimal::ONE_HUNDRED).round_dp(2).normalize())
+}
+
+pub fn print_sizing(sizing: &Sizing, output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Plain => println!("{}", sizing.stake),
+        OutputFormat::Table | OutputFormat::Csv => {
+            let full = (sizing.bankroll * sizing.kelly).round_dp(2);
+            let mut rows = vec![
+                ["Your probability".into(), percent(sizing.prob)],
+                [
+                    "Price".into(),
+                    format_price_with(sizing.price, |p| p.normalize().to_string()),
+                ],
+                ["Edge".into(), percent(sizing.edge())],
+                ["Bankroll".into(), format!("${:.2}", sizing.bankroll)],
+                [
+                    "Full Kelly".into(),
+                    format!("{} (${full:.2})", percent(sizing.kelly)),
+                ],
+                [
+                    format!("Stake ({}× Kelly)", sizing.fraction.normalize()),
+                    format!("${:.2}", sizing.stake),
+                ],
+            ];
+            if !sizing.stake.is_zero() {
+                rows.push(["Shares".into(), sizing.shares().normalize().to_string()]);
+                rows.push([
+                    "Expected profit".into(),
+                    format!("${:.2}", sizing.expected_profit()),
+                ]);
+            }
+            print_detail_table(rows);
+            if sizing.kelly.is_zero() {
+                println!(
+                    "No edge: the price is at or above your probability, so Kelly stakes nothing."
+                );
+            }
+        }
+        OutputFormat::Json => super::print_json(&json!({
+            "prob": sizing.prob.to_string(),
+            "price": sizing.price.to_string(),
+            "edge": sizing.edge().to_string(),
+            "bankroll": sizing.bankroll.to_string(),
+            "kelly": sizing.kelly.round_dp(6).normalize().to_string(),
+            "fraction": sizing.fraction.to_string(),
+            "stake": sizing.stake.to_string(),
+            "shares": sizing.shares().normalize().to_string(),
+            "expected_profit": sizing.expected_profit().to_string(),
+        }))?,
+    }
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use serde_json::json;
+
+use super::{OutputFormat, print_detail_table};
+use crate::commands::state::KeyMode;
+
+pub struct ExportSummary {
+    pub file: PathBuf,
+    pub profiles: Vec<String>,
+    pub keys: KeyMode,
+    pub watchlist: usize,
+    pub alerts: usize,
+    pub contacts: usize,
+}
+
+pub fn print_export(summary: &ExportSummary, output: &OutputFormat) -> Result<()> {
+    match output {
+        OutputFormat::Plain => println!("{}", summary.file.display()),
+        OutputFormat::Json => super::print_json(&json!({
+            "file": summary.file.display().to_string(),
+            "profiles": summary.profiles,
+            "keys": summary.keys.to_string(),
+            "watchlist": summary.watchlist,
+            "alerts": summary.alerts,
+            "contacts": summary.contacts,
+        }))?,
+        OutputFormat::Table | OutputFormat::Csv => print_detail_table(vec![
+            ["File".into(), summary.file.display().to_string()],
+            ["Profiles".into(), list(&summary.profiles)],
+            ["Keys".into(), summary.keys.to_string()],
+            ["Watchlist".into(), summary.watchlist.to_string()],
+            ["Alerts".into(), summary.alerts.to_string()],
+            ["Contacts".into(), summary.contacts.to_string()],
+        ]),
+    }
+    Ok(())
+}
+
+/// What `state import` wrote: profiles saved and skipped, and how many
+/// watchlist entries, alerts and contacts were added.
+#[derive(Default)]
+pub struct ImportSummary {
+    pub profiles: Vec<String>,
+    /// Profiles that already existed and were left alone.
+    pub skipped: Vec<String>,
+    pub watchlist: usize,
+    pub alerts: usize,
+    pub contacts: usize,
+}
+
+pub fn print_import(summary: &ImportSummary, output: &OutputFormat) -> Result<()> {
+    match output {
+        OutputFormat::Plain => println!("{}", summary.profiles.join(",")),
+        OutputFormat::Json => super::print_json(&json!({
+            "profiles": summary.profiles,
+            "skipped": summary.skipped,
+            "watchlist_added": summary.watchlist,
+            "alerts_added": summary.alerts,
+            "contacts_added": summary.contacts,
+        }))?,
+        OutputFormat::Table | OutputFormat::Csv => {
+            let mut rows = vec![["Profiles".into(), list(&summary.profiles)]];
+            if !summary.skipped.is_empty() {
+                rows.push([
+                    "Skipped".into(),
+                    format!(
+                        "{} (already exist; --force replaces them)",
+                        list(&summary.skipped)
+                    ),
+                ]);
+            }
+            rows.extend([
+                ["Watchlist".into(), format!("+{}", summary.watchlist)],
+                ["Alerts".into(), format!("+{}", summary.alerts)],
+                ["Contacts".into(), format!("+{}", summary.contacts)],
+            ]);
+            print_detail_table(rows);
+        }
+    }
+    Ok(())
+}
+
+fn list(names: &[String]) -> String {
+    if names.is_empty() {
+        "none".into()
+    } else {
+        names.join(", ")
+    }
+}
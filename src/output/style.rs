@@ -0,0 +1,140 @@
+//! Colors for table output: bids and asks, gains and losses, prices that
+//! went up or down. Off unless stdout is a terminal and `NO_COLOR` isn't
+//! set, or `--color always` asks for it; the profile's `theme` picks the
+//! palette.
+//!
+//! Table cells are colored through tabled's `Color`, so the escape codes
+//! don't count towards column widths.
+
+use std::io::IsTerminal;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use polymarket_client_sdk::types::Decimal;
+use serde::{Deserialize, Serialize};
+use tabled::Table;
+use tabled::settings::Color;
+use tabled::settings::object::Cell;
+
+/// `--color`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal and `NO_COLOR` isn't set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// The palette, from the profile's `theme`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    /// Green for up and bids, red for down and asks
+    #[default]
+    Default,
+    /// Blue for up and bids, yellow for down and asks, told apart without
+    /// red and green
+    Colorblind,
+    /// Bold for up and bids, dim for down and asks, without colors
+    Mono,
+}
+
+/// Which way a value leans.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tone {
+    /// A bid, a gain, a rise.
+    Up,
+    /// An ask, a loss, a fall.
+    Down,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static THEME: Mutex<Theme> = Mutex::new(Theme::Default);
+
+/// Turns colors on or off for `choice`, looking at `NO_COLOR` and stdout
+/// for `auto`.
+pub fn set_color(choice: ColorChoice, theme: Theme) {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let enabled = enabled_for(choice, no_color, std::io::stdout().is_terminal());
+    ENABLED.store(enabled, Ordering::Relaxed);
+    *THEME.lock().unwrap_or_else(|e| e.into_inner()) = theme;
+}
+
+fn enabled_for(choice: ColorChoice, no_color: bool, terminal: bool) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => terminal && !no_color,
+    }
+}
+
+fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+fn escapes(tone: Tone, theme: Theme) -> (&'static str, &'static str) {
+    match (theme, tone) {
+        (Theme::Default, Tone::Up) => ("\u{1b}[32m", "\u{1b}[39m"),
+        (Theme::Default, Tone::Down) => ("\u{1b}[31m", "\u{1b}[39m"),
+        (Theme::Colorblind, Tone::Up) => ("\u{1b}[34m", "\u{1b}[39m"),
+        (Theme::Colorblind, Tone::Down) => ("\u{1b}[33m", "\u{1b}[39m"),
+        (Theme::Mono, Tone::Up) => ("\u{1b}[1m", "\u{1b}[22m"),
+        (Theme::Mono, Tone::Down) => ("\u{1b}[2m", "\u{1b}[22m"),
+    }
+}
+
+fn color(tone: Tone) -> Color {
+    let (prefix, suffix) = escapes(tone, *THEME.lock().unwrap_or_else(|e| e.into_inner()));
+    Color::new(prefix, suffix)
+}
+
+/// The tone of a change or PnL: up when positive, down when negative.
+pub fn sign(d: Decimal) -> Option<Tone> {
+    if d.is_zero() {
+        None
+    } else if d.is_sign_negative() {
+        Some(Tone::Down)
+    } else {
+        Some(Tone::Up)
+    }
+}
+
+/// Colors the cells of `column` from row `first_row` down, one tone per
+/// row; `None` leaves a cell as it is.
+pub fn color_column(
+    table: &mut Table,
+    column: usize,
+    first_row: usize,
+    tones: impl IntoIterator<Item = Option<Tone>>,
+) {
+    if !enabled() {
+        return;
+    }
+    for (i, tone) in tones.into_iter().enumerate() {
+        if let Some(tone) = tone {
+            table.modify(Cell::new(first_row + i, column), color(tone));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_colors_only_a_terminal_without_no_color() {
+        assert!(enabled_for(ColorChoice::Auto, false, true));
+        assert!(!enabled_for(ColorChoice::Auto, true, true));
+        assert!(!enabled_for(ColorChoice::Auto, false, false));
+        assert!(enabled_for(ColorChoice::Always, true, false));
+        assert!(!enabled_for(ColorChoice::Never, false, true));
+    }
+
+    #[test]
+    fn sign_picks_the_tone() {
+        assert_eq!(sign(Decimal::ONE), Some(Tone::Up));
+        assert_eq!(sign(-Decimal::ONE), Some(Tone::Down));
+        assert_eq!(sign(Decimal::ZERO), None);
+    }
+}
@@ -0,0 +1,270 @@
+use chrono::DateTime;
+use polymarket_client_sdk::data::types::Side;
+use polymarket_client_sdk::types::{Address, B256, Decimal, U256};
+use serde_json::json;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+use super::{OutputFormat, csv_opt, format_decimal, format_number, print_csv};
+use super::{print_detail_table, time, truncate};
+use crate::commands::sync::TokenMarket;
+
+const POSITION_FIELDS: [&str; 5] = ["token_id", "title", "outcome", "condition_id", "shares"];
+const TRADE_FIELDS: [&str; 10] = [
+    "time",
+    "transaction_hash",
+    "side",
+    "token_id",
+    "title",
+    "outcome",
+    "shares",
+    "price",
+    "usdc",
+    "fee",
+];
+
+/// What one `sync` run scanned and found.
+pub struct SyncSummary {
+    pub address: Address,
+    pub from_block: u64,
+    pub synced_to: Option<u64>,
+    pub new_events: usize,
+    pub total_events: usize,
+}
+
+pub struct SyncedPosition {
+    pub token: U256,
+    pub shares: Decimal,
+    /// `None` when the token couldn't be looked up.
+    pub market: Option<TokenMarket>,
+}
+
+pub struct SyncedTrade {
+    pub timestamp: i64,
+    pub tx: B256,
+    pub side: Side,
+    pub token: U256,
+    pub shares: Decimal,
+    pub usdc: Decimal,
+    pub fee: Decimal,
+    pub market: Option<TokenMarket>,
+}
+
+impl SyncedTrade {
+    fn price(&self) -> Option<Decimal> {
+        (!self.shares.is_zero()).then(|| self.usdc / self.shares)
+    }
+}
+
+fn title(market: Option<&TokenMarket>) -> Option<&str> {
+    market.map(|m| m.question.as_str())
+}
+
+fn outcome(market: Option<&TokenMarket>) -> Option<&str> {
+    market.and_then(|m| m.outcome.as_deref())
+}
+
+pub fn print_sync_summary(s: &SyncSummary, output: &OutputFormat) -> anyhow::Result<()> {
+    let synced_to = s.synced_to.map(|b| b.to_string());
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            let rows = vec![
+                ["Wallet".into(), s.address.to_string()],
+                ["Scanned From".into(), s.from_block.to_string()],
+                ["Synced To".into(), synced_to.clone().unwrap_or("—".into())],
+                ["New Events".into(), s.new_events.to_string()],
+                ["Total Events".into(), s.total_events.to_string()],
+            ];
+            print_detail_table(rows);
+        }
+        OutputFormat::Json => {
+            super::print_json(&json!({
+                "address": s.address.to_string(),
+                "from_block": s.from_block,
+                "synced_to": s.synced_to,
+                "new_events": s.new_events,
+                "total_events": s.total_events,
+            }))?;
+        }
+        OutputFormat::Csv => {
+            print_csv(
+                &[
+                    "address",
+                    "from_block",
+                    "synced_to",
+                    "new_events",
+                    "total_events",
+                ],
+                &[vec![
+                    s.address.to_string(),
+                    s.from_block.to_string(),
+                    csv_opt(synced_to),
+                    s.new_events.to_string(),
+                    s.total_events.to_string(),
+                ]],
+            );
+        }
+    }
+    Ok(())
+}
+
+pub fn print_synced_positions(
+    positions: &[SyncedPosition],
+    synced_to: Option<u64>,
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            if positions.is_empty() {
+                println!("No positions in the synced history.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "Market")]
+                market: String,
+                #[tabled(rename = "Outcome")]
+                outcome: String,
+                #[tabled(rename = "Shares")]
+                shares: String,
+                #[tabled(rename = "Token")]
+                token: String,
+            }
+            let rows: Vec<Row> = positions
+                .iter()
+                .map(|p| Row {
+                    market: truncate(title(p.market.as_ref()).unwrap_or("—"), 50),
+                    outcome: outcome(p.market.as_ref()).unwrap_or("—").into(),
+                    shares: format_number(p.shares),
+                    token: truncate(&p.token.to_string(), 14),
+                })
+                .collect();
+            let mut table = Table::new(rows);
+            table.with(Style::rounded());
+            println!("{table}");
+            if let Some(block) = synced_to {
+                println!("As of block {block}.");
+            }
+        }
+        OutputFormat::Json => {
+            let data: Vec<_> = positions
+                .iter()
+                .map(|p| {
+                    json!({
+                        "token_id": p.token.to_string(),
+                        "title": title(p.market.as_ref()),
+                        "outcome": outcome(p.market.as_ref()),
+                        "condition_id": p.market.as_ref().and_then(|m| m.condition_id).map(|c| c.to_string()),
+                        "shares": p.shares.to_string(),
+                    })
+                })
+                .collect();
+            super::print_json(&data)?;
+        }
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = positions
+                .iter()
+                .map(|p| {
+                    vec![
+                        p.token.to_string(),
+                        csv_opt(title(p.market.as_ref())),
+                        csv_opt(outcome(p.market.as_ref())),
+                        csv_opt(p.market.as_ref().and_then(|m| m.condition_id)),
+                        p.shares.to_string(),
+                    ]
+                })
+                .collect();
+            print_csv(&POSITION_FIELDS, &rows);
+        }
+    }
+    Ok(())
+}
+
+pub fn print_synced_trades(trades: &[SyncedTrade], output: &OutputFormat) -> anyhow::Result<()> {
+    let when = |t: &SyncedTrade| time::iso_from_unix(t.timestamp).unwrap_or_default();
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            if trades.is_empty() {
+                println!("No trades in the synced history.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "Time")]
+                time: String,
+                #[tabled(rename = "Side")]
+                side: String,
+                #[tabled(rename = "Market")]
+                market: String,
+                #[tabled(rename = "Outcome")]
+                outcome: String,
+                #[tabled(rename = "Shares")]
+                shares: String,
+                #[tabled(rename = "Price")]
+                price: String,
+                #[tabled(rename = "Value")]
+                value: String,
+            }
+            let rows: Vec<Row> = trades
+                .iter()
+                .map(|t| Row {
+                    time: DateTime::from_timestamp(t.timestamp, 0)
+                        .map(time::format_datetime)
+                        .unwrap_or_default(),
+                    side: t.side.to_string(),
+                    market: truncate(title(t.market.as_ref()).unwrap_or("—"), 40),
+                    outcome: outcome(t.market.as_ref()).unwrap_or("—").into(),
+                    shares: format_number(t.shares),
+                    price: t
+                        .price()
+                        .map_or("—".into(), |p| p.round_dp(4).normalize().to_string()),
+                    value: format_decimal(t.usdc),
+                })
+                .collect();
+            let mut table = Table::new(rows);
+            table.with(Style::rounded());
+            println!("{table}");
+        }
+        OutputFormat::Json => {
+            let data: Vec<_> = trades
+                .iter()
+                .map(|t| {
+                    json!({
+                        "time": when(t),
+                        "transaction_hash": t.tx.to_string(),
+                        "side": t.side.to_string(),
+                        "token_id": t.token.to_string(),
+                        "title": title(t.market.as_ref()),
+                        "outcome": outcome(t.market.as_ref()),
+                        "shares": t.shares.to_string(),
+                        "price": t.price().map(|p| p.round_dp(6).normalize().to_string()),
+                        "usdc": t.usdc.to_string(),
+                        "fee": t.fee.to_string(),
+                    })
+                })
+                .collect();
+            super::print_json(&data)?;
+        }
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = trades
+                .iter()
+                .map(|t| {
+                    vec![
+                        when(t),
+                        t.tx.to_string(),
+                        t.side.to_string(),
+                        t.token.to_string(),
+                        csv_opt(title(t.market.as_ref())),
+                        csv_opt(outcome(t.market.as_ref())),
+                        t.shares.to_string(),
+                        csv_opt(t.price().map(|p| p.round_dp(6).normalize())),
+                        t.usdc.to_string(),
+                        t.fee.to_string(),
+                    ]
+                })
+                .collect();
+            print_csv(&TRADE_FIELDS, &rows);
+        }
+    }
+    Ok(())
+}
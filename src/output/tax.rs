@@ -0,0 +1,184 @@
+use chrono::{DateTime, Utc};
+use polymarket_client_sdk::types::Decimal;
+use serde_json::json;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+use super::portfolio::format_pnl;
+use super::time::format_date;
+use super::{OutputFormat, format_decimal, print_csv, print_detail_table, truncate};
+
+/// Shares of one lot closed by a sale, merge or redemption. Amounts are in
+/// USDC.
+pub struct Disposal {
+    pub description: String,
+    /// `None` when the shares came from several days, or from outside the
+    /// history.
+    pub acquired: Option<DateTime<Utc>>,
+    pub disposed: DateTime<Utc>,
+    pub shares: Decimal,
+    pub proceeds: Decimal,
+    pub cost_basis: Decimal,
+    pub long_term: bool,
+}
+
+impl Disposal {
+    pub fn gain(&self) -> Decimal {
+        self.proceeds - self.cost_basis
+    }
+
+    fn acquired_date(&self, day: fn(DateTime<Utc>) -> String) -> String {
+        self.acquired.map_or_else(|| "various".into(), day)
+    }
+
+    fn term(&self) -> &'static str {
+        if self.long_term { "long" } else { "short" }
+    }
+}
+
+/// A day for the CSV, which keeps dates in UTC whatever `--utc` and
+/// `--date-format` say.
+fn csv_date(t: DateTime<Utc>) -> String {
+    t.format("%Y-%m-%d").to_string()
+}
+
+fn amount(d: Decimal) -> String {
+    d.round_dp(6).to_string()
+}
+
+pub fn print_tax_report(
+    year: i32,
+    method: &str,
+    disposals: &[Disposal],
+    output: &OutputFormat,
+) -> anyhow::Result<()> {
+    let total = |f: fn(&Disposal) -> Decimal, long: Option<bool>| -> Decimal {
+        disposals
+            .iter()
+            .filter(|d| long.is_none_or(|l| d.long_term == l))
+            .map(f)
+            .sum()
+    };
+    match output {
+        OutputFormat::Table => {
+            if disposals.is_empty() {
+                println!("No realized gains or losses in {year}.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "Market")]
+                market: String,
+                #[tabled(rename = "Acquired")]
+                acquired: String,
+                #[tabled(rename = "Disposed")]
+                disposed: String,
+                #[tabled(rename = "Shares")]
+                shares: String,
+                #[tabled(rename = "Proceeds")]
+                proceeds: String,
+                #[tabled(rename = "Cost Basis")]
+                cost_basis: String,
+                #[tabled(rename = "Gain")]
+                gain: String,
+                #[tabled(rename = "Term")]
+                term: &'static str,
+            }
+            let rows: Vec<Row> = disposals
+                .iter()
+                .map(|d| Row {
+                    market: truncate(&d.description, 40),
+                    acquired: d.acquired_date(format_date),
+                    disposed: format_date(d.disposed),
+                    shares: super::format_number(d.shares),
+                    proceeds: format_decimal(d.proceeds),
+                    cost_basis: format_decimal(d.cost_basis),
+                    gain: format_pnl(d.gain()),
+                    term: d.term(),
+                })
+                .collect();
+            println!("{}", Table::new(rows).with(Style::rounded()));
+            print_detail_table(vec![
+                ["Year".into(), year.to_string()],
+                ["Method".into(), method.to_uppercase()],
+                ["Lots".into(), disposals.len().to_string()],
+                [
+                    "Proceeds".into(),
+                    format_decimal(total(|d| d.proceeds, None)),
+                ],
+                [
+                    "Cost basis".into(),
+                    format_decimal(total(|d| d.cost_basis, None)),
+                ],
+                [
+                    "Short-term gain".into(),
+                    format_pnl(total(Disposal::gain, Some(false))),
+                ],
+                [
+                    "Long-term gain".into(),
+                    format_pnl(total(Disposal::gain, Some(true))),
+                ],
+                ["Net gain".into(), format_pnl(total(Disposal::gain, None))],
+            ]);
+        }
+        OutputFormat::Plain => println!("{}", total(Disposal::gain, None).round_dp(2)),
+        OutputFormat::Json => {
+            let lots: Vec<_> = disposals
+                .iter()
+                .map(|d| {
+                    json!({
+                        "description": d.description,
+                        "acquired": d.acquired.map(|t| t.to_rfc3339()),
+                        "disposed": d.disposed.to_rfc3339(),
+                        "shares": amount(d.shares),
+                        "proceeds": amount(d.proceeds),
+                        "cost_basis": amount(d.cost_basis),
+                        "gain": amount(d.gain()),
+                        "term": d.term(),
+                    })
+                })
+                .collect();
+            super::print_json(&json!({
+                "year": year,
+                "method": method,
+                "proceeds": amount(total(|d| d.proceeds, None)),
+                "cost_basis": amount(total(|d| d.cost_basis, None)),
+                "short_term_gain": amount(total(Disposal::gain, Some(false))),
+                "long_term_gain": amount(total(Disposal::gain, Some(true))),
+                "net_gain": amount(total(Disposal::gain, None)),
+                "lots": lots,
+            }))?;
+        }
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = disposals
+                .iter()
+                .map(|d| {
+                    vec![
+                        d.description.clone(),
+                        d.acquired_date(csv_date),
+                        csv_date(d.disposed),
+                        amount(d.shares),
+                        amount(d.proceeds),
+                        amount(d.cost_basis),
+                        amount(d.gain()),
+                        d.term().to_string(),
+                    ]
+                })
+                .collect();
+            print_csv(
+                &[
+                    "description",
+                    "date_acquired",
+                    "date_disposed",
+                    "shares",
+                    "proceeds",
+                    "cost_basis",
+                    "gain",
+                    "term",
+                ],
+                &rows,
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,126 @@
+//! How timestamps are shown: in local time unless `--utc`, and in the
+//! layout of `--date-format` when one is given. JSON keeps timestamps in
+//! ISO 8601, in UTC, whatever the flags say.
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local, SecondsFormat, TimeZone, Utc};
+
+/// Whether timestamps are shown in UTC (`--utc`) rather than local time.
+static UTC: AtomicBool = AtomicBool::new(false);
+
+/// The `--date-format` layout, replacing each default below.
+static DATE_FORMAT: Mutex<Option<String>> = Mutex::new(None);
+
+pub fn set_utc(utc: bool) {
+    UTC.store(utc, Ordering::Relaxed);
+}
+
+/// Sets the `--date-format` layout, a strftime string such as
+/// `%d/%m/%Y %H:%M`. Fails on one chrono can't format with.
+pub fn set_date_format(format: Option<String>) -> anyhow::Result<()> {
+    if let Some(format) = &format {
+        anyhow::ensure!(
+            !StrftimeItems::new(format).any(|item| matches!(item, Item::Error)),
+            crate::errors::validation(format!(
+                "Invalid --date-format '{format}': expected a strftime layout such as %d/%m/%Y %H:%M"
+            ))
+        );
+    }
+    *DATE_FORMAT.lock().unwrap_or_else(|e| e.into_inner()) = format;
+    Ok(())
+}
+
+fn render(t: DateTime<impl TimeZone>, default: &str) -> String {
+    let format = DATE_FORMAT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    render_with(
+        t,
+        format.as_deref().unwrap_or(default),
+        UTC.load(Ordering::Relaxed),
+    )
+}
+
+fn render_with(t: DateTime<impl TimeZone>, format: &str, utc: bool) -> String {
+    if utc {
+        t.with_timezone(&Utc).format(format).to_string()
+    } else {
+        t.with_timezone(&Local).format(format).to_string()
+    }
+}
+
+/// The day of `t`, e.g. 2025-06-30.
+pub fn format_date(t: DateTime<impl TimeZone>) -> String {
+    render(t, "%Y-%m-%d")
+}
+
+/// `t` to the minute, e.g. 2025-06-30 18:00.
+pub fn format_datetime(t: DateTime<impl TimeZone>) -> String {
+    render(t, "%Y-%m-%d %H:%M")
+}
+
+/// `t` to the minute with its UTC offset, for times that are easy to read
+/// in the wrong zone, such as an order's expiry.
+pub fn format_datetime_zoned(t: DateTime<impl TimeZone>) -> String {
+    render(t, "%Y-%m-%d %H:%M %Z")
+}
+
+/// `t` to the second, for logs of events as they happen.
+pub fn format_timestamp(t: DateTime<impl TimeZone>) -> String {
+    render(t, "%Y-%m-%d %H:%M:%S")
+}
+
+/// The time of day, at the start of each line a running command prints.
+/// `--date-format` doesn't apply, so the lines stay short.
+pub fn format_clock(t: DateTime<impl TimeZone>) -> String {
+    render_with(t, "%H:%M:%S", UTC.load(Ordering::Relaxed))
+}
+
+/// `t` for JSON: ISO 8601 in UTC, to the second.
+pub fn iso(t: DateTime<Utc>) -> String {
+    t.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// A Unix timestamp in seconds for JSON, or `None` if it is out of range.
+pub fn iso_from_unix(secs: i64) -> Option<String> {
+    DateTime::from_timestamp(secs, 0).map(iso)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_in_utc_or_local_time() {
+        let t = DateTime::parse_from_rfc3339("2025-06-30T20:05:09+02:00").unwrap();
+        assert_eq!(
+            render_with(t, "%Y-%m-%d %H:%M %Z", true),
+            "2025-06-30 18:05 UTC"
+        );
+        assert_eq!(render_with(t, "%d/%m/%Y", true), "30/06/2025");
+        let local = t.with_timezone(&Local).format("%H:%M").to_string();
+        assert_eq!(render_with(t, "%H:%M", false), local);
+    }
+
+    #[test]
+    fn bad_date_formats_are_refused() {
+        assert!(set_date_format(Some("%Q".into())).is_err());
+        assert!(set_date_format(Some("%Y-%m-%d %".into())).is_err());
+    }
+
+    #[test]
+    fn json_times_are_iso_8601_in_utc() {
+        let t = DateTime::parse_from_rfc3339("2025-06-30T18:05:09Z")
+            .unwrap()
+            .to_utc();
+        assert_eq!(iso(t), "2025-06-30T18:05:09Z");
+        assert_eq!(
+            iso_from_unix(1_751_306_709).as_deref(),
+            Some("2025-06-30T18:05:09Z")
+        );
+    }
+}
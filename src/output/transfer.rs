@@ -0,0 +1,46 @@
+use alloy::primitives::B256;
+use anyhow::Result;
+use polymarket_client_sdk::types::{Address, Decimal};
+
+use super::{OutputFormat, print_detail_table};
+
+pub struct TransferResult {
+    pub to: Address,
+    pub amount: Decimal,
+    pub tx_hash: B256,
+    /// Only known when the command waited for the transaction to be mined.
+    pub block_number: Option<u64>,
+}
+
+pub fn print_transfer(result: &TransferResult, output: &OutputFormat) -> Result<()> {
+    let polygonscan = format!("https://polygonscan.com/tx/{}", result.tx_hash);
+    match output {
+        OutputFormat::Json => {
+            let json = serde_json::json!({
+                "to": result.to.to_string(),
+                "amount": result.amount.to_string(),
+                "transaction_hash": format!("{}", result.tx_hash),
+                "block_number": result.block_number,
+                "confirmed": result.block_number.is_some(),
+                "polygonscan": polygonscan,
+            });
+            super::print_json(&json)?;
+        }
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Plain => {
+            let rows = vec![
+                ["To".into(), result.to.to_string()],
+                ["Amount".into(), format!("{} USDC", result.amount)],
+                ["Tx Hash".into(), format!("{}", result.tx_hash)],
+                [
+                    "Block".into(),
+                    result
+                        .block_number
+                        .map_or_else(|| "pending".into(), |b| b.to_string()),
+                ],
+                ["Polygonscan".into(), polygonscan],
+            ];
+            print_detail_table(rows);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,201 @@
+use alloy::primitives::B256;
+use anyhow::Result;
+use polymarket_client_sdk::types::{Address, Decimal};
+use serde_json::json;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+use super::{OutputFormat, print_csv, print_detail_table};
+use crate::config::SentTx;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TxState {
+    Pending,
+    Confirmed,
+    Reverted,
+    /// Another transaction with the same nonce was mined instead.
+    Replaced,
+    /// Journaled, but the node no longer knows it.
+    Dropped,
+}
+
+impl std::fmt::Display for TxState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Pending => "pending",
+            Self::Confirmed => "confirmed",
+            Self::Reverted => "reverted",
+            Self::Replaced => "replaced",
+            Self::Dropped => "dropped",
+        })
+    }
+}
+
+/// Fees are in gwei, the fee paid in POL.
+pub struct TxStatus {
+    pub hash: B256,
+    pub state: TxState,
+    pub from: Option<Address>,
+    pub to: Option<Address>,
+    pub nonce: Option<u64>,
+    pub max_fee: Option<Decimal>,
+    pub priority_fee: Option<Decimal>,
+    pub block: Option<u64>,
+    pub confirmations: Option<u64>,
+    pub gas_used: Option<u64>,
+    pub fee_paid: Option<Decimal>,
+}
+
+pub fn print_tx_status(status: &TxStatus, output: &OutputFormat) -> Result<()> {
+    let polygonscan = format!("https://polygonscan.com/tx/{}", status.hash);
+    match output {
+        OutputFormat::Plain => println!("{}", status.state),
+        OutputFormat::Json => super::print_json(&json!({
+            "transaction_hash": status.hash.to_string(),
+            "state": status.state.to_string(),
+            "from": status.from.map(|a| a.to_string()),
+            "to": status.to.map(|a| a.to_string()),
+            "nonce": status.nonce,
+            "max_fee_gwei": status.max_fee.map(|d| d.to_string()),
+            "priority_fee_gwei": status.priority_fee.map(|d| d.to_string()),
+            "block_number": status.block,
+            "confirmations": status.confirmations,
+            "gas_used": status.gas_used,
+            "fee_pol": status.fee_paid.map(|d| d.to_string()),
+            "polygonscan": polygonscan,
+        }))?,
+        OutputFormat::Table | OutputFormat::Csv => {
+            let mut rows = vec![
+                ["Tx Hash".into(), status.hash.to_string()],
+                ["State".into(), status.state.to_string()],
+            ];
+            let mut add = |label: &str, value: Option<String>| {
+                if let Some(value) = value {
+                    rows.push([label.into(), value]);
+                }
+            };
+            add("From", status.from.map(|a| a.to_string()));
+            add("To", status.to.map(|a| a.to_string()));
+            add("Nonce", status.nonce.map(|n| n.to_string()));
+            add("Max fee", status.max_fee.map(|d| format!("{d} gwei")));
+            add(
+                "Priority fee",
+                status.priority_fee.map(|d| format!("{d} gwei")),
+            );
+            add("Block", status.block.map(|b| b.to_string()));
+            add("Confirmations", status.confirmations.map(|c| c.to_string()));
+            add("Gas used", status.gas_used.map(|g| g.to_string()));
+            add(
+                "Fee paid",
+                status.fee_paid.map(|d| format!("{} POL", d.round_dp(6))),
+            );
+            rows.push(["Polygonscan".into(), polygonscan]);
+            print_detail_table(rows);
+        }
+    }
+    Ok(())
+}
+
+/// Fees are in gwei.
+pub struct SpeedupResult {
+    pub replaced: B256,
+    pub hash: B256,
+    pub nonce: u64,
+    pub max_fee: Decimal,
+    pub priority_fee: Decimal,
+}
+
+pub fn print_speedup(result: &SpeedupResult, output: &OutputFormat) -> Result<()> {
+    match output {
+        OutputFormat::Plain => println!("{}", result.hash),
+        OutputFormat::Json => super::print_json(&json!({
+            "replaced": result.replaced.to_string(),
+            "transaction_hash": result.hash.to_string(),
+            "nonce": result.nonce,
+            "max_fee_gwei": result.max_fee.to_string(),
+            "priority_fee_gwei": result.priority_fee.to_string(),
+        }))?,
+        OutputFormat::Table | OutputFormat::Csv => print_detail_table(vec![
+            ["Replaced".into(), result.replaced.to_string()],
+            ["Tx Hash".into(), result.hash.to_string()],
+            ["Nonce".into(), result.nonce.to_string()],
+            ["Max fee".into(), format!("{} gwei", result.max_fee)],
+            [
+                "Priority fee".into(),
+                format!("{} gwei", result.priority_fee),
+            ],
+        ]),
+    }
+    Ok(())
+}
+
+fn fee_gwei(tx: &SentTx) -> String {
+    tx.max_fee
+        .or(tx.gas_price)
+        .and_then(|wei| crate::commands::gas::gwei(wei).ok())
+        .map_or_else(String::new, |d| d.round_dp(2).to_string())
+}
+
+pub fn print_transactions(transactions: &[SentTx], output: &OutputFormat) -> Result<()> {
+    match output {
+        OutputFormat::Table => {
+            if transactions.is_empty() {
+                println!("No transactions sent yet.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "Sent")]
+                sent: String,
+                #[tabled(rename = "Chain")]
+                chain: u64,
+                #[tabled(rename = "Nonce")]
+                nonce: u64,
+                #[tabled(rename = "Hash")]
+                hash: String,
+                #[tabled(rename = "To")]
+                to: String,
+                #[tabled(rename = "Max Fee (gwei)")]
+                max_fee: String,
+            }
+            let rows: Vec<Row> = transactions
+                .iter()
+                .map(|tx| Row {
+                    sent: super::time::format_datetime(tx.sent_at),
+                    chain: tx.chain_id,
+                    nonce: tx.nonce,
+                    hash: tx.hash.to_string(),
+                    to: tx.to.map(|a| a.to_string()).unwrap_or_default(),
+                    max_fee: fee_gwei(tx),
+                })
+                .collect();
+            println!("{}", Table::new(rows).with(Style::rounded()));
+        }
+        OutputFormat::Plain => {
+            for tx in transactions {
+                println!("{}\t{}\t{}", tx.hash, tx.chain_id, tx.nonce);
+            }
+        }
+        OutputFormat::Json => super::print_json(&transactions)?,
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = transactions
+                .iter()
+                .map(|tx| {
+                    vec![
+                        tx.sent_at.to_rfc3339(),
+                        tx.chain_id.to_string(),
+                        tx.nonce.to_string(),
+                        tx.hash.to_string(),
+                        tx.to.map(|a| a.to_string()).unwrap_or_default(),
+                        fee_gwei(tx),
+                    ]
+                })
+                .collect();
+            print_csv(
+                &["sent", "chain_id", "nonce", "hash", "to", "max_fee_gwei"],
+                &rows,
+            );
+        }
+    }
+    Ok(())
+}
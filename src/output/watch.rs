@@ -0,0 +1,159 @@
+use polymarket_client_sdk::gamma::types::response::Market;
+use polymarket_client_sdk::types::Decimal;
+use serde_json::json;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+use super::{OutputFormat, csv_opt, format_decimal, format_price_with, print_csv, truncate};
+use crate::config::WatchEntry;
+
+pub fn print_watchlist(entries: &[WatchEntry], output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            if entries.is_empty() {
+                println!("Watchlist is empty. Add a market with `polymarket watch add <market>`.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "ID")]
+                id: String,
+                #[tabled(rename = "Slug")]
+                slug: String,
+                #[tabled(rename = "Question")]
+                question: String,
+            }
+            let rows: Vec<Row> = entries
+                .iter()
+                .map(|e| Row {
+                    id: e.id.clone(),
+                    slug: e.slug.clone().unwrap_or_else(|| "—".into()),
+                    question: truncate(&e.question, 60),
+                })
+                .collect();
+            let table = Table::new(rows).with(Style::rounded()).to_string();
+            println!("{table}");
+        }
+        OutputFormat::Json => super::print_json(&entries)?,
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = entries
+                .iter()
+                .map(|e| {
+                    vec![
+                        e.id.clone(),
+                        csv_opt(e.slug.as_ref()),
+                        csv_opt(e.condition_id.as_ref()),
+                        e.question.clone(),
+                    ]
+                })
+                .collect();
+            print_csv(&["id", "slug", "condition_id", "question"], &rows);
+        }
+    }
+    Ok(())
+}
+
+/// A price change in cents with an explicit sign, e.g. "+1.50¢".
+fn format_change(change: Decimal) -> String {
+    let cents = change * Decimal::from(100);
+    let sign = if cents.is_sign_negative() { "" } else { "+" };
+    format!("{sign}{cents:.2}¢")
+}
+
+pub fn print_watch_prices(markets: &[Market], output: &OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Table | OutputFormat::Plain => {
+            if markets.is_empty() {
+                println!("Watchlist is empty. Add a market with `polymarket watch add <market>`.");
+                return Ok(());
+            }
+            #[derive(Tabled)]
+            struct Row {
+                #[tabled(rename = "Question")]
+                question: String,
+                #[tabled(rename = "Price (Yes)")]
+                price: String,
+                #[tabled(rename = "24h Change")]
+                change: String,
+                #[tabled(rename = "Volume (24hr)")]
+                volume: String,
+            }
+            let rows: Vec<Row> = markets
+                .iter()
+                .map(|m| Row {
+                    question: truncate(m.question.as_deref().unwrap_or("—"), 60),
+                    price: m
+                        .outcome_prices
+                        .as_ref()
+                        .and_then(|p| p.first())
+                        .map_or_else(
+                            || "—".into(),
+                            |p| format_price_with(*p, super::markets::cents),
+                        ),
+                    change: m
+                        .one_day_price_change
+                        .map_or_else(|| "—".into(), format_change),
+                    volume: m.volume_24hr.map_or_else(|| "—".into(), format_decimal),
+                })
+                .collect();
+            let table = Table::new(rows).with(Style::rounded()).to_string();
+            println!("{table}");
+        }
+        OutputFormat::Json => {
+            let data: Vec<_> = markets
+                .iter()
+                .map(|m| {
+                    json!({
+                        "id": m.id,
+                        "slug": m.slug,
+                        "question": m.question,
+                        "outcomes": m.outcomes,
+                        "outcome_prices": m.outcome_prices,
+                        "one_day_price_change": m.one_day_price_change,
+                        "volume_24hr": m.volume_24hr,
+                    })
+                })
+                .collect();
+            super::print_json(&data)?;
+        }
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = markets
+                .iter()
+                .map(|m| {
+                    vec![
+                        m.id.clone(),
+                        csv_opt(m.slug.as_ref()),
+                        csv_opt(m.question.as_ref()),
+                        csv_opt(m.outcome_prices.as_ref().and_then(|p| p.first())),
+                        csv_opt(m.one_day_price_change),
+                        csv_opt(m.volume_24hr),
+                    ]
+                })
+                .collect();
+            print_csv(
+                &[
+                    "id",
+                    "slug",
+                    "question",
+                    "price_yes",
+                    "one_day_price_change",
+                    "volume_24hr",
+                ],
+                &rows,
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_change_is_signed_cents() {
+        assert_eq!(format_change("0.015".parse().unwrap()), "+1.50¢");
+        assert_eq!(format_change("-0.2".parse().unwrap()), "-20.00¢");
+        assert_eq!(format_change(Decimal::ZERO), "+0.00¢");
+    }
+}
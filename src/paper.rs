@@ -0,0 +1,734 @@
+//! Paper trading: orders fill against the live order book into a local
+//! account (the `paper_*` tables of the database, see [`crate::db`])
+//! instead of being sent to the CLOB. Fills take the book's prices without
+//! moving it, and pay no fees.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use polymarket_client_sdk::clob::types::request::OrderBookSummaryRequest;
+use polymarket_client_sdk::clob::types::response::{OrderBookSummaryResponse, OrderSummary};
+use polymarket_client_sdk::clob::types::{OrderType, Side};
+use polymarket_client_sdk::types::Decimal;
+use rusqlite::{Connection, OptionalExtension, params};
+use rust_decimal::RoundingStrategy;
+use serde::{Deserialize, Serialize};
+
+use crate::clients;
+use crate::commands::clob::parse_token_id;
+use crate::db;
+use crate::errors;
+use crate::net;
+
+/// Shares bought with a USDC amount are rounded down to this many places.
+const SHARE_DP: u32 = 2;
+
+pub fn default_balance() -> Decimal {
+    Decimal::from(1000)
+}
+
+/// One price level of a book, or one fill.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Level {
+    pub price: Decimal,
+    pub size: Decimal,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Book {
+    pub bids: Vec<Level>,
+    pub asks: Vec<Level>,
+}
+
+impl From<&OrderBookSummaryResponse> for Book {
+    fn from(book: &OrderBookSummaryResponse) -> Self {
+        let levels = |side: &[OrderSummary]| {
+            side.iter()
+                .map(|l| Level {
+                    price: l.price,
+                    size: l.size,
+                })
+                .collect()
+        };
+        Self {
+            bids: levels(&book.bids),
+            asks: levels(&book.asks),
+        }
+    }
+}
+
+impl Book {
+    /// Levels a taker on `side` trades against, best first, up to `limit`.
+    pub fn crossing(&self, side: Side, limit: Option<Decimal>) -> Vec<Level> {
+        let buy = side == Side::Buy;
+        let mut levels: Vec<Level> = if buy { &self.asks } else { &self.bids }
+            .iter()
+            .filter(|l| l.size > Decimal::ZERO)
+            .filter(|l| limit.is_none_or(|p| if buy { l.price <= p } else { l.price >= p }))
+            .copied()
+            .collect();
+        if buy {
+            levels.sort_by_key(|l| l.price);
+        } else {
+            levels.sort_by_key(|l| std::cmp::Reverse(l.price));
+        }
+        levels
+    }
+}
+
+/// What a taker wants: a number of shares, or USDC to spend.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Target {
+    Shares(Decimal),
+    Usdc(Decimal),
+}
+
+/// Takes liquidity from `levels` (best first) until `target` is met.
+/// Returns the fills and whether the target was met in full.
+pub fn take(levels: &[Level], target: Target) -> (Vec<Level>, bool) {
+    let mut fills = Vec::new();
+    let (Target::Shares(mut remaining) | Target::Usdc(mut remaining)) = target;
+    for level in levels {
+        let wanted = match target {
+            Target::Shares(_) => remaining,
+            Target::Usdc(_) => {
+                (remaining / level.price).round_dp_with_strategy(SHARE_DP, RoundingStrategy::ToZero)
+            }
+        };
+        if wanted <= Decimal::ZERO {
+            return (fills, true);
+        }
+        let size = wanted.min(level.size);
+        fills.push(Level {
+            price: level.price,
+            size,
+        });
+        remaining -= match target {
+            Target::Shares(_) => size,
+            Target::Usdc(_) => size * level.price,
+        };
+        if size == wanted {
+            return (fills, true);
+        }
+    }
+    (fills, remaining <= Decimal::ZERO)
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Position {
+    pub size: Decimal,
+    pub avg_price: Decimal,
+}
+
+/// A limit order resting in the paper account.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PaperOrder {
+    pub id: String,
+    pub token_id: String,
+    pub side: Side,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub filled: Decimal,
+    pub order_type: OrderType,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PaperOrder {
+    pub fn remaining(&self) -> Decimal {
+        self.size - self.filled
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Fill {
+    pub order_id: String,
+    pub token_id: String,
+    pub side: Side,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// An order to simulate. `price` is the limit; `None` makes it a market
+/// order.
+pub struct NewOrder {
+    pub token_id: String,
+    pub side: Side,
+    pub price: Option<Decimal>,
+    pub target: Target,
+    pub order_type: OrderType,
+    pub post_only: bool,
+}
+
+/// What happened to a new order.
+#[derive(Debug, Serialize)]
+pub struct Placed {
+    pub order_id: String,
+    pub side: Side,
+    /// Shares filled right away
+    pub filled: Decimal,
+    pub avg_price: Option<Decimal>,
+    /// USDC paid (buy) or received (sell)
+    pub value: Decimal,
+    /// Shares left resting on the paper book
+    pub resting: Decimal,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Account {
+    pub balance: Decimal,
+    pub starting_balance: Decimal,
+    #[serde(default)]
+    pub positions: BTreeMap<String, Position>,
+    #[serde(default)]
+    pub orders: Vec<PaperOrder>,
+    #[serde(default)]
+    pub fills: Vec<Fill>,
+    #[serde(default)]
+    next_id: u64,
+}
+
+impl Default for Account {
+    fn default() -> Self {
+        Self::new(default_balance())
+    }
+}
+
+impl Account {
+    pub fn new(balance: Decimal) -> Self {
+        Self {
+            balance,
+            starting_balance: balance,
+            positions: BTreeMap::new(),
+            orders: Vec::new(),
+            fills: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// The saved account, or a fresh one with the default balance.
+    pub fn load() -> Result<Self> {
+        let account = Self::read(&db::open()?).context("Failed to read paper account")?;
+        Ok(account.unwrap_or_default())
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let mut conn = db::open()?;
+        let tx = conn.transaction()?;
+        self.write(&tx).context("Failed to write paper account")?;
+        Ok(tx.commit()?)
+    }
+
+    /// The saved account, or `None` if there is none yet.
+    pub(crate) fn read(conn: &Connection) -> Result<Option<Self>> {
+        let Some((balance, starting_balance, next_id)) = conn
+            .query_row(
+                "SELECT balance, starting_balance, next_id FROM paper_account",
+                [],
+                |row| Ok((db::parsed(row, 0)?, db::parsed(row, 1)?, row.get(2)?)),
+            )
+            .optional()?
+        else {
+            return Ok(None);
+        };
+        let positions = conn
+            .prepare("SELECT token_id, size, avg_price FROM paper_positions")?
+            .query_map([], |row| {
+                Ok((
+                    row.get(0)?,
+                    Position {
+                        size: db::parsed(row, 1)?,
+                        avg_price: db::parsed(row, 2)?,
+                    },
+                ))
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        let orders = conn
+            .prepare(
+                "SELECT id, token_id, side, price, size, filled, order_type, created_at
+                 FROM paper_orders ORDER BY rowid",
+            )?
+            .query_map([], |row| {
+                Ok(PaperOrder {
+                    id: row.get(0)?,
+                    token_id: row.get(1)?,
+                    side: db::named(row, 2)?,
+                    price: db::parsed(row, 3)?,
+                    size: db::parsed(row, 4)?,
+                    filled: db::parsed(row, 5)?,
+                    order_type: db::named(row, 6)?,
+                    created_at: row.get(7)?,
+                })
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        let fills = conn
+            .prepare(
+                "SELECT order_id, token_id, side, price, size, timestamp
+                 FROM paper_fills ORDER BY seq",
+            )?
+            .query_map([], |row| {
+                Ok(Fill {
+                    order_id: row.get(0)?,
+                    token_id: row.get(1)?,
+                    side: db::named(row, 2)?,
+                    price: db::parsed(row, 3)?,
+                    size: db::parsed(row, 4)?,
+                    timestamp: row.get(5)?,
+                })
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(Some(Self {
+            balance,
+            starting_balance,
+            positions,
+            orders,
+            fills,
+            next_id,
+        }))
+    }
+
+    /// Replaces the saved account with this one.
+    pub(crate) fn write(&self, conn: &Connection) -> Result<()> {
+        conn.execute_batch(
+            "DELETE FROM paper_account;
+             DELETE FROM paper_positions;
+             DELETE FROM paper_orders;
+             DELETE FROM paper_fills;",
+        )?;
+        conn.execute(
+            "INSERT INTO paper_account (id, balance, starting_balance, next_id)
+             VALUES (1, ?1, ?2, ?3)",
+            params![
+                self.balance.to_string(),
+                self.starting_balance.to_string(),
+                self.next_id
+            ],
+        )?;
+        let mut insert = conn.prepare(
+            "INSERT INTO paper_positions (token_id, size, avg_price) VALUES (?1, ?2, ?3)",
+        )?;
+        for (token_id, p) in &self.positions {
+            insert.execute(params![
+                token_id,
+                p.size.to_string(),
+                p.avg_price.to_string()
+            ])?;
+        }
+        let mut insert = conn.prepare(
+            "INSERT INTO paper_orders
+             (id, token_id, side, price, size, filled, order_type, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        )?;
+        for o in &self.orders {
+            insert.execute(params![
+                o.id,
+                o.token_id,
+                db::name(&o.side)?,
+                o.price.to_string(),
+                o.size.to_string(),
+                o.filled.to_string(),
+                db::name(&o.order_type)?,
+                o.created_at,
+            ])?;
+        }
+        let mut insert = conn.prepare(
+            "INSERT INTO paper_fills (order_id, token_id, side, price, size, timestamp)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )?;
+        for f in &self.fills {
+            insert.execute(params![
+                f.order_id,
+                f.token_id,
+                db::name(&f.side)?,
+                f.price.to_string(),
+                f.size.to_string(),
+                f.timestamp,
+            ])?;
+        }
+        Ok(())
+    }
+
+    /// Balance not set aside for resting buys.
+    pub fn available_balance(&self) -> Decimal {
+        self.balance
+            - self
+                .orders
+                .iter()
+                .filter(|o| o.side == Side::Buy)
+                .map(|o| o.remaining() * o.price)
+                .sum::<Decimal>()
+    }
+
+    /// Shares of `token_id` not set aside for resting sells.
+    pub fn available_shares(&self, token_id: &str) -> Decimal {
+        let held = self
+            .positions
+            .get(token_id)
+            .map_or(Decimal::ZERO, |p| p.size);
+        held - self
+            .orders
+            .iter()
+            .filter(|o| o.side == Side::Sell && o.token_id == token_id)
+            .map(PaperOrder::remaining)
+            .sum::<Decimal>()
+    }
+
+    /// Fills `order` against `book` as far as its type allows. GTC and GTD
+    /// limit orders rest with whatever is left.
+    pub fn place(&mut self, order: NewOrder, book: &Book) -> Result<Placed> {
+        let NewOrder {
+            token_id,
+            side,
+            price,
+            target,
+            order_type,
+            post_only,
+        } = order;
+        anyhow::ensure!(
+            matches!(side, Side::Buy | Side::Sell),
+            "Invalid side: {side}"
+        );
+        match (side, target) {
+            (Side::Buy, Target::Usdc(amount)) => self.ensure_balance(amount)?,
+            (Side::Buy, Target::Shares(size)) => {
+                self.ensure_balance(size * price.unwrap_or(Decimal::ONE))?;
+            }
+            (_, Target::Shares(size)) => {
+                let available = self.available_shares(&token_id);
+                anyhow::ensure!(
+                    size <= available,
+                    errors::insufficient_balance(format!(
+                        "Insufficient paper position: selling {size} shares, {available} available"
+                    ))
+                );
+            }
+            (_, Target::Usdc(_)) => anyhow::bail!("Sell orders are sized in shares"),
+        }
+
+        let levels = book.crossing(side, price);
+        anyhow::ensure!(
+            !post_only || levels.is_empty(),
+            "Post-only order would cross the book"
+        );
+        let (fills, complete) = take(&levels, target);
+        anyhow::ensure!(
+            order_type != OrderType::FOK || complete,
+            "FOK order can't be filled in full at the current book"
+        );
+        let rests = price.is_some() && matches!(order_type, OrderType::GTC | OrderType::GTD);
+        anyhow::ensure!(
+            rests || !fills.is_empty(),
+            "No liquidity to fill against at the current book"
+        );
+
+        self.next_id += 1;
+        let order_id = format!("paper-{}", self.next_id);
+        for fill in &fills {
+            self.apply_fill(&order_id, &token_id, side, *fill);
+        }
+        let filled: Decimal = fills.iter().map(|f| f.size).sum();
+        let value: Decimal = fills.iter().map(|f| f.price * f.size).sum();
+        let resting = match (rests, price, target) {
+            (true, Some(price), Target::Shares(size)) if filled < size => {
+                self.orders.push(PaperOrder {
+                    id: order_id.clone(),
+                    token_id,
+                    side,
+                    price,
+                    size,
+                    filled,
+                    order_type,
+                    created_at: Utc::now(),
+                });
+                size - filled
+            }
+            _ => Decimal::ZERO,
+        };
+        Ok(Placed {
+            order_id,
+            side,
+            filled,
+            avg_price: (!filled.is_zero()).then(|| value / filled),
+            value,
+            resting,
+        })
+    }
+
+    fn ensure_balance(&self, cost: Decimal) -> Result<()> {
+        let available = self.available_balance();
+        anyhow::ensure!(
+            cost <= available,
+            errors::insufficient_balance(format!(
+                "Insufficient paper balance: need {cost} USDC, {available} available"
+            ))
+        );
+        Ok(())
+    }
+
+    fn apply_fill(&mut self, order_id: &str, token_id: &str, side: Side, fill: Level) {
+        let position = self.positions.entry(token_id.to_string()).or_default();
+        if side == Side::Buy {
+            let size = position.size + fill.size;
+            position.avg_price =
+                (position.avg_price * position.size + fill.price * fill.size) / size;
+            position.size = size;
+            self.balance -= fill.price * fill.size;
+        } else {
+            position.size -= fill.size;
+            self.balance += fill.price * fill.size;
+            if position.size <= Decimal::ZERO {
+                self.positions.remove(token_id);
+            }
+        }
+        self.fills.push(Fill {
+            order_id: order_id.to_string(),
+            token_id: token_id.to_string(),
+            side,
+            price: fill.price,
+            size: fill.size,
+            timestamp: Utc::now(),
+        });
+    }
+
+    /// Fills resting orders that `books` now cross, dropping completed
+    /// ones. Returns the new fills.
+    pub fn match_resting(&mut self, books: &HashMap<String, Book>) -> Vec<Fill> {
+        let before = self.fills.len();
+        for i in 0..self.orders.len() {
+            let order = &self.orders[i];
+            let Some(book) = books.get(&order.token_id) else {
+                continue;
+            };
+            let levels = book.crossing(order.side, Some(order.price));
+            let (fills, _) = take(&levels, Target::Shares(order.remaining()));
+            let (id, token_id, side) = (order.id.clone(), order.token_id.clone(), order.side);
+            for fill in fills {
+                self.apply_fill(&id, &token_id, side, fill);
+                self.orders[i].filled += fill.size;
+            }
+        }
+        self.orders.retain(|o| o.remaining() > Decimal::ZERO);
+        self.fills[before..].to_vec()
+    }
+
+    pub fn cancel(&mut self, id: &str) -> Result<()> {
+        let before = self.orders.len();
+        self.orders.retain(|o| o.id != id);
+        anyhow::ensure!(self.orders.len() < before, "No open paper order {id}");
+        Ok(())
+    }
+}
+
+/// The live book for `token_id`.
+pub async fn fetch_book(token_id: &str) -> Result<Book> {
+    let request = OrderBookSummaryRequest::builder()
+        .token_id(parse_token_id(token_id)?)
+        .build();
+    let book = clients::clob()?.order_book(&request).await?;
+    Ok(Book::from(&book))
+}
+
+/// Loads the account and fills resting orders against the current books,
+/// saving if anything filled. Returns the new fills too.
+pub async fn load_matched() -> Result<(Account, Vec<Fill>)> {
+    let mut account = Account::load()?;
+    if account.orders.is_empty() {
+        return Ok((account, Vec::new()));
+    }
+    let tokens: HashSet<&String> = account.orders.iter().map(|o| &o.token_id).collect();
+    let books: HashMap<String, _> = net::fetch_all(tokens, |token| async move {
+        Ok((token.clone(), fetch_book(token).await?))
+    })
+    .await?
+    .into_iter()
+    .collect();
+    let fills = account.match_resting(&books);
+    if !fills.is_empty() {
+        account.save()?;
+    }
+    Ok((account, fills))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn level(price: Decimal, size: Decimal) -> Level {
+        Level { price, size }
+    }
+
+    fn book() -> Book {
+        Book {
+            bids: vec![level(dec!(0.40), dec!(100)), level(dec!(0.42), dec!(50))],
+            asks: vec![level(dec!(0.47), dec!(200)), level(dec!(0.45), dec!(100))],
+        }
+    }
+
+    fn limit(side: Side, price: Decimal, size: Decimal, order_type: OrderType) -> NewOrder {
+        NewOrder {
+            token_id: "1".into(),
+            side,
+            price: Some(price),
+            target: Target::Shares(size),
+            order_type,
+            post_only: false,
+        }
+    }
+
+    #[test]
+    fn crossing_sorts_best_first_and_stops_at_limit() {
+        let asks = book().crossing(Side::Buy, Some(dec!(0.46)));
+        assert_eq!(asks, [level(dec!(0.45), dec!(100))]);
+        let bids = book().crossing(Side::Sell, None);
+        assert_eq!(
+            bids,
+            [level(dec!(0.42), dec!(50)), level(dec!(0.40), dec!(100))]
+        );
+    }
+
+    #[test]
+    fn take_walks_levels_for_shares_and_usdc() {
+        let asks = book().crossing(Side::Buy, None);
+        let (fills, complete) = take(&asks, Target::Shares(dec!(150)));
+        assert!(complete);
+        assert_eq!(
+            fills,
+            [level(dec!(0.45), dec!(100)), level(dec!(0.47), dec!(50))]
+        );
+
+        // 45 USDC buys the first level; the rest buys 10.63 (rounded down) at 0.47.
+        let (fills, complete) = take(&asks, Target::Usdc(dec!(50)));
+        assert!(complete);
+        assert_eq!(
+            fills,
+            [level(dec!(0.45), dec!(100)), level(dec!(0.47), dec!(10.63))]
+        );
+
+        let (_, complete) = take(&asks, Target::Shares(dec!(1000)));
+        assert!(!complete);
+    }
+
+    #[test]
+    fn limit_buy_fills_what_crosses_and_rests_the_rest() {
+        let mut account = Account::default();
+        let placed = account
+            .place(
+                limit(Side::Buy, dec!(0.45), dec!(150), OrderType::GTC),
+                &book(),
+            )
+            .unwrap();
+        assert_eq!(placed.filled, dec!(100));
+        assert_eq!(placed.resting, dec!(50));
+        assert_eq!(account.balance, dec!(955));
+        // The resting 50 shares at 0.45 are set aside.
+        assert_eq!(account.available_balance(), dec!(932.5));
+        assert_eq!(account.positions["1"].size, dec!(100));
+
+        let mut books = HashMap::new();
+        books.insert(
+            "1".to_string(),
+            Book {
+                bids: Vec::new(),
+                asks: vec![level(dec!(0.44), dec!(80))],
+            },
+        );
+        let fills = account.match_resting(&books);
+        assert_eq!(fills.len(), 1);
+        assert!(account.orders.is_empty());
+        assert_eq!(account.positions["1"].size, dec!(150));
+        assert_eq!(account.balance, dec!(933));
+    }
+
+    #[test]
+    fn order_types_follow_clob_rules() {
+        let mut account = Account::default();
+        let err = account
+            .place(
+                limit(Side::Buy, dec!(0.50), dec!(1000), OrderType::FOK),
+                &book(),
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("FOK"), "got: {err}");
+
+        let placed = account
+            .place(
+                limit(Side::Buy, dec!(0.45), dec!(150), OrderType::FAK),
+                &book(),
+            )
+            .unwrap();
+        assert_eq!(placed.resting, Decimal::ZERO);
+        assert!(account.orders.is_empty());
+
+        let mut post_only = limit(Side::Buy, dec!(0.46), dec!(10), OrderType::GTC);
+        post_only.post_only = true;
+        assert!(account.place(post_only, &book()).is_err());
+    }
+
+    #[test]
+    fn market_sell_realizes_cash_and_checks_position() {
+        let mut account = Account::default();
+        let sell = |size: Decimal| NewOrder {
+            token_id: "1".into(),
+            side: Side::Sell,
+            price: None,
+            target: Target::Shares(size),
+            order_type: OrderType::FOK,
+            post_only: false,
+        };
+        assert!(account.place(sell(dec!(10)), &book()).is_err());
+
+        account
+            .place(
+                limit(Side::Buy, dec!(0.45), dec!(100), OrderType::FOK),
+                &book(),
+            )
+            .unwrap();
+        let placed = account.place(sell(dec!(100)), &book()).unwrap();
+        assert_eq!(placed.value, dec!(41));
+        assert!(account.positions.is_empty());
+        assert_eq!(account.balance, dec!(996));
+    }
+
+    #[test]
+    fn insufficient_balance_is_rejected() {
+        let mut account = Account::new(dec!(10));
+        let err = account
+            .place(
+                limit(Side::Buy, dec!(0.45), dec!(100), OrderType::GTC),
+                &book(),
+            )
+            .unwrap_err();
+        assert!(
+            err.to_string().starts_with("Insufficient paper balance"),
+            "got: {err}"
+        );
+    }
+
+    #[test]
+    fn account_round_trips_through_the_database() {
+        let conn = db::open_in_memory();
+        assert!(Account::read(&conn).unwrap().is_none());
+        let mut account = Account::default();
+        account
+            .place(
+                limit(Side::Buy, dec!(0.45), dec!(150), OrderType::GTC),
+                &book(),
+            )
+            .unwrap();
+        account.write(&conn).unwrap();
+        let read = Account::read(&conn).unwrap().unwrap();
+        assert_eq!(read.balance, account.balance);
+        assert_eq!(read.positions, account.positions);
+        assert_eq!(read.orders.len(), 1);
+        assert_eq!(read.orders[0].side, Side::Buy);
+        assert_eq!(read.orders[0].order_type, OrderType::GTC);
+        assert_eq!(read.fills.len(), account.fills.len());
+        assert_eq!(read.next_id, account.next_id);
+
+        // Writing again replaces rather than adds.
+        Account::default().write(&conn).unwrap();
+        assert!(Account::read(&conn).unwrap().unwrap().orders.is_empty());
+    }
+}
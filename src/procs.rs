@@ -0,0 +1,238 @@
+//! Long-running commands that can trade (`daemon run`, `copy watch`, `order
+//! twap`, a foreground `clob stop`, `alert run`) register here while they
+//! run, so `panic` can find and stop them.
+//!
+//! Each writes a file in the config directory's `run/` and holds an
+//! exclusive lock on it until it exits. A file nobody holds the lock on was
+//! left by a process that died, so a recycled PID is never signalled. Only
+//! Linux and macOS have the locks and signals this needs; elsewhere nothing
+//! registers.
+
+use std::fs::{self, File};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+
+/// How long a signalled process gets to exit before it is reported as
+/// still running.
+const STOP_GRACE: Duration = Duration::from_secs(5);
+
+/// A registered process, as its file describes it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Process {
+    pub pid: u32,
+    pub command: String,
+    pub started: chrono::DateTime<chrono::Utc>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+/// This process's entry, removed when dropped.
+pub struct Registration {
+    path: PathBuf,
+    // Holds the lock for as long as the command runs.
+    _file: File,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Registers this process as running `command` until the returned guard is
+/// dropped. `None`, after a warning, when it can't be, as with
+/// `--no-config`: the command still runs, but `panic` won't see it.
+pub fn register(command: &str) -> Option<Registration> {
+    if config::no_config() {
+        return None;
+    }
+    match try_register(command) {
+        Ok(registration) => registration,
+        Err(e) => {
+            eprintln!("Warning: `panic` won't be able to stop this command: {e:#}");
+            None
+        }
+    }
+}
+
+#[cfg(unix)]
+fn try_register(command: &str) -> Result<Option<Registration>> {
+    use std::io::Write;
+
+    let dir = config::run_dir()?;
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let pid = std::process::id();
+    // Written under another name and renamed once locked, so `running`
+    // never finds it unlocked and takes it for stale.
+    let partial = dir.join(format!("{pid}.partial"));
+    let path = dir.join(format!("{pid}.json"));
+    let mut file = File::create(&partial)
+        .with_context(|| format!("Failed to create {}", partial.display()))?;
+    anyhow::ensure!(
+        sys::try_lock(&file)?,
+        "{} is locked by another process",
+        partial.display()
+    );
+    let process = Process {
+        pid,
+        command: command.to_string(),
+        started: chrono::Utc::now(),
+        path: PathBuf::new(),
+    };
+    file.write_all(serde_json::to_string(&process)?.as_bytes())?;
+    fs::rename(&partial, &path).with_context(|| format!("Failed to create {}", path.display()))?;
+    Ok(Some(Registration { path, _file: file }))
+}
+
+#[cfg(not(unix))]
+fn try_register(_command: &str) -> Result<Option<Registration>> {
+    Ok(None)
+}
+
+/// The other registered processes still running. Files left by processes
+/// that have exited are removed along the way.
+pub fn running() -> Result<Vec<Process>> {
+    if config::no_config() {
+        return Ok(Vec::new());
+    }
+    let dir = config::run_dir()?;
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+    let mut running = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_none_or(|e| e != "json") {
+            continue;
+        }
+        let Ok(file) = File::open(&path) else {
+            continue;
+        };
+        if !sys::held(&file)? {
+            let _ = fs::remove_file(&path);
+            continue;
+        }
+        let Ok(process) = serde_json::from_str::<Process>(&fs::read_to_string(&path)?) else {
+            continue;
+        };
+        if process.pid != std::process::id() {
+            running.push(Process { path, ..process });
+        }
+    }
+    running.sort_by_key(|p| p.started);
+    Ok(running)
+}
+
+/// Sends each of `processes` SIGTERM and waits for them to exit. Returns
+/// each with the reason it couldn't be stopped, if any.
+pub async fn stop(processes: Vec<Process>) -> Vec<(Process, Option<String>)> {
+    let mut signalled = Vec::with_capacity(processes.len());
+    for process in processes {
+        let error = sys::terminate(process.pid).err().map(|e| format!("{e:#}"));
+        signalled.push((process, error));
+    }
+    let deadline = tokio::time::Instant::now() + STOP_GRACE;
+    loop {
+        let waiting = signalled
+            .iter()
+            .any(|(p, error)| error.is_none() && still_running(p));
+        if !waiting || tokio::time::Instant::now() >= deadline {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    for (process, error) in &mut signalled {
+        if error.is_none() && still_running(process) {
+            *error = Some(format!(
+                "still running {}s after SIGTERM",
+                STOP_GRACE.as_secs()
+            ));
+        } else if error.is_none() {
+            let _ = fs::remove_file(&process.path);
+        }
+    }
+    signalled
+}
+
+fn still_running(process: &Process) -> bool {
+    File::open(&process.path)
+        .ok()
+        .is_some_and(|file| sys::held(&file).unwrap_or(true))
+}
+
+#[cfg(unix)]
+mod sys {
+    use std::fs::File;
+    use std::io;
+    use std::os::fd::AsRawFd;
+
+    use anyhow::Result;
+
+    /// Takes an exclusive lock on `file` without waiting; false if another
+    /// process holds one.
+    pub fn try_lock(file: &File) -> Result<bool> {
+        // SAFETY: the descriptor stays open for the call.
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0 {
+            return Ok(true);
+        }
+        let err = io::Error::last_os_error();
+        if err.raw_os_error() == Some(libc::EWOULDBLOCK) {
+            return Ok(false);
+        }
+        Err(err.into())
+    }
+
+    /// Whether another process holds `file`'s lock. A lock taken to find
+    /// out is released when `file` is closed.
+    pub fn held(file: &File) -> Result<bool> {
+        try_lock(file).map(|locked| !locked)
+    }
+
+    pub fn terminate(pid: u32) -> Result<()> {
+        let pid = libc::pid_t::try_from(pid)?;
+        // SAFETY: kill has no memory effects; the lock check has made sure
+        // `pid` is still the process that registered.
+        if unsafe { libc::kill(pid, libc::SIGTERM) } == 0 {
+            return Ok(());
+        }
+        Err(io::Error::last_os_error().into())
+    }
+}
+
+#[cfg(not(unix))]
+mod sys {
+    use std::fs::File;
+
+    use anyhow::Result;
+
+    pub fn held(_file: &File) -> Result<bool> {
+        Ok(false)
+    }
+
+    pub fn terminate(_pid: u32) -> Result<()> {
+        anyhow::bail!("Stopping processes needs Unix signals, which this platform lacks")
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_lock_held_elsewhere_marks_the_process_alive() {
+        let path = std::env::temp_dir().join(format!("polymarket-procs-{}", std::process::id()));
+        let holder = File::create(&path).unwrap();
+        assert!(sys::try_lock(&holder).unwrap());
+        // flock locks belong to the open file, so a second open contends
+        // like another process would.
+        assert!(sys::held(&File::open(&path).unwrap()).unwrap());
+        drop(holder);
+        assert!(!sys::held(&File::open(&path).unwrap()).unwrap());
+        fs::remove_file(&path).unwrap();
+    }
+}
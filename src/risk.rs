@@ -0,0 +1,316 @@
+//! Limits on what orders may commit, from the profile's `risk` settings, so
+//! a fat-fingered size or a bot gone wrong can only lose so much.
+//!
+//! Amounts are USDC notional. An order's is its price times its size, or a
+//! market buy's amount; a market sell's shares are valued at the best bid.
+//! A market's exposure is what the wallet's positions in it are worth now
+//! plus what its resting buys have committed. Buys add to exposure and
+//! sells only take from it, so a sell is held to the per-order limit alone.
+//!
+//! An order over a limit is refused unless `--override-risk` is given.
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::Result;
+use polymarket_client_sdk::auth::Normal;
+use polymarket_client_sdk::auth::state::Authenticated;
+use polymarket_client_sdk::clob;
+use polymarket_client_sdk::clob::types::Side;
+use polymarket_client_sdk::clob::types::request::OrderBookSummaryRequest;
+use polymarket_client_sdk::clob::types::response::OpenOrderResponse;
+use polymarket_client_sdk::data::types::response::Position;
+use polymarket_client_sdk::types::{Address, B256, Decimal, U256};
+
+use crate::clients;
+use crate::commands::portfolio::{fetch_open_orders, fetch_positions};
+use crate::config::{self, Risk};
+use crate::errors;
+use crate::net;
+use crate::paper::{Book, Target};
+
+static OVERRIDE: AtomicBool = AtomicBool::new(false);
+
+pub fn set_override(yes: bool) {
+    OVERRIDE.store(yes, Ordering::Relaxed);
+}
+
+pub(crate) fn overridden() -> bool {
+    OVERRIDE.load(Ordering::Relaxed)
+}
+
+/// An order about to be sent.
+#[derive(Clone, Debug)]
+pub struct Proposed {
+    pub token: U256,
+    pub side: Side,
+    /// Its limit price; `None` for a market order without one.
+    pub price: Option<Decimal>,
+    pub target: Target,
+    /// An open order it takes the place of, so what that one committed
+    /// isn't counted twice.
+    pub replaces: Option<String>,
+}
+
+impl Proposed {
+    pub fn limit(token: U256, side: Side, price: Decimal, size: Decimal) -> Self {
+        Self {
+            token,
+            side,
+            price: Some(price),
+            target: Target::Shares(size),
+            replaces: None,
+        }
+    }
+
+    /// A market order for `amount`: USDC for a buy, shares for a sell.
+    pub fn market(token: U256, side: Side, price: Option<Decimal>, amount: Decimal) -> Self {
+        let target = if side == Side::Sell {
+            Target::Shares(amount)
+        } else {
+            Target::Usdc(amount)
+        };
+        Self {
+            token,
+            side,
+            price,
+            target,
+            replaces: None,
+        }
+    }
+
+    pub fn replacing(self, order_id: &str) -> Self {
+        Self {
+            replaces: Some(order_id.to_string()),
+            ..self
+        }
+    }
+}
+
+/// A proposed order with its market and USDC notional worked out.
+struct Valued {
+    market: B256,
+    side: Side,
+    notional: Decimal,
+}
+
+#[derive(Debug, Default)]
+struct Exposure {
+    markets: HashMap<B256, Decimal>,
+    total: Decimal,
+}
+
+/// What the wallet has at stake: positions at their current value and the
+/// unfilled part of its resting buys, less those in `replaced`.
+fn exposure(positions: &[Position], orders: &[OpenOrderResponse], replaced: &[&str]) -> Exposure {
+    let held = positions.iter().map(|p| (p.condition_id, p.current_value));
+    let bid = orders
+        .iter()
+        .filter(|o| o.side == Side::Buy && !replaced.contains(&o.id.as_str()))
+        .map(|o| (o.market, (o.original_size - o.size_matched) * o.price));
+    let mut exposure = Exposure::default();
+    for (market, usdc) in held.chain(bid) {
+        *exposure.markets.entry(market).or_default() += usdc;
+        exposure.total += usdc;
+    }
+    exposure
+}
+
+fn usdc(n: Decimal) -> Decimal {
+    n.round_dp(2).normalize()
+}
+
+/// The limits `orders` break, taken together on top of `exposure`.
+fn breaches(limits: &Risk, orders: &[Valued], mut exposure: Exposure) -> Vec<String> {
+    let mut breaches = Vec::new();
+    let mut bought = Vec::new();
+    for o in orders {
+        if let Some(max) = limits.max_order_size
+            && o.notional > max
+        {
+            breaches.push(format!(
+                "an order of {} USDC is over risk.max_order_size ({max})",
+                usdc(o.notional)
+            ));
+        }
+        if o.side == Side::Buy {
+            *exposure.markets.entry(o.market).or_default() += o.notional;
+            exposure.total += o.notional;
+            if !bought.contains(&o.market) {
+                bought.push(o.market);
+            }
+        }
+    }
+    if let Some(max) = limits.max_market_notional {
+        for market in &bought {
+            let at_stake = exposure.markets[market];
+            if at_stake > max {
+                breaches.push(format!(
+                    "{} USDC in market {market} would be over risk.max_market_notional ({max})",
+                    usdc(at_stake)
+                ));
+            }
+        }
+    }
+    if let Some(max) = limits.max_total_exposure
+        && !bought.is_empty()
+        && exposure.total > max
+    {
+        breaches.push(format!(
+            "{} USDC across all markets would be over risk.max_total_exposure ({max})",
+            usdc(exposure.total)
+        ));
+    }
+    breaches
+}
+
+/// Checks `orders` against the profile's risk limits before they are sent.
+/// Orders over a limit fail with a validation error, or are let through
+/// with a warning under `--override-risk`. `owner` resolves the trading
+/// wallet; it is only awaited when exposure has to be looked up, and
+/// without limits nothing is fetched at all.
+pub async fn check(
+    client: &clob::Client<Authenticated<Normal>>,
+    orders: &[Proposed],
+    owner: impl Future<Output = Result<Address>>,
+) -> Result<()> {
+    check_with_override(client, orders, owner, overridden()).await
+}
+
+/// Like [`check`], with `allow` in place of this run's `--override-risk`:
+/// for orders sent later on the say-so of another run, such as a daemon
+/// stop, which keeps the flag it was armed with.
+pub async fn check_with_override(
+    client: &clob::Client<Authenticated<Normal>>,
+    orders: &[Proposed],
+    owner: impl Future<Output = Result<Address>>,
+    allow: bool,
+) -> Result<()> {
+    let limits = config::risk_limits();
+    if limits.is_empty() || orders.is_empty() {
+        return Ok(());
+    }
+
+    let public = clients::clob()?;
+    let mut books = HashMap::new();
+    let mut valued = Vec::with_capacity(orders.len());
+    for order in orders {
+        if let Entry::Vacant(entry) = books.entry(order.token) {
+            let request = OrderBookSummaryRequest::builder()
+                .token_id(order.token)
+                .build();
+            entry.insert(net::retry(|| public.order_book(&request)).await?);
+        }
+        let book = &books[&order.token];
+        let notional = match order.target {
+            Target::Usdc(usdc) => usdc,
+            Target::Shares(shares) => {
+                // A share pays out at most 1 USDC, which values an order
+                // with nothing on the book to take.
+                let price = order.price.or_else(|| {
+                    Book::from(book)
+                        .crossing(order.side, None)
+                        .first()
+                        .map(|l| l.price)
+                });
+                shares * price.unwrap_or(Decimal::ONE)
+            }
+        };
+        valued.push(Valued {
+            market: book.market,
+            side: order.side,
+            notional,
+        });
+    }
+
+    let needs_exposure =
+        limits.max_market_notional.is_some() || limits.max_total_exposure.is_some();
+    let exposure = if needs_exposure && valued.iter().any(|v| v.side == Side::Buy) {
+        let owner = owner.await?;
+        let data = clients::data()?;
+        let (positions, open) =
+            tokio::try_join!(fetch_positions(&data, owner), fetch_open_orders(client))?;
+        let replaced: Vec<&str> = orders
+            .iter()
+            .filter_map(|o| o.replaces.as_deref())
+            .collect();
+        exposure(&positions, &open, &replaced)
+    } else {
+        Exposure::default()
+    };
+
+    let breaches = breaches(&limits, &valued, exposure);
+    if breaches.is_empty() {
+        return Ok(());
+    }
+    if allow {
+        eprintln!(
+            "Warning: over the risk limits (--override-risk): {}",
+            breaches.join("; ")
+        );
+        return Ok(());
+    }
+    Err(errors::validation(format!(
+        "Refused by the profile's risk limits: {}. Pass --override-risk to send it anyway",
+        breaches.join("; ")
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn buy(market: u8, notional: Decimal) -> Valued {
+        Valued {
+            market: B256::with_last_byte(market),
+            side: Side::Buy,
+            notional,
+        }
+    }
+
+    fn limits(order: Option<Decimal>, market: Option<Decimal>, total: Option<Decimal>) -> Risk {
+        Risk {
+            max_order_size: order,
+            max_market_notional: market,
+            max_total_exposure: total,
+        }
+    }
+
+    #[test]
+    fn breaches_add_orders_to_what_is_already_at_stake() {
+        let mut exposure = Exposure::default();
+        exposure.markets.insert(B256::with_last_byte(1), dec!(80));
+        exposure.total = dec!(80);
+        let found = breaches(
+            &limits(None, Some(dec!(100)), Some(dec!(150))),
+            &[buy(1, dec!(15)), buy(1, dec!(10)), buy(2, dec!(50))],
+            exposure,
+        );
+        assert_eq!(found.len(), 2);
+        assert!(found[0].starts_with("105 USDC in market 0x"), "{found:?}");
+        assert!(
+            found[1].starts_with("155 USDC across all markets"),
+            "{found:?}"
+        );
+    }
+
+    #[test]
+    fn sells_only_face_the_order_limit() {
+        let exposure = Exposure {
+            total: dec!(1000),
+            ..Exposure::default()
+        };
+        let sell = Valued {
+            side: Side::Sell,
+            ..buy(1, dec!(40))
+        };
+        let risk = limits(Some(dec!(50)), Some(dec!(10)), Some(dec!(10)));
+        assert!(breaches(&risk, &[sell], exposure).is_empty());
+        assert_eq!(
+            breaches(&risk, &[buy(1, dec!(60))], Exposure::default())[0],
+            "an order of 60 USDC is over risk.max_order_size (50)"
+        );
+    }
+}
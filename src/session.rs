@@ -0,0 +1,146 @@
+//! The CLOB session: API credentials derived for the wallet, cached in the
+//! config directory so authenticated commands don't sign and request them
+//! again on every run.
+//!
+//! Each profile's session is a V3 keystore file in `sessions/` whose
+//! password comes from the wallet's private key, so only that wallet can
+//! read it back. A Ledger has no key to derive it from, so it gets no
+//! session; save its API key in the profile with `apikey derive --save`
+//! instead.
+
+use std::fs;
+
+use alloy::primitives::{Address, keccak256};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use polymarket_client_sdk::auth::{Credentials, ExposeSecret as _, Signer as _, Uuid};
+use serde::{Deserialize, Serialize};
+
+use crate::auth::WalletSigner;
+use crate::config;
+
+/// How long a session lasts unless `auth login --ttl` says otherwise.
+pub const DEFAULT_TTL: &str = "24h";
+
+/// Mixed into the keystore password so it differs from anything else
+/// derived from the key.
+const PASSWORD_DOMAIN: &[u8] = b"polymarket-cli session";
+
+#[derive(Serialize, Deserialize)]
+struct Cached {
+    address: Address,
+    key: String,
+    secret: String,
+    passphrase: String,
+    created: DateTime<Utc>,
+    expires: DateTime<Utc>,
+}
+
+pub struct Session {
+    pub credentials: Credentials,
+    pub created: DateTime<Utc>,
+    pub expires: DateTime<Utc>,
+}
+
+impl Session {
+    pub fn is_expired(&self) -> bool {
+        self.expires <= Utc::now()
+    }
+}
+
+fn password(signer: &WalletSigner) -> Option<String> {
+    match signer {
+        WalletSigner::Local(s) => {
+            let mut material = PASSWORD_DOMAIN.to_vec();
+            material.extend_from_slice(s.to_bytes().as_slice());
+            Some(keccak256(material).to_string())
+        }
+        WalletSigner::Ledger(_) => None,
+    }
+}
+
+/// The active profile's session, expired or not. `None` if there is none,
+/// the signer is a Ledger, or it was cached for another wallet.
+pub fn load(signer: &WalletSigner) -> Result<Option<Session>> {
+    let Some(password) = password(signer) else {
+        return Ok(None);
+    };
+    let path = config::sessions_dir()?.join(config::session_file());
+    if !path.exists() {
+        return Ok(None);
+    }
+    // A keystore another key wrote fails its MAC check: not this wallet's.
+    let Ok(plain) = eth_keystore::decrypt_key(&path, password) else {
+        tracing::debug!(path = %path.display(), "session belongs to another wallet");
+        return Ok(None);
+    };
+    let cached: Cached = serde_json::from_slice(&plain).context("Invalid cached session")?;
+    if cached.address != signer.address() {
+        return Ok(None);
+    }
+    let key = Uuid::parse_str(&cached.key).context("Invalid cached API key")?;
+    Ok(Some(Session {
+        credentials: Credentials::new(key, cached.secret, cached.passphrase),
+        created: cached.created,
+        expires: cached.expires,
+    }))
+}
+
+/// Caches `credentials` for `ttl`, replacing any earlier session. Returns
+/// `None` for a Ledger, which can't have one.
+pub fn save(
+    signer: &WalletSigner,
+    credentials: &Credentials,
+    ttl: Duration,
+) -> Result<Option<Session>> {
+    let Some(password) = password(signer) else {
+        return Ok(None);
+    };
+    let created = Utc::now();
+    let cached = Cached {
+        address: signer.address(),
+        key: credentials.key().to_string(),
+        secret: credentials.secret().expose_secret().to_string(),
+        passphrase: credentials.passphrase().expose_secret().to_string(),
+        created,
+        expires: created + ttl,
+    };
+    let dir = config::ensure_dir(&config::sessions_dir()?)?;
+    eth_keystore::encrypt_key(
+        &dir,
+        &mut rand::thread_rng(),
+        serde_json::to_vec(&cached)?,
+        password,
+        Some(&config::session_file()),
+    )
+    .context("Failed to write session file")?;
+    Ok(Some(Session {
+        credentials: credentials.clone(),
+        created,
+        expires: cached.expires,
+    }))
+}
+
+/// Deletes the active profile's session; `false` if there was none.
+pub fn remove() -> Result<bool> {
+    let path = config::sessions_dir()?.join(config::session_file());
+    if !path.exists() {
+        return Ok(false);
+    }
+    fs::remove_file(&path).context("Failed to remove session file")?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn password_differs_per_key() {
+        let signer = |key: &str| WalletSigner::Local(key.parse().unwrap());
+        let a = signer("0x0123456789012345678901234567890123456789012345678901234567890123");
+        let b = signer("0x0123456789012345678901234567890123456789012345678901234567890124");
+        assert_eq!(password(&a), password(&a));
+        assert_ne!(password(&a), password(&b));
+    }
+}
@@ -1,7 +1,5 @@
 use clap::Parser;
 
-use crate::output::OutputFormat;
-
 pub async fn run_shell() {
     println!();
     println!("  Polymarket CLI · Interactive Shell");
@@ -46,16 +44,26 @@ pub async fn run_shell() {
 
                 match crate::Cli::try_parse_from(&full_args) {
                     Ok(cli) => {
-                        let output = cli.output;
-                        if let Err(e) = crate::run(cli).await {
-                            match output {
-                                OutputFormat::Json => {
-                                    println!("{}", serde_json::json!({"error": e.to_string()}));
-                                }
-                                OutputFormat::Table => {
-                                    eprintln!("Error: {e}");
-                                }
-                            }
+                        // A `--profile`, `--ledger`, `--paper`, `--no-config`, `--funder`,
+                        // endpoint or network flag on one shell command shouldn't stick
+                        // to the rest of the session.
+                        let profile = crate::config::profile_override();
+                        let ledger = crate::config::ledger_enabled();
+                        let paper = crate::config::paper_flag();
+                        let no_config = crate::config::no_config_flag();
+                        let funder = crate::config::funder_override();
+                        let endpoints = crate::config::endpoint_overrides();
+                        let network = crate::config::network_overrides();
+                        let result = crate::run(cli).await;
+                        let _ = crate::config::set_profile_override(profile);
+                        crate::config::set_ledger(ledger);
+                        crate::config::set_paper(paper);
+                        crate::config::set_no_config(no_config);
+                        crate::config::set_funder_override(funder);
+                        let _ = crate::config::set_endpoint_overrides(endpoints);
+                        crate::config::set_network_overrides(network);
+                        if let Err(e) = result {
+                            crate::errors::report(&e);
                         }
                     }
                     Err(e) => {
@@ -0,0 +1,330 @@
+use std::str::FromStr;
+
+use polymarket_client_sdk::clob::types::Side;
+use polymarket_client_sdk::clob::types::response::{OpenOrderResponse, OrderBookSummaryResponse};
+use polymarket_client_sdk::data::types::response::Position;
+use polymarket_client_sdk::types::{Decimal, U256};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+pub struct Outcome {
+    pub name: String,
+    pub token_id: U256,
+    pub price: Option<Decimal>,
+}
+
+pub struct WatchedMarket {
+    pub question: String,
+    pub outcomes: Vec<Outcome>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Focus {
+    Markets,
+    Orders,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Mode {
+    Normal,
+    /// Typing `PRICE SIZE` for a limit order on the selected outcome.
+    OrderEntry {
+        side: Side,
+        input: String,
+    },
+    ConfirmCancel {
+        order_id: String,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Action {
+    None,
+    Quit,
+    Refresh,
+    /// The selected outcome changed, so its book needs fetching.
+    LoadBook,
+    PlaceOrder {
+        token_id: U256,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+    },
+    CancelOrder(String),
+}
+
+pub struct App {
+    pub markets: Vec<WatchedMarket>,
+    pub selected_market: usize,
+    pub selected_outcome: usize,
+    pub focus: Focus,
+    pub book: Option<OrderBookSummaryResponse>,
+    pub positions: Vec<Position>,
+    pub orders: Vec<OpenOrderResponse>,
+    pub selected_order: usize,
+    pub mode: Mode,
+    pub status: String,
+    /// Whether a wallet is configured, i.e. orders and positions are available.
+    pub trading: bool,
+}
+
+/// Parses order entry input of the form `PRICE SIZE`, e.g. `0.45 100`.
+pub fn parse_order_input(input: &str) -> anyhow::Result<(Decimal, Decimal)> {
+    let mut parts = input.split_whitespace();
+    let (Some(price), Some(size), None) = (parts.next(), parts.next(), parts.next()) else {
+        anyhow::bail!("Enter a price and size, e.g. 0.45 100");
+    };
+    let price = Decimal::from_str(price).map_err(|_| anyhow::anyhow!("Invalid price: {price}"))?;
+    let size = Decimal::from_str(size).map_err(|_| anyhow::anyhow!("Invalid size: {size}"))?;
+    anyhow::ensure!(
+        price > Decimal::ZERO && price < Decimal::ONE,
+        "Price must be between 0 and 1"
+    );
+    anyhow::ensure!(size > Decimal::ZERO, "Size must be positive");
+    Ok((price, size))
+}
+
+impl App {
+    pub fn new(markets: Vec<WatchedMarket>, trading: bool) -> Self {
+        Self {
+            markets,
+            selected_market: 0,
+            selected_outcome: 0,
+            focus: Focus::Markets,
+            book: None,
+            positions: Vec::new(),
+            orders: Vec::new(),
+            selected_order: 0,
+            mode: Mode::Normal,
+            status: String::new(),
+            trading,
+        }
+    }
+
+    pub fn selected(&self) -> Option<(&WatchedMarket, &Outcome)> {
+        let market = self.markets.get(self.selected_market)?;
+        Some((market, market.outcomes.get(self.selected_outcome)?))
+    }
+
+    pub fn selected_token(&self) -> Option<U256> {
+        self.selected().map(|(_, o)| o.token_id)
+    }
+
+    /// Keeps the order cursor in range after the order list is refreshed.
+    pub fn set_orders(&mut self, orders: Vec<OpenOrderResponse>) {
+        self.orders = orders;
+        self.selected_order = self.selected_order.min(self.orders.len().saturating_sub(1));
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Action {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Action::Quit;
+        }
+        match std::mem::replace(&mut self.mode, Mode::Normal) {
+            Mode::Normal => self.handle_normal_key(key.code),
+            Mode::OrderEntry { side, input } => self.handle_entry_key(key.code, side, input),
+            Mode::ConfirmCancel { order_id } => {
+                if matches!(key.code, KeyCode::Char('y' | 'Y')) {
+                    Action::CancelOrder(order_id)
+                } else {
+                    self.status = "Cancel aborted".into();
+                    Action::None
+                }
+            }
+        }
+    }
+
+    fn handle_normal_key(&mut self, code: KeyCode) -> Action {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => Action::Quit,
+            KeyCode::Char('r') => Action::Refresh,
+            KeyCode::Tab => {
+                self.focus = match self.focus {
+                    Focus::Markets if self.trading => Focus::Orders,
+                    _ => Focus::Markets,
+                };
+                Action::None
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Left | KeyCode::Char('h') => self.move_outcome(-1),
+            KeyCode::Right | KeyCode::Char('l') => self.move_outcome(1),
+            KeyCode::Char(c @ ('b' | 's')) => {
+                if !self.trading {
+                    self.status = "No wallet configured: trading is disabled".into();
+                } else if self.selected().is_some() {
+                    let side = if c == 'b' { Side::Buy } else { Side::Sell };
+                    self.mode = Mode::OrderEntry {
+                        side,
+                        input: String::new(),
+                    };
+                }
+                Action::None
+            }
+            KeyCode::Char('c') if self.focus == Focus::Orders => {
+                if let Some(order) = self.orders.get(self.selected_order) {
+                    self.mode = Mode::ConfirmCancel {
+                        order_id: order.id.clone(),
+                    };
+                }
+                Action::None
+            }
+            _ => Action::None,
+        }
+    }
+
+    fn handle_entry_key(&mut self, code: KeyCode, side: Side, mut input: String) -> Action {
+        match code {
+            KeyCode::Esc => return Action::None,
+            KeyCode::Enter => {
+                let token_id = self.selected_token();
+                return match (parse_order_input(&input), token_id) {
+                    (Ok((price, size)), Some(token_id)) => Action::PlaceOrder {
+                        token_id,
+                        side,
+                        price,
+                        size,
+                    },
+                    (Err(e), _) => {
+                        self.status = e.to_string();
+                        self.mode = Mode::OrderEntry { side, input };
+                        Action::None
+                    }
+                    (_, None) => Action::None,
+                };
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() || c == '.' || c == ' ' => input.push(c),
+            _ => {}
+        }
+        self.mode = Mode::OrderEntry { side, input };
+        Action::None
+    }
+
+    fn move_selection(&mut self, delta: isize) -> Action {
+        match self.focus {
+            Focus::Markets => {
+                let next = step(self.selected_market, delta, self.markets.len());
+                if next == self.selected_market {
+                    return Action::None;
+                }
+                self.selected_market = next;
+                self.selected_outcome = 0;
+                self.book = None;
+                Action::LoadBook
+            }
+            Focus::Orders => {
+                self.selected_order = step(self.selected_order, delta, self.orders.len());
+                Action::None
+            }
+        }
+    }
+
+    fn move_outcome(&mut self, delta: isize) -> Action {
+        let count = self
+            .markets
+            .get(self.selected_market)
+            .map_or(0, |m| m.outcomes.len());
+        let next = step(self.selected_outcome, delta, count);
+        if next == self.selected_outcome {
+            return Action::None;
+        }
+        self.selected_outcome = next;
+        self.book = None;
+        Action::LoadBook
+    }
+}
+
+/// Moves `index` by `delta`, clamped to `0..len`.
+fn step(index: usize, delta: isize, len: usize) -> usize {
+    index
+        .saturating_add_signed(delta)
+        .min(len.saturating_sub(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn market(outcomes: &[&str]) -> WatchedMarket {
+        WatchedMarket {
+            question: "Q?".into(),
+            outcomes: outcomes
+                .iter()
+                .enumerate()
+                .map(|(i, name)| Outcome {
+                    name: (*name).into(),
+                    token_id: U256::from(i + 1),
+                    price: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn press(app: &mut App, code: KeyCode) -> Action {
+        app.handle_key(KeyEvent::from(code))
+    }
+
+    #[test]
+    fn parse_order_input_price_and_size() {
+        let (price, size) = parse_order_input(" 0.45  100 ").unwrap();
+        assert_eq!(price.to_string(), "0.45");
+        assert_eq!(size, Decimal::from(100));
+    }
+
+    #[test]
+    fn parse_order_input_rejects_bad_input() {
+        assert!(parse_order_input("0.45").is_err());
+        assert!(parse_order_input("1.5 10").is_err());
+        assert!(parse_order_input("0.5 0").is_err());
+        assert!(parse_order_input("0.5 10 3").is_err());
+    }
+
+    #[test]
+    fn navigation_clamps_and_requests_book() {
+        let mut app = App::new(vec![market(&["Yes", "No"]), market(&["Yes", "No"])], false);
+        assert_eq!(press(&mut app, KeyCode::Up), Action::None);
+        assert_eq!(press(&mut app, KeyCode::Down), Action::LoadBook);
+        assert_eq!(press(&mut app, KeyCode::Down), Action::None);
+        assert_eq!(app.selected_market, 1);
+        assert_eq!(press(&mut app, KeyCode::Right), Action::LoadBook);
+        assert_eq!(app.selected_token(), Some(U256::from(2)));
+    }
+
+    #[test]
+    fn order_entry_builds_place_order() {
+        let mut app = App::new(vec![market(&["Yes", "No"])], true);
+        press(&mut app, KeyCode::Char('b'));
+        for c in "0.4 25".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        assert_eq!(
+            press(&mut app, KeyCode::Enter),
+            Action::PlaceOrder {
+                token_id: U256::from(1),
+                side: Side::Buy,
+                price: "0.4".parse().unwrap(),
+                size: Decimal::from(25),
+            }
+        );
+        assert_eq!(app.mode, Mode::Normal);
+    }
+
+    #[test]
+    fn order_entry_disabled_without_wallet() {
+        let mut app = App::new(vec![market(&["Yes", "No"])], false);
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(app.mode, Mode::Normal);
+        assert!(app.status.contains("No wallet"));
+    }
+
+    #[test]
+    fn invalid_order_entry_stays_open() {
+        let mut app = App::new(vec![market(&["Yes", "No"])], true);
+        press(&mut app, KeyCode::Char('s'));
+        press(&mut app, KeyCode::Char('2'));
+        assert_eq!(press(&mut app, KeyCode::Enter), Action::None);
+        assert!(matches!(app.mode, Mode::OrderEntry { .. }));
+    }
+}
@@ -0,0 +1,418 @@
+//! Full-screen dashboard: watched markets, the selected outcome's order book,
+//! and — with a wallet configured — positions and open orders, with order
+//! placement and cancellation from the keyboard. `replay --tui` plays a
+//! recording through it instead of live data.
+
+mod app;
+mod ui;
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use clap::Args;
+use clap_complete::engine::ArgValueCandidates;
+use polymarket_client_sdk::auth::Normal;
+use polymarket_client_sdk::auth::state::Authenticated;
+use polymarket_client_sdk::clob::types::request::{MidpointRequest, OrderBookSummaryRequest};
+use polymarket_client_sdk::clob::types::response::OrderBookSummaryResponse;
+use polymarket_client_sdk::clob::types::{OrderType, Side};
+use polymarket_client_sdk::gamma::types::request::MarketsRequest;
+use polymarket_client_sdk::types::{Address, Decimal, U256};
+use polymarket_client_sdk::{clob, data};
+use ratatui::DefaultTerminal;
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+
+use self::app::{Action, App, Outcome, WatchedMarket};
+use crate::auth::{self, WalletSigner};
+use crate::clients;
+use crate::commands::completions::watchlist_candidates;
+use crate::commands::markets::fetch_market;
+use crate::commands::portfolio::{fetch_open_orders, fetch_positions};
+use crate::commands::replay::{Event as Recorded, Frame, best_prices, delay};
+use crate::commands::wallet::resolve_trading_address;
+use crate::config::{self, KeySource};
+use crate::net;
+use crate::output::time::{format_clock, format_timestamp};
+use crate::output::truncate;
+use crate::risk::{self, Proposed};
+
+/// Markets shown when none are given: the most active by 24h volume.
+const DEFAULT_MARKETS: i32 = 10;
+const INPUT_POLL: Duration = Duration::from_millis(250);
+
+/// Authenticated client plus what it needs to sign orders and look up
+/// positions.
+struct Trader {
+    signer: WalletSigner,
+    client: clob::Client<Authenticated<Normal>>,
+    address: Address,
+}
+
+#[derive(Args)]
+pub struct TuiArgs {
+    /// Markets to watch: slugs, market IDs, condition IDs or token IDs
+    /// (default: top markets by 24h volume)
+    #[arg(add = ArgValueCandidates::new(watchlist_candidates))]
+    pub markets: Vec<String>,
+    /// Seconds between data refreshes
+    #[arg(long, default_value = "5")]
+    pub refresh: u64,
+}
+
+pub async fn execute(
+    args: TuiArgs,
+    private_key: Option<&str>,
+    signature_type: Option<&str>,
+) -> Result<()> {
+    anyhow::ensure!(args.refresh > 0, "--refresh must be at least 1 second");
+    let refresh = Duration::from_secs(args.refresh);
+    let watched = load_markets(&args.markets).await?;
+    anyhow::ensure!(!watched.is_empty(), "No markets to show");
+
+    // Authenticate (and unlock any keystore) before taking over the terminal.
+    let trader = if matches!(
+        config::key_source(private_key),
+        KeySource::None | KeySource::WatchOnly
+    ) {
+        None
+    } else {
+        let signer = auth::resolve_signer(private_key).await?;
+        let client = auth::authenticate_with_signer(&signer, signature_type).await?;
+        let address = resolve_trading_address(private_key, signature_type).await?;
+        Some(Trader {
+            signer,
+            client,
+            address,
+        })
+    };
+
+    let mut app = App::new(watched, trader.is_some());
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut app, trader.as_ref(), refresh).await;
+    ratatui::restore();
+    result
+}
+
+async fn load_markets(ids: &[String]) -> Result<Vec<WatchedMarket>> {
+    let client = clients::gamma()?;
+    let markets = if ids.is_empty() {
+        let request = MarketsRequest::builder()
+            .limit(DEFAULT_MARKETS)
+            .closed(false)
+            .order("volume24hr".to_string())
+            .build();
+        net::retry(|| client.markets(&request)).await?
+    } else {
+        let client = &client;
+        net::fetch_all(ids, |id| fetch_market(client, id)).await?
+    };
+
+    Ok(markets
+        .into_iter()
+        .map(|m| {
+            let names = m.outcomes.unwrap_or_default();
+            let prices = m.outcome_prices.unwrap_or_default();
+            let tokens = m.clob_token_ids.unwrap_or_default();
+            WatchedMarket {
+                question: m.question.unwrap_or_default(),
+                outcomes: names
+                    .into_iter()
+                    .zip(tokens)
+                    .enumerate()
+                    .map(|(i, (name, token_id))| Outcome {
+                        name,
+                        token_id,
+                        price: prices.get(i).copied(),
+                    })
+                    .collect(),
+            }
+        })
+        .filter(|m| !m.outcomes.is_empty())
+        .collect())
+}
+
+async fn event_loop(
+    terminal: &mut DefaultTerminal,
+    app: &mut App,
+    trader: Option<&Trader>,
+    refresh: Duration,
+) -> Result<()> {
+    let clob_client = clients::clob()?;
+    let data_client = clients::data()?;
+    let mut next_refresh = Instant::now();
+
+    loop {
+        if Instant::now() >= next_refresh {
+            app.status = "Refreshing…".into();
+            terminal.draw(|f| ui::draw(f, app))?;
+            let result = refresh_all(app, &clob_client, &data_client, trader).await;
+            report(app, result);
+            next_refresh = Instant::now() + refresh;
+        }
+
+        terminal.draw(|f| ui::draw(f, app))?;
+        if !event::poll(INPUT_POLL)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match app.handle_key(key) {
+            Action::None => {}
+            Action::Quit => return Ok(()),
+            Action::Refresh => next_refresh = Instant::now(),
+            Action::LoadBook => {
+                let result = load_book(app, &clob_client).await;
+                report(app, result);
+            }
+            Action::PlaceOrder {
+                token_id,
+                side,
+                price,
+                size,
+            } => {
+                if let Some(trader) = trader {
+                    app.status = "Placing order…".into();
+                    terminal.draw(|f| ui::draw(f, app))?;
+                    match place_order(trader, token_id, side, price, size).await {
+                        Ok(message) => app.status = message,
+                        Err(e) => app.status = format!("Order failed: {e}"),
+                    }
+                    next_refresh = Instant::now();
+                }
+            }
+            Action::CancelOrder(order_id) => {
+                if let Some(trader) = trader {
+                    match net::once(trader.client.cancel_order(&order_id)).await {
+                        Ok(r) if r.canceled.contains(&order_id) => {
+                            app.status = "Order canceled".into();
+                        }
+                        Ok(_) => app.status = "Order was not canceled".into(),
+                        Err(e) => app.status = format!("Cancel failed: {e}"),
+                    }
+                    next_refresh = Instant::now();
+                }
+            }
+        }
+    }
+}
+
+/// Plays recorded frames through the dashboard, `speed` times as fast as
+/// they were recorded (`None` jumps to the end), until the user quits.
+pub async fn replay(frames: &[Frame], speed: Option<f64>) -> Result<()> {
+    let watched = replay_markets(frames).await;
+    let mut app = App::new(watched, false);
+    let mut terminal = ratatui::init();
+    let result = replay_loop(&mut terminal, &mut app, frames, speed);
+    ratatui::restore();
+    result
+}
+
+/// The recorded tokens grouped into their markets, named from Gamma when
+/// it can be reached and by ID when it can't.
+async fn replay_markets(frames: &[Frame]) -> Vec<WatchedMarket> {
+    let mut groups: Vec<(String, Vec<U256>)> = Vec::new();
+    for frame in frames {
+        let Recorded::Book(book) = &frame.event else {
+            continue;
+        };
+        let market = book.market.to_string();
+        match groups.iter_mut().find(|(m, _)| *m == market) {
+            Some((_, tokens)) if tokens.contains(&book.asset_id) => {}
+            Some((_, tokens)) => tokens.push(book.asset_id),
+            None => groups.push((market, vec![book.asset_id])),
+        }
+    }
+    let looked_up = match clients::gamma() {
+        Ok(client) => {
+            let client = &client;
+            net::fetch_all(&groups, |(market, _)| async move {
+                Ok(fetch_market(client, market).await.ok())
+            })
+            .await
+            .unwrap_or_default()
+        }
+        Err(_) => Vec::new(),
+    };
+    groups
+        .into_iter()
+        .enumerate()
+        .map(|(i, (condition_id, tokens))| {
+            let market = looked_up.get(i).cloned().flatten();
+            let names: HashMap<U256, String> = market
+                .as_ref()
+                .map(|m| {
+                    let tokens = m.clob_token_ids.clone().unwrap_or_default();
+                    let names = m.outcomes.clone().unwrap_or_default();
+                    tokens.into_iter().zip(names).collect()
+                })
+                .unwrap_or_default();
+            WatchedMarket {
+                question: market.and_then(|m| m.question).unwrap_or(condition_id),
+                outcomes: tokens
+                    .into_iter()
+                    .map(|token_id| Outcome {
+                        name: names
+                            .get(&token_id)
+                            .cloned()
+                            .unwrap_or_else(|| truncate(&token_id.to_string(), 12)),
+                        token_id,
+                        price: None,
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+fn replay_loop(
+    terminal: &mut DefaultTerminal,
+    app: &mut App,
+    frames: &[Frame],
+    speed: Option<f64>,
+) -> Result<()> {
+    let first = frames[0].recorded_at;
+    let started = Instant::now();
+    let mut books: HashMap<U256, OrderBookSummaryResponse> = HashMap::new();
+    let mut last_trade = None;
+    let mut next = 0;
+
+    loop {
+        let elapsed = started.elapsed();
+        while let Some(frame) = frames.get(next) {
+            if speed.is_some_and(|s| delay(first, frame.recorded_at, s) > elapsed) {
+                break;
+            }
+            match &frame.event {
+                Recorded::Book(book) => {
+                    if let (Some(bid), Some(ask)) = best_prices(book) {
+                        let outcome = app
+                            .markets
+                            .iter_mut()
+                            .flat_map(|m| &mut m.outcomes)
+                            .find(|o| o.token_id == frame.token_id);
+                        if let Some(outcome) = outcome {
+                            outcome.price = Some((bid + ask) / Decimal::TWO);
+                        }
+                    }
+                    books.insert(frame.token_id, book.clone());
+                }
+                Recorded::Trade { price } => last_trade = Some(*price),
+            }
+            next += 1;
+        }
+        app.book = app
+            .selected_token()
+            .and_then(|token_id| books.get(&token_id).cloned());
+
+        let at = frames[next.saturating_sub(1)].recorded_at;
+        let mut status = format!("Replay {}", format_timestamp(at));
+        if let Some(price) = last_trade {
+            status.push_str(&format!(", last trade {price}"));
+        }
+        if next == frames.len() {
+            status.push_str(": end of recording");
+        }
+        app.status = status;
+
+        terminal.draw(|f| ui::draw(f, app))?;
+        if !event::poll(INPUT_POLL)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind == KeyEventKind::Press && app.handle_key(key) == Action::Quit {
+            return Ok(());
+        }
+    }
+}
+
+/// Shows a fetch error in the status line instead of leaving the dashboard.
+fn report(app: &mut App, result: Result<()>) {
+    match result {
+        Ok(()) => app.status = format!("Updated {}", format_clock(chrono::Utc::now())),
+        Err(e) => app.status = format!("Error: {e}"),
+    }
+}
+
+async fn load_book(app: &mut App, client: &clob::Client) -> Result<()> {
+    let Some(token_id) = app.selected_token() else {
+        return Ok(());
+    };
+    let request = OrderBookSummaryRequest::builder()
+        .token_id(token_id)
+        .build();
+    app.book = Some(net::retry(|| client.order_book(&request)).await?);
+    Ok(())
+}
+
+async fn refresh_all(
+    app: &mut App,
+    clob_client: &clob::Client,
+    data_client: &data::Client,
+    trader: Option<&Trader>,
+) -> Result<()> {
+    let requests: Vec<_> = app
+        .markets
+        .iter()
+        .flat_map(|m| &m.outcomes)
+        .map(|o| MidpointRequest::builder().token_id(o.token_id).build())
+        .collect();
+    let midpoints = net::retry(|| clob_client.midpoints(&requests))
+        .await?
+        .midpoints;
+    for outcome in app.markets.iter_mut().flat_map(|m| &mut m.outcomes) {
+        if let Some(mid) = midpoints.get(&outcome.token_id) {
+            outcome.price = Some(*mid);
+        }
+    }
+
+    load_book(app, clob_client).await?;
+
+    if let Some(trader) = trader {
+        let (positions, orders) = tokio::try_join!(
+            fetch_positions(data_client, trader.address),
+            fetch_open_orders(&trader.client),
+        )?;
+        app.positions = positions;
+        app.set_orders(orders);
+    }
+    Ok(())
+}
+
+async fn place_order(
+    trader: &Trader,
+    token_id: U256,
+    side: Side,
+    price: Decimal,
+    size: Decimal,
+) -> Result<String> {
+    risk::check(
+        &trader.client,
+        &[Proposed::limit(token_id, side, price, size)],
+        async { Ok(trader.address) },
+    )
+    .await?;
+    let order = trader
+        .client
+        .limit_order()
+        .token_id(token_id)
+        .side(side)
+        .price(price)
+        .size(size)
+        .order_type(OrderType::GTC)
+        .build()
+        .await?;
+    let signed = auth::sign_order(&trader.client, &trader.signer, order).await?;
+    let result = net::once(trader.client.post_order(signed)).await?;
+    if !result.success {
+        anyhow::bail!(result.error_msg.unwrap_or_else(|| "rejected".into()));
+    }
+    Ok(format!("{side} {size} @ {price}: {}", result.status))
+}
@@ -0,0 +1,241 @@
+use polymarket_client_sdk::clob::types::Side;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table, TableState};
+
+use super::app::{App, Focus, Mode};
+use crate::output::clob::{book_side, mid_and_spread};
+use crate::output::truncate;
+
+/// Levels shown per side of the book panel.
+const BOOK_DEPTH: usize = 10;
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [main, bottom, footer] = Layout::vertical([
+        Constraint::Percentage(60),
+        Constraint::Fill(1),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [markets, book] =
+        Layout::horizontal([Constraint::Percentage(55), Constraint::Fill(1)]).areas(main);
+    let [positions, orders] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Fill(1)]).areas(bottom);
+
+    draw_markets(frame, app, markets);
+    draw_book(frame, app, book);
+    draw_positions(frame, app, positions);
+    draw_orders(frame, app, orders);
+    draw_footer(frame, app, footer);
+}
+
+fn panel(title: &str, focused: bool) -> Block<'_> {
+    let block = Block::bordered().title(title);
+    if focused {
+        block.border_style(Style::new().fg(Color::Cyan))
+    } else {
+        block
+    }
+}
+
+fn selected_style() -> Style {
+    Style::new().add_modifier(Modifier::REVERSED)
+}
+
+fn draw_markets(frame: &mut Frame, app: &App, area: Rect) {
+    let rows = app.markets.iter().enumerate().map(|(i, m)| {
+        let outcomes = m
+            .outcomes
+            .iter()
+            .enumerate()
+            .map(|(j, o)| {
+                let price = o.price.map_or_else(|| "—".into(), |p| format!("{p:.3}"));
+                let text = format!("{} {price}", o.name);
+                let style = if i == app.selected_market && j == app.selected_outcome {
+                    Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::new()
+                };
+                Span::styled(format!("{text}  "), style)
+            })
+            .collect::<Vec<_>>();
+        Row::new(vec![
+            Cell::from(truncate(&m.question, 48)),
+            Cell::from(Line::from(outcomes)),
+        ])
+    });
+    let table = Table::new(rows, [Constraint::Fill(3), Constraint::Fill(2)])
+        .header(Row::new(["Market", "Outcomes (mid)"]).style(Style::new().bold()))
+        .row_highlight_style(selected_style())
+        .block(panel("Markets", app.focus == Focus::Markets));
+    let mut state = TableState::default().with_selected(Some(app.selected_market));
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+fn draw_book(frame: &mut Frame, app: &App, area: Rect) {
+    let title = app.selected().map_or_else(
+        || "Order Book".to_string(),
+        |(_, o)| format!("Order Book — {}", o.name),
+    );
+    let Some(book) = &app.book else {
+        frame.render_widget(Paragraph::new("Loading…").block(panel(&title, false)), area);
+        return;
+    };
+
+    let bids = book_side(&book.bids, true, Some(BOOK_DEPTH));
+    let asks = book_side(&book.asks, false, Some(BOOK_DEPTH));
+    let title = match mid_and_spread(&bids, &asks) {
+        Some((mid, spread)) => format!("{title}  mid {mid:.3}  spread {spread:.3}"),
+        None => title,
+    };
+
+    // Asks are listed worst first so the two sides meet at the spread.
+    let ask_rows = asks.iter().rev().map(|l| {
+        Row::new([
+            format!("{:.3}", l.price),
+            l.size.to_string(),
+            l.cumulative.to_string(),
+        ])
+        .style(Style::new().fg(Color::Red))
+    });
+    let bid_rows = bids.iter().map(|l| {
+        Row::new([
+            format!("{:.3}", l.price),
+            l.size.to_string(),
+            l.cumulative.to_string(),
+        ])
+        .style(Style::new().fg(Color::Green))
+    });
+    let table = Table::new(
+        ask_rows.chain(bid_rows),
+        [
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(Row::new(["Price", "Size", "Total"]).style(Style::new().bold()))
+    .block(panel(&title, false));
+    frame.render_widget(table, area);
+}
+
+fn draw_positions(frame: &mut Frame, app: &App, area: Rect) {
+    if !app.trading {
+        frame.render_widget(
+            Paragraph::new("No wallet configured").block(panel("Positions", false)),
+            area,
+        );
+        return;
+    }
+    let rows = app.positions.iter().map(|p| {
+        let pnl_style = if p.cash_pnl.is_sign_negative() {
+            Style::new().fg(Color::Red)
+        } else {
+            Style::new().fg(Color::Green)
+        };
+        Row::new(vec![
+            Cell::from(truncate(&p.title, 30)),
+            Cell::from(p.outcome.clone()),
+            Cell::from(format!("{:.2}", p.size)),
+            Cell::from(format!("{:.3}", p.cur_price)),
+            Cell::from(format!("{:.2}", p.cash_pnl)).style(pnl_style),
+        ])
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(3),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(Row::new(["Market", "Outcome", "Size", "Mark", "PnL"]).style(Style::new().bold()))
+    .block(panel("Positions", false));
+    frame.render_widget(table, area);
+}
+
+fn draw_orders(frame: &mut Frame, app: &App, area: Rect) {
+    if !app.trading {
+        frame.render_widget(
+            Paragraph::new("No wallet configured").block(panel("Open Orders", false)),
+            area,
+        );
+        return;
+    }
+    let rows = app.orders.iter().map(|o| {
+        let side_style = if o.side == Side::Buy {
+            Style::new().fg(Color::Green)
+        } else {
+            Style::new().fg(Color::Red)
+        };
+        Row::new(vec![
+            Cell::from(truncate(&o.id, 12)),
+            Cell::from(o.side.to_string()).style(side_style),
+            Cell::from(o.outcome.clone()),
+            Cell::from(format!("{:.3}", o.price)),
+            Cell::from((o.original_size - o.size_matched).to_string()),
+        ])
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(Row::new(["ID", "Side", "Outcome", "Price", "Remaining"]).style(Style::new().bold()))
+    .row_highlight_style(selected_style())
+    .block(panel("Open Orders", app.focus == Focus::Orders));
+    let mut state = TableState::default();
+    if app.focus == Focus::Orders && !app.orders.is_empty() {
+        state.select(Some(app.selected_order));
+    }
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
+    let line = match &app.mode {
+        Mode::OrderEntry { side, input } => {
+            let outcome = app.selected().map_or("", |(_, o)| o.name.as_str());
+            let mut spans = vec![
+                Span::styled(
+                    format!(" {side} {outcome} — price size: "),
+                    Style::new().bold(),
+                ),
+                Span::raw(input.as_str()),
+                Span::styled("█", Style::new().fg(Color::Cyan)),
+            ];
+            if !app.status.is_empty() {
+                spans.push(Span::styled(
+                    format!("   {}", app.status),
+                    Style::new().fg(Color::Red),
+                ));
+            }
+            Line::from(spans)
+        }
+        Mode::ConfirmCancel { order_id } => Line::from(Span::styled(
+            format!(" Cancel order {}? (y/n)", truncate(order_id, 16)),
+            Style::new().bold().fg(Color::Yellow),
+        )),
+        Mode::Normal => {
+            let help = if app.trading {
+                " q quit  ↑↓ select  ←→ outcome  tab focus  b buy  s sell  c cancel  r refresh"
+            } else {
+                " q quit  ↑↓ select  ←→ outcome  r refresh"
+            };
+            Line::from(vec![
+                Span::styled(help, Style::new().fg(Color::DarkGray)),
+                Span::raw("   "),
+                Span::raw(app.status.as_str()),
+            ])
+        }
+    };
+    frame.render_widget(Paragraph::new(line), area);
+}
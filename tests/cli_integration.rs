@@ -7,6 +7,12 @@ fn polymarket() -> Command {
     let mut cmd = Command::cargo_bin("polymarket").unwrap();
     cmd.env_remove("POLYMARKET_PRIVATE_KEY");
     cmd.env_remove("POLYMARKET_SIGNATURE_TYPE");
+    cmd.env_remove("POLYMARKET_PROFILE");
+    cmd.env_remove("POLYMARKET_CONFIG_DIR");
+    cmd.env_remove("XDG_CONFIG_HOME");
+    cmd.env_remove("POLYMARKET_OFFLINE");
+    cmd.env_remove("POLYMARKET_NO_CONFIG");
+    cmd.env("POLYMARKET_SKIP_VERSION_CHECK", "1");
     cmd
 }
 
@@ -21,93 +27,2663 @@ fn help_lists_all_top_level_commands() {
             .and(predicate::str::contains("series"))
             .and(predicate::str::contains("comments"))
             .and(predicate::str::contains("profiles"))
+            .and(predicate::str::contains("leaderboard"))
             .and(predicate::str::contains("sports"))
             .and(predicate::str::contains("approve"))
+            .and(predicate::str::contains("balance"))
+            .and(predicate::str::contains("allowance"))
+            .and(predicate::str::contains("transfer"))
+            .and(predicate::str::contains("gas"))
+            .and(predicate::str::contains("tx"))
+            .and(predicate::str::contains("apikey"))
+            .and(predicate::str::contains("auth"))
             .and(predicate::str::contains("clob"))
+            .and(predicate::str::contains("order"))
+            .and(predicate::str::contains("copy"))
+            .and(predicate::str::contains("quote"))
+            .and(predicate::str::contains("book"))
+            .and(predicate::str::contains("fees"))
+            .and(predicate::str::contains("scan"))
+            .and(predicate::str::contains("size"))
+            .and(predicate::str::contains("backtest"))
+            .and(predicate::str::contains("contacts"))
             .and(predicate::str::contains("ctf"))
+            .and(predicate::str::contains("redeem"))
+            .and(predicate::str::contains("resolution"))
             .and(predicate::str::contains("data"))
             .and(predicate::str::contains("bridge"))
+            .and(predicate::str::contains("deposit"))
             .and(predicate::str::contains("wallet"))
+            .and(predicate::str::contains("config"))
+            .and(predicate::str::contains("state"))
+            .and(predicate::str::contains("watch"))
+            .and(predicate::str::contains("alert"))
+            .and(predicate::str::contains("daemon"))
+            .and(predicate::str::contains("panic"))
+            .and(predicate::str::contains("paper"))
+            .and(predicate::str::contains("stream"))
+            .and(predicate::str::contains("record"))
+            .and(predicate::str::contains("replay"))
+            .and(predicate::str::contains("trades"))
+            .and(predicate::str::contains("portfolio"))
+            .and(predicate::str::contains("tax"))
+            .and(predicate::str::contains("sync"))
+            .and(predicate::str::contains("convert"))
+            .and(predicate::str::contains("rewards"))
+            .and(predicate::str::contains("tui"))
+            .and(predicate::str::contains("cache"))
+            .and(predicate::str::contains("db"))
+            .and(predicate::str::contains("completions"))
+            .and(predicate::str::contains("doctor"))
             .and(predicate::str::contains("status")),
     );
 }
 
+#[test]
+fn doctor_reports_a_bad_key_and_unreachable_endpoints() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-doctor-{}", std::process::id()));
+    polymarket()
+        .env("HOME", &home)
+        .env("POLYMARKET_PRIVATE_KEY", "0xnotakey")
+        .args([
+            "-o",
+            "json",
+            "--clob-url",
+            "http://127.0.0.1:1",
+            "--rpc-url",
+            "http://127.0.0.1:1",
+            "--timeout",
+            "2s",
+            "doctor",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"ok\": false"))
+        .stdout(predicate::str::contains("Invalid private key"))
+        .stdout(predicate::str::contains("\"status\": \"skipped\""));
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+#[test]
+fn doctor_offline_skips_network_checks() {
+    let home = std::env::temp_dir().join(format!(
+        "polymarket-cli-doctor-offline-{}",
+        std::process::id()
+    ));
+    polymarket()
+        .env("HOME", &home)
+        .args(["-o", "json", "--offline", "doctor"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"detail\": \"Offline\""))
+        .stdout(predicate::str::contains("Compatibility"));
+    let _ = std::fs::remove_dir_all(&home);
+}
+
 #[test]
 fn version_outputs_binary_name() {
     polymarket()
-        .arg("--version")
+        .arg("--version")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("polymarket"));
+}
+
+#[test]
+fn markets_help_lists_subcommands() {
+    polymarket()
+        .args(["markets", "--help"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("list")
+                .and(predicate::str::contains("get"))
+                .and(predicate::str::contains("search"))
+                .and(predicate::str::contains("top"))
+                .and(predicate::str::contains("holders"))
+                .and(predicate::str::contains("activity"))
+                .and(predicate::str::contains("tags")),
+        );
+}
+
+#[test]
+fn events_help_lists_subcommands() {
+    polymarket()
+        .args(["events", "--help"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("list")
+                .and(predicate::str::contains("get"))
+                .and(predicate::str::contains("tags")),
+        );
+}
+
+#[test]
+fn wallet_help_lists_subcommands() {
+    polymarket()
+        .args(["wallet", "--help"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("create")
+                .and(predicate::str::contains("import"))
+                .and(predicate::str::contains("address"))
+                .and(predicate::str::contains("show"))
+                .and(predicate::str::contains("reset"))
+                .and(predicate::str::contains("encrypt"))
+                .and(predicate::str::contains("decrypt"))
+                .and(predicate::str::contains("list"))
+                .and(predicate::str::contains("use")),
+        );
+}
+
+#[test]
+fn wallet_use_requires_profile() {
+    polymarket().args(["wallet", "use"]).assert().failure();
+}
+
+#[test]
+fn invalid_profile_name_rejected() {
+    polymarket()
+        .args(["--profile", "../etc", "wallet", "show"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid profile name"));
+}
+
+#[test]
+fn no_args_shows_usage() {
+    polymarket()
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Usage"));
+}
+
+#[test]
+fn unknown_command_fails() {
+    polymarket().arg("nonexistent").assert().failure();
+}
+
+#[test]
+fn invalid_output_format_rejected() {
+    polymarket()
+        .args(["--output", "xml", "status"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value"));
+}
+
+#[test]
+fn invalid_color_choice_rejected() {
+    polymarket()
+        .args(["--color", "sometimes", "status"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("invalid value 'sometimes'"));
+}
+
+#[test]
+fn markets_search_requires_query() {
+    polymarket().args(["markets", "search"]).assert().failure();
+}
+
+#[test]
+fn markets_search_help_shows_limit_and_json() {
+    polymarket()
+        .args(["markets", "search", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--limit").and(predicate::str::contains("--json")));
+}
+
+#[test]
+fn markets_list_rejects_invalid_liquidity() {
+    polymarket()
+        .args(["markets", "list", "--liquidity-min", "lots"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value"));
+}
+
+#[test]
+fn markets_top_rejects_unknown_ranking() {
+    polymarket()
+        .args(["markets", "top", "--by", "spread"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("24h-change"));
+}
+
+#[test]
+fn markets_activity_rejects_negative_min_usdc() {
+    polymarket()
+        .args(["markets", "activity", "some-market", "--min-usdc=-5"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--min-usdc must not be negative"));
+}
+
+#[test]
+fn leaderboard_rejects_unknown_ranking() {
+    polymarket()
+        .args(["leaderboard", "--by", "pnl"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("profit"));
+}
+
+#[test]
+fn leaderboard_ranking_flags_do_not_apply_to_user() {
+    polymarket()
+        .args([
+            "leaderboard",
+            "--by",
+            "volume",
+            "user",
+            "0x0000000000000000000000000000000000000001",
+        ])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn leaderboard_user_rejects_unknown_address() {
+    polymarket()
+        .args(["leaderboard", "user", "nobody-saved"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn markets_list_cursor_conflicts_with_offset() {
+    polymarket()
+        .args(["markets", "list", "--cursor", "25", "--offset", "25"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn markets_show_rejects_malformed_condition_id() {
+    polymarket()
+        .args(["markets", "show", "0x1234"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid condition ID"));
+}
+
+#[test]
+fn clob_create_order_help_shows_dry_run() {
+    polymarket()
+        .args(["clob", "create-order", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--dry-run"));
+}
+
+#[test]
+fn clob_create_order_accepts_type_alias() {
+    polymarket()
+        .args([
+            "clob",
+            "create-order",
+            "--token",
+            "1",
+            "--side",
+            "buy",
+            "--price",
+            "0.5",
+            "--size",
+            "1",
+            "--type",
+            "BOGUS",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value 'BOGUS'"));
+}
+
+#[test]
+fn clob_cancel_market_requires_market_or_asset() {
+    polymarket()
+        .args(["clob", "cancel-market"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("condition ID or --asset"));
+}
+
+#[test]
+fn order_cancel_market_requires_market_or_asset() {
+    polymarket()
+        .args(["order", "cancel-market"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("condition ID or --asset"));
+}
+
+#[test]
+fn clob_orders_rejects_invalid_older_than() {
+    polymarket()
+        .args(["clob", "orders", "--older-than", "soon"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid duration"));
+}
+
+#[test]
+fn clob_book_rejects_zero_watch_interval() {
+    polymarket()
+        .args(["clob", "book", "1", "--watch", "0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("at least 1 second"));
+}
+
+#[test]
+fn stream_book_rejects_invalid_token_id() {
+    polymarket()
+        .args(["stream", "book", "abc"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid token ID"));
+}
+
+#[test]
+fn balance_without_wallet_explains_setup() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-nohome-{}", std::process::id()));
+    polymarket()
+        .env("HOME", &home)
+        .arg("balance")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No wallet configured"));
+}
+
+#[test]
+fn balance_rejects_invalid_address() {
+    polymarket()
+        .args(["balance", "0x1234"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("0x-prefixed"));
+}
+
+#[test]
+fn transfer_requires_recipient_and_amount() {
+    polymarket().arg("transfer").assert().failure();
+}
+
+#[test]
+fn transfer_rejects_excess_usdc_precision() {
+    polymarket()
+        .args([
+            "transfer",
+            "0x0000000000000000000000000000000000000001",
+            "1.0000001",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("precision"));
+}
+
+#[test]
+fn rewards_rejects_out_of_range_days() {
+    polymarket()
+        .args(["rewards", "--days", "0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--days"));
+}
+
+#[test]
+fn redeem_requires_condition_or_all() {
+    polymarket().arg("redeem").assert().failure();
+}
+
+#[test]
+fn redeem_condition_conflicts_with_all() {
+    polymarket()
+        .args(["redeem", "0xabc", "--all"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn redeem_rejects_invalid_condition_id() {
+    polymarket()
+        .args(["redeem", "not-a-condition"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid condition ID"));
+}
+
+#[test]
+fn transfer_rejects_invalid_gas_price() {
+    polymarket()
+        .args([
+            "transfer",
+            "0x0000000000000000000000000000000000000001",
+            "1",
+            "--gas-price",
+            "fast",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid gas price"));
+}
+
+#[test]
+fn apikey_help_lists_subcommands() {
+    polymarket()
+        .args(["apikey", "--help"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("create")
+                .and(predicate::str::contains("derive"))
+                .and(predicate::str::contains("list"))
+                .and(predicate::str::contains("delete")),
+        );
+}
+
+#[test]
+fn apikey_create_without_wallet_explains_setup() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-nohome-{}", std::process::id()));
+    polymarket()
+        .env("HOME", &home)
+        .args(["apikey", "create"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No wallet configured"));
+}
+
+#[test]
+fn allowance_help_lists_subcommands() {
+    polymarket()
+        .args(["allowance", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("show").and(predicate::str::contains("set")));
+}
+
+#[test]
+fn stream_user_without_wallet_explains_setup() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-nohome-{}", std::process::id()));
+    polymarket()
+        .env("HOME", &home)
+        .args(["stream", "user"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No wallet configured"));
+}
+
+#[test]
+fn stream_help_lists_subcommands() {
+    polymarket()
+        .args(["stream", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("book").and(predicate::str::contains("trades")));
+}
+
+#[test]
+fn watch_help_lists_subcommands() {
+    polymarket()
+        .args(["watch", "--help"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("add")
+                .and(predicate::str::contains("remove"))
+                .and(predicate::str::contains("list"))
+                .and(predicate::str::contains("prices")),
+        );
+}
+
+#[test]
+fn watch_list_empty_without_watchlist() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-watch-{}", std::process::id()));
+    polymarket()
+        .env("HOME", &home)
+        .args(["-o", "json", "watch", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[]"));
+}
+
+#[test]
+fn watch_remove_unknown_market_fails() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-watch-{}", std::process::id()));
+    polymarket()
+        .env("HOME", &home)
+        .args(["watch", "remove", "not-watched"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Not on watchlist"));
+}
+
+#[test]
+fn watch_list_csv_quotes_fields() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-csv-{}", std::process::id()));
+    let dir = home.join(".config").join("polymarket");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("watchlist.json"),
+        r#"[{"id": "12", "slug": "rain", "question": "Rain, or shine?"}]"#,
+    )
+    .unwrap();
+    polymarket()
+        .env("HOME", &home)
+        .args(["-o", "csv", "watch", "list"])
+        .assert()
+        .success()
+        .stdout("id,slug,condition_id,question\n12,rain,,\"Rain, or shine?\"\n");
+    polymarket()
+        .env("HOME", &home)
+        .args(["-o", "csv", "--no-header", "watch", "list"])
+        .assert()
+        .success()
+        .stdout("12,rain,,\"Rain, or shine?\"\n");
+}
+
+#[test]
+fn csv_errors_go_to_stderr() {
+    polymarket()
+        .args(["-o", "csv", "clob", "book", "1", "--watch", "0"])
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(predicate::str::contains("Error:"));
+}
+
+#[test]
+fn plain_wallet_import_prints_only_the_address() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-plain-{}", std::process::id()));
+    polymarket()
+        .env("HOME", &home)
+        .args([
+            "-o",
+            "plain",
+            "wallet",
+            "import",
+            "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+            "--force",
+        ])
+        .assert()
+        .success()
+        .stdout("0x2c7536E3605D9C16a7a3D7b1898e529396a65c23\n");
+}
+
+#[test]
+fn alert_list_empty_without_alerts() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-alert-{}", std::process::id()));
+    polymarket()
+        .env("HOME", &home)
+        .args(["-o", "json", "alert", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[]"));
+}
+
+#[test]
+fn alert_run_without_alerts_fails() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-alert-{}", std::process::id()));
+    polymarket()
+        .env("HOME", &home)
+        .args(["alert", "run", "--once"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No alerts"));
+}
+
+/// A free local port for a child process to listen on.
+fn free_addr() -> std::net::SocketAddr {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+}
+
+/// The full response to `GET path`, retrying while the server comes up.
+fn http_get(addr: std::net::SocketAddr, path: &str) -> String {
+    use std::io::{Read, Write};
+
+    for _ in 0..50 {
+        if let Ok(mut stream) = std::net::TcpStream::connect(addr) {
+            write!(stream, "GET {path} HTTP/1.1\r\n\r\n").unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            return response;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    panic!("nothing listening on {addr}");
+}
+
+#[test]
+fn alert_run_serves_prometheus_metrics() {
+    let dir = std::env::temp_dir().join(format!("polymarket-cli-metrics-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("alerts.json"),
+        r#"[{"id":1,"market_id":"1","question":"Q","outcome":"Yes","token_id":"1","direction":"above","price":"0.5"}]"#,
+    )
+    .unwrap();
+    let addr = free_addr();
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin("polymarket"))
+        .env("POLYMARKET_CONFIG_DIR", &dir)
+        .env("POLYMARKET_SKIP_VERSION_CHECK", "1")
+        .args(["--clob-url", "http://127.0.0.1:1", "--retries", "0"])
+        .args(["alert", "run", "--metrics-addr", &addr.to_string()])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .unwrap();
+    let body = http_get(addr, "/metrics");
+    child.kill().unwrap();
+    let _ = child.wait();
+    let _ = std::fs::remove_dir_all(&dir);
+    assert!(body.starts_with("HTTP/1.1 200 OK"), "{body}");
+    assert!(body.contains("polymarket_orders_placed_total 0"));
+    assert!(body.contains("# TYPE polymarket_api_request_duration_seconds histogram"));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn daemon_install_service_writes_and_removes_a_systemd_unit() {
+    let dir = std::env::temp_dir().join(format!("polymarket-cli-service-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let unit = dir.join("systemd/user/polymarket-alerts.service");
+    polymarket()
+        .env("XDG_CONFIG_HOME", &dir)
+        .env("POLYMARKET_CONFIG_DIR", dir.join("polymarket"))
+        .args([
+            "daemon",
+            "install-service",
+            "--runner",
+            "alerts",
+            "--interval",
+            "60",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "systemctl --user enable --now polymarket-alerts.service",
+        ));
+    let contents = std::fs::read_to_string(&unit).unwrap();
+    assert!(contents.contains(" alert run --interval 60\n"));
+    assert!(contents.contains("Restart=on-failure"));
+    assert!(contents.contains("Environment=POLYMARKET_CONFIG_DIR="));
+    polymarket()
+        .env("XDG_CONFIG_HOME", &dir)
+        .args(["daemon", "uninstall-service", "--runner", "alerts"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("systemctl --user disable --now"));
+    assert!(!unit.exists());
+    polymarket()
+        .env("XDG_CONFIG_HOME", &dir)
+        .args(["daemon", "uninstall-service", "--runner", "alerts"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No alerts service is installed."));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn stream_serves_health_and_status() {
+    let addr = free_addr();
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin("polymarket"))
+        .env("POLYMARKET_SKIP_VERSION_CHECK", "1")
+        .args(["stream", "book", "123", "--serve", &addr.to_string()])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .unwrap();
+    let status = http_get(addr, "/status");
+    let healthz = http_get(addr, "/healthz");
+    let missing = http_get(addr, "/nope");
+    child.kill().unwrap();
+    let _ = child.wait();
+    assert!(status.starts_with("HTTP/1.1 200 OK"), "{status}");
+    assert!(status.contains(r#""command":"stream""#));
+    assert!(status.contains(r#""subscriptions":["123"]"#));
+    // Healthy once the socket is up, which needs the network.
+    assert!(
+        healthz.starts_with("HTTP/1.1 200") || healthz.starts_with("HTTP/1.1 503"),
+        "{healthz}"
+    );
+    assert!(missing.starts_with("HTTP/1.1 404"));
+}
+
+#[test]
+fn resolution_watch_needs_markets_or_a_wallet() {
+    let home =
+        std::env::temp_dir().join(format!("polymarket-cli-resolution-{}", std::process::id()));
+    polymarket()
+        .env("HOME", &home)
+        .args(["resolution", "watch", "--once"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Nothing to watch"));
+}
+
+#[test]
+fn deposit_wait_flags_need_the_right_mode() {
+    polymarket()
+        .args(["deposit", "--amount", "25", "--wait"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+    polymarket()
+        .args(["deposit", "--no-wait"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--amount"));
+    // The bridge wait has its own flag, apart from the global --timeout.
+    polymarket()
+        .args(["deposit", "--wait-timeout", "soon"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid duration 'soon'"));
+}
+
+#[test]
+fn gas_flags_are_checked_before_sending() {
+    polymarket()
+        .args([
+            "transfer",
+            "0x0000000000000000000000000000000000000001",
+            "1",
+            "--gas-price",
+            "30",
+            "--max-fee",
+            "50",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+    polymarket()
+        .args([
+            "-o",
+            "json",
+            "ctf",
+            "split",
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "--amount",
+            "1",
+            "--max-fee",
+            "30",
+            "--priority-fee",
+            "40",
+        ])
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("--priority-fee"));
+    polymarket()
+        .args(["allowance", "set", "--gas-multiplier", "0.5"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--gas-multiplier"));
+}
+
+#[test]
+fn auth_status_and_logout_without_a_session() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-auth-{}", std::process::id()));
+    let key = "0x0123456789012345678901234567890123456789012345678901234567890123";
+    polymarket()
+        .env("HOME", &home)
+        .args(["--private-key", key, "-o", "json", "auth", "status"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""state": "none""#));
+    polymarket()
+        .env("HOME", &home)
+        .args(["auth", "logout"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No session"));
+    polymarket()
+        .env("HOME", &home)
+        .args(["--private-key", key, "auth", "login", "--ttl", "0s"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--ttl"));
+}
+
+#[test]
+fn tx_commands_check_their_input() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-tx-{}", std::process::id()));
+    polymarket()
+        .env("HOME", &home)
+        .args(["-o", "json", "tx", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[]"));
+    polymarket()
+        .args(["tx", "status", "0x1234"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Invalid transaction hash"));
+    polymarket()
+        .args([
+            "transfer",
+            "0x0000000000000000000000000000000000000001",
+            "1",
+            "--nonce",
+            "4",
+            "--replace",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn order_ladder_checks_sizes_before_fetching_the_market() {
+    polymarket()
+        .args([
+            "order", "ladder", "--token", "1", "--side", "buy", "--from", "0.3", "--to", "0.4",
+            "--levels", "6",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--size-per-level"));
+    polymarket()
+        .args([
+            "order",
+            "ladder",
+            "--token",
+            "1",
+            "--side",
+            "buy",
+            "--from",
+            "0.3",
+            "--to",
+            "0.4",
+            "--levels",
+            "6",
+            "--size-per-level",
+            "0",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--size-per-level must be positive",
+        ));
+}
+
+#[test]
+fn order_place_takes_the_create_order_flags() {
+    polymarket()
+        .args([
+            "order",
+            "place",
+            "--token",
+            "1",
+            "--side",
+            "buy",
+            "--size",
+            "5",
+            "--post-only",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--price"));
+    polymarket()
+        .args([
+            "order", "place", "--token", "1", "--side", "buy", "--size", "5", "--type", "IOC",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("invalid value 'IOC'"));
+    polymarket()
+        .args([
+            "order",
+            "place",
+            "--token",
+            "1",
+            "--side",
+            "buy",
+            "--size",
+            "5",
+            "--expires-in",
+            "1h",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("an expiry needs --price"));
+    polymarket()
+        .args([
+            "order",
+            "place",
+            "--token",
+            "1",
+            "--side",
+            "buy",
+            "--price",
+            "0.5",
+            "--size",
+            "5",
+            "--expires-in",
+            "soon",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Invalid duration 'soon'"));
+}
+
+#[test]
+fn order_amend_needs_a_change() {
+    polymarket()
+        .args(["order", "amend", "0xabc"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--price"));
+}
+
+#[test]
+fn portfolio_history_checks_its_interval() {
+    polymarket()
+        .args(["portfolio", "history", "--interval", "30m"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--interval must be at least 1h"));
+    polymarket()
+        .args(["portfolio", "history", "--from", "01/02/2025"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("YYYY-MM-DD"));
+}
+
+#[test]
+fn positions_takes_an_address_or_a_subcommand() {
+    polymarket()
+        .args(["positions", "not-an-address"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Invalid address"));
+    polymarket()
+        .args(["positions", "close", "--limit", "5"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn positions_close_checks_its_arguments() {
+    polymarket()
+        .args(["positions", "close"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("<CONDITION_ID>"));
+    polymarket()
+        .args([
+            "positions",
+            "close",
+            "--all",
+            "--market",
+            "--limit-offset",
+            "0.01",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("cannot be used with"));
+    polymarket()
+        .args(["positions", "close", "--all", "--limit-offset", "1.5"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--limit-offset must be"));
+    polymarket()
+        .args(["positions", "close", "0x01"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Invalid condition ID"));
+}
+
+#[test]
+fn tax_report_checks_its_arguments() {
+    polymarket()
+        .args(["tax", "report", "--year", "1999"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--year must be between 2020"));
+    polymarket()
+        .args(["tax", "report", "--year", "2024", "--method", "hifo"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("fifo"));
+}
+
+#[test]
+fn fields_cut_json_output_down() {
+    polymarket()
+        .args(["convert", "0.42", "--fields", "price,probability"])
+        .assert()
+        .success()
+        .stdout("{\n  \"price\": \"0.42\",\n  \"probability\": \"42\"\n}\n");
+    polymarket()
+        .args(["convert", "0.42", "--fields", "price..x"])
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("--fields takes field names"));
+}
+
+#[test]
+fn date_format_must_be_a_strftime_layout() {
+    polymarket()
+        .args(["--date-format", "%Q", "convert", "0.42"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Invalid --date-format"));
+}
+
+#[test]
+fn convert_shows_every_convention() {
+    polymarket()
+        .args(["-o", "json", "convert", "0.42"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains(r#""probability": "42""#)
+                .and(predicate::str::contains(r#""decimal_odds": "2.381""#)),
+        );
+    polymarket()
+        .args(["-o", "plain", "--odds-format", "american", "convert", "0.6"])
+        .assert()
+        .success()
+        .stdout("-150\n");
+    polymarket()
+        .args(["-o", "plain", "convert", "-150"])
+        .assert()
+        .success()
+        .stdout("0.6\n");
+    polymarket()
+        .args(["convert", "+50"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("American odds"));
+}
+
+#[test]
+fn markets_compare_needs_two_markets() {
+    polymarket()
+        .args(["markets", "compare", "will-trump-win"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("2 values required"));
+}
+
+#[test]
+fn create_order_checks_its_expiry() {
+    polymarket()
+        .args([
+            "clob",
+            "create-order",
+            "--token",
+            "1",
+            "--side",
+            "buy",
+            "--price",
+            "0.5",
+            "--size",
+            "10",
+            "--order-type",
+            "GTC",
+            "--expires-in",
+            "2h",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Only GTD orders expire"));
+    polymarket()
+        .args([
+            "clob",
+            "create-order",
+            "--token",
+            "1",
+            "--side",
+            "buy",
+            "--price",
+            "0.5",
+            "--size",
+            "10",
+            "--expires-in",
+            "30s",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("more than a minute"));
+}
+
+#[test]
+fn market_order_takes_one_slippage_bound() {
+    let order = [
+        "clob",
+        "market-order",
+        "--token",
+        "1",
+        "--side",
+        "buy",
+        "--amount",
+        "5",
+    ];
+    polymarket()
+        .args(order)
+        .args(["--max-slippage", "1%", "--limit-price", "0.5"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("cannot be used with"));
+    polymarket()
+        .args(order)
+        .args(["--max-slippage", "150%"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("below 100%"));
+}
+
+#[test]
+fn state_export_and_import_move_contacts_without_keys() {
+    let tmp = std::env::temp_dir();
+    let from = tmp.join(format!("polymarket-cli-state-from-{}", std::process::id()));
+    let to = tmp.join(format!("polymarket-cli-state-to-{}", std::process::id()));
+    let file = from.join("state.json");
+    let _ = std::fs::remove_dir_all(&from);
+    let _ = std::fs::remove_dir_all(&to);
+    std::fs::create_dir_all(&from).unwrap();
+    polymarket()
+        .env("HOME", &from)
+        .args([
+            "wallet",
+            "import",
+            "0x0123456789012345678901234567890123456789012345678901234567890123",
+            "--yes",
+        ])
+        .assert()
+        .success();
+    polymarket()
+        .env("HOME", &from)
+        .args([
+            "contacts",
+            "add",
+            "savings",
+            "0x56687bf447db6ffa42ffe2204a05edaa20f55839",
+        ])
+        .assert()
+        .success();
+    polymarket()
+        .env("HOME", &from)
+        .arg("state")
+        .arg("export")
+        .arg(&file)
+        .assert()
+        .success();
+    let exported = std::fs::read_to_string(&file).unwrap();
+    assert!(!exported.contains("0123456789012345678901234567890123456789012345678901234567890123"));
+    polymarket()
+        .env("HOME", &from)
+        .arg("state")
+        .arg("export")
+        .arg(&file)
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--force"));
+
+    polymarket()
+        .env("HOME", &to)
+        .args(["-o", "json", "state", "import"])
+        .arg(&file)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"contacts_added\": 1"));
+    polymarket()
+        .env("HOME", &to)
+        .args(["-o", "plain", "contacts", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("savings"));
+    polymarket()
+        .env("HOME", &to)
+        .args(["-o", "json", "state", "import"])
+        .arg(&file)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "\"skipped\": [\n    \"default\"\n  ]",
+        ));
+}
+
+#[test]
+fn config_dir_env_var_moves_all_state() {
+    let dir =
+        std::env::temp_dir().join(format!("polymarket-cli-config-dir-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    polymarket()
+        .env("POLYMARKET_CONFIG_DIR", &dir)
+        .args([
+            "contacts",
+            "add",
+            "savings",
+            "0x56687bf447db6ffa42ffe2204a05edaa20f55839",
+        ])
+        .assert()
+        .success();
+    assert!(dir.join("contacts.json").exists());
+    polymarket()
+        .env("POLYMARKET_CONFIG_DIR", &dir)
+        .args(["wallet", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(dir.to_str().unwrap()));
+}
+
+#[test]
+fn db_stats_and_vacuum_cover_the_database() {
+    let dir = std::env::temp_dir().join(format!("polymarket-cli-db-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    // Alerts from before the database are moved into it.
+    std::fs::write(
+        dir.join("alerts.json"),
+        r#"[{"id":1,"market_id":"1","question":"Q?","outcome":"Yes","token_id":"7","direction":"above","price":"0.6"}]"#,
+    )
+    .unwrap();
+    polymarket()
+        .env("POLYMARKET_CONFIG_DIR", &dir)
+        .args(["-o", "json", "db", "stats"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains(r#""schema_version": 2"#)
+                .and(predicate::str::contains(r#""table": "alerts""#))
+                .and(predicate::str::contains(r#""rows": 1"#)),
+        );
+    assert!(dir.join("polymarket.db").exists());
+    assert!(dir.join("alerts.json.imported").exists());
+    polymarket()
+        .env("POLYMARKET_CONFIG_DIR", &dir)
+        .args(["db", "vacuum"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("compacted the database"));
+    let conn = rusqlite::Connection::open(dir.join("polymarket.db")).unwrap();
+    conn.pragma_update(None, "user_version", 99).unwrap();
+    drop(conn);
+    polymarket()
+        .env("POLYMARKET_CONFIG_DIR", &dir)
+        .args(["db", "stats"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("newer than this CLI's"));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn sync_reads_need_a_synced_index() {
+    let dir = std::env::temp_dir().join(format!("polymarket-cli-sync-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let key = "0x0123456789012345678901234567890123456789012345678901234567890123";
+    for args in [
+        vec!["sync", "positions"],
+        vec!["sync", "trades"],
+        vec!["tax", "report", "--year", "2024", "--synced"],
+    ] {
+        polymarket()
+            .env("POLYMARKET_CONFIG_DIR", &dir)
+            .args(["--private-key", key, "--signature-type", "eoa"])
+            .args(&args)
+            .assert()
+            .code(2)
+            .stderr(predicate::str::contains("Run `polymarket sync` first"));
+    }
+    polymarket()
+        .env("POLYMARKET_CONFIG_DIR", &dir)
+        .args(["--private-key", key, "sync", "--chunk", "10"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--chunk must be at least 100"));
+}
+
+#[test]
+fn copy_watch_goes_live_only_after_a_dry_run() {
+    let dir = std::env::temp_dir().join(format!("polymarket-cli-copy-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    polymarket()
+        .env("POLYMARKET_CONFIG_DIR", &dir)
+        .args([
+            "copy",
+            "watch",
+            "0x56687bf447db6ffa42ffe2204a05edaa20f55839",
+            "--max-exposure",
+            "100",
+            "--live",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("without --live first"));
+    polymarket()
+        .args([
+            "copy",
+            "watch",
+            "0x56687bf447db6ffa42ffe2204a05edaa20f55839",
+            "--max-exposure",
+            "100",
+            "--scale",
+            "0",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--scale must be positive"));
+    polymarket()
+        .args([
+            "copy",
+            "watch",
+            "0x56687bf447db6ffa42ffe2204a05edaa20f55839",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--max-exposure"));
+}
+
+#[test]
+fn record_rejects_a_bad_rotate_size() {
+    let dir = std::env::temp_dir().join(format!("polymarket-cli-record-{}", std::process::id()));
+    polymarket()
+        .args([
+            "record",
+            "--tokens",
+            "123",
+            "--rotate-size",
+            "lots",
+            "--out",
+        ])
+        .arg(dir.join("tape.jsonl"))
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Invalid size 'lots'"));
+    assert!(!dir.join("tape.jsonl").exists());
+}
+
+const RECORDING: &str = concat!(
+    r#"{"type":"book","recorded_at":"2025-06-01T12:00:00.000Z","market":"0x0000000000000000000000000000000000000000000000000000000000000001","asset_id":"7","timestamp":"2025-06-01T12:00:00+00:00","bids":[{"price":"0.3","size":"10"}],"asks":[{"price":"0.32","size":"10"}],"min_order_size":"5","neg_risk":false,"tick_size":"0.01"}"#,
+    "\n",
+    r#"{"type":"trade","recorded_at":"2025-06-01T12:00:01.000Z","asset_id":"7","price":"0.31","side":"BUY","size":"4"}"#,
+    "\n",
+    r#"{"type":"book","recorded_at":"2025-06-01T12:00:02.000Z","market":"0x0000000000000000000000000000000000000000000000000000000000000001","asset_id":"7","timestamp":"2025-06-01T12:00:02+00:00","bids":[{"price":"0.6","size":"10"}],"asks":[{"price":"0.62","size":"10"}],"min_order_size":"5","neg_risk":false,"tick_size":"0.01"}"#,
+    "\n",
+);
+
+#[test]
+fn replay_prints_the_recording_in_order() {
+    let dir = std::env::temp_dir().join(format!("polymarket-cli-replay-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("tape.jsonl");
+    std::fs::write(&file, RECORDING).unwrap();
+    let out = polymarket()
+        .args(["replay", "--speed", "max"])
+        .arg(&file)
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let types: Vec<String> = String::from_utf8(out.stdout)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap()["type"].to_string())
+        .collect();
+    assert_eq!(types, [r#""book""#, r#""trade""#, r#""book""#]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn replay_runs_a_strategy_against_the_book() {
+    let dir = std::env::temp_dir().join(format!("polymarket-cli-replay-bt-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("tape.jsonl");
+    std::fs::write(&file, RECORDING).unwrap();
+    let strategy = dir.join("dips.toml");
+    std::fs::write(
+        &strategy,
+        "type = \"threshold\"\nbuy_below = 0.35\nsell_above = 0.5\nstake = 32\n",
+    )
+    .unwrap();
+    // Buys 100 shares at the 0.32 ask and sells them at the 0.6 bid.
+    polymarket()
+        .args(["-o", "json", "replay"])
+        .arg(&file)
+        .arg("--strategy")
+        .arg(&strategy)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""pnl": "28"#));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn replay_reads_what_record_put_in_the_database() {
+    let dir = std::env::temp_dir().join(format!("polymarket-cli-replay-db-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    polymarket()
+        .args(["record", "--tokens", "7", "--db", "--rotate-size", "1MB"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+    polymarket()
+        .env("POLYMARKET_CONFIG_DIR", &dir)
+        .args(["replay", "--db"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Nothing recorded"));
+    let conn = rusqlite::Connection::open(dir.join("polymarket.db")).unwrap();
+    for line in RECORDING.lines() {
+        let record: serde_json::Value = serde_json::from_str(line).unwrap();
+        conn.execute(
+            "INSERT INTO recordings (recorded_at, type, token_id, record) VALUES (?1, ?2, '7', ?3)",
+            [
+                record["recorded_at"].as_str().unwrap(),
+                record["type"].as_str().unwrap(),
+                line,
+            ],
+        )
+        .unwrap();
+    }
+    drop(conn);
+    let out = polymarket()
+        .env("POLYMARKET_CONFIG_DIR", &dir)
+        .args(["replay", "--db", "--speed", "max"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    assert_eq!(String::from_utf8(out.stdout).unwrap().lines().count(), 3);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn book_shows_a_token_or_its_stats() {
+    polymarket()
+        .args(["book", "1", "--watch", "0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("at least 1 second"));
+    polymarket().args(["book"]).assert().failure();
+    polymarket()
+        .args(["book", "stats", "1", "--depth", "3"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn book_stats_rejects_a_zero_band() {
+    polymarket()
+        .args(["book", "stats", "123", "--within", "1,0"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--within bands must be positive"));
+}
+
+#[test]
+fn alert_add_requires_a_threshold() {
+    polymarket()
+        .args(["alert", "add", "--market", "12"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--above"));
+}
+
+#[test]
+fn alert_add_rejects_both_thresholds() {
+    polymarket()
+        .args([
+            "alert", "add", "--market", "12", "--above", "0.7", "--below", "0.3",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn alert_remove_unknown_id_fails() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-alert-{}", std::process::id()));
+    polymarket()
+        .env("HOME", &home)
+        .args(["alert", "remove", "7"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No alert with ID 7"));
+}
+
+#[test]
+fn stream_user_notify_requires_notifiers() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-nohome-{}", std::process::id()));
+    polymarket()
+        .env("HOME", &home)
+        .env_remove("POLYMARKET_PRIVATE_KEY")
+        .args(["stream", "user", "--notify"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No notifiers configured"));
+}
+
+#[test]
+fn completions_bash_registers_dynamic_completer() {
+    polymarket()
+        .args(["completions", "bash"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("COMPLETE=\"bash\"")
+                .and(predicate::str::contains("polymarket")),
+        );
+}
+
+#[test]
+fn completions_static_zsh_lists_subcommands() {
+    polymarket()
+        .args(["completions", "zsh", "--static"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("#compdef polymarket").and(predicate::str::contains("watch")),
+        );
+}
+
+#[test]
+fn completions_rejects_unknown_shell() {
+    polymarket()
+        .args(["completions", "tcsh"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value"));
+}
+
+#[test]
+fn dynamic_completion_offers_watchlist_slugs() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-complete-{}", std::process::id()));
+    let dir = home.join(".config").join("polymarket");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("watchlist.json"),
+        r#"[{"id": "12", "slug": "will-it-rain", "question": "Will it rain?"}]"#,
+    )
+    .unwrap();
+    polymarket()
+        .env("HOME", &home)
+        .env("COMPLETE", "fish")
+        .args(["--", "polymarket", "watch", "remove", "will"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("will-it-rain"));
+}
+
+#[test]
+fn ledger_conflicts_with_private_key() {
+    polymarket()
+        .args(["--ledger", "--private-key", "0x1", "wallet", "address"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn help_shows_ledger_flag() {
+    polymarket()
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--ledger"));
+}
+
+#[test]
+fn link_proxy_without_wallet_explains_setup() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-nohome-{}", std::process::id()));
+    polymarket()
+        .env("HOME", &home)
+        .args([
+            "wallet",
+            "link-proxy",
+            "0x1111111111111111111111111111111111111111",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No wallet configured"));
+}
+
+#[test]
+fn link_proxy_rejects_eoa_signature_type() {
+    polymarket()
+        .args([
+            "wallet",
+            "link-proxy",
+            "0x1111111111111111111111111111111111111111",
+            "--signature-type",
+            "eoa",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value"));
+}
+
+#[test]
+fn invalid_funder_is_rejected() {
+    polymarket()
+        .args(["--funder", "not-an-address", "status"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid --funder"));
+}
+
+#[test]
+fn create_order_requires_price_or_odds() {
+    polymarket()
+        .args([
+            "clob",
+            "create-order",
+            "--token",
+            "1",
+            "--side",
+            "buy",
+            "--size",
+            "10",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--price"));
+}
+
+#[test]
+fn create_order_rejects_price_with_odds() {
+    polymarket()
+        .args([
+            "clob",
+            "create-order",
+            "--token",
+            "1",
+            "--side",
+            "buy",
+            "--price",
+            "0.5",
+            "--odds",
+            "2",
+            "--size",
+            "10",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn create_order_help_shows_notional_and_yes() {
+    polymarket()
+        .args(["clob", "create-order", "--help"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("--notional")
+                .and(predicate::str::contains("--odds"))
+                .and(predicate::str::contains("--yes")),
+        );
+}
+
+#[test]
+fn batch_rejects_unknown_file_type() {
+    let path = std::env::temp_dir().join(format!("polymarket-batch-{}.txt", std::process::id()));
+    std::fs::write(&path, "token,side,price,size\n").unwrap();
+    polymarket()
+        .args(["clob", "batch"])
+        .arg(&path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("use .json or .csv"));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn batch_reports_malformed_row_before_signing() {
+    let path = std::env::temp_dir().join(format!("polymarket-batch-{}.csv", std::process::id()));
+    std::fs::write(&path, "token,side,price,size\n1,hold,0.5,10\n").unwrap();
+    polymarket()
+        .args(["clob", "batch"])
+        .arg(&path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Line 2: Invalid side: hold"));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn stop_rejects_buying_all_before_signing() {
+    polymarket()
+        .args([
+            "clob",
+            "stop",
+            "--token",
+            "1",
+            "--trigger",
+            "0.3",
+            "--side",
+            "buy",
+            "--size",
+            "all",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--size all only works with --side sell",
+        ));
+}
+
+#[test]
+fn stop_rejects_trigger_outside_unit_range() {
+    polymarket()
+        .args([
+            "clob",
+            "stop",
+            "--token",
+            "1",
+            "--trigger",
+            "1.5",
+            "--side",
+            "sell",
+            "--size",
+            "10",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--trigger must be between 0 and 1",
+        ));
+}
+
+#[test]
+fn stop_daemon_conflicts_with_dry_run() {
+    polymarket()
+        .args([
+            "clob",
+            "stop",
+            "--token",
+            "1",
+            "--trigger",
+            "0.3",
+            "--side",
+            "sell",
+            "--size",
+            "10",
+            "--daemon",
+            "--dry-run",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn daemon_status_without_daemon_reports_not_running() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-daemon-{}", std::process::id()));
+    polymarket()
+        .env("HOME", &home)
+        .args(["-o", "json", "daemon", "status"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""running": false"#));
+    polymarket()
+        .env("HOME", &home)
+        .args(["daemon", "stops"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "The daemon is not running. Start it with `polymarket daemon run`.",
+        ));
+}
+
+#[test]
+fn paper_mode_rejects_unsimulated_commands() {
+    polymarket()
+        .args(["--paper", "clob", "batch", "orders.json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Not available in paper mode"));
+    polymarket()
+        .args(["--paper", "--yes", "panic"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Not available in paper mode"));
+}
+
+#[test]
+fn paper_account_reset_status_and_cancel() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-paper-{}", std::process::id()));
+    polymarket()
+        .env("HOME", &home)
+        .args(["paper", "reset", "--balance", "500"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Paper account reset with 500 USDC",
+        ));
+    polymarket()
+        .env("HOME", &home)
+        .args(["-o", "json", "paper", "status"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains(r#""usdc_balance": "500""#)
+                .and(predicate::str::contains(r#""open_orders": 0"#)),
+        );
+    polymarket()
+        .env("HOME", &home)
+        .args(["--paper", "clob", "cancel", "paper-9"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No open paper order paper-9"));
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+#[test]
+fn help_shows_endpoint_flags() {
+    polymarket()
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--rpc-url").and(predicate::str::contains("--clob-url")));
+}
+
+#[test]
+fn invalid_rpc_url_is_rejected() {
+    polymarket()
+        .args(["--rpc-url", "not-a-url", "status"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid --rpc-url"));
+}
+
+#[test]
+fn help_shows_network_flags() {
+    polymarket().arg("--help").assert().success().stdout(
+        predicate::str::contains("--timeout")
+            .and(predicate::str::contains("--retries"))
+            .and(predicate::str::contains("--backoff")),
+    );
+}
+
+#[test]
+fn invalid_timeout_is_rejected() {
+    polymarket()
+        .args(["--timeout", "soon", "status"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid timeout"));
+}
+
+#[test]
+fn markets_get_is_served_from_cache_and_cache_clear_empties_it() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-cache-{}", std::process::id()));
+    let dir = home.join(".config/polymarket/cache/markets");
+    std::fs::create_dir_all(&dir).unwrap();
+    let entry = serde_json::json!({
+        "fetched_at": chrono::Utc::now().to_rfc3339(),
+        "value": {"id": "12", "question": "Served from the cache?", "slug": "cached-market"},
+    });
+    std::fs::write(dir.join("slug-cached-market.json"), entry.to_string()).unwrap();
+
+    polymarket()
+        .env("HOME", &home)
+        .args(["--output", "json", "markets", "get", "cached-market"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Served from the cache?"));
+    polymarket()
+        .env("HOME", &home)
+        .args(["cache", "clear"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cleared 1 cached entries."));
+    assert!(!home.join(".config/polymarket/cache").exists());
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+#[test]
+fn markets_source_shows_rules_and_links_from_a_cached_market() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-source-{}", std::process::id()));
+    let dir = home.join(".config/polymarket/cache/markets");
+    std::fs::create_dir_all(&dir).unwrap();
+    let entry = serde_json::json!({
+        "fetched_at": chrono::Utc::now().to_rfc3339(),
+        "value": {
+            "id": "12",
+            "question": "Will it rain?",
+            "slug": "rain",
+            "description": "Resolves Yes if https://weather.gov reports rain.",
+        },
+    });
+    std::fs::write(dir.join("slug-rain.json"), entry.to_string()).unwrap();
+
+    polymarket()
+        .env("HOME", &home)
+        .args(["markets", "source", "rain"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Rules:"))
+        .stdout(predicate::str::contains("Links:\nhttps://weather.gov\n"))
+        .stdout(predicate::str::contains("Unavailable: Gamma doesn't say"));
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+#[test]
+fn create_order_rejects_price_off_the_tick_grid() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-tick-{}", std::process::id()));
+    let dir = home.join(".config/polymarket/cache/order-params");
+    std::fs::create_dir_all(&dir).unwrap();
+    let entry = serde_json::json!({
+        "fetched_at": chrono::Utc::now().to_rfc3339(),
+        "value": {"tick_size": "0.01", "min_order_size": "5", "neg_risk": false},
+    });
+    std::fs::write(dir.join("123.json"), entry.to_string()).unwrap();
+
+    let create_order = |price: &str, size: &str| {
+        polymarket()
+            .env("HOME", &home)
+            .args(["clob", "create-order", "--token", "123", "--side", "buy"])
+            .args(["--price", price, "--size", size])
+            .assert()
+            .failure()
+    };
+    create_order("0.555", "10").stderr(predicate::str::contains(
+        "not a multiple of this market's tick size 0.01; try 0.55 or 0.56",
+    ));
+    create_order("0.55", "2").stderr(predicate::str::contains(
+        "below this market's minimum order of 5 shares",
+    ));
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+#[test]
+fn quote_requires_a_market() {
+    polymarket()
+        .arg("quote")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("<MARKETS>"));
+}
+
+#[test]
+fn quote_interval_requires_watch() {
+    polymarket()
+        .args(["quote", "will-it-rain", "--interval", "2"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--watch"));
+}
+
+#[test]
+fn fees_price_requires_size() {
+    polymarket()
+        .args(["fees", "will-it-rain", "--price", "0.4"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--size"));
+}
+
+#[test]
+fn scan_arb_rejects_invalid_min_edge() {
+    polymarket()
+        .args(["scan", "arb", "--min-edge", "a-cent"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--min-edge"));
+}
+
+#[test]
+fn order_twap_rejects_jitter_of_one_or_more() {
+    polymarket()
+        .args([
+            "order",
+            "twap",
+            "--token",
+            "123",
+            "--side",
+            "buy",
+            "--total",
+            "500",
+            "--duration",
+            "1h",
+            "--slice",
+            "50",
+            "--jitter",
+            "1",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--jitter must be at least 0 and below 1",
+        ));
+}
+
+#[test]
+fn order_twap_needs_its_arguments_without_a_subcommand() {
+    polymarket()
+        .args(["order", "twap", "--token", "123", "--side", "buy"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--total <TOTAL>"));
+    polymarket()
+        .args(["order", "twap", "list", "--token", "123"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn order_list_takes_the_open_order_filters() {
+    polymarket()
+        .args(["order", "list", "--older-than", "soon"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid duration"));
+}
+
+#[test]
+fn order_twap_list_shows_saved_progress() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-twap-{}", std::process::id()));
+    let dir = home.join(".config/polymarket");
+    std::fs::create_dir_all(&dir).unwrap();
+    let jobs = serde_json::json!([{
+        "id": 1,
+        "token_id": "123",
+        "side": "BUY",
+        "total": "500",
+        "slice": "50",
+        "slices": 10,
+        "interval_secs": 360,
+        "jitter": "0.2",
+        "order_type": "FAK",
+        "sent": 4,
+        "filled_shares": "400",
+        "filled_usdc": "200",
+        "started_at": "2026-01-01T00:00:00Z",
+    }]);
+    std::fs::write(dir.join("twap.json"), jobs.to_string()).unwrap();
+
+    polymarket()
+        .env("HOME", &home)
+        .args(["order", "twap", "list"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("$200.00 of $500.00 (40%)")
+                .and(predicate::str::contains("4/10"))
+                .and(predicate::str::contains("paused")),
+        );
+    polymarket()
+        .env("HOME", &home)
+        .args(["order", "twap", "remove", "1"])
+        .assert()
+        .success();
+    polymarket()
+        .env("HOME", &home)
+        .args(["order", "twap", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No TWAP jobs."));
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+#[test]
+fn size_recommends_fractional_kelly_stake() {
+    polymarket()
+        .args([
+            "-o",
+            "json",
+            "size",
+            "--prob",
+            "0.6",
+            "--price",
+            "0.5",
+            "--bankroll",
+            "1000",
+        ])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains(r#""kelly": "0.2""#)
+                .and(predicate::str::contains(r#""stake": "100.00""#)),
+        );
+}
+
+#[test]
+fn size_place_requires_token() {
+    polymarket()
+        .args(["size", "--prob", "0.6", "--price", "0.5", "--place"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--token"));
+}
+
+#[test]
+fn trades_market_rejects_inverted_dates() {
+    polymarket()
+        .args([
+            "trades",
+            "market",
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "--from",
+            "2025-02-01",
+            "--to",
+            "2025-01-01",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--from must not be after --to"));
+}
+
+#[test]
+fn backtest_replays_tape_through_threshold_strategy() {
+    let dir = std::env::temp_dir().join(format!("pm-backtest-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let strategy = dir.join("dips.toml");
+    let data = dir.join("tape.csv");
+    std::fs::write(
+        &strategy,
+        "type = \"threshold\"\nbuy_below = 0.4\nsell_above = 0.6\nstake = 100\n",
+    )
+    .unwrap();
+    std::fs::write(
+        &data,
+        "title,outcome_index,price,timestamp\n\"Rain, today?\",0,0.5,1\n\"Rain, today?\",1,0.6,2\n\"Rain, today?\",0,0.6,3\n",
+    )
+    .unwrap();
+
+    polymarket()
+        .args(["-o", "json", "backtest", "--strategy"])
+        .arg(&strategy)
+        .arg("--data")
+        .arg(&data)
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains(r#""pnl": "50""#)
+                .and(predicate::str::contains(r#""round_trips": 1"#)),
+        );
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn contacts_add_list_and_remove() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-contacts-{}", std::process::id()));
+    let address = "0x56687bf447db6ffa42ffe2204a05edaa20f55839";
+    polymarket()
+        .env("HOME", &home)
+        .args(["contacts", "add", "savings", address])
+        .assert()
+        .success();
+    polymarket()
+        .env("HOME", &home)
+        .args(["contacts", "add", "Savings", address])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+    polymarket()
+        .env("HOME", &home)
+        .args(["-o", "csv", "contacts", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "savings,0x56687BF447DB6fFA42FFE2204a05EDAA20f55839",
+        ));
+    polymarket()
+        .env("HOME", &home)
+        .args(["contacts", "remove", "SAVINGS"])
+        .assert()
+        .success();
+    polymarket()
+        .env("HOME", &home)
+        .args(["-o", "json", "contacts", "list"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("polymarket"));
+        .stdout(predicate::str::contains("[]"));
+    std::fs::remove_dir_all(&home).ok();
 }
 
 #[test]
-fn markets_help_lists_subcommands() {
+fn funder_rejects_unknown_contact() {
+    let home = std::env::temp_dir().join(format!(
+        "polymarket-cli-contacts-funder-{}",
+        std::process::id()
+    ));
     polymarket()
-        .args(["markets", "--help"])
+        .env("HOME", &home)
+        .args(["--funder", "nobody", "contacts", "list"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "neither a 0x-prefixed address nor a saved contact",
+        ));
+}
+
+#[test]
+fn wallet_export_requires_the_confirmation_phrase() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-export-{}", std::process::id()));
+    let key = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+    polymarket()
+        .env("HOME", &home)
+        .args(["wallet", "import", key, "--force"])
+        .assert()
+        .success();
+
+    polymarket()
+        .env("HOME", &home)
+        .args(["wallet", "export", "--show-private-key"])
+        .write_stdin("yes\n")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(key).not())
+        .stderr(predicate::str::contains("Cancelled"));
+    polymarket()
+        .env("HOME", &home)
+        .args(["-o", "plain", "wallet", "export", "--show-private-key"])
+        .write_stdin("export my key\n")
+        .assert()
+        .success()
+        .stdout(format!("{key}\n"));
+
+    let keystore = home.join("backup.json");
+    polymarket()
+        .env("HOME", &home)
+        .env("POLYMARKET_PASSPHRASE", "hunter2")
+        .args(["wallet", "export", "--yes", "--keystore"])
+        .arg(&keystore)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23",
+        ));
+    assert!(keystore.exists());
+    std::fs::remove_dir_all(&home).ok();
+}
+
+#[test]
+fn watch_only_wallet_shows_address_but_cannot_sign() {
+    let home =
+        std::env::temp_dir().join(format!("polymarket-cli-watch-only-{}", std::process::id()));
+    let address = "0x56687BF447DB6fFA42FFE2204a05EDAA20f55839";
+    polymarket()
+        .env("HOME", &home)
+        .args(["-o", "plain", "wallet", "import", "--watch-only", address])
+        .assert()
+        .success()
+        .stdout(format!("{address}\n"));
+    polymarket()
+        .env("HOME", &home)
+        .args(["-o", "json", "wallet", "show"])
         .assert()
         .success()
         .stdout(
-            predicate::str::contains("list")
-                .and(predicate::str::contains("get"))
-                .and(predicate::str::contains("search"))
-                .and(predicate::str::contains("tags")),
+            predicate::str::contains(format!(r#""address":"{address}""#))
+                .and(predicate::str::contains(r#""watch_only":true"#))
+                .and(predicate::str::contains(r#""proxy_address":null"#)),
         );
+    polymarket()
+        .env("HOME", &home)
+        .args(["wallet", "export", "--yes", "--show-private-key"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("watch-only wallet"));
+    polymarket()
+        .env("HOME", &home)
+        .args(["wallet", "import", "--watch-only", address])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Replace it?").and(predicate::str::contains("--yes")));
+    std::fs::remove_dir_all(&home).ok();
 }
 
 #[test]
-fn events_help_lists_subcommands() {
+fn panic_asks_once_before_doing_anything() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-panic-{}", std::process::id()));
     polymarket()
-        .args(["events", "--help"])
+        .env("HOME", &home)
+        .args(["panic", "--close-positions"])
+        .write_stdin("n\n")
         .assert()
         .success()
-        .stdout(
-            predicate::str::contains("list")
-                .and(predicate::str::contains("get"))
-                .and(predicate::str::contains("tags")),
+        .stderr(predicate::str::contains(
+            "Stop the daemon, cancel all open orders and sell every position at market? [y/N]",
+        ))
+        .stdout(predicate::str::contains("Aborted."));
+    std::fs::remove_dir_all(&home).ok();
+}
+
+#[cfg(unix)]
+#[test]
+fn panic_stops_running_bots_before_anything_else() {
+    let dir =
+        std::env::temp_dir().join(format!("polymarket-cli-panic-bots-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("alerts.json"),
+        r#"[{"id":1,"market_id":"1","question":"Q","outcome":"Yes","token_id":"1","direction":"above","price":"0.5"}]"#,
+    )
+    .unwrap();
+    let mut bot = std::process::Command::new(assert_cmd::cargo::cargo_bin("polymarket"))
+        .env("POLYMARKET_CONFIG_DIR", &dir)
+        .env_remove("POLYMARKET_PRIVATE_KEY")
+        .args([
+            "--clob-url",
+            "http://127.0.0.1:1",
+            "alert",
+            "run",
+            "--interval",
+            "60",
+        ])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .unwrap();
+    let run = dir.join("run");
+    let registered = || std::fs::read_dir(&run).is_ok_and(|mut d| d.next().is_some());
+    for _ in 0..100 {
+        if registered() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    assert!(registered(), "alert run never registered");
+
+    // No wallet, so the cancel fails, but only after the bot is stopped.
+    polymarket()
+        .env("POLYMARKET_CONFIG_DIR", &dir)
+        .args(["--yes", "panic"])
+        .assert()
+        .failure();
+    let exited = (0..50).any(|_| {
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        bot.try_wait().unwrap().is_some()
+    });
+    if !exited {
+        bot.kill().ok();
+    }
+    assert!(exited, "alert run survived panic");
+    assert!(!registered());
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn fund_moving_commands_ask_unless_yes() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-confirm-{}", std::process::id()));
+    polymarket()
+        .env("HOME", &home)
+        .args(["clob", "cancel-all"])
+        .assert()
+        .failure()
+        .stderr(
+            predicate::str::contains("Cancel all open orders? [y/N]")
+                .and(predicate::str::contains("Pass --yes")),
         );
+    polymarket()
+        .env("HOME", &home)
+        .args(["clob", "cancel-all"])
+        .write_stdin("n\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Aborted."));
+    polymarket()
+        .env("HOME", &home)
+        .args(["--yes", "clob", "cancel-all"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("[y/N]").not());
+    polymarket()
+        .env("HOME", &home)
+        .args([
+            "transfer",
+            "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23",
+            "5",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Send 5 USDC to 0x2c7536E3605D9C16a7a3D7b1898e529396a65c23?",
+        ));
 }
 
 #[test]
-fn wallet_help_lists_subcommands() {
+fn verbose_logs_failed_requests_to_stderr() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-verbose-{}", std::process::id()));
+    let args = [
+        "--retries",
+        "0",
+        "--clob-url",
+        "http://127.0.0.1:9",
+        "clob",
+        "ok",
+    ];
     polymarket()
-        .args(["wallet", "--help"])
+        .env("HOME", &home)
+        .args(args)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("DEBUG").not());
+    polymarket()
+        .env("HOME", &home)
+        .arg("-v")
+        .args(args)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "DEBUG polymarket::net: request failed",
+        ));
+}
+
+#[test]
+fn log_file_setting_keeps_the_log_off_stderr() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-log-file-{}", std::process::id()));
+    polymarket()
+        .env("HOME", &home)
+        .args(["config", "set", "log.level", "DEBUG"])
+        .assert()
+        .success();
+    polymarket()
+        .env("HOME", &home)
+        .args(["config", "set", "log.file", "polymarket.log"])
+        .assert()
+        .success();
+    polymarket()
+        .env("HOME", &home)
+        .args(["config", "set", "log.level", "loud"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid log.level"));
+
+    polymarket()
+        .env("HOME", &home)
+        .args([
+            "--retries",
+            "0",
+            "--clob-url",
+            "http://127.0.0.1:9",
+            "clob",
+            "ok",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("DEBUG").not());
+    let log = std::fs::read_to_string(home.join(".config/polymarket/polymarket.log")).unwrap();
+    assert!(log.contains("DEBUG polymarket::net: request failed"));
+}
+
+#[test]
+fn config_set_show_and_unset_round_trip() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-config-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&home);
+
+    polymarket()
+        .env("HOME", &home)
+        .args([
+            "config",
+            "set",
+            "endpoints.clob_url",
+            "http://localhost:8080",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Set endpoints.clob_url to http://localhost:8080.",
+        ));
+    polymarket()
+        .env("HOME", &home)
+        .args(["config", "set", "network.retries", "5"])
+        .assert()
+        .success();
+    polymarket()
+        .env("HOME", &home)
+        .args(["-o", "json", "config", "show"])
         .assert()
         .success()
         .stdout(
-            predicate::str::contains("create")
-                .and(predicate::str::contains("import"))
-                .and(predicate::str::contains("address"))
-                .and(predicate::str::contains("show"))
-                .and(predicate::str::contains("reset")),
+            predicate::str::contains(r#""clob_url": "http://localhost:8080""#)
+                .and(predicate::str::contains(r#""retries": 5"#)),
+        );
+    polymarket()
+        .env("HOME", &home)
+        .args(["config", "unset", "endpoints.clob_url"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Unset endpoints.clob_url."));
+    polymarket()
+        .env("HOME", &home)
+        .args(["config", "show"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("network.retries")
+                .and(predicate::str::contains("localhost").not()),
         );
+    let _ = std::fs::remove_dir_all(&home);
 }
 
 #[test]
-fn no_args_shows_usage() {
+fn config_set_rejects_wallet_fields_and_bad_values() {
+    let home =
+        std::env::temp_dir().join(format!("polymarket-cli-config-bad-{}", std::process::id()));
     polymarket()
+        .env("HOME", &home)
+        .args(["config", "set", "private_key", "0xabc"])
         .assert()
         .failure()
-        .stderr(predicate::str::contains("Usage"));
+        .stderr(predicate::str::contains("invalid value 'private_key'"));
+    polymarket()
+        .env("HOME", &home)
+        .args(["config", "set", "signature_type", "safe"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid signature_type 'safe'"));
+    polymarket()
+        .env("HOME", &home)
+        .args(["config", "set", "network.timeout", "soon"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid network.timeout"));
+    polymarket()
+        .env("HOME", &home)
+        .args(["config", "set", "theme", "neon"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid value for theme"));
+    polymarket()
+        .env("HOME", &home)
+        .args(["config", "set", "risk.max_order_size", "lots"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("use a positive USDC amount"));
+    let _ = std::fs::remove_dir_all(&home);
 }
 
 #[test]
-fn unknown_command_fails() {
-    polymarket().arg("nonexistent").assert().failure();
+fn output_format_layers_flag_over_env_over_profile() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-output-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&home);
+    polymarket()
+        .env("HOME", &home)
+        .args(["config", "set", "output", "json"])
+        .assert()
+        .success();
+
+    polymarket()
+        .env("HOME", &home)
+        .args(["config", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""output": "json""#));
+    polymarket()
+        .env("HOME", &home)
+        .env("POLYMARKET_OUTPUT", "table")
+        .args(["config", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("│ output"));
+    polymarket()
+        .env("HOME", &home)
+        .env("POLYMARKET_OUTPUT", "json")
+        .args(["-o", "table", "config", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("│ output"));
+    let _ = std::fs::remove_dir_all(&home);
 }
 
 #[test]
-fn invalid_output_format_rejected() {
+fn invalid_output_env_var_is_rejected() {
     polymarket()
-        .args(["--output", "xml", "status"])
+        .env("POLYMARKET_OUTPUT", "xml")
+        .args(["config", "show"])
         .assert()
         .failure()
-        .stderr(predicate::str::contains("invalid value"));
+        .stderr(predicate::str::contains("Invalid POLYMARKET_OUTPUT: xml"));
 }
 
 #[test]
-fn markets_search_requires_query() {
-    polymarket().args(["markets", "search"]).assert().failure();
+fn tui_help_shows_refresh() {
+    polymarket()
+        .args(["tui", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--refresh").and(predicate::str::contains("MARKETS")));
+}
+
+#[test]
+fn tui_rejects_zero_refresh() {
+    polymarket()
+        .args(["tui", "--refresh", "0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--refresh"));
+}
+
+#[test]
+fn trades_list_rejects_invalid_date() {
+    polymarket()
+        .args(["trades", "list", "--since", "01/02/2025"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("YYYY-MM-DD"));
+}
+
+#[test]
+fn trades_list_rejects_unknown_format() {
+    polymarket()
+        .args(["trades", "list", "--format", "xml"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn data_positions_without_wallet_explains_setup() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-nohome-{}", std::process::id()));
+    polymarket()
+        .env("HOME", &home)
+        .args(["data", "positions"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No wallet configured"));
 }
 
 #[test]
@@ -141,11 +2717,45 @@ fn json_mode_error_is_valid_json_with_error_key() {
     let parsed: serde_json::Value = serde_json::from_str(stdout.trim())
         .unwrap_or_else(|e| panic!("stdout not valid JSON: {e}\nstdout: {stdout}"));
     assert!(
-        parsed.get("error").is_some(),
-        "missing 'error' key: {parsed}"
+        parsed["error"]["code"].is_string() && parsed["error"]["message"].is_string(),
+        "missing 'error' code or message: {parsed}"
     );
 }
 
+#[test]
+fn json_errors_carry_a_class_and_exit_code() {
+    let home = std::env::temp_dir().join(format!("polymarket-cli-errors-{}", std::process::id()));
+    let cases: [(&[&str], &str, i32); 3] = [
+        (&["balance"], "auth", 3),
+        (
+            &[
+                "--retries",
+                "0",
+                "--clob-url",
+                "http://127.0.0.1:9",
+                "clob",
+                "midpoint",
+                "123",
+            ],
+            "network",
+            4,
+        ),
+        (&["markets", "show", "0xzz"], "validation", 2),
+    ];
+    for (args, code, exit) in cases {
+        let output = polymarket()
+            .env("HOME", &home)
+            .args(["-o", "json"])
+            .args(args)
+            .output()
+            .unwrap();
+        let parsed: serde_json::Value =
+            serde_json::from_slice(&output.stdout).expect("stdout is JSON");
+        assert_eq!(parsed["error"]["code"], code, "{args:?}");
+        assert_eq!(output.status.code(), Some(exit), "{args:?}");
+    }
+}
+
 #[test]
 fn table_mode_error_goes_to_stderr() {
     polymarket()
@@ -177,6 +2787,120 @@ fn wallet_show_json_has_configured_field() {
     );
 }
 
+#[test]
+fn wallet_show_reads_a_keyring_address_without_the_keyring() {
+    let dir = std::env::temp_dir().join(format!("polymarket-cli-keyring-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("profiles")).unwrap();
+    std::fs::write(
+        dir.join("profiles/default.json"),
+        r#"{"chain_id": 137, "signature_type": "eoa", "keyring": true,
+            "address": "0x14791697260E4c9A71f18484C9f997B308e59325"}"#,
+    )
+    .unwrap();
+    polymarket()
+        .env("POLYMARKET_CONFIG_DIR", &dir)
+        .args(["wallet", "show"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("0x14791697260E4c9A71f18484C9f997B308e59325")
+                .and(predicate::str::contains("OS keyring")),
+        );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn no_config_never_touches_the_config_dir() {
+    let dir = std::env::temp_dir().join(format!("polymarket-cli-no-config-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    polymarket()
+        .env("POLYMARKET_CONFIG_DIR", &dir)
+        .env(
+            "POLYMARKET_PRIVATE_KEY",
+            "0x0123456789012345678901234567890123456789012345678901234567890123",
+        )
+        .args(["--no-config", "wallet", "show"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("0x14791697260E4c9A71f18484C9f997B308e59325")
+                .and(predicate::str::contains("none (--no-config)")),
+        );
+    polymarket()
+        .env("POLYMARKET_CONFIG_DIR", &dir)
+        .env("POLYMARKET_NO_CONFIG", "1")
+        .args([
+            "wallet",
+            "import",
+            "0x0123456789012345678901234567890123456789012345678901234567890123",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--no-config"));
+    assert!(!dir.exists());
+}
+
+#[test]
+fn create_order_refuses_a_used_client_id() {
+    let dir = std::env::temp_dir().join(format!("polymarket-cli-client-id-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("client_orders")).unwrap();
+    std::fs::write(
+        dir.join("client_orders/run-42.json"),
+        r#"{"client_id": "run-42", "token_id": "123", "side": "BUY", "status": "LIVE",
+            "order_id": "0x1111111111111111111111111111111111111111111111111111111111111111",
+            "submitted_at": "2026-01-02T03:04:05Z"}"#,
+    )
+    .unwrap();
+    let order = |client_id: &str| {
+        let mut cmd = polymarket();
+        cmd.env("POLYMARKET_CONFIG_DIR", &dir).args([
+            "--yes",
+            "clob",
+            "create-order",
+            "--token",
+            "123",
+            "--side",
+            "buy",
+            "--price",
+            "0.5",
+            "--size",
+            "10",
+            "--client-id",
+            client_id,
+        ]);
+        cmd
+    };
+    order("run-42")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "Client ID run-42 was already used for order 0x1111",
+        ));
+    order("not a uuid")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Invalid client ID"));
+    polymarket()
+        .env("POLYMARKET_CONFIG_DIR", &dir)
+        .args(["clob", "orders", "--client-id", "run-43"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "No order was placed with client ID run-43",
+        ));
+    polymarket()
+        .env("POLYMARKET_CONFIG_DIR", &dir)
+        .args(["order", "list", "--client-id", "run-43"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "No order was placed with client ID run-43",
+        ));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
 #[test]
 fn tags_help_lists_subcommands() {
     polymarket()
@@ -280,6 +3004,24 @@ fn events_get_requires_id() {
     polymarket().args(["events", "get"]).assert().failure();
 }
 
+#[test]
+fn events_show_is_alias_for_get() {
+    polymarket()
+        .args(["events", "show", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("markets and outcomes"));
+}
+
+#[test]
+fn events_odds_interval_requires_watch() {
+    polymarket()
+        .args(["events", "odds", "some-event", "--interval", "2"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--watch"));
+}
+
 #[test]
 fn tags_get_requires_id() {
     polymarket().args(["tags", "get"]).assert().failure();
@@ -398,6 +3140,38 @@ fn ctf_redeem_help_shows_index_sets_flag() {
         );
 }
 
+#[test]
+fn ctf_split_takes_positional_condition() {
+    polymarket()
+        .args([
+            "ctf",
+            "split",
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "--amount",
+            "1.0000001",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("precision"));
+}
+
+#[test]
+fn ctf_merge_rejects_positional_and_flag_condition() {
+    polymarket()
+        .args([
+            "ctf",
+            "merge",
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "--condition",
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "--amount",
+            "1",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
 #[test]
 fn ctf_split_requires_condition_and_amount() {
     polymarket().args(["ctf", "split"]).assert().failure();